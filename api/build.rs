@@ -1,8 +1,13 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("cargo:rerun-if-changed=proto/blog.proto");
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
     tonic_prost_build::configure()
+        // Описание схемы нужно серверу, чтобы разбирать сообщения без знания их типов
+        .file_descriptor_set_path(out_dir.join("blog_descriptor.bin"))
         .build_server(true)
         .build_client(true)
+        // Клиент не привязан к tonic::transport, чтобы собираться и под wasm32 (gRPC-Web)
+        .build_transport(false)
         .compile_well_known_types(true)
         .extern_path(".google.protobuf", "::prost_types")
         .compile_protos(&["proto/blog.proto"], &["proto"])?;
@@ -0,0 +1,26 @@
+//! Генерирует TypeScript-определения и JSON Schema для типов REST API.
+//!
+//! ```bash
+//! cargo run -p api --example generate_schemas --features typescript,json-schema [OUT_DIR]
+//! ```
+//!
+//! По умолчанию файлы записываются в `api/generated/`.
+
+use std::path::PathBuf;
+
+fn main() -> std::io::Result<()> {
+    let out_dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("generated"));
+
+    let typescript_dir = out_dir.join("typescript");
+    api::codegen::export_typescript(&typescript_dir)?;
+    println!("TypeScript: {}", typescript_dir.display());
+
+    let schema_dir = out_dir.join("json-schema");
+    api::codegen::export_json_schemas(&schema_dir)?;
+    println!("JSON Schema: {}", schema_dir.display());
+
+    Ok(())
+}
@@ -0,0 +1,176 @@
+//! Генерация TypeScript-определений и JSON Schema для типов REST API.
+//!
+//! Источник истины - типы из [`crate::rest`]: фронтенды на TypeScript и
+//! API-шлюзы получают описания, сгенерированные из того же кода, что
+//! использует сервер.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::rest::*;
+
+/// Записывает TypeScript-определения всех типов REST API в `out_dir`.
+///
+/// Для каждого типа создаётся файл `<Type>.ts`; зависимости типов
+/// экспортируются вместе с ними.
+#[cfg(feature = "typescript")]
+pub fn export_typescript(out_dir: &Path) -> io::Result<()> {
+    use ts_rs::TS;
+
+    fn export<T: TS + 'static>(out_dir: &Path) -> io::Result<()> {
+        T::export_all_to(out_dir).map_err(io::Error::other)
+    }
+
+    fs::create_dir_all(out_dir)?;
+
+    export::<RegisterRequest>(out_dir)?;
+    export::<UserResponse>(out_dir)?;
+    export::<UserProfileResponse>(out_dir)?;
+    export::<UpdateProfileRequest>(out_dir)?;
+    export::<LoginRequest>(out_dir)?;
+    export::<UsernameAvailabilityResponse>(out_dir)?;
+    export::<ServerInfoResponse>(out_dir)?;
+    export::<PostLimitsResponse>(out_dir)?;
+    export::<RefreshTokenRequest>(out_dir)?;
+    export::<LogoutRequest>(out_dir)?;
+    export::<ForgotPasswordRequest>(out_dir)?;
+    export::<ResetPasswordRequest>(out_dir)?;
+    export::<TokenResponse>(out_dir)?;
+    export::<TwoFactorChallengeResponse>(out_dir)?;
+    export::<TotpLoginRequest>(out_dir)?;
+    export::<TotpEnrollmentResponse>(out_dir)?;
+    export::<TotpVerifyRequest>(out_dir)?;
+    export::<SessionResponse>(out_dir)?;
+    export::<SessionListResponse>(out_dir)?;
+    export::<PostStatus>(out_dir)?;
+    export::<PostSortField>(out_dir)?;
+    export::<SortOrder>(out_dir)?;
+    export::<CreatePostRequest>(out_dir)?;
+    export::<UpdatePostRequest>(out_dir)?;
+    export::<CreatePostsRequest>(out_dir)?;
+    export::<CreatePostsResponse>(out_dir)?;
+    export::<DeletePostsRequest>(out_dir)?;
+    export::<PaginationQuery>(out_dir)?;
+    export::<ListPostsQuery>(out_dir)?;
+    export::<PostFieldsQuery>(out_dir)?;
+    export::<TrendingPostsQuery>(out_dir)?;
+    export::<PostResponse>(out_dir)?;
+    export::<PostEventResponse>(out_dir)?;
+    export::<UploadResponse>(out_dir)?;
+    export::<CategoryResponse>(out_dir)?;
+    export::<CategoryListResponse>(out_dir)?;
+    export::<CreateCategoryRequest>(out_dir)?;
+    export::<BlogResponse>(out_dir)?;
+    export::<BlogListResponse>(out_dir)?;
+    export::<CreateBlogRequest>(out_dir)?;
+    export::<UpdateBlogRequest>(out_dir)?;
+    export::<AuditAction>(out_dir)?;
+    export::<AuditLogQuery>(out_dir)?;
+    export::<AuditEntryResponse>(out_dir)?;
+    export::<UserRole>(out_dir)?;
+    export::<AdminUserResponse>(out_dir)?;
+    export::<SystemStatsResponse>(out_dir)?;
+    export::<BodyLoggingSettings>(out_dir)?;
+    export::<FollowResponse>(out_dir)?;
+    export::<DigestFrequency>(out_dir)?;
+    export::<DigestSettingsResponse>(out_dir)?;
+    export::<UpdateDigestSettingsRequest>(out_dir)?;
+    export::<ReportReason>(out_dir)?;
+    export::<ReportStatus>(out_dir)?;
+    export::<ReportAction>(out_dir)?;
+    export::<ReportPostRequest>(out_dir)?;
+    export::<ResolveReportRequest>(out_dir)?;
+    export::<ListReportsQuery>(out_dir)?;
+    export::<ReportResponse>(out_dir)?;
+    export::<ErrorCode>(out_dir)?;
+    export::<ErrorResponse>(out_dir)?;
+    // Обобщённый тип экспортируется как `Paginated<T>`
+    export::<Paginated<PostResponse>>(out_dir)?;
+
+    Ok(())
+}
+
+/// Записывает JSON Schema всех типов REST API в `out_dir`.
+///
+/// Для каждого типа создаётся файл `<Type>.schema.json`.
+#[cfg(feature = "json-schema")]
+pub fn export_json_schemas(out_dir: &Path) -> io::Result<()> {
+    use schemars::{JsonSchema, schema_for};
+
+    fn export<T: JsonSchema>(out_dir: &Path, name: &str) -> io::Result<()> {
+        let schema = schema_for!(T);
+        let json = serde_json::to_string_pretty(&schema).map_err(io::Error::other)?;
+        fs::write(out_dir.join(format!("{name}.schema.json")), json + "\n")
+    }
+
+    fs::create_dir_all(out_dir)?;
+
+    export::<RegisterRequest>(out_dir, "RegisterRequest")?;
+    export::<UserResponse>(out_dir, "UserResponse")?;
+    export::<UserProfileResponse>(out_dir, "UserProfileResponse")?;
+    export::<UpdateProfileRequest>(out_dir, "UpdateProfileRequest")?;
+    export::<LoginRequest>(out_dir, "LoginRequest")?;
+    export::<UsernameAvailabilityResponse>(out_dir, "UsernameAvailabilityResponse")?;
+    export::<ServerInfoResponse>(out_dir, "ServerInfoResponse")?;
+    export::<PostLimitsResponse>(out_dir, "PostLimitsResponse")?;
+    export::<RefreshTokenRequest>(out_dir, "RefreshTokenRequest")?;
+    export::<LogoutRequest>(out_dir, "LogoutRequest")?;
+    export::<ForgotPasswordRequest>(out_dir, "ForgotPasswordRequest")?;
+    export::<ResetPasswordRequest>(out_dir, "ResetPasswordRequest")?;
+    export::<TokenResponse>(out_dir, "TokenResponse")?;
+    export::<TwoFactorChallengeResponse>(out_dir, "TwoFactorChallengeResponse")?;
+    export::<TotpLoginRequest>(out_dir, "TotpLoginRequest")?;
+    export::<TotpEnrollmentResponse>(out_dir, "TotpEnrollmentResponse")?;
+    export::<TotpVerifyRequest>(out_dir, "TotpVerifyRequest")?;
+    export::<SessionResponse>(out_dir, "SessionResponse")?;
+    export::<SessionListResponse>(out_dir, "SessionListResponse")?;
+    export::<PostStatus>(out_dir, "PostStatus")?;
+    export::<PostSortField>(out_dir, "PostSortField")?;
+    export::<SortOrder>(out_dir, "SortOrder")?;
+    export::<CreatePostRequest>(out_dir, "CreatePostRequest")?;
+    export::<UpdatePostRequest>(out_dir, "UpdatePostRequest")?;
+    export::<CreatePostsRequest>(out_dir, "CreatePostsRequest")?;
+    export::<CreatePostsResponse>(out_dir, "CreatePostsResponse")?;
+    export::<DeletePostsRequest>(out_dir, "DeletePostsRequest")?;
+    export::<PaginationQuery>(out_dir, "PaginationQuery")?;
+    export::<ListPostsQuery>(out_dir, "ListPostsQuery")?;
+    export::<PostFieldsQuery>(out_dir, "PostFieldsQuery")?;
+    export::<TrendingPostsQuery>(out_dir, "TrendingPostsQuery")?;
+    export::<PostResponse>(out_dir, "PostResponse")?;
+    export::<PostEventResponse>(out_dir, "PostEventResponse")?;
+    export::<UploadResponse>(out_dir, "UploadResponse")?;
+    export::<CategoryResponse>(out_dir, "CategoryResponse")?;
+    export::<CategoryListResponse>(out_dir, "CategoryListResponse")?;
+    export::<CreateCategoryRequest>(out_dir, "CreateCategoryRequest")?;
+    export::<BlogResponse>(out_dir, "BlogResponse")?;
+    export::<BlogListResponse>(out_dir, "BlogListResponse")?;
+    export::<CreateBlogRequest>(out_dir, "CreateBlogRequest")?;
+    export::<UpdateBlogRequest>(out_dir, "UpdateBlogRequest")?;
+    export::<AuditAction>(out_dir, "AuditAction")?;
+    export::<AuditLogQuery>(out_dir, "AuditLogQuery")?;
+    export::<AuditEntryResponse>(out_dir, "AuditEntryResponse")?;
+    export::<UserRole>(out_dir, "UserRole")?;
+    export::<AdminUserResponse>(out_dir, "AdminUserResponse")?;
+    export::<SystemStatsResponse>(out_dir, "SystemStatsResponse")?;
+    export::<BodyLoggingSettings>(out_dir, "BodyLoggingSettings")?;
+    export::<FollowResponse>(out_dir, "FollowResponse")?;
+    export::<DigestFrequency>(out_dir, "DigestFrequency")?;
+    export::<DigestSettingsResponse>(out_dir, "DigestSettingsResponse")?;
+    export::<UpdateDigestSettingsRequest>(out_dir, "UpdateDigestSettingsRequest")?;
+    export::<ReportReason>(out_dir, "ReportReason")?;
+    export::<ReportStatus>(out_dir, "ReportStatus")?;
+    export::<ReportAction>(out_dir, "ReportAction")?;
+    export::<ReportPostRequest>(out_dir, "ReportPostRequest")?;
+    export::<ResolveReportRequest>(out_dir, "ResolveReportRequest")?;
+    export::<ListReportsQuery>(out_dir, "ListReportsQuery")?;
+    export::<ReportResponse>(out_dir, "ReportResponse")?;
+    export::<ErrorCode>(out_dir, "ErrorCode")?;
+    export::<ErrorResponse>(out_dir, "ErrorResponse")?;
+    export::<Paginated<PostResponse>>(out_dir, "PostPage")?;
+    export::<Paginated<AuditEntryResponse>>(out_dir, "AuditEntryPage")?;
+    export::<Paginated<AdminUserResponse>>(out_dir, "AdminUserPage")?;
+    export::<Paginated<ReportResponse>>(out_dir, "ReportPage")?;
+
+    Ok(())
+}
@@ -0,0 +1,647 @@
+//! Преобразования между типами REST API и сообщениями gRPC.
+//!
+//! Транспорты называют одни и те же поля по-разному (`uuid`/`id`, `content`/`data`,
+//! временные метки ISO 8601/`Timestamp`). Соответствие описано здесь один раз:
+//! сервер и клиенты переводят одно представление в другое через `From`, а не
+//! перекладывают поля вручную.
+
+use prost_types::Timestamp;
+
+use crate::api as proto;
+use crate::rest;
+
+/// Разбирает временную метку RFC 3339; `None`, если строка не разобрана
+fn timestamp_from_rfc3339(value: &str) -> Option<Timestamp> {
+    value.parse().ok()
+}
+
+/// Записывает временную метку в RFC 3339; отсутствующая метка - пустая строка
+fn timestamp_to_rfc3339(timestamp: Option<Timestamp>) -> String {
+    timestamp
+        .map(|timestamp| timestamp.to_string())
+        .unwrap_or_default()
+}
+
+impl From<rest::PostStatus> for proto::PostStatus {
+    fn from(status: rest::PostStatus) -> Self {
+        match status {
+            rest::PostStatus::Draft => proto::PostStatus::Draft,
+            rest::PostStatus::Published => proto::PostStatus::Published,
+        }
+    }
+}
+
+impl From<proto::PostStatus> for rest::PostStatus {
+    fn from(status: proto::PostStatus) -> Self {
+        match status {
+            proto::PostStatus::Draft => rest::PostStatus::Draft,
+            proto::PostStatus::Published => rest::PostStatus::Published,
+        }
+    }
+}
+
+impl From<rest::PostSortField> for proto::PostSortField {
+    fn from(field: rest::PostSortField) -> Self {
+        match field {
+            rest::PostSortField::CreatedAt => proto::PostSortField::CreatedAt,
+            rest::PostSortField::UpdatedAt => proto::PostSortField::UpdatedAt,
+            rest::PostSortField::Title => proto::PostSortField::Title,
+        }
+    }
+}
+
+impl From<proto::PostSortField> for rest::PostSortField {
+    fn from(field: proto::PostSortField) -> Self {
+        match field {
+            proto::PostSortField::CreatedAt => rest::PostSortField::CreatedAt,
+            proto::PostSortField::UpdatedAt => rest::PostSortField::UpdatedAt,
+            proto::PostSortField::Title => rest::PostSortField::Title,
+        }
+    }
+}
+
+impl From<rest::SortOrder> for proto::SortOrder {
+    fn from(order: rest::SortOrder) -> Self {
+        match order {
+            rest::SortOrder::Asc => proto::SortOrder::Asc,
+            rest::SortOrder::Desc => proto::SortOrder::Desc,
+        }
+    }
+}
+
+impl From<proto::SortOrder> for rest::SortOrder {
+    fn from(order: proto::SortOrder) -> Self {
+        match order {
+            proto::SortOrder::Asc => rest::SortOrder::Asc,
+            proto::SortOrder::Desc => rest::SortOrder::Desc,
+        }
+    }
+}
+
+impl From<rest::CreatePostRequest> for proto::CreatePostRequest {
+    fn from(request: rest::CreatePostRequest) -> Self {
+        Self {
+            title: request.title,
+            data: request.content,
+            tags: request.tags,
+            status: proto::PostStatus::from(request.status) as i32,
+            attachment_ids: request.attachment_ids,
+            category_id: request.category_id,
+            summary: request.summary,
+            // В REST блог поста задаётся путём `/api/v1/blogs/{slug}/posts`
+            blog_slug: None,
+        }
+    }
+}
+
+impl From<proto::CreatePostRequest> for rest::CreatePostRequest {
+    fn from(request: proto::CreatePostRequest) -> Self {
+        Self {
+            status: request.status().into(),
+            title: request.title,
+            content: request.data,
+            tags: request.tags,
+            attachment_ids: request.attachment_ids,
+            category_id: request.category_id,
+            summary: request.summary,
+        }
+    }
+}
+
+impl From<rest::PostResponse> for proto::Post {
+    fn from(post: rest::PostResponse) -> Self {
+        Self {
+            created_ts: timestamp_from_rfc3339(&post.created_at),
+            last_updated_ts: timestamp_from_rfc3339(&post.updated_at),
+            status: proto::PostStatus::from(post.status) as i32,
+            id: post.uuid,
+            title: post.title,
+            data: post.content.unwrap_or_default(),
+            content_html: post.content_html,
+            summary: post.summary,
+            author_id: post.author_id,
+            author_username: post.author_username,
+            author_avatar_url: post.author_avatar_url,
+            tags: post.tags,
+            category_id: post.category_id,
+            blog_id: post.blog_id,
+            attachment_urls: post.attachments,
+            like_count: post.like_count,
+            liked_by_me: post.liked_by_me,
+        }
+    }
+}
+
+impl From<proto::Post> for rest::PostResponse {
+    fn from(post: proto::Post) -> Self {
+        Self {
+            status: post.status().into(),
+            created_at: timestamp_to_rfc3339(post.created_ts),
+            updated_at: timestamp_to_rfc3339(post.last_updated_ts),
+            uuid: post.id,
+            title: post.title,
+            // Содержимое поста не бывает пустым: пустое поле означает, что оно не запрошено
+            content: Some(post.data).filter(|content| !content.is_empty()),
+            content_html: post.content_html,
+            summary: post.summary,
+            author_id: post.author_id,
+            author_username: post.author_username,
+            author_avatar_url: post.author_avatar_url,
+            tags: post.tags,
+            category_id: post.category_id,
+            blog_id: post.blog_id,
+            attachments: post.attachment_urls,
+            like_count: post.like_count,
+            liked_by_me: post.liked_by_me,
+        }
+    }
+}
+
+impl From<rest::UploadResponse> for proto::Attachment {
+    fn from(upload: rest::UploadResponse) -> Self {
+        Self {
+            id: upload.id,
+            url: upload.url,
+            file_name: upload.file_name,
+            content_type: upload.content_type,
+            size: upload.size,
+        }
+    }
+}
+
+impl From<proto::Attachment> for rest::UploadResponse {
+    fn from(attachment: proto::Attachment) -> Self {
+        Self {
+            id: attachment.id,
+            url: attachment.url,
+            file_name: attachment.file_name,
+            content_type: attachment.content_type,
+            size: attachment.size,
+        }
+    }
+}
+
+impl From<rest::UserProfileResponse> for proto::UserProfile {
+    fn from(profile: rest::UserProfileResponse) -> Self {
+        Self {
+            created_ts: timestamp_from_rfc3339(&profile.created_at),
+            id: profile.id,
+            username: profile.username,
+            display_name: profile.display_name,
+            bio: profile.bio,
+            avatar_url: profile.avatar_url,
+        }
+    }
+}
+
+impl From<proto::UserProfile> for rest::UserProfileResponse {
+    fn from(profile: proto::UserProfile) -> Self {
+        Self {
+            created_at: timestamp_to_rfc3339(profile.created_ts),
+            id: profile.id,
+            username: profile.username,
+            display_name: profile.display_name,
+            bio: profile.bio,
+            avatar_url: profile.avatar_url,
+        }
+    }
+}
+
+impl From<rest::SessionResponse> for proto::Session {
+    fn from(session: rest::SessionResponse) -> Self {
+        Self {
+            created_ts: timestamp_from_rfc3339(&session.created_at),
+            last_used_ts: timestamp_from_rfc3339(&session.last_used_at),
+            id: session.id,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            current: session.current,
+        }
+    }
+}
+
+impl From<proto::Session> for rest::SessionResponse {
+    fn from(session: proto::Session) -> Self {
+        Self {
+            created_at: timestamp_to_rfc3339(session.created_ts),
+            last_used_at: timestamp_to_rfc3339(session.last_used_ts),
+            id: session.id,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            current: session.current,
+        }
+    }
+}
+
+impl From<rest::UserRole> for proto::UserRole {
+    fn from(role: rest::UserRole) -> Self {
+        match role {
+            rest::UserRole::User => proto::UserRole::User,
+            rest::UserRole::Moderator => proto::UserRole::Moderator,
+            rest::UserRole::Admin => proto::UserRole::Admin,
+        }
+    }
+}
+
+impl From<proto::UserRole> for rest::UserRole {
+    fn from(role: proto::UserRole) -> Self {
+        match role {
+            proto::UserRole::User => rest::UserRole::User,
+            proto::UserRole::Moderator => rest::UserRole::Moderator,
+            proto::UserRole::Admin => rest::UserRole::Admin,
+        }
+    }
+}
+
+impl From<rest::AdminUserResponse> for proto::AdminUser {
+    fn from(user: rest::AdminUserResponse) -> Self {
+        Self {
+            created_ts: timestamp_from_rfc3339(&user.created_at),
+            disabled_ts: user.disabled_at.as_deref().and_then(timestamp_from_rfc3339),
+            role: proto::UserRole::from(user.role) as i32,
+            id: user.id,
+            username: user.username,
+            email: user.email,
+        }
+    }
+}
+
+impl From<proto::AdminUser> for rest::AdminUserResponse {
+    fn from(user: proto::AdminUser) -> Self {
+        Self {
+            role: user.role().into(),
+            created_at: timestamp_to_rfc3339(user.created_ts),
+            disabled_at: user.disabled_ts.map(|timestamp| timestamp.to_string()),
+            id: user.id,
+            username: user.username,
+            email: user.email,
+        }
+    }
+}
+
+/// Статус ответа не заполняется: его задаёт сервер
+impl From<rest::SystemStatsResponse> for proto::SystemStatsResponse {
+    fn from(stats: rest::SystemStatsResponse) -> Self {
+        Self {
+            status: None,
+            users: stats.users,
+            posts: stats.posts,
+            recent_signups: stats.recent_signups,
+            recent_signups_days: stats.recent_signups_days,
+        }
+    }
+}
+
+impl From<proto::SystemStatsResponse> for rest::SystemStatsResponse {
+    fn from(stats: proto::SystemStatsResponse) -> Self {
+        Self {
+            users: stats.users,
+            posts: stats.posts,
+            recent_signups: stats.recent_signups,
+            recent_signups_days: stats.recent_signups_days,
+        }
+    }
+}
+
+impl From<rest::DigestFrequency> for proto::DigestFrequency {
+    fn from(frequency: rest::DigestFrequency) -> Self {
+        match frequency {
+            rest::DigestFrequency::Daily => proto::DigestFrequency::Daily,
+            rest::DigestFrequency::Weekly => proto::DigestFrequency::Weekly,
+        }
+    }
+}
+
+impl From<proto::DigestFrequency> for rest::DigestFrequency {
+    fn from(frequency: proto::DigestFrequency) -> Self {
+        match frequency {
+            proto::DigestFrequency::Daily => rest::DigestFrequency::Daily,
+            proto::DigestFrequency::Weekly => rest::DigestFrequency::Weekly,
+        }
+    }
+}
+
+/// Статус ответа не заполняется: его задаёт сервер
+impl From<rest::DigestSettingsResponse> for proto::DigestSettingsResponse {
+    fn from(settings: rest::DigestSettingsResponse) -> Self {
+        Self {
+            status: None,
+            frequency: settings
+                .frequency
+                .map(|frequency| proto::DigestFrequency::from(frequency) as i32),
+            last_sent_ts: settings
+                .last_sent_at
+                .as_deref()
+                .and_then(timestamp_from_rfc3339),
+        }
+    }
+}
+
+impl From<proto::DigestSettingsResponse> for rest::DigestSettingsResponse {
+    fn from(settings: proto::DigestSettingsResponse) -> Self {
+        Self {
+            // Неизвестное значение перечисления, как и отсутствие поля, - нет подписки
+            frequency: settings
+                .frequency
+                .and_then(|frequency| proto::DigestFrequency::try_from(frequency).ok())
+                .map(Into::into),
+            last_sent_at: settings.last_sent_ts.map(|timestamp| timestamp.to_string()),
+        }
+    }
+}
+
+impl From<rest::ReportReason> for proto::ReportReason {
+    fn from(reason: rest::ReportReason) -> Self {
+        match reason {
+            rest::ReportReason::Spam => proto::ReportReason::Spam,
+            rest::ReportReason::Harassment => proto::ReportReason::Harassment,
+            rest::ReportReason::HateSpeech => proto::ReportReason::HateSpeech,
+            rest::ReportReason::Misinformation => proto::ReportReason::Misinformation,
+            rest::ReportReason::Inappropriate => proto::ReportReason::Inappropriate,
+            rest::ReportReason::Other => proto::ReportReason::Other,
+        }
+    }
+}
+
+impl From<proto::ReportReason> for rest::ReportReason {
+    fn from(reason: proto::ReportReason) -> Self {
+        match reason {
+            proto::ReportReason::Spam => rest::ReportReason::Spam,
+            proto::ReportReason::Harassment => rest::ReportReason::Harassment,
+            proto::ReportReason::HateSpeech => rest::ReportReason::HateSpeech,
+            proto::ReportReason::Misinformation => rest::ReportReason::Misinformation,
+            proto::ReportReason::Inappropriate => rest::ReportReason::Inappropriate,
+            proto::ReportReason::Other => rest::ReportReason::Other,
+        }
+    }
+}
+
+impl From<rest::ReportStatus> for proto::ReportStatus {
+    fn from(status: rest::ReportStatus) -> Self {
+        match status {
+            rest::ReportStatus::Open => proto::ReportStatus::Open,
+            rest::ReportStatus::Hidden => proto::ReportStatus::Hidden,
+            rest::ReportStatus::Dismissed => proto::ReportStatus::Dismissed,
+        }
+    }
+}
+
+impl From<proto::ReportStatus> for rest::ReportStatus {
+    fn from(status: proto::ReportStatus) -> Self {
+        match status {
+            proto::ReportStatus::Open => rest::ReportStatus::Open,
+            proto::ReportStatus::Hidden => rest::ReportStatus::Hidden,
+            proto::ReportStatus::Dismissed => rest::ReportStatus::Dismissed,
+        }
+    }
+}
+
+impl From<rest::ReportAction> for proto::ReportAction {
+    fn from(action: rest::ReportAction) -> Self {
+        match action {
+            rest::ReportAction::HidePost => proto::ReportAction::HidePost,
+            rest::ReportAction::Dismiss => proto::ReportAction::Dismiss,
+        }
+    }
+}
+
+impl From<proto::ReportAction> for rest::ReportAction {
+    fn from(action: proto::ReportAction) -> Self {
+        match action {
+            proto::ReportAction::HidePost => rest::ReportAction::HidePost,
+            proto::ReportAction::Dismiss => rest::ReportAction::Dismiss,
+        }
+    }
+}
+
+impl From<rest::ReportResponse> for proto::Report {
+    fn from(report: rest::ReportResponse) -> Self {
+        Self {
+            created_ts: timestamp_from_rfc3339(&report.created_at),
+            resolved_ts: report
+                .resolved_at
+                .as_deref()
+                .and_then(timestamp_from_rfc3339),
+            reason: proto::ReportReason::from(report.reason) as i32,
+            status: proto::ReportStatus::from(report.status) as i32,
+            id: report.id,
+            post_id: report.post_id,
+            reporter_id: report.reporter_id,
+            details: report.details,
+            resolved_by: report.resolved_by,
+        }
+    }
+}
+
+impl From<proto::Report> for rest::ReportResponse {
+    fn from(report: proto::Report) -> Self {
+        Self {
+            reason: report.reason().into(),
+            status: report.status().into(),
+            created_at: timestamp_to_rfc3339(report.created_ts),
+            resolved_at: report.resolved_ts.map(|timestamp| timestamp.to_string()),
+            id: report.id,
+            post_id: report.post_id,
+            reporter_id: report.reporter_id,
+            details: report.details,
+            resolved_by: report.resolved_by,
+        }
+    }
+}
+
+impl From<rest::CategoryResponse> for proto::Category {
+    fn from(category: rest::CategoryResponse) -> Self {
+        Self {
+            created_ts: timestamp_from_rfc3339(&category.created_at),
+            id: category.id,
+            name: category.name,
+            parent_id: category.parent_id,
+        }
+    }
+}
+
+impl From<proto::Category> for rest::CategoryResponse {
+    fn from(category: proto::Category) -> Self {
+        Self {
+            created_at: timestamp_to_rfc3339(category.created_ts),
+            id: category.id,
+            name: category.name,
+            parent_id: category.parent_id,
+        }
+    }
+}
+
+impl From<rest::BlogResponse> for proto::BlogInfo {
+    fn from(blog: rest::BlogResponse) -> Self {
+        Self {
+            created_ts: timestamp_from_rfc3339(&blog.created_at),
+            id: blog.id,
+            slug: blog.slug,
+            name: blog.name,
+            owner_id: blog.owner_id,
+        }
+    }
+}
+
+impl From<proto::BlogInfo> for rest::BlogResponse {
+    fn from(blog: proto::BlogInfo) -> Self {
+        Self {
+            created_at: timestamp_to_rfc3339(blog.created_ts),
+            id: blog.id,
+            slug: blog.slug,
+            name: blog.name,
+            owner_id: blog.owner_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn post_response() -> rest::PostResponse {
+        rest::PostResponse {
+            uuid: "4f6c1c1e-2f43-4a5e-9d4e-1a2b3c4d5e6f".to_string(),
+            title: "Title".to_string(),
+            content: Some("Content".to_string()),
+            content_html: Some("<p>Content</p>".to_string()),
+            summary: "Short summary".to_string(),
+            author_id: "0b7e7a1e-5b2a-4d8e-8f0e-6c5d4b3a2918".to_string(),
+            author_username: "alice".to_string(),
+            author_avatar_url: None,
+            tags: vec!["rust".to_string()],
+            category_id: Some("9d2c7a4b-6e1f-4c3a-8b5d-2f0e1a3c5b7d".to_string()),
+            blog_id: Some("1c9e4f2a-7b3d-4e5f-a6b7-c8d9e0f1a2b3".to_string()),
+            attachments: vec!["/uploads/a.png".to_string()],
+            status: rest::PostStatus::Draft,
+            like_count: 3,
+            liked_by_me: true,
+            created_at: "2024-05-01T10:00:00+00:00".to_string(),
+            updated_at: "2024-05-01T10:30:00.250+00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_post_round_trip_keeps_fields() {
+        let post = proto::Post::from(post_response());
+
+        assert_eq!(post.id, "4f6c1c1e-2f43-4a5e-9d4e-1a2b3c4d5e6f");
+        assert_eq!(post.data, "Content");
+        assert_eq!(post.status(), proto::PostStatus::Draft);
+        assert_eq!(post.created_ts.unwrap().seconds, 1_714_557_600);
+        assert_eq!(post.last_updated_ts.unwrap().nanos, 250_000_000);
+
+        let back = rest::PostResponse::from(post);
+        let original = post_response();
+        assert_eq!(back.uuid, original.uuid);
+        assert_eq!(back.content, original.content);
+        assert_eq!(back.summary, original.summary);
+        assert_eq!(back.attachments, original.attachments);
+        assert_eq!(back.category_id, original.category_id);
+        assert_eq!(back.blog_id, original.blog_id);
+        assert_eq!(back.status, original.status);
+        assert_eq!(back.created_at, "2024-05-01T10:00:00Z");
+        assert_eq!(back.updated_at, "2024-05-01T10:30:00.250Z");
+    }
+
+    #[test]
+    fn test_post_without_content_round_trip() {
+        let response = rest::PostResponse {
+            content: None,
+            content_html: None,
+            ..post_response()
+        };
+
+        let post = proto::Post::from(response);
+
+        assert!(post.data.is_empty());
+        assert_eq!(rest::PostResponse::from(post).content, None);
+    }
+
+    #[test]
+    fn test_invalid_timestamp_is_dropped() {
+        let response = rest::PostResponse {
+            created_at: "yesterday".to_string(),
+            ..post_response()
+        };
+
+        let post = proto::Post::from(response);
+
+        assert!(post.created_ts.is_none());
+        assert!(rest::PostResponse::from(post).created_at.is_empty());
+    }
+
+    #[test]
+    fn test_admin_user_round_trip_keeps_disabled_at() {
+        let user = rest::AdminUserResponse {
+            id: "0b7e7a1e-5b2a-4d8e-8f0e-6c5d4b3a2918".to_string(),
+            username: "alice".to_string(),
+            email: "alice@example.com".to_string(),
+            role: rest::UserRole::Moderator,
+            created_at: "2024-05-01T10:00:00Z".to_string(),
+            disabled_at: Some("2024-06-01T08:00:00Z".to_string()),
+        };
+
+        let proto_user = proto::AdminUser::from(user);
+        assert_eq!(proto_user.role(), proto::UserRole::Moderator);
+        assert_eq!(proto_user.disabled_ts.unwrap().seconds, 1_717_228_800);
+
+        let back = rest::AdminUserResponse::from(proto_user);
+        assert_eq!(back.role, rest::UserRole::Moderator);
+        assert_eq!(back.created_at, "2024-05-01T10:00:00Z");
+        assert_eq!(back.disabled_at.as_deref(), Some("2024-06-01T08:00:00Z"));
+    }
+
+    #[test]
+    fn test_report_round_trip_keeps_reason_and_resolution() {
+        let report = rest::ReportResponse {
+            id: "4f6c1c1e-2f43-4a5e-9d4e-1a2b3c4d5e6f".to_string(),
+            post_id: "0b7e7a1e-5b2a-4d8e-8f0e-6c5d4b3a2918".to_string(),
+            reporter_id: "9a8b7c6d-5e4f-4a3b-8c2d-1e0f9a8b7c6d".to_string(),
+            reason: rest::ReportReason::HateSpeech,
+            details: None,
+            status: rest::ReportStatus::Hidden,
+            resolved_by: Some("0b7e7a1e-5b2a-4d8e-8f0e-6c5d4b3a2918".to_string()),
+            resolved_at: Some("2024-06-01T08:00:00Z".to_string()),
+            created_at: "2024-05-01T10:00:00Z".to_string(),
+        };
+
+        let proto_report = proto::Report::from(report);
+        assert_eq!(proto_report.reason(), proto::ReportReason::HateSpeech);
+        assert_eq!(proto_report.status(), proto::ReportStatus::Hidden);
+        assert_eq!(proto_report.resolved_ts.unwrap().seconds, 1_717_228_800);
+
+        let back = rest::ReportResponse::from(proto_report);
+        assert_eq!(back.reason, rest::ReportReason::HateSpeech);
+        assert_eq!(back.status, rest::ReportStatus::Hidden);
+        assert!(back.details.is_none());
+        assert_eq!(back.resolved_at.as_deref(), Some("2024-06-01T08:00:00Z"));
+    }
+
+    #[test]
+    fn test_digest_settings_round_trip_keeps_default_frequency() {
+        // Daily - нулевое значение перечисления, но подписка не должна потеряться
+        let settings = rest::DigestSettingsResponse {
+            frequency: Some(rest::DigestFrequency::Daily),
+            last_sent_at: Some("2024-06-01T08:00:00Z".to_string()),
+        };
+
+        let proto_settings = proto::DigestSettingsResponse::from(settings);
+        assert_eq!(
+            proto_settings.frequency,
+            Some(proto::DigestFrequency::Daily as i32)
+        );
+
+        let back = rest::DigestSettingsResponse::from(proto_settings);
+        assert_eq!(back.frequency, Some(rest::DigestFrequency::Daily));
+        assert_eq!(back.last_sent_at.as_deref(), Some("2024-06-01T08:00:00Z"));
+
+        let unsubscribed = rest::DigestSettingsResponse::from(proto::DigestSettingsResponse {
+            status: None,
+            frequency: None,
+            last_sent_ts: None,
+        });
+        assert!(unsubscribed.frequency.is_none());
+        assert!(unsubscribed.last_sent_at.is_none());
+    }
+}
@@ -7,6 +7,9 @@
 //! * `rest` - Включает REST API типы (JSON serialization)
 //! * `grpc` - Включает gRPC типы (Protocol Buffers)
 //! * `default` - Включает оба: `rest` и `grpc`
+//! * `typescript` - Генерация TypeScript-определений для REST типов (ts-rs)
+//! * `json-schema` - Генерация JSON Schema для REST типов (schemars)
+//! * `openapi` - Схемы OpenAPI для REST типов (utoipa), используются сервером
 //!
 //! ## REST API
 //!
@@ -18,13 +21,34 @@
 //! При включении feature `grpc` типы генерируются автоматически из
 //! `proto/blog.proto` с помощью tonic-build. Доступны сервисы и типы
 //! для gRPC взаимодействия.
+//!
+//! ## Преобразования
+//!
+//! При включении обоих features модуль [`convert`] содержит `From` между типами
+//! REST и сообщениями gRPC, чтобы сервер и клиенты не перекладывали поля вручную.
+//!
+//! ## Версии
+//!
+//! Модуль [`version`] доступен без features: в нём версии API и названия
+//! возможностей сервера, о которых договариваются клиент и сервер.
+
+pub mod version;
 
 #[cfg(feature = "rest")]
 pub mod rest;
 
+#[cfg(any(feature = "typescript", feature = "json-schema"))]
+pub mod codegen;
+
+#[cfg(all(feature = "rest", feature = "grpc"))]
+pub mod convert;
+
 #[cfg(feature = "grpc")]
 pub mod api {
     tonic::include_proto!("blog");
+
+    /// Скомпилированное описание `proto/blog.proto` (`FileDescriptorSet`)
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("blog_descriptor");
 }
 
 #[cfg(feature = "grpc")]
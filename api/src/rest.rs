@@ -1,76 +1,1394 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use validator::ValidationErrorsKind;
+pub use validator::{Validate, ValidationError, ValidationErrors};
+
+/// Минимальная длина имени пользователя
+pub const USERNAME_MIN_LEN: u64 = 3;
+/// Максимальная длина имени пользователя
+pub const USERNAME_MAX_LEN: u64 = 32;
+/// Минимальная длина пароля
+pub const PASSWORD_MIN_LEN: u64 = 8;
+/// Максимальная длина пароля
+pub const PASSWORD_MAX_LEN: u64 = 128;
+/// Максимальная длина заголовка поста (ограничение колонки `posts.title`)
+pub const POST_TITLE_MAX_LEN: u64 = 500;
+/// Максимальная длина содержимого поста
+pub const POST_CONTENT_MAX_LEN: u64 = 100_000;
+/// Максимальный размер страницы при получении списка постов
+pub const MAX_PAGE_SIZE: u32 = 100;
+/// Максимальное количество тегов у поста
+pub const MAX_TAGS_PER_POST: u64 = 10;
+/// Максимальная длина тега (ограничение колонки `post_tags.tag`)
+pub const TAG_MAX_LEN: usize = 32;
+/// Максимальное количество вложений у поста
+pub const MAX_ATTACHMENTS_PER_POST: u64 = 10;
+/// Максимальное количество постов в одном пакетном запросе
+pub const MAX_POSTS_PER_BATCH: u64 = 100;
+/// Максимальная длина отображаемого имени (ограничение колонки `users.display_name`)
+pub const DISPLAY_NAME_MAX_LEN: u64 = 64;
+/// Максимальная длина описания профиля (ограничение колонки `users.bio`)
+pub const BIO_MAX_LEN: u64 = 1000;
+/// Максимальная длина ссылки на аватар (ограничение колонки `users.avatar_url`)
+pub const AVATAR_URL_MAX_LEN: u64 = 512;
+/// Количество цифр в коде двухфакторной аутентификации (TOTP)
+pub const TOTP_CODE_LEN: u64 = 6;
+/// Максимальная длина пояснения к жалобе (ограничение колонки `reports.details`)
+pub const REPORT_DETAILS_MAX_LEN: u64 = 1000;
+/// Максимальная длина имени категории (ограничение колонки `categories.name`)
+pub const CATEGORY_NAME_MAX_LEN: u64 = 64;
+/// Максимальная длина описания поста
+pub const POST_SUMMARY_MAX_LEN: u64 = 500;
+/// Минимальная длина адреса блога
+pub const BLOG_SLUG_MIN_LEN: u64 = 3;
+/// Максимальная длина адреса блога (ограничение колонки `blogs.slug`)
+pub const BLOG_SLUG_MAX_LEN: u64 = 64;
+/// Максимальная длина названия блога (ограничение колонки `blogs.name`)
+pub const BLOG_NAME_MAX_LEN: u64 = 128;
+
+/// Проверяет, что имя пользователя не содержит `@`: такие идентификаторы при входе считаются email.
+fn validate_username(username: &str) -> Result<(), ValidationError> {
+    if username.contains('@') {
+        Err(ValidationError::new("username_at_sign").with_message("must not contain '@'".into()))
+    } else {
+        Ok(())
+    }
+}
+
+/// Проверяет, что каждый тег непустой и не длиннее [`TAG_MAX_LEN`] символов.
+fn validate_tags(tags: &[String]) -> Result<(), ValidationError> {
+    let valid = tags.iter().all(|tag| {
+        let len = tag.trim().chars().count();
+        len > 0 && len <= TAG_MAX_LEN
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(ValidationError::new("tag_length")
+            .with_message("each tag must be between 1 and 32 characters".into()))
+    }
+}
+
+/// Статус публикации поста.
+///
+/// Черновики видны только автору. Если статус не указан при создании,
+/// пост сразу публикуется.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum PostStatus {
+    /// Черновик
+    Draft,
+    /// Опубликованный пост
+    #[default]
+    Published,
+}
+
+/// Поле сортировки списка постов.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum PostSortField {
+    /// Время создания
+    #[default]
+    CreatedAt,
+    /// Время последнего обновления
+    UpdatedAt,
+    /// Заголовок
+    Title,
+}
+
+/// Направление сортировки.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum SortOrder {
+    /// По возрастанию
+    Asc,
+    /// По убыванию
+    #[default]
+    Desc,
+}
 
 /// Запрос на регистрацию нового пользователя.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RegisterRequest {
     /// Уникальное имя пользователя
+    #[validate(length(
+        min = USERNAME_MIN_LEN,
+        max = USERNAME_MAX_LEN,
+        message = "must be between 3 and 32 characters"
+    ))]
+    #[validate(custom(function = "validate_username"))]
     pub username: String,
     /// Пароль (будет захэширован на сервере)
+    #[validate(length(
+        min = PASSWORD_MIN_LEN,
+        max = PASSWORD_MAX_LEN,
+        message = "must be between 8 and 128 characters"
+    ))]
     pub password: String,
     /// Уникальный email адрес
+    #[validate(email(message = "must be a valid email address"))]
     pub email: String,
 }
 
-/// Запрос на вход пользователя.
+/// Данные зарегистрированного пользователя.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserResponse {
+    /// UUID пользователя
+    pub id: String,
+    /// Имя пользователя
+    pub username: String,
+    /// Email адрес
+    pub email: String,
+    /// Временная метка регистрации (ISO 8601)
+    pub created_at: String,
+}
+
+/// Публичный профиль пользователя.
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UserProfileResponse {
+    /// UUID пользователя
+    pub id: String,
+    /// Имя пользователя
+    pub username: String,
+    /// Отображаемое имя
+    pub display_name: Option<String>,
+    /// Описание профиля
+    pub bio: Option<String>,
+    /// Ссылка на изображение аватара
+    pub avatar_url: Option<String>,
+    /// Временная метка регистрации (ISO 8601)
+    pub created_at: String,
+}
+
+/// Запрос на обновление профиля текущего пользователя.
+///
+/// Поля заменяются целиком: отсутствующее поле очищается.
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateProfileRequest {
+    /// Отображаемое имя
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    #[validate(length(max = DISPLAY_NAME_MAX_LEN, message = "must be at most 64 characters"))]
+    pub display_name: Option<String>,
+    /// Описание профиля
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    #[validate(length(max = BIO_MAX_LEN, message = "must be at most 1000 characters"))]
+    pub bio: Option<String>,
+    /// Ссылка на изображение аватара (http или https)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    #[validate(
+        length(max = AVATAR_URL_MAX_LEN, message = "must be at most 512 characters"),
+        url(message = "must be a valid URL")
+    )]
+    pub avatar_url: Option<String>,
+}
+
+/// Запрос на вход пользователя.
+///
+/// Нужно указать `username` или `email`; в `username` по-прежнему можно передать email.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct LoginRequest {
     /// Имя пользователя или email
-    pub username: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub username: Option<String>,
+    /// Email; если указан, используется вместо `username`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    #[validate(email(message = "must be a valid email address"))]
+    pub email: Option<String>,
     /// Пароль
+    #[validate(length(min = 1, message = "must not be empty"))]
     pub password: String,
 }
 
-/// Запрос на обновление access токена с помощью refresh токена.
+impl LoginRequest {
+    /// Идентификатор пользователя: `email`, если указан, иначе `username`
+    pub fn identifier(&self) -> Option<&str> {
+        self.email.as_deref().or(self.username.as_deref())
+    }
+}
+
+/// Сведения о сервере: версия, обслуживаемые версии API и возможности.
+///
+/// Названия версий и возможностей - из [`crate::version`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ServerInfoResponse {
+    /// Версия сервера
+    pub version: String,
+    /// Обслуживаемые версии API (`v1`, ...)
+    pub api_versions: Vec<String>,
+    /// Включённые необязательные возможности
+    pub features: Vec<String>,
+    /// Ограничения на создание постов; нет у серверов, которые их не сообщают
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub post_limits: Option<PostLimitsResponse>,
+}
+
+/// Ограничения на создание постов, которые сервер проверяет.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PostLimitsResponse {
+    /// Сколько постов пользователь может создать за сутки (UTC); `None` - без квоты
+    pub max_posts_per_day: Option<u32>,
+    /// Максимальный размер содержимого поста в байтах UTF-8
+    pub max_content_bytes: u64,
+    /// Максимальная длина заголовка в символах
+    pub max_title_chars: u32,
+}
+
+/// Ответ на проверку доступности имени пользователя.
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UsernameAvailabilityResponse {
+    /// Проверенное имя пользователя
+    pub username: String,
+    /// `true`, если имя ещё не занято
+    pub available: bool,
+}
+
+/// Запрос на обновление access токена с помощью refresh токена.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct RefreshTokenRequest {
     /// JWT refresh токен
+    #[validate(length(min = 1, message = "must not be empty"))]
     pub refresh_token: String,
 }
 
+/// Запрос на выход: отзывает переданный refresh токен.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct LogoutRequest {
+    /// JWT refresh токен, который больше нельзя будет использовать
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub refresh_token: String,
+}
+
+/// Запрос на сброс пароля: токен сброса отправляется владельцу email.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ForgotPasswordRequest {
+    /// Email, указанный при регистрации
+    #[validate(email(message = "must be a valid email address"))]
+    pub email: String,
+}
+
+/// Запрос на установку нового пароля по токену сброса.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ResetPasswordRequest {
+    /// Одноразовый токен сброса пароля
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub token: String,
+    /// Новый пароль
+    #[validate(length(
+        min = PASSWORD_MIN_LEN,
+        max = PASSWORD_MAX_LEN,
+        message = "must be between 8 and 128 characters"
+    ))]
+    pub new_password: String,
+}
+
 /// Ответ с JWT токенами.
 ///
 /// Возвращается при успешной аутентификации или обновлении токена.
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct TokenResponse {
     /// JWT access токен для аутентификации запросов
     pub access_token: String,
     /// JWT refresh токен для получения нового access токена
     pub refresh_token: String,
     /// Время жизни access токена в секундах
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
     pub expires_in: i64,
 }
 
-/// Запрос на создание нового поста.
+/// Ответ на вход в аккаунт с включённой двухфакторной аутентификацией (HTTP 202).
+///
+/// Токены выдаются после отправки кода TOTP вместе с `challenge_token`
+/// (см. [`TotpLoginRequest`]).
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TwoFactorChallengeResponse {
+    /// Всегда `true`: для входа нужен код двухфакторной аутентификации
+    pub requires_2fa: bool,
+    /// Одноразовый токен второго шага входа
+    pub challenge_token: String,
+    /// Время жизни `challenge_token` в секундах
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
+    pub expires_in: i64,
+}
+
+/// Второй шаг входа: код TOTP для токена из [`TwoFactorChallengeResponse`].
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TotpLoginRequest {
+    /// Токен, полученный при проверке пароля
+    #[validate(length(min = 1, message = "must not be empty"))]
+    pub challenge_token: String,
+    /// Код из приложения-аутентификатора
+    #[validate(length(equal = TOTP_CODE_LEN, message = "must be 6 digits"))]
+    pub code: String,
+}
+
+/// Секрет TOTP для добавления в приложение-аутентификатор.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TotpEnrollmentResponse {
+    /// Секрет в кодировке Base32 для ручного ввода
+    pub secret: String,
+    /// `otpauth://` URI, который обычно показывается QR-кодом
+    pub provisioning_uri: String,
+}
+
+/// Сеанс входа: устройство, которому выдан refresh токен.
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SessionResponse {
+    /// UUID сеанса
+    pub id: String,
+    /// `User-Agent` клиента при последнем использовании
+    pub user_agent: Option<String>,
+    /// IP-адрес клиента при последнем использовании
+    pub ip_address: Option<String>,
+    /// Временная метка входа (ISO 8601)
+    pub created_at: String,
+    /// Временная метка последнего входа или обновления токена (ISO 8601)
+    pub last_used_at: String,
+    /// Сеанс, которому принадлежит токен запроса
+    pub current: bool,
+}
+
+/// Действующие сеансы входа текущего пользователя.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SessionListResponse {
+    /// Сеансы, начиная с последнего использованного
+    pub sessions: Vec<SessionResponse>,
+}
+
+/// Подтверждение подключения двухфакторной аутентификации первым кодом.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct TotpVerifyRequest {
+    /// Код из приложения-аутентификатора
+    #[validate(length(equal = TOTP_CODE_LEN, message = "must be 6 digits"))]
+    pub code: String,
+}
+
+/// Запрос на создание нового поста.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct CreatePostRequest {
     /// Заголовок поста
+    #[validate(length(
+        min = 1,
+        max = POST_TITLE_MAX_LEN,
+        message = "must be between 1 and 500 characters"
+    ))]
     pub title: String,
     /// Содержимое поста
+    #[validate(length(
+        min = 1,
+        max = POST_CONTENT_MAX_LEN,
+        message = "must be between 1 and 100000 characters"
+    ))]
     pub content: String,
+    /// Теги поста (регистр не учитывается)
+    #[serde(default)]
+    #[validate(
+        length(max = MAX_TAGS_PER_POST, message = "must contain at most 10 tags"),
+        custom(function = "validate_tags")
+    )]
+    pub tags: Vec<String>,
+    /// UUID загруженных файлов, прикрепляемых к посту (см. `POST /api/v1/uploads`)
+    #[serde(default)]
+    #[validate(length(
+        max = MAX_ATTACHMENTS_PER_POST,
+        message = "must contain at most 10 attachments"
+    ))]
+    pub attachment_ids: Vec<String>,
+    /// UUID категории поста (см. `GET /api/v1/categories`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub category_id: Option<String>,
+    /// Краткое описание поста для списков; без него описание составляется
+    /// из начала содержимого
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    #[validate(length(max = POST_SUMMARY_MAX_LEN, message = "must be at most 500 characters"))]
+    pub summary: Option<String>,
+    /// Статус нового поста (по умолчанию `published`)
+    #[serde(default)]
+    #[cfg_attr(feature = "typescript", ts(optional, as = "Option<PostStatus>"))]
+    pub status: PostStatus,
 }
 
 /// Запрос на обновление существующего поста.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct UpdatePostRequest {
     /// Новый заголовок поста
+    #[validate(length(
+        min = 1,
+        max = POST_TITLE_MAX_LEN,
+        message = "must be between 1 and 500 characters"
+    ))]
     pub title: String,
     /// Новое содержимое поста
+    #[validate(length(
+        min = 1,
+        max = POST_CONTENT_MAX_LEN,
+        message = "must be between 1 and 100000 characters"
+    ))]
     pub content: String,
+    /// Теги поста (регистр не учитывается)
+    #[serde(default)]
+    #[validate(
+        length(max = MAX_TAGS_PER_POST, message = "must contain at most 10 tags"),
+        custom(function = "validate_tags")
+    )]
+    pub tags: Vec<String>,
+    /// Новый набор вложений поста; если поле не указано, вложения не меняются
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    #[validate(length(
+        max = MAX_ATTACHMENTS_PER_POST,
+        message = "must contain at most 10 attachments"
+    ))]
+    pub attachment_ids: Option<Vec<String>>,
+    /// UUID новой категории поста; пустая строка убирает категорию,
+    /// если поле не указано, категория не меняется
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub category_id: Option<String>,
+    /// Новое описание поста; пустая строка возвращает описание из начала содержимого,
+    /// если поле не указано, описание не меняется
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    #[validate(length(max = POST_SUMMARY_MAX_LEN, message = "must be at most 500 characters"))]
+    pub summary: Option<String>,
+}
+
+/// Пакетное создание постов: все посты создаются в одной транзакции.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreatePostsRequest {
+    /// Создаваемые посты
+    #[validate(
+        length(
+            min = 1,
+            max = MAX_POSTS_PER_BATCH,
+            message = "must contain between 1 and 100 posts"
+        ),
+        nested
+    )]
+    pub posts: Vec<CreatePostRequest>,
+}
+
+/// Результат пакетного создания постов.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreatePostsResponse {
+    /// Созданные посты в порядке запроса
+    pub posts: Vec<PostResponse>,
+}
+
+/// Пакетное удаление постов: удаляются все посты или ни одного.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DeletePostsRequest {
+    /// UUID удаляемых постов
+    #[validate(length(
+        min = 1,
+        max = MAX_POSTS_PER_BATCH,
+        message = "must contain between 1 and 100 posts"
+    ))]
+    pub post_ids: Vec<String>,
+}
+
+/// Параметры пагинации списка постов или пользователей (query string).
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct PaginationQuery {
+    /// Количество элементов на странице
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "must be between 1 and 100"))]
+    pub page_size: u32,
+    /// Номер страницы (начиная с 0)
+    pub page: u32,
+}
+
+/// Параметры запроса списка постов (query string).
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct ListPostsQuery {
+    /// Количество постов на странице
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "must be between 1 and 100"))]
+    pub page_size: u32,
+    /// Номер страницы (начиная с 0); не используется вместе с `cursor`
+    #[serde(default)]
+    pub page: u32,
+    /// Курсор из `next_cursor` предыдущей страницы: следующая страница начинается
+    /// сразу после него и не сдвигается при добавлении новых постов.
+    /// Поддерживается только для порядка по умолчанию
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub cursor: Option<String>,
+    /// Вернуть только посты с этим тегом
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub tag: Option<String>,
+    /// Вернуть только посты категории с этим UUID и её подкатегорий
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub category: Option<String>,
+    /// Поле сортировки (по умолчанию `created_at`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub sort_by: Option<PostSortField>,
+    /// Направление сортировки (по умолчанию `desc`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub order: Option<SortOrder>,
+    /// Поля поста через запятую, например `title,created_at`: посты в ответе содержат
+    /// только эти поля и `uuid`. Без параметра возвращаются все поля
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub fields: Option<String>,
+}
+
+/// Выбор полей поста в ответе (query string).
+///
+/// Используется вместе с другими параметрами запроса у маршрутов, возвращающих посты.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct PostFieldsQuery {
+    /// Поля поста через запятую, например `title,created_at`: пост в ответе содержит
+    /// только эти поля и `uuid`. Без параметра возвращаются все поля
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub fields: Option<String>,
+}
+
+/// Параметры запроса популярных постов (query string).
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct TrendingPostsQuery {
+    /// Сколько постов вернуть
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "must be between 1 and 100"))]
+    pub limit: u32,
 }
 
 /// Ответ с данными поста.
+///
+/// Если в запросе указан параметр `fields`, в ответе есть только перечисленные поля.
 #[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PostResponse {
     /// UUID поста
     pub uuid: String,
     /// Заголовок поста
     pub title: String,
-    /// Содержимое поста
-    pub content: String,
+    /// Содержимое поста (Markdown); нет, если оно не запрошено в `fields`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub content: Option<String>,
+    /// Содержимое, отрендеренное в HTML и очищенное от опасной разметки
+    /// (`null`, если рендеринг отключён на сервере или содержимое не запрошено)
+    pub content_html: Option<String>,
+    /// Краткое описание поста: заданное автором или начало содержимого
+    #[serde(default)]
+    pub summary: String,
     /// UUID автора поста
     pub author_id: String,
+    /// Имя автора поста
+    pub author_username: String,
+    /// Ссылка на аватар автора поста
+    pub author_avatar_url: Option<String>,
+    /// Теги поста
+    pub tags: Vec<String>,
+    /// UUID категории поста (`null`, если категория не выбрана)
+    #[serde(default)]
+    pub category_id: Option<String>,
+    /// UUID блога поста (`null` у постов общей ленты сервера)
+    #[serde(default)]
+    pub blog_id: Option<String>,
+    /// Ссылки на прикреплённые файлы
+    pub attachments: Vec<String>,
+    /// Статус публикации
+    pub status: PostStatus,
+    /// Количество лайков
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
+    pub like_count: u64,
+    /// Лайкнул ли пост пользователь, выполнивший запрос (`false` для анонимных запросов)
+    pub liked_by_me: bool,
     /// Временная метка создания (ISO 8601)
     pub created_at: String,
     /// Временная метка последнего обновления (ISO 8601)
     pub updated_at: String,
 }
+
+/// Ответ на загрузку файла.
+///
+/// `id` передаётся в `attachment_ids` при создании или обновлении поста.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UploadResponse {
+    /// UUID вложения
+    pub id: String,
+    /// Публичная ссылка на файл
+    pub url: String,
+    /// Имя файла
+    pub file_name: String,
+    /// MIME-тип файла
+    pub content_type: String,
+    /// Размер файла в байтах
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
+    pub size: u64,
+}
+
+/// Категория постов.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CategoryResponse {
+    /// UUID категории
+    pub id: String,
+    /// Имя категории
+    pub name: String,
+    /// UUID родительской категории (`null` у категорий верхнего уровня)
+    pub parent_id: Option<String>,
+    /// Временная метка создания (ISO 8601)
+    pub created_at: String,
+}
+
+/// Все категории постов.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CategoryListResponse {
+    /// Категории, упорядоченные по имени; дерево собирается по `parent_id`
+    pub categories: Vec<CategoryResponse>,
+}
+
+/// Запрос на создание категории.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateCategoryRequest {
+    /// Имя категории, уникальное среди категорий с общим родителем
+    #[validate(length(
+        min = 1,
+        max = CATEGORY_NAME_MAX_LEN,
+        message = "must be between 1 and 64 characters"
+    ))]
+    pub name: String,
+    /// UUID родительской категории; без него создаётся категория верхнего уровня
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub parent_id: Option<String>,
+}
+
+/// Блог на сервере.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BlogResponse {
+    /// UUID блога
+    pub id: String,
+    /// Адрес блога в путях API, например `/api/v1/blogs/{slug}/posts`
+    pub slug: String,
+    /// Название блога
+    pub name: String,
+    /// UUID владельца блога
+    pub owner_id: String,
+    /// Временная метка создания (ISO 8601)
+    pub created_at: String,
+}
+
+/// Все блоги сервера.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BlogListResponse {
+    /// Блоги, упорядоченные по адресу
+    pub blogs: Vec<BlogResponse>,
+}
+
+/// Запрос на создание блога; владельцем блога становится автор запроса.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct CreateBlogRequest {
+    /// Адрес блога: латинские буквы, цифры и дефисы, уникален на сервере
+    #[validate(length(
+        min = BLOG_SLUG_MIN_LEN,
+        max = BLOG_SLUG_MAX_LEN,
+        message = "must be between 3 and 64 characters"
+    ))]
+    pub slug: String,
+    /// Название блога
+    #[validate(length(
+        min = 1,
+        max = BLOG_NAME_MAX_LEN,
+        message = "must be between 1 and 128 characters"
+    ))]
+    pub name: String,
+}
+
+/// Запрос на изменение блога.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateBlogRequest {
+    /// Новое название блога
+    #[validate(length(
+        min = 1,
+        max = BLOG_NAME_MAX_LEN,
+        message = "must be between 1 and 128 characters"
+    ))]
+    pub name: String,
+}
+
+/// Действие пользователя в журнале аудита.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum AuditAction {
+    /// Регистрация
+    Register,
+    /// Вход по паролю
+    Login,
+    /// Создание поста
+    CreatePost,
+    /// Изменение поста
+    UpdatePost,
+    /// Перенос поста в корзину
+    DeletePost,
+    /// Восстановление поста из корзины
+    RestorePost,
+    /// Окончательное удаление поста
+    PurgePost,
+    /// Скрытие поста модератором по жалобе
+    HidePost,
+    /// Отключение пользователя администратором
+    DisableUser,
+    /// Включение пользователя администратором
+    EnableUser,
+}
+
+/// Параметры запроса журнала аудита (query string).
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct AuditLogQuery {
+    /// Количество записей на странице
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "must be between 1 and 100"))]
+    pub page_size: u32,
+    /// Номер страницы (начиная с 0)
+    pub page: u32,
+    /// Вернуть только действия пользователя с этим UUID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub user_id: Option<String>,
+    /// Начало интервала (RFC 3339, включительно)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub from: Option<String>,
+    /// Конец интервала (RFC 3339, не включая)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub to: Option<String>,
+}
+
+/// Запись журнала аудита.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AuditEntryResponse {
+    /// UUID записи
+    pub id: String,
+    /// UUID пользователя, выполнившего действие
+    pub user_id: String,
+    /// Выполненное действие
+    pub action: AuditAction,
+    /// UUID поста для действий с постами или пользователя для действий с пользователями
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub target_id: Option<String>,
+    /// Время действия (RFC 3339)
+    pub created_at: String,
+}
+
+/// Роль пользователя.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum UserRole {
+    /// Обычный пользователь
+    User,
+    /// Модератор
+    Moderator,
+    /// Администратор
+    Admin,
+}
+
+/// Пользователь в списке пользователей для администратора.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AdminUserResponse {
+    /// UUID пользователя
+    pub id: String,
+    /// Имя пользователя
+    pub username: String,
+    /// Email адрес
+    pub email: String,
+    /// Роль пользователя
+    pub role: UserRole,
+    /// Временная метка регистрации (RFC 3339)
+    pub created_at: String,
+    /// Когда пользователь был отключён (RFC 3339); нет у активных пользователей
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub disabled_at: Option<String>,
+}
+
+/// Подписан ли текущий пользователь на автора.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FollowResponse {
+    /// Есть ли подписка
+    pub following: bool,
+}
+
+/// Частота email-дайджеста новых постов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum DigestFrequency {
+    /// Раз в сутки
+    Daily,
+    /// Раз в неделю
+    Weekly,
+}
+
+/// Настройки email-дайджеста текущего пользователя.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct DigestSettingsResponse {
+    /// Частота писем; нет, если пользователь не подписан
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub frequency: Option<DigestFrequency>,
+    /// Время предыдущего дайджеста (RFC 3339); для новой подписки - время подписки
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub last_sent_at: Option<String>,
+}
+
+/// Запрос на изменение настроек email-дайджеста.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct UpdateDigestSettingsRequest {
+    /// Новая частота писем; `null` или отсутствие поля отписывает от дайджеста
+    #[serde(default)]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub frequency: Option<DigestFrequency>,
+}
+
+/// Сводные показатели системы.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct SystemStatsResponse {
+    /// Количество пользователей
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
+    pub users: u64,
+    /// Количество постов, включая черновики и не считая постов в корзине
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
+    pub posts: u64,
+    /// Количество регистраций за последние `recent_signups_days` дней
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
+    pub recent_signups: u64,
+    /// За сколько последних дней считаются новые регистрации
+    pub recent_signups_days: u32,
+}
+
+/// Отладочное логирование тел запросов и ответов REST и gRPC.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BodyLoggingSettings {
+    /// Пишутся ли тела в лог сервера (пароли и токены скрываются)
+    pub enabled: bool,
+}
+
+/// Причина жалобы на пост.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum ReportReason {
+    /// Спам или реклама
+    Spam,
+    /// Оскорбления и травля
+    Harassment,
+    /// Язык вражды
+    HateSpeech,
+    /// Заведомо ложная информация
+    Misinformation,
+    /// Неприемлемое содержимое
+    Inappropriate,
+    /// Другая причина (описывается в `details`)
+    Other,
+}
+
+/// Состояние жалобы.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum ReportStatus {
+    /// Ждёт решения модератора
+    Open,
+    /// Модератор скрыл пост
+    Hidden,
+    /// Модератор отклонил жалобу
+    Dismissed,
+}
+
+/// Решение модератора по жалобе.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum ReportAction {
+    /// Скрыть пост (перенести в корзину без права восстановления автором)
+    /// и закрыть все открытые жалобы на него
+    HidePost,
+    /// Отклонить жалобу
+    Dismiss,
+}
+
+/// Запрос на подачу жалобы на пост.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReportPostRequest {
+    /// Причина жалобы
+    pub reason: ReportReason,
+    /// Необязательное пояснение
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    #[validate(length(max = REPORT_DETAILS_MAX_LEN, message = "must be at most 1000 characters"))]
+    pub details: Option<String>,
+}
+
+/// Запрос на решение по жалобе.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ResolveReportRequest {
+    /// Решение модератора
+    pub action: ReportAction,
+}
+
+/// Параметры запроса списка жалоб (query string).
+#[derive(Debug, Serialize, Deserialize, Validate)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema, utoipa::IntoParams))]
+#[cfg_attr(feature = "openapi", into_params(parameter_in = Query))]
+pub struct ListReportsQuery {
+    /// Количество жалоб на странице
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "must be between 1 and 100"))]
+    pub page_size: u32,
+    /// Номер страницы (начиная с 0)
+    pub page: u32,
+    /// Вернуть только жалобы в этом состоянии
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub status: Option<ReportStatus>,
+}
+
+/// Жалоба на пост.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ReportResponse {
+    /// UUID жалобы
+    pub id: String,
+    /// UUID поста
+    pub post_id: String,
+    /// UUID пожаловавшегося пользователя
+    pub reporter_id: String,
+    /// Причина жалобы
+    pub reason: ReportReason,
+    /// Пояснение к жалобе
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub details: Option<String>,
+    /// Состояние жалобы
+    pub status: ReportStatus,
+    /// UUID модератора, принявшего решение
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub resolved_by: Option<String>,
+    /// Время решения (RFC 3339); нет у открытых жалоб
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub resolved_at: Option<String>,
+    /// Временная метка жалобы (RFC 3339)
+    pub created_at: String,
+}
+
+/// Событие живой ленты постов, передаваемое через Server-Sent Events.
+///
+/// Поле `data` события SSE содержит этот объект, имя события SSE совпадает с `type`.
+/// Черновики в ленту не попадают: публикация черновика приходит как `created`,
+/// возврат поста в черновики - как `deleted`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum PostEventResponse {
+    /// Пост опубликован
+    Created {
+        /// Данные поста
+        post: PostResponse,
+    },
+    /// Опубликованный пост изменён
+    Updated {
+        /// Данные поста
+        post: PostResponse,
+    },
+    /// Пост удалён или снят с публикации
+    Deleted {
+        /// UUID поста
+        post_id: String,
+    },
+}
+
+impl PostEventResponse {
+    /// Имя события SSE (`created`, `updated` или `deleted`)
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            PostEventResponse::Created { .. } => "created",
+            PostEventResponse::Updated { .. } => "updated",
+            PostEventResponse::Deleted { .. } => "deleted",
+        }
+    }
+}
+
+/// Машиночитаемый код ошибки в [`ErrorResponse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum ErrorCode {
+    /// Некорректный запрос (HTTP 400)
+    BadRequest,
+    /// Поля запроса не прошли валидацию, подробности в `details` (HTTP 400)
+    ValidationFailed,
+    /// Требуется аутентификация или токен недействителен (HTTP 401)
+    Unauthorized,
+    /// Действие запрещено (HTTP 403)
+    Forbidden,
+    /// Ресурс не найден (HTTP 404)
+    NotFound,
+    /// Конфликт с текущим состоянием ресурса (HTTP 409)
+    Conflict,
+    /// Превышен лимит частоты запросов, см. заголовок `Retry-After` (HTTP 429)
+    RateLimited,
+    /// Вход заблокирован после серии неудачных попыток, см. заголовок `Retry-After` (HTTP 423)
+    AccountLocked,
+    /// Превышена суточная квота постов, см. заголовок `Retry-After` (HTTP 429),
+    /// или ограничение размера поста (HTTP 403)
+    QuotaExceeded,
+    /// Внутренняя ошибка сервера (HTTP 500)
+    InternalError,
+}
+
+/// Тело ответа с ошибкой.
+///
+/// Возвращается сервером для всех неуспешных запросов.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct ErrorResponse {
+    /// Машиночитаемый код ошибки
+    pub code: ErrorCode,
+    /// Описание ошибки для человека
+    pub message: String,
+    /// Ошибки отдельных полей запроса (`поле -> сообщения`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    #[cfg_attr(
+        feature = "typescript",
+        ts(as = "Option<BTreeMap<String, Vec<String>>>", optional)
+    )]
+    pub details: BTreeMap<String, Vec<String>>,
+    /// ID запроса из логов сервера, по которому ошибку можно найти
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub trace_id: Option<String>,
+}
+
+/// Страница элементов списка с метаданными пагинации.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS))]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct Paginated<T> {
+    /// Элементы текущей страницы
+    pub items: Vec<T>,
+    /// Общее количество элементов
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
+    pub total: u64,
+    /// Номер страницы (начиная с 0)
+    pub page: u32,
+    /// Размер страницы
+    pub page_size: u32,
+    /// Курсор следующей страницы (см. `ListPostsQuery::cursor`); есть, только если
+    /// следующая страница существует и список поддерживает обход по курсору
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub next_cursor: Option<String>,
+}
+
+/// Преобразует нарушения валидации в словарь `поле -> сообщения`.
+///
+/// Используется для формирования структурированного ответа об ошибке на сервере
+/// и для показа ошибок рядом с полями на клиенте. Поля вложенных структур
+/// записываются путём вида `posts[0].title`.
+pub fn field_errors(errors: &ValidationErrors) -> BTreeMap<String, Vec<String>> {
+    let mut fields = BTreeMap::new();
+    collect_field_errors(errors, "", &mut fields);
+    fields
+}
+
+fn collect_field_errors(
+    errors: &ValidationErrors,
+    prefix: &str,
+    fields: &mut BTreeMap<String, Vec<String>>,
+) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{prefix}.{field}")
+        };
+        match kind {
+            ValidationErrorsKind::Field(violations) => {
+                let messages = violations
+                    .iter()
+                    .map(|violation| match &violation.message {
+                        Some(message) => message.to_string(),
+                        None => violation.code.to_string(),
+                    })
+                    .collect();
+                fields.insert(path, messages);
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(nested, &path, fields),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(nested, &format!("{path}[{index}]"), fields);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_request_validation() {
+        let request = RegisterRequest {
+            username: "al".to_string(),
+            password: "short".to_string(),
+            email: "not-an-email".to_string(),
+        };
+
+        let errors = request.validate().unwrap_err();
+        let fields = field_errors(&errors);
+
+        assert_eq!(
+            fields.keys().collect::<Vec<_>>(),
+            vec!["email", "password", "username"]
+        );
+        assert_eq!(fields["email"], vec!["must be a valid email address"]);
+    }
+
+    #[test]
+    fn test_login_request_identifier() {
+        let request: LoginRequest =
+            serde_json::from_str(r#"{"username": "alice", "password": "secret"}"#).unwrap();
+        assert_eq!(request.identifier(), Some("alice"));
+
+        let request: LoginRequest = serde_json::from_str(
+            r#"{"username": "alice", "email": "alice@example.com", "password": "secret"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.identifier(), Some("alice@example.com"));
+
+        let request: LoginRequest = serde_json::from_str(r#"{"password": "secret"}"#).unwrap();
+        assert_eq!(request.identifier(), None);
+    }
+
+    #[test]
+    fn test_pagination_query_range() {
+        let query = PaginationQuery {
+            page_size: 0,
+            page: 0,
+        };
+        assert!(query.validate().is_err());
+
+        let query = PaginationQuery {
+            page_size: MAX_PAGE_SIZE,
+            page: 3,
+        };
+        assert!(query.validate().is_ok());
+    }
+
+    #[test]
+    fn test_create_posts_request_nested_errors() {
+        let request: CreatePostsRequest = serde_json::from_str(
+            r#"{"posts": [{"title": "ok", "content": "ok"}, {"title": "", "content": "ok"}]}"#,
+        )
+        .unwrap();
+
+        let errors = request.validate().unwrap_err();
+        let fields = field_errors(&errors);
+
+        assert_eq!(fields.keys().collect::<Vec<_>>(), vec!["posts[1].title"]);
+
+        let request = CreatePostsRequest { posts: Vec::new() };
+        let fields = field_errors(&request.validate().unwrap_err());
+        assert_eq!(
+            fields["posts"],
+            vec!["must contain between 1 and 100 posts"]
+        );
+    }
+}
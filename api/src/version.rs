@@ -0,0 +1,27 @@
+//! Версии протокола и возможности сервера.
+//!
+//! Сервер сообщает их через `GET /api/info` и gRPC `GetServerInfo`, а клиент
+//! при создании проверяет, что сервер обслуживает нужную ему версию API.
+
+/// Версия API, которой пользуется клиент из этого репозитория
+pub const API_VERSION: &str = "v1";
+
+/// Версии API, которые обслуживает сервер из этого репозитория.
+///
+/// REST маршруты `v2` (`/api/v2/...`) пока совпадают с `v1` и обслуживаются теми же
+/// обработчиками; несовместимые изменения будут вноситься только в `v2`.
+pub const SUPPORTED_API_VERSIONS: &[&str] = &[API_VERSION, "v2"];
+
+/// Названия необязательных возможностей сервера.
+pub mod features {
+    /// Поток событий об изменении постов (`/api/v1/posts/events`, `StreamPosts`)
+    pub const POST_EVENTS: &str = "post_events";
+    /// Двухфакторная аутентификация (TOTP)
+    pub const TWO_FACTOR: &str = "two_factor";
+    /// Загрузка файлов для вложений
+    pub const UPLOADS: &str = "uploads";
+    /// Вход через внешних OAuth-провайдеров (есть хотя бы один настроенный)
+    pub const OAUTH: &str = "oauth";
+    /// Сервер отдаёт содержимое постов, отрендеренное из Markdown в HTML
+    pub const CONTENT_HTML: &str = "content_html";
+}
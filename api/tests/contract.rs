@@ -0,0 +1,1060 @@
+//! Контрактные тесты типов REST и сообщений gRPC.
+//!
+//! Каждый DTO из [`api::rest`] и каждое сообщение `proto/blog.proto` проходит через
+//! сериализацию и обратно без потерь. Соответствие полей REST и proto описано в
+//! [`contracts`]: поле, добавленное или переименованное только с одной стороны,
+//! роняет тест, пока расхождение не описано явно.
+
+#![cfg(all(feature = "rest", feature = "grpc"))]
+
+use std::collections::BTreeSet;
+
+use api::rest;
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, Kind, MessageDescriptor, Value};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+/// Временная метка образцов; совпадает с [`TIMESTAMP_SECONDS`] и [`TIMESTAMP_NANOS`]
+const TIMESTAMP: &str = "2024-05-01T10:00:00.250Z";
+const TIMESTAMP_SECONDS: i64 = 1_714_557_600;
+const TIMESTAMP_NANOS: i32 = 250_000_000;
+
+const ID: &str = "4f6c1c1e-2f43-4a5e-9d4e-1a2b3c4d5e6f";
+const OTHER_ID: &str = "0b7e7a1e-5b2a-4d8e-8f0e-6c5d4b3a2918";
+
+fn pool() -> DescriptorPool {
+    DescriptorPool::decode(api::FILE_DESCRIPTOR_SET).unwrap()
+}
+
+fn message_descriptor(name: &str) -> MessageDescriptor {
+    pool()
+        .get_message_by_name(&format!("blog.{name}"))
+        .unwrap_or_else(|| panic!("blog.{name} is not in proto/blog.proto"))
+}
+
+/// Сообщение, в котором заданы все поля: значения не совпадают со значениями
+/// по умолчанию, поэтому потерянное поле видно при сравнении.
+fn populated(descriptor: &MessageDescriptor) -> DynamicMessage {
+    let mut message = DynamicMessage::new(descriptor.clone());
+    if descriptor.full_name() == "google.protobuf.Timestamp" {
+        message.set_field_by_name("seconds", Value::I64(TIMESTAMP_SECONDS));
+        message.set_field_by_name("nanos", Value::I32(TIMESTAMP_NANOS));
+        return message;
+    }
+
+    for field in descriptor.fields() {
+        let value = match field.kind() {
+            Kind::String => Value::String(format!("{}-value", field.name())),
+            Kind::Bytes => Value::Bytes(vec![1, 2, 3].into()),
+            Kind::Bool => Value::Bool(true),
+            Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => Value::I32(7),
+            Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => Value::I64(7),
+            Kind::Uint32 | Kind::Fixed32 => Value::U32(7),
+            Kind::Uint64 | Kind::Fixed64 => Value::U64(7),
+            Kind::Float => Value::F32(1.5),
+            Kind::Double => Value::F64(1.5),
+            // Последнее значение перечисления - не нулевое значение по умолчанию
+            Kind::Enum(descriptor) => {
+                Value::EnumNumber(descriptor.values().last().unwrap().number())
+            }
+            Kind::Message(descriptor) => Value::Message(populated(&descriptor)),
+        };
+        let value = if field.is_list() {
+            Value::List(vec![value])
+        } else {
+            value
+        };
+        message.set_field(&field, value);
+    }
+    message
+}
+
+/// Декодирует сообщение типом `M` и снова кодирует его
+fn proto_round_trip<M: Message + Default>(bytes: &[u8]) -> Vec<u8> {
+    M::decode(bytes).unwrap().encode_to_vec()
+}
+
+macro_rules! proto_messages {
+    ($($name:ident),* $(,)?) => {
+        vec![$((stringify!($name), proto_round_trip::<api::$name> as fn(&[u8]) -> Vec<u8>)),*]
+    };
+}
+
+#[test]
+fn test_every_proto_message_round_trips() {
+    let messages = proto_messages![
+        GetServerInfoRequest,
+        ServerInfoResponse,
+        PostLimits,
+        RegisterRequest,
+        RegisterResponse,
+        LoginRequest,
+        LoginResponse,
+        LoginWithTotpRequest,
+        RefreshTokenRequest,
+        RefreshTokenResponse,
+        CheckUsernameRequest,
+        CheckUsernameResponse,
+        LogoutRequest,
+        LogoutResponse,
+        LogoutAllRequest,
+        LogoutAllResponse,
+        ListSessionsRequest,
+        ListSessionsResponse,
+        RevokeSessionRequest,
+        RevokeSessionResponse,
+        ForgotPasswordRequest,
+        ForgotPasswordResponse,
+        ResetPasswordRequest,
+        ResetPasswordResponse,
+        GetUserProfileRequest,
+        UpdateMyProfileRequest,
+        UserProfileResponse,
+        JwtContainer,
+        CreatePostRequest,
+        GetPostRequest,
+        UpdatePostRequest,
+        AttachmentIds,
+        PostResponse,
+        DeletePostRequest,
+        DeletePostResponse,
+        CreatePostsResponse,
+        DeletePostsRequest,
+        DeletePostsResponse,
+        RestorePostRequest,
+        PurgePostRequest,
+        PurgePostResponse,
+        PublishPostRequest,
+        UnpublishPostRequest,
+        LikePostRequest,
+        UnlikePostRequest,
+        FollowUserRequest,
+        UnfollowUserRequest,
+        GetFollowStatusRequest,
+        FollowResponse,
+        GetDigestSettingsRequest,
+        UpdateDigestSettingsRequest,
+        DigestSettingsResponse,
+        UploadFileRequest,
+        UploadFileResponse,
+        Attachment,
+        ListPostsRequest,
+        ListPostsByAuthorRequest,
+        GetFeedRequest,
+        ListPostsResponse,
+        ListTrendingPostsRequest,
+        ListTrendingPostsResponse,
+        StreamPostsRequest,
+        ListUsersRequest,
+        ListUsersResponse,
+        SetUserDisabledRequest,
+        SetUserDisabledResponse,
+        ForceDeletePostRequest,
+        ForceDeletePostResponse,
+        GetSystemStatsRequest,
+        SystemStatsResponse,
+        ListCategoriesRequest,
+        ListCategoriesResponse,
+        CreateCategoryRequest,
+        CategoryResponse,
+        ListBlogsRequest,
+        ListBlogsResponse,
+        GetBlogRequest,
+        CreateBlogRequest,
+        UpdateBlogRequest,
+        DeleteBlogRequest,
+        DeleteBlogResponse,
+        BlogResponse,
+        ReportPostRequest,
+        ReportResponse,
+        ListReportsRequest,
+        ListReportsResponse,
+        ResolveReportRequest,
+        Response,
+        Post,
+        Session,
+        UserProfile,
+        AdminUser,
+        Report,
+        Category,
+        BlogInfo,
+        User,
+    ];
+
+    // prost переименовывает типы в UpperCamelCase (`JWTContainer` -> `JwtContainer`)
+    let listed: BTreeSet<String> = messages
+        .iter()
+        .map(|(name, _)| name.to_ascii_lowercase())
+        .collect();
+    let pool = pool();
+    let declared: BTreeSet<String> = pool
+        .all_messages()
+        .filter(|descriptor| descriptor.package_name() == "blog")
+        .map(|descriptor| descriptor.name().to_ascii_lowercase())
+        .collect();
+    assert_eq!(listed, declared, "every proto message must be listed here");
+
+    for descriptor in pool
+        .all_messages()
+        .filter(|descriptor| descriptor.package_name() == "blog")
+    {
+        let (_, round_trip) = messages
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(descriptor.name()))
+            .unwrap();
+        let original = populated(&descriptor);
+
+        let bytes = round_trip(&original.encode_to_vec());
+
+        let decoded = DynamicMessage::decode(descriptor.clone(), bytes.as_slice()).unwrap();
+        assert_eq!(decoded, original, "{} lost fields", descriptor.name());
+    }
+}
+
+fn post() -> serde_json::Value {
+    json!({
+        "uuid": ID,
+        "title": "Title",
+        "content": "Content",
+        "content_html": "<p>Content</p>",
+        "summary": "Summary",
+        "author_id": OTHER_ID,
+        "author_username": "alice",
+        "author_avatar_url": "https://example.com/alice.png",
+        "tags": ["rust"],
+        "category_id": ID,
+        "blog_id": ID,
+        "attachments": ["/uploads/a.png"],
+        "status": "draft",
+        "like_count": 3,
+        "liked_by_me": true,
+        "created_at": TIMESTAMP,
+        "updated_at": TIMESTAMP,
+    })
+}
+
+fn create_post() -> serde_json::Value {
+    json!({
+        "title": "Title",
+        "content": "Content",
+        "tags": ["rust"],
+        "attachment_ids": [ID],
+        "category_id": ID,
+        "summary": "Summary",
+        "status": "draft",
+    })
+}
+
+fn session() -> serde_json::Value {
+    json!({
+        "id": ID,
+        "user_agent": "curl/8.0",
+        "ip_address": "127.0.0.1",
+        "created_at": TIMESTAMP,
+        "last_used_at": TIMESTAMP,
+        "current": true,
+    })
+}
+
+fn category() -> serde_json::Value {
+    json!({"id": ID, "name": "Rust", "parent_id": OTHER_ID, "created_at": TIMESTAMP})
+}
+
+fn blog() -> serde_json::Value {
+    json!({"id": ID, "slug": "rust", "name": "Rust", "owner_id": OTHER_ID, "created_at": TIMESTAMP})
+}
+
+/// Образец JSON DTO, в котором заданы все поля
+struct Sample {
+    name: &'static str,
+    json: serde_json::Value,
+    round_trip: fn(&serde_json::Value),
+}
+
+/// Разбирает `json` в `T` и проверяет, что сериализация даёт тот же JSON
+fn json_round_trip<T: Serialize + DeserializeOwned>(json: &serde_json::Value) {
+    let name = std::any::type_name::<T>();
+    let dto: T = serde_json::from_value(json.clone())
+        .unwrap_or_else(|e| panic!("{name} rejected its sample: {e}"));
+    assert_eq!(&serde_json::to_value(&dto).unwrap(), json, "{name}");
+}
+
+/// Перечисления проверяются списком всех значений
+macro_rules! rest_samples {
+    ($($name:ident: $ty:ty = $json:expr;)*) => {
+        vec![$(Sample {
+            name: stringify!($name),
+            json: $json,
+            round_trip: json_round_trip::<$ty>,
+        }),*]
+    };
+}
+
+fn rest_samples() -> Vec<Sample> {
+    rest_samples! {
+        PostStatus: Vec<rest::PostStatus> = json!(["draft", "published"]);
+        PostSortField: Vec<rest::PostSortField> = json!(["created_at", "updated_at", "title"]);
+        SortOrder: Vec<rest::SortOrder> = json!(["asc", "desc"]);
+        RegisterRequest: rest::RegisterRequest =
+            json!({"username": "alice", "password": "password123", "email": "alice@example.com"});
+        UserResponse: rest::UserResponse = json!({
+            "id": ID, "username": "alice", "email": "alice@example.com", "created_at": TIMESTAMP,
+        });
+        UserProfileResponse: rest::UserProfileResponse = json!({
+            "id": ID,
+            "username": "alice",
+            "display_name": "Alice",
+            "bio": "Rustacean",
+            "avatar_url": "https://example.com/alice.png",
+            "created_at": TIMESTAMP,
+        });
+        UpdateProfileRequest: rest::UpdateProfileRequest = json!({
+            "display_name": "Alice", "bio": "Rustacean", "avatar_url": "https://example.com/alice.png",
+        });
+        LoginRequest: rest::LoginRequest =
+            json!({"username": "alice", "email": "alice@example.com", "password": "password123"});
+        ServerInfoResponse: rest::ServerInfoResponse = json!({
+            "version": "0.1.0",
+            "api_versions": ["v1"],
+            "features": ["posts"],
+            "post_limits": {"max_posts_per_day": 10, "max_content_bytes": 100000, "max_title_chars": 500},
+        });
+        PostLimitsResponse: rest::PostLimitsResponse =
+            json!({"max_posts_per_day": 10, "max_content_bytes": 100000, "max_title_chars": 500});
+        UsernameAvailabilityResponse: rest::UsernameAvailabilityResponse =
+            json!({"username": "alice", "available": true});
+        RefreshTokenRequest: rest::RefreshTokenRequest = json!({"refresh_token": "refresh"});
+        LogoutRequest: rest::LogoutRequest = json!({"refresh_token": "refresh"});
+        ForgotPasswordRequest: rest::ForgotPasswordRequest = json!({"email": "alice@example.com"});
+        ResetPasswordRequest: rest::ResetPasswordRequest =
+            json!({"token": "reset", "new_password": "password123"});
+        TokenResponse: rest::TokenResponse =
+            json!({"access_token": "access", "refresh_token": "refresh", "expires_in": 3600});
+        TwoFactorChallengeResponse: rest::TwoFactorChallengeResponse =
+            json!({"requires_2fa": true, "challenge_token": "challenge", "expires_in": 300});
+        TotpLoginRequest: rest::TotpLoginRequest =
+            json!({"challenge_token": "challenge", "code": "123456"});
+        TotpEnrollmentResponse: rest::TotpEnrollmentResponse =
+            json!({"secret": "JBSWY3DPEHPK3PXP", "provisioning_uri": "otpauth://totp/blog:alice"});
+        SessionResponse: rest::SessionResponse = session();
+        SessionListResponse: rest::SessionListResponse = json!({"sessions": [session()]});
+        TotpVerifyRequest: rest::TotpVerifyRequest = json!({"code": "123456"});
+        CreatePostRequest: rest::CreatePostRequest = create_post();
+        UpdatePostRequest: rest::UpdatePostRequest = json!({
+            "title": "Title",
+            "content": "Content",
+            "tags": ["rust"],
+            "attachment_ids": [ID],
+            "category_id": ID,
+            "summary": "Summary",
+        });
+        CreatePostsRequest: rest::CreatePostsRequest = json!({"posts": [create_post()]});
+        CreatePostsResponse: rest::CreatePostsResponse = json!({"posts": [post()]});
+        DeletePostsRequest: rest::DeletePostsRequest = json!({"post_ids": [ID]});
+        PaginationQuery: rest::PaginationQuery = json!({"page_size": 10, "page": 2});
+        ListPostsQuery: rest::ListPostsQuery = json!({
+            "page_size": 10,
+            "page": 2,
+            "cursor": "cursor",
+            "tag": "rust",
+            "category": ID,
+            "sort_by": "title",
+            "order": "asc",
+            "fields": "title,summary",
+        });
+        PostFieldsQuery: rest::PostFieldsQuery = json!({"fields": "title,summary"});
+        TrendingPostsQuery: rest::TrendingPostsQuery = json!({"limit": 5});
+        PostResponse: rest::PostResponse = post();
+        UploadResponse: rest::UploadResponse = json!({
+            "id": ID,
+            "url": "/uploads/a.png",
+            "file_name": "a.png",
+            "content_type": "image/png",
+            "size": 1024,
+        });
+        CategoryResponse: rest::CategoryResponse = category();
+        CategoryListResponse: rest::CategoryListResponse = json!({"categories": [category()]});
+        CreateCategoryRequest: rest::CreateCategoryRequest =
+            json!({"name": "Rust", "parent_id": OTHER_ID});
+        BlogResponse: rest::BlogResponse = blog();
+        BlogListResponse: rest::BlogListResponse = json!({"blogs": [blog()]});
+        CreateBlogRequest: rest::CreateBlogRequest = json!({"slug": "rust", "name": "Rust"});
+        UpdateBlogRequest: rest::UpdateBlogRequest = json!({"name": "Rust"});
+        AuditAction: Vec<rest::AuditAction> = json!([
+            "register", "login", "create_post", "update_post", "delete_post",
+            "restore_post", "purge_post", "hide_post", "disable_user", "enable_user",
+        ]);
+        AuditLogQuery: rest::AuditLogQuery = json!({
+            "page_size": 10, "page": 2, "user_id": ID, "from": TIMESTAMP, "to": TIMESTAMP,
+        });
+        AuditEntryResponse: rest::AuditEntryResponse = json!({
+            "id": ID, "user_id": OTHER_ID, "action": "hide_post", "target_id": ID, "created_at": TIMESTAMP,
+        });
+        UserRole: Vec<rest::UserRole> = json!(["user", "moderator", "admin"]);
+        AdminUserResponse: rest::AdminUserResponse = json!({
+            "id": ID,
+            "username": "alice",
+            "email": "alice@example.com",
+            "role": "moderator",
+            "created_at": TIMESTAMP,
+            "disabled_at": TIMESTAMP,
+        });
+        FollowResponse: rest::FollowResponse = json!({"following": true});
+        DigestFrequency: Vec<rest::DigestFrequency> = json!(["daily", "weekly"]);
+        DigestSettingsResponse: rest::DigestSettingsResponse =
+            json!({"frequency": "weekly", "last_sent_at": TIMESTAMP});
+        UpdateDigestSettingsRequest: rest::UpdateDigestSettingsRequest =
+            json!({"frequency": "weekly"});
+        SystemStatsResponse: rest::SystemStatsResponse =
+            json!({"users": 10, "posts": 20, "recent_signups": 3, "recent_signups_days": 7});
+        BodyLoggingSettings: rest::BodyLoggingSettings = json!({"enabled": true});
+        ReportReason: Vec<rest::ReportReason> = json!([
+            "spam", "harassment", "hate_speech", "misinformation", "inappropriate", "other",
+        ]);
+        ReportStatus: Vec<rest::ReportStatus> = json!(["open", "hidden", "dismissed"]);
+        ReportAction: Vec<rest::ReportAction> = json!(["hide_post", "dismiss"]);
+        ReportPostRequest: rest::ReportPostRequest = json!({"reason": "spam", "details": "Ads"});
+        ResolveReportRequest: rest::ResolveReportRequest = json!({"action": "hide_post"});
+        ListReportsQuery: rest::ListReportsQuery =
+            json!({"page_size": 10, "page": 2, "status": "open"});
+        ReportResponse: rest::ReportResponse = json!({
+            "id": ID,
+            "post_id": OTHER_ID,
+            "reporter_id": OTHER_ID,
+            "reason": "hate_speech",
+            "details": "Ads",
+            "status": "hidden",
+            "resolved_by": ID,
+            "resolved_at": TIMESTAMP,
+            "created_at": TIMESTAMP,
+        });
+        PostEventResponse: Vec<rest::PostEventResponse> = json!([
+            {"type": "created", "post": post()},
+            {"type": "updated", "post": post()},
+            {"type": "deleted", "post_id": ID},
+        ]);
+        ErrorCode: Vec<rest::ErrorCode> = json!([
+            "bad_request", "validation_failed", "unauthorized", "forbidden", "not_found",
+            "conflict", "rate_limited", "account_locked", "quota_exceeded", "internal_error",
+        ]);
+        ErrorResponse: rest::ErrorResponse = json!({
+            "code": "validation_failed",
+            "message": "Validation failed",
+            "details": {"title": ["must not be empty"]},
+            "trace_id": ID,
+        });
+        Paginated: rest::Paginated<rest::PostResponse> = json!({
+            "items": [post()], "total": 1, "page": 0, "page_size": 10, "next_cursor": "cursor",
+        });
+    }
+}
+
+fn rest_sample(name: &str) -> serde_json::Value {
+    rest_samples()
+        .into_iter()
+        .find(|sample| sample.name == name)
+        .unwrap_or_else(|| panic!("no sample for rest::{name}"))
+        .json
+}
+
+#[test]
+fn test_every_rest_dto_round_trips() {
+    let samples = rest_samples();
+
+    // Типы объявлены в rest.rs на верхнем уровне, поэтому их можно найти в исходнике
+    let declared: BTreeSet<&str> = include_str!("../src/rest.rs")
+        .lines()
+        .filter_map(|line| {
+            line.strip_prefix("pub struct ")
+                .or_else(|| line.strip_prefix("pub enum "))
+        })
+        .map(|rest| rest.split(|c: char| !c.is_alphanumeric()).next().unwrap())
+        .collect();
+    let listed: BTreeSet<&str> = samples.iter().map(|sample| sample.name).collect();
+    assert_eq!(listed, declared, "every REST DTO must have a sample here");
+
+    for sample in &samples {
+        (sample.round_trip)(&sample.json);
+    }
+}
+
+/// Соответствие полей DTO и сообщения, описывающих одно и то же.
+struct Contract {
+    rest: &'static str,
+    proto: &'static str,
+    /// Поля с одним смыслом: REST и proto
+    fields: &'static [(&'static str, &'static str)],
+    /// Поля только REST DTO (например, из пути запроса в proto)
+    rest_only: &'static [&'static str],
+    /// Поля только сообщения (например, параметры пути REST)
+    proto_only: &'static [&'static str],
+}
+
+/// `status` ответов gRPC в REST передаётся кодом HTTP и [`rest::ErrorResponse`]
+fn contracts() -> Vec<Contract> {
+    vec![
+        Contract {
+            rest: "RegisterRequest",
+            proto: "RegisterRequest",
+            fields: &[
+                ("username", "login"),
+                ("password", "password"),
+                ("email", "email"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "LoginRequest",
+            proto: "LoginRequest",
+            // В gRPC имя пользователя и email передаются одним полем
+            fields: &[
+                ("username", "email_or_login"),
+                ("email", "email_or_login"),
+                ("password", "password"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "TokenResponse",
+            proto: "JWTContainer",
+            // В REST срок жизни - секунды, в gRPC - момент истечения
+            fields: &[
+                ("access_token", "access_token"),
+                ("refresh_token", "refresh_token"),
+                ("expires_in", "expires_in"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "TwoFactorChallengeResponse",
+            proto: "LoginResponse",
+            fields: &[("challenge_token", "two_factor_challenge")],
+            rest_only: &["requires_2fa", "expires_in"],
+            proto_only: &["status", "token"],
+        },
+        Contract {
+            rest: "TotpLoginRequest",
+            proto: "LoginWithTotpRequest",
+            fields: &[("challenge_token", "challenge_token"), ("code", "code")],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "RefreshTokenRequest",
+            proto: "RefreshTokenRequest",
+            fields: &[("refresh_token", "refresh_token")],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "LogoutRequest",
+            proto: "LogoutRequest",
+            fields: &[("refresh_token", "refresh_token")],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "ForgotPasswordRequest",
+            proto: "ForgotPasswordRequest",
+            fields: &[("email", "email")],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "ResetPasswordRequest",
+            proto: "ResetPasswordRequest",
+            fields: &[("token", "token"), ("new_password", "new_password")],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "UsernameAvailabilityResponse",
+            proto: "CheckUsernameResponse",
+            fields: &[("available", "available")],
+            rest_only: &["username"],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "ServerInfoResponse",
+            proto: "ServerInfoResponse",
+            fields: &[
+                ("version", "version"),
+                ("api_versions", "api_versions"),
+                ("features", "features"),
+                ("post_limits", "post_limits"),
+            ],
+            rest_only: &[],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "PostLimitsResponse",
+            proto: "PostLimits",
+            fields: &[
+                ("max_posts_per_day", "max_posts_per_day"),
+                ("max_content_bytes", "max_content_bytes"),
+                ("max_title_chars", "max_title_chars"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "SessionResponse",
+            proto: "Session",
+            fields: &[
+                ("id", "id"),
+                ("user_agent", "user_agent"),
+                ("ip_address", "ip_address"),
+                ("created_at", "created_ts"),
+                ("last_used_at", "last_used_ts"),
+                ("current", "current"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "SessionListResponse",
+            proto: "ListSessionsResponse",
+            fields: &[("sessions", "sessions")],
+            rest_only: &[],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "UserProfileResponse",
+            proto: "UserProfile",
+            fields: &[
+                ("id", "id"),
+                ("username", "username"),
+                ("display_name", "display_name"),
+                ("bio", "bio"),
+                ("avatar_url", "avatar_url"),
+                ("created_at", "created_ts"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "UpdateProfileRequest",
+            proto: "UpdateMyProfileRequest",
+            fields: &[
+                ("display_name", "display_name"),
+                ("bio", "bio"),
+                ("avatar_url", "avatar_url"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "PostResponse",
+            proto: "Post",
+            fields: &[
+                ("uuid", "id"),
+                ("title", "title"),
+                ("content", "data"),
+                ("content_html", "content_html"),
+                ("summary", "summary"),
+                ("author_id", "author_id"),
+                ("author_username", "author_username"),
+                ("author_avatar_url", "author_avatar_url"),
+                ("tags", "tags"),
+                ("category_id", "category_id"),
+                ("blog_id", "blog_id"),
+                ("attachments", "attachment_urls"),
+                ("status", "status"),
+                ("like_count", "like_count"),
+                ("liked_by_me", "liked_by_me"),
+                ("created_at", "created_ts"),
+                ("updated_at", "last_updated_ts"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "CreatePostRequest",
+            proto: "CreatePostRequest",
+            fields: &[
+                ("title", "title"),
+                ("content", "data"),
+                ("tags", "tags"),
+                ("status", "status"),
+                ("attachment_ids", "attachment_ids"),
+                ("category_id", "category_id"),
+                ("summary", "summary"),
+            ],
+            rest_only: &[],
+            // В REST блог задаётся путём `/api/v1/blogs/{slug}/posts`
+            proto_only: &["blog_slug"],
+        },
+        Contract {
+            rest: "UpdatePostRequest",
+            proto: "UpdatePostRequest",
+            // В gRPC заголовок, содержимое и теги передаются в `post`
+            fields: &[
+                ("title", "post"),
+                ("content", "post"),
+                ("tags", "post"),
+                ("attachment_ids", "attachment_ids"),
+                ("category_id", "category_id"),
+                ("summary", "summary"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "CreatePostsResponse",
+            proto: "CreatePostsResponse",
+            fields: &[("posts", "posts")],
+            rest_only: &[],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "DeletePostsRequest",
+            proto: "DeletePostsRequest",
+            fields: &[("post_ids", "post_ids")],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "ListPostsQuery",
+            proto: "ListPostsRequest",
+            fields: &[
+                ("page_size", "page_size"),
+                ("page", "page_count"),
+                ("cursor", "cursor"),
+                ("tag", "tag"),
+                ("category", "category_id"),
+                ("sort_by", "sort_by"),
+                ("order", "order"),
+                ("fields", "field_mask"),
+            ],
+            rest_only: &[],
+            proto_only: &["blog_slug"],
+        },
+        Contract {
+            rest: "PostFieldsQuery",
+            proto: "GetPostRequest",
+            fields: &[("fields", "field_mask")],
+            rest_only: &[],
+            proto_only: &["id"],
+        },
+        Contract {
+            rest: "TrendingPostsQuery",
+            proto: "ListTrendingPostsRequest",
+            fields: &[("limit", "limit")],
+            rest_only: &[],
+            // В REST - отдельный параметр `fields` (`PostFieldsQuery`)
+            proto_only: &["field_mask"],
+        },
+        Contract {
+            rest: "Paginated",
+            proto: "ListPostsResponse",
+            fields: &[
+                ("items", "posts"),
+                ("total", "total"),
+                ("next_cursor", "next_cursor"),
+            ],
+            rest_only: &["page", "page_size"],
+            proto_only: &["status", "has_next"],
+        },
+        Contract {
+            rest: "Paginated",
+            proto: "ListUsersResponse",
+            fields: &[("items", "users"), ("total", "total")],
+            rest_only: &["page", "page_size", "next_cursor"],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "Paginated",
+            proto: "ListReportsResponse",
+            fields: &[("items", "reports"), ("total", "total")],
+            rest_only: &["page", "page_size", "next_cursor"],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "UploadResponse",
+            proto: "Attachment",
+            fields: &[
+                ("id", "id"),
+                ("url", "url"),
+                ("file_name", "file_name"),
+                ("content_type", "content_type"),
+                ("size", "size"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "CategoryResponse",
+            proto: "Category",
+            fields: &[
+                ("id", "id"),
+                ("name", "name"),
+                ("parent_id", "parent_id"),
+                ("created_at", "created_ts"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "CategoryListResponse",
+            proto: "ListCategoriesResponse",
+            fields: &[("categories", "categories")],
+            rest_only: &[],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "CreateCategoryRequest",
+            proto: "CreateCategoryRequest",
+            fields: &[("name", "name"), ("parent_id", "parent_id")],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "BlogResponse",
+            proto: "BlogInfo",
+            fields: &[
+                ("id", "id"),
+                ("slug", "slug"),
+                ("name", "name"),
+                ("owner_id", "owner_id"),
+                ("created_at", "created_ts"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "BlogListResponse",
+            proto: "ListBlogsResponse",
+            fields: &[("blogs", "blogs")],
+            rest_only: &[],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "CreateBlogRequest",
+            proto: "CreateBlogRequest",
+            fields: &[("slug", "slug"), ("name", "name")],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "UpdateBlogRequest",
+            proto: "UpdateBlogRequest",
+            fields: &[("name", "name")],
+            rest_only: &[],
+            proto_only: &["slug"],
+        },
+        Contract {
+            rest: "AdminUserResponse",
+            proto: "AdminUser",
+            fields: &[
+                ("id", "id"),
+                ("username", "username"),
+                ("email", "email"),
+                ("role", "role"),
+                ("created_at", "created_ts"),
+                ("disabled_at", "disabled_ts"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "FollowResponse",
+            proto: "FollowResponse",
+            fields: &[("following", "following")],
+            rest_only: &[],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "DigestSettingsResponse",
+            proto: "DigestSettingsResponse",
+            fields: &[("frequency", "frequency"), ("last_sent_at", "last_sent_ts")],
+            rest_only: &[],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "UpdateDigestSettingsRequest",
+            proto: "UpdateDigestSettingsRequest",
+            fields: &[("frequency", "frequency")],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "SystemStatsResponse",
+            proto: "SystemStatsResponse",
+            fields: &[
+                ("users", "users"),
+                ("posts", "posts"),
+                ("recent_signups", "recent_signups"),
+                ("recent_signups_days", "recent_signups_days"),
+            ],
+            rest_only: &[],
+            proto_only: &["status"],
+        },
+        Contract {
+            rest: "ReportPostRequest",
+            proto: "ReportPostRequest",
+            fields: &[("reason", "reason"), ("details", "details")],
+            rest_only: &[],
+            proto_only: &["post_id"],
+        },
+        Contract {
+            rest: "ResolveReportRequest",
+            proto: "ResolveReportRequest",
+            fields: &[("action", "action")],
+            rest_only: &[],
+            proto_only: &["report_id"],
+        },
+        Contract {
+            rest: "ListReportsQuery",
+            proto: "ListReportsRequest",
+            fields: &[
+                ("page_size", "page_size"),
+                ("page", "page_count"),
+                ("status", "status"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "ReportResponse",
+            proto: "Report",
+            fields: &[
+                ("id", "id"),
+                ("post_id", "post_id"),
+                ("reporter_id", "reporter_id"),
+                ("reason", "reason"),
+                ("details", "details"),
+                ("status", "status"),
+                ("resolved_by", "resolved_by"),
+                ("resolved_at", "resolved_ts"),
+                ("created_at", "created_ts"),
+            ],
+            rest_only: &[],
+            proto_only: &[],
+        },
+        Contract {
+            rest: "ErrorResponse",
+            proto: "Response",
+            fields: &[
+                ("code", "code"),
+                ("message", "details"),
+                ("trace_id", "trace_id"),
+            ],
+            // Ошибки отдельных полей есть только в REST
+            rest_only: &["details"],
+            proto_only: &[],
+        },
+    ]
+}
+
+#[test]
+fn test_rest_and_proto_fields_match_contracts() {
+    for contract in contracts() {
+        let pair = format!("rest::{} <-> blog.{}", contract.rest, contract.proto);
+
+        let rest_fields: BTreeSet<String> = rest_sample(contract.rest)
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        let described: BTreeSet<String> = contract
+            .fields
+            .iter()
+            .map(|(rest, _)| rest.to_string())
+            .chain(contract.rest_only.iter().map(|field| field.to_string()))
+            .collect();
+        assert_eq!(rest_fields, described, "{pair}: rest fields differ");
+
+        let proto_fields: BTreeSet<String> = message_descriptor(contract.proto)
+            .fields()
+            .map(|field| field.name().to_string())
+            .collect();
+        let described: BTreeSet<String> = contract
+            .fields
+            .iter()
+            .map(|(_, proto)| proto.to_string())
+            .chain(contract.proto_only.iter().map(|field| field.to_string()))
+            .collect();
+        assert_eq!(proto_fields, described, "{pair}: proto fields differ");
+    }
+}
+
+/// Проверяет `From` между DTO и сообщением в обе стороны.
+///
+/// `lossy` - поля сообщения, которых нет в DTO: после преобразования они пустые.
+fn conversion_round_trip<R, P>(rest_name: &str, proto_name: &str, lossy: &[&str])
+where
+    R: Serialize + DeserializeOwned + From<P>,
+    P: Message + Default + From<R>,
+{
+    let sample = rest_sample(rest_name);
+    let dto: R = serde_json::from_value(sample.clone()).unwrap();
+    let back = R::from(P::from(dto));
+    assert_eq!(
+        serde_json::to_value(&back).unwrap(),
+        sample,
+        "rest::{rest_name} -> blog.{proto_name} -> rest::{rest_name}"
+    );
+
+    let descriptor = message_descriptor(proto_name);
+    let original = populated(&descriptor);
+    let message = P::decode(original.encode_to_vec().as_slice()).unwrap();
+    let back = P::from(R::from(message));
+    let mut expected = original;
+    for field in lossy {
+        expected.clear_field_by_name(field);
+    }
+    assert_eq!(
+        DynamicMessage::decode(descriptor, back.encode_to_vec().as_slice()).unwrap(),
+        expected,
+        "blog.{proto_name} -> rest::{rest_name} -> blog.{proto_name}"
+    );
+}
+
+#[test]
+fn test_conversions_keep_every_field() {
+    conversion_round_trip::<rest::PostResponse, api::Post>("PostResponse", "Post", &[]);
+    conversion_round_trip::<rest::CreatePostRequest, api::CreatePostRequest>(
+        "CreatePostRequest",
+        "CreatePostRequest",
+        &["blog_slug"],
+    );
+    conversion_round_trip::<rest::UploadResponse, api::Attachment>(
+        "UploadResponse",
+        "Attachment",
+        &[],
+    );
+    conversion_round_trip::<rest::UserProfileResponse, api::UserProfile>(
+        "UserProfileResponse",
+        "UserProfile",
+        &[],
+    );
+    conversion_round_trip::<rest::SessionResponse, api::Session>("SessionResponse", "Session", &[]);
+    conversion_round_trip::<rest::AdminUserResponse, api::AdminUser>(
+        "AdminUserResponse",
+        "AdminUser",
+        &[],
+    );
+    conversion_round_trip::<rest::SystemStatsResponse, api::SystemStatsResponse>(
+        "SystemStatsResponse",
+        "SystemStatsResponse",
+        &["status"],
+    );
+    conversion_round_trip::<rest::DigestSettingsResponse, api::DigestSettingsResponse>(
+        "DigestSettingsResponse",
+        "DigestSettingsResponse",
+        &["status"],
+    );
+    conversion_round_trip::<rest::ReportResponse, api::Report>("ReportResponse", "Report", &[]);
+    conversion_round_trip::<rest::CategoryResponse, api::Category>(
+        "CategoryResponse",
+        "Category",
+        &[],
+    );
+    conversion_round_trip::<rest::BlogResponse, api::BlogInfo>("BlogResponse", "BlogInfo", &[]);
+}
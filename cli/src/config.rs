@@ -0,0 +1,173 @@
+//! Файл настроек CLI с именованными профилями.
+//!
+//! Профиль задаёт адрес сервера, транспорт и файл токенов, чтобы не указывать
+//! `--server` и `--use-grpc` при каждом запуске. Флаги командной строки имеют
+//! приоритет над профилем.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::{fmt, io};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Профиль, используемый без `--profile` и без `default_profile` в файле
+pub const DEFAULT_PROFILE: &str = "default";
+
+type ConfigResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// Содержимое `config.toml`.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Config {
+    /// Профиль, используемый без `--profile`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Именованный набор настроек подключения.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct Profile {
+    /// URL сервера
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    /// Транспорт
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transport: Option<TransportKind>,
+    /// Где хранятся токены
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_store: Option<TokenStoreKind>,
+    /// Файл, в котором хранятся токены
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_file: Option<String>,
+}
+
+/// Транспорт профиля.
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TransportKind {
+    Http,
+    Grpc,
+}
+
+/// Хранилище токенов профиля.
+#[derive(Serialize, Deserialize, ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenStoreKind {
+    /// JSON-файл (по умолчанию `.blog_token` в текущей директории)
+    #[default]
+    File,
+    /// Хранилище учётных данных ОС; токены каждого профиля хранятся отдельно
+    Keyring,
+}
+
+/// Настройка, доступная через `config get` и `config set`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ConfigKey {
+    /// URL сервера профиля
+    Server,
+    /// Транспорт профиля: http или grpc
+    Transport,
+    /// Хранилище токенов профиля: file или keyring
+    TokenStore,
+    /// Файл токенов профиля
+    TokenFile,
+    /// Профиль по умолчанию (общая настройка, не зависит от --profile)
+    DefaultProfile,
+}
+
+impl fmt::Display for ConfigKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Имя в том виде, в каком оно указывается в командной строке
+        match self.to_possible_value() {
+            Some(value) => f.write_str(value.get_name()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Config {
+    /// Путь по умолчанию: `~/.config/blog-cli/config.toml` (на Linux)
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("blog-cli").join("config.toml"))
+    }
+
+    /// Читает файл настроек; отсутствующий файл считается пустым.
+    pub fn load(path: &Path) -> ConfigResult<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => toml::from_str(&content)
+                .map_err(|e| format!("Invalid config file {}: {}", path.display(), e).into()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Failed to read config file {}: {}", path.display(), e).into()),
+        }
+    }
+
+    /// Записывает файл настроек, создавая каталог при необходимости.
+    pub fn save(&self, path: &Path) -> ConfigResult<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Имя профиля: из `--profile`, затем `default_profile`, затем [`DEFAULT_PROFILE`]
+    pub fn profile_name<'a>(&'a self, requested: Option<&'a str>) -> &'a str {
+        requested
+            .or(self.default_profile.as_deref())
+            .unwrap_or(DEFAULT_PROFILE)
+    }
+
+    /// Профиль для подключения.
+    ///
+    /// Явно запрошенный профиль должен существовать; отсутствующий профиль по
+    /// умолчанию означает настройки из флагов.
+    pub fn profile(&self, requested: Option<&str>) -> ConfigResult<Profile> {
+        let name = self.profile_name(requested);
+        match self.profiles.get(name) {
+            Some(profile) => Ok(profile.clone()),
+            None if requested.is_none() => Ok(Profile::default()),
+            None => Err(format!("Profile {} not found", name).into()),
+        }
+    }
+
+    /// Значение настройки или `None`, если она не задана
+    pub fn get(&self, profile: &str, key: ConfigKey) -> Option<String> {
+        match key {
+            ConfigKey::Server => self.profiles.get(profile)?.server.clone(),
+            ConfigKey::Transport => {
+                let transport = self.profiles.get(profile)?.transport?;
+                Some(transport.to_possible_value()?.get_name().to_string())
+            }
+            ConfigKey::TokenStore => {
+                let token_store = self.profiles.get(profile)?.token_store?;
+                Some(token_store.to_possible_value()?.get_name().to_string())
+            }
+            ConfigKey::TokenFile => self.profiles.get(profile)?.token_file.clone(),
+            ConfigKey::DefaultProfile => self.default_profile.clone(),
+        }
+    }
+
+    /// Устанавливает настройку, создавая профиль при необходимости.
+    pub fn set(&mut self, profile: &str, key: ConfigKey, value: String) -> ConfigResult<()> {
+        match key {
+            ConfigKey::Server => self.profile_mut(profile).server = Some(value),
+            ConfigKey::Transport => {
+                self.profile_mut(profile).transport = Some(TransportKind::from_str(&value, true)?);
+            }
+            ConfigKey::TokenStore => {
+                self.profile_mut(profile).token_store =
+                    Some(TokenStoreKind::from_str(&value, true)?);
+            }
+            ConfigKey::TokenFile => self.profile_mut(profile).token_file = Some(value),
+            ConfigKey::DefaultProfile => self.default_profile = Some(value),
+        }
+        Ok(())
+    }
+
+    fn profile_mut(&mut self, name: &str) -> &mut Profile {
+        self.profiles.entry(name.to_string()).or_default()
+    }
+}
@@ -0,0 +1,88 @@
+//! Источники содержимого поста: аргумент, файл, стандартный ввод или редактор.
+
+use std::error::Error;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// Строка-подсказка нового поста в редакторе; удаляется из результата
+const EDITOR_HINT: &str =
+    "<!-- Write the post content in Markdown, save and close the editor. This line is removed. -->";
+
+/// Откуда взять содержимое поста (ровно один из вариантов).
+#[derive(clap::Args, Debug)]
+#[group(required = true, multiple = false)]
+pub struct ContentArgs {
+    /// Содержимое поста
+    #[arg(short, long)]
+    content: Option<String>,
+    /// Файл с содержимым поста ("-" - стандартный ввод)
+    #[arg(short, long, value_name = "PATH")]
+    file: Option<PathBuf>,
+    /// Написать содержимое в редакторе из $VISUAL или $EDITOR
+    #[arg(long)]
+    editor: bool,
+}
+
+impl ContentArgs {
+    /// Содержимое будет написано в редакторе
+    pub fn uses_editor(&self) -> bool {
+        self.editor
+    }
+
+    /// Читает содержимое поста.
+    ///
+    /// `current` - текущее содержимое, с которым открывается редактор при обновлении поста.
+    pub fn read(self, current: Option<&str>) -> Result<String, Box<dyn Error>> {
+        let content = match (self.content, self.file) {
+            (Some(content), _) => return Ok(content),
+            (None, Some(path)) if path == Path::new("-") => {
+                let mut content = String::new();
+                std::io::stdin().read_to_string(&mut content)?;
+                content
+            }
+            (None, Some(path)) => std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?,
+            (None, None) => edit(current)?,
+        };
+        if content.trim().is_empty() {
+            return Err("Post content is empty".into());
+        }
+        Ok(content)
+    }
+}
+
+/// Открывает редактор с шаблоном и возвращает сохранённый текст.
+fn edit(current: Option<&str>) -> Result<String, Box<dyn Error>> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Переменная может содержать аргументы, например "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().ok_or("$EDITOR is empty")?;
+
+    let path = std::env::temp_dir().join(format!("blog-post-{}.md", process::id()));
+    let template = match current {
+        Some(content) => format!("{}\n", content),
+        None => format!("{}\n\n", EDITOR_HINT),
+    };
+    std::fs::write(&path, template)?;
+
+    let status = process::Command::new(program)
+        .args(parts)
+        .arg(&path)
+        .status()
+        .map_err(|e| format!("Failed to run editor {}: {}", program, e));
+    let content = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+
+    if !status?.success() {
+        return Err("Editor exited with an error, post not saved".into());
+    }
+    let content = content?;
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| line.trim() != EDITOR_HINT)
+        .collect();
+    Ok(lines.join("\n").trim().to_string())
+}
@@ -8,6 +8,8 @@
 //! - Управление постами (создание, чтение, обновление, удаление)
 //! - Поддержка HTTP и gRPC транспорта
 //! - Сохранение токенов в файл `.blog_token`
+//! - Именованные профили в `~/.config/blog-cli/config.toml`
+//! - Хранение токенов в хранилище учётных данных ОС (feature `keyring`)
 //! - Безопасный ввод пароля с консоли (без отображения символов)
 //!
 //! ## Примеры использования
@@ -22,17 +24,118 @@
 //! # Вход с запросом пароля с консоли (пароль не будет виден при вводе)
 //! cargo run --bin cli -- login -u user
 //!
-//! # Создание поста
-//! cargo run --bin cli -- create-post -t "Title" -c "Content"
+//! # Вход с кодом двухфакторной аутентификации (без --totp код запрашивается с консоли)
+//! cargo run --bin cli -- login -u user --totp 123456
+//!
+//! # Создание поста с тегами
+//! cargo run --bin cli -- create-post -t "Title" -c "Content" --tags rust,wasm
+//!
+//! # Содержимое из файла, стандартного ввода или редактора $EDITOR
+//! cargo run --bin cli -- create-post -t "Title" --file post.md
+//! cat post.md | cargo run --bin cli -- create-post -t "Title" --file -
+//! cargo run --bin cli -- update-post -u <uuid> -t "Title" --editor
 //!
 //! # Список постов
 //! cargo run --bin cli -- list-posts --page-size 10 --page 0
 //!
+//! # Посты с тегом
+//! cargo run --bin cli -- list-posts --tag rust
+//!
+//! # Обновление списка постов каждые 2 секунды
+//! cargo run --bin cli -- list-posts --watch --interval 2
+//!
+//! # Черновик и его публикация
+//! cargo run --bin cli -- create-post -t "Title" -c "Content" --draft
+//! cargo run --bin cli -- publish-post -u <uuid>
+//!
+//! # Популярные посты
+//! cargo run --bin cli -- trending --limit 5
+//!
+//! # Лайк поста
+//! cargo run --bin cli -- like-post -u <uuid>
+//!
+//! # Подписка на автора и лента его постов
+//! cargo run --bin cli -- follow -u <user-uuid>
+//! cargo run --bin cli -- feed
+//!
+//! # Жалоба на пост
+//! cargo run --bin cli -- report-post -u <uuid> --reason spam --details "Link farm"
+//!
+//! # Токены в хранилище учётных данных ОС (сборка с --features keyring) или в своём файле
+//! cargo run --bin cli --features keyring -- --token-store keyring login -u user
+//! cargo run --bin cli -- --token-file ~/.blog_token login -u user
+//!
+//! # Текущий пользователь и срок действия сохранённого токена
+//! cargo run --bin cli -- whoami
+//!
+//! # Выход: отзыв токена и удаление .blog_token
+//! cargo run --bin cli -- logout
+//!
+//! # Выход со всех устройств (отзывает все токены пользователя)
+//! cargo run --bin cli -- logout-all
+//!
+//! # Сеансы входа и закрытие одного из них
+//! cargo run --bin cli -- list-sessions
+//! cargo run --bin cli -- revoke-session -u <session-uuid>
+//!
+//! # Сброс пароля
+//! cargo run --bin cli -- forgot-password -e user@example.com
+//! cargo run --bin cli -- reset-password -t <token>
+//!
+//! # Профиль пользователя
+//! cargo run --bin cli -- update-profile --display-name "Alice" --bio "Rustacean"
+//! cargo run --bin cli -- get-profile -u <user-uuid>
+//!
+//! # Email-дайджест новых постов
+//! cargo run --bin cli -- digest
+//! cargo run --bin cli -- digest --frequency weekly
+//! cargo run --bin cli -- digest --frequency off
+//!
+//! # HTTPS сервер с самоподписанным сертификатом
+//! cargo run --bin cli -- --server https://localhost:8080 --ca-cert ca.pem list-posts
+//!
+//! # Экспорт своих постов и импорт постов из каталога
+//! cargo run --bin cli -- export --format markdown --out backup/
+//! cargo run --bin cli -- import backup/ --on-conflict overwrite
+//!
+//! # Автодополнение для bash и man-страница
+//! cargo run --bin cli -- completions bash > /etc/bash_completion.d/cli
+//! cargo run --bin cli -- man > cli.1
+//!
+//! # Таблица или JSON вместо текстового вывода (list-posts, get-post, login)
+//! cargo run --bin cli -- --output table list-posts
+//! cargo run --bin cli -- --output json get-post -u <uuid>
+//!
+//! # Без информационных сообщений; код завершения сообщает причину ошибки
+//! cargo run --bin cli -- --quiet delete-post -u <uuid> || echo "exit code $?"
+//!
 //! # Использование gRPC вместо HTTP
 //! cargo run --bin cli -- --use-grpc --server http://localhost:50051 list-posts --page-size 10 --page 0
+//!
+//! # Профиль с адресом gRPC сервера и запуск с ним
+//! cargo run --bin cli -- --profile prod config set server http://blog.example.com:50051
+//! cargo run --bin cli -- --profile prod config set transport grpc
+//! cargo run --bin cli -- --profile prod list-posts
 //! ```
 
-use clap::{Parser, Subcommand};
+mod config;
+mod content;
+mod output;
+mod transfer;
+
+use std::io::IsTerminal;
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use client::blog_client::{BlogClient, BlogClientBuilder, Transport};
+use client::error::ClientError;
+use client::token_store::{DEFAULT_TOKEN_FILE, FileTokenStore};
+use config::{Config, ConfigKey, TokenStoreKind, TransportKind};
+use content::ContentArgs;
+use output::{OutputFormat, info};
+use transfer::{ExportFormat, OnConflict};
+use uuid::Uuid;
 
 /// Доступные команды CLI.
 #[derive(Subcommand, Debug)]
@@ -41,16 +144,93 @@ enum Command {
     Register(RegisterArgs),
     /// Вход пользователя в систему
     Login(LoginArgs),
+    /// Текущий пользователь и срок действия сохранённого токена
+    Whoami,
+    /// Выход: отзыв токена и удаление файла токенов
+    Logout,
+    /// Выход со всех устройств: отзыв всех токенов пользователя
+    LogoutAll,
+    /// Список сеансов входа текущего пользователя
+    ListSessions,
+    /// Закрытие сеанса входа
+    RevokeSession(RevokeSessionArgs),
+    /// Запрос токена сброса пароля
+    ForgotPassword(ForgotPasswordArgs),
+    /// Установка нового пароля по токену сброса
+    ResetPassword(ResetPasswordArgs),
+    /// Получение профиля пользователя по ID
+    GetProfile(GetProfileArgs),
+    /// Обновление профиля текущего пользователя
+    UpdateProfile(UpdateProfileArgs),
+    /// Настройки email-дайджеста новых постов: без аргументов показывает текущие
+    Digest(DigestArgs),
     /// Создание нового поста
     CreatePost(CreatePostArgs),
     /// Получение поста по ID
     GetPost(GetPostArgs),
     /// Обновление существующего поста
     UpdatePost(UpdatePostArgs),
-    /// Удаление поста
+    /// Перенос поста в корзину
     DeletePost(DeletePostArgs),
+    /// Перенос нескольких постов в корзину одной операцией
+    DeletePosts(DeletePostsArgs),
+    /// Восстановление поста из корзины
+    RestorePost(DeletePostArgs),
+    /// Окончательное удаление поста из корзины
+    PurgePost(DeletePostArgs),
+    /// Публикация черновика
+    PublishPost(PublishPostArgs),
+    /// Возврат поста в черновики
+    UnpublishPost(PublishPostArgs),
+    /// Лайк поста
+    LikePost(LikePostArgs),
+    /// Снятие лайка с поста
+    UnlikePost(LikePostArgs),
+    /// Жалоба модераторам на пост
+    ReportPost(ReportPostArgs),
     /// Получение списка постов с пагинацией
     ListPosts(ListPostsArgs),
+    /// Получение постов пользователя с пагинацией
+    ListUserPosts(ListUserPostsArgs),
+    /// Получение всех категорий постов
+    ListCategories,
+    /// Популярные посты: больше всего лайков за последнее время
+    Trending(TrendingArgs),
+    /// Лента: посты авторов из подписок
+    Feed(FeedArgs),
+    /// Подписка на автора
+    Follow(FollowArgs),
+    /// Отмена подписки на автора
+    Unfollow(FollowArgs),
+    /// Загрузка файла для вложения в пост
+    UploadFile(UploadFileArgs),
+    /// Сохранение всех своих постов в файлы
+    Export(ExportArgs),
+    /// Создание постов из файлов
+    Import(ImportArgs),
+    /// Просмотр и изменение настроек профиля
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// Вывод скрипта автодополнения для оболочки
+    Completions(CompletionsArgs),
+    /// Вывод man-страницы в формате roff
+    Man(ManArgs),
+}
+
+/// Команды работы с файлом настроек.
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Показать значение настройки
+    Get {
+        #[arg(value_enum)]
+        key: ConfigKey,
+    },
+    /// Изменить значение настройки
+    Set {
+        #[arg(value_enum)]
+        key: ConfigKey,
+        value: String,
+    },
 }
 
 #[derive(Parser, Debug)]
@@ -69,14 +249,97 @@ struct LoginArgs {
     username: String,
     #[arg(short, long)]
     password: Option<String>,
+    /// Код двухфакторной аутентификации (если нужен и не указан, запрашивается с консоли)
+    #[arg(long)]
+    totp: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct ForgotPasswordArgs {
+    #[arg(short, long, required = true)]
+    email: String,
+}
+
+#[derive(Parser, Debug)]
+struct ResetPasswordArgs {
+    /// Токен сброса пароля
+    #[arg(short, long, required = true)]
+    token: String,
+    /// Новый пароль (если не указан, запрашивается с консоли)
+    #[arg(short, long)]
+    password: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct RevokeSessionArgs {
+    /// ID сеанса
+    #[arg(short, long, required = true)]
+    uuid: String,
+}
+
+#[derive(Parser, Debug)]
+struct GetProfileArgs {
+    /// ID пользователя
+    #[arg(short, long, required = true)]
+    uuid: String,
+}
+
+#[derive(Parser, Debug)]
+struct UpdateProfileArgs {
+    /// Отображаемое имя
+    #[arg(long)]
+    display_name: Option<String>,
+    /// Описание профиля
+    #[arg(long)]
+    bio: Option<String>,
+    /// Ссылка на изображение аватара
+    #[arg(long)]
+    avatar_url: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct DigestArgs {
+    /// Новая частота писем; `off` отписывает от дайджеста
+    #[arg(long, value_enum)]
+    frequency: Option<DigestFrequency>,
+}
+
+/// Частота email-дайджеста.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum DigestFrequency {
+    Daily,
+    Weekly,
+    Off,
+}
+
+impl From<DigestFrequency> for Option<client::types::DigestFrequency> {
+    fn from(frequency: DigestFrequency) -> Self {
+        match frequency {
+            DigestFrequency::Daily => Some(client::types::DigestFrequency::Daily),
+            DigestFrequency::Weekly => Some(client::types::DigestFrequency::Weekly),
+            DigestFrequency::Off => None,
+        }
+    }
 }
 
 #[derive(Parser, Debug)]
 struct CreatePostArgs {
     #[arg(short, long, required = true)]
     title: String,
-    #[arg(short, long, required = true)]
-    content: String,
+    #[command(flatten)]
+    content: ContentArgs,
+    /// Теги через запятую
+    #[arg(long, value_delimiter = ',')]
+    tags: Vec<String>,
+    /// ID категории поста (см. list-categories)
+    #[arg(long)]
+    category: Option<String>,
+    /// Краткое описание для списков; без флага берётся начало содержимого
+    #[arg(long)]
+    summary: Option<String>,
+    /// Сохранить как черновик (виден только автору)
+    #[arg(long)]
+    draft: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -91,8 +354,19 @@ struct UpdatePostArgs {
     uuid: String,
     #[arg(short, long, required = true)]
     title: String,
-    #[arg(short, long, required = true)]
-    content: String,
+    /// Редактор открывается с текущим содержимым поста
+    #[command(flatten)]
+    content: ContentArgs,
+    /// Теги через запятую (заменяют текущие теги поста; без флага теги сохраняются)
+    #[arg(long, value_delimiter = ',')]
+    tags: Vec<String>,
+    /// ID новой категории поста; пустая строка убирает категорию, без флага категория сохраняется
+    #[arg(long)]
+    category: Option<String>,
+    /// Новое описание поста; пустая строка возвращает описание из начала содержимого,
+    /// без флага описание сохраняется
+    #[arg(long)]
+    summary: Option<String>,
 }
 
 #[derive(Parser, Debug)]
@@ -101,25 +375,342 @@ struct DeletePostArgs {
     uuid: String,
 }
 
+#[derive(Parser, Debug)]
+struct DeletePostsArgs {
+    /// UUID постов через запятую
+    #[arg(short, long, required = true, value_delimiter = ',')]
+    uuids: Vec<Uuid>,
+}
+
+#[derive(Parser, Debug)]
+struct PublishPostArgs {
+    #[arg(short, long, required = true)]
+    uuid: String,
+}
+
+#[derive(Parser, Debug)]
+struct LikePostArgs {
+    #[arg(short, long, required = true)]
+    uuid: String,
+}
+
+#[derive(Parser, Debug)]
+struct ReportPostArgs {
+    #[arg(short, long, required = true)]
+    uuid: String,
+    /// Причина жалобы
+    #[arg(long, value_enum)]
+    reason: ReportReason,
+    /// Пояснение для модератора
+    #[arg(long)]
+    details: Option<String>,
+}
+
+/// Причина жалобы на пост.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+#[value(rename_all = "snake_case")]
+enum ReportReason {
+    Spam,
+    Harassment,
+    HateSpeech,
+    Misinformation,
+    Inappropriate,
+    Other,
+}
+
+impl From<ReportReason> for client::types::ReportReason {
+    fn from(reason: ReportReason) -> Self {
+        match reason {
+            ReportReason::Spam => Self::Spam,
+            ReportReason::Harassment => Self::Harassment,
+            ReportReason::HateSpeech => Self::HateSpeech,
+            ReportReason::Misinformation => Self::Misinformation,
+            ReportReason::Inappropriate => Self::Inappropriate,
+            ReportReason::Other => Self::Other,
+        }
+    }
+}
+
 #[derive(Parser, Debug)]
 struct ListPostsArgs {
     #[arg(long, default_value = "10")]
     page_size: u32,
     #[arg(long, default_value = "0")]
     page: u32,
+    /// Показать только посты с этим тегом
+    #[arg(long)]
+    tag: Option<String>,
+    /// Показать только посты категории с этим ID и её подкатегорий (не сочетается с --tag)
+    #[arg(long, conflicts_with = "tag")]
+    category: Option<String>,
+    /// Поле сортировки (по умолчанию created_at; не сочетается с --tag и --category)
+    #[arg(long, value_enum, conflicts_with_all = ["tag", "category"])]
+    sort_by: Option<SortField>,
+    /// Направление сортировки (по умолчанию desc; не сочетается с --tag и --category)
+    #[arg(long, value_enum, conflicts_with_all = ["tag", "category"])]
+    order: Option<SortOrder>,
+    /// Обновлять список, пока команда не будет прервана (Ctrl+C)
+    #[arg(long)]
+    watch: bool,
+    /// Интервал опроса сервера в секундах для --watch
+    #[arg(long, default_value_t = 5, requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+    interval: u64,
+}
+
+/// Поле сортировки списка постов.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+#[value(rename_all = "snake_case")]
+enum SortField {
+    CreatedAt,
+    UpdatedAt,
+    Title,
+}
+
+/// Направление сортировки списка постов.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl From<SortField> for client::types::PostSortField {
+    fn from(field: SortField) -> Self {
+        match field {
+            SortField::CreatedAt => Self::CreatedAt,
+            SortField::UpdatedAt => Self::UpdatedAt,
+            SortField::Title => Self::Title,
+        }
+    }
+}
+
+impl From<SortOrder> for client::types::SortOrder {
+    fn from(order: SortOrder) -> Self {
+        match order {
+            SortOrder::Asc => Self::Asc,
+            SortOrder::Desc => Self::Desc,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+struct TrendingArgs {
+    /// Сколько постов показать
+    #[arg(long, default_value = "10")]
+    limit: u32,
+}
+
+#[derive(Parser, Debug)]
+struct ListUserPostsArgs {
+    /// ID автора
+    #[arg(short, long, required = true)]
+    user_id: String,
+    #[arg(long, default_value = "10")]
+    page_size: u32,
+    #[arg(long, default_value = "0")]
+    page: u32,
+}
+
+#[derive(Parser, Debug)]
+struct FeedArgs {
+    #[arg(long, default_value = "10")]
+    page_size: u32,
+    #[arg(long, default_value = "0")]
+    page: u32,
+}
+
+#[derive(Parser, Debug)]
+struct FollowArgs {
+    /// ID автора
+    #[arg(short, long, required = true)]
+    user_id: String,
+}
+
+#[derive(Parser, Debug)]
+struct UploadFileArgs {
+    /// Путь к файлу
+    #[arg(short, long, required = true)]
+    file: std::path::PathBuf,
+    /// MIME-тип файла (по умолчанию определяется по расширению)
+    #[arg(long)]
+    content_type: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct ExportArgs {
+    /// Формат файлов
+    #[arg(long, value_enum, default_value_t = ExportFormat::Markdown)]
+    format: ExportFormat,
+    /// Каталог для файлов (создаётся при необходимости)
+    #[arg(long, required = true)]
+    out: std::path::PathBuf,
+}
+
+#[derive(Parser, Debug)]
+struct ImportArgs {
+    /// Каталог с .json и .md файлами или один файл
+    path: std::path::PathBuf,
+    /// Что делать с постом, заголовок которого совпадает с уже существующим
+    #[arg(long, value_enum, default_value_t = OnConflict::Skip)]
+    on_conflict: OnConflict,
 }
 
-/// Загружает данные аутентификации из файла.
-fn load_auth_data() -> Result<client::types::AuthData, Box<dyn std::error::Error>> {
-    let json = std::fs::read_to_string(".blog_token")?;
-    let auth_data: client::types::AuthData = serde_json::from_str(&json)?;
-    Ok(auth_data)
+#[derive(Parser, Debug)]
+struct CompletionsArgs {
+    /// Оболочка
+    #[arg(value_enum)]
+    shell: clap_complete::Shell,
+}
+
+#[derive(Parser, Debug)]
+struct ManArgs {
+    /// Записать страницы CLI и всех команд в каталог вместо вывода основной страницы
+    #[arg(long)]
+    out_dir: Option<std::path::PathBuf>,
+}
+
+/// За сколько секунд до истечения токена whoami предупреждает об этом
+const TOKEN_EXPIRY_WARNING_SECONDS: i64 = 300;
+
+/// Определяет MIME-тип файла по расширению; сервер принимает только эти типы.
+fn content_type_for(path: &std::path::Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_lowercase();
+    let content_type = match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "pdf" => "application/pdf",
+        "txt" => "text/plain",
+        _ => return None,
+    };
+    Some(content_type)
+}
+
+/// Загружает страницу постов с учётом тега, категории и сортировки.
+async fn list_posts(
+    client: &dyn BlogClient,
+    args: &ListPostsArgs,
+) -> client::types::ClientResult<client::types::PostPage> {
+    match (&args.tag, &args.category) {
+        (Some(tag), _) => {
+            client
+                .list_posts_by_tag(tag, args.page_size, args.page)
+                .await
+        }
+        (None, Some(category)) => {
+            client
+                .list_posts_by_category(category, args.page_size, args.page)
+                .await
+        }
+        (None, None) => {
+            let sort = client::types::PostSort {
+                field: args.sort_by.map(Into::into).unwrap_or_default(),
+                order: args.order.map(Into::into).unwrap_or_default(),
+            };
+            client
+                .list_posts(args.page_size, args.page, Some(sort))
+                .await
+        }
+    }
+}
+
+/// Опрашивает сервер и печатает список заново, когда он изменился.
+///
+/// Ошибка запроса не прерывает наблюдение: сервер может быть временно недоступен.
+async fn watch_posts(client: &dyn BlogClient, args: &ListPostsArgs, output: OutputFormat) {
+    let interval = std::time::Duration::from_secs(args.interval);
+    // В JSON каждое изменение печатается отдельным документом, экран не очищается
+    let redraw = output != OutputFormat::Json && std::io::stdout().is_terminal();
+    let mut shown = None;
+    loop {
+        match list_posts(client, args).await {
+            Ok(page) if shown.as_ref() != Some(&page) => {
+                if redraw {
+                    print!("\x1b[2J\x1b[H");
+                }
+                if output != OutputFormat::Json {
+                    info!(
+                        "Every {}s, updated at {}. Press Ctrl+C to stop.",
+                        args.interval,
+                        chrono::Local::now().format("%H:%M:%S")
+                    );
+                }
+                output::post_page(output, &page);
+                shown = Some(page);
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("Failed to refresh posts: {}", e),
+        }
+        tokio::time::sleep(interval).await;
+    }
 }
 
-/// Сохраняет данные аутентификации в файл.
-fn save_auth_data(auth_data: &client::types::AuthData) -> Result<(), Box<dyn std::error::Error>> {
-    let json = serde_json::to_string(auth_data)?;
-    std::fs::write(".blog_token", json)?;
+/// Печатает профиль пользователя.
+fn print_profile(profile: &client::types::UserProfile) {
+    println!("User: {} ({})", profile.username, profile.id);
+    if let Some(display_name) = &profile.display_name {
+        println!("Display name: {}", display_name);
+    }
+    if let Some(avatar_url) = &profile.avatar_url {
+        println!("Avatar: {}", avatar_url);
+    }
+    println!("Member since: {}", profile.created_at.format("%Y-%m-%d"));
+    if let Some(bio) = &profile.bio {
+        println!("{}", bio);
+    }
+}
+
+fn print_digest_settings(settings: &client::types::DigestSettings) {
+    match settings.frequency {
+        Some(client::types::DigestFrequency::Daily) => println!("Digest: daily"),
+        Some(client::types::DigestFrequency::Weekly) => println!("Digest: weekly"),
+        None => println!("Digest: off"),
+    }
+    if let (Some(_), Some(last_sent_at)) = (settings.frequency, settings.last_sent_at) {
+        println!(
+            "Covers posts since: {}",
+            last_sent_at.format("%Y-%m-%d %H:%M UTC")
+        );
+    }
+}
+
+/// Хранит токены в хранилище учётных данных ОС; у каждого профиля своя запись.
+#[cfg(feature = "keyring")]
+fn with_keyring(
+    builder: BlogClientBuilder,
+    profile: &str,
+) -> Result<BlogClientBuilder, Box<dyn std::error::Error>> {
+    use client::token_store::{DEFAULT_KEYRING_SERVICE, KeyringTokenStore};
+    Ok(builder.token_store(KeyringTokenStore::new(DEFAULT_KEYRING_SERVICE, profile)))
+}
+
+#[cfg(not(feature = "keyring"))]
+fn with_keyring(
+    _builder: BlogClientBuilder,
+    _profile: &str,
+) -> Result<BlogClientBuilder, Box<dyn std::error::Error>> {
+    Err("CLI is built without keyring support, rebuild it with --features keyring".into())
+}
+
+/// Выполняет команду `config`.
+fn run_config(
+    config: &mut Config,
+    path: &std::path::Path,
+    profile: Option<&str>,
+    command: ConfigCommand,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let profile = config.profile_name(profile).to_string();
+    match command {
+        ConfigCommand::Get { key } => match config.get(&profile, key) {
+            Some(value) => println!("{}", value),
+            None => return Err(format!("{} is not set in profile {}", key, profile).into()),
+        },
+        ConfigCommand::Set { key, value } => {
+            config.set(&profile, key, value)?;
+            config.save(path)?;
+            info!("Saved to {}", path.display());
+        }
+    }
     Ok(())
 }
 
@@ -131,44 +722,201 @@ struct Args {
     #[arg(short, long)]
     use_grpc: bool,
 
-    /// URL сервера
-    #[arg(short, long, default_value = "http://localhost:8080")]
-    server: String,
+    /// URL сервера (по умолчанию из профиля или http://localhost:8080)
+    #[arg(short, long)]
+    server: Option<String>,
+
+    /// Профиль из файла настроек
+    #[arg(long)]
+    profile: Option<String>,
+
+    /// Файл настроек (по умолчанию ~/.config/blog-cli/config.toml)
+    #[arg(long)]
+    config: Option<std::path::PathBuf>,
+
+    /// Где хранить токены (по умолчанию из профиля или file)
+    #[arg(long, value_enum)]
+    token_store: Option<TokenStoreKind>,
+
+    /// Файл токенов для --token-store file (по умолчанию из профиля или .blog_token)
+    #[arg(long)]
+    token_file: Option<String>,
+
+    /// PEM-файл дополнительного доверенного CA для https:// сервера
+    #[arg(long)]
+    ca_cert: Option<String>,
+
+    /// Не проверять TLS-сертификат сервера (только для разработки)
+    #[arg(long)]
+    insecure: bool,
+
+    /// Таймаут запроса в секундах (по умолчанию 30)
+    #[arg(long)]
+    timeout: Option<u64>,
+
+    /// Не печатать информационные сообщения; результаты команд и ошибки печатаются
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Формат вывода list-posts, list-user-posts, get-post, login и whoami
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Plain)]
+    output: OutputFormat,
 
     /// Команда для выполнения
     #[command(subcommand)]
     command: Command,
 }
 
+/// Коды завершения: скрипт может отличить причину ошибки, не разбирая сообщение.
+///
+/// Код 2 занимают ошибки разбора аргументов clap.
+mod exit_code {
+    /// Прочие ошибки
+    pub const FAILURE: u8 = 1;
+    /// Нужен вход, доступ запрещён или вход заблокирован
+    pub const AUTH: u8 = 3;
+    /// Пост, пользователь или сеанс не найден
+    pub const NOT_FOUND: u8 = 4;
+    /// Сервер или клиент отклонил данные запроса
+    pub const VALIDATION: u8 = 5;
+    /// Сервер недоступен или не ответил вовремя
+    pub const TRANSPORT: u8 = 6;
+}
+
+/// Код завершения для ошибки команды
+fn exit_code_for(error: &(dyn std::error::Error + 'static)) -> u8 {
+    match error.downcast_ref::<ClientError>() {
+        Some(
+            ClientError::Unauthorized
+            | ClientError::Forbidden(_)
+            | ClientError::AccountLocked { .. }
+            | ClientError::TwoFactorRequired { .. },
+        ) => exit_code::AUTH,
+        Some(ClientError::NotFound) => exit_code::NOT_FOUND,
+        Some(ClientError::InvalidRequest(_) | ClientError::Validation { .. }) => {
+            exit_code::VALIDATION
+        }
+        Some(ClientError::TransportError(_) | ClientError::Timeout) => exit_code::TRANSPORT,
+        _ => exit_code::FAILURE,
+    }
+}
+
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() -> ExitCode {
     let args = Args::parse();
+    output::set_quiet(args.quiet);
 
-    let client: Box<dyn client::blog_client::BlogClient> = if args.use_grpc {
-        Box::new(
-            client::grpc_client::GrpcClient::new(args.server.clone())
-                .await
-                .expect("Failed to create gRPC client"),
-        )
+    // Ошибки печатаются в stderr и с --quiet: stdout остаётся для результатов
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(exit_code_for(e.as_ref()))
+        }
+    }
+}
+
+async fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    // Файлы для установки не зависят от настроек и сервера
+    let command = match args.command {
+        Command::Completions(args) => {
+            let mut command = Args::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(args.shell, &mut command, name, &mut std::io::stdout());
+            return Ok(());
+        }
+        Command::Man(args) => {
+            match args.out_dir {
+                Some(dir) => {
+                    std::fs::create_dir_all(&dir)?;
+                    clap_mangen::generate_to(Args::command(), &dir)?;
+                }
+                None => clap_mangen::Man::new(Args::command()).render(&mut std::io::stdout())?,
+            }
+            return Ok(());
+        }
+        command => command,
+    };
+
+    let config_path = args
+        .config
+        .clone()
+        .or_else(Config::default_path)
+        .ok_or("Cannot determine config directory, pass --config")?;
+    let mut config = Config::load(&config_path)?;
+
+    // Настройки не требуют подключения к серверу
+    let command = match command {
+        Command::Config(command) => {
+            return run_config(&mut config, &config_path, args.profile.as_deref(), command);
+        }
+        command => command,
+    };
+
+    // Флаги командной строки важнее настроек профиля
+    let profile = config.profile(args.profile.as_deref())?;
+    let use_grpc = args.use_grpc || profile.transport == Some(TransportKind::Grpc);
+    let server = args
+        .server
+        .or(profile.server)
+        .unwrap_or_else(|| "http://localhost:8080".to_string());
+    let token_store = args.token_store.or(profile.token_store).unwrap_or_default();
+    let token_file = args
+        .token_file
+        .or(profile.token_file)
+        .unwrap_or_else(|| DEFAULT_TOKEN_FILE.to_string());
+    // Файл токенов для сообщений; None - токены в хранилище учётных данных ОС
+    let shown_token_file = (token_store == TokenStoreKind::File).then_some(token_file.as_str());
+
+    // TLS нужен для https:// адреса; HTTP клиенту reqwest включает его и сам
+    let tls = if server.starts_with("https://") || args.ca_cert.is_some() || args.insecure {
+        let mut tls = match &args.ca_cert {
+            Some(path) => client::tls::TlsOptions::with_ca_certificate_file(path)?,
+            None => client::tls::TlsOptions::default(),
+        };
+        tls.danger_accept_invalid_certs = args.insecure;
+        Some(tls)
     } else {
-        Box::new(
-            client::http_client::HttpClient::new(args.server.clone())
-                .await
-                .expect("Failed to create HTTP client"),
-        )
+        None
     };
 
-    println!(
-        "Client initialized using {} transport",
-        if args.use_grpc { "gRPC" } else { "HTTP" }
-    );
+    let transport = if use_grpc {
+        Transport::Grpc(server)
+    } else {
+        Transport::Http(server)
+    };
+    // Токены сохраняются, чтобы вход сохранялся между запусками
+    let builder = BlogClientBuilder::new(transport);
+    let mut builder = match token_store {
+        TokenStoreKind::File => builder.token_store(FileTokenStore::new(&token_file)),
+        TokenStoreKind::Keyring => {
+            with_keyring(builder, config.profile_name(args.profile.as_deref()))?
+        }
+    };
+    if let Some(tls) = tls {
+        builder = builder.tls(tls);
+    }
+    if let Some(seconds) = args.timeout {
+        builder = builder.request_timeout(Some(std::time::Duration::from_secs(seconds)));
+    }
+    // Общий клиент всех вызовов команды; клоны Arc можно передавать в задачи
+    let client: Arc<dyn BlogClient> = Arc::from(builder.build().await?);
 
-    match args.command {
+    let output = args.output;
+    // В stdout при JSON выводе должен попасть только сам JSON
+    if output != OutputFormat::Json {
+        info!(
+            "Client initialized using {} transport",
+            if use_grpc { "gRPC" } else { "HTTP" }
+        );
+    }
+
+    match command {
         Command::Register(args) => {
             client
                 .register(&args.username, &args.email, &args.password)
                 .await?;
-            println!("User registered: {}", args.username);
+            info!("User registered: {}", args.username);
         }
         Command::Login(args) => {
             let password = if let Some(pwd) = args.password {
@@ -177,50 +925,279 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 rpassword::prompt_password("Password: ")?
             };
 
-            client.login(&args.username, &password).await?;
-            println!("User logged in: {}", args.username);
-            if let Ok(Some(auth_data)) = client.get_auth_data().await {
-                save_auth_data(&auth_data)?;
-                println!("Tokens saved to .blog_token file");
+            let user_id = match args.totp {
+                Some(code) => {
+                    client
+                        .login_with_totp(&args.username, &password, &code)
+                        .await?
+                }
+                None => match client.login(&args.username, &password).await {
+                    Err(ClientError::TwoFactorRequired { .. }) => {
+                        let code = rpassword::prompt_password("Authentication code: ")?;
+                        client
+                            .login_with_totp(&args.username, &password, code.trim())
+                            .await?
+                    }
+                    result => result?,
+                },
+            };
+            output::login(output, &args.username, user_id, shown_token_file);
+        }
+        Command::Whoami => {
+            let claims = client
+                .get_auth_data()
+                .await?
+                .ok_or("Not logged in, run login first")?
+                .claims()?;
+            output::whoami(output, &claims);
+
+            // Предупреждения в stderr, чтобы не портить JSON вывод
+            let expires_in = claims.exp - chrono::Utc::now().timestamp();
+            if expires_in <= 0 {
+                eprintln!(
+                    "Warning: access token has expired, it will be refreshed on the next request"
+                );
+            } else if claims.expires_soon(TOKEN_EXPIRY_WARNING_SECONDS) {
+                eprintln!("Warning: access token expires in {} seconds", expires_in);
             }
         }
-        Command::CreatePost(args) => {
-            let auth_data = load_auth_data()?;
-            client.setup_auth_data(&auth_data).await?;
+        Command::Logout => {
+            // Клиент отзывает refresh токен на сервере и удаляет сохранённые токены
+            client.logout().await?;
+            match shown_token_file {
+                Some(token_file) => info!("Logged out, {} removed", token_file),
+                None => info!("Logged out, tokens removed from system keyring"),
+            }
+        }
+        Command::LogoutAll => {
+            // Клиент удаляет сохранённые токены: они больше недействительны
+            client.logout_all_sessions().await?;
+            info!("Logged out from all devices");
+        }
+        Command::ListSessions => {
+            let sessions = client.list_sessions().await?;
+            for session in &sessions {
+                let marker = if session.current { " (current)" } else { "" };
+                println!("{}{}", session.id, marker);
+                println!(
+                    "  Device: {}",
+                    session.user_agent.as_deref().unwrap_or("unknown")
+                );
+                println!(
+                    "  IP: {}",
+                    session.ip_address.as_deref().unwrap_or("unknown")
+                );
+                println!(
+                    "  Signed in: {}, last used: {}",
+                    session.created_at.format("%Y-%m-%d %H:%M"),
+                    session.last_used_at.format("%Y-%m-%d %H:%M")
+                );
+            }
+            println!("Total sessions: {}", sessions.len());
+        }
+        Command::RevokeSession(args) => {
+            client.revoke_session(&args.uuid).await?;
+            info!("Session {} revoked", args.uuid);
+        }
+        Command::ForgotPassword(args) => {
+            client.forgot_password(&args.email).await?;
+            info!(
+                "If {} is registered, a password reset token has been sent",
+                args.email
+            );
+        }
+        Command::ResetPassword(args) => {
+            let password = if let Some(pwd) = args.password {
+                pwd
+            } else {
+                rpassword::prompt_password("New password: ")?
+            };
 
-            client.create_post(&args.title, &args.content).await?;
-            println!("Post created: {}", args.title);
+            client.reset_password(&args.token, &password).await?;
+            info!("Password has been reset, you can log in now");
+        }
+        Command::GetProfile(args) => {
+            let profile = client.get_user_profile(&args.uuid).await?;
+            print_profile(&profile);
+        }
+        Command::UpdateProfile(args) => {
+            let profile = client
+                .update_my_profile(
+                    args.display_name.as_deref(),
+                    args.bio.as_deref(),
+                    args.avatar_url.as_deref(),
+                )
+                .await?;
+            info!("Profile updated");
+            print_profile(&profile);
+        }
+        Command::Digest(args) => {
+            let settings = match args.frequency {
+                Some(frequency) => {
+                    let settings = client.update_digest_settings(frequency.into()).await?;
+                    info!("Digest settings updated");
+                    settings
+                }
+                None => client.get_digest_settings().await?,
+            };
+            print_digest_settings(&settings);
+        }
+        Command::CreatePost(args) => {
+            let content = args.content.read(None)?;
+            if args.draft {
+                client
+                    .create_draft(
+                        &args.title,
+                        &content,
+                        &args.tags,
+                        args.category.as_deref(),
+                        args.summary.as_deref(),
+                    )
+                    .await?;
+                info!("Draft created: {}", args.title);
+            } else {
+                client
+                    .create_post(
+                        &args.title,
+                        &content,
+                        &args.tags,
+                        args.category.as_deref(),
+                        args.summary.as_deref(),
+                    )
+                    .await?;
+                info!("Post created: {}", args.title);
+            }
         }
         Command::GetPost(args) => {
+            // С сохранённым токеном автор видит и свои черновики
             let post = client.get_post(&args.uuid).await?;
-            println!("Post retrieved: {}", post.title);
-            println!("{}", post.content);
+            output::post(output, &post);
         }
         Command::UpdatePost(args) => {
-            let auth_data = load_auth_data()?;
-            client.setup_auth_data(&auth_data).await?;
+            let current = if args.tags.is_empty() || args.content.uses_editor() {
+                Some(client.get_post(&args.uuid).await?)
+            } else {
+                None
+            };
+            let content = args
+                .content
+                .read(current.as_ref().map(|post| post.content.as_str()))?;
+            // Без --tags сохраняем текущие теги поста
+            let tags = match current {
+                Some(post) if args.tags.is_empty() => post.tags,
+                _ => args.tags,
+            };
 
             client
-                .update_post(&args.uuid, &args.title, &args.content)
+                .update_post(
+                    &args.uuid,
+                    &args.title,
+                    &content,
+                    &tags,
+                    args.category.as_deref(),
+                    args.summary.as_deref(),
+                )
                 .await?;
-            println!("Post updated: {}", args.uuid);
+            info!("Post updated: {}", args.uuid);
         }
         Command::DeletePost(args) => {
-            let auth_data = load_auth_data()?;
-            client.setup_auth_data(&auth_data).await?;
-
             client.delete_post(&args.uuid).await?;
-            println!("Post deleted: {}", args.uuid);
+            info!("Post moved to trash: {}", args.uuid);
+        }
+        Command::DeletePosts(args) => {
+            let count = args.uuids.len();
+            client.delete_posts(args.uuids).await?;
+            info!("Posts moved to trash: {}", count);
+        }
+        Command::RestorePost(args) => {
+            client.restore_post(&args.uuid).await?;
+            info!("Post restored: {}", args.uuid);
+        }
+        Command::PurgePost(args) => {
+            client.purge_post(&args.uuid).await?;
+            info!("Post purged: {}", args.uuid);
+        }
+        Command::PublishPost(args) => {
+            client.publish_post(&args.uuid).await?;
+            info!("Post published: {}", args.uuid);
+        }
+        Command::UnpublishPost(args) => {
+            client.unpublish_post(&args.uuid).await?;
+            info!("Post moved to drafts: {}", args.uuid);
+        }
+        Command::LikePost(args) => {
+            let likes = client.like_post(&args.uuid).await?;
+            info!("Post liked: {} ({} likes)", args.uuid, likes);
+        }
+        Command::UnlikePost(args) => {
+            let likes = client.unlike_post(&args.uuid).await?;
+            info!("Like removed: {} ({} likes)", args.uuid, likes);
+        }
+        Command::ReportPost(args) => {
+            let report = client
+                .report_post(&args.uuid, args.reason.into(), args.details.as_deref())
+                .await?;
+            info!("Post reported: {} (report {})", args.uuid, report.id);
+        }
+        Command::ListPosts(args) if args.watch => {
+            watch_posts(client.as_ref(), &args, output).await;
         }
         Command::ListPosts(args) => {
-            let auth_data = load_auth_data()?;
-            client.setup_auth_data(&auth_data).await?;
+            let page = list_posts(client.as_ref(), &args).await?;
+            output::post_page(output, &page);
+        }
+        Command::ListUserPosts(args) => {
+            let page = client
+                .list_posts_by_author(&args.user_id, args.page_size, args.page)
+                .await?;
+            output::post_page(output, &page);
+        }
+        Command::ListCategories => {
+            let categories = client.list_categories().await?;
+            output::categories(output, &categories);
+        }
+        Command::Trending(args) => {
+            let posts = client.list_trending_posts(args.limit).await?;
+            output::trending_posts(output, &posts);
+        }
+        Command::Feed(args) => {
+            let page = client.get_feed(args.page_size, args.page).await?;
+            output::post_page(output, &page);
+        }
+        Command::Follow(args) => {
+            client.follow_user(&args.user_id).await?;
+            info!("Following user: {}", args.user_id);
+        }
+        Command::Unfollow(args) => {
+            client.unfollow_user(&args.user_id).await?;
+            info!("Unfollowed user: {}", args.user_id);
+        }
+        Command::UploadFile(args) => {
+            let content_type = match args.content_type {
+                Some(content_type) => content_type,
+                None => content_type_for(&args.file)
+                    .ok_or("Unknown file type, pass --content-type")?
+                    .to_string(),
+            };
+            let file_name = args
+                .file
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("file")
+                .to_string();
+            let data = std::fs::read(&args.file)?;
 
-            let posts = client.list_posts(args.page_size, args.page).await?;
-            println!("Posts (page {}, size {}):", args.page, args.page_size);
-            for post in posts {
-                println!("  - {}: {}", post.id, post.title);
-            }
+            let url = client.upload_file(&file_name, &content_type, data).await?;
+            println!("File uploaded: {}", url);
+        }
+        Command::Export(args) => {
+            transfer::export(client.as_ref(), args.format, &args.out).await?;
+        }
+        Command::Import(args) => {
+            transfer::import(client.as_ref(), &args.path, args.on_conflict).await?;
+        }
+        Command::Config(_) | Command::Completions(_) | Command::Man(_) => {
+            unreachable!("handled before client initialization")
         }
     }
 
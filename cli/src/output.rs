@@ -0,0 +1,389 @@
+//! Вывод результатов команд в выбранном формате.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use client::Claims;
+use client::types::{Category, Post, PostPage, PostStatus};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+/// Информационные сообщения отключены флагом `--quiet`
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Печатает информационное сообщение, если не задан `--quiet`.
+///
+/// Результаты команд (списки, посты, JSON) печатаются всегда.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+pub(crate) use info;
+
+/// Отключает информационные сообщения
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Формат вывода результатов.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Текстовые строки для чтения человеком
+    #[default]
+    Plain,
+    /// Таблица с выровненными колонками
+    Table,
+    /// JSON для скриптов
+    Json,
+}
+
+/// Печатает результат входа.
+///
+/// `token_file` равен `None`, если токены сохранены в хранилище учётных данных ОС.
+pub fn login(format: OutputFormat, username: &str, user_id: Uuid, token_file: Option<&str>) {
+    match format {
+        OutputFormat::Plain => {
+            info!("User logged in: {}", username);
+            match token_file {
+                Some(token_file) => info!("Tokens saved to {} file", token_file),
+                None => info!("Tokens saved to system keyring"),
+            }
+        }
+        OutputFormat::Table => {
+            let mut table = Table::new(["Field", "Value"]);
+            table.row(["Username".to_string(), username.to_string()]);
+            table.row(["User ID".to_string(), user_id.to_string()]);
+            match token_file {
+                Some(token_file) => table.row(["Token file".to_string(), token_file.to_string()]),
+                None => table.row(["Token store".to_string(), "system keyring".to_string()]),
+            }
+            print!("{}", table);
+        }
+        OutputFormat::Json => print_json(&json!({
+            "username": username,
+            "user_id": user_id.to_string(),
+            "token_file": token_file,
+        })),
+    }
+}
+
+/// Печатает данные сохранённого токена.
+pub fn whoami(format: OutputFormat, claims: &Claims) {
+    let issued_at = claims.iat.and_then(|iat| DateTime::from_timestamp(iat, 0));
+    let expires_at = DateTime::from_timestamp(claims.exp, 0);
+    match format {
+        OutputFormat::Plain => {
+            println!("User: {} ({})", claims.user_name, claims.sub);
+            if let Some(issued_at) = issued_at {
+                println!("Issued at: {}", timestamp(issued_at));
+            }
+            if let Some(expires_at) = expires_at {
+                println!("Expires at: {}", timestamp(expires_at));
+            }
+        }
+        OutputFormat::Table => {
+            let mut table = Table::new(["Field", "Value"]);
+            table.row(["Username".to_string(), claims.user_name.clone()]);
+            table.row(["User ID".to_string(), claims.sub.clone()]);
+            if let Some(issued_at) = issued_at {
+                table.row(["Issued at".to_string(), timestamp(issued_at)]);
+            }
+            if let Some(expires_at) = expires_at {
+                table.row(["Expires at".to_string(), timestamp(expires_at)]);
+            }
+            print!("{}", table);
+        }
+        OutputFormat::Json => print_json(&json!({
+            "username": claims.user_name,
+            "user_id": claims.sub,
+            "issued_at": issued_at.map(|time| time.to_rfc3339()),
+            "expires_at": expires_at.map(|time| time.to_rfc3339()),
+        })),
+    }
+}
+
+/// Печатает пост вместе с содержимым.
+pub fn post(format: OutputFormat, post: &Post) {
+    match format {
+        OutputFormat::Plain => {
+            println!("Post retrieved: {}", post.title);
+            if post.status == PostStatus::Draft {
+                println!("Status: draft");
+            }
+            let liked = if post.liked_by_me {
+                " (including you)"
+            } else {
+                ""
+            };
+            println!("Likes: {}{}", post.like_count, liked);
+            if !post.tags.is_empty() {
+                println!("Tags: {}", post.tags.join(", "));
+            }
+            if let Some(category_id) = post.category_id {
+                println!("Category: {}", category_id);
+            }
+            for url in &post.attachments {
+                println!("Attachment: {}", url);
+            }
+            println!("{}", post.content);
+        }
+        OutputFormat::Table => {
+            let mut table = Table::new(["Field", "Value"]);
+            table.row(["ID".to_string(), post.id.to_string()]);
+            table.row(["Title".to_string(), post.title.clone()]);
+            table.row(["Summary".to_string(), post.summary.clone()]);
+            table.row(["Author".to_string(), post.author_username.clone()]);
+            table.row(["Status".to_string(), status(post.status).to_string()]);
+            table.row(["Likes".to_string(), post.like_count.to_string()]);
+            table.row(["Tags".to_string(), post.tags.join(", ")]);
+            if let Some(category_id) = post.category_id {
+                table.row(["Category".to_string(), category_id.to_string()]);
+            }
+            for url in &post.attachments {
+                table.row(["Attachment".to_string(), url.clone()]);
+            }
+            table.row(["Created".to_string(), timestamp(post.created_at)]);
+            table.row(["Updated".to_string(), timestamp(post.updated_at)]);
+            print!("{}", table);
+            // Многострочное содержимое не помещается в ячейку
+            println!();
+            println!("{}", post.content);
+        }
+        OutputFormat::Json => print_json(&post_json(post)),
+    }
+}
+
+/// Печатает страницу постов.
+pub fn post_page(format: OutputFormat, page: &PostPage) {
+    match format {
+        OutputFormat::Plain => {
+            println!(
+                "Posts (page {}, size {}, total {}):",
+                page.page, page.page_size, page.total
+            );
+            for post in &page.posts {
+                let draft = if post.status == PostStatus::Draft {
+                    " [draft]"
+                } else {
+                    ""
+                };
+                println!(
+                    "  - {}: {} (by {}, {} likes){}",
+                    post.id, post.title, post.author_username, post.like_count, draft
+                );
+                if !post.summary.is_empty() {
+                    println!("    {}", post.summary);
+                }
+            }
+            if page.has_next {
+                println!("More posts on page {}", page.page + 1);
+            }
+        }
+        OutputFormat::Table => {
+            let mut table = Table::new(["ID", "Title", "Author", "Status", "Likes", "Created"]);
+            for post in &page.posts {
+                table.row([
+                    post.id.to_string(),
+                    post.title.clone(),
+                    post.author_username.clone(),
+                    status(post.status).to_string(),
+                    post.like_count.to_string(),
+                    timestamp(post.created_at),
+                ]);
+            }
+            print!("{}", table);
+            println!(
+                "Page {} of size {}, total {}{}",
+                page.page,
+                page.page_size,
+                page.total,
+                if page.has_next {
+                    ", more on next page"
+                } else {
+                    ""
+                }
+            );
+        }
+        OutputFormat::Json => print_json(&json!({
+            "posts": page.posts.iter().map(post_json).collect::<Vec<_>>(),
+            "total": page.total,
+            "page": page.page,
+            "page_size": page.page_size,
+            "has_next": page.has_next,
+        })),
+    }
+}
+
+/// Печатает популярные посты.
+pub fn trending_posts(format: OutputFormat, posts: &[Post]) {
+    match format {
+        OutputFormat::Plain => {
+            println!("Trending posts:");
+            for (rank, post) in posts.iter().enumerate() {
+                println!(
+                    "  {}. {}: {} (by {}, {} likes)",
+                    rank + 1,
+                    post.id,
+                    post.title,
+                    post.author_username,
+                    post.like_count
+                );
+            }
+        }
+        OutputFormat::Table => {
+            let mut table = Table::new(["#", "ID", "Title", "Author", "Likes", "Created"]);
+            for (rank, post) in posts.iter().enumerate() {
+                table.row([
+                    (rank + 1).to_string(),
+                    post.id.to_string(),
+                    post.title.clone(),
+                    post.author_username.clone(),
+                    post.like_count.to_string(),
+                    timestamp(post.created_at),
+                ]);
+            }
+            print!("{}", table);
+        }
+        OutputFormat::Json => print_json(&json!({
+            "posts": posts.iter().map(post_json).collect::<Vec<_>>(),
+        })),
+    }
+}
+
+/// Печатает категории постов; в текстовом виде - деревом с отступами.
+pub fn categories(format: OutputFormat, categories: &[Category]) {
+    match format {
+        OutputFormat::Plain => {
+            println!("Categories:");
+            print_category_tree(categories, None, 1);
+        }
+        OutputFormat::Table => {
+            let mut table = Table::new(["ID", "Name", "Parent", "Created"]);
+            for category in categories {
+                table.row([
+                    category.id.to_string(),
+                    category.name.clone(),
+                    category
+                        .parent_id
+                        .map(|id| id.to_string())
+                        .unwrap_or_default(),
+                    timestamp(category.created_at),
+                ]);
+            }
+            print!("{}", table);
+        }
+        OutputFormat::Json => print_json(&json!({
+            "categories": categories
+                .iter()
+                .map(|category| json!({
+                    "id": category.id.to_string(),
+                    "name": category.name,
+                    "parent_id": category.parent_id.map(|id| id.to_string()),
+                    "created_at": category.created_at.to_rfc3339(),
+                }))
+                .collect::<Vec<_>>(),
+        })),
+    }
+}
+
+/// Печатает подкатегории `parent` (без родителя - категории верхнего уровня) с отступом `depth`
+fn print_category_tree(categories: &[Category], parent: Option<Uuid>, depth: usize) {
+    for category in categories.iter().filter(|c| c.parent_id == parent) {
+        println!("{}- {}: {}", "  ".repeat(depth), category.id, category.name);
+        print_category_tree(categories, Some(category.id), depth + 1);
+    }
+}
+
+fn post_json(post: &Post) -> Value {
+    json!({
+        "id": post.id.to_string(),
+        "title": post.title,
+        "content": post.content,
+        "summary": post.summary,
+        "author_id": post.author_id.to_string(),
+        "author_username": post.author_username,
+        "tags": post.tags,
+        "category_id": post.category_id.map(|id| id.to_string()),
+        "attachments": post.attachments,
+        "status": status(post.status),
+        "like_count": post.like_count,
+        "liked_by_me": post.liked_by_me,
+        "created_at": post.created_at.to_rfc3339(),
+        "updated_at": post.updated_at.to_rfc3339(),
+    })
+}
+
+fn status(status: PostStatus) -> &'static str {
+    match status {
+        PostStatus::Draft => "draft",
+        PostStatus::Published => "published",
+    }
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M").to_string()
+}
+
+fn print_json(value: &Value) {
+    // Value всегда сериализуется без ошибок
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    );
+}
+
+/// Таблица с колонками, выровненными по самому длинному значению.
+struct Table<const N: usize> {
+    headers: [&'static str; N],
+    rows: Vec<[String; N]>,
+}
+
+impl<const N: usize> Table<N> {
+    fn new(headers: [&'static str; N]) -> Self {
+        Self {
+            headers,
+            rows: Vec::new(),
+        }
+    }
+
+    fn row(&mut self, cells: [String; N]) {
+        self.rows.push(cells);
+    }
+}
+
+impl<const N: usize> fmt::Display for Table<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut widths = self.headers.map(|header| header.chars().count());
+        for row in &self.rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let line = |f: &mut fmt::Formatter<'_>, cells: &[&str]| -> fmt::Result {
+            let cells: Vec<String> = cells
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:<width$}", cell))
+                .collect();
+            writeln!(f, "{}", cells.join("  ").trim_end())
+        };
+
+        line(f, &self.headers)?;
+        let separators = widths.map(|width| "-".repeat(width));
+        line(f, &separators.each_ref().map(String::as_str))?;
+        for row in &self.rows {
+            line(f, &row.each_ref().map(String::as_str))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,270 @@
+//! Экспорт постов пользователя в файлы и импорт постов из файлов.
+//!
+//! Каждый пост хранится в отдельном файле `<id>.json` или `<id>.md`. В Markdown
+//! файле метаданные записываются в TOML заголовке между строками `+++`, а
+//! содержимое поста следует после него.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use client::blog_client::BlogClient;
+use client::types::{Post, PostStatus};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::output::{self, info};
+
+/// Размер страницы при загрузке постов (максимум, который принимает сервер)
+const PAGE_SIZE: u32 = 100;
+/// Граница TOML заголовка Markdown файла
+const FRONT_MATTER_DELIMITER: &str = "+++";
+
+type TransferResult<T> = Result<T, Box<dyn Error>>;
+
+/// Формат экспортированных файлов.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ExportFormat {
+    /// JSON файл с метаданными и содержимым
+    Json,
+    /// Markdown файл с TOML заголовком
+    Markdown,
+}
+
+/// Что делать с импортируемым постом, если у пользователя уже есть пост с таким заголовком.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum OnConflict {
+    /// Оставить существующий пост без изменений
+    Skip,
+    /// Заменить содержимое, теги и статус существующего поста
+    Overwrite,
+}
+
+/// Пост в экспортированном файле.
+#[derive(Serialize, Deserialize, Debug)]
+struct ExportedPost {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    title: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    updated_at: Option<String>,
+    /// В Markdown файле содержимое пишется после заголовка, а не в нём
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    content: String,
+}
+
+impl From<Post> for ExportedPost {
+    fn from(post: Post) -> Self {
+        Self {
+            id: Some(post.id.to_string()),
+            title: post.title,
+            tags: post.tags,
+            draft: post.status == PostStatus::Draft,
+            created_at: Some(post.created_at.to_rfc3339()),
+            updated_at: Some(post.updated_at.to_rfc3339()),
+            content: post.content,
+        }
+    }
+}
+
+impl ExportedPost {
+    fn into_markdown(mut self) -> TransferResult<String> {
+        let content = std::mem::take(&mut self.content);
+        Ok(format!(
+            "{delimiter}\n{}{delimiter}\n\n{}\n",
+            toml::to_string(&self)?,
+            content,
+            delimiter = FRONT_MATTER_DELIMITER
+        ))
+    }
+
+    fn from_markdown(text: &str) -> TransferResult<Self> {
+        let (front_matter, content) = text
+            .strip_prefix(FRONT_MATTER_DELIMITER)
+            .and_then(|rest| rest.split_once(&format!("\n{}", FRONT_MATTER_DELIMITER)))
+            .ok_or("Missing +++ front matter")?;
+        let mut post: Self = toml::from_str(front_matter)?;
+        post.content = content.trim_start_matches(['\r', '\n']).to_string();
+        Ok(post)
+    }
+
+    fn read(path: &Path) -> TransferResult<Self> {
+        let text = fs::read_to_string(path)?;
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => Ok(serde_json::from_str(&text)?),
+            _ => Self::from_markdown(&text),
+        }
+    }
+}
+
+/// Сохраняет все посты текущего пользователя, включая черновики, в каталог `out`.
+pub async fn export(
+    client: &dyn BlogClient,
+    format: ExportFormat,
+    out: &Path,
+) -> TransferResult<()> {
+    let posts = own_posts(client).await?;
+    fs::create_dir_all(out)?;
+
+    let count = posts.len();
+    let progress = progress_bar(count, "Exporting")?;
+    for post in posts {
+        let id = post.id;
+        let post = ExportedPost::from(post);
+        let (extension, data) = match format {
+            ExportFormat::Json => ("json", serde_json::to_string_pretty(&post)? + "\n"),
+            ExportFormat::Markdown => ("md", post.into_markdown()?),
+        };
+        fs::write(out.join(format!("{}.{}", id, extension)), data)?;
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+
+    info!("Exported {} posts to {}", count, out.display());
+    Ok(())
+}
+
+/// Создаёт посты из файла или из всех `.json` и `.md` файлов каталога.
+///
+/// Файлы сначала читаются целиком, чтобы ошибка формата не оставила импорт
+/// выполненным наполовину. Ошибки сервера по отдельным постам не прерывают импорт.
+pub async fn import(
+    client: &dyn BlogClient,
+    path: &Path,
+    on_conflict: OnConflict,
+) -> TransferResult<()> {
+    let posts = post_files(path)?
+        .into_iter()
+        .map(|file| match ExportedPost::read(&file) {
+            Ok(post) => Ok((file, post)),
+            Err(e) => Err(format!("Failed to read {}: {}", file.display(), e)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut existing: HashMap<String, Uuid> = own_posts(client)
+        .await?
+        .into_iter()
+        .map(|post| (post.title, post.id))
+        .collect();
+
+    let (mut created, mut updated, mut skipped, mut failed) = (0, 0, 0, 0);
+    let progress = progress_bar(posts.len(), "Importing")?;
+    for (file, post) in posts {
+        let result = match existing.get(&post.title) {
+            Some(_) if matches!(on_conflict, OnConflict::Skip) => {
+                skipped += 1;
+                Ok(())
+            }
+            Some(&id) => overwrite(client, id, &post).await.map(|()| updated += 1),
+            None => {
+                let id = if post.draft {
+                    client
+                        .create_draft(&post.title, &post.content, &post.tags, None, None)
+                        .await
+                } else {
+                    client
+                        .create_post(&post.title, &post.content, &post.tags, None, None)
+                        .await
+                };
+                // Повторяющиеся заголовки внутри импорта тоже считаются конфликтом
+                id.map(|id| {
+                    existing.insert(post.title.clone(), id);
+                    created += 1;
+                })
+            }
+        };
+        if let Err(e) = result {
+            failed += 1;
+            progress.suspend(|| eprintln!("Failed to import {}: {}", file.display(), e));
+        }
+        progress.inc(1);
+    }
+    progress.finish_and_clear();
+
+    info!(
+        "Imported posts: {} created, {} overwritten, {} skipped, {} failed",
+        created, updated, skipped, failed
+    );
+    if failed > 0 {
+        return Err(format!("{} posts failed to import", failed).into());
+    }
+    Ok(())
+}
+
+async fn overwrite(
+    client: &dyn BlogClient,
+    id: Uuid,
+    post: &ExportedPost,
+) -> client::types::ClientResult<()> {
+    let id = id.to_string();
+    client
+        .update_post(&id, &post.title, &post.content, &post.tags, None, None)
+        .await?;
+    let current = client.get_post(&id).await?;
+    match (post.draft, current.status) {
+        (true, PostStatus::Published) => client.unpublish_post(&id).await,
+        (false, PostStatus::Draft) => client.publish_post(&id).await,
+        _ => Ok(()),
+    }
+}
+
+/// Все посты текущего пользователя, постранично
+async fn own_posts(client: &dyn BlogClient) -> TransferResult<Vec<Post>> {
+    let user_id = client
+        .get_auth_data()
+        .await?
+        .and_then(|auth| auth.user_id())
+        .ok_or("Not logged in, run login first")?
+        .to_string();
+
+    let mut posts = Vec::new();
+    let mut page = 0;
+    loop {
+        let result = client
+            .list_posts_by_author(&user_id, PAGE_SIZE, page)
+            .await?;
+        posts.extend(result.posts);
+        if !result.has_next {
+            return Ok(posts);
+        }
+        page += 1;
+    }
+}
+
+/// Файл или `.json` и `.md` файлы каталога в порядке имён
+fn post_files(path: &Path) -> TransferResult<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(path)? {
+        let file = entry?.path();
+        let extension = file.extension().and_then(|extension| extension.to_str());
+        if matches!(extension, Some("json" | "md")) {
+            files.push(file);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Индикатор выполнения в stderr; без терминала и с `--quiet` он не выводится
+fn progress_bar(len: usize, message: &'static str) -> TransferResult<ProgressBar> {
+    if output::is_quiet() {
+        return Ok(ProgressBar::hidden());
+    }
+    let progress = ProgressBar::new(len as u64).with_message(message);
+    progress.set_style(
+        ProgressStyle::with_template("{msg} [{bar:40}] {pos}/{len}")?.progress_chars("=> "),
+    );
+    Ok(progress)
+}
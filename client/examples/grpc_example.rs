@@ -30,6 +30,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .create_post(
             "My First gRPC Post",
             "This is the content of my first gRPC post!",
+            &["rust".to_string(), "grpc".to_string()],
+            None,
+            Some("A short introduction"),
         )
         .await?;
     println!("Пост создан с ID: {}", post_id);
@@ -40,6 +43,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Пост получен:");
     println!("  Title: {}", post.title);
     println!("  Content: {}", post.content);
+    println!("  Summary: {}", post.summary);
     println!("  Created at: {}", post.created_at);
 
     // Обновление поста
@@ -49,6 +53,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             &post_id.to_string(),
             "Updated gRPC Title",
             "Updated content via gRPC!",
+            &["rust".to_string()],
+            None,
+            None,
         )
         .await?;
     println!("Пост обновлен");
@@ -62,9 +69,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Список постов
     println!("\nПолучение списка постов...");
-    let posts = client.list_posts(10, 0).await?;
-    println!("Найдено постов: {}", posts.len());
-    for (i, post) in posts.iter().enumerate() {
+    let page = client.list_posts(10, 0, None).await?;
+    println!(
+        "Найдено постов: {} (всего {})",
+        page.posts.len(),
+        page.total
+    );
+    for (i, post) in page.posts.iter().enumerate() {
         println!("  {}. {} - {}", i + 1, post.title, post.id);
     }
 
@@ -27,7 +27,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Создание поста
     println!("\nСоздание поста...");
     let post_id = client
-        .create_post("My First Post", "This is the content of my first post!")
+        .create_post(
+            "My First Post",
+            "This is the content of my first post!",
+            &["rust".to_string()],
+            None,
+            Some("A short introduction"),
+        )
         .await?;
     println!("Пост создан с ID: {}", post_id);
 
@@ -37,12 +43,20 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Пост получен:");
     println!("  Title: {}", post.title);
     println!("  Content: {}", post.content);
+    println!("  Summary: {}", post.summary);
     println!("  Created at: {}", post.created_at);
 
     // Обновление поста
     println!("\nОбновление поста...");
     client
-        .update_post(&post_id.to_string(), "Updated Title", "Updated content!")
+        .update_post(
+            &post_id.to_string(),
+            "Updated Title",
+            "Updated content!",
+            &["rust".to_string()],
+            None,
+            None,
+        )
         .await?;
     println!("Пост обновлен");
 
@@ -55,9 +69,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Список постов
     println!("\nПолучение списка постов...");
-    let posts = client.list_posts(10, 0).await?;
-    println!("Найдено постов: {}", posts.len());
-    for (i, post) in posts.iter().enumerate() {
+    let page = client.list_posts(10, 0, None).await?;
+    println!(
+        "Найдено постов: {} (всего {})",
+        page.posts.len(),
+        page.total
+    );
+    for (i, post) in page.posts.iter().enumerate() {
         println!("  {}. {} - {}", i + 1, post.title, post.id);
     }
 
@@ -0,0 +1,145 @@
+//! Административные вызовы: пользователи, принудительное удаление постов,
+//! категории и статистика системы.
+//!
+//! [`AdminClient`] расширяет [`BlogClient`]: сервер выполняет эти вызовы только для
+//! пользователя с ролью администратора (роль передаётся в JWT), остальным отвечает
+//! [`ClientError::Forbidden`](crate::error::ClientError::Forbidden). Клиента с
+//! административными вызовами создаёт [`BlogClientBuilder::build_admin`](crate::blog_client::BlogClientBuilder::build_admin).
+//!
+//! ```rust,no_run
+//! use client::admin::AdminClient;
+//! use client::blog_client::{BlogClientBuilder, Transport};
+//!
+//! # async fn example() -> Result<(), client::error::ClientError> {
+//! let client = BlogClientBuilder::new(Transport::Http("http://localhost:8080".to_string()))
+//!     .build_admin()
+//!     .await?;
+//! client.login("admin", "password").await?;
+//!
+//! let stats = client.system_stats().await?;
+//! println!("{} users, {} posts", stats.users, stats.posts);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{blog_client::BlogClient, types};
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+/// Административные вызовы блог-платформы (требуется роль администратора).
+///
+/// * [`list_users`](AdminClient::list_users) - Список пользователей с пагинацией
+/// * [`disable_user`](AdminClient::disable_user) - Отключение пользователя
+/// * [`enable_user`](AdminClient::enable_user) - Включение отключённого пользователя
+/// * [`force_delete_post`](AdminClient::force_delete_post) - Окончательное удаление любого поста
+/// * [`system_stats`](AdminClient::system_stats) - Сводные показатели системы
+/// * [`create_category`](AdminClient::create_category) - Создание категории постов
+/// * [`list_reports`](AdminClient::list_reports) - Очередь жалоб на посты
+/// * [`resolve_report`](AdminClient::resolve_report) - Решение по жалобе
+pub trait AdminClient: BlogClient {
+    /// Получает список пользователей в порядке регистрации с пагинацией.
+    async fn list_users(&self, page_size: u32, page: u32) -> types::ClientResult<types::UserPage>;
+    /// Отключает пользователя: он не может войти, а выданные ему токены отзываются.
+    async fn disable_user(&self, user_id: &str) -> types::ClientResult<()>;
+    /// Включает отключённого пользователя.
+    async fn enable_user(&self, user_id: &str) -> types::ClientResult<()>;
+    /// Окончательно удаляет любой пост, в том числе черновик или пост из корзины.
+    async fn force_delete_post(&self, post_id: &str) -> types::ClientResult<()>;
+    /// Получает число пользователей, постов и новых регистраций.
+    async fn system_stats(&self) -> types::ClientResult<types::SystemStats>;
+    /// Создаёт категорию постов; с `parent_id` - подкатегорию существующей категории.
+    async fn create_category(
+        &self,
+        name: &str,
+        parent_id: Option<&str>,
+    ) -> types::ClientResult<types::Category>;
+    /// Получает жалобы на посты от старых к новым с пагинацией; со `status` - только
+    /// жалобы в этом состоянии.
+    async fn list_reports(
+        &self,
+        status: Option<types::ReportStatus>,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::ReportPage>;
+    /// Принимает решение по открытой жалобе: скрывает пост или отклоняет жалобу.
+    async fn resolve_report(
+        &self,
+        report_id: &str,
+        action: types::ReportAction,
+    ) -> types::ClientResult<types::Report>;
+
+    /// Превращает клиента в `Arc<dyn AdminClient>`; как и
+    /// [`BlogClient::into_dyn`], но с административными вызовами.
+    #[cfg_attr(target_arch = "wasm32", allow(clippy::arc_with_non_send_sync))]
+    fn into_dyn_admin(self) -> Arc<dyn AdminClient>
+    where
+        Self: Sized + 'static,
+    {
+        Arc::new(self)
+    }
+}
+
+/// Реализует [`AdminClient`] для умного указателя, передавая вызовы клиенту внутри.
+macro_rules! forward_admin_client {
+    ($pointer:ident) => {
+        #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+        #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+        impl<T: AdminClient + ?Sized> AdminClient for $pointer<T> {
+            async fn list_users(
+                &self,
+                page_size: u32,
+                page: u32,
+            ) -> types::ClientResult<types::UserPage> {
+                (**self).list_users(page_size, page).await
+            }
+
+            async fn disable_user(&self, user_id: &str) -> types::ClientResult<()> {
+                (**self).disable_user(user_id).await
+            }
+
+            async fn enable_user(&self, user_id: &str) -> types::ClientResult<()> {
+                (**self).enable_user(user_id).await
+            }
+
+            async fn force_delete_post(&self, post_id: &str) -> types::ClientResult<()> {
+                (**self).force_delete_post(post_id).await
+            }
+
+            async fn system_stats(&self) -> types::ClientResult<types::SystemStats> {
+                (**self).system_stats().await
+            }
+
+            async fn create_category(
+                &self,
+                name: &str,
+                parent_id: Option<&str>,
+            ) -> types::ClientResult<types::Category> {
+                (**self).create_category(name, parent_id).await
+            }
+
+            async fn list_reports(
+                &self,
+                status: Option<types::ReportStatus>,
+                page_size: u32,
+                page: u32,
+            ) -> types::ClientResult<types::ReportPage> {
+                (**self).list_reports(status, page_size, page).await
+            }
+
+            async fn resolve_report(
+                &self,
+                report_id: &str,
+                action: types::ReportAction,
+            ) -> types::ClientResult<types::Report> {
+                (**self).resolve_report(report_id, action).await
+            }
+        }
+    };
+}
+
+forward_admin_client!(Arc);
+forward_admin_client!(Box);
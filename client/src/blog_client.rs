@@ -1,5 +1,10 @@
-use crate::types;
+use crate::{
+    TokenUpdateEvent, admin::AdminClient, cancellation::CancellationToken, config::ClientConfig,
+    token_manager::mpsc, token_store::TokenStore, types,
+};
 use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
 /// Тип транспорта для клиента.
@@ -10,31 +15,365 @@ pub enum Transport {
     Http(String),
 }
 
+/// Буфер обновления токена по умолчанию: за 5 минут до истечения
+const DEFAULT_TOKEN_REFRESH_BUFFER_SECONDS: i64 = 300;
+
+/// Создаёт клиента для выбранного [`Transport`] с общими настройками.
+///
+/// ```rust,no_run
+/// use client::blog_client::{BlogClientBuilder, Transport};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), client::error::ClientError> {
+/// let client = BlogClientBuilder::new(Transport::Http("http://localhost:8080".to_string()))
+///     .token_refresh_buffer(60)
+///     .request_timeout(Some(Duration::from_secs(5)))
+///     .build()
+///     .await?;
+/// let posts = client.list_posts(10, 0, None).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct BlogClientBuilder {
+    transport: Transport,
+    token_refresh_buffer_seconds: i64,
+    token_sender: Option<mpsc::UnboundedSender<TokenUpdateEvent>>,
+    session_expired_sender: Option<mpsc::UnboundedSender<()>>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    expected_issuer: Option<String>,
+    expected_audience: Option<String>,
+    cancellation_token: Option<CancellationToken>,
+    config: ClientConfig,
+}
+
+impl BlogClientBuilder {
+    pub fn new(transport: Transport) -> Self {
+        Self {
+            transport,
+            token_refresh_buffer_seconds: DEFAULT_TOKEN_REFRESH_BUFFER_SECONDS,
+            token_sender: None,
+            session_expired_sender: None,
+            token_store: None,
+            expected_issuer: None,
+            expected_audience: None,
+            cancellation_token: None,
+            config: ClientConfig::default(),
+        }
+    }
+
+    /// За сколько секунд до истечения обновлять access токен
+    pub fn token_refresh_buffer(mut self, seconds: i64) -> Self {
+        self.token_refresh_buffer_seconds = seconds;
+        self
+    }
+
+    /// Отправлять в `token_sender` каждый новый access токен
+    pub fn token_notifier(mut self, token_sender: mpsc::UnboundedSender<TokenUpdateEvent>) -> Self {
+        self.token_sender = Some(token_sender);
+        self
+    }
+
+    /// Отправлять в `sender` уведомление, когда сервер отклонил refresh токен.
+    ///
+    /// Сохранённые токены к этому моменту уже удалены: пользователю нужно войти заново.
+    pub fn session_expired_notifier(mut self, sender: mpsc::UnboundedSender<()>) -> Self {
+        self.session_expired_sender = Some(sender);
+        self
+    }
+
+    /// Хранить токены в `store`: сохранённый вход восстанавливается при создании клиента
+    pub fn token_store(mut self, store: impl TokenStore + 'static) -> Self {
+        self.token_store = Some(Arc::new(store));
+        self
+    }
+
+    /// Ожидаемые издатель и аудитория токенов (`jwt_issuer` и `jwt_audience` сервера)
+    pub fn expected_token_claims(
+        mut self,
+        issuer: Option<String>,
+        audience: Option<String>,
+    ) -> Self {
+        self.expected_issuer = issuer;
+        self.expected_audience = audience;
+        self
+    }
+
+    /// Прерывать вызовы клиента при отмене `token`, возвращая
+    /// [`ClientError::Cancelled`](crate::error::ClientError::Cancelled)
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Таймаут установки соединения; `None` - без ограничения
+    pub fn connect_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.config.connect_timeout = timeout;
+        self
+    }
+
+    /// Таймаут каждого запроса; `None` - без ограничения
+    pub fn request_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.config.request_timeout = timeout;
+        self
+    }
+
+    /// Просить сервер сжимать ответы (по умолчанию включено)
+    pub fn compression(mut self, enabled: bool) -> Self {
+        self.config.compression = enabled;
+        self
+    }
+
+    /// Подключаться по TLS с указанными настройками
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tls(mut self, tls: crate::tls::TlsOptions) -> Self {
+        self.config.tls = Some(tls);
+        self
+    }
+
+    /// Сколько простаивающих HTTP соединений держать открытыми для одного хоста
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.config.pool.max_idle_per_host = max;
+        self
+    }
+
+    /// Через сколько закрывать простаивающее HTTP соединение; `None` - не закрывать
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.config.pool.idle_timeout = timeout;
+        self
+    }
+
+    /// Говорить с HTTP сервером сразу по HTTP/2, без согласования версии
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.config.pool.http2_prior_knowledge = true;
+        self
+    }
+
+    /// Период TCP keepalive HTTP соединений; `None` - не включать keepalive
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tcp_keepalive(mut self, interval: Option<Duration>) -> Self {
+        self.config.pool.tcp_keepalive = interval;
+        self
+    }
+
+    /// Создаёт клиента; для gRPC устанавливает соединение с сервером.
+    ///
+    /// Затем запрашивает у сервера [`ServerInfo`](types::ServerInfo) и проверяет,
+    /// что он обслуживает версию API клиента. Сервер без этого запроса считается
+    /// сервером `v1`; если сервер недоступен, проверка пропускается.
+    ///
+    /// Возвращает [`ClientError::TransportError`](crate::error::ClientError::TransportError), если транспорт отключён features крейта,
+    /// и [`ClientError::IncompatibleServer`](crate::error::ClientError::IncompatibleServer), если сервер не обслуживает версию клиента.
+    pub async fn build(self) -> types::ClientResult<Box<dyn BlogClient>> {
+        let client: Box<dyn BlogClient> = self.build_admin().await?;
+        Ok(client)
+    }
+
+    /// Создаёт клиента с административными вызовами [`AdminClient`].
+    ///
+    /// Создание и проверка сервера те же, что у [`build`](Self::build). Сами вызовы
+    /// выполнятся, только если пользователь вошёл с ролью администратора.
+    pub async fn build_admin(self) -> types::ClientResult<Box<dyn AdminClient>> {
+        let client = match &self.transport {
+            Transport::Http(url) => self.build_http(url).await?,
+            Transport::Grpc(url) => self.build_grpc(url).await?,
+        };
+        negotiate_version(client.as_ref()).await?;
+        Ok(client)
+    }
+
+    #[cfg(feature = "http")]
+    async fn build_http(&self, url: &str) -> types::ClientResult<Box<dyn AdminClient>> {
+        use crate::http_client::HttpClient;
+
+        let mut client = HttpClient::new_with_config(url.to_string(), &self.config).await?;
+
+        client.set_token_refresh_buffer(self.token_refresh_buffer_seconds);
+        client.set_expected_token_claims(
+            self.expected_issuer.clone(),
+            self.expected_audience.clone(),
+        );
+        if let Some(token_sender) = &self.token_sender {
+            client.set_token_notifier(token_sender.clone());
+        }
+        if let Some(sender) = &self.session_expired_sender {
+            client.set_session_expired_notifier(sender.clone());
+        }
+        if let Some(store) = &self.token_store {
+            client.set_token_store(store.clone())?;
+        }
+        Ok(self.boxed(client))
+    }
+
+    #[cfg(not(feature = "http"))]
+    async fn build_http(&self, _url: &str) -> types::ClientResult<Box<dyn AdminClient>> {
+        Err(crate::error::ClientError::TransportError(
+            "HTTP transport is disabled (feature `http`)".to_string(),
+        ))
+    }
+
+    #[cfg(feature = "grpc")]
+    async fn build_grpc(&self, url: &str) -> types::ClientResult<Box<dyn AdminClient>> {
+        use crate::grpc_client::GrpcClient;
+
+        let mut client = GrpcClient::new_with_config(url.to_string(), &self.config).await?;
+
+        client.set_token_refresh_buffer(self.token_refresh_buffer_seconds);
+        client.set_expected_token_claims(
+            self.expected_issuer.clone(),
+            self.expected_audience.clone(),
+        );
+        if let Some(token_sender) = &self.token_sender {
+            client.set_token_notifier(token_sender.clone());
+        }
+        if let Some(sender) = &self.session_expired_sender {
+            client.set_session_expired_notifier(sender.clone());
+        }
+        if let Some(store) = &self.token_store {
+            client.set_token_store(store.clone())?;
+        }
+        Ok(self.boxed(client))
+    }
+
+    #[cfg(not(feature = "grpc"))]
+    async fn build_grpc(&self, _url: &str) -> types::ClientResult<Box<dyn AdminClient>> {
+        Err(crate::error::ClientError::TransportError(
+            "gRPC transport is disabled (feature `grpc`)".to_string(),
+        ))
+    }
+
+    /// Оборачивает клиента в [`CancellableClient`], если задан токен отмены
+    #[cfg(any(feature = "http", feature = "grpc"))]
+    fn boxed<C>(&self, client: C) -> Box<dyn AdminClient>
+    where
+        C: AdminClient + 'static,
+    {
+        match &self.cancellation_token {
+            Some(token) => Box::new(crate::cancellation::CancellableClient::new(
+                client,
+                token.clone(),
+            )),
+            None => Box::new(client),
+        }
+    }
+}
+
+/// Проверяет совместимость сервера, созданного [`BlogClientBuilder`].
+async fn negotiate_version(client: &dyn BlogClient) -> types::ClientResult<()> {
+    use crate::error::ClientError;
+
+    match client.server_info().await {
+        Ok(info) => info.check_compatible(),
+        // Сервер, появившийся до согласования версий, обслуживает только v1
+        Err(ClientError::NotFound) => Ok(()),
+        // Недоступность сервера проявится в первом запросе, где её можно обработать
+        Err(error) if error.is_network_error() => Ok(()),
+        Err(error) => Err(error),
+    }
+}
+
 // Для не-WASM требуется Send для поддержки многопоточности
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 /// Основной трейт клиента для взаимодействия с блог-платформой.
 ///
 /// Определяет единый интерфейс для всех типов клиентов (HTTP, gRPC).
-/// В нативной среде требует `Send` для поддержки многопоточности.
+/// В нативной среде требует `Send` и `Sync` для поддержки многопоточности.
+///
+/// Трейт объектно-безопасен: клиента удобно хранить как `Arc<dyn BlogClient>` (см.
+/// [`into_dyn`](BlogClient::into_dyn)) и раздавать клоны `Arc` задачам и компонентам.
+/// `Arc<T>` и `Box<T>` сами реализуют трейт, поэтому подходят везде, где ожидается
+/// `impl BlogClient`.
+///
+/// # Сервер
+///
+/// * [`server_info`](BlogClient::server_info) - Версия сервера, версии API и возможности
 ///
 /// # Методы аутентификации
 ///
 /// * [`login`](BlogClient::login) - Вход пользователя
+/// * [`login_with_totp`](BlogClient::login_with_totp) - Вход с кодом двухфакторной аутентификации
 /// * [`register`](BlogClient::register) - Регистрация нового пользователя
+/// * [`is_username_available`](BlogClient::is_username_available) - Проверка, свободно ли имя пользователя
+/// * [`logout`](BlogClient::logout) - Выход с отзывом refresh токена на сервере
+/// * [`forgot_password`](BlogClient::forgot_password) - Запрос токена сброса пароля
+/// * [`reset_password`](BlogClient::reset_password) - Установка нового пароля по токену сброса
 /// * [`setup_token`](BlogClient::setup_token) - Установка токена вручную
 /// * [`get_token`](BlogClient::get_token) - Получение текущего токена
 ///
+/// # Профили пользователей
+///
+/// * [`get_user_profile`](BlogClient::get_user_profile) - Получение профиля пользователя
+/// * [`update_my_profile`](BlogClient::update_my_profile) - Обновление своего профиля
+/// * [`get_digest_settings`](BlogClient::get_digest_settings) - Настройки email-дайджеста
+/// * [`update_digest_settings`](BlogClient::update_digest_settings) - Подписка на email-дайджест и отписка
+///
 /// # CRUD операции с постами
 ///
 /// * [`create_post`](BlogClient::create_post) - Создание нового поста
+/// * [`create_draft`](BlogClient::create_draft) - Создание черновика
+/// * [`create_posts`](BlogClient::create_posts) - Пакетное создание постов
 /// * [`get_post`](BlogClient::get_post) - Получение поста по ID
 /// * [`update_post`](BlogClient::update_post) - Обновление поста
-/// * [`delete_post`](BlogClient::delete_post) - Удаление поста
+/// * [`delete_post`](BlogClient::delete_post) - Перенос поста в корзину
+/// * [`delete_posts`](BlogClient::delete_posts) - Пакетный перенос постов в корзину
+/// * [`restore_post`](BlogClient::restore_post) - Восстановление поста из корзины
+/// * [`purge_post`](BlogClient::purge_post) - Окончательное удаление поста из корзины
+/// * [`publish_post`](BlogClient::publish_post) - Публикация черновика
+/// * [`unpublish_post`](BlogClient::unpublish_post) - Возврат поста в черновики
+/// * [`like_post`](BlogClient::like_post) - Лайк поста
+/// * [`unlike_post`](BlogClient::unlike_post) - Снятие лайка
+/// * [`report_post`](BlogClient::report_post) - Жалоба на пост
+/// * [`follow_user`](BlogClient::follow_user) - Подписка на автора
+/// * [`unfollow_user`](BlogClient::unfollow_user) - Отмена подписки
+/// * [`is_following`](BlogClient::is_following) - Проверка подписки
+/// * [`get_feed`](BlogClient::get_feed) - Лента постов авторов из подписок
 /// * [`list_posts`](BlogClient::list_posts) - Получение списка постов с пагинацией
-pub trait BlogClient {
+/// * [`list_posts_by_tag`](BlogClient::list_posts_by_tag) - Получение постов с заданным тегом
+/// * [`list_posts_by_category`](BlogClient::list_posts_by_category) - Получение постов категории
+/// * [`list_posts_after`](BlogClient::list_posts_after) - Получение постов после курсора
+/// * [`list_trending_posts`](BlogClient::list_trending_posts) - Получение популярных постов
+/// * [`list_posts_by_author`](BlogClient::list_posts_by_author) - Получение постов автора
+///
+/// # Категории
+///
+/// * [`list_categories`](BlogClient::list_categories) - Получение всех категорий постов
+///
+/// # Блоги
+///
+/// * [`select_blog`](BlogClient::select_blog) - Выбор блога, с постами которого работает клиент
+/// * [`selected_blog`](BlogClient::selected_blog) - Адрес выбранного блога
+/// * [`list_blogs`](BlogClient::list_blogs) - Получение всех блогов сервера
+/// * [`get_blog`](BlogClient::get_blog) - Получение блога по адресу
+/// * [`create_blog`](BlogClient::create_blog) - Создание блога
+/// * [`update_blog`](BlogClient::update_blog) - Изменение названия блога
+/// * [`delete_blog`](BlogClient::delete_blog) - Удаление блога
+///
+/// # Файлы
+///
+/// * [`upload_file`](BlogClient::upload_file) - Загрузка файла для вложения в пост
+pub trait BlogClient: MaybeSend + MaybeSync {
+    /// Запрашивает версию сервера, обслуживаемые версии API и включённые возможности.
+    ///
+    /// Сервер, который не поддерживает запрос, возвращает
+    /// [`ClientError::NotFound`](crate::error::ClientError::NotFound).
+    async fn server_info(&self) -> types::ClientResult<types::ServerInfo>;
     /// Выполняет вход пользователя в систему.
+    ///
+    /// Если у пользователя включена 2FA, возвращает
+    /// [`ClientError::TwoFactorRequired`](crate::error::ClientError::TwoFactorRequired).
     async fn login(&self, username: &str, password: &str) -> types::ClientResult<Uuid>;
+    /// Выполняет вход с кодом TOTP из приложения-аутентификатора.
+    ///
+    /// Для пользователя без 2FA код не используется.
+    async fn login_with_totp(
+        &self,
+        username: &str,
+        password: &str,
+        code: &str,
+    ) -> types::ClientResult<Uuid>;
     /// Регистрирует нового пользователя.
     async fn register(
         &self,
@@ -42,6 +381,21 @@ pub trait BlogClient {
         email: &str,
         password: &str,
     ) -> types::ClientResult<()>;
+    /// Проверяет, свободно ли имя пользователя.
+    async fn is_username_available(&self, username: &str) -> types::ClientResult<bool>;
+    /// Отзывает текущий refresh токен на сервере и очищает сохранённые токены.
+    async fn logout(&self) -> types::ClientResult<()>;
+    /// Отзывает все токены пользователя на всех устройствах (требуется аутентификация)
+    /// и очищает сохранённые токены.
+    async fn logout_all_sessions(&self) -> types::ClientResult<()>;
+    /// Возвращает действующие сеансы входа текущего пользователя (требуется аутентификация).
+    async fn list_sessions(&self) -> types::ClientResult<Vec<types::Session>>;
+    /// Закрывает сеанс входа: его refresh токен больше не обновляется (требуется аутентификация).
+    async fn revoke_session(&self, session_id: &str) -> types::ClientResult<()>;
+    /// Запрашивает токен сброса пароля для пользователя с указанным email.
+    async fn forgot_password(&self, email: &str) -> types::ClientResult<()>;
+    /// Устанавливает новый пароль по одноразовому токену сброса.
+    async fn reset_password(&self, token: &str, new_password: &str) -> types::ClientResult<()>;
     /// Устанавливает JWT токен для аутентификации запросов.
     async fn setup_token(&self, token: &str) -> types::ClientResult<()>;
     /// Возвращает текущий JWT токен, если он установлен.
@@ -51,19 +405,546 @@ pub trait BlogClient {
     /// Возвращает полные данные аутентификации, если они установлены.
     async fn get_auth_data(&self) -> types::ClientResult<Option<types::AuthData>>;
 
-    /// Создаёт новый пост в блоге (требуется аутентификация).
-    async fn create_post(&self, title: &str, content: &str) -> types::ClientResult<Uuid>;
+    /// Получает публичный профиль пользователя по его ID.
+    async fn get_user_profile(&self, user_id: &str) -> types::ClientResult<types::UserProfile>;
+    /// Заменяет поля профиля текущего пользователя (требуется аутентификация);
+    /// `None` очищает поле.
+    async fn update_my_profile(
+        &self,
+        display_name: Option<&str>,
+        bio: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> types::ClientResult<types::UserProfile>;
+    /// Получает настройки email-дайджеста текущего пользователя (требуется аутентификация).
+    async fn get_digest_settings(&self) -> types::ClientResult<types::DigestSettings>;
+    /// Подписывает текущего пользователя на email-дайджест с частотой `frequency`
+    /// или меняет её (требуется аутентификация); `None` отписывает от дайджеста.
+    async fn update_digest_settings(
+        &self,
+        frequency: Option<types::DigestFrequency>,
+    ) -> types::ClientResult<types::DigestSettings>;
+
+    /// Создаёт и сразу публикует новый пост в блоге (требуется аутентификация);
+    /// `category_id` - ID категории поста из [`list_categories`](BlogClient::list_categories).
+    ///
+    /// Без `summary` описание поста составляется сервером из начала содержимого.
+    async fn create_post(
+        &self,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<Uuid>;
+    /// Создаёт черновик, который виден только автору (требуется аутентификация).
+    async fn create_draft(
+        &self,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<Uuid>;
+    /// Создаёт и публикует посты из пар `(заголовок, содержимое)` одной транзакцией
+    /// (требуется аутентификация): при ошибке не создаётся ни один.
+    ///
+    /// Возвращает ID созданных постов в порядке `posts`. Через gRPC-Web (WASM)
+    /// недоступно: метод использует клиентский поток.
+    async fn create_posts(&self, posts: Vec<(String, String)>) -> types::ClientResult<Vec<Uuid>>;
     /// Получает пост по его ID.
     async fn get_post(&self, post_id: &str) -> types::ClientResult<types::Post>;
-    /// Обновляет существующий пост (требуется быть автором).
+    /// Обновляет существующий пост (требуется быть автором); теги заменяются целиком.
+    ///
+    /// Без `category_id` категория поста не меняется, пустая строка убирает её.
+    /// Так же и с `summary`: пустая строка возвращает описание из начала содержимого.
     async fn update_post(
         &self,
         post_id: &str,
         title: &str,
         content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
     ) -> types::ClientResult<()>;
-    /// Удаляет пост (требуется быть автором).
+    /// Переносит пост в корзину (требуется быть автором).
     async fn delete_post(&self, post_id: &str) -> types::ClientResult<()>;
-    /// Получает список постов с пагинацией.
-    async fn list_posts(&self, page_size: u32, page: u32) -> types::ClientResult<Vec<types::Post>>;
+    /// Переносит посты в корзину одной транзакцией (требуется быть автором каждого):
+    /// если хотя бы один пост недоступен, не удаляется ни один.
+    async fn delete_posts(&self, post_ids: Vec<Uuid>) -> types::ClientResult<()>;
+    /// Восстанавливает пост из корзины (требуется быть автором).
+    async fn restore_post(&self, post_id: &str) -> types::ClientResult<()>;
+    /// Окончательно удаляет пост из корзины (требуется быть автором).
+    async fn purge_post(&self, post_id: &str) -> types::ClientResult<()>;
+    /// Публикует черновик (требуется быть автором).
+    async fn publish_post(&self, post_id: &str) -> types::ClientResult<()>;
+    /// Возвращает опубликованный пост в черновики (требуется быть автором).
+    async fn unpublish_post(&self, post_id: &str) -> types::ClientResult<()>;
+    /// Ставит лайк посту и возвращает новое количество лайков (требуется аутентификация).
+    async fn like_post(&self, post_id: &str) -> types::ClientResult<u64>;
+    /// Снимает лайк с поста и возвращает новое количество лайков (требуется аутентификация).
+    async fn unlike_post(&self, post_id: &str) -> types::ClientResult<u64>;
+    /// Отправляет модераторам жалобу на пост (требуется аутентификация).
+    ///
+    /// На один пост пользователь жалуется один раз: повторную жалобу сервер отклоняет
+    /// (`Conflict` по HTTP, `InvalidRequest` по gRPC).
+    async fn report_post(
+        &self,
+        post_id: &str,
+        reason: types::ReportReason,
+        details: Option<&str>,
+    ) -> types::ClientResult<types::Report>;
+    /// Подписывается на автора (требуется аутентификация); повторная подписка ничего не меняет.
+    async fn follow_user(&self, user_id: &str) -> types::ClientResult<()>;
+    /// Отменяет подписку на автора (требуется аутентификация).
+    async fn unfollow_user(&self, user_id: &str) -> types::ClientResult<()>;
+    /// Проверяет, подписан ли текущий пользователь на автора (требуется аутентификация).
+    async fn is_following(&self, user_id: &str) -> types::ClientResult<bool>;
+    /// Получает ленту: опубликованные посты авторов из подписок, от новых к старым
+    /// (требуется аутентификация).
+    async fn get_feed(&self, page_size: u32, page: u32) -> types::ClientResult<types::PostPage>;
+    /// Получает список постов с пагинацией; без `sort` сначала идут новые посты.
+    async fn list_posts(
+        &self,
+        page_size: u32,
+        page: u32,
+        sort: Option<types::PostSort>,
+    ) -> types::ClientResult<types::PostPage>;
+    /// Получает список постов с заданным тегом с пагинацией.
+    async fn list_posts_by_tag(
+        &self,
+        tag: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage>;
+    /// Получает список постов категории и всех её подкатегорий с пагинацией.
+    async fn list_posts_by_category(
+        &self,
+        category_id: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage>;
+    /// Получает до `limit` новых постов после курсора из `PostPage::next_cursor`.
+    ///
+    /// Без курсора возвращает начало ленты. В отличие от номеров страниц, курсор не
+    /// сдвигается, когда во время листания появляются новые посты.
+    async fn list_posts_after(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> types::ClientResult<types::PostPage>;
+    /// Получает до `limit` популярных постов: больше всего лайков за последнее время.
+    async fn list_trending_posts(&self, limit: u32) -> types::ClientResult<Vec<types::Post>>;
+    /// Получает список постов автора с пагинацией; свои черновики видны только автору.
+    async fn list_posts_by_author(
+        &self,
+        author_id: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage>;
+    /// Получает все категории постов; дерево категорий собирается по `parent_id`.
+    async fn list_categories(&self) -> types::ClientResult<Vec<types::Category>>;
+    /// Выбирает блог, с постами которого работает клиент; `None` возвращает к общей
+    /// ленте сервера.
+    ///
+    /// Списки постов ([`list_posts`](BlogClient::list_posts) и остальные `list_posts_*`,
+    /// кроме постов автора) содержат только посты выбранного блога, а
+    /// [`create_post`](BlogClient::create_post), [`create_draft`](BlogClient::create_draft)
+    /// и [`create_posts`](BlogClient::create_posts) создают посты в нём. Существование блога
+    /// проверяет сервер при следующем запросе.
+    fn select_blog(&self, slug: Option<&str>);
+    /// Возвращает адрес блога, выбранного [`select_blog`](BlogClient::select_blog).
+    fn selected_blog(&self) -> Option<String>;
+    /// Получает все блоги сервера, упорядоченные по адресу.
+    async fn list_blogs(&self) -> types::ClientResult<Vec<types::Blog>>;
+    /// Получает блог по адресу.
+    async fn get_blog(&self, slug: &str) -> types::ClientResult<types::Blog>;
+    /// Создаёт блог, владельцем которого становится текущий пользователь
+    /// (требуется аутентификация).
+    async fn create_blog(&self, slug: &str, name: &str) -> types::ClientResult<types::Blog>;
+    /// Меняет название блога (требуется быть владельцем блога или администратором).
+    async fn update_blog(&self, slug: &str, name: &str) -> types::ClientResult<types::Blog>;
+    /// Удаляет блог (требуется быть владельцем блога или администратором); посты блога
+    /// остаются в общей ленте сервера.
+    async fn delete_blog(&self, slug: &str) -> types::ClientResult<()>;
+    /// Загружает файл и возвращает ссылку на него (требуется аутентификация).
+    async fn upload_file(
+        &self,
+        file_name: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> types::ClientResult<String>;
+
+    /// Превращает клиента в `Arc<dyn BlogClient>`, один экземпляр которого разделяют
+    /// задачи и компоненты вместо клонов конкретного клиента.
+    ///
+    /// Клиента из [`BlogClientBuilder::build`] в `Arc` переводит `Arc::from`:
+    /// `into_dyn` для `Box<dyn BlogClient>` добавил бы ещё один уровень косвенности.
+    // В браузере Arc нужен только ради общего типа с нативной сборкой
+    #[cfg_attr(target_arch = "wasm32", allow(clippy::arc_with_non_send_sync))]
+    fn into_dyn(self) -> Arc<dyn BlogClient>
+    where
+        Self: Sized + 'static,
+    {
+        Arc::new(self)
+    }
+}
+
+/// Ограничение на клиента: вне браузера его разделяют между потоками, поэтому он
+/// должен быть `Send`.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+
+/// Ограничение на клиента: в браузере его нет.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// Ограничение на клиента: вне браузера методы трейта возвращают `Send`
+/// futures, поэтому клиент должен быть `Sync`.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSync: Sync {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+
+/// Ограничение на клиента: в браузере его нет.
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSync {}
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSync for T {}
+
+/// Реализует [`BlogClient`] для умного указателя, передавая вызовы клиенту внутри.
+macro_rules! forward_blog_client {
+    ($pointer:ident) => {
+        #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+        #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+        impl<T: BlogClient + ?Sized> BlogClient for $pointer<T> {
+            async fn server_info(&self) -> types::ClientResult<types::ServerInfo> {
+                (**self).server_info().await
+            }
+
+            async fn login(&self, username: &str, password: &str) -> types::ClientResult<Uuid> {
+                (**self).login(username, password).await
+            }
+
+            async fn login_with_totp(
+                &self,
+                username: &str,
+                password: &str,
+                code: &str,
+            ) -> types::ClientResult<Uuid> {
+                (**self).login_with_totp(username, password, code).await
+            }
+
+            async fn register(
+                &self,
+                username: &str,
+                email: &str,
+                password: &str,
+            ) -> types::ClientResult<()> {
+                (**self).register(username, email, password).await
+            }
+
+            async fn is_username_available(&self, username: &str) -> types::ClientResult<bool> {
+                (**self).is_username_available(username).await
+            }
+
+            async fn logout(&self) -> types::ClientResult<()> {
+                (**self).logout().await
+            }
+
+            async fn logout_all_sessions(&self) -> types::ClientResult<()> {
+                (**self).logout_all_sessions().await
+            }
+
+            async fn list_sessions(&self) -> types::ClientResult<Vec<types::Session>> {
+                (**self).list_sessions().await
+            }
+
+            async fn revoke_session(&self, session_id: &str) -> types::ClientResult<()> {
+                (**self).revoke_session(session_id).await
+            }
+
+            async fn forgot_password(&self, email: &str) -> types::ClientResult<()> {
+                (**self).forgot_password(email).await
+            }
+
+            async fn reset_password(
+                &self,
+                token: &str,
+                new_password: &str,
+            ) -> types::ClientResult<()> {
+                (**self).reset_password(token, new_password).await
+            }
+
+            async fn setup_token(&self, token: &str) -> types::ClientResult<()> {
+                (**self).setup_token(token).await
+            }
+
+            async fn get_token(&self) -> types::ClientResult<Option<String>> {
+                (**self).get_token().await
+            }
+
+            async fn setup_auth_data(
+                &self,
+                auth_data: &types::AuthData,
+            ) -> types::ClientResult<()> {
+                (**self).setup_auth_data(auth_data).await
+            }
+
+            async fn get_auth_data(&self) -> types::ClientResult<Option<types::AuthData>> {
+                (**self).get_auth_data().await
+            }
+
+            async fn get_user_profile(
+                &self,
+                user_id: &str,
+            ) -> types::ClientResult<types::UserProfile> {
+                (**self).get_user_profile(user_id).await
+            }
+
+            async fn update_my_profile(
+                &self,
+                display_name: Option<&str>,
+                bio: Option<&str>,
+                avatar_url: Option<&str>,
+            ) -> types::ClientResult<types::UserProfile> {
+                (**self)
+                    .update_my_profile(display_name, bio, avatar_url)
+                    .await
+            }
+
+            async fn get_digest_settings(&self) -> types::ClientResult<types::DigestSettings> {
+                (**self).get_digest_settings().await
+            }
+
+            async fn update_digest_settings(
+                &self,
+                frequency: Option<types::DigestFrequency>,
+            ) -> types::ClientResult<types::DigestSettings> {
+                (**self).update_digest_settings(frequency).await
+            }
+
+            async fn create_post(
+                &self,
+                title: &str,
+                content: &str,
+                tags: &[String],
+                category_id: Option<&str>,
+                summary: Option<&str>,
+            ) -> types::ClientResult<Uuid> {
+                (**self)
+                    .create_post(title, content, tags, category_id, summary)
+                    .await
+            }
+
+            async fn create_draft(
+                &self,
+                title: &str,
+                content: &str,
+                tags: &[String],
+                category_id: Option<&str>,
+                summary: Option<&str>,
+            ) -> types::ClientResult<Uuid> {
+                (**self)
+                    .create_draft(title, content, tags, category_id, summary)
+                    .await
+            }
+
+            async fn create_posts(
+                &self,
+                posts: Vec<(String, String)>,
+            ) -> types::ClientResult<Vec<Uuid>> {
+                (**self).create_posts(posts).await
+            }
+
+            async fn get_post(&self, post_id: &str) -> types::ClientResult<types::Post> {
+                (**self).get_post(post_id).await
+            }
+
+            async fn update_post(
+                &self,
+                post_id: &str,
+                title: &str,
+                content: &str,
+                tags: &[String],
+                category_id: Option<&str>,
+                summary: Option<&str>,
+            ) -> types::ClientResult<()> {
+                (**self)
+                    .update_post(post_id, title, content, tags, category_id, summary)
+                    .await
+            }
+
+            async fn delete_post(&self, post_id: &str) -> types::ClientResult<()> {
+                (**self).delete_post(post_id).await
+            }
+
+            async fn delete_posts(&self, post_ids: Vec<Uuid>) -> types::ClientResult<()> {
+                (**self).delete_posts(post_ids).await
+            }
+
+            async fn restore_post(&self, post_id: &str) -> types::ClientResult<()> {
+                (**self).restore_post(post_id).await
+            }
+
+            async fn purge_post(&self, post_id: &str) -> types::ClientResult<()> {
+                (**self).purge_post(post_id).await
+            }
+
+            async fn publish_post(&self, post_id: &str) -> types::ClientResult<()> {
+                (**self).publish_post(post_id).await
+            }
+
+            async fn unpublish_post(&self, post_id: &str) -> types::ClientResult<()> {
+                (**self).unpublish_post(post_id).await
+            }
+
+            async fn like_post(&self, post_id: &str) -> types::ClientResult<u64> {
+                (**self).like_post(post_id).await
+            }
+
+            async fn unlike_post(&self, post_id: &str) -> types::ClientResult<u64> {
+                (**self).unlike_post(post_id).await
+            }
+
+            async fn report_post(
+                &self,
+                post_id: &str,
+                reason: types::ReportReason,
+                details: Option<&str>,
+            ) -> types::ClientResult<types::Report> {
+                (**self).report_post(post_id, reason, details).await
+            }
+
+            async fn follow_user(&self, user_id: &str) -> types::ClientResult<()> {
+                (**self).follow_user(user_id).await
+            }
+
+            async fn unfollow_user(&self, user_id: &str) -> types::ClientResult<()> {
+                (**self).unfollow_user(user_id).await
+            }
+
+            async fn is_following(&self, user_id: &str) -> types::ClientResult<bool> {
+                (**self).is_following(user_id).await
+            }
+
+            async fn get_feed(
+                &self,
+                page_size: u32,
+                page: u32,
+            ) -> types::ClientResult<types::PostPage> {
+                (**self).get_feed(page_size, page).await
+            }
+
+            async fn list_posts(
+                &self,
+                page_size: u32,
+                page: u32,
+                sort: Option<types::PostSort>,
+            ) -> types::ClientResult<types::PostPage> {
+                (**self).list_posts(page_size, page, sort).await
+            }
+
+            async fn list_posts_by_tag(
+                &self,
+                tag: &str,
+                page_size: u32,
+                page: u32,
+            ) -> types::ClientResult<types::PostPage> {
+                (**self).list_posts_by_tag(tag, page_size, page).await
+            }
+
+            async fn list_posts_by_category(
+                &self,
+                category_id: &str,
+                page_size: u32,
+                page: u32,
+            ) -> types::ClientResult<types::PostPage> {
+                (**self)
+                    .list_posts_by_category(category_id, page_size, page)
+                    .await
+            }
+
+            async fn list_posts_after(
+                &self,
+                cursor: Option<&str>,
+                limit: u32,
+            ) -> types::ClientResult<types::PostPage> {
+                (**self).list_posts_after(cursor, limit).await
+            }
+
+            async fn list_trending_posts(
+                &self,
+                limit: u32,
+            ) -> types::ClientResult<Vec<types::Post>> {
+                (**self).list_trending_posts(limit).await
+            }
+
+            async fn list_posts_by_author(
+                &self,
+                author_id: &str,
+                page_size: u32,
+                page: u32,
+            ) -> types::ClientResult<types::PostPage> {
+                (**self)
+                    .list_posts_by_author(author_id, page_size, page)
+                    .await
+            }
+
+            async fn list_categories(&self) -> types::ClientResult<Vec<types::Category>> {
+                (**self).list_categories().await
+            }
+
+            fn select_blog(&self, slug: Option<&str>) {
+                (**self).select_blog(slug)
+            }
+
+            fn selected_blog(&self) -> Option<String> {
+                (**self).selected_blog()
+            }
+
+            async fn list_blogs(&self) -> types::ClientResult<Vec<types::Blog>> {
+                (**self).list_blogs().await
+            }
+
+            async fn get_blog(&self, slug: &str) -> types::ClientResult<types::Blog> {
+                (**self).get_blog(slug).await
+            }
+
+            async fn create_blog(
+                &self,
+                slug: &str,
+                name: &str,
+            ) -> types::ClientResult<types::Blog> {
+                (**self).create_blog(slug, name).await
+            }
+
+            async fn update_blog(
+                &self,
+                slug: &str,
+                name: &str,
+            ) -> types::ClientResult<types::Blog> {
+                (**self).update_blog(slug, name).await
+            }
+
+            async fn delete_blog(&self, slug: &str) -> types::ClientResult<()> {
+                (**self).delete_blog(slug).await
+            }
+
+            async fn upload_file(
+                &self,
+                file_name: &str,
+                content_type: &str,
+                data: Vec<u8>,
+            ) -> types::ClientResult<String> {
+                (**self).upload_file(file_name, content_type, data).await
+            }
+        }
+    };
 }
+
+forward_blog_client!(Arc);
+forward_blog_client!(Box);
@@ -0,0 +1,540 @@
+//! Кооперативная отмена запросов клиента через [`CancellationToken`].
+//!
+//! [`CancellableClient`] оборачивает HTTP или gRPC клиента: после отмены токена
+//! текущие вызовы прерываются, а новые сразу завершаются ошибкой
+//! [`ClientError::Cancelled`]. Для одного вызова, в том числе потокового API,
+//! подойдёт [`cancellable`].
+
+use std::future::Future;
+use std::pin::pin;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use async_trait::async_trait;
+use event_listener::Event;
+use futures_util::future::{Either, select};
+use uuid::Uuid;
+
+use crate::{admin::AdminClient, blog_client::BlogClient, error::ClientError, types};
+
+/// Токен отмены, общий для всех своих клонов.
+///
+/// Не зависит от async рантайма, поэтому работает и в браузере.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<TokenState>,
+}
+
+#[derive(Default)]
+struct TokenState {
+    cancelled: AtomicBool,
+    event: Event,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Отменяет токен и будит всех, кто ждёт [`CancellationToken::cancelled`]
+    pub fn cancel(&self) {
+        self.inner.cancelled.store(true, Ordering::SeqCst);
+        self.inner.event.notify(usize::MAX);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Завершается, когда токен отменён
+    pub async fn cancelled(&self) {
+        loop {
+            if self.is_cancelled() {
+                return;
+            }
+            let listener = self.inner.event.listen();
+            // Отмена могла случиться до подписки
+            if self.is_cancelled() {
+                return;
+            }
+            listener.await;
+        }
+    }
+}
+
+impl std::fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("is_cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Выполняет `future`, пока не отменён `token`.
+///
+/// Если токен отменён до или во время вызова, `future` сбрасывается и возвращается
+/// [`ClientError::Cancelled`]. Уже отправленный запрос сервер может успеть выполнить.
+pub async fn cancellable<T>(
+    token: &CancellationToken,
+    future: impl Future<Output = types::ClientResult<T>>,
+) -> types::ClientResult<T> {
+    // Отмену проверяем первой, даже если `future` уже готова
+    match select(pin!(token.cancelled()), pin!(future)).await {
+        Either::Left(_) => Err(ClientError::Cancelled),
+        Either::Right((result, _)) => result,
+    }
+}
+
+/// Клиент, все вызовы которого прерываются отменой общего токена.
+///
+/// ```rust,no_run
+/// use client::cancellation::{CancellableClient, CancellationToken};
+/// use client::{blog_client::BlogClient, http_client::HttpClient};
+///
+/// # async fn example() -> Result<(), client::error::ClientError> {
+/// let token = CancellationToken::new();
+/// let client = CancellableClient::new(
+///     HttpClient::new("http://localhost:8080".to_string()).await?,
+///     token.clone(),
+/// );
+///
+/// // Например, по Ctrl+C из другой задачи
+/// token.cancel();
+/// assert!(client.list_posts(10, 0, None).await.is_err());
+/// # Ok(())
+/// # }
+/// ```
+pub struct CancellableClient<C> {
+    inner: C,
+    token: CancellationToken,
+}
+
+impl<C> CancellableClient<C> {
+    pub fn new(inner: C, token: CancellationToken) -> Self {
+        Self { inner, token }
+    }
+
+    /// Токен, отмена которого прерывает вызовы
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Обёрнутый клиент, например для вызова потокового API через [`cancellable`]
+    pub fn inner(&self) -> &C {
+        &self.inner
+    }
+
+    async fn run<T>(
+        &self,
+        future: impl Future<Output = types::ClientResult<T>>,
+    ) -> types::ClientResult<T> {
+        cancellable(&self.token, future).await
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C: BlogClient> BlogClient for CancellableClient<C> {
+    async fn login(&self, username: &str, password: &str) -> types::ClientResult<Uuid> {
+        self.run(self.inner.login(username, password)).await
+    }
+
+    async fn login_with_totp(
+        &self,
+        username: &str,
+        password: &str,
+        code: &str,
+    ) -> types::ClientResult<Uuid> {
+        self.run(self.inner.login_with_totp(username, password, code))
+            .await
+    }
+
+    async fn register(
+        &self,
+        username: &str,
+        email: &str,
+        password: &str,
+    ) -> types::ClientResult<()> {
+        self.run(self.inner.register(username, email, password))
+            .await
+    }
+
+    async fn server_info(&self) -> types::ClientResult<types::ServerInfo> {
+        self.run(self.inner.server_info()).await
+    }
+
+    async fn is_username_available(&self, username: &str) -> types::ClientResult<bool> {
+        self.run(self.inner.is_username_available(username)).await
+    }
+
+    async fn logout(&self) -> types::ClientResult<()> {
+        self.run(self.inner.logout()).await
+    }
+
+    async fn logout_all_sessions(&self) -> types::ClientResult<()> {
+        self.run(self.inner.logout_all_sessions()).await
+    }
+
+    async fn list_sessions(&self) -> types::ClientResult<Vec<types::Session>> {
+        self.run(self.inner.list_sessions()).await
+    }
+
+    async fn revoke_session(&self, session_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.revoke_session(session_id)).await
+    }
+
+    async fn forgot_password(&self, email: &str) -> types::ClientResult<()> {
+        self.run(self.inner.forgot_password(email)).await
+    }
+
+    async fn reset_password(&self, token: &str, new_password: &str) -> types::ClientResult<()> {
+        self.run(self.inner.reset_password(token, new_password))
+            .await
+    }
+
+    async fn setup_token(&self, token: &str) -> types::ClientResult<()> {
+        self.run(self.inner.setup_token(token)).await
+    }
+
+    async fn get_token(&self) -> types::ClientResult<Option<String>> {
+        self.run(self.inner.get_token()).await
+    }
+
+    async fn setup_auth_data(&self, auth_data: &types::AuthData) -> types::ClientResult<()> {
+        self.run(self.inner.setup_auth_data(auth_data)).await
+    }
+
+    async fn get_auth_data(&self) -> types::ClientResult<Option<types::AuthData>> {
+        self.run(self.inner.get_auth_data()).await
+    }
+
+    async fn get_user_profile(&self, user_id: &str) -> types::ClientResult<types::UserProfile> {
+        self.run(self.inner.get_user_profile(user_id)).await
+    }
+
+    async fn update_my_profile(
+        &self,
+        display_name: Option<&str>,
+        bio: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> types::ClientResult<types::UserProfile> {
+        self.run(self.inner.update_my_profile(display_name, bio, avatar_url))
+            .await
+    }
+
+    async fn get_digest_settings(&self) -> types::ClientResult<types::DigestSettings> {
+        self.run(self.inner.get_digest_settings()).await
+    }
+
+    async fn update_digest_settings(
+        &self,
+        frequency: Option<types::DigestFrequency>,
+    ) -> types::ClientResult<types::DigestSettings> {
+        self.run(self.inner.update_digest_settings(frequency)).await
+    }
+
+    async fn create_post(
+        &self,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<Uuid> {
+        self.run(
+            self.inner
+                .create_post(title, content, tags, category_id, summary),
+        )
+        .await
+    }
+
+    async fn create_draft(
+        &self,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<Uuid> {
+        self.run(
+            self.inner
+                .create_draft(title, content, tags, category_id, summary),
+        )
+        .await
+    }
+
+    async fn create_posts(&self, posts: Vec<(String, String)>) -> types::ClientResult<Vec<Uuid>> {
+        self.run(self.inner.create_posts(posts)).await
+    }
+
+    async fn get_post(&self, post_id: &str) -> types::ClientResult<types::Post> {
+        self.run(self.inner.get_post(post_id)).await
+    }
+
+    async fn update_post(
+        &self,
+        post_id: &str,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<()> {
+        self.run(
+            self.inner
+                .update_post(post_id, title, content, tags, category_id, summary),
+        )
+        .await
+    }
+
+    async fn delete_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.delete_post(post_id)).await
+    }
+
+    async fn delete_posts(&self, post_ids: Vec<Uuid>) -> types::ClientResult<()> {
+        self.run(self.inner.delete_posts(post_ids)).await
+    }
+
+    async fn restore_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.restore_post(post_id)).await
+    }
+
+    async fn purge_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.purge_post(post_id)).await
+    }
+
+    async fn publish_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.publish_post(post_id)).await
+    }
+
+    async fn unpublish_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.unpublish_post(post_id)).await
+    }
+
+    async fn like_post(&self, post_id: &str) -> types::ClientResult<u64> {
+        self.run(self.inner.like_post(post_id)).await
+    }
+
+    async fn unlike_post(&self, post_id: &str) -> types::ClientResult<u64> {
+        self.run(self.inner.unlike_post(post_id)).await
+    }
+
+    async fn report_post(
+        &self,
+        post_id: &str,
+        reason: types::ReportReason,
+        details: Option<&str>,
+    ) -> types::ClientResult<types::Report> {
+        self.run(self.inner.report_post(post_id, reason, details))
+            .await
+    }
+
+    async fn follow_user(&self, user_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.follow_user(user_id)).await
+    }
+
+    async fn unfollow_user(&self, user_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.unfollow_user(user_id)).await
+    }
+
+    async fn is_following(&self, user_id: &str) -> types::ClientResult<bool> {
+        self.run(self.inner.is_following(user_id)).await
+    }
+
+    async fn get_feed(&self, page_size: u32, page: u32) -> types::ClientResult<types::PostPage> {
+        self.run(self.inner.get_feed(page_size, page)).await
+    }
+
+    async fn list_posts(
+        &self,
+        page_size: u32,
+        page: u32,
+        sort: Option<types::PostSort>,
+    ) -> types::ClientResult<types::PostPage> {
+        self.run(self.inner.list_posts(page_size, page, sort)).await
+    }
+
+    async fn list_posts_by_tag(
+        &self,
+        tag: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.run(self.inner.list_posts_by_tag(tag, page_size, page))
+            .await
+    }
+
+    async fn list_posts_by_category(
+        &self,
+        category_id: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.run(
+            self.inner
+                .list_posts_by_category(category_id, page_size, page),
+        )
+        .await
+    }
+
+    async fn list_categories(&self) -> types::ClientResult<Vec<types::Category>> {
+        self.run(self.inner.list_categories()).await
+    }
+
+    fn select_blog(&self, slug: Option<&str>) {
+        self.inner.select_blog(slug)
+    }
+
+    fn selected_blog(&self) -> Option<String> {
+        self.inner.selected_blog()
+    }
+
+    async fn list_blogs(&self) -> types::ClientResult<Vec<types::Blog>> {
+        self.run(self.inner.list_blogs()).await
+    }
+
+    async fn get_blog(&self, slug: &str) -> types::ClientResult<types::Blog> {
+        self.run(self.inner.get_blog(slug)).await
+    }
+
+    async fn create_blog(&self, slug: &str, name: &str) -> types::ClientResult<types::Blog> {
+        self.run(self.inner.create_blog(slug, name)).await
+    }
+
+    async fn update_blog(&self, slug: &str, name: &str) -> types::ClientResult<types::Blog> {
+        self.run(self.inner.update_blog(slug, name)).await
+    }
+
+    async fn delete_blog(&self, slug: &str) -> types::ClientResult<()> {
+        self.run(self.inner.delete_blog(slug)).await
+    }
+
+    async fn list_posts_after(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.run(self.inner.list_posts_after(cursor, limit)).await
+    }
+
+    async fn list_trending_posts(&self, limit: u32) -> types::ClientResult<Vec<types::Post>> {
+        self.run(self.inner.list_trending_posts(limit)).await
+    }
+
+    async fn list_posts_by_author(
+        &self,
+        author_id: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.run(self.inner.list_posts_by_author(author_id, page_size, page))
+            .await
+    }
+
+    async fn upload_file(
+        &self,
+        file_name: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> types::ClientResult<String> {
+        self.run(self.inner.upload_file(file_name, content_type, data))
+            .await
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<C: AdminClient> AdminClient for CancellableClient<C> {
+    async fn list_users(&self, page_size: u32, page: u32) -> types::ClientResult<types::UserPage> {
+        self.run(self.inner.list_users(page_size, page)).await
+    }
+
+    async fn disable_user(&self, user_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.disable_user(user_id)).await
+    }
+
+    async fn enable_user(&self, user_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.enable_user(user_id)).await
+    }
+
+    async fn force_delete_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.run(self.inner.force_delete_post(post_id)).await
+    }
+
+    async fn system_stats(&self) -> types::ClientResult<types::SystemStats> {
+        self.run(self.inner.system_stats()).await
+    }
+
+    async fn create_category(
+        &self,
+        name: &str,
+        parent_id: Option<&str>,
+    ) -> types::ClientResult<types::Category> {
+        self.run(self.inner.create_category(name, parent_id)).await
+    }
+
+    async fn list_reports(
+        &self,
+        status: Option<types::ReportStatus>,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::ReportPage> {
+        self.run(self.inner.list_reports(status, page_size, page))
+            .await
+    }
+
+    async fn resolve_report(
+        &self,
+        report_id: &str,
+        action: types::ReportAction,
+    ) -> types::ClientResult<types::Report> {
+        self.run(self.inner.resolve_report(report_id, action)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cancelled_token_interrupts_pending_call() {
+        let token = CancellationToken::new();
+        let call = cancellable(&token, std::future::pending::<types::ClientResult<()>>());
+
+        token.cancel();
+
+        assert!(matches!(call.await, Err(ClientError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_active_token_returns_call_result() {
+        let token = CancellationToken::new();
+
+        let result = cancellable(&token, async { Ok(42) }).await;
+
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_shared_client_wraps_and_moves_between_tasks() {
+        let client = crate::http_client::HttpClient::new("http://localhost:8080".to_string())
+            .await
+            .unwrap()
+            .into_dyn();
+        let token = CancellationToken::new();
+        let cancellable = CancellableClient::new(client.clone(), token.clone());
+
+        // Обёртка и исходный Arc работают с одним и тем же клиентом
+        client.select_blog(Some("rust"));
+        assert_eq!(cancellable.selected_blog(), Some("rust".to_string()));
+
+        token.cancel();
+        let result = tokio::spawn(async move { cancellable.list_posts(10, 0, None).await })
+            .await
+            .unwrap();
+        assert!(matches!(result, Err(ClientError::Cancelled)));
+    }
+}
@@ -0,0 +1,85 @@
+//! Настройки подключения клиентов.
+
+use std::time::Duration;
+
+/// Таймаут установки соединения по умолчанию
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Таймаут запроса по умолчанию
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+/// Сколько простаивающее соединение живёт в пуле по умолчанию (как в reqwest)
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// Период TCP keepalive по умолчанию (как в reqwest)
+#[cfg(not(target_arch = "wasm32"))]
+const DEFAULT_TCP_KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// Параметры подключения HTTP и gRPC клиентов.
+///
+/// Истёкший таймаут возвращается как [`ClientError::Timeout`](crate::error::ClientError::Timeout).
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Сколько ждать установки соединения с сервером; `None` - без ограничения.
+    ///
+    /// В браузере не используется: соединениями управляет `fetch`.
+    pub connect_timeout: Option<Duration>,
+    /// Сколько ждать ответа на каждый запрос; `None` - без ограничения.
+    ///
+    /// Поток постов gRPC ограничивается только до начала ответа, лента событий HTTP - не
+    /// ограничивается.
+    pub request_timeout: Option<Duration>,
+    /// Просить сервер сжимать ответы: gzip и brotli для HTTP, gzip для gRPC. Большие
+    /// списки постов при этом передаются в несколько раз быстрее.
+    ///
+    /// В браузере HTTP ответы распаковывает `fetch` независимо от настройки.
+    pub compression: bool,
+    /// Настройки TLS для `https://` адреса
+    #[cfg(not(target_arch = "wasm32"))]
+    pub tls: Option<crate::tls::TlsOptions>,
+    /// Пул соединений HTTP клиента
+    #[cfg(not(target_arch = "wasm32"))]
+    pub pool: PoolOptions,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+            request_timeout: Some(DEFAULT_REQUEST_TIMEOUT),
+            compression: true,
+            #[cfg(not(target_arch = "wasm32"))]
+            tls: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pool: PoolOptions::default(),
+        }
+    }
+}
+
+/// Переиспользование соединений HTTP клиента (reqwest).
+///
+/// Значения по умолчанию совпадают с настройками reqwest. gRPC клиент работает через
+/// одно HTTP/2 соединение и эти настройки не использует; в браузере соединениями
+/// управляет `fetch`.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolOptions {
+    /// Сколько простаивающих соединений держать открытыми для одного хоста
+    pub max_idle_per_host: usize,
+    /// Через сколько закрывать простаивающее соединение; `None` - не закрывать
+    pub idle_timeout: Option<Duration>,
+    /// Сразу говорить по HTTP/2 без согласования: только для серверов с h2c или HTTP/2 по TLS
+    pub http2_prior_knowledge: bool,
+    /// Период TCP keepalive; `None` - не включать keepalive
+    pub tcp_keepalive: Option<Duration>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for PoolOptions {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: usize::MAX,
+            idle_timeout: Some(DEFAULT_POOL_IDLE_TIMEOUT),
+            http2_prior_knowledge: false,
+            tcp_keepalive: Some(DEFAULT_TCP_KEEPALIVE),
+        }
+    }
+}
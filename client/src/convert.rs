@@ -0,0 +1,303 @@
+//! Преобразование ответов REST API в клиентские типы.
+//!
+//! Общее для обоих транспортов: gRPC клиент сначала переводит сообщения protobuf
+//! в типы [`api::rest`] (см. `api::convert`), поэтому поля сопоставляются только здесь.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{error::ClientError, types};
+
+/// Разбирает UUID из ответа сервера
+fn parse_uuid(value: &str) -> types::ClientResult<Uuid> {
+    Uuid::parse_str(value).map_err(|e| ClientError::InternalError(format!("Invalid UUID: {}", e)))
+}
+
+/// Разбирает временную метку RFC 3339; неразобранная метка заменяется текущим временем
+fn parse_datetime(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+impl From<types::PostSortField> for api::rest::PostSortField {
+    fn from(field: types::PostSortField) -> Self {
+        match field {
+            types::PostSortField::CreatedAt => api::rest::PostSortField::CreatedAt,
+            types::PostSortField::UpdatedAt => api::rest::PostSortField::UpdatedAt,
+            types::PostSortField::Title => api::rest::PostSortField::Title,
+        }
+    }
+}
+
+impl From<types::SortOrder> for api::rest::SortOrder {
+    fn from(order: types::SortOrder) -> Self {
+        match order {
+            types::SortOrder::Asc => api::rest::SortOrder::Asc,
+            types::SortOrder::Desc => api::rest::SortOrder::Desc,
+        }
+    }
+}
+
+impl From<types::ReportReason> for api::rest::ReportReason {
+    fn from(reason: types::ReportReason) -> Self {
+        match reason {
+            types::ReportReason::Spam => api::rest::ReportReason::Spam,
+            types::ReportReason::Harassment => api::rest::ReportReason::Harassment,
+            types::ReportReason::HateSpeech => api::rest::ReportReason::HateSpeech,
+            types::ReportReason::Misinformation => api::rest::ReportReason::Misinformation,
+            types::ReportReason::Inappropriate => api::rest::ReportReason::Inappropriate,
+            types::ReportReason::Other => api::rest::ReportReason::Other,
+        }
+    }
+}
+
+impl From<api::rest::ReportReason> for types::ReportReason {
+    fn from(reason: api::rest::ReportReason) -> Self {
+        match reason {
+            api::rest::ReportReason::Spam => types::ReportReason::Spam,
+            api::rest::ReportReason::Harassment => types::ReportReason::Harassment,
+            api::rest::ReportReason::HateSpeech => types::ReportReason::HateSpeech,
+            api::rest::ReportReason::Misinformation => types::ReportReason::Misinformation,
+            api::rest::ReportReason::Inappropriate => types::ReportReason::Inappropriate,
+            api::rest::ReportReason::Other => types::ReportReason::Other,
+        }
+    }
+}
+
+impl From<types::ReportStatus> for api::rest::ReportStatus {
+    fn from(status: types::ReportStatus) -> Self {
+        match status {
+            types::ReportStatus::Open => api::rest::ReportStatus::Open,
+            types::ReportStatus::Hidden => api::rest::ReportStatus::Hidden,
+            types::ReportStatus::Dismissed => api::rest::ReportStatus::Dismissed,
+        }
+    }
+}
+
+impl From<types::ReportAction> for api::rest::ReportAction {
+    fn from(action: types::ReportAction) -> Self {
+        match action {
+            types::ReportAction::HidePost => api::rest::ReportAction::HidePost,
+            types::ReportAction::Dismiss => api::rest::ReportAction::Dismiss,
+        }
+    }
+}
+
+impl From<types::DigestFrequency> for api::rest::DigestFrequency {
+    fn from(frequency: types::DigestFrequency) -> Self {
+        match frequency {
+            types::DigestFrequency::Daily => api::rest::DigestFrequency::Daily,
+            types::DigestFrequency::Weekly => api::rest::DigestFrequency::Weekly,
+        }
+    }
+}
+
+impl From<api::rest::DigestFrequency> for types::DigestFrequency {
+    fn from(frequency: api::rest::DigestFrequency) -> Self {
+        match frequency {
+            api::rest::DigestFrequency::Daily => types::DigestFrequency::Daily,
+            api::rest::DigestFrequency::Weekly => types::DigestFrequency::Weekly,
+        }
+    }
+}
+
+impl From<api::rest::DigestSettingsResponse> for types::DigestSettings {
+    fn from(settings: api::rest::DigestSettingsResponse) -> Self {
+        Self {
+            frequency: settings.frequency.map(Into::into),
+            last_sent_at: settings.last_sent_at.as_deref().map(parse_datetime),
+        }
+    }
+}
+
+/// Преобразует [`api::rest::PostResponse`] в клиентский тип поста.
+pub(crate) fn post_from_response(
+    post_response: api::rest::PostResponse,
+) -> types::ClientResult<types::Post> {
+    let id = parse_uuid(&post_response.uuid)?;
+
+    // Автор может отсутствовать в ответе gRPC сервера старой версии
+    let author_id = if post_response.author_id.is_empty() {
+        Uuid::nil()
+    } else {
+        parse_uuid(&post_response.author_id)?
+    };
+
+    Ok(types::Post {
+        id,
+        title: post_response.title,
+        content: post_response.content.unwrap_or_default(),
+        content_html: post_response.content_html,
+        summary: post_response.summary,
+        author_id,
+        author_username: post_response.author_username,
+        author_avatar_url: post_response.author_avatar_url,
+        tags: post_response.tags,
+        category_id: post_response
+            .category_id
+            .as_deref()
+            .map(parse_uuid)
+            .transpose()?,
+        blog_id: post_response
+            .blog_id
+            .as_deref()
+            .map(parse_uuid)
+            .transpose()?,
+        attachments: post_response.attachments,
+        status: match post_response.status {
+            api::rest::PostStatus::Draft => types::PostStatus::Draft,
+            api::rest::PostStatus::Published => types::PostStatus::Published,
+        },
+        like_count: post_response.like_count,
+        liked_by_me: post_response.liked_by_me,
+        created_at: parse_datetime(&post_response.created_at),
+        updated_at: parse_datetime(&post_response.updated_at),
+    })
+}
+
+/// Преобразует [`api::rest::CategoryResponse`] в клиентский тип категории.
+pub(crate) fn category_from_response(
+    category_response: api::rest::CategoryResponse,
+) -> types::ClientResult<types::Category> {
+    Ok(types::Category {
+        id: parse_uuid(&category_response.id)?,
+        name: category_response.name,
+        parent_id: category_response
+            .parent_id
+            .as_deref()
+            .map(parse_uuid)
+            .transpose()?,
+        created_at: parse_datetime(&category_response.created_at),
+    })
+}
+
+/// Преобразует [`api::rest::BlogResponse`] в клиентский тип блога.
+pub(crate) fn blog_from_response(
+    blog_response: api::rest::BlogResponse,
+) -> types::ClientResult<types::Blog> {
+    Ok(types::Blog {
+        id: parse_uuid(&blog_response.id)?,
+        slug: blog_response.slug,
+        name: blog_response.name,
+        owner_id: parse_uuid(&blog_response.owner_id)?,
+        created_at: parse_datetime(&blog_response.created_at),
+    })
+}
+
+/// Преобразует [`api::rest::UserProfileResponse`] в клиентский тип профиля.
+pub(crate) fn profile_from_response(
+    profile_response: api::rest::UserProfileResponse,
+) -> types::ClientResult<types::UserProfile> {
+    Ok(types::UserProfile {
+        id: parse_uuid(&profile_response.id)?,
+        username: profile_response.username,
+        display_name: profile_response.display_name,
+        bio: profile_response.bio,
+        avatar_url: profile_response.avatar_url,
+        created_at: parse_datetime(&profile_response.created_at),
+    })
+}
+
+/// Преобразует [`api::rest::SessionResponse`] в клиентский тип сеанса.
+pub(crate) fn session_from_response(
+    session_response: api::rest::SessionResponse,
+) -> types::ClientResult<types::Session> {
+    Ok(types::Session {
+        id: parse_uuid(&session_response.id)?,
+        user_agent: session_response.user_agent,
+        ip_address: session_response.ip_address,
+        created_at: parse_datetime(&session_response.created_at),
+        last_used_at: parse_datetime(&session_response.last_used_at),
+        current: session_response.current,
+    })
+}
+
+/// Преобразует [`api::rest::AdminUserResponse`] в клиентский тип пользователя.
+pub(crate) fn admin_user_from_response(
+    user_response: api::rest::AdminUserResponse,
+) -> types::ClientResult<types::AdminUser> {
+    Ok(types::AdminUser {
+        id: parse_uuid(&user_response.id)?,
+        username: user_response.username,
+        email: user_response.email,
+        role: match user_response.role {
+            api::rest::UserRole::User => types::UserRole::User,
+            api::rest::UserRole::Moderator => types::UserRole::Moderator,
+            api::rest::UserRole::Admin => types::UserRole::Admin,
+        },
+        created_at: parse_datetime(&user_response.created_at),
+        disabled_at: user_response.disabled_at.as_deref().map(parse_datetime),
+    })
+}
+
+/// Собирает страницу пользователей; следующая страница есть, если пользователей
+/// больше, чем на этой и предыдущих страницах.
+pub(crate) fn user_page_from_response(
+    users: Vec<api::rest::AdminUserResponse>,
+    total: u64,
+    page: u32,
+    page_size: u32,
+) -> types::ClientResult<types::UserPage> {
+    Ok(types::UserPage {
+        users: users
+            .into_iter()
+            .map(admin_user_from_response)
+            .collect::<Result<Vec<_>, _>>()?,
+        total,
+        page,
+        page_size,
+        has_next: (page as u64 + 1) * (page_size as u64) < total,
+    })
+}
+
+/// Преобразует [`api::rest::ReportResponse`] в клиентский тип жалобы.
+pub(crate) fn report_from_response(
+    report_response: api::rest::ReportResponse,
+) -> types::ClientResult<types::Report> {
+    Ok(types::Report {
+        id: parse_uuid(&report_response.id)?,
+        post_id: parse_uuid(&report_response.post_id)?,
+        reporter_id: parse_uuid(&report_response.reporter_id)?,
+        reason: report_response.reason.into(),
+        details: report_response.details,
+        status: match report_response.status {
+            api::rest::ReportStatus::Open => types::ReportStatus::Open,
+            api::rest::ReportStatus::Hidden => types::ReportStatus::Hidden,
+            api::rest::ReportStatus::Dismissed => types::ReportStatus::Dismissed,
+        },
+        created_at: parse_datetime(&report_response.created_at),
+        resolved_at: report_response.resolved_at.as_deref().map(parse_datetime),
+    })
+}
+
+/// Собирает страницу жалоб; следующая страница есть, если жалоб больше, чем на
+/// этой и предыдущих страницах.
+pub(crate) fn report_page_from_response(
+    reports: Vec<api::rest::ReportResponse>,
+    total: u64,
+    page: u32,
+    page_size: u32,
+) -> types::ClientResult<types::ReportPage> {
+    Ok(types::ReportPage {
+        reports: reports
+            .into_iter()
+            .map(report_from_response)
+            .collect::<Result<Vec<_>, _>>()?,
+        total,
+        page,
+        page_size,
+        has_next: (page as u64 + 1) * (page_size as u64) < total,
+    })
+}
+
+impl From<api::rest::SystemStatsResponse> for types::SystemStats {
+    fn from(stats: api::rest::SystemStatsResponse) -> Self {
+        Self {
+            users: stats.users,
+            posts: stats.posts,
+            recent_signups: stats.recent_signups,
+            recent_signups_days: stats.recent_signups_days,
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use thiserror::Error;
 
 /// Ошибки клиента при взаимодействии с API.
@@ -15,6 +17,67 @@ pub enum ClientError {
     #[error("Invalid Request: {0}")]
     InvalidRequest(String),
 
+    /// Поля запроса не прошли валидацию на клиенте или на сервере
+    #[error("Validation Error: {message}")]
+    Validation {
+        /// Описание ошибки вместе с ошибками полей
+        message: String,
+        /// Ошибки отдельных полей (`поле -> сообщения`)
+        details: BTreeMap<String, Vec<String>>,
+    },
+
+    /// Действие запрещено (HTTP 403)
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    /// Конфликт с текущим состоянием ресурса (HTTP 409)
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    /// Превышен лимит запросов (HTTP 429, gRPC `RESOURCE_EXHAUSTED`)
+    #[error("Too Many Requests{}", retry_hint(*retry_after))]
+    RateLimited {
+        /// Через сколько секунд можно повторить запрос, если сервер это сообщил
+        retry_after: Option<u64>,
+    },
+
+    /// Вход заблокирован после серии неудачных попыток (HTTP 423, gRPC `ACCOUNT_LOCKED`)
+    #[error("Account Locked{}", retry_hint(*retry_after))]
+    AccountLocked {
+        /// Через сколько секунд блокировка будет снята, если сервер это сообщил
+        retry_after: Option<u64>,
+    },
+
+    /// Превышена суточная квота постов или ограничение размера поста
+    /// (HTTP 429 или 403, gRPC `QUOTA_EXCEEDED`)
+    #[error("Quota Exceeded: {message}{}", retry_hint(*retry_after))]
+    QuotaExceeded {
+        /// Какое ограничение превышено
+        message: String,
+        /// Через сколько секунд квота восстановится; `None`, если ожидание не поможет
+        /// или сервер не сообщил срок
+        retry_after: Option<u64>,
+    },
+
+    /// Пароль принят, но для входа нужен код двухфакторной аутентификации
+    #[error("Two-factor authentication code required")]
+    TwoFactorRequired {
+        /// Токен второго шага входа
+        challenge_token: String,
+    },
+
+    /// Сервер не обслуживает версию API этого клиента
+    #[error(
+        "Incompatible server {server_version}: supports API {api_versions:?}, client needs {}",
+        api::version::API_VERSION
+    )]
+    IncompatibleServer {
+        /// Версия сервера
+        server_version: String,
+        /// Версии API, которые обслуживает сервер
+        api_versions: Vec<String>,
+    },
+
     /// Внутренняя ошибка сервера (HTTP 500)
     #[error("Internal Error: {0}")]
     InternalError(String),
@@ -22,25 +85,189 @@ pub enum ClientError {
     /// Ошибка на уровне транспорта (сеть, протокол)
     #[error("Transport Error: {0}")]
     TransportError(String),
+
+    /// Не удалось прочитать или записать токены в [`TokenStore`](crate::token_store::TokenStore)
+    #[error("Token Storage Error: {0}")]
+    TokenStoreError(String),
+
+    /// Истёк таймаут подключения или запроса (см. [`ClientConfig`](crate::config::ClientConfig))
+    #[error("Request timed out")]
+    Timeout,
+
+    /// Вызов отменён через [`CancellationToken`](crate::cancellation::CancellationToken)
+    #[error("Request cancelled")]
+    Cancelled,
 }
 
-#[cfg(feature = "grpc")]
+impl ClientError {
+    /// Запрос не дошёл до сервера или ответ не был получен: повтор может быть успешным.
+    pub fn is_network_error(&self) -> bool {
+        matches!(self, ClientError::TransportError(_) | ClientError::Timeout)
+    }
+}
+
+/// Внутренняя ошибка сервера с ID запроса, который можно указать в отчёте об ошибке
+pub(crate) fn internal_error(message: String, trace_id: Option<String>) -> ClientError {
+    match trace_id {
+        Some(trace_id) => ClientError::InternalError(format!("{message} (trace_id: {trace_id})")),
+        None => ClientError::InternalError(message),
+    }
+}
+
+fn retry_hint(retry_after: Option<u64>) -> String {
+    retry_after
+        .map(|secs| format!(", retry in {secs} s"))
+        .unwrap_or_default()
+}
+
+#[cfg(all(feature = "grpc", not(target_arch = "wasm32")))]
 impl From<tonic::transport::Error> for ClientError {
     fn from(value: tonic::transport::Error) -> Self {
+        if is_timeout(&value) {
+            return ClientError::Timeout;
+        }
         ClientError::TransportError(value.to_string())
     }
 }
 
+/// Вызвана ли ошибка истёкшим таймаутом (ищется по цепочке причин)
+#[cfg(all(feature = "grpc", not(target_arch = "wasm32")))]
+fn is_timeout(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if error.is::<tonic::TimeoutExpired>()
+            || error
+                .downcast_ref::<std::io::Error>()
+                .is_some_and(|e| e.kind() == std::io::ErrorKind::TimedOut)
+        {
+            return true;
+        }
+        source = error.source();
+    }
+    false
+}
+
 #[cfg(feature = "grpc")]
 impl From<tonic::Status> for ClientError {
+    /// Переводит код gRPC в вариант ошибки; остальные коды (например `UNAVAILABLE`)
+    /// остаются ошибкой транспорта с полным текстом статуса, включая metadata.
     fn from(value: tonic::Status) -> Self {
-        ClientError::TransportError(value.to_string())
+        let message = value.message().to_string();
+        match value.code() {
+            tonic::Code::NotFound => ClientError::NotFound,
+            tonic::Code::Unauthenticated => ClientError::Unauthorized,
+            tonic::Code::PermissionDenied => ClientError::Forbidden(message),
+            tonic::Code::InvalidArgument | tonic::Code::OutOfRange => {
+                ClientError::InvalidRequest(message)
+            }
+            tonic::Code::AlreadyExists | tonic::Code::Aborted => ClientError::Conflict(message),
+            tonic::Code::ResourceExhausted => {
+                let retry_after = value
+                    .metadata()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse().ok());
+                ClientError::RateLimited { retry_after }
+            }
+            tonic::Code::DeadlineExceeded => ClientError::Timeout,
+            // Таймаут канала tonic возвращает как CANCELLED с текстом TimeoutExpired
+            tonic::Code::Cancelled if message == tonic::TimeoutExpired(()).to_string() => {
+                ClientError::Timeout
+            }
+            tonic::Code::Internal | tonic::Code::Unknown | tonic::Code::DataLoss => {
+                ClientError::InternalError(message)
+            }
+            _ if value.metadata().is_empty() => ClientError::TransportError(value.to_string()),
+            // `Display` статуса не включает metadata, добавляем её для диагностики
+            _ => ClientError::TransportError(format!(
+                "{}, metadata: {:?}",
+                value,
+                value.metadata().clone().into_headers()
+            )),
+        }
     }
 }
 
 #[cfg(feature = "http")]
 impl From<reqwest::Error> for ClientError {
     fn from(value: reqwest::Error) -> Self {
+        if value.is_timeout() {
+            return ClientError::Timeout;
+        }
         ClientError::TransportError(value.to_string())
     }
 }
+
+#[cfg(any(feature = "http", feature = "grpc"))]
+impl From<api::rest::ValidationErrors> for ClientError {
+    fn from(value: api::rest::ValidationErrors) -> Self {
+        let details = api::rest::field_errors(&value);
+        let message = details
+            .iter()
+            .map(|(field, messages)| format!("{}: {}", field, messages.join(", ")))
+            .collect::<Vec<_>>()
+            .join("; ");
+        ClientError::Validation { message, details }
+    }
+}
+
+#[cfg(all(test, feature = "grpc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_maps_to_typed_error() {
+        let error = ClientError::from(tonic::Status::not_found("post"));
+        assert!(matches!(error, ClientError::NotFound));
+
+        let error = ClientError::from(tonic::Status::unauthenticated("expired"));
+        assert!(matches!(error, ClientError::Unauthorized));
+
+        let error = ClientError::from(tonic::Status::permission_denied("not the author"));
+        assert!(matches!(error, ClientError::Forbidden(message) if message == "not the author"));
+
+        let error = ClientError::from(tonic::Status::invalid_argument("bad uuid"));
+        assert!(matches!(error, ClientError::InvalidRequest(message) if message == "bad uuid"));
+
+        let error = ClientError::from(tonic::Status::deadline_exceeded("slow"));
+        assert!(matches!(error, ClientError::Timeout));
+    }
+
+    #[test]
+    fn test_status_keeps_metadata_for_unmapped_codes() {
+        let mut status = tonic::Status::unavailable("server is restarting");
+        status
+            .metadata_mut()
+            .insert("x-request-id", "42".parse().unwrap());
+
+        let ClientError::TransportError(text) = ClientError::from(status) else {
+            panic!("UNAVAILABLE must stay a transport error");
+        };
+        assert!(text.contains("server is restarting"));
+        assert!(text.contains("x-request-id"));
+    }
+
+    #[test]
+    fn test_network_errors_are_retryable() {
+        assert!(ClientError::from(tonic::Status::unavailable("down")).is_network_error());
+        assert!(ClientError::from(tonic::Status::deadline_exceeded("slow")).is_network_error());
+        assert!(!ClientError::from(tonic::Status::invalid_argument("bad")).is_network_error());
+        assert!(!ClientError::from(tonic::Status::not_found("post")).is_network_error());
+    }
+
+    #[test]
+    fn test_resource_exhausted_reads_retry_after() {
+        let mut status = tonic::Status::resource_exhausted("slow down");
+        status
+            .metadata_mut()
+            .insert("retry-after", "7".parse().unwrap());
+
+        let error = ClientError::from(status);
+        assert!(matches!(
+            error,
+            ClientError::RateLimited {
+                retry_after: Some(7)
+            }
+        ));
+    }
+}
@@ -0,0 +1,174 @@
+//! gRPC канал с ленивым подключением и переподключением.
+//!
+//! Канал создаётся без обращения к серверу, поэтому клиент можно создать, пока
+//! сервер ещё не запущен. Если запрос завершился ошибкой транспорта, в фоне
+//! устанавливается новое соединение с экспоненциальной паузой между попытками;
+//! после успеха все клоны канала переходят на него.
+
+use std::future::{Future, poll_fn};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock, Weak};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::sync::watch;
+use tonic::body::Body;
+use tonic::codegen::{Service, http};
+use tonic::transport::{Channel, Endpoint, Error};
+
+/// Пауза перед повторной попыткой подключения после первой неудачи
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(100);
+/// Максимальная пауза между попытками подключения
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+/// Канал, с которым работает сгенерированный gRPC клиент
+#[derive(Clone)]
+pub struct ReconnectingChannel {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    endpoint: Endpoint,
+    channel: RwLock<Channel>,
+    /// Последний запрос дошёл до сервера или соединение установлено заново
+    connected: watch::Sender<bool>,
+    /// Идёт фоновое переподключение
+    reconnecting: AtomicBool,
+}
+
+impl ReconnectingChannel {
+    /// Создаёт канал без подключения: соединение устанавливается при первом запросе.
+    pub fn new(endpoint: Endpoint) -> Self {
+        let channel = endpoint.connect_lazy();
+        Self {
+            shared: Arc::new(Shared {
+                endpoint,
+                channel: RwLock::new(channel),
+                connected: watch::Sender::new(false),
+                reconnecting: AtomicBool::new(false),
+            }),
+        }
+    }
+
+    /// Соединение с сервером установлено и последний запрос не потерпел ошибку транспорта
+    pub fn is_connected(&self) -> bool {
+        *self.shared.connected.borrow()
+    }
+
+    /// Ждёт соединения с сервером не дольше `timeout`, при необходимости подключаясь.
+    ///
+    /// Возвращает `false`, если за отведённое время подключиться не удалось.
+    pub async fn wait_for_ready(&self, timeout: Duration) -> bool {
+        let mut connected = self.shared.connected.subscribe();
+        if *connected.borrow_and_update() {
+            return true;
+        }
+        Shared::reconnect(&self.shared);
+        matches!(
+            tokio::time::timeout(timeout, connected.wait_for(|connected| *connected)).await,
+            Ok(Ok(_))
+        )
+    }
+}
+
+impl Shared {
+    fn channel(&self) -> Channel {
+        self.channel
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn set_connected(&self, connected: bool) {
+        self.connected.send_if_modified(|current| {
+            let changed = *current != connected;
+            *current = connected;
+            changed
+        });
+    }
+
+    /// Запускает фоновое переподключение, если оно ещё не идёт.
+    ///
+    /// Задача держит только слабую ссылку и завершается вместе с клиентом.
+    fn reconnect(shared: &Arc<Self>) {
+        if shared.reconnecting.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let weak = Arc::downgrade(shared);
+        tokio::spawn(async move {
+            let mut delay = INITIAL_RECONNECT_DELAY;
+            while let Some(shared) = Weak::upgrade(&weak) {
+                let endpoint = shared.endpoint.clone();
+                drop(shared);
+
+                let result = endpoint.connect().await;
+                let Some(shared) = Weak::upgrade(&weak) else {
+                    return;
+                };
+                if let Ok(channel) = result {
+                    *shared
+                        .channel
+                        .write()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner()) = channel;
+                    shared.set_connected(true);
+                    shared.reconnecting.store(false, Ordering::Release);
+                    return;
+                }
+                drop(shared);
+
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+        });
+    }
+}
+
+impl Service<http::Request<Body>> for ReconnectingChannel {
+    type Response = http::Response<Body>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Готовность проверяется у текущего канала при вызове: он мог смениться
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        let shared = self.shared.clone();
+        let mut channel = shared.channel();
+
+        Box::pin(async move {
+            let result = match poll_fn(|cx| channel.poll_ready(cx)).await {
+                Ok(()) => channel.call(request).await,
+                Err(e) => Err(e),
+            };
+            match &result {
+                // Ответ получен, даже если это gRPC ошибка: соединение работает
+                Ok(_) => shared.set_connected(true),
+                Err(_) => {
+                    shared.set_connected(false);
+                    Shared::reconnect(&shared);
+                }
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lazy_channel_does_not_connect_until_ready() {
+        // На порту 1 сервер не запущен: создание канала всё равно успешно
+        let endpoint =
+            Endpoint::from_static("http://127.0.0.1:1").connect_timeout(Duration::from_millis(50));
+        let channel = ReconnectingChannel::new(endpoint);
+
+        assert!(!channel.is_connected());
+        assert!(!channel.wait_for_ready(Duration::from_millis(200)).await);
+        assert!(!channel.is_connected());
+    }
+}
@@ -1,31 +1,160 @@
 use api::blog_client::BlogClient as BlogGrpcClient;
+use api::rest::Validate;
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use futures_util::{Stream, StreamExt};
 use prost_types::Timestamp;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use tonic::Request;
+use tonic::codec::CompressionEncoding;
 use tonic::metadata::MetadataValue;
+#[cfg(not(target_arch = "wasm32"))]
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint};
 use uuid::Uuid;
 
 use crate::{
-    blog_client::BlogClient, error::ClientError, interceptor::decode_token_without_validation,
-    token_manager::TokenManager, types,
+    admin::AdminClient,
+    blog_client::BlogClient,
+    config::ClientConfig,
+    convert::{
+        blog_from_response, category_from_response, post_from_response, profile_from_response,
+        report_from_response, report_page_from_response, session_from_response,
+        user_page_from_response,
+    },
+    error::{ClientError, internal_error},
+    interceptor::{ExpectedClaims, decode_token_without_validation},
+    token_manager::{TokenManager, TokenUpdateEvent, mpsc},
+    token_store::TokenStore,
+    types,
 };
 
+/// Транспорт gRPC: HTTP/2 канал tonic с переподключением или gRPC-Web поверх `fetch` в браузере
+#[cfg(not(target_arch = "wasm32"))]
+type Transport = crate::grpc_channel::ReconnectingChannel;
+#[cfg(target_arch = "wasm32")]
+type Transport = crate::grpc_web::GrpcWebChannel;
+
 pub struct GrpcClient {
-    client: BlogGrpcClient<tonic::transport::Channel>,
+    client: BlogGrpcClient<Transport>,
+    #[cfg(not(target_arch = "wasm32"))]
+    channel: Transport,
     token_manager: TokenManager,
+    request_timeout: Option<Duration>,
+    /// Адрес блога из [`BlogClient::select_blog`]
+    selected_blog: RwLock<Option<String>>,
 }
 
 impl GrpcClient {
     pub async fn new(url: String) -> Result<Self, ClientError> {
-        let client = BlogGrpcClient::connect(url).await?;
+        Self::new_with_config(url, &ClientConfig::default()).await
+    }
+
+    /// Создает GrpcClient с таймаутами и настройками TLS из `config`.
+    ///
+    /// Соединение устанавливается при первом запросе, поэтому клиент создается,
+    /// даже если сервер еще не запущен; ошибкой завершаются только неверный URL
+    /// или настройки TLS.
+    pub async fn new_with_config(url: String, config: &ClientConfig) -> Result<Self, ClientError> {
+        let channel = Self::connect(url, config).await?;
+        let mut client = BlogGrpcClient::new(channel.clone());
+        if config.compression {
+            // Запросы не сжимаются: они небольшие, а сервер может не поддерживать сжатие
+            client = client.accept_compressed(CompressionEncoding::Gzip);
+        }
         Ok(Self {
             client,
+            #[cfg(not(target_arch = "wasm32"))]
+            channel,
             token_manager: TokenManager::new(300), // Обновлять токен за 5 минут до истечения
+            request_timeout: config.request_timeout,
+            selected_blog: RwLock::new(None),
         })
     }
 
-    pub async fn set_token(&self, token: &str) {
+    /// Создает GrpcClient с поддержкой уведомлений об обновлении токена
+    pub async fn new_with_token_notifier(
+        url: String,
+        token_sender: mpsc::UnboundedSender<TokenUpdateEvent>,
+    ) -> Result<Self, ClientError> {
+        let mut client = Self::new(url).await?;
+        client.set_token_notifier(token_sender);
+        Ok(client)
+    }
+
+    /// Создает ленивый канал; при заданных `config.tls` - по TLS.
+    ///
+    /// После ошибки транспорта канал переподключается в фоне, см. [`crate::grpc_channel`].
+    ///
+    /// Сертификат сервера проверяется по корневым сертификатам webpki и CA из `tls`.
+    /// Таймаут запроса проверяется и на клиенте: сервер может не ответить вовсе.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn connect(url: String, config: &ClientConfig) -> Result<Transport, ClientError> {
+        let mut endpoint = Endpoint::from_shared(url)?;
+        if let Some(connect_timeout) = config.connect_timeout {
+            endpoint = endpoint.connect_timeout(connect_timeout);
+        }
+        if let Some(request_timeout) = config.request_timeout {
+            endpoint = endpoint.timeout(request_timeout);
+        }
+
+        if let Some(tls) = &config.tls {
+            let mut tls_config = ClientTlsConfig::new().with_webpki_roots();
+            if let Some(pem) = &tls.ca_certificate_pem {
+                tls_config = tls_config.ca_certificate(Certificate::from_pem(pem));
+            }
+            endpoint = if tls.danger_accept_invalid_certs {
+                endpoint.tls_config_with_verifier(
+                    tls_config,
+                    Arc::new(crate::tls::NoCertificateVerification::new()),
+                )?
+            } else {
+                endpoint.tls_config(tls_config)?
+            };
+        }
+
+        Ok(crate::grpc_channel::ReconnectingChannel::new(endpoint))
+    }
+
+    /// В браузере соединение не устанавливается заранее: каждый вызов - отдельный `fetch`
+    #[cfg(target_arch = "wasm32")]
+    async fn connect(url: String, _config: &ClientConfig) -> Result<Transport, ClientError> {
+        crate::grpc_web::channel(url)
+    }
+
+    /// Подключается к gRPC серверу по TLS
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn new_with_tls(
+        url: String,
+        tls: &crate::tls::TlsOptions,
+    ) -> Result<Self, ClientError> {
+        let config = ClientConfig {
+            tls: Some(tls.clone()),
+            ..ClientConfig::default()
+        };
+        Self::new_with_config(url, &config).await
+    }
+
+    /// Соединение с сервером установлено и последний запрос не потерпел ошибку транспорта
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn is_connected(&self) -> bool {
+        self.channel.is_connected()
+    }
+
+    /// Ждет соединения с сервером не дольше `timeout`.
+    ///
+    /// Если соединения нет, сразу начинает подключение, не дожидаясь запроса.
+    /// Возвращает [`ClientError::Timeout`], если сервер не стал доступен за это время.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn wait_for_ready(&self, timeout: Duration) -> Result<(), ClientError> {
+        if self.channel.wait_for_ready(timeout).await {
+            Ok(())
+        } else {
+            Err(ClientError::Timeout)
+        }
+    }
+
+    pub async fn set_token(&self, token: &str) -> Result<(), ClientError> {
         // Сохраняем существующий refresh_token, если он есть
         let existing_refresh_token = self
             .token_manager
@@ -38,7 +167,7 @@ impl GrpcClient {
                 access_token: token.to_string(),
                 refresh_token: existing_refresh_token,
             })
-            .await;
+            .await
     }
 
     pub async fn get_token(&self) -> Option<String> {
@@ -50,24 +179,80 @@ impl GrpcClient {
         self.token_manager.set_token_refresh_buffer(seconds);
     }
 
+    /// Подключает хранилище токенов и восстанавливает из него сохранённый вход
+    pub fn set_token_store(&mut self, store: Arc<dyn TokenStore>) -> Result<(), ClientError> {
+        self.token_manager.set_store(store)
+    }
+
+    /// Включает уведомления об обновлении токена
+    pub fn set_token_notifier(&mut self, token_sender: mpsc::UnboundedSender<TokenUpdateEvent>) {
+        self.token_manager.set_notifier(token_sender);
+    }
+
+    /// Включает уведомления об истечении сессии: сервер отклонил refresh токен
+    pub fn set_session_expired_notifier(&mut self, sender: mpsc::UnboundedSender<()>) {
+        self.token_manager.set_session_expired_notifier(sender);
+    }
+
+    /// Задаёт ожидаемых издателя и аудиторию токенов (`jwt_issuer` и `jwt_audience` сервера);
+    /// токен с другими значениями не принимается
+    pub fn set_expected_token_claims(&mut self, issuer: Option<String>, audience: Option<String>) {
+        self.token_manager
+            .set_expected_claims(ExpectedClaims { issuer, audience });
+    }
+
+    /// Сохраняет токены из ответа на вход и возвращает ID пользователя
+    async fn complete_login(&self, response: api::LoginResponse) -> types::ClientResult<Uuid> {
+        check_response(response.status)?;
+
+        if let Some(challenge_token) = response.two_factor_challenge {
+            return Err(ClientError::TwoFactorRequired { challenge_token });
+        }
+
+        let token_container = response
+            .token
+            .ok_or_else(|| ClientError::InternalError("No token in response".to_string()))?;
+
+        // Декодируем токен для получения user ID
+        let claims = decode_token_without_validation(
+            &token_container.access_token,
+            self.token_manager.expected_claims(),
+        )?;
+        let user_id = Uuid::parse_str(&claims.sub).unwrap_or(Uuid::nil());
+
+        // Сохраняем токены для последующих запросов
+        let auth_data = types::AuthData {
+            access_token: token_container.access_token,
+            refresh_token: token_container.refresh_token,
+        };
+        self.token_manager.set_auth_data(auth_data).await?;
+
+        Ok(user_id)
+    }
+
     /// Проверяет токен и обновляет его при необходимости
     async fn ensure_valid_token(&self) -> Result<(), ClientError> {
         let client = self.client.clone();
+        let request_timeout = self.request_timeout;
         self.token_manager
             .ensure_valid_token(|refresh_token| async move {
-                Self::refresh_auth_token_internal(client, refresh_token).await
+                Self::refresh_auth_token_internal(client, request_timeout, refresh_token).await
             })
             .await
     }
 
     /// Внутренний метод для обновления токена через gRPC
     async fn refresh_auth_token_internal(
-        mut client: BlogGrpcClient<tonic::transport::Channel>,
+        mut client: BlogGrpcClient<Transport>,
+        request_timeout: Option<Duration>,
         refresh_token: types::Token,
     ) -> types::ClientResult<types::AuthData> {
-        let request = Request::new(api::RefreshTokenRequest {
-            refresh_token: refresh_token.clone(),
-        });
+        let request = with_timeout(
+            Request::new(api::RefreshTokenRequest {
+                refresh_token: refresh_token.clone(),
+            }),
+            request_timeout,
+        );
 
         let response = client.refresh_token(request).await?.into_inner();
 
@@ -83,7 +268,16 @@ impl GrpcClient {
         })
     }
 
+    /// Запрос с токеном и таймаутом из [`ClientConfig::request_timeout`]
     async fn create_request<T>(&self, message: T) -> Result<Request<T>, ClientError> {
+        Ok(with_timeout(
+            self.create_stream_request(message).await?,
+            self.request_timeout,
+        ))
+    }
+
+    /// Запрос с токеном без срока выполнения: сервер закрыл бы поток по его истечении
+    async fn create_stream_request<T>(&self, message: T) -> Result<Request<T>, ClientError> {
         let auth_data = self.token_manager.get_auth_data().await;
         let mut request = Request::new(message);
 
@@ -97,17 +291,212 @@ impl GrpcClient {
         Ok(request)
     }
 
+    /// Получает все видимые посты потоком, при указании `tag` - только с этим тегом.
+    ///
+    /// Сервер читает посты из базы порциями по `chunk_size` (0 - размер по умолчанию)
+    /// и отправляет их по одному, поэтому весь список не держится в памяти целиком.
+    pub async fn stream_posts(
+        &self,
+        tag: Option<&str>,
+        chunk_size: u32,
+    ) -> types::ClientResult<impl Stream<Item = types::ClientResult<types::Post>> + use<>> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_stream_request(api::StreamPostsRequest {
+                chunk_size,
+                tag: tag.map(str::to_string),
+            })
+            .await?;
+
+        let stream = self
+            .client
+            .clone()
+            .stream_posts(request)
+            .await?
+            .into_inner();
+
+        Ok(stream.map(|post| {
+            post.map_err(ClientError::from)
+                .and_then(proto_post_to_client_post)
+        }))
+    }
+
+    /// Запрашивает страницу постов в порядке `sort`, при указании `tag` - только с этим тегом,
+    /// при указании `category` - только из этой категории и её подкатегорий
+    async fn fetch_posts(
+        &self,
+        page_size: u32,
+        page: u32,
+        tag: Option<&str>,
+        category: Option<&str>,
+        sort: types::PostSort,
+    ) -> types::ClientResult<types::PostPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let sort_by = api::PostSortField::from(api::rest::PostSortField::from(sort.field));
+        let order = api::SortOrder::from(api::rest::SortOrder::from(sort.order));
+
+        let request = self
+            .create_request(api::ListPostsRequest {
+                page_count: page,
+                page_size,
+                tag: tag.map(str::to_string),
+                sort_by: sort_by as i32,
+                order: order as i32,
+                cursor: None,
+                category_id: category.map(str::to_string),
+                field_mask: None,
+                blog_slug: self.selected_blog(),
+            })
+            .await?;
+
+        let response = self.client.clone().list_posts(request).await?.into_inner();
+
+        post_page_from_proto(response, page, page_size)
+    }
+
+    /// Запрашивает до `limit` постов после курсора `cursor` (без курсора - с начала ленты)
+    async fn fetch_posts_after(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::ListPostsRequest {
+                page_count: 0,
+                page_size: limit,
+                tag: None,
+                sort_by: api::PostSortField::CreatedAt as i32,
+                order: api::SortOrder::Desc as i32,
+                cursor: cursor.map(str::to_string),
+                category_id: None,
+                field_mask: None,
+                blog_slug: self.selected_blog(),
+            })
+            .await?;
+
+        let response = self.client.clone().list_posts(request).await?.into_inner();
+
+        post_page_from_proto(response, 0, limit)
+    }
+
+    /// Запрашивает страницу постов автора `author_id`
+    async fn fetch_author_posts(
+        &self,
+        author_id: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::ListPostsByAuthorRequest {
+                author_id: author_id.to_string(),
+                page_count: page,
+                page_size,
+                field_mask: None,
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .list_posts_by_author(request)
+            .await?
+            .into_inner();
+
+        post_page_from_proto(response, page, page_size)
+    }
+
+    /// Отключает (`disabled`) или включает пользователя
+    async fn set_user_disabled(&self, user_id: &str, disabled: bool) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::SetUserDisabledRequest {
+                user_id: user_id.to_string(),
+                disabled,
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .set_user_disabled(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)
+    }
+
+    /// Создаёт пост с заданным статусом публикации
+    async fn send_create_post(
+        &self,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+        status: api::rest::PostStatus,
+    ) -> types::ClientResult<Uuid> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        // Поля проверяются по тем же правилам, что и в HTTP клиенте
+        let request_body = api::rest::CreatePostRequest {
+            title: title.to_string(),
+            content: content.to_string(),
+            tags: tags.to_vec(),
+            attachment_ids: Vec::new(),
+            category_id: category_id.map(str::to_string),
+            summary: summary.map(str::to_string),
+            status,
+        };
+        request_body.validate()?;
+
+        let request = self
+            .create_request(api::CreatePostRequest {
+                blog_slug: self.selected_blog(),
+                ..request_body.into()
+            })
+            .await?;
+
+        let response = self.client.clone().create_post(request).await?.into_inner();
+
+        check_response(response.response.clone())?;
+
+        let post = response
+            .post
+            .ok_or_else(|| ClientError::InternalError("No post in response".to_string()))?;
+
+        let id = Uuid::parse_str(&post.id)
+            .map_err(|e| ClientError::InternalError(format!("Invalid UUID: {}", e)))?;
+
+        Ok(id)
+    }
+
     fn create_request_without_token<T>(&self, message: T) -> Request<T> {
-        Request::new(message)
+        with_timeout(Request::new(message), self.request_timeout)
     }
 }
 
-// Helper functions to convert between protobuf and chrono timestamps
-fn timestamp_to_datetime(ts: Option<Timestamp>) -> DateTime<Utc> {
-    ts.and_then(|t| DateTime::from_timestamp(t.seconds, t.nanos as u32))
-        .unwrap_or_else(Utc::now)
+/// Передаёт серверу срок выполнения запроса (`grpc-timeout`), если таймаут задан
+fn with_timeout<T>(mut request: Request<T>, timeout: Option<Duration>) -> Request<T> {
+    if let Some(timeout) = timeout {
+        request.set_timeout(timeout);
+    }
+    request
 }
 
+// Helper functions to convert between protobuf and chrono timestamps
 fn datetime_to_timestamp(dt: DateTime<Utc>) -> Option<Timestamp> {
     Some(Timestamp {
         seconds: dt.timestamp(),
@@ -115,19 +504,45 @@ fn datetime_to_timestamp(dt: DateTime<Utc>) -> Option<Timestamp> {
     })
 }
 
+/// Преобразует сообщение поста в клиентский тип через ответ REST API (см. `api::convert`)
 fn proto_post_to_client_post(post: api::Post) -> Result<types::Post, ClientError> {
-    let id = Uuid::parse_str(&post.id)
-        .map_err(|e| ClientError::InternalError(format!("Invalid UUID: {}", e)))?;
-
-    Ok(types::Post {
-        id,
-        title: post.title,
-        content: post.data,
-        created_at: timestamp_to_datetime(post.created_ts),
-        updated_at: timestamp_to_datetime(post.last_updated_ts),
+    post_from_response(post.into())
+}
+
+/// Преобразует ответ со списком постов в страницу клиента
+fn post_page_from_proto(
+    response: api::ListPostsResponse,
+    page: u32,
+    page_size: u32,
+) -> Result<types::PostPage, ClientError> {
+    check_response(response.status)?;
+
+    let posts = response
+        .posts
+        .into_iter()
+        .map(proto_post_to_client_post)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(types::PostPage {
+        posts,
+        total: response.total,
+        page,
+        page_size,
+        has_next: response.has_next,
+        next_cursor: response.next_cursor,
     })
 }
 
+fn proto_profile_to_client_profile(
+    profile: api::UserProfile,
+) -> Result<types::UserProfile, ClientError> {
+    profile_from_response(profile.into())
+}
+
+fn proto_session_to_client_session(session: api::Session) -> Result<types::Session, ClientError> {
+    session_from_response(session.into())
+}
+
 fn check_response(response: Option<api::Response>) -> Result<(), ClientError> {
     let response = response.ok_or_else(|| ClientError::InternalError("No response".to_string()))?;
 
@@ -137,9 +552,17 @@ fn check_response(response: Option<api::Response>) -> Result<(), ClientError> {
         api::Status::InvalidRequest => Err(ClientError::InvalidRequest(
             response.details.unwrap_or_default(),
         )),
-        api::Status::InternalError => Err(ClientError::InternalError(
+        api::Status::InternalError => Err(internal_error(
             response.details.unwrap_or_default(),
+            response.trace_id,
         )),
+        api::Status::AccountLocked => Err(ClientError::AccountLocked { retry_after: None }),
+        api::Status::Forbidden => Err(ClientError::Forbidden(response.details.unwrap_or_default())),
+        api::Status::NotFound => Err(ClientError::NotFound),
+        api::Status::QuotaExceeded => Err(ClientError::QuotaExceeded {
+            message: response.details.unwrap_or_default(),
+            retry_after: None,
+        }),
     }
 }
 
@@ -153,31 +576,32 @@ impl BlogClient for GrpcClient {
         });
 
         let response = self.client.clone().login(request).await?.into_inner();
+        self.complete_login(response).await
+    }
 
-        check_response(response.status)?;
-
-        let token_container = response
-            .token
-            .ok_or_else(|| ClientError::InternalError("No token in response".to_string()))?;
-
-        // Декодируем токен для получения user ID
-        let user_id = decode_token_without_validation(&token_container.access_token)
-            .ok()
-            .and_then(|claims| Uuid::parse_str(&claims.sub).ok())
-            .unwrap_or(Uuid::nil());
-
-        // Сохраняем токены для последующих запросов
-        let access_token = token_container.access_token.clone();
-        let refresh_token = token_container.refresh_token.clone();
-
-        let auth_data = types::AuthData {
-            access_token: access_token.clone(),
-            refresh_token,
+    async fn login_with_totp(
+        &self,
+        username: &str,
+        password: &str,
+        code: &str,
+    ) -> types::ClientResult<Uuid> {
+        let challenge_token = match self.login(username, password).await {
+            Err(ClientError::TwoFactorRequired { challenge_token }) => challenge_token,
+            result => return result,
         };
 
-        self.token_manager.set_auth_data(auth_data).await;
+        let request = self.create_request_without_token(api::LoginWithTotpRequest {
+            challenge_token,
+            code: code.to_string(),
+        });
 
-        Ok(user_id)
+        let response = self
+            .client
+            .clone()
+            .login_with_totp(request)
+            .await?
+            .into_inner();
+        self.complete_login(response).await
     }
 
     async fn register(
@@ -197,121 +621,859 @@ impl BlogClient for GrpcClient {
         check_response(response.status)
     }
 
-    async fn setup_token(&self, token: &str) -> types::ClientResult<()> {
-        self.set_token(token).await;
-        self.ensure_valid_token().await
-    }
+    async fn server_info(&self) -> types::ClientResult<types::ServerInfo> {
+        let request = self.create_request_without_token(api::GetServerInfoRequest {});
 
-    async fn get_token(&self) -> types::ClientResult<Option<String>> {
-        Ok(self.get_token().await)
-    }
+        let response = match self.client.clone().get_server_info(request).await {
+            Ok(response) => response.into_inner(),
+            // Сервер без GetServerInfo отвечает UNIMPLEMENTED
+            Err(status) if status.code() == tonic::Code::Unimplemented => {
+                return Err(ClientError::NotFound);
+            }
+            Err(status) => return Err(status.into()),
+        };
 
-    async fn setup_auth_data(&self, auth_data: &types::AuthData) -> types::ClientResult<()> {
-        self.token_manager.set_auth_data(auth_data.clone()).await;
-        Ok(())
-    }
+        check_response(response.status)?;
 
-    async fn get_auth_data(&self) -> types::ClientResult<Option<types::AuthData>> {
-        Ok(self.token_manager.get_auth_data().await)
+        Ok(types::ServerInfo {
+            version: response.version,
+            api_versions: response.api_versions,
+            features: response.features,
+            post_limits: response.post_limits.map(|limits| types::PostLimits {
+                max_posts_per_day: limits.max_posts_per_day,
+                max_content_bytes: limits.max_content_bytes,
+                max_title_chars: limits.max_title_chars,
+            }),
+        })
     }
 
-    async fn create_post(&self, title: &str, content: &str) -> types::ClientResult<Uuid> {
-        // Проверяем и обновляем токен при необходимости
-        self.ensure_valid_token().await?;
+    async fn is_username_available(&self, username: &str) -> types::ClientResult<bool> {
+        let request = self.create_request_without_token(api::CheckUsernameRequest {
+            login: username.to_string(),
+        });
 
-        let request = self
-            .create_request(api::CreatePostRequest {
-                title: title.to_string(),
-                data: content.to_string(),
-            })
-            .await?;
+        let response = self
+            .client
+            .clone()
+            .check_username(request)
+            .await?
+            .into_inner();
 
-        let response = self.client.clone().create_post(request).await?.into_inner();
+        check_response(response.status)?;
 
-        check_response(response.response.clone())?;
+        Ok(response.available)
+    }
 
-        let post = response
-            .post
-            .ok_or_else(|| ClientError::InternalError("No post in response".to_string()))?;
+    async fn logout(&self) -> types::ClientResult<()> {
+        let refresh_token = self.token_manager.get_refresh_token().await;
 
-        let id = Uuid::parse_str(&post.id)
-            .map_err(|e| ClientError::InternalError(format!("Invalid UUID: {}", e)))?;
+        if let Some(refresh_token) = refresh_token.filter(|token| !token.is_empty()) {
+            let request = self.create_request_without_token(api::LogoutRequest { refresh_token });
 
-        Ok(id)
+            let response = self.client.clone().logout(request).await?.into_inner();
+
+            check_response(response.status)?;
+        }
+
+        self.token_manager.clear_auth_data().await?;
+        Ok(())
     }
 
-    async fn get_post(&self, post_id: &str) -> types::ClientResult<types::Post> {
+    async fn logout_all_sessions(&self) -> types::ClientResult<()> {
         // Проверяем и обновляем токен при необходимости
         self.ensure_valid_token().await?;
 
-        let request = self
-            .create_request(api::GetPostRequest {
-                id: post_id.to_string(),
-            })
-            .await?;
-
-        let response = self.client.clone().get_post(request).await?.into_inner();
+        let request = self.create_request(api::LogoutAllRequest {}).await?;
 
-        check_response(response.response)?;
+        let response = self.client.clone().logout_all(request).await?.into_inner();
 
-        let post = response.post.ok_or(ClientError::NotFound)?;
+        check_response(response.status)?;
 
-        proto_post_to_client_post(post)
+        self.token_manager.clear_auth_data().await?;
+        Ok(())
     }
 
-    async fn update_post(
-        &self,
-        post_id: &str,
-        title: &str,
-        content: &str,
-    ) -> types::ClientResult<()> {
+    async fn list_sessions(&self) -> types::ClientResult<Vec<types::Session>> {
         // Проверяем и обновляем токен при необходимости
         self.ensure_valid_token().await?;
 
-        let request = self
-            .create_request(api::UpdatePostRequest {
-                post: Some(api::Post {
-                    id: post_id.to_string(),
-                    title: title.to_string(),
-                    data: content.to_string(),
-                    created_ts: None,
-                    last_updated_ts: datetime_to_timestamp(Utc::now()),
-                }),
-            })
-            .await?;
+        let request = self.create_request(api::ListSessionsRequest {}).await?;
 
-        let response = self.client.clone().update_post(request).await?.into_inner();
+        let response = self
+            .client
+            .clone()
+            .list_sessions(request)
+            .await?
+            .into_inner();
 
-        check_response(response.response)
+        check_response(response.status)?;
+
+        response
+            .sessions
+            .into_iter()
+            .map(proto_session_to_client_session)
+            .collect()
     }
 
-    async fn delete_post(&self, post_id: &str) -> types::ClientResult<()> {
+    async fn revoke_session(&self, session_id: &str) -> types::ClientResult<()> {
         // Проверяем и обновляем токен при необходимости
         self.ensure_valid_token().await?;
 
         let request = self
-            .create_request(api::DeletePostRequest {
-                post_id: post_id.to_string(),
+            .create_request(api::RevokeSessionRequest {
+                session_id: session_id.to_string(),
             })
             .await?;
 
-        let response = self.client.clone().delete_post(request).await?.into_inner();
+        let response = self
+            .client
+            .clone()
+            .revoke_session(request)
+            .await?
+            .into_inner();
 
-        check_response(response.status)
+        check_response(response.status)?;
+        Ok(())
     }
 
-    async fn list_posts(&self, page_size: u32, page: u32) -> types::ClientResult<Vec<types::Post>> {
-        // Проверяем и обновляем токен при необходимости
-        self.ensure_valid_token().await?;
+    async fn forgot_password(&self, email: &str) -> types::ClientResult<()> {
+        let request = self.create_request_without_token(api::ForgotPasswordRequest {
+            email: email.to_string(),
+        });
 
-        let request = self
-            .create_request(api::ListPostsRequest {
+        let response = self
+            .client
+            .clone()
+            .forgot_password(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)
+    }
+
+    async fn reset_password(&self, token: &str, new_password: &str) -> types::ClientResult<()> {
+        let request = self.create_request_without_token(api::ResetPasswordRequest {
+            token: token.to_string(),
+            new_password: new_password.to_string(),
+        });
+
+        let response = self
+            .client
+            .clone()
+            .reset_password(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)
+    }
+
+    async fn setup_token(&self, token: &str) -> types::ClientResult<()> {
+        self.set_token(token).await?;
+        self.ensure_valid_token().await
+    }
+
+    async fn get_token(&self) -> types::ClientResult<Option<String>> {
+        Ok(self.get_token().await)
+    }
+
+    async fn setup_auth_data(&self, auth_data: &types::AuthData) -> types::ClientResult<()> {
+        self.token_manager.set_auth_data(auth_data.clone()).await
+    }
+
+    async fn get_auth_data(&self) -> types::ClientResult<Option<types::AuthData>> {
+        Ok(self.token_manager.get_auth_data().await)
+    }
+
+    async fn get_user_profile(&self, user_id: &str) -> types::ClientResult<types::UserProfile> {
+        let request = self.create_request_without_token(api::GetUserProfileRequest {
+            user_id: user_id.to_string(),
+        });
+
+        let response = self
+            .client
+            .clone()
+            .get_user_profile(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)?;
+
+        let profile = response.profile.ok_or(ClientError::NotFound)?;
+
+        proto_profile_to_client_profile(profile)
+    }
+
+    async fn update_my_profile(
+        &self,
+        display_name: Option<&str>,
+        bio: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> types::ClientResult<types::UserProfile> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::UpdateMyProfileRequest {
+                display_name: display_name.map(str::to_string),
+                bio: bio.map(str::to_string),
+                avatar_url: avatar_url.map(str::to_string),
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .update_my_profile(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)?;
+
+        let profile = response
+            .profile
+            .ok_or_else(|| ClientError::InternalError("No profile in response".to_string()))?;
+
+        proto_profile_to_client_profile(profile)
+    }
+
+    async fn get_digest_settings(&self) -> types::ClientResult<types::DigestSettings> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::GetDigestSettingsRequest {})
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .get_digest_settings(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status.clone())?;
+
+        Ok(api::rest::DigestSettingsResponse::from(response).into())
+    }
+
+    async fn update_digest_settings(
+        &self,
+        frequency: Option<types::DigestFrequency>,
+    ) -> types::ClientResult<types::DigestSettings> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::UpdateDigestSettingsRequest {
+                frequency: frequency.map(|frequency| {
+                    api::DigestFrequency::from(api::rest::DigestFrequency::from(frequency)) as i32
+                }),
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .update_digest_settings(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status.clone())?;
+
+        Ok(api::rest::DigestSettingsResponse::from(response).into())
+    }
+
+    async fn create_post(
+        &self,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<Uuid> {
+        self.send_create_post(
+            title,
+            content,
+            tags,
+            category_id,
+            summary,
+            api::rest::PostStatus::Published,
+        )
+        .await
+    }
+
+    async fn create_draft(
+        &self,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<Uuid> {
+        self.send_create_post(
+            title,
+            content,
+            tags,
+            category_id,
+            summary,
+            api::rest::PostStatus::Draft,
+        )
+        .await
+    }
+
+    async fn create_posts(&self, posts: Vec<(String, String)>) -> types::ClientResult<Vec<Uuid>> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let blog_slug = self.selected_blog();
+        let messages: Vec<api::CreatePostRequest> = posts
+            .into_iter()
+            .map(|(title, content)| api::CreatePostRequest {
+                title,
+                data: content,
+                tags: Vec::new(),
+                status: api::PostStatus::Published as i32,
+                attachment_ids: Vec::new(),
+                category_id: None,
+                summary: None,
+                blog_slug: blog_slug.clone(),
+            })
+            .collect();
+        let request = self
+            .create_request(futures_util::stream::iter(messages))
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .create_posts(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)?;
+
+        response
+            .posts
+            .iter()
+            .map(|post| {
+                Uuid::parse_str(&post.id)
+                    .map_err(|e| ClientError::InternalError(format!("Invalid UUID: {}", e)))
+            })
+            .collect()
+    }
+
+    async fn get_post(&self, post_id: &str) -> types::ClientResult<types::Post> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::GetPostRequest {
+                id: post_id.to_string(),
+                field_mask: None,
+            })
+            .await?;
+
+        let response = self.client.clone().get_post(request).await?.into_inner();
+
+        check_response(response.response)?;
+
+        let post = response.post.ok_or(ClientError::NotFound)?;
+
+        proto_post_to_client_post(post)
+    }
+
+    async fn update_post(
+        &self,
+        post_id: &str,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::UpdatePostRequest {
+                post: Some(api::Post {
+                    id: post_id.to_string(),
+                    title: title.to_string(),
+                    data: content.to_string(),
+                    created_ts: None,
+                    last_updated_ts: datetime_to_timestamp(Utc::now()),
+                    tags: tags.to_vec(),
+                    ..Default::default()
+                }),
+                attachment_ids: None,
+                category_id: category_id.map(str::to_string),
+                summary: summary.map(str::to_string),
+            })
+            .await?;
+
+        let response = self.client.clone().update_post(request).await?.into_inner();
+
+        check_response(response.response)
+    }
+
+    async fn delete_post(&self, post_id: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::DeletePostRequest {
+                post_id: post_id.to_string(),
+            })
+            .await?;
+
+        let response = self.client.clone().delete_post(request).await?.into_inner();
+
+        check_response(response.status)
+    }
+
+    async fn delete_posts(&self, post_ids: Vec<Uuid>) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::DeletePostsRequest {
+                post_ids: post_ids.iter().map(Uuid::to_string).collect(),
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .delete_posts(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)
+    }
+
+    async fn restore_post(&self, post_id: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::RestorePostRequest {
+                post_id: post_id.to_string(),
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .restore_post(request)
+            .await?
+            .into_inner();
+
+        check_response(response.response)
+    }
+
+    async fn purge_post(&self, post_id: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::PurgePostRequest {
+                post_id: post_id.to_string(),
+            })
+            .await?;
+
+        let response = self.client.clone().purge_post(request).await?.into_inner();
+
+        check_response(response.status)
+    }
+
+    async fn publish_post(&self, post_id: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::PublishPostRequest {
+                post_id: post_id.to_string(),
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .publish_post(request)
+            .await?
+            .into_inner();
+
+        check_response(response.response)
+    }
+
+    async fn unpublish_post(&self, post_id: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::UnpublishPostRequest {
+                post_id: post_id.to_string(),
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .unpublish_post(request)
+            .await?
+            .into_inner();
+
+        check_response(response.response)
+    }
+
+    async fn like_post(&self, post_id: &str) -> types::ClientResult<u64> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::LikePostRequest {
+                post_id: post_id.to_string(),
+            })
+            .await?;
+
+        let response = self.client.clone().like_post(request).await?.into_inner();
+
+        check_response(response.response)?;
+
+        let post = response
+            .post
+            .ok_or_else(|| ClientError::InternalError("No post in response".to_string()))?;
+
+        Ok(post.like_count)
+    }
+
+    async fn unlike_post(&self, post_id: &str) -> types::ClientResult<u64> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::UnlikePostRequest {
+                post_id: post_id.to_string(),
+            })
+            .await?;
+
+        let response = self.client.clone().unlike_post(request).await?.into_inner();
+
+        check_response(response.response)?;
+
+        let post = response
+            .post
+            .ok_or_else(|| ClientError::InternalError("No post in response".to_string()))?;
+
+        Ok(post.like_count)
+    }
+
+    async fn report_post(
+        &self,
+        post_id: &str,
+        reason: types::ReportReason,
+        details: Option<&str>,
+    ) -> types::ClientResult<types::Report> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        // Пояснение проверяется по тем же правилам, что и в HTTP клиенте
+        let reason = api::rest::ReportReason::from(reason);
+        api::rest::ReportPostRequest {
+            reason,
+            details: details.map(str::to_string),
+        }
+        .validate()?;
+
+        let request = self
+            .create_request(api::ReportPostRequest {
+                post_id: post_id.to_string(),
+                reason: api::ReportReason::from(reason) as i32,
+                details: details.map(str::to_string),
+            })
+            .await?;
+
+        let response = self.client.clone().report_post(request).await?.into_inner();
+
+        check_response(response.status)?;
+
+        let report = response
+            .report
+            .ok_or_else(|| ClientError::InternalError("No report in response".to_string()))?;
+
+        report_from_response(report.into())
+    }
+
+    async fn follow_user(&self, user_id: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::FollowUserRequest {
+                user_id: user_id.to_string(),
+            })
+            .await?;
+
+        let response = self.client.clone().follow_user(request).await?.into_inner();
+
+        check_response(response.status)
+    }
+
+    async fn unfollow_user(&self, user_id: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::UnfollowUserRequest {
+                user_id: user_id.to_string(),
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .unfollow_user(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)
+    }
+
+    async fn is_following(&self, user_id: &str) -> types::ClientResult<bool> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::GetFollowStatusRequest {
+                user_id: user_id.to_string(),
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .get_follow_status(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)?;
+
+        Ok(response.following)
+    }
+
+    async fn get_feed(&self, page_size: u32, page: u32) -> types::ClientResult<types::PostPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        // Размер страницы проверяется по тем же правилам, что и в HTTP клиенте
+        api::rest::PaginationQuery { page_size, page }.validate()?;
+
+        let request = self
+            .create_request(api::GetFeedRequest {
                 page_count: page,
                 page_size,
+                field_mask: None,
             })
             .await?;
 
-        let response = self.client.clone().list_posts(request).await?.into_inner();
+        let response = self.client.clone().get_feed(request).await?.into_inner();
+
+        post_page_from_proto(response, page, page_size)
+    }
+
+    async fn list_posts(
+        &self,
+        page_size: u32,
+        page: u32,
+        sort: Option<types::PostSort>,
+    ) -> types::ClientResult<types::PostPage> {
+        self.fetch_posts(page_size, page, None, None, sort.unwrap_or_default())
+            .await
+    }
+
+    async fn list_posts_by_tag(
+        &self,
+        tag: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.fetch_posts(page_size, page, Some(tag), None, types::PostSort::default())
+            .await
+    }
+
+    async fn list_posts_by_category(
+        &self,
+        category_id: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.fetch_posts(
+            page_size,
+            page,
+            None,
+            Some(category_id),
+            types::PostSort::default(),
+        )
+        .await
+    }
+
+    async fn list_categories(&self) -> types::ClientResult<Vec<types::Category>> {
+        let request = self.create_request_without_token(api::ListCategoriesRequest {});
+
+        let response = self
+            .client
+            .clone()
+            .list_categories(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)?;
+
+        response
+            .categories
+            .into_iter()
+            .map(|category| category_from_response(category.into()))
+            .collect()
+    }
+
+    async fn list_posts_after(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.fetch_posts_after(cursor, limit).await
+    }
+
+    fn select_blog(&self, slug: Option<&str>) {
+        *self
+            .selected_blog
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = slug.map(str::to_string);
+    }
+
+    fn selected_blog(&self) -> Option<String> {
+        self.selected_blog
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    async fn list_blogs(&self) -> types::ClientResult<Vec<types::Blog>> {
+        let request = self.create_request_without_token(api::ListBlogsRequest {});
+
+        let response = self.client.clone().list_blogs(request).await?.into_inner();
+
+        check_response(response.status)?;
+
+        response
+            .blogs
+            .into_iter()
+            .map(|blog| blog_from_response(blog.into()))
+            .collect()
+    }
+
+    async fn get_blog(&self, slug: &str) -> types::ClientResult<types::Blog> {
+        let request = self.create_request_without_token(api::GetBlogRequest {
+            slug: slug.to_string(),
+        });
+
+        let response = self.client.clone().get_blog(request).await?.into_inner();
+
+        check_response(response.status)?;
+
+        let blog = response.blog.ok_or(ClientError::NotFound)?;
+
+        blog_from_response(blog.into())
+    }
+
+    async fn create_blog(&self, slug: &str, name: &str) -> types::ClientResult<types::Blog> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        // Поля проверяются по тем же правилам, что и в HTTP клиенте
+        let request_body = api::rest::CreateBlogRequest {
+            slug: slug.to_string(),
+            name: name.to_string(),
+        };
+        request_body.validate()?;
+
+        let request = self
+            .create_request(api::CreateBlogRequest {
+                slug: request_body.slug,
+                name: request_body.name,
+            })
+            .await?;
+
+        let response = self.client.clone().create_blog(request).await?.into_inner();
+
+        check_response(response.status)?;
+
+        let blog = response
+            .blog
+            .ok_or_else(|| ClientError::InternalError("No blog in response".to_string()))?;
+
+        blog_from_response(blog.into())
+    }
+
+    async fn update_blog(&self, slug: &str, name: &str) -> types::ClientResult<types::Blog> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request_body = api::rest::UpdateBlogRequest {
+            name: name.to_string(),
+        };
+        request_body.validate()?;
+
+        let request = self
+            .create_request(api::UpdateBlogRequest {
+                slug: slug.to_string(),
+                name: request_body.name,
+            })
+            .await?;
+
+        let response = self.client.clone().update_blog(request).await?.into_inner();
+
+        check_response(response.status)?;
+
+        let blog = response
+            .blog
+            .ok_or_else(|| ClientError::InternalError("No blog in response".to_string()))?;
+
+        blog_from_response(blog.into())
+    }
+
+    async fn delete_blog(&self, slug: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::DeleteBlogRequest {
+                slug: slug.to_string(),
+            })
+            .await?;
+
+        let response = self.client.clone().delete_blog(request).await?.into_inner();
+
+        check_response(response.status)
+    }
+
+    async fn list_trending_posts(&self, limit: u32) -> types::ClientResult<Vec<types::Post>> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::ListTrendingPostsRequest {
+                limit,
+                field_mask: None,
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .list_trending_posts(request)
+            .await?
+            .into_inner();
 
         check_response(response.status)?;
 
@@ -321,4 +1483,297 @@ impl BlogClient for GrpcClient {
             .map(proto_post_to_client_post)
             .collect()
     }
+
+    async fn list_posts_by_author(
+        &self,
+        author_id: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.fetch_author_posts(author_id, page_size, page).await
+    }
+
+    async fn upload_file(
+        &self,
+        file_name: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> types::ClientResult<String> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::UploadFileRequest {
+                file_name: file_name.to_string(),
+                content_type: content_type.to_string(),
+                data,
+            })
+            .await?;
+
+        let response = self.client.clone().upload_file(request).await?.into_inner();
+
+        check_response(response.status)?;
+
+        let attachment = response
+            .attachment
+            .ok_or_else(|| ClientError::InternalError("No attachment in response".to_string()))?;
+
+        Ok(attachment.url)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AdminClient for GrpcClient {
+    async fn list_users(&self, page_size: u32, page: u32) -> types::ClientResult<types::UserPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        // Размер страницы проверяется по тем же правилам, что и в HTTP клиенте
+        api::rest::PaginationQuery { page_size, page }.validate()?;
+
+        let request = self
+            .create_request(api::ListUsersRequest {
+                page_count: page,
+                page_size,
+            })
+            .await?;
+
+        let response = self.client.clone().list_users(request).await?.into_inner();
+
+        check_response(response.status)?;
+
+        user_page_from_response(
+            response.users.into_iter().map(Into::into).collect(),
+            response.total,
+            page,
+            page_size,
+        )
+    }
+
+    async fn disable_user(&self, user_id: &str) -> types::ClientResult<()> {
+        self.set_user_disabled(user_id, true).await
+    }
+
+    async fn enable_user(&self, user_id: &str) -> types::ClientResult<()> {
+        self.set_user_disabled(user_id, false).await
+    }
+
+    async fn force_delete_post(&self, post_id: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::ForceDeletePostRequest {
+                post_id: post_id.to_string(),
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .force_delete_post(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)
+    }
+
+    async fn system_stats(&self) -> types::ClientResult<types::SystemStats> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self.create_request(api::GetSystemStatsRequest {}).await?;
+
+        let response = self
+            .client
+            .clone()
+            .get_system_stats(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status.clone())?;
+
+        Ok(api::rest::SystemStatsResponse::from(response).into())
+    }
+
+    async fn create_category(
+        &self,
+        name: &str,
+        parent_id: Option<&str>,
+    ) -> types::ClientResult<types::Category> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        // Название проверяется по тем же правилам, что и в HTTP клиенте
+        let request_body = api::rest::CreateCategoryRequest {
+            name: name.to_string(),
+            parent_id: parent_id.map(str::to_string),
+        };
+        request_body.validate()?;
+
+        let request = self
+            .create_request(api::CreateCategoryRequest {
+                name: request_body.name,
+                parent_id: request_body.parent_id,
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .create_category(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)?;
+
+        let category = response
+            .category
+            .ok_or_else(|| ClientError::InternalError("No category in response".to_string()))?;
+
+        category_from_response(category.into())
+    }
+
+    async fn list_reports(
+        &self,
+        status: Option<types::ReportStatus>,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::ReportPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        // Размер страницы проверяется по тем же правилам, что и в HTTP клиенте
+        let status = status.map(api::rest::ReportStatus::from);
+        api::rest::ListReportsQuery {
+            page_size,
+            page,
+            status,
+        }
+        .validate()?;
+
+        let request = self
+            .create_request(api::ListReportsRequest {
+                page_count: page,
+                page_size,
+                status: status.map(|status| api::ReportStatus::from(status) as i32),
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .list_reports(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)?;
+
+        report_page_from_response(
+            response.reports.into_iter().map(Into::into).collect(),
+            response.total,
+            page,
+            page_size,
+        )
+    }
+
+    async fn resolve_report(
+        &self,
+        report_id: &str,
+        action: types::ReportAction,
+    ) -> types::ClientResult<types::Report> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let request = self
+            .create_request(api::ResolveReportRequest {
+                report_id: report_id.to_string(),
+                action: api::ReportAction::from(api::rest::ReportAction::from(action)) as i32,
+            })
+            .await?;
+
+        let response = self
+            .client
+            .clone()
+            .resolve_report(request)
+            .await?
+            .into_inner();
+
+        check_response(response.status)?;
+
+        let report = response
+            .report
+            .ok_or_else(|| ClientError::InternalError("No report in response".to_string()))?;
+
+        report_from_response(report.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proto_post(author_id: &str, author_username: &str) -> api::Post {
+        api::Post {
+            id: Uuid::new_v4().to_string(),
+            title: "Title".to_string(),
+            data: "Content".to_string(),
+            author_id: author_id.to_string(),
+            author_username: author_username.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_post_keeps_author() {
+        let author_id = Uuid::new_v4();
+
+        let post = proto_post_to_client_post(proto_post(&author_id.to_string(), "alice")).unwrap();
+
+        assert_eq!(post.author_id, author_id);
+        assert_eq!(post.author_username, "alice");
+    }
+
+    #[test]
+    fn test_post_without_author_from_old_server() {
+        let post = proto_post_to_client_post(proto_post("", "")).unwrap();
+
+        assert_eq!(post.author_id, Uuid::nil());
+        assert!(post.author_username.is_empty());
+    }
+
+    #[test]
+    fn test_quota_exceeded_status_keeps_details() {
+        let response = api::Response {
+            code: api::Status::QuotaExceeded as i32,
+            details: Some("Quota exceeded: at most 5 posts can be created per day".to_string()),
+            trace_id: None,
+        };
+
+        let error = check_response(Some(response)).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ClientError::QuotaExceeded { ref message, retry_after: None }
+                if message.contains("5 posts")
+        ));
+    }
+
+    #[test]
+    fn test_internal_error_includes_trace_id() {
+        let response = api::Response {
+            code: api::Status::InternalError as i32,
+            details: Some("Internal server error".to_string()),
+            trace_id: Some("0190f1c2-7d4e-7a3b-9c1d-2e3f4a5b6c7d".to_string()),
+        };
+
+        let error = check_response(Some(response)).unwrap_err();
+
+        assert!(matches!(
+            error,
+            ClientError::InternalError(ref message)
+                if message.ends_with("(trace_id: 0190f1c2-7d4e-7a3b-9c1d-2e3f4a5b6c7d)")
+        ));
+    }
 }
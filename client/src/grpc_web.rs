@@ -0,0 +1,77 @@
+//! gRPC-Web транспорт для браузера.
+//!
+//! Браузер не даёт доступа к HTTP/2 trailers, поэтому в WASM gRPC-запросы
+//! отправляются по протоколу gRPC-Web через `fetch` (reqwest). Кодирование
+//! сообщений и разбор trailers из тела ответа выполняет [`GrpcWebClientService`].
+
+use http_body_util::{BodyExt, Full};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tonic::body::Body;
+use tonic::codegen::{Bytes, Service, StdError, http};
+use tonic_web::{GrpcWebCall, GrpcWebClientService};
+
+use crate::error::ClientError;
+
+/// Канал gRPC-Web, с которым работает сгенерированный gRPC клиент
+pub type GrpcWebChannel = GrpcWebClientService<FetchTransport>;
+
+/// Создаёт канал gRPC-Web к серверу по адресу `url`
+pub fn channel(url: String) -> Result<GrpcWebChannel, ClientError> {
+    let client = reqwest::Client::builder()
+        .build()
+        .map_err(|e| ClientError::TransportError(e.to_string()))?;
+
+    Ok(GrpcWebClientService::new(FetchTransport {
+        client,
+        base_url: url.trim_end_matches('/').to_string(),
+    }))
+}
+
+/// Отправляет закодированные gRPC-Web запросы через `fetch`.
+///
+/// `fetch` отдаёт тело ответа только целиком, поэтому серверные стримы
+/// приходят одним ответом после завершения вызова.
+#[derive(Clone)]
+pub struct FetchTransport {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl Service<http::Request<GrpcWebCall<Body>>> for FetchTransport {
+    type Response = http::Response<Full<Bytes>>;
+    type Error = StdError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<GrpcWebCall<Body>>) -> Self::Future {
+        let client = self.client.clone();
+        let url = format!("{}{}", self.base_url, request.uri().path());
+
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = body.collect().await?.to_bytes();
+
+            let mut headers = parts.headers;
+            // Эти заголовки браузер выставляет сам и не даёт переопределить
+            headers.remove(http::header::TE);
+            headers.remove(http::header::USER_AGENT);
+            headers.insert("x-grpc-web", http::HeaderValue::from_static("1"));
+
+            let response = client.post(url).headers(headers).body(body).send().await?;
+
+            let status = response.status();
+            let headers = response.headers().clone();
+            let body = response.bytes().await?;
+
+            let mut response = http::Response::new(Full::new(body));
+            *response.status_mut() = status;
+            *response.headers_mut() = headers;
+            Ok(response)
+        })
+    }
+}
@@ -1,27 +1,78 @@
+use api::rest::{ErrorCode, Validate};
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use reqwest::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
-use tokio::sync::mpsc;
+use futures_util::{Stream, StreamExt, stream};
+use reqwest::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderValue};
+use reqwest::{Method, StatusCode, multipart};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use uuid::Uuid;
 
 use crate::{
+    admin::AdminClient,
     blog_client::BlogClient,
-    error::ClientError,
-    interceptor::decode_token_without_validation,
-    token_manager::{TokenManager, TokenUpdateEvent},
+    config::ClientConfig,
+    convert::{
+        blog_from_response, category_from_response, post_from_response, profile_from_response,
+        report_from_response, report_page_from_response, session_from_response,
+        user_page_from_response,
+    },
+    error::{ClientError, internal_error},
+    interceptor::{ExpectedClaims, decode_token_without_validation},
+    token_manager::{TokenManager, TokenUpdateEvent, mpsc},
+    token_store::TokenStore,
     types,
 };
 
+/// Заголовок с ключом идемпотентности изменяющих запросов
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Сколько раз отправляется изменяющий запрос, прежде чем вернуть ошибку
+const MAX_SEND_ATTEMPTS: u32 = 3;
+
 #[derive(Clone)]
 pub struct HttpClient {
     client: reqwest::Client,
     base_url: String,
     token_manager: TokenManager,
+    request_timeout: Option<Duration>,
+    /// Адрес блога из [`BlogClient::select_blog`]; общий для копий клиента
+    selected_blog: Arc<RwLock<Option<String>>>,
 }
 
 impl HttpClient {
     pub async fn new(url: String) -> Result<Self, ClientError> {
-        let client = reqwest::Client::builder()
+        Self::new_with_config(url, &ClientConfig::default()).await
+    }
+
+    /// Создает HttpClient с таймаутами, настройками TLS и пула соединений из `config`
+    pub async fn new_with_config(url: String, config: &ClientConfig) -> Result<Self, ClientError> {
+        #[allow(unused_mut)]
+        let mut builder = reqwest::Client::builder();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(connect_timeout) = config.connect_timeout {
+                builder = builder.connect_timeout(connect_timeout);
+            }
+            builder = builder
+                .gzip(config.compression)
+                .brotli(config.compression)
+                .pool_max_idle_per_host(config.pool.max_idle_per_host)
+                .pool_idle_timeout(config.pool.idle_timeout)
+                .tcp_keepalive(config.pool.tcp_keepalive);
+            if config.pool.http2_prior_knowledge {
+                builder = builder.http2_prior_knowledge();
+            }
+            if let Some(tls) = &config.tls {
+                builder = builder.tls_danger_accept_invalid_certs(tls.danger_accept_invalid_certs);
+                if let Some(pem) = &tls.ca_certificate_pem {
+                    let certs = reqwest::Certificate::from_pem_bundle(pem)
+                        .map_err(|e| ClientError::TransportError(e.to_string()))?;
+                    builder = builder.tls_certs_merge(certs);
+                }
+            }
+        }
+        let client = builder
             .build()
             .map_err(|e| ClientError::TransportError(e.to_string()))?;
 
@@ -31,28 +82,35 @@ impl HttpClient {
             client,
             base_url,
             token_manager: TokenManager::new(300), // Обновлять токен за 5 минут до истечения
+            request_timeout: config.request_timeout,
+            selected_blog: Arc::new(RwLock::new(None)),
         })
     }
 
+    /// Создает HttpClient для HTTPS сервера с дополнительными настройками TLS
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn new_with_tls(
+        url: String,
+        tls: &crate::tls::TlsOptions,
+    ) -> Result<Self, ClientError> {
+        let config = ClientConfig {
+            tls: Some(tls.clone()),
+            ..ClientConfig::default()
+        };
+        Self::new_with_config(url, &config).await
+    }
+
     /// Создает HttpClient с поддержкой уведомлений об обновлении токена
     pub async fn new_with_token_notifier(
         url: String,
         token_sender: mpsc::UnboundedSender<TokenUpdateEvent>,
     ) -> Result<Self, ClientError> {
-        let client = reqwest::Client::builder()
-            .build()
-            .map_err(|e| ClientError::TransportError(e.to_string()))?;
-
-        let base_url = url.trim_end_matches('/').to_string();
-
-        Ok(Self {
-            client,
-            base_url,
-            token_manager: TokenManager::new_with_notifier(300, token_sender),
-        })
+        let mut client = Self::new(url).await?;
+        client.set_token_notifier(token_sender);
+        Ok(client)
     }
 
-    pub async fn set_token(&self, token: String) {
+    pub async fn set_token(&self, token: String) -> Result<(), ClientError> {
         // Сохраняем существующий refresh_token, если он есть
         let existing_refresh_token = self
             .token_manager
@@ -65,7 +123,7 @@ impl HttpClient {
                 access_token: token.clone(),
                 refresh_token: existing_refresh_token,
             })
-            .await;
+            .await
     }
 
     pub async fn get_token(&self) -> Option<String> {
@@ -77,21 +135,89 @@ impl HttpClient {
         self.token_manager.set_token_refresh_buffer(seconds);
     }
 
+    /// Подключает хранилище токенов и восстанавливает из него сохранённый вход
+    pub fn set_token_store(&mut self, store: Arc<dyn TokenStore>) -> Result<(), ClientError> {
+        self.token_manager.set_store(store)
+    }
+
+    /// Включает уведомления об обновлении токена
+    pub fn set_token_notifier(&mut self, token_sender: mpsc::UnboundedSender<TokenUpdateEvent>) {
+        self.token_manager.set_notifier(token_sender);
+    }
+
+    /// Включает уведомления об истечении сессии: сервер отклонил refresh токен
+    pub fn set_session_expired_notifier(&mut self, sender: mpsc::UnboundedSender<()>) {
+        self.token_manager.set_session_expired_notifier(sender);
+    }
+
+    /// Задаёт ожидаемых издателя и аудиторию токенов (`jwt_issuer` и `jwt_audience` сервера);
+    /// токен с другими значениями не принимается
+    pub fn set_expected_token_claims(&mut self, issuer: Option<String>, audience: Option<String>) {
+        self.token_manager
+            .set_expected_claims(ExpectedClaims { issuer, audience });
+    }
+
+    /// Начинает запрос с таймаутом из [`ClientConfig::request_timeout`]
+    fn request(&self, method: Method, url: &str) -> reqwest::RequestBuilder {
+        with_timeout(self.client.request(method, url), self.request_timeout)
+    }
+
     /// Проверяет токен и обновляет его при необходимости
     async fn ensure_valid_token(&self) -> Result<(), ClientError> {
         let client = self.client.clone();
         let base_url = self.base_url.clone();
+        let request_timeout = self.request_timeout;
         self.token_manager
             .ensure_valid_token(|refresh_token| async move {
-                Self::refresh_auth_token_internal(client, base_url, refresh_token).await
+                Self::refresh_auth_token_internal(client, base_url, request_timeout, refresh_token)
+                    .await
             })
             .await
     }
 
+    /// Отправляет запрос, собранный `send` с заголовками авторизации.
+    ///
+    /// Если сервер ответил 401, а refresh токен есть, токен один раз обновляется и запрос
+    /// отправляется повторно; ответ повторной попытки возвращается как есть.
+    async fn send_authorized<F, Fut, E>(&self, send: F) -> types::ClientResult<reqwest::Response>
+    where
+        F: Fn(HeaderMap) -> Fut,
+        Fut: Future<Output = Result<reqwest::Response, E>>,
+        ClientError: From<E>,
+    {
+        let access_token = self.token_manager.get_access_token().await;
+        let response = send(self.create_headers().await?).await?;
+
+        let Some(access_token) = access_token else {
+            return Ok(response);
+        };
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let refreshed = self
+            .token_manager
+            .refresh_rejected_token(&access_token, |refresh_token| {
+                Self::refresh_auth_token_internal(
+                    self.client.clone(),
+                    self.base_url.clone(),
+                    self.request_timeout,
+                    refresh_token,
+                )
+            })
+            .await?;
+        if !refreshed {
+            return Ok(response);
+        }
+
+        Ok(send(self.create_headers().await?).await?)
+    }
+
     /// Внутренний метод для обновления токена через HTTP
     async fn refresh_auth_token_internal(
         client: reqwest::Client,
         base_url: String,
+        request_timeout: Option<Duration>,
         refresh_token: types::Token,
     ) -> types::ClientResult<types::AuthData> {
         let url = format!("{}/api/v1/auth/refresh", base_url);
@@ -102,13 +228,15 @@ impl HttpClient {
 
         dbg!("Refreshing token with refresh_token: {}", refresh_token);
 
-        let response = client
-            .post(&url)
+        let response = with_timeout(client.post(&url), request_timeout)
             .header(CONTENT_TYPE, "application/json")
             .json(&request_body)
             .send()
             .await?;
 
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(ClientError::Unauthorized);
+        }
         if !response.status().is_success() {
             return Err(ClientError::TransportError(format!(
                 "Failed to refresh token: {}",
@@ -127,164 +255,252 @@ impl HttpClient {
         })
     }
 
-    /// Создает заголовки с токеном авторизации
-    async fn create_headers(&self) -> Result<HeaderMap, ClientError> {
-        let mut headers = HeaderMap::new();
-        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
-
-        let auth_data = self.token_manager.get_auth_data().await;
-        if let Some(data) = auth_data.as_ref() {
-            let auth_value = format!("Bearer {}", data.access_token);
-            headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&auth_value).map_err(|e| {
-                    ClientError::InternalError(format!("Invalid header value: {}", e))
-                })?,
-            );
+    /// Отправляет изменяющий запрос с заголовком `Idempotency-Key` и повторяет его при сбоях.
+    ///
+    /// Ключ генерируется один раз на вызов, поэтому сервер выполнит запрос не более
+    /// одного раза, даже если ответ на первую попытку потерялся. Повторяются сетевые
+    /// ошибки и ответы 502/503/504, всего до [`MAX_SEND_ATTEMPTS`] попыток.
+    async fn send_idempotent(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> types::ClientResult<reqwest::Response> {
+        let key = Uuid::new_v4().to_string();
+        let mut attempt = 1;
+
+        loop {
+            let attempt_request = request
+                .try_clone()
+                .ok_or_else(|| ClientError::InternalError("Request cannot be retried".to_string()))?
+                .header(IDEMPOTENCY_KEY_HEADER, &key);
+
+            match attempt_request.send().await {
+                Ok(response)
+                    if matches!(response.status().as_u16(), 502..=504)
+                        && attempt < MAX_SEND_ATTEMPTS => {}
+                Ok(response) => return Ok(response),
+                Err(_) if attempt < MAX_SEND_ATTEMPTS => {}
+                Err(e) => return Err(e.into()),
+            }
+            attempt += 1;
         }
-
-        Ok(headers)
     }
 
-    /// Обрабатывает ошибку HTTP-ответа
-    async fn handle_error_response(response: reqwest::Response) -> ClientError {
-        let status = response.status();
+    /// Подписывается на живую ленту опубликованных постов (Server-Sent Events).
+    ///
+    /// Поток завершается, когда сервер закрывает соединение; для продолжения
+    /// нужно подписаться заново.
+    pub async fn subscribe_post_events(
+        &self,
+    ) -> types::ClientResult<impl Stream<Item = types::ClientResult<types::PostEvent>> + use<>>
+    {
+        let url = format!("{}/api/v1/posts/events", self.base_url);
 
-        if status == reqwest::StatusCode::UNAUTHORIZED {
-            return ClientError::Unauthorized;
-        }
+        // Лента не ограничивается таймаутом запроса: соединение открыто, пока идут события
+        let response = self
+            .client
+            .get(&url)
+            .header(ACCEPT, "text/event-stream")
+            .send()
+            .await?;
 
-        if status == reqwest::StatusCode::NOT_FOUND {
-            return ClientError::NotFound;
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
         }
 
-        if status.is_client_error() {
-            let error_msg = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            return ClientError::InvalidRequest(error_msg);
-        }
+        let state = (
+            response.bytes_stream(),
+            SseDecoder::default(),
+            VecDeque::<String>::new(),
+        );
+        Ok(stream::unfold(
+            state,
+            |(mut body, mut decoder, mut pending)| async move {
+                loop {
+                    if let Some(data) = pending.pop_front() {
+                        let event = post_event_from_data(&data);
+                        return Some((event, (body, decoder, pending)));
+                    }
+                    match body.next().await? {
+                        Ok(chunk) => pending.extend(decoder.feed(&chunk)),
+                        Err(e) => return Some((Err(e.into()), (body, decoder, pending))),
+                    }
+                }
+            },
+        ))
+    }
 
-        let error_msg = response
-            .text()
-            .await
-            .unwrap_or_else(|_| "Unknown error".to_string());
-        ClientError::InternalError(error_msg)
+    /// Адрес постов выбранного блога или, без него, общей ленты сервера
+    fn posts_url(&self) -> String {
+        match self.selected_blog() {
+            Some(slug) => format!("{}/api/v1/blogs/{}/posts", self.base_url, slug),
+            None => format!("{}/api/v1/posts", self.base_url),
+        }
     }
-}
 
-#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
-#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
-impl BlogClient for HttpClient {
-    async fn login(&self, username: &str, password: &str) -> types::ClientResult<Uuid> {
-        let url = format!("{}/api/v1/auth/login", self.base_url);
+    /// Запрашивает страницу постов в порядке `sort`, при указании `tag` - только с этим тегом,
+    /// при указании `category` - только из этой категории и её подкатегорий
+    async fn fetch_posts(
+        &self,
+        page_size: u32,
+        page: u32,
+        tag: Option<&str>,
+        category: Option<&str>,
+        sort: types::PostSort,
+    ) -> types::ClientResult<types::PostPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
 
-        let request_body = api::rest::LoginRequest {
-            username: username.to_string(),
-            password: password.to_string(),
+        let query = api::rest::ListPostsQuery {
+            page_size,
+            page,
+            tag: tag.map(str::to_string),
+            category: category.map(str::to_string),
+            sort_by: Some(sort.field.into()),
+            order: Some(sort.order.into()),
+            cursor: None,
+            fields: None,
         };
+        query.validate()?;
+
+        let url = self.posts_url();
 
         let response = self
-            .client
-            .post(&url)
-            .header(CONTENT_TYPE, "application/json")
-            .json(&request_body)
-            .send()
+            .send_authorized(|headers| {
+                self.request(Method::GET, &url)
+                    .headers(headers)
+                    .query(&query)
+                    .send()
+            })
             .await?;
 
-        if !response.status().is_success() {
-            return Err(Self::handle_error_response(response).await);
-        }
-
-        let token_response: api::rest::TokenResponse = response.json().await?;
+        Self::read_post_page(response).await
+    }
 
-        // Создаем и сохраняем токены
-        let access_token = token_response.access_token.clone();
-        let refresh_token = token_response.refresh_token.clone();
+    /// Запрашивает до `limit` постов после курсора `cursor` (без курсора - с начала ленты)
+    async fn fetch_posts_after(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
 
-        let auth_data = types::AuthData {
-            access_token: access_token.clone(),
-            refresh_token,
+        let query = api::rest::ListPostsQuery {
+            page_size: limit,
+            page: 0,
+            tag: None,
+            category: None,
+            sort_by: None,
+            order: None,
+            cursor: cursor.map(str::to_string),
+            fields: None,
         };
+        query.validate()?;
 
-        // Сохраняем токены в auth_data
-        self.token_manager.set_auth_data(auth_data).await;
+        let url = self.posts_url();
 
-        // Декодируем токен для получения user ID
-        let user_id = decode_token_without_validation(&access_token)
-            .ok()
-            .and_then(|claims| Uuid::parse_str(&claims.sub).ok())
-            .unwrap_or(Uuid::nil());
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::GET, &url)
+                    .headers(headers)
+                    .query(&query)
+                    .send()
+            })
+            .await?;
 
-        Ok(user_id)
+        // В режиме курсора номер страницы не используется: продолжение есть, только если выдан курсор
+        let mut page = Self::read_post_page(response).await?;
+        page.has_next = page.next_cursor.is_some();
+        Ok(page)
     }
 
-    async fn register(
+    /// Запрашивает страницу постов автора `author_id`
+    async fn fetch_author_posts(
         &self,
-        username: &str,
-        email: &str,
-        password: &str,
-    ) -> types::ClientResult<()> {
-        let url = format!("{}/api/v1/auth/register", self.base_url);
+        author_id: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
 
-        let request_body = api::rest::RegisterRequest {
-            username: username.to_string(),
-            password: password.to_string(),
-            email: email.to_string(),
-        };
+        let query = api::rest::PaginationQuery { page_size, page };
+        query.validate()?;
+
+        let url = format!("{}/api/v1/users/{}/posts", self.base_url, author_id);
 
         let response = self
-            .client
-            .post(&url)
-            .header(CONTENT_TYPE, "application/json")
-            .json(&request_body)
-            .send()
+            .send_authorized(|headers| {
+                self.request(Method::GET, &url)
+                    .headers(headers)
+                    .query(&query)
+                    .send()
+            })
             .await?;
 
+        Self::read_post_page(response).await
+    }
+
+    /// Разбирает ответ со страницей постов
+    async fn read_post_page(response: reqwest::Response) -> types::ClientResult<types::PostPage> {
         if !response.status().is_success() {
             return Err(Self::handle_error_response(response).await);
         }
 
-        Ok(())
-    }
-
-    async fn setup_token(&self, token: &str) -> types::ClientResult<()> {
-        self.set_token(token.to_string()).await;
-        self.ensure_valid_token().await
-    }
-
-    async fn get_token(&self) -> types::ClientResult<Option<String>> {
-        Ok(self.token_manager.get_access_token().await)
-    }
-
-    async fn setup_auth_data(&self, auth_data: &types::AuthData) -> types::ClientResult<()> {
-        self.token_manager.set_auth_data(auth_data.clone()).await;
-        Ok(())
-    }
+        let posts_response: api::rest::Paginated<api::rest::PostResponse> = response.json().await?;
 
-    async fn get_auth_data(&self) -> types::ClientResult<Option<types::AuthData>> {
-        Ok(self.token_manager.get_auth_data().await)
+        let has_next = (posts_response.page as u64 + 1) * (posts_response.page_size as u64)
+            < posts_response.total;
+        let posts = posts_response
+            .items
+            .into_iter()
+            .map(post_from_response)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(types::PostPage {
+            posts,
+            total: posts_response.total,
+            page: posts_response.page,
+            page_size: posts_response.page_size,
+            has_next,
+            next_cursor: posts_response.next_cursor,
+        })
     }
 
-    async fn create_post(&self, title: &str, content: &str) -> types::ClientResult<Uuid> {
+    /// Создаёт пост с заданным статусом публикации
+    async fn send_create_post(
+        &self,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+        status: api::rest::PostStatus,
+    ) -> types::ClientResult<Uuid> {
         // Проверяем и обновляем токен при необходимости
         self.ensure_valid_token().await?;
 
-        let url = format!("{}/api/v1/posts", self.base_url);
-        let headers = self.create_headers().await?;
+        let url = self.posts_url();
 
         let request_body = api::rest::CreatePostRequest {
             title: title.to_string(),
             content: content.to_string(),
+            tags: tags.to_vec(),
+            attachment_ids: Vec::new(),
+            category_id: category_id.map(str::to_string),
+            summary: summary.map(str::to_string),
+            status,
         };
+        request_body.validate()?;
 
         let response = self
-            .client
-            .post(&url)
-            .headers(headers)
-            .json(&request_body)
-            .send()
+            .send_authorized(|headers| {
+                self.send_idempotent(
+                    self.request(Method::POST, &url)
+                        .headers(headers)
+                        .json(&request_body),
+                )
+            })
             .await?;
 
         if !response.status().is_success() {
@@ -299,64 +515,33 @@ impl BlogClient for HttpClient {
         Ok(id)
     }
 
-    async fn get_post(&self, post_id: &str) -> types::ClientResult<types::Post> {
+    /// Выполняет действие над постом: `action` - `publish`, `unpublish`, `restore` или `purge`
+    async fn post_action(&self, post_id: &str, action: &str) -> types::ClientResult<()> {
         // Проверяем и обновляем токен при необходимости
         self.ensure_valid_token().await?;
 
-        let url = format!("{}/api/v1/posts/{}", self.base_url, post_id);
-        let headers = self.create_headers().await?;
+        let url = format!("{}/api/v1/posts/{}/{}", self.base_url, post_id, action);
 
-        let response = self.client.get(&url).headers(headers).send().await?;
+        let response = self
+            .send_authorized(|headers| self.request(Method::POST, &url).headers(headers).send())
+            .await?;
 
         if !response.status().is_success() {
             return Err(Self::handle_error_response(response).await);
         }
 
-        let post_response: api::rest::PostResponse = response.json().await?;
-
-        let id = Uuid::parse_str(&post_response.uuid)
-            .map_err(|e| ClientError::InternalError(format!("Invalid UUID: {}", e)))?;
-
-        let created_at = DateTime::parse_from_rfc3339(&post_response.created_at)
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or_else(|_| Utc::now());
-
-        let updated_at = DateTime::parse_from_rfc3339(&post_response.updated_at)
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or_else(|_| Utc::now());
-
-        Ok(types::Post {
-            id,
-            title: post_response.title,
-            content: post_response.content,
-            created_at,
-            updated_at,
-        })
+        Ok(())
     }
 
-    async fn update_post(
-        &self,
-        post_id: &str,
-        title: &str,
-        content: &str,
-    ) -> types::ClientResult<()> {
+    /// Выполняет административное действие без тела ответа (`/api/v1/admin/{path}`)
+    async fn admin_action(&self, method: Method, path: &str) -> types::ClientResult<()> {
         // Проверяем и обновляем токен при необходимости
         self.ensure_valid_token().await?;
 
-        let url = format!("{}/api/v1/posts/{}", self.base_url, post_id);
-        let headers = self.create_headers().await?;
-
-        let request_body = api::rest::UpdatePostRequest {
-            title: title.to_string(),
-            content: content.to_string(),
-        };
+        let url = format!("{}/api/v1/admin/{}", self.base_url, path);
 
         let response = self
-            .client
-            .put(&url)
-            .headers(headers)
-            .json(&request_body)
-            .send()
+            .send_authorized(|headers| self.request(method.clone(), &url).headers(headers).send())
             .await?;
 
         if !response.status().is_success() {
@@ -366,63 +551,1357 @@ impl BlogClient for HttpClient {
         Ok(())
     }
 
-    async fn delete_post(&self, post_id: &str) -> types::ClientResult<()> {
+    /// Ставит (`POST`) или снимает (`DELETE`) лайк и возвращает количество лайков
+    async fn send_like(&self, post_id: &str, method: Method) -> types::ClientResult<u64> {
         // Проверяем и обновляем токен при необходимости
         self.ensure_valid_token().await?;
 
-        let url = format!("{}/api/v1/posts/{}", self.base_url, post_id);
-        let headers = self.create_headers().await?;
+        let url = format!("{}/api/v1/posts/{}/like", self.base_url, post_id);
 
-        let response = self.client.delete(&url).headers(headers).send().await?;
+        let response = self
+            .send_authorized(|headers| self.request(method.clone(), &url).headers(headers).send())
+            .await?;
 
         if !response.status().is_success() {
             return Err(Self::handle_error_response(response).await);
         }
 
-        Ok(())
+        let post_response: api::rest::PostResponse = response.json().await?;
+
+        Ok(post_response.like_count)
     }
 
-    async fn list_posts(&self, page_size: u32, page: u32) -> types::ClientResult<Vec<types::Post>> {
+    /// Отправляет запрос к подписке на автора и возвращает её состояние
+    async fn send_follow(&self, user_id: &str, method: Method) -> types::ClientResult<bool> {
         // Проверяем и обновляем токен при необходимости
         self.ensure_valid_token().await?;
 
-        dbg!(page_size, page);
-        let url = format!(
-            "{}/api/v1/posts?page_size={}&page={}",
-            self.base_url, page_size, page
-        );
-        let headers = self.create_headers().await?;
+        let url = format!("{}/api/v1/users/{}/follow", self.base_url, user_id);
 
-        let response = self.client.get(&url).headers(headers).send().await?;
+        let response = self
+            .send_authorized(|headers| self.request(method.clone(), &url).headers(headers).send())
+            .await?;
 
         if !response.status().is_success() {
             return Err(Self::handle_error_response(response).await);
         }
 
-        let posts_response: Vec<api::rest::PostResponse> = response.json().await?;
+        let follow_response: api::rest::FollowResponse = response.json().await?;
 
-        posts_response
-            .into_iter()
-            .map(|post_response| {
-                let id = Uuid::parse_str(&post_response.uuid)
-                    .map_err(|e| ClientError::InternalError(format!("Invalid UUID: {}", e)))?;
-
-                let created_at = DateTime::parse_from_rfc3339(&post_response.created_at)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now());
-
-                let updated_at = DateTime::parse_from_rfc3339(&post_response.updated_at)
-                    .map(|dt| dt.with_timezone(&Utc))
-                    .unwrap_or_else(|_| Utc::now());
-
-                Ok(types::Post {
-                    id,
-                    title: post_response.title,
-                    content: post_response.content,
-                    created_at,
-                    updated_at,
-                })
-            })
-            .collect()
+        Ok(follow_response.following)
+    }
+
+    /// Создает заголовки с токеном авторизации
+    async fn create_headers(&self) -> Result<HeaderMap, ClientError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+        let auth_data = self.token_manager.get_auth_data().await;
+        if let Some(data) = auth_data.as_ref() {
+            let auth_value = format!("Bearer {}", data.access_token);
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&auth_value).map_err(|e| {
+                    ClientError::InternalError(format!("Invalid header value: {}", e))
+                })?,
+            );
+        }
+
+        Ok(headers)
+    }
+
+    /// Сохраняет токены после входа и возвращает ID пользователя из access токена
+    async fn store_tokens(
+        &self,
+        token_response: api::rest::TokenResponse,
+    ) -> types::ClientResult<Uuid> {
+        // Декодируем токен для получения user ID
+        let claims = decode_token_without_validation(
+            &token_response.access_token,
+            self.token_manager.expected_claims(),
+        )?;
+        let user_id = Uuid::parse_str(&claims.sub).unwrap_or(Uuid::nil());
+
+        let auth_data = types::AuthData {
+            access_token: token_response.access_token,
+            refresh_token: token_response.refresh_token,
+        };
+        self.token_manager.set_auth_data(auth_data).await?;
+
+        Ok(user_id)
+    }
+
+    /// Обрабатывает ошибку HTTP-ответа
+    async fn handle_error_response(response: reqwest::Response) -> ClientError {
+        let status = response.status();
+
+        // Retry-After есть только в заголовках, поэтому читаем его до тела
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.parse().ok());
+        if status == reqwest::StatusCode::LOCKED {
+            return ClientError::AccountLocked { retry_after };
+        }
+
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+
+        // Сервер возвращает ErrorResponse; если тело в другом формате или код ошибки
+        // неизвестен, ориентируемся на статус
+        let Ok(error) = serde_json::from_str::<api::rest::ErrorResponse>(&body) else {
+            return match status {
+                reqwest::StatusCode::TOO_MANY_REQUESTS => ClientError::RateLimited { retry_after },
+                reqwest::StatusCode::UNAUTHORIZED => ClientError::Unauthorized,
+                reqwest::StatusCode::NOT_FOUND => ClientError::NotFound,
+                _ if status.is_client_error() => ClientError::InvalidRequest(body),
+                _ => ClientError::InternalError(body),
+            };
+        };
+
+        let message = error_message(&error);
+        match error.code {
+            ErrorCode::Unauthorized => ClientError::Unauthorized,
+            ErrorCode::NotFound => ClientError::NotFound,
+            ErrorCode::ValidationFailed => ClientError::Validation {
+                message,
+                details: error.details,
+            },
+            ErrorCode::BadRequest => ClientError::InvalidRequest(message),
+            ErrorCode::Forbidden => ClientError::Forbidden(message),
+            ErrorCode::Conflict => ClientError::Conflict(message),
+            ErrorCode::RateLimited => ClientError::RateLimited { retry_after },
+            // 429 с этим кодом - суточная квота, 403 - ограничение размера поста
+            ErrorCode::QuotaExceeded => ClientError::QuotaExceeded {
+                message,
+                retry_after,
+            },
+            ErrorCode::AccountLocked => ClientError::AccountLocked { retry_after: None },
+            ErrorCode::InternalError => internal_error(message, error.trace_id),
+        }
+    }
+}
+
+/// Разбирает событие живой ленты из поля `data`.
+fn post_event_from_data(data: &str) -> types::ClientResult<types::PostEvent> {
+    let event: api::rest::PostEventResponse = serde_json::from_str(data)
+        .map_err(|e| ClientError::InternalError(format!("Invalid post event: {}", e)))?;
+
+    Ok(match event {
+        api::rest::PostEventResponse::Created { post } => {
+            types::PostEvent::Created(post_from_response(post)?)
+        }
+        api::rest::PostEventResponse::Updated { post } => {
+            types::PostEvent::Updated(post_from_response(post)?)
+        }
+        api::rest::PostEventResponse::Deleted { post_id } => types::PostEvent::Deleted(
+            Uuid::parse_str(&post_id)
+                .map_err(|e| ClientError::InternalError(format!("Invalid UUID: {}", e)))?,
+        ),
+    })
+}
+
+/// Разбирает поток Server-Sent Events на поля `data` отдельных событий.
+///
+/// Комментарии (строки с `:`) и прочие поля пропускаются.
+#[derive(Default)]
+struct SseDecoder {
+    buffer: Vec<u8>,
+}
+
+impl SseDecoder {
+    /// Добавляет очередной фрагмент тела и возвращает `data` полностью полученных событий
+    fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.windows(2).position(|window| window == b"\n\n") {
+            let frame: Vec<u8> = self.buffer.drain(..end + 2).collect();
+            let data = String::from_utf8_lossy(&frame)
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|value| value.strip_prefix(' ').unwrap_or(value))
+                .collect::<Vec<_>>()
+                .join("\n");
+            if !data.is_empty() {
+                events.push(data);
+            }
+        }
+        events
+    }
+}
+
+/// Ограничивает время ожидания ответа на запрос, если таймаут задан
+fn with_timeout(
+    request: reqwest::RequestBuilder,
+    timeout: Option<Duration>,
+) -> reqwest::RequestBuilder {
+    match timeout {
+        Some(timeout) => request.timeout(timeout),
+        None => request,
+    }
+}
+
+/// Формирует текст ошибки из [`api::rest::ErrorResponse`], включая ошибки полей.
+fn error_message(error: &api::rest::ErrorResponse) -> String {
+    if error.details.is_empty() {
+        return error.message.clone();
+    }
+
+    let fields = error
+        .details
+        .iter()
+        .map(|(field, messages)| format!("{}: {}", field, messages.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ");
+    format!("{} ({})", error.message, fields)
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl BlogClient for HttpClient {
+    async fn login(&self, username: &str, password: &str) -> types::ClientResult<Uuid> {
+        let url = format!("{}/api/v1/auth/login", self.base_url);
+
+        let request_body = api::rest::LoginRequest {
+            username: Some(username.to_string()),
+            email: None,
+            password: password.to_string(),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .request(Method::POST, &url)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        // 202 означает, что пароль принят, но нужен второй шаг с кодом 2FA
+        if response.status() == reqwest::StatusCode::ACCEPTED {
+            let challenge: api::rest::TwoFactorChallengeResponse = response.json().await?;
+            return Err(ClientError::TwoFactorRequired {
+                challenge_token: challenge.challenge_token,
+            });
+        }
+
+        let token_response: api::rest::TokenResponse = response.json().await?;
+        self.store_tokens(token_response).await
+    }
+
+    async fn login_with_totp(
+        &self,
+        username: &str,
+        password: &str,
+        code: &str,
+    ) -> types::ClientResult<Uuid> {
+        let challenge_token = match self.login(username, password).await {
+            Err(ClientError::TwoFactorRequired { challenge_token }) => challenge_token,
+            result => return result,
+        };
+
+        let url = format!("{}/api/v1/auth/login/2fa", self.base_url);
+
+        let request_body = api::rest::TotpLoginRequest {
+            challenge_token,
+            code: code.to_string(),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .request(Method::POST, &url)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let token_response: api::rest::TokenResponse = response.json().await?;
+        self.store_tokens(token_response).await
+    }
+
+    async fn register(
+        &self,
+        username: &str,
+        email: &str,
+        password: &str,
+    ) -> types::ClientResult<()> {
+        let url = format!("{}/api/v1/auth/register", self.base_url);
+
+        let request_body = api::rest::RegisterRequest {
+            username: username.to_string(),
+            password: password.to_string(),
+            email: email.to_string(),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .send_idempotent(
+                self.request(Method::POST, &url)
+                    .header(CONTENT_TYPE, "application/json")
+                    .json(&request_body),
+            )
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        Ok(())
+    }
+
+    async fn server_info(&self) -> types::ClientResult<types::ServerInfo> {
+        let url = format!("{}/api/info", self.base_url);
+
+        let response = self.request(Method::GET, &url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let info: api::rest::ServerInfoResponse = response.json().await?;
+
+        Ok(types::ServerInfo {
+            version: info.version,
+            api_versions: info.api_versions,
+            features: info.features,
+            post_limits: info.post_limits.map(|limits| types::PostLimits {
+                max_posts_per_day: limits.max_posts_per_day,
+                max_content_bytes: limits.max_content_bytes,
+                max_title_chars: limits.max_title_chars,
+            }),
+        })
+    }
+
+    async fn is_username_available(&self, username: &str) -> types::ClientResult<bool> {
+        let url = format!("{}/api/v1/auth/username-available", self.base_url);
+
+        let response = self
+            .request(Method::GET, &url)
+            .query(&[("username", username)])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let availability: api::rest::UsernameAvailabilityResponse = response.json().await?;
+
+        Ok(availability.available)
+    }
+
+    async fn logout(&self) -> types::ClientResult<()> {
+        let refresh_token = self.token_manager.get_refresh_token().await;
+
+        if let Some(refresh_token) = refresh_token.filter(|token| !token.is_empty()) {
+            let url = format!("{}/api/v1/auth/logout", self.base_url);
+
+            let request_body = api::rest::LogoutRequest { refresh_token };
+
+            let response = self
+                .request(Method::POST, &url)
+                .header(CONTENT_TYPE, "application/json")
+                .json(&request_body)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(Self::handle_error_response(response).await);
+            }
+        }
+
+        self.token_manager.clear_auth_data().await?;
+        Ok(())
+    }
+
+    async fn logout_all_sessions(&self) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/auth/logout-all", self.base_url);
+
+        let response = self
+            .send_authorized(|headers| self.request(Method::POST, &url).headers(headers).send())
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        self.token_manager.clear_auth_data().await?;
+        Ok(())
+    }
+
+    async fn list_sessions(&self) -> types::ClientResult<Vec<types::Session>> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/auth/sessions", self.base_url);
+
+        let response = self
+            .send_authorized(|headers| self.request(Method::GET, &url).headers(headers).send())
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let list_response: api::rest::SessionListResponse = response.json().await?;
+
+        list_response
+            .sessions
+            .into_iter()
+            .map(session_from_response)
+            .collect()
+    }
+
+    async fn revoke_session(&self, session_id: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/auth/sessions/{}", self.base_url, session_id);
+
+        let response = self
+            .send_authorized(|headers| self.request(Method::DELETE, &url).headers(headers).send())
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        Ok(())
+    }
+
+    async fn forgot_password(&self, email: &str) -> types::ClientResult<()> {
+        let url = format!("{}/api/v1/auth/forgot-password", self.base_url);
+
+        let request_body = api::rest::ForgotPasswordRequest {
+            email: email.to_string(),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .request(Method::POST, &url)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        Ok(())
+    }
+
+    async fn reset_password(&self, token: &str, new_password: &str) -> types::ClientResult<()> {
+        let url = format!("{}/api/v1/auth/reset-password", self.base_url);
+
+        let request_body = api::rest::ResetPasswordRequest {
+            token: token.to_string(),
+            new_password: new_password.to_string(),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .request(Method::POST, &url)
+            .header(CONTENT_TYPE, "application/json")
+            .json(&request_body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        Ok(())
+    }
+
+    async fn setup_token(&self, token: &str) -> types::ClientResult<()> {
+        self.set_token(token.to_string()).await?;
+        self.ensure_valid_token().await
+    }
+
+    async fn get_token(&self) -> types::ClientResult<Option<String>> {
+        Ok(self.token_manager.get_access_token().await)
+    }
+
+    async fn setup_auth_data(&self, auth_data: &types::AuthData) -> types::ClientResult<()> {
+        self.token_manager.set_auth_data(auth_data.clone()).await
+    }
+
+    async fn get_auth_data(&self) -> types::ClientResult<Option<types::AuthData>> {
+        Ok(self.token_manager.get_auth_data().await)
+    }
+
+    async fn get_user_profile(&self, user_id: &str) -> types::ClientResult<types::UserProfile> {
+        let url = format!("{}/api/v1/users/{}", self.base_url, user_id);
+
+        let response = self.request(Method::GET, &url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let profile_response: api::rest::UserProfileResponse = response.json().await?;
+
+        profile_from_response(profile_response)
+    }
+
+    async fn update_my_profile(
+        &self,
+        display_name: Option<&str>,
+        bio: Option<&str>,
+        avatar_url: Option<&str>,
+    ) -> types::ClientResult<types::UserProfile> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/users/me", self.base_url);
+
+        let request_body = api::rest::UpdateProfileRequest {
+            display_name: display_name.map(str::to_string),
+            bio: bio.map(str::to_string),
+            avatar_url: avatar_url.map(str::to_string),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::PUT, &url)
+                    .headers(headers)
+                    .json(&request_body)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let profile_response: api::rest::UserProfileResponse = response.json().await?;
+
+        profile_from_response(profile_response)
+    }
+
+    async fn get_digest_settings(&self) -> types::ClientResult<types::DigestSettings> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/users/me/digest", self.base_url);
+
+        let response = self
+            .send_authorized(|headers| self.request(Method::GET, &url).headers(headers).send())
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let settings_response: api::rest::DigestSettingsResponse = response.json().await?;
+
+        Ok(settings_response.into())
+    }
+
+    async fn update_digest_settings(
+        &self,
+        frequency: Option<types::DigestFrequency>,
+    ) -> types::ClientResult<types::DigestSettings> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/users/me/digest", self.base_url);
+
+        let request_body = api::rest::UpdateDigestSettingsRequest {
+            frequency: frequency.map(Into::into),
+        };
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::PUT, &url)
+                    .headers(headers)
+                    .json(&request_body)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let settings_response: api::rest::DigestSettingsResponse = response.json().await?;
+
+        Ok(settings_response.into())
+    }
+
+    async fn create_post(
+        &self,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<Uuid> {
+        self.send_create_post(
+            title,
+            content,
+            tags,
+            category_id,
+            summary,
+            api::rest::PostStatus::Published,
+        )
+        .await
+    }
+
+    async fn create_draft(
+        &self,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<Uuid> {
+        self.send_create_post(
+            title,
+            content,
+            tags,
+            category_id,
+            summary,
+            api::rest::PostStatus::Draft,
+        )
+        .await
+    }
+
+    async fn create_posts(&self, posts: Vec<(String, String)>) -> types::ClientResult<Vec<Uuid>> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/batch", self.posts_url());
+
+        let request_body = api::rest::CreatePostsRequest {
+            posts: posts
+                .into_iter()
+                .map(|(title, content)| api::rest::CreatePostRequest {
+                    title,
+                    content,
+                    tags: Vec::new(),
+                    attachment_ids: Vec::new(),
+                    category_id: None,
+                    summary: None,
+                    status: api::rest::PostStatus::Published,
+                })
+                .collect(),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .send_authorized(|headers| {
+                self.send_idempotent(
+                    self.request(Method::POST, &url)
+                        .headers(headers)
+                        .json(&request_body),
+                )
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let posts_response: api::rest::CreatePostsResponse = response.json().await?;
+
+        posts_response
+            .posts
+            .iter()
+            .map(|post| {
+                Uuid::parse_str(&post.uuid)
+                    .map_err(|e| ClientError::InternalError(format!("Invalid UUID: {}", e)))
+            })
+            .collect()
+    }
+
+    async fn get_post(&self, post_id: &str) -> types::ClientResult<types::Post> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/posts/{}", self.base_url, post_id);
+
+        let response = self
+            .send_authorized(|headers| self.request(Method::GET, &url).headers(headers).send())
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let post_response: api::rest::PostResponse = response.json().await?;
+
+        post_from_response(post_response)
+    }
+
+    async fn update_post(
+        &self,
+        post_id: &str,
+        title: &str,
+        content: &str,
+        tags: &[String],
+        category_id: Option<&str>,
+        summary: Option<&str>,
+    ) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/posts/{}", self.base_url, post_id);
+
+        let request_body = api::rest::UpdatePostRequest {
+            title: title.to_string(),
+            content: content.to_string(),
+            tags: tags.to_vec(),
+            attachment_ids: None,
+            category_id: category_id.map(str::to_string),
+            summary: summary.map(str::to_string),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .send_authorized(|headers| {
+                self.send_idempotent(
+                    self.request(Method::PUT, &url)
+                        .headers(headers)
+                        .json(&request_body),
+                )
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        Ok(())
+    }
+
+    async fn delete_post(&self, post_id: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/posts/{}", self.base_url, post_id);
+
+        let response = self
+            .send_authorized(|headers| self.request(Method::DELETE, &url).headers(headers).send())
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        Ok(())
+    }
+
+    async fn delete_posts(&self, post_ids: Vec<Uuid>) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/posts/batch", self.base_url);
+
+        let request_body = api::rest::DeletePostsRequest {
+            post_ids: post_ids.iter().map(Uuid::to_string).collect(),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::DELETE, &url)
+                    .headers(headers)
+                    .json(&request_body)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        Ok(())
+    }
+
+    async fn publish_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.post_action(post_id, "publish").await
+    }
+
+    async fn unpublish_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.post_action(post_id, "unpublish").await
+    }
+
+    async fn restore_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.post_action(post_id, "restore").await
+    }
+
+    async fn purge_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.post_action(post_id, "purge").await
+    }
+
+    async fn like_post(&self, post_id: &str) -> types::ClientResult<u64> {
+        self.send_like(post_id, Method::POST).await
+    }
+
+    async fn unlike_post(&self, post_id: &str) -> types::ClientResult<u64> {
+        self.send_like(post_id, Method::DELETE).await
+    }
+
+    async fn report_post(
+        &self,
+        post_id: &str,
+        reason: types::ReportReason,
+        details: Option<&str>,
+    ) -> types::ClientResult<types::Report> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/posts/{}/report", self.base_url, post_id);
+        let request_body = api::rest::ReportPostRequest {
+            reason: reason.into(),
+            details: details.map(str::to_string),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::POST, &url)
+                    .headers(headers)
+                    .json(&request_body)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let report_response: api::rest::ReportResponse = response.json().await?;
+
+        report_from_response(report_response)
+    }
+
+    async fn follow_user(&self, user_id: &str) -> types::ClientResult<()> {
+        self.send_follow(user_id, Method::POST).await.map(|_| ())
+    }
+
+    async fn unfollow_user(&self, user_id: &str) -> types::ClientResult<()> {
+        self.send_follow(user_id, Method::DELETE).await.map(|_| ())
+    }
+
+    async fn is_following(&self, user_id: &str) -> types::ClientResult<bool> {
+        self.send_follow(user_id, Method::GET).await
+    }
+
+    async fn get_feed(&self, page_size: u32, page: u32) -> types::ClientResult<types::PostPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let query = api::rest::PaginationQuery { page_size, page };
+        query.validate()?;
+
+        let url = format!("{}/api/v1/feed", self.base_url);
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::GET, &url)
+                    .headers(headers)
+                    .query(&query)
+                    .send()
+            })
+            .await?;
+
+        Self::read_post_page(response).await
+    }
+
+    async fn list_posts(
+        &self,
+        page_size: u32,
+        page: u32,
+        sort: Option<types::PostSort>,
+    ) -> types::ClientResult<types::PostPage> {
+        self.fetch_posts(page_size, page, None, None, sort.unwrap_or_default())
+            .await
+    }
+
+    async fn list_posts_by_tag(
+        &self,
+        tag: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.fetch_posts(page_size, page, Some(tag), None, types::PostSort::default())
+            .await
+    }
+
+    async fn list_posts_by_category(
+        &self,
+        category_id: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.fetch_posts(
+            page_size,
+            page,
+            None,
+            Some(category_id),
+            types::PostSort::default(),
+        )
+        .await
+    }
+
+    async fn list_categories(&self) -> types::ClientResult<Vec<types::Category>> {
+        let url = format!("{}/api/v1/categories", self.base_url);
+
+        let response = self.request(Method::GET, &url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let categories_response: api::rest::CategoryListResponse = response.json().await?;
+
+        categories_response
+            .categories
+            .into_iter()
+            .map(category_from_response)
+            .collect()
+    }
+
+    async fn list_posts_after(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.fetch_posts_after(cursor, limit).await
+    }
+
+    fn select_blog(&self, slug: Option<&str>) {
+        *self
+            .selected_blog
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = slug.map(str::to_string);
+    }
+
+    fn selected_blog(&self) -> Option<String> {
+        self.selected_blog
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    async fn list_blogs(&self) -> types::ClientResult<Vec<types::Blog>> {
+        let url = format!("{}/api/v1/blogs", self.base_url);
+
+        let response = self.request(Method::GET, &url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let blogs_response: api::rest::BlogListResponse = response.json().await?;
+
+        blogs_response
+            .blogs
+            .into_iter()
+            .map(blog_from_response)
+            .collect()
+    }
+
+    async fn get_blog(&self, slug: &str) -> types::ClientResult<types::Blog> {
+        let url = format!("{}/api/v1/blogs/{}", self.base_url, slug);
+
+        let response = self.request(Method::GET, &url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let blog_response: api::rest::BlogResponse = response.json().await?;
+
+        blog_from_response(blog_response)
+    }
+
+    async fn create_blog(&self, slug: &str, name: &str) -> types::ClientResult<types::Blog> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/blogs", self.base_url);
+
+        let request_body = api::rest::CreateBlogRequest {
+            slug: slug.to_string(),
+            name: name.to_string(),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::POST, &url)
+                    .headers(headers)
+                    .json(&request_body)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let blog_response: api::rest::BlogResponse = response.json().await?;
+
+        blog_from_response(blog_response)
+    }
+
+    async fn update_blog(&self, slug: &str, name: &str) -> types::ClientResult<types::Blog> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/blogs/{}", self.base_url, slug);
+
+        let request_body = api::rest::UpdateBlogRequest {
+            name: name.to_string(),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::PUT, &url)
+                    .headers(headers)
+                    .json(&request_body)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let blog_response: api::rest::BlogResponse = response.json().await?;
+
+        blog_from_response(blog_response)
+    }
+
+    async fn delete_blog(&self, slug: &str) -> types::ClientResult<()> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/blogs/{}", self.base_url, slug);
+
+        let response = self
+            .send_authorized(|headers| self.request(Method::DELETE, &url).headers(headers).send())
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        Ok(())
+    }
+
+    async fn list_trending_posts(&self, limit: u32) -> types::ClientResult<Vec<types::Post>> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let query = api::rest::TrendingPostsQuery { limit };
+        query.validate()?;
+
+        let url = format!("{}/api/v1/posts/trending", self.base_url);
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::GET, &url)
+                    .headers(headers)
+                    .query(&query)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let posts: Vec<api::rest::PostResponse> = response.json().await?;
+
+        posts.into_iter().map(post_from_response).collect()
+    }
+
+    async fn list_posts_by_author(
+        &self,
+        author_id: &str,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::PostPage> {
+        self.fetch_author_posts(author_id, page_size, page).await
+    }
+
+    async fn upload_file(
+        &self,
+        file_name: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> types::ClientResult<String> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/uploads", self.base_url);
+        let (url, data) = (&url, &data);
+
+        // Форма собирается заново для каждой попытки: тело multipart нельзя отправить дважды
+        let response = self
+            .send_authorized(|mut headers| async move {
+                // Тип тела с границей частей выставляет reqwest
+                headers.remove(CONTENT_TYPE);
+
+                let part = multipart::Part::bytes(data.clone())
+                    .file_name(file_name.to_string())
+                    .mime_str(content_type)?;
+                let form = multipart::Form::new().part("file", part);
+
+                self.request(Method::POST, url)
+                    .headers(headers)
+                    .multipart(form)
+                    .send()
+                    .await
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let upload_response: api::rest::UploadResponse = response.json().await?;
+
+        Ok(upload_response.url)
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl AdminClient for HttpClient {
+    async fn list_users(&self, page_size: u32, page: u32) -> types::ClientResult<types::UserPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let query = api::rest::PaginationQuery { page_size, page };
+        query.validate()?;
+
+        let url = format!("{}/api/v1/admin/users", self.base_url);
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::GET, &url)
+                    .headers(headers)
+                    .query(&query)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let users_response: api::rest::Paginated<api::rest::AdminUserResponse> =
+            response.json().await?;
+
+        user_page_from_response(
+            users_response.items,
+            users_response.total,
+            users_response.page,
+            users_response.page_size,
+        )
+    }
+
+    async fn disable_user(&self, user_id: &str) -> types::ClientResult<()> {
+        self.admin_action(Method::POST, &format!("users/{}/disable", user_id))
+            .await
+    }
+
+    async fn enable_user(&self, user_id: &str) -> types::ClientResult<()> {
+        self.admin_action(Method::POST, &format!("users/{}/enable", user_id))
+            .await
+    }
+
+    async fn force_delete_post(&self, post_id: &str) -> types::ClientResult<()> {
+        self.admin_action(Method::DELETE, &format!("posts/{}", post_id))
+            .await
+    }
+
+    async fn system_stats(&self) -> types::ClientResult<types::SystemStats> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/admin/stats", self.base_url);
+
+        let response = self
+            .send_authorized(|headers| self.request(Method::GET, &url).headers(headers).send())
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let stats_response: api::rest::SystemStatsResponse = response.json().await?;
+
+        Ok(stats_response.into())
+    }
+
+    async fn create_category(
+        &self,
+        name: &str,
+        parent_id: Option<&str>,
+    ) -> types::ClientResult<types::Category> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!("{}/api/v1/admin/categories", self.base_url);
+
+        let request_body = api::rest::CreateCategoryRequest {
+            name: name.to_string(),
+            parent_id: parent_id.map(str::to_string),
+        };
+        request_body.validate()?;
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::POST, &url)
+                    .headers(headers)
+                    .json(&request_body)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let category_response: api::rest::CategoryResponse = response.json().await?;
+
+        category_from_response(category_response)
+    }
+
+    async fn list_reports(
+        &self,
+        status: Option<types::ReportStatus>,
+        page_size: u32,
+        page: u32,
+    ) -> types::ClientResult<types::ReportPage> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let query = api::rest::ListReportsQuery {
+            page_size,
+            page,
+            status: status.map(Into::into),
+        };
+        query.validate()?;
+
+        let url = format!("{}/api/v1/moderation/reports", self.base_url);
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::GET, &url)
+                    .headers(headers)
+                    .query(&query)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let reports_response: api::rest::Paginated<api::rest::ReportResponse> =
+            response.json().await?;
+
+        report_page_from_response(
+            reports_response.items,
+            reports_response.total,
+            reports_response.page,
+            reports_response.page_size,
+        )
+    }
+
+    async fn resolve_report(
+        &self,
+        report_id: &str,
+        action: types::ReportAction,
+    ) -> types::ClientResult<types::Report> {
+        // Проверяем и обновляем токен при необходимости
+        self.ensure_valid_token().await?;
+
+        let url = format!(
+            "{}/api/v1/moderation/reports/{}/resolve",
+            self.base_url, report_id
+        );
+        let request_body = api::rest::ResolveReportRequest {
+            action: action.into(),
+        };
+
+        let response = self
+            .send_authorized(|headers| {
+                self.request(Method::POST, &url)
+                    .headers(headers)
+                    .json(&request_body)
+                    .send()
+            })
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::handle_error_response(response).await);
+        }
+
+        let report_response: api::rest::ReportResponse = response.json().await?;
+
+        report_from_response(report_response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_decoder_splits_events_across_chunks() {
+        let mut decoder = SseDecoder::default();
+
+        assert!(
+            decoder
+                .feed(b": keep-alive\n\nevent: deleted\nda")
+                .is_empty()
+        );
+        let events = decoder.feed(b"ta: {\"type\":\"deleted\"}\n\ndata: 1\ndata: 2\n\n");
+
+        assert_eq!(events, vec!["{\"type\":\"deleted\"}", "1\n2"]);
+    }
+
+    #[tokio::test]
+    async fn test_client_accepts_custom_pool_options() {
+        let config = ClientConfig {
+            pool: crate::config::PoolOptions {
+                max_idle_per_host: 0,
+                idle_timeout: None,
+                http2_prior_knowledge: true,
+                tcp_keepalive: None,
+            },
+            ..ClientConfig::default()
+        };
+
+        let client = HttpClient::new_with_config("http://localhost:8080/".to_string(), &config)
+            .await
+            .unwrap();
+
+        assert_eq!(client.base_url, "http://localhost:8080");
     }
 }
@@ -2,12 +2,35 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
 use crate::error::ClientError;
+use crate::types::UserRole;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Claims {
     pub sub: String,
     pub user_name: String,
+    /// Роль пользователя (`user`, `moderator` или `admin`); в старых токенах её нет
+    #[serde(default)]
+    pub role: Option<String>,
     pub exp: i64,
+    /// Время выдачи токена (Unix timestamp)
+    #[serde(default)]
+    pub iat: Option<i64>,
+    /// Издатель токена, если сервер его указывает
+    #[serde(default)]
+    pub iss: Option<String>,
+    /// Аудитория токена, если сервер её указывает
+    #[serde(default)]
+    pub aud: Option<String>,
+    /// Уникальный ID токена
+    #[serde(default)]
+    pub jti: Option<String>,
+}
+
+/// Ожидаемые издатель и аудитория токенов; незаданные значения не проверяются
+#[derive(Debug, Clone, Default)]
+pub struct ExpectedClaims {
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
 }
 
 impl Claims {
@@ -16,15 +39,48 @@ impl Claims {
         let now = Utc::now().timestamp();
         self.exp <= now + buffer_seconds
     }
+
+    /// Роль пользователя; токен без роли или с неизвестной ролью - обычный пользователь
+    pub fn role(&self) -> UserRole {
+        match self.role.as_deref() {
+            Some("admin") => UserRole::Admin,
+            Some("moderator") => UserRole::Moderator,
+            _ => UserRole::User,
+        }
+    }
 }
 
 /// Декодирует JWT токен без проверки подписи
 /// (для клиента проверка подписи не требуется, так как мы получаем токен от доверенного сервера)
-pub fn decode_token_without_validation(token: &str) -> Result<Claims, ClientError> {
+///
+/// Издатель и аудитория сверяются с `expected`, чтобы не принять токен, выданный для
+/// другого сервиса.
+pub fn decode_token_without_validation(
+    token: &str,
+    expected: &ExpectedClaims,
+) -> Result<Claims, ClientError> {
     let token_data = jsonwebtoken::dangerous::insecure_decode::<Claims>(token)
         .map_err(|e| ClientError::InternalError(format!("Failed to decode token: {}", e)))?;
+    let claims = token_data.claims;
+
+    if let Some(issuer) = &expected.issuer
+        && claims.iss.as_ref() != Some(issuer)
+    {
+        return Err(ClientError::InternalError(format!(
+            "Unexpected token issuer: {:?}",
+            claims.iss
+        )));
+    }
+    if let Some(audience) = &expected.audience
+        && claims.aud.as_ref() != Some(audience)
+    {
+        return Err(ClientError::InternalError(format!(
+            "Unexpected token audience: {:?}",
+            claims.aud
+        )));
+    }
 
-    Ok(token_data.claims)
+    Ok(claims)
 }
 
 #[cfg(test)]
@@ -36,9 +92,72 @@ mod tests {
         let claims = Claims {
             sub: "user_id".to_string(),
             user_name: "test_user".to_string(),
+            role: None,
             exp: Utc::now().timestamp() + 30, // истечет через 30 секунд
+            iat: None,
+            iss: None,
+            aud: None,
+            jti: None,
         };
         assert!(claims.expires_soon(60)); // Истекает в течение минуты
         assert!(!claims.expires_soon(10)); // Не истекает в течение 10 секунд
     }
+
+    #[test]
+    fn test_role_from_claims() {
+        let claims = |role: &str| -> Claims {
+            serde_json::from_str(&format!(
+                r#"{{"sub":"id","user_name":"test_user","exp":0{role}}}"#
+            ))
+            .unwrap()
+        };
+        assert_eq!(claims(r#","role":"admin""#).role(), UserRole::Admin);
+        assert_eq!(claims(r#","role":"moderator""#).role(), UserRole::Moderator);
+        assert_eq!(claims(r#","role":"owner""#).role(), UserRole::User);
+        assert_eq!(claims("").role(), UserRole::User);
+    }
+
+    /// Токен с фиктивной подписью: клиент подпись не проверяет, а подписывать
+    /// нечем, потому что криптографический backend jsonwebtoken у клиента не включён.
+    fn unsigned_token(claims: &Claims) -> String {
+        use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims).unwrap());
+        format!("{header}.{payload}.signature")
+    }
+
+    #[test]
+    fn test_decode_checks_issuer_and_audience() {
+        let claims = Claims {
+            sub: "user_id".to_string(),
+            user_name: "test_user".to_string(),
+            role: None,
+            exp: Utc::now().timestamp() + 300,
+            iat: Some(Utc::now().timestamp()),
+            iss: Some("blog".to_string()),
+            aud: Some("blog-clients".to_string()),
+            jti: Some("1".to_string()),
+        };
+        let token = unsigned_token(&claims);
+
+        let expected = ExpectedClaims {
+            issuer: Some("blog".to_string()),
+            audience: Some("blog-clients".to_string()),
+        };
+        assert!(decode_token_without_validation(&token, &expected).is_ok());
+        assert!(decode_token_without_validation(&token, &ExpectedClaims::default()).is_ok());
+
+        let other_issuer = ExpectedClaims {
+            issuer: Some("other".to_string()),
+            audience: None,
+        };
+        assert!(decode_token_without_validation(&token, &other_issuer).is_err());
+
+        let other_audience = ExpectedClaims {
+            issuer: None,
+            audience: Some("other".to_string()),
+        };
+        assert!(decode_token_without_validation(&token, &other_audience).is_err());
+    }
 }
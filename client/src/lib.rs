@@ -8,15 +8,22 @@
 //! - gRPC клиент через tonic
 //! - Поддержка WebAssembly (WASM) для использования в браузере
 //! - Автоматическое управление JWT токенами
+//! - Отмена долгих вызовов через [`cancellation::CancellationToken`]
 //! - Единый интерфейс [`blog_client::BlogClient`] для всех транспортов
+//! - Административные вызовы через расширение [`admin::AdminClient`]
 //!
 //! ## Features
 //!
 //! - `http` - Включает HTTP клиент (reqwest)
 //! - `grpc` - Включает gRPC клиент (tonic)
-//! - `wasm` - Включает поддержку WebAssembly
+//! - `wasm` - Включает поддержку WebAssembly; gRPC клиент в браузере работает по gRPC-Web;
+//!   добавляет хранение токенов и очередь офлайн-изменений в `localStorage`
 //! - `default` - Включает и `http`, и `grpc`
 //!
+//! Клиент не привязан к async рантайму: блокировки и каналы уведомлений взяты из
+//! `futures`, поэтому в `wasm32-unknown-unknown` tokio не собирается. Нативно tokio
+//! нужен только транспорту gRPC.
+//!
 //! ## Примеры использования
 //!
 //! ### HTTP клиент
@@ -35,10 +42,10 @@
 //!     let user_id = client.login("user", "password").await?;
 //!     
 //!     // Создание поста
-//!     let post_id = client.create_post("Title", "Content").await?;
+//!     let post_id = client.create_post("Title", "Content", &["rust".to_string()], None, None).await?;
 //!     
 //!     // Получение списка постов
-//!     let posts = client.list_posts(10, 0).await?;
+//!     let posts = client.list_posts(10, 0, None).await?;
 //!     
 //!     Ok(())
 //! }
@@ -53,27 +60,66 @@
 //! async fn main() -> Result<(), Box<dyn std::error::Error>> {
 //!     let client = GrpcClient::new("http://localhost:50051".to_string()).await?;
 //!     
-//!     let posts = client.list_posts(10, 0).await?;
+//!     let posts = client.list_posts(10, 0, None).await?;
 //!     
 //!     Ok(())
 //! }
 //! ```
+//!
+//! ### Выбор транспорта при запуске
+//!
+//! ```rust,no_run
+//! use client::blog_client::{BlogClientBuilder, Transport};
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let transport = Transport::Grpc("http://localhost:50051".to_string());
+//!     let client = BlogClientBuilder::new(transport).build().await?;
+//!
+//!     let posts = client.list_posts(10, 0, None).await?;
+//!
+//!     Ok(())
+//! }
+//! ```
 
+pub mod admin;
 pub mod blog_client;
+pub mod cancellation;
+pub mod config;
 pub mod error;
+pub mod token_store;
 pub mod types;
 
 #[cfg(feature = "grpc")]
 pub mod grpc_client;
 
+#[cfg(all(feature = "grpc", not(target_arch = "wasm32")))]
+pub mod grpc_channel;
+
+#[cfg(all(feature = "grpc", target_arch = "wasm32", not(feature = "wasm")))]
+compile_error!("gRPC client for wasm32 requires the `wasm` feature (gRPC-Web transport)");
+
+#[cfg(all(feature = "grpc", feature = "wasm", target_arch = "wasm32"))]
+pub mod grpc_web;
+
 #[cfg(feature = "http")]
 pub mod http_client;
 
+#[cfg(feature = "wasm")]
+pub mod offline_queue;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tls;
+
+#[cfg(any(feature = "http", feature = "grpc"))]
+mod convert;
 mod interceptor;
 mod token_manager;
 
-// Экспортируем TokenUpdateEvent для использования в WASM-слое
-pub use token_manager::TokenUpdateEvent;
+// Экспортируем TokenUpdateEvent и каналы уведомлений для использования в WASM-слое
+pub use token_manager::{TokenUpdateEvent, mpsc};
+// Утверждения токена, которые возвращает AuthData::claims
+pub use interceptor::Claims;
 
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
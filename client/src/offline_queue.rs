@@ -0,0 +1,234 @@
+//! Очередь изменений постов, сделанных без сети (feature `wasm`).
+//!
+//! Операция, которую не удалось отправить из-за недоступности сервера, сохраняется
+//! в `localStorage` и отправляется позже вызовом [`OfflineQueue::replay`] - обычно
+//! по событию `online` браузера. Операции отправляются строго в порядке добавления.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use gloo_storage::{LocalStorage, Storage};
+
+use crate::{blog_client::BlogClient, error::ClientError, types};
+
+/// Ключ localStorage по умолчанию
+pub const DEFAULT_QUEUE_KEY: &str = "offline_queue";
+
+/// Отложенная операция изменения постов.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PendingOperation {
+    /// Создание поста или черновика
+    CreatePost {
+        title: String,
+        content: String,
+        tags: Vec<String>,
+        #[serde(default)]
+        category_id: Option<String>,
+        #[serde(default)]
+        summary: Option<String>,
+        draft: bool,
+    },
+    /// Обновление поста; без `category_id` и `summary` категория и описание не меняются
+    UpdatePost {
+        post_id: String,
+        title: String,
+        content: String,
+        tags: Vec<String>,
+        #[serde(default)]
+        category_id: Option<String>,
+        #[serde(default)]
+        summary: Option<String>,
+    },
+    /// Перенос поста в корзину
+    DeletePost { post_id: String },
+}
+
+impl PendingOperation {
+    /// Краткое описание операции для показа пользователю
+    pub fn describe(&self) -> String {
+        match self {
+            PendingOperation::CreatePost { title, draft, .. } if *draft => {
+                format!("Create draft \"{}\"", title)
+            }
+            PendingOperation::CreatePost { title, .. } => format!("Create post \"{}\"", title),
+            PendingOperation::UpdatePost { title, .. } => format!("Update post \"{}\"", title),
+            PendingOperation::DeletePost { post_id } => format!("Delete post {}", post_id),
+        }
+    }
+
+    async fn apply(&self, client: &dyn BlogClient) -> types::ClientResult<()> {
+        match self {
+            PendingOperation::CreatePost {
+                title,
+                content,
+                tags,
+                category_id,
+                summary,
+                draft: true,
+            } => client
+                .create_draft(
+                    title,
+                    content,
+                    tags,
+                    category_id.as_deref(),
+                    summary.as_deref(),
+                )
+                .await
+                .map(|_| ()),
+            PendingOperation::CreatePost {
+                title,
+                content,
+                tags,
+                category_id,
+                summary,
+                draft: false,
+            } => client
+                .create_post(
+                    title,
+                    content,
+                    tags,
+                    category_id.as_deref(),
+                    summary.as_deref(),
+                )
+                .await
+                .map(|_| ()),
+            PendingOperation::UpdatePost {
+                post_id,
+                title,
+                content,
+                tags,
+                category_id,
+                summary,
+            } => {
+                client
+                    .update_post(
+                        post_id,
+                        title,
+                        content,
+                        tags,
+                        category_id.as_deref(),
+                        summary.as_deref(),
+                    )
+                    .await
+            }
+            PendingOperation::DeletePost { post_id } => client.delete_post(post_id).await,
+        }
+    }
+}
+
+/// Чем закончилась отправка операции через [`OfflineQueue::submit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Submitted {
+    /// Операция выполнена на сервере
+    Sent,
+    /// Сервер недоступен: операция сохранена и будет отправлена при [`OfflineQueue::replay`]
+    Queued,
+}
+
+/// Результат отправки накопленных операций.
+#[derive(Debug, Default)]
+pub struct ReplaySummary {
+    /// Сколько операций выполнено на сервере
+    pub sent: usize,
+    /// Операции, которые сервер отклонил; они удалены из очереди, так как повтор не поможет
+    pub rejected: Vec<(PendingOperation, ClientError)>,
+}
+
+/// Очередь операций в `localStorage`.
+///
+/// Повреждённая запись считается пустой очередью.
+#[derive(Debug)]
+pub struct OfflineQueue {
+    key: String,
+    replaying: AtomicBool,
+}
+
+impl OfflineQueue {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            replaying: AtomicBool::new(false),
+        }
+    }
+
+    /// Операции, ожидающие отправки, в порядке добавления
+    pub fn pending_operations(&self) -> Vec<PendingOperation> {
+        LocalStorage::get(&self.key).unwrap_or_default()
+    }
+
+    /// Выполняет операцию или, если сервер недоступен, ставит её в очередь.
+    ///
+    /// Пока очередь не пуста, новые операции сразу добавляются в её конец, чтобы
+    /// сервер получил изменения в том порядке, в котором они были сделаны.
+    /// Ошибки, не связанные с сетью, возвращаются как есть.
+    pub async fn submit(
+        &self,
+        client: &dyn BlogClient,
+        operation: PendingOperation,
+    ) -> types::ClientResult<Submitted> {
+        let mut pending = self.pending_operations();
+        if pending.is_empty() {
+            match operation.apply(client).await {
+                Ok(()) => return Ok(Submitted::Sent),
+                Err(e) if !e.is_network_error() => return Err(e),
+                Err(_) => {}
+            }
+            // Пока шёл запрос, в очередь могли добавиться другие операции
+            pending = self.pending_operations();
+        }
+        pending.push(operation);
+        self.save(&pending)?;
+        Ok(Submitted::Queued)
+    }
+
+    /// Отправляет накопленные операции по порядку.
+    ///
+    /// Останавливается на первой сетевой ошибке, оставляя эту и следующие операции
+    /// в очереди. Повторный вызов во время отправки ничего не делает.
+    pub async fn replay(&self, client: &dyn BlogClient) -> types::ClientResult<ReplaySummary> {
+        let mut summary = ReplaySummary::default();
+        if self.replaying.swap(true, Ordering::AcqRel) {
+            return Ok(summary);
+        }
+        let result = self.replay_pending(client, &mut summary).await;
+        self.replaying.store(false, Ordering::Release);
+        result.map(|()| summary)
+    }
+
+    /// Удаляет все операции из очереди
+    pub fn clear(&self) {
+        LocalStorage::delete(&self.key);
+    }
+
+    async fn replay_pending(
+        &self,
+        client: &dyn BlogClient,
+        summary: &mut ReplaySummary,
+    ) -> types::ClientResult<()> {
+        while let Some(operation) = self.pending_operations().into_iter().next() {
+            match operation.apply(client).await {
+                Ok(()) => summary.sent += 1,
+                Err(e) if e.is_network_error() => break,
+                Err(e) => summary.rejected.push((operation, e)),
+            }
+            // Новые операции добавляются только в конец, поэтому первая - отправленная
+            let mut pending = self.pending_operations();
+            if !pending.is_empty() {
+                pending.remove(0);
+            }
+            self.save(&pending)?;
+        }
+        Ok(())
+    }
+
+    fn save(&self, pending: &[PendingOperation]) -> types::ClientResult<()> {
+        LocalStorage::set(&self.key, pending)
+            .map_err(|e| ClientError::InternalError(format!("Failed to save offline queue: {}", e)))
+    }
+}
+
+impl Default for OfflineQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_QUEUE_KEY)
+    }
+}
@@ -0,0 +1,100 @@
+//! Настройки TLS для нативных клиентов.
+
+use crate::error::ClientError;
+
+/// Параметры TLS-подключения к серверу.
+///
+/// По умолчанию сертификат сервера проверяется по встроенным корневым сертификатам.
+#[derive(Debug, Clone, Default)]
+pub struct TlsOptions {
+    /// Дополнительный доверенный корневой сертификат (PEM), например собственный CA
+    pub ca_certificate_pem: Option<Vec<u8>>,
+    /// Не проверять сертификат сервера.
+    ///
+    /// Только для разработки с самоподписанными сертификатами: соединение остаётся
+    /// зашифрованным, но не защищено от подмены сервера.
+    pub danger_accept_invalid_certs: bool,
+}
+
+impl TlsOptions {
+    /// Доверять дополнительно CA из PEM-файла
+    pub fn with_ca_certificate_file(
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, ClientError> {
+        let path = path.as_ref();
+        let pem = std::fs::read(path).map_err(|e| {
+            ClientError::TransportError(format!(
+                "Failed to read CA certificate {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(Self {
+            ca_certificate_pem: Some(pem),
+            ..Self::default()
+        })
+    }
+
+    /// Принимать любой сертификат сервера (см. [`TlsOptions::danger_accept_invalid_certs`])
+    pub fn insecure() -> Self {
+        Self {
+            danger_accept_invalid_certs: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Проверка сертификата, которая принимает любой сертификат сервера.
+///
+/// Подписи рукопожатия по-прежнему проверяются, пропускается только проверка цепочки и имени.
+#[cfg(feature = "grpc")]
+#[derive(Debug)]
+pub(crate) struct NoCertificateVerification {
+    algorithms: rustls::crypto::WebPkiSupportedAlgorithms,
+}
+
+#[cfg(feature = "grpc")]
+impl NoCertificateVerification {
+    pub(crate) fn new() -> Self {
+        Self {
+            algorithms: rustls::crypto::aws_lc_rs::default_provider()
+                .signature_verification_algorithms,
+        }
+    }
+}
+
+#[cfg(feature = "grpc")]
+impl rustls::client::danger::ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::pki_types::CertificateDer<'_>,
+        _intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        _server_name: &rustls::pki_types::ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: rustls::pki_types::UnixTime,
+    ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::danger::ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(message, cert, dss, &self.algorithms)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.algorithms.supported_schemes()
+    }
+}
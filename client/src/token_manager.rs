@@ -1,8 +1,16 @@
 use std::sync::Arc;
 
-use tokio::sync::{Mutex, RwLock, mpsc};
+use futures_util::lock::Mutex;
 
-use crate::{error::ClientError, interceptor::decode_token_without_validation, types};
+use crate::{
+    error::ClientError,
+    interceptor::{ExpectedClaims, decode_token_without_validation},
+    token_store::{MemoryTokenStore, TokenStore},
+    types,
+};
+
+/// Каналы уведомлений об обновлении токена и истечении сессии; не зависят от async рантайма
+pub use futures_channel::mpsc;
 
 /// Событие об обновлении токена
 #[derive(Debug, Clone)]
@@ -13,70 +21,100 @@ pub struct TokenUpdateEvent {
 /// Менеджер токенов с автоматической проверкой и обновлением
 #[derive(Clone)]
 pub struct TokenManager {
-    auth_data: Arc<RwLock<Option<types::AuthData>>>,
+    auth_data: Arc<Mutex<Option<types::AuthData>>>,
     token_refresh_buffer_seconds: i64,
+    expected_claims: ExpectedClaims,
     refresh_lock: Arc<Mutex<()>>,
     token_update_sender: Option<mpsc::UnboundedSender<TokenUpdateEvent>>,
+    session_expired_sender: Option<mpsc::UnboundedSender<()>>,
+    store: Arc<dyn TokenStore>,
 }
 
 impl TokenManager {
     pub fn new(token_refresh_buffer_seconds: i64) -> Self {
         Self {
-            auth_data: Arc::new(RwLock::new(None)),
+            auth_data: Arc::new(Mutex::new(None)),
             token_refresh_buffer_seconds,
+            expected_claims: ExpectedClaims::default(),
             refresh_lock: Arc::new(Mutex::new(())),
             token_update_sender: None,
+            session_expired_sender: None,
+            store: Arc::new(MemoryTokenStore::new()),
         }
     }
 
-    /// Создает TokenManager с channel для уведомлений об обновлении токена
-    pub fn new_with_notifier(
-        token_refresh_buffer_seconds: i64,
-        sender: mpsc::UnboundedSender<TokenUpdateEvent>,
-    ) -> Self {
-        Self {
-            auth_data: Arc::new(RwLock::new(None)),
-            token_refresh_buffer_seconds,
-            refresh_lock: Arc::new(Mutex::new(())),
-            token_update_sender: Some(sender),
-        }
-    }
-
-    /// Устанавливает данные аутентификации
-    pub async fn set_auth_data(&self, auth_data: types::AuthData) {
+    /// Устанавливает данные аутентификации и сохраняет их в хранилище
+    pub async fn set_auth_data(&self, auth_data: types::AuthData) -> Result<(), ClientError> {
         let access_token = auth_data.access_token.clone();
-        let mut data = self.auth_data.write().await;
+        let mut data = self.auth_data.lock().await;
+        self.store.save(&auth_data)?;
         *data = Some(auth_data);
 
         // Уведомляем об обновлении токена
         if let Some(sender) = &self.token_update_sender {
-            let _ = sender.send(TokenUpdateEvent { access_token });
+            let _ = sender.unbounded_send(TokenUpdateEvent { access_token });
         }
+        Ok(())
     }
 
     /// Получает access token
     pub async fn get_access_token(&self) -> Option<String> {
-        let auth_data = self.auth_data.read().await;
+        let auth_data = self.auth_data.lock().await;
         auth_data.as_ref().map(|data| data.access_token.clone())
     }
 
     /// Получает refresh token
     pub async fn get_refresh_token(&self) -> Option<String> {
-        let auth_data = self.auth_data.read().await;
+        let auth_data = self.auth_data.lock().await;
         auth_data.as_ref().map(|data| data.refresh_token.clone())
     }
 
     /// Получает полные данные аутентификации
     pub async fn get_auth_data(&self) -> Option<types::AuthData> {
-        let auth_data = self.auth_data.read().await;
+        let auth_data = self.auth_data.lock().await;
         auth_data.clone()
     }
 
+    /// Удаляет данные аутентификации, в том числе из хранилища
+    pub async fn clear_auth_data(&self) -> Result<(), ClientError> {
+        let mut data = self.auth_data.lock().await;
+        *data = None;
+        self.store.clear()
+    }
+
     /// Устанавливает буфер времени для обновления токена (в секундах)
     pub fn set_token_refresh_buffer(&mut self, seconds: i64) {
         self.token_refresh_buffer_seconds = seconds;
     }
 
+    /// Подключает хранилище токенов и загружает из него сохранённые токены
+    pub fn set_store(&mut self, store: Arc<dyn TokenStore>) -> Result<(), ClientError> {
+        let auth_data = store.load()?;
+        self.auth_data = Arc::new(Mutex::new(auth_data));
+        self.store = store;
+        Ok(())
+    }
+
+    /// Устанавливает channel для уведомлений об обновлении токена
+    pub fn set_notifier(&mut self, sender: mpsc::UnboundedSender<TokenUpdateEvent>) {
+        self.token_update_sender = Some(sender);
+    }
+
+    /// Устанавливает channel для уведомлений об истечении сессии
+    pub fn set_session_expired_notifier(&mut self, sender: mpsc::UnboundedSender<()>) {
+        self.session_expired_sender = Some(sender);
+    }
+
+    /// Устанавливает ожидаемые издателя и аудиторию токенов
+    pub fn set_expected_claims(&mut self, expected_claims: ExpectedClaims) {
+        self.expected_claims = expected_claims;
+    }
+
+    /// Ожидаемые издатель и аудитория токенов
+    pub fn expected_claims(&self) -> &ExpectedClaims {
+        &self.expected_claims
+    }
+
     /// Проверяет токен и обновляет его при необходимости
     /// Использует мьютекс для предотвращения одновременного обновления токена несколькими запросами
     pub async fn ensure_valid_token<F, Fut>(&self, refresh_fn: F) -> Result<(), ClientError>
@@ -85,11 +123,11 @@ impl TokenManager {
         Fut: std::future::Future<Output = Result<types::AuthData, ClientError>>,
     {
         // Сначала быстро проверяем без блокировки
-        let auth_data_clone = self.auth_data.read().await.clone();
+        let auth_data_clone = self.auth_data.lock().await.clone();
 
         if let Some(data) = auth_data_clone {
             // Декодируем access token для проверки времени жизни
-            match decode_token_without_validation(&data.access_token) {
+            match decode_token_without_validation(&data.access_token, &self.expected_claims) {
                 Ok(claims) => {
                     // Если токен истекает скоро, обновляем его
                     if claims.expires_soon(self.token_refresh_buffer_seconds) {
@@ -98,11 +136,12 @@ impl TokenManager {
 
                         // Проверяем токен еще раз после захвата мьютекса
                         // (возможно, другой поток уже обновил его)
-                        let current_auth_data = self.auth_data.read().await.clone();
+                        let current_auth_data = self.auth_data.lock().await.clone();
                         if let Some(current_data) = current_auth_data {
-                            if let Ok(current_claims) =
-                                decode_token_without_validation(&current_data.access_token)
-                            {
+                            if let Ok(current_claims) = decode_token_without_validation(
+                                &current_data.access_token,
+                                &self.expected_claims,
+                            ) {
                                 // Если токен уже обновлен другим потоком, не обновляем снова
                                 if !current_claims.expires_soon(self.token_refresh_buffer_seconds) {
                                     return Ok(());
@@ -110,16 +149,8 @@ impl TokenManager {
                             }
 
                             // Обновляем токен через переданную функцию
-                            let new_auth_data = refresh_fn(current_data.refresh_token).await?;
-
-                            let access_token = new_auth_data.access_token.clone();
-                            let mut auth_data_write = self.auth_data.write().await;
-                            *auth_data_write = Some(new_auth_data);
-
-                            // Уведомляем об обновлении токена
-                            if let Some(sender) = &self.token_update_sender {
-                                let _ = sender.send(TokenUpdateEvent { access_token });
-                            }
+                            self.refresh_with(current_data.refresh_token, refresh_fn)
+                                .await?;
                         }
                     }
                 }
@@ -132,6 +163,70 @@ impl TokenManager {
 
         Ok(())
     }
+
+    /// Обновляет access токен, который сервер отклонил (ответ 401).
+    ///
+    /// Возвращает `false`, если refresh токена нет и повторять запрос бессмысленно;
+    /// сессия в этом случае считается истёкшей. Если токен уже обновил другой запрос, `refresh_fn` не вызывается.
+    #[cfg(feature = "http")]
+    pub async fn refresh_rejected_token<F, Fut>(
+        &self,
+        rejected_access_token: &str,
+        refresh_fn: F,
+    ) -> Result<bool, ClientError>
+    where
+        F: FnOnce(String) -> Fut,
+        Fut: std::future::Future<Output = Result<types::AuthData, ClientError>>,
+    {
+        let _guard = self.refresh_lock.lock().await;
+
+        let Some(current_data) = self.auth_data.lock().await.clone() else {
+            return Ok(false);
+        };
+        if current_data.access_token != rejected_access_token {
+            return Ok(true);
+        }
+        if current_data.refresh_token.is_empty() {
+            self.expire_session().await?;
+            return Ok(false);
+        }
+
+        self.refresh_with(current_data.refresh_token, refresh_fn)
+            .await?;
+        Ok(true)
+    }
+
+    /// Обновляет токены через `refresh_fn`.
+    ///
+    /// Если сервер отклонил refresh токен, сессия завершена: токены удаляются
+    /// и отправляется уведомление об истечении сессии.
+    async fn refresh_with<F, Fut>(
+        &self,
+        refresh_token: String,
+        refresh_fn: F,
+    ) -> Result<(), ClientError>
+    where
+        F: FnOnce(String) -> Fut,
+        Fut: std::future::Future<Output = Result<types::AuthData, ClientError>>,
+    {
+        match refresh_fn(refresh_token).await {
+            Ok(new_auth_data) => self.set_auth_data(new_auth_data).await,
+            Err(ClientError::Unauthorized) => {
+                self.expire_session().await?;
+                Err(ClientError::Unauthorized)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Удаляет токены истёкшей сессии и уведомляет об этом
+    async fn expire_session(&self) -> Result<(), ClientError> {
+        self.clear_auth_data().await?;
+        if let Some(sender) = &self.session_expired_sender {
+            let _ = sender.unbounded_send(());
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -147,7 +242,7 @@ mod tests {
             refresh_token: "test_refresh".to_string(),
         };
 
-        manager.set_auth_data(auth_data.clone()).await;
+        manager.set_auth_data(auth_data.clone()).await.unwrap();
 
         let token = manager.get_access_token().await;
         assert_eq!(token, Some("test_access".to_string()));
@@ -155,4 +250,140 @@ mod tests {
         let refresh = manager.get_refresh_token().await;
         assert_eq!(refresh, Some("test_refresh".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_token_manager_persists_to_store() {
+        let store = Arc::new(MemoryTokenStore::new());
+        let auth_data = types::AuthData {
+            access_token: "test_access".to_string(),
+            refresh_token: "test_refresh".to_string(),
+        };
+
+        let mut manager = TokenManager::new(300);
+        manager.set_store(store.clone()).unwrap();
+        manager.set_auth_data(auth_data).await.unwrap();
+
+        // Новый менеджер восстанавливает токены из того же хранилища
+        let mut restored = TokenManager::new(300);
+        restored.set_store(store.clone()).unwrap();
+        assert_eq!(
+            restored.get_access_token().await,
+            Some("test_access".to_string())
+        );
+
+        restored.clear_auth_data().await.unwrap();
+        assert!(store.load().unwrap().is_none());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_refresh_rejected_token_refreshes_once() {
+        let manager = TokenManager::new(300);
+        manager
+            .set_auth_data(types::AuthData {
+                access_token: "old_access".to_string(),
+                refresh_token: "old_refresh".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let refreshed = manager
+            .refresh_rejected_token("old_access", |refresh_token| async move {
+                assert_eq!(refresh_token, "old_refresh");
+                Ok(types::AuthData {
+                    access_token: "new_access".to_string(),
+                    refresh_token: "new_refresh".to_string(),
+                })
+            })
+            .await
+            .unwrap();
+        assert!(refreshed);
+        assert_eq!(
+            manager.get_access_token().await,
+            Some("new_access".to_string())
+        );
+
+        // Токен уже заменён другим запросом: повторно не обновляем
+        let refreshed = manager
+            .refresh_rejected_token("old_access", |_| async {
+                panic!("token must not be refreshed twice")
+            })
+            .await
+            .unwrap();
+        assert!(refreshed);
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_refresh_rejected_token_without_refresh_token() {
+        let manager = TokenManager::new(300);
+        manager
+            .set_auth_data(types::AuthData {
+                access_token: "access".to_string(),
+                refresh_token: String::new(),
+            })
+            .await
+            .unwrap();
+
+        let refreshed = manager
+            .refresh_rejected_token("access", |_| async {
+                panic!("token cannot be refreshed without refresh token")
+            })
+            .await
+            .unwrap();
+        assert!(!refreshed);
+        assert!(manager.get_auth_data().await.is_none());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_rejected_refresh_expires_session() {
+        let store = Arc::new(MemoryTokenStore::new());
+        let (sender, mut receiver) = mpsc::unbounded();
+        let mut manager = TokenManager::new(300);
+        manager.set_store(store.clone()).unwrap();
+        manager.set_session_expired_notifier(sender);
+        manager
+            .set_auth_data(types::AuthData {
+                access_token: "access".to_string(),
+                refresh_token: "revoked_refresh".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let result = manager
+            .refresh_rejected_token("access", |_| async { Err(ClientError::Unauthorized) })
+            .await;
+        assert!(matches!(result, Err(ClientError::Unauthorized)));
+        assert!(manager.get_auth_data().await.is_none());
+        assert!(store.load().unwrap().is_none());
+        assert!(receiver.try_recv().is_ok());
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn test_failed_refresh_keeps_session() {
+        let (sender, mut receiver) = mpsc::unbounded();
+        let mut manager = TokenManager::new(300);
+        manager.set_session_expired_notifier(sender);
+        manager
+            .set_auth_data(types::AuthData {
+                access_token: "access".to_string(),
+                refresh_token: "refresh".to_string(),
+            })
+            .await
+            .unwrap();
+
+        // Сетевой сбой не завершает сессию: токены остаются для следующей попытки
+        let result = manager
+            .refresh_rejected_token("access", |_| async {
+                Err(ClientError::TransportError(
+                    "connection refused".to_string(),
+                ))
+            })
+            .await;
+        assert!(matches!(result, Err(ClientError::TransportError(_))));
+        assert!(manager.get_auth_data().await.is_some());
+        assert!(receiver.try_recv().is_err());
+    }
 }
@@ -0,0 +1,227 @@
+//! Хранилища токенов, из которых клиент восстанавливает вход после перезапуска.
+
+use std::sync::Mutex;
+
+#[cfg(feature = "wasm")]
+use gloo_storage::{LocalStorage, Storage};
+
+use crate::{error::ClientError, types};
+
+/// Файл токенов по умолчанию (в текущей директории)
+#[cfg(not(target_arch = "wasm32"))]
+pub const DEFAULT_TOKEN_FILE: &str = ".blog_token";
+
+/// Ключ localStorage по умолчанию
+#[cfg(feature = "wasm")]
+pub const DEFAULT_STORAGE_KEY: &str = "auth_token";
+
+/// Сервис, под которым токены хранятся в хранилище учётных данных ОС
+#[cfg(all(feature = "keyring", not(target_arch = "wasm32")))]
+pub const DEFAULT_KEYRING_SERVICE: &str = "blog-client";
+
+/// Хранилище данных аутентификации.
+///
+/// Клиент загружает токены при подключении хранилища, сохраняет их после входа
+/// и каждого обновления и очищает при выходе.
+pub trait TokenStore: Send + Sync {
+    /// Возвращает сохранённые токены; `None`, если их нет
+    fn load(&self) -> Result<Option<types::AuthData>, ClientError>;
+    /// Сохраняет токены, заменяя предыдущие
+    fn save(&self, auth_data: &types::AuthData) -> Result<(), ClientError>;
+    /// Удаляет сохранённые токены
+    fn clear(&self) -> Result<(), ClientError>;
+}
+
+/// Хранилище в памяти: токены живут, пока существует хранилище.
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    auth_data: Mutex<Option<types::AuthData>>,
+}
+
+impl MemoryTokenStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<types::AuthData>> {
+        // Данные не бывают частично записанными, поэтому отравление мьютекса не страшно
+        self.auth_data
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&self) -> Result<Option<types::AuthData>, ClientError> {
+        Ok(self.lock().clone())
+    }
+
+    fn save(&self, auth_data: &types::AuthData) -> Result<(), ClientError> {
+        *self.lock() = Some(auth_data.clone());
+        Ok(())
+    }
+
+    fn clear(&self) -> Result<(), ClientError> {
+        *self.lock() = None;
+        Ok(())
+    }
+}
+
+/// Хранилище в JSON-файле.
+///
+/// Повреждённый файл считается отсутствующим: его перезапишет следующий вход.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileTokenStore {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Путь к файлу токенов
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    fn error(&self, action: &str, e: impl std::fmt::Display) -> ClientError {
+        ClientError::TokenStoreError(format!(
+            "Failed to {} {}: {}",
+            action,
+            self.path.display(),
+            e
+        ))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for FileTokenStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_TOKEN_FILE)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> Result<Option<types::AuthData>, ClientError> {
+        let json = match std::fs::read_to_string(&self.path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(self.error("read", e)),
+        };
+        Ok(serde_json::from_str(&json).ok())
+    }
+
+    fn save(&self, auth_data: &types::AuthData) -> Result<(), ClientError> {
+        let json = serde_json::to_string(auth_data).map_err(|e| self.error("encode", e))?;
+        std::fs::write(&self.path, json).map_err(|e| self.error("write", e))
+    }
+
+    fn clear(&self) -> Result<(), ClientError> {
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(self.error("remove", e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Хранилище учётных данных ОС: Keychain в macOS, Secret Service в Linux,
+/// Credential Manager в Windows (feature `keyring`).
+///
+/// Токены хранятся одной JSON-записью для пары сервиса и учётной записи.
+/// Повреждённая запись считается отсутствующей: её перезапишет следующий вход.
+#[cfg(all(feature = "keyring", not(target_arch = "wasm32")))]
+#[derive(Debug, Clone)]
+pub struct KeyringTokenStore {
+    service: String,
+    account: String,
+}
+
+#[cfg(all(feature = "keyring", not(target_arch = "wasm32")))]
+impl KeyringTokenStore {
+    pub fn new(service: impl Into<String>, account: impl Into<String>) -> Self {
+        Self {
+            service: service.into(),
+            account: account.into(),
+        }
+    }
+
+    fn entry(&self) -> Result<keyring::Entry, ClientError> {
+        keyring::Entry::new(&self.service, &self.account).map_err(|e| self.error("open", e))
+    }
+
+    fn error(&self, action: &str, e: impl std::fmt::Display) -> ClientError {
+        ClientError::TokenStoreError(format!(
+            "Failed to {} keyring entry {}/{}: {}",
+            action, self.service, self.account, e
+        ))
+    }
+}
+
+#[cfg(all(feature = "keyring", not(target_arch = "wasm32")))]
+impl TokenStore for KeyringTokenStore {
+    fn load(&self) -> Result<Option<types::AuthData>, ClientError> {
+        match self.entry()?.get_password() {
+            Ok(json) => Ok(serde_json::from_str(&json).ok()),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(self.error("read", e)),
+        }
+    }
+
+    fn save(&self, auth_data: &types::AuthData) -> Result<(), ClientError> {
+        let json = serde_json::to_string(auth_data).map_err(|e| self.error("encode", e))?;
+        self.entry()?
+            .set_password(&json)
+            .map_err(|e| self.error("write", e))
+    }
+
+    fn clear(&self) -> Result<(), ClientError> {
+        match self.entry()?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(self.error("remove", e)),
+        }
+    }
+}
+
+/// Хранилище в `localStorage` браузера (feature `wasm`).
+///
+/// Повреждённая запись считается отсутствующей: её перезапишет следующий вход.
+#[cfg(feature = "wasm")]
+#[derive(Debug, Clone)]
+pub struct LocalStorageTokenStore {
+    key: String,
+}
+
+#[cfg(feature = "wasm")]
+impl LocalStorageTokenStore {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into() }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl Default for LocalStorageTokenStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_STORAGE_KEY)
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl TokenStore for LocalStorageTokenStore {
+    fn load(&self) -> Result<Option<types::AuthData>, ClientError> {
+        Ok(LocalStorage::get(&self.key).ok())
+    }
+
+    fn save(&self, auth_data: &types::AuthData) -> Result<(), ClientError> {
+        LocalStorage::set(&self.key, auth_data)
+            .map_err(|e| ClientError::TokenStoreError(format!("Failed to save tokens: {}", e)))
+    }
+
+    fn clear(&self) -> Result<(), ClientError> {
+        LocalStorage::delete(&self.key);
+        Ok(())
+    }
+}
@@ -3,23 +3,115 @@ use uuid::Uuid;
 /// Тип Result для операций клиента.
 pub type ClientResult<T> = Result<T, crate::error::ClientError>;
 
+/// Статус публикации поста.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostStatus {
+    /// Черновик, виден только автору
+    Draft,
+    /// Опубликованный пост
+    Published,
+}
+
+/// Поле сортировки списка постов.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PostSortField {
+    /// Время создания
+    #[default]
+    CreatedAt,
+    /// Время последнего обновления
+    UpdatedAt,
+    /// Заголовок
+    Title,
+}
+
+/// Направление сортировки.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// По возрастанию
+    Asc,
+    /// По убыванию
+    #[default]
+    Desc,
+}
+
+/// Порядок сортировки списка постов; по умолчанию сначала идут новые посты.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PostSort {
+    /// Поле сортировки
+    pub field: PostSortField,
+    /// Направление сортировки
+    pub order: SortOrder,
+}
+
 /// Представление поста блога.
 ///
 /// Содержит все данные поста, включая метаданные о создании и обновлении.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Post {
     /// Уникальный идентификатор поста
     pub id: Uuid,
     /// Заголовок поста
     pub title: String,
-    /// Содержимое поста
+    /// Содержимое поста (Markdown); пустое, если сервер вернул пост без содержимого
     pub content: String,
+    /// Содержимое, отрендеренное сервером в HTML (если рендеринг включён)
+    pub content_html: Option<String>,
+    /// Краткое описание поста: заданное автором или начало содержимого
+    pub summary: String,
+    /// ID автора поста
+    pub author_id: Uuid,
+    /// Имя автора поста
+    pub author_username: String,
+    /// Ссылка на аватар автора поста
+    pub author_avatar_url: Option<String>,
+    /// Теги поста
+    pub tags: Vec<String>,
+    /// ID категории поста
+    pub category_id: Option<Uuid>,
+    /// ID блога поста; `None` у постов общей ленты сервера
+    pub blog_id: Option<Uuid>,
+    /// Ссылки на прикреплённые файлы
+    pub attachments: Vec<String>,
+    /// Статус публикации
+    pub status: PostStatus,
+    /// Количество лайков
+    pub like_count: u64,
+    /// Лайкнул ли пост текущий пользователь
+    pub liked_by_me: bool,
     /// Временная метка создания
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Временная метка последнего обновления
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Страница списка постов с метаданными пагинации.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostPage {
+    /// Посты текущей страницы
+    pub posts: Vec<Post>,
+    /// Общее количество постов
+    pub total: u64,
+    /// Номер страницы (начиная с 0)
+    pub page: u32,
+    /// Размер страницы
+    pub page_size: u32,
+    /// Есть ли посты после текущей страницы
+    pub has_next: bool,
+    /// Курсор следующей страницы для [`list_posts_after`](crate::blog_client::BlogClient::list_posts_after)
+    pub next_cursor: Option<String>,
+}
+
+/// Событие живой ленты опубликованных постов.
+#[derive(Debug, Clone)]
+pub enum PostEvent {
+    /// Пост опубликован
+    Created(Post),
+    /// Опубликованный пост изменён
+    Updated(Post),
+    /// Пост удалён или снят с публикации
+    Deleted(Uuid),
+}
+
 /// Представление пользователя.
 #[derive(Debug, Clone)]
 pub struct User {
@@ -29,6 +121,263 @@ pub struct User {
     pub username: String,
 }
 
+/// Публичный профиль пользователя.
+#[derive(Debug, Clone)]
+pub struct UserProfile {
+    /// Уникальный идентификатор пользователя
+    pub id: Uuid,
+    /// Имя пользователя
+    pub username: String,
+    /// Отображаемое имя
+    pub display_name: Option<String>,
+    /// Описание профиля
+    pub bio: Option<String>,
+    /// Ссылка на изображение аватара
+    pub avatar_url: Option<String>,
+    /// Временная метка регистрации
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Частота email-дайджеста новых постов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestFrequency {
+    /// Раз в сутки
+    Daily,
+    /// Раз в неделю
+    Weekly,
+}
+
+/// Категория постов; категории образуют дерево через `parent_id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Category {
+    /// Уникальный идентификатор категории
+    pub id: Uuid,
+    /// Название категории
+    pub name: String,
+    /// ID родительской категории; `None` у категорий верхнего уровня
+    pub parent_id: Option<Uuid>,
+    /// Временная метка создания
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Блог на сервере; у каждого блога свой адрес, владелец и посты.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blog {
+    /// Уникальный идентификатор блога
+    pub id: Uuid,
+    /// Адрес блога, например для [`BlogClient::select_blog`](crate::blog_client::BlogClient::select_blog)
+    pub slug: String,
+    /// Название блога
+    pub name: String,
+    /// ID владельца блога
+    pub owner_id: Uuid,
+    /// Временная метка создания
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Настройки email-дайджеста текущего пользователя.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigestSettings {
+    /// Частота писем; `None`, если пользователь не подписан
+    pub frequency: Option<DigestFrequency>,
+    /// Время предыдущего дайджеста; для новой подписки - время подписки
+    pub last_sent_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Сеанс входа: устройство, которому выдан refresh токен.
+#[derive(Debug, Clone)]
+pub struct Session {
+    /// Уникальный идентификатор сеанса
+    pub id: Uuid,
+    /// `User-Agent` клиента при последнем использовании
+    pub user_agent: Option<String>,
+    /// IP-адрес клиента при последнем использовании
+    pub ip_address: Option<String>,
+    /// Временная метка входа
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Временная метка последнего входа или обновления токена
+    pub last_used_at: chrono::DateTime<chrono::Utc>,
+    /// Сеанс, которому принадлежит текущий токен клиента
+    pub current: bool,
+}
+
+/// Роль пользователя.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserRole {
+    /// Обычный пользователь
+    User,
+    /// Модератор
+    Moderator,
+    /// Администратор
+    Admin,
+}
+
+/// Пользователь в списке пользователей для администратора.
+#[derive(Debug, Clone)]
+pub struct AdminUser {
+    /// Уникальный идентификатор пользователя
+    pub id: Uuid,
+    /// Имя пользователя
+    pub username: String,
+    /// Email пользователя
+    pub email: String,
+    /// Роль пользователя
+    pub role: UserRole,
+    /// Временная метка регистрации
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Когда пользователь был отключён; `None` для активных пользователей
+    pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Страница списка пользователей с метаданными пагинации.
+#[derive(Debug, Clone)]
+pub struct UserPage {
+    /// Пользователи текущей страницы
+    pub users: Vec<AdminUser>,
+    /// Общее количество пользователей
+    pub total: u64,
+    /// Номер страницы (начиная с 0)
+    pub page: u32,
+    /// Размер страницы
+    pub page_size: u32,
+    /// Есть ли пользователи после текущей страницы
+    pub has_next: bool,
+}
+
+/// Сводные показатели системы.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemStats {
+    /// Количество пользователей
+    pub users: u64,
+    /// Количество постов, включая черновики и не считая постов в корзине
+    pub posts: u64,
+    /// Количество регистраций за последние `recent_signups_days` дней
+    pub recent_signups: u64,
+    /// За сколько последних дней считаются новые регистрации
+    pub recent_signups_days: u32,
+}
+
+/// Причина жалобы на пост.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportReason {
+    /// Спам или реклама
+    Spam,
+    /// Оскорбления и травля
+    Harassment,
+    /// Язык вражды
+    HateSpeech,
+    /// Недостоверная информация
+    Misinformation,
+    /// Неприемлемое содержимое
+    Inappropriate,
+    /// Другая причина, описанная в пояснении
+    Other,
+}
+
+/// Состояние жалобы на пост.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportStatus {
+    /// Ждёт решения модератора
+    Open,
+    /// Модератор скрыл пост
+    Hidden,
+    /// Модератор отклонил жалобу
+    Dismissed,
+}
+
+/// Жалоба на пост.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    /// Уникальный идентификатор жалобы
+    pub id: Uuid,
+    /// ID поста, на который пожаловались
+    pub post_id: Uuid,
+    /// ID пользователя, отправившего жалобу
+    pub reporter_id: Uuid,
+    /// Причина жалобы
+    pub reason: ReportReason,
+    /// Пояснение к жалобе
+    pub details: Option<String>,
+    /// Состояние жалобы
+    pub status: ReportStatus,
+    /// Временная метка отправки жалобы
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Когда модератор принял решение; `None` для открытых жалоб
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Решение модератора по жалобе.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportAction {
+    /// Скрыть пост и закрыть все открытые жалобы на него
+    HidePost,
+    /// Отклонить жалобу
+    Dismiss,
+}
+
+/// Страница очереди жалоб с метаданными пагинации.
+#[derive(Debug, Clone)]
+pub struct ReportPage {
+    /// Жалобы текущей страницы, от старых к новым
+    pub reports: Vec<Report>,
+    /// Общее количество жалоб с учётом фильтра по состоянию
+    pub total: u64,
+    /// Номер страницы (начиная с 0)
+    pub page: u32,
+    /// Размер страницы
+    pub page_size: u32,
+    /// Есть ли жалобы после текущей страницы
+    pub has_next: bool,
+}
+
+/// Сведения о сервере, полученные при создании клиента.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerInfo {
+    /// Версия сервера
+    pub version: String,
+    /// Обслуживаемые версии API (`v1`, ...)
+    pub api_versions: Vec<String>,
+    /// Включённые необязательные возможности (см. [`api::version::features`])
+    pub features: Vec<String>,
+    /// Ограничения на создание постов; `None`, если сервер их не сообщает
+    pub post_limits: Option<PostLimits>,
+}
+
+/// Ограничения на создание постов, которые проверяет сервер.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostLimits {
+    /// Сколько постов можно создать за сутки (UTC); `None` - без квоты
+    pub max_posts_per_day: Option<u32>,
+    /// Максимальный размер содержимого поста в байтах UTF-8
+    pub max_content_bytes: u64,
+    /// Максимальная длина заголовка в символах
+    pub max_title_chars: u32,
+}
+
+impl ServerInfo {
+    /// Включена ли на сервере возможность `feature`
+    pub fn supports(&self, feature: &str) -> bool {
+        self.features.iter().any(|f| f == feature)
+    }
+
+    /// Проверяет, что сервер обслуживает версию API этого клиента.
+    ///
+    /// Возвращает [`ClientError::IncompatibleServer`](crate::error::ClientError::IncompatibleServer), если не обслуживает.
+    pub fn check_compatible(&self) -> ClientResult<()> {
+        if self
+            .api_versions
+            .iter()
+            .any(|version| version == api::version::API_VERSION)
+        {
+            return Ok(());
+        }
+        Err(crate::error::ClientError::IncompatibleServer {
+            server_version: self.version.clone(),
+            api_versions: self.api_versions.clone(),
+        })
+    }
+}
+
 pub(crate) type Token = String;
 
 /// Данные аутентификации (внутренний тип).
@@ -39,3 +388,50 @@ pub struct AuthData {
     /// JWT refresh токен
     pub refresh_token: Token,
 }
+
+impl AuthData {
+    /// Утверждения access токена (подпись токена не проверяется)
+    pub fn claims(&self) -> ClientResult<crate::Claims> {
+        crate::interceptor::decode_token_without_validation(&self.access_token, &Default::default())
+    }
+
+    /// ID пользователя из access токена
+    pub fn user_id(&self) -> Option<Uuid> {
+        Uuid::parse_str(&self.claims().ok()?.sub).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ClientError;
+
+    fn server_info(api_versions: &[&str]) -> ServerInfo {
+        ServerInfo {
+            version: "0.2.0".to_string(),
+            api_versions: api_versions.iter().map(|v| v.to_string()).collect(),
+            features: vec!["post_events".to_string()],
+            post_limits: None,
+        }
+    }
+
+    #[test]
+    fn test_server_with_client_version_is_compatible() {
+        let info = server_info(&["v1", "v2"]);
+
+        assert!(info.check_compatible().is_ok());
+        assert!(info.supports("post_events"));
+        assert!(!info.supports("oauth"));
+    }
+
+    #[test]
+    fn test_server_without_client_version_is_incompatible() {
+        let error = server_info(&["v2"]).check_compatible().unwrap_err();
+
+        assert!(matches!(
+            error,
+            ClientError::IncompatibleServer { server_version, api_versions }
+                if server_version == "0.2.0" && api_versions == ["v2"]
+        ));
+    }
+}
@@ -0,0 +1,4 @@
+fn main() {
+    // Миграции встраиваются в бинарник макросом `sqlx::migrate!`
+    println!("cargo:rerun-if-changed=migrations");
+}
@@ -0,0 +1,10 @@
+-- Отозванные refresh токены (выход из системы)
+-- Хранится SHA-256 отпечаток токена, а не сам токен
+CREATE TABLE IF NOT EXISTS revoked_tokens (
+    token_hash VARCHAR(64) PRIMARY KEY,
+    expires_at TIMESTAMPTZ NOT NULL,
+    revoked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Индекс для очистки записей с истёкшим сроком действия
+CREATE INDEX IF NOT EXISTS idx_revoked_tokens_expires_at ON revoked_tokens(expires_at);
@@ -0,0 +1,9 @@
+-- Теги постов (связь многие-ко-многим хранится в одной таблице)
+CREATE TABLE IF NOT EXISTS post_tags (
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    tag VARCHAR(32) NOT NULL,
+    PRIMARY KEY (post_id, tag)
+);
+
+-- Индекс для фильтрации постов по тегу
+CREATE INDEX IF NOT EXISTS idx_post_tags_tag ON post_tags(tag);
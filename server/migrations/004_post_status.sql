@@ -0,0 +1,8 @@
+-- Статус публикации поста: черновики видны только автору.
+-- Существующие посты считаются опубликованными.
+ALTER TABLE posts
+    ADD COLUMN IF NOT EXISTS status VARCHAR(16) NOT NULL DEFAULT 'published'
+        CHECK (status IN ('draft', 'published'));
+
+-- Индекс для выборки опубликованных постов
+CREATE INDEX IF NOT EXISTS idx_posts_status ON posts(status);
@@ -0,0 +1,6 @@
+-- Публичный профиль пользователя: отображаемое имя, описание и ссылка на аватар.
+-- Все поля необязательные.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS display_name VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS bio VARCHAR(1000),
+    ADD COLUMN IF NOT EXISTS avatar_url VARCHAR(512);
@@ -0,0 +1,5 @@
+-- Роль пользователя: user, moderator или admin.
+-- Существующие пользователи получают роль user; администраторы назначаются вручную.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS role VARCHAR(16) NOT NULL DEFAULT 'user'
+        CHECK (role IN ('user', 'moderator', 'admin'));
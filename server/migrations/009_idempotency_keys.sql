@@ -0,0 +1,15 @@
+-- Ключи идемпотентности REST-запросов
+-- scope включает метод, путь и пользователя, поэтому разные клиенты могут использовать одинаковые ключи
+-- Пока запрос выполняется, response_status и response_body равны NULL
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    scope TEXT NOT NULL,
+    idempotency_key VARCHAR(255) NOT NULL,
+    request_hash VARCHAR(64) NOT NULL,
+    response_status SMALLINT,
+    response_body TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (scope, idempotency_key)
+);
+
+-- Индекс для удаления устаревших ключей
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created_at ON idempotency_keys(created_at);
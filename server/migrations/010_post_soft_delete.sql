@@ -0,0 +1,8 @@
+-- Мягкое удаление постов: удалённый пост попадает в корзину,
+-- откуда его можно восстановить или удалить окончательно.
+ALTER TABLE posts
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ NULL;
+
+-- Индекс для выборки постов из корзины
+CREATE INDEX IF NOT EXISTS idx_posts_deleted_at ON posts(deleted_at)
+    WHERE deleted_at IS NOT NULL;
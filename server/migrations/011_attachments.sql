@@ -0,0 +1,16 @@
+-- Загруженные файлы (вложения). Файл хранится в BlobStorage под ключом storage_key,
+-- post_id заполняется, когда автор прикрепляет вложение к посту.
+CREATE TABLE IF NOT EXISTS attachments (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    post_id UUID REFERENCES posts(id) ON DELETE SET NULL,
+    storage_key VARCHAR(128) NOT NULL,
+    url VARCHAR(1024) NOT NULL,
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(128) NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Индекс для выборки вложений поста
+CREATE INDEX IF NOT EXISTS idx_attachments_post_id ON attachments(post_id);
@@ -0,0 +1,4 @@
+-- HTML-представление содержимого поста, отрендеренное из Markdown при сохранении.
+-- NULL, если рендеринг отключён в конфигурации или пост сохранён до его появления.
+ALTER TABLE posts
+    ADD COLUMN IF NOT EXISTS content_html TEXT NULL;
@@ -0,0 +1,13 @@
+-- Журнал действий пользователей: вход, регистрация и изменения постов.
+-- user_id без внешнего ключа, чтобы записи переживали удаление пользователя.
+CREATE TABLE IF NOT EXISTS audit_log (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    action VARCHAR(32) NOT NULL,
+    target_id UUID,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Индексы для выборки журнала по пользователю и по времени
+CREATE INDEX IF NOT EXISTS idx_audit_log_user_id_created_at ON audit_log(user_id, created_at);
+CREATE INDEX IF NOT EXISTS idx_audit_log_created_at ON audit_log(created_at);
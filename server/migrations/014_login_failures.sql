@@ -0,0 +1,7 @@
+-- Неудачные попытки входа подряд и блокировка входа после их серии.
+-- Запись удаляется при успешном входе.
+CREATE TABLE IF NOT EXISTS login_failures (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    failed_attempts INTEGER NOT NULL DEFAULT 0,
+    locked_until TIMESTAMPTZ
+);
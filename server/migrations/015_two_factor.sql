@@ -0,0 +1,16 @@
+-- Секреты TOTP для двухфакторной аутентификации.
+-- Секрет сохраняется при подключении и включается после подтверждения первым кодом.
+CREATE TABLE IF NOT EXISTS user_totp (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    secret VARCHAR(64) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Незавершённые входы: выдаются после проверки пароля и обмениваются на токены по коду TOTP.
+-- Как и токены сброса пароля, хранятся только SHA-256 отпечатки.
+CREATE TABLE IF NOT EXISTS login_challenges (
+    token_hash VARCHAR(64) PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    expires_at TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,11 @@
+-- Аккаунты у внешних провайдеров входа (Google, GitHub), связанные с пользователями.
+-- subject - постоянный ID пользователя у провайдера (`sub` в OpenID Connect).
+CREATE TABLE IF NOT EXISTS oauth_identities (
+    provider VARCHAR(32) NOT NULL,
+    subject VARCHAR(255) NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (provider, subject)
+);
+
+CREATE INDEX IF NOT EXISTS idx_oauth_identities_user_id ON oauth_identities(user_id);
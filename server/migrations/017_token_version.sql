@@ -0,0 +1,3 @@
+-- Версия токенов пользователя: записывается в выдаваемые JWT.
+-- Выход со всех устройств увеличивает её, и все выданные ранее токены перестают действовать.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS token_version INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- Отключение пользователя администратором: отключённый пользователь не может войти.
+-- NULL - пользователь активен.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS disabled_at TIMESTAMPTZ;
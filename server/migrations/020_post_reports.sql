@@ -0,0 +1,17 @@
+-- Жалобы пользователей на посты: один пользователь жалуется на пост один раз.
+-- Модератор скрывает пост (переносит в корзину) или отклоняет жалобу.
+CREATE TABLE IF NOT EXISTS reports (
+    id UUID PRIMARY KEY,
+    post_id UUID NOT NULL REFERENCES posts(id) ON DELETE CASCADE,
+    reporter_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    reason VARCHAR(32) NOT NULL, -- 'spam' | 'harassment' | 'hate_speech' | 'misinformation' | 'inappropriate' | 'other'
+    details VARCHAR(1000),
+    status VARCHAR(16) NOT NULL DEFAULT 'open', -- 'open' | 'hidden' | 'dismissed'
+    resolved_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (post_id, reporter_id)
+);
+
+-- Индекс для очереди жалоб модератора
+CREATE INDEX IF NOT EXISTS idx_reports_status_created_at ON reports(status, created_at);
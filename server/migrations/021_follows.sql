@@ -0,0 +1,11 @@
+-- Подписки пользователей на авторов: лента показывает посты авторов из подписок
+CREATE TABLE IF NOT EXISTS follows (
+    follower_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    followee_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (follower_id, followee_id),
+    CHECK (follower_id <> followee_id)
+);
+
+-- Индекс для поиска подписчиков автора
+CREATE INDEX IF NOT EXISTS idx_follows_followee_id ON follows(followee_id);
@@ -0,0 +1,19 @@
+-- Время первой публикации поста: по нему дайджест выбирает посты, опубликованные
+-- после предыдущего письма. Уже опубликованные посты считаются опубликованными при создании.
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS published_at TIMESTAMPTZ;
+UPDATE posts SET published_at = created_at WHERE status = 'published' AND published_at IS NULL;
+
+-- Индекс для выборки постов, опубликованных за период
+CREATE INDEX IF NOT EXISTS idx_posts_published_at ON posts(published_at);
+
+-- Подписки пользователей на email-дайджест новых постов
+CREATE TABLE IF NOT EXISTS digest_subscriptions (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    frequency VARCHAR(16) NOT NULL, -- 'daily' | 'weekly'
+    last_sent_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Индекс для выборки подписок, которым пора отправить дайджест
+CREATE INDEX IF NOT EXISTS idx_digest_subscriptions_frequency_last_sent_at
+    ON digest_subscriptions(frequency, last_sent_at);
@@ -0,0 +1,17 @@
+-- Категории постов; подкатегория ссылается на родительскую категорию
+CREATE TABLE IF NOT EXISTS categories (
+    id UUID PRIMARY KEY,
+    name VARCHAR(64) NOT NULL,
+    parent_id UUID REFERENCES categories(id) ON DELETE RESTRICT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Имена категорий уникальны среди категорий с общим родителем (без учёта регистра)
+CREATE UNIQUE INDEX IF NOT EXISTS idx_categories_parent_id_name
+    ON categories(COALESCE(parent_id, '00000000-0000-0000-0000-000000000000'::uuid), LOWER(name));
+
+-- Категория поста; пост может быть без категории
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS category_id UUID REFERENCES categories(id) ON DELETE SET NULL;
+
+-- Индекс для выборки постов категории
+CREATE INDEX IF NOT EXISTS idx_posts_category_id ON posts(category_id);
@@ -0,0 +1,2 @@
+-- Краткое описание поста, заданное автором; без него описание берётся из начала содержимого
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS summary TEXT;
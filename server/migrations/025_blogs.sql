@@ -0,0 +1,15 @@
+-- Блоги: один сервер обслуживает несколько блогов со своими владельцами
+CREATE TABLE IF NOT EXISTS blogs (
+    id UUID PRIMARY KEY,
+    slug VARCHAR(64) NOT NULL UNIQUE,
+    name VARCHAR(128) NOT NULL,
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Блог поста; посты без блога относятся к общей ленте сервера.
+-- После удаления блога его посты остаются в общей ленте
+ALTER TABLE posts ADD COLUMN IF NOT EXISTS blog_id UUID REFERENCES blogs(id) ON DELETE SET NULL;
+
+-- Индекс для выборки постов блога
+CREATE INDEX IF NOT EXISTS idx_posts_blog_id ON posts(blog_id);
@@ -0,0 +1,167 @@
+use crate::application::dto::admin::{SystemStatsDto, UserPageDto, UserSummaryDto};
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::user::Role;
+use crate::domain::repositories::admin::AdminRepository;
+use crate::domain::repositories::repo::UserRepository;
+use crate::infrastructure::body_log::BodyLogging;
+use std::sync::Arc;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+/// За сколько последних дней регистрации считаются новыми в статистике
+const RECENT_SIGNUPS_DAYS: u32 = 7;
+
+/// Администрирование: пользователи, статистика системы и отладочное логирование тел.
+/// Все действия доступны только администратору, права проверяются здесь для REST и gRPC.
+pub struct AdminApplication<Repo: AdminRepository + UserRepository + ?Sized> {
+    repository: Arc<Repo>,
+    body_logging: BodyLogging,
+}
+
+impl<Repo: AdminRepository + UserRepository + ?Sized> AdminApplication<Repo> {
+    pub fn new(repository: Arc<Repo>) -> Self {
+        Self {
+            repository,
+            body_logging: BodyLogging::default(),
+        }
+    }
+
+    /// Переключатель логирования тел, общий с middleware REST и слоем gRPC
+    pub fn with_body_logging(mut self, body_logging: BodyLogging) -> Self {
+        self.body_logging = body_logging;
+        self
+    }
+
+    /// Возвращает страницу пользователей в порядке регистрации.
+    #[instrument(skip(self))]
+    pub async fn list_users(
+        &self,
+        page: u32,
+        page_size: u32,
+        role: Role,
+    ) -> DomainResult<UserPageDto> {
+        debug!("Fetching users");
+        ensure_admin(role)?;
+
+        let users = self.repository.list_users(page, page_size).await?;
+        let total = self.repository.count_users().await?;
+        info!("Retrieved {} of {} users", users.len(), total);
+        Ok(UserPageDto {
+            users: users.into_iter().map(UserSummaryDto::from_entity).collect(),
+            total,
+            page,
+            page_size,
+        })
+    }
+
+    /// Отключает пользователя или включает его снова.
+    ///
+    /// Отключённый пользователь не может войти, а выданные ему токены и сеансы
+    /// отзываются сразу. Отключить самого себя администратор не может.
+    #[instrument(skip(self), fields(user_id = %user_id, admin_id = %admin_id))]
+    pub async fn set_user_disabled(
+        &self,
+        user_id: Uuid,
+        disabled: bool,
+        admin_id: Uuid,
+        role: Role,
+    ) -> DomainResult<()> {
+        debug!("Changing user disabled state");
+        ensure_admin(role)?;
+        if disabled && user_id == admin_id {
+            warn!("Administrator tried to disable their own account");
+            return Err(DomainError::Forbidden {
+                reason: "administrators cannot disable their own account".to_string(),
+            });
+        }
+
+        let disabled_at = disabled.then(chrono::Utc::now);
+        if !self
+            .repository
+            .set_user_disabled(user_id, disabled_at)
+            .await?
+        {
+            warn!("User not found");
+            return Err(DomainError::ProfileNotFound { user_id });
+        }
+        if disabled {
+            self.repository.increment_token_version(user_id).await?;
+            self.repository.delete_user_sessions(user_id).await?;
+        }
+
+        info!("User disabled state changed to {}", disabled);
+        Ok(())
+    }
+
+    /// Возвращает число пользователей, постов и новых регистраций за последние дни.
+    #[instrument(skip(self))]
+    pub async fn get_system_stats(&self, role: Role) -> DomainResult<SystemStatsDto> {
+        debug!("Collecting system stats");
+        ensure_admin(role)?;
+
+        let since = chrono::Utc::now() - chrono::Duration::days(RECENT_SIGNUPS_DAYS.into());
+        let stats = self.repository.get_system_stats(since).await?;
+        info!("System stats collected");
+        Ok(SystemStatsDto::from_entity(stats, RECENT_SIGNUPS_DAYS))
+    }
+
+    /// Включено ли логирование тел запросов и ответов.
+    pub fn body_logging_enabled(&self, role: Role) -> DomainResult<bool> {
+        ensure_admin(role)?;
+        Ok(self.body_logging.is_enabled())
+    }
+
+    /// Включает или выключает логирование тел запросов и ответов без перезапуска.
+    #[instrument(skip(self), fields(admin_id = %admin_id))]
+    pub fn set_body_logging(&self, enabled: bool, admin_id: Uuid, role: Role) -> DomainResult<()> {
+        ensure_admin(role)?;
+        self.body_logging.set(enabled);
+        warn!(
+            "Body logging {} by {}",
+            if enabled { "enabled" } else { "disabled" },
+            admin_id
+        );
+        Ok(())
+    }
+}
+
+/// Возвращает `Forbidden`, если роль не даёт прав администратора
+fn ensure_admin(role: Role) -> DomainResult<()> {
+    if !role.can_administer() {
+        warn!("Admin access denied");
+        return Err(DomainError::Forbidden {
+            reason: "only administrators can perform this action".to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::memrepo::InMemoryRepository;
+
+    #[test]
+    fn test_only_admin_toggles_body_logging() {
+        let body_logging = BodyLogging::new(false);
+        let app = AdminApplication::new(Arc::new(InMemoryRepository::new()))
+            .with_body_logging(body_logging.clone());
+        let admin_id = Uuid::now_v7();
+
+        for role in [Role::User, Role::Moderator] {
+            assert!(matches!(
+                app.set_body_logging(true, admin_id, role),
+                Err(DomainError::Forbidden { .. })
+            ));
+            assert!(matches!(
+                app.body_logging_enabled(role),
+                Err(DomainError::Forbidden { .. })
+            ));
+        }
+        assert!(!body_logging.is_enabled());
+
+        app.set_body_logging(true, admin_id, Role::Admin).unwrap();
+        assert!(body_logging.is_enabled());
+        assert!(app.body_logging_enabled(Role::Admin).unwrap());
+    }
+}
@@ -0,0 +1,277 @@
+use crate::application::admin::AdminApplication;
+use crate::application::auth::AuthApplication;
+use crate::application::dto::audit::{AuditEntryDto, AuditPageDto};
+use crate::application::dto::auth::{LoginDto, LoginOutcome, RegisterDto, TokenDto, TotpLoginDto};
+use crate::application::dto::post::{CreatePostDto, PostDto, UpdatePostDto};
+use crate::application::post::PostApplication;
+use crate::domain::entities::audit::{AuditAction, AuditEntry, AuditFilter};
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::oauth::OAuthProviderKind;
+use crate::domain::entities::session::DeviceInfo;
+use crate::domain::entities::user::{Role, User};
+use crate::domain::repositories::admin::AdminRepository;
+use crate::domain::repositories::audit::AuditRepository;
+use crate::domain::repositories::post::PostRepository;
+use crate::domain::repositories::repo::UserRepository;
+use std::ops::Deref;
+use std::sync::Arc;
+use tracing::{debug, error, info, instrument, warn};
+use uuid::Uuid;
+
+pub struct AuditApplication<Repo: AuditRepository + ?Sized> {
+    audit_repository: Arc<Repo>,
+}
+
+impl<Repo: AuditRepository + ?Sized> AuditApplication<Repo> {
+    pub fn new(audit_repository: Arc<Repo>) -> Self {
+        Self { audit_repository }
+    }
+
+    /// Записывает действие пользователя в журнал.
+    ///
+    /// Действие к этому моменту уже выполнено, поэтому ошибка записи только
+    /// логируется и не отменяет запрос.
+    #[instrument(skip(self), fields(user_id = %user_id, action = action.as_str()))]
+    pub async fn record(&self, user_id: Uuid, action: AuditAction, target_id: Option<Uuid>) {
+        let entry = AuditEntry {
+            id: Uuid::now_v7(),
+            user_id,
+            action,
+            target_id,
+            created_at: chrono::Utc::now(),
+        };
+        if let Err(e) = self.audit_repository.record_audit_entry(&entry).await {
+            error!("Failed to record audit entry: {}", e);
+        }
+    }
+
+    /// Возвращает страницу журнала от новых записей к старым; доступно только администратору.
+    #[instrument(skip(self))]
+    pub async fn list_entries(
+        &self,
+        filter: AuditFilter,
+        page: u32,
+        page_size: u32,
+        role: Role,
+    ) -> DomainResult<AuditPageDto> {
+        debug!("Fetching audit log");
+        if !role.can_view_audit_log() {
+            warn!("Audit log access denied");
+            return Err(DomainError::Forbidden {
+                reason: "only administrators can view the audit log".to_string(),
+            });
+        }
+
+        let entries = self
+            .audit_repository
+            .get_audit_entries(&filter, page, page_size)
+            .await?;
+        let total = self.audit_repository.count_audit_entries(&filter).await?;
+        info!("Retrieved {} of {} audit entries", entries.len(), total);
+        Ok(AuditPageDto {
+            entries: entries
+                .into_iter()
+                .map(AuditEntryDto::from_entity)
+                .collect(),
+            total,
+            page,
+            page_size,
+        })
+    }
+}
+
+/// Декоратор сервиса приложения, записывающий успешные действия в журнал аудита.
+///
+/// Журналируемые методы переопределены в `impl` блоках для конкретных сервисов,
+/// остальные методы доступны через [`Deref`] без изменений.
+pub struct Audited<App, Repo: AuditRepository + ?Sized> {
+    inner: App,
+    audit: Arc<AuditApplication<Repo>>,
+}
+
+impl<App, Repo: AuditRepository + ?Sized> Audited<App, Repo> {
+    pub fn new(inner: App, audit: Arc<AuditApplication<Repo>>) -> Self {
+        Self { inner, audit }
+    }
+}
+
+impl<App, Repo: AuditRepository + ?Sized> Deref for Audited<App, Repo> {
+    type Target = App;
+
+    fn deref(&self) -> &App {
+        &self.inner
+    }
+}
+
+impl<Repo: UserRepository + ?Sized, Audit: AuditRepository + ?Sized>
+    Audited<AuthApplication<Repo>, Audit>
+{
+    pub async fn create_user(&self, dto: RegisterDto) -> DomainResult<User> {
+        let user = self.inner.create_user(dto).await?;
+        self.audit
+            .record(user.id, AuditAction::Register, None)
+            .await;
+        Ok(user)
+    }
+
+    /// Вход записывается, когда выданы токены: при включённой 2FA - после кода TOTP
+    pub async fn login(&self, dto: LoginDto) -> DomainResult<LoginOutcome> {
+        let outcome = self.inner.login(dto).await?;
+        if let LoginOutcome::Authenticated(token) = &outcome {
+            self.audit
+                .record(token.user_id, AuditAction::Login, None)
+                .await;
+        }
+        Ok(outcome)
+    }
+
+    pub async fn login_with_totp(&self, dto: TotpLoginDto) -> DomainResult<TokenDto> {
+        let token = self.inner.login_with_totp(dto).await?;
+        self.audit
+            .record(token.user_id, AuditAction::Login, None)
+            .await;
+        Ok(token)
+    }
+
+    pub async fn login_with_oauth(
+        &self,
+        provider: OAuthProviderKind,
+        code: &str,
+        device: DeviceInfo,
+    ) -> DomainResult<LoginOutcome> {
+        let outcome = self.inner.login_with_oauth(provider, code, device).await?;
+        if let LoginOutcome::Authenticated(token) = &outcome {
+            self.audit
+                .record(token.user_id, AuditAction::Login, None)
+                .await;
+        }
+        Ok(outcome)
+    }
+}
+
+impl<
+    Posts: PostRepository + ?Sized,
+    Users: UserRepository + ?Sized,
+    Audit: AuditRepository + ?Sized,
+> Audited<PostApplication<Posts, Users>, Audit>
+{
+    pub async fn create_post(&self, dto: CreatePostDto) -> DomainResult<PostDto> {
+        let author_id = dto.author_id;
+        let post = self.inner.create_post(dto).await?;
+        self.audit
+            .record(author_id, AuditAction::CreatePost, Some(post.uuid))
+            .await;
+        Ok(post)
+    }
+
+    pub async fn create_posts(&self, dtos: Vec<CreatePostDto>) -> DomainResult<Vec<PostDto>> {
+        let posts = self.inner.create_posts(dtos).await?;
+        for post in &posts {
+            self.audit
+                .record(post.author_id, AuditAction::CreatePost, Some(post.uuid))
+                .await;
+        }
+        Ok(posts)
+    }
+
+    pub async fn update_post(
+        &self,
+        dto: UpdatePostDto,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<PostDto> {
+        let post = self.inner.update_post(dto, user_id, role).await?;
+        self.audit
+            .record(user_id, AuditAction::UpdatePost, Some(post.uuid))
+            .await;
+        Ok(post)
+    }
+
+    pub async fn delete_post(&self, post_id: Uuid, user_id: Uuid, role: Role) -> DomainResult<()> {
+        self.inner.delete_post(post_id, user_id, role).await?;
+        self.audit
+            .record(user_id, AuditAction::DeletePost, Some(post_id))
+            .await;
+        Ok(())
+    }
+
+    pub async fn delete_posts(
+        &self,
+        post_ids: Vec<Uuid>,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<Vec<Uuid>> {
+        let post_ids = self.inner.delete_posts(post_ids, user_id, role).await?;
+        for post_id in &post_ids {
+            self.audit
+                .record(user_id, AuditAction::DeletePost, Some(*post_id))
+                .await;
+        }
+        Ok(post_ids)
+    }
+
+    pub async fn restore_post(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<PostDto> {
+        let post = self.inner.restore_post(post_id, user_id, role).await?;
+        self.audit
+            .record(user_id, AuditAction::RestorePost, Some(post_id))
+            .await;
+        Ok(post)
+    }
+
+    pub async fn purge_post(&self, post_id: Uuid, user_id: Uuid, role: Role) -> DomainResult<()> {
+        self.inner.purge_post(post_id, user_id, role).await?;
+        self.audit
+            .record(user_id, AuditAction::PurgePost, Some(post_id))
+            .await;
+        Ok(())
+    }
+
+    pub async fn force_delete_post(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<()> {
+        self.inner.force_delete_post(post_id, user_id, role).await?;
+        self.audit
+            .record(user_id, AuditAction::PurgePost, Some(post_id))
+            .await;
+        Ok(())
+    }
+
+    pub async fn hide_post(&self, post_id: Uuid, user_id: Uuid, role: Role) -> DomainResult<()> {
+        self.inner.hide_post(post_id, user_id, role).await?;
+        self.audit
+            .record(user_id, AuditAction::HidePost, Some(post_id))
+            .await;
+        Ok(())
+    }
+}
+
+impl<Repo: AdminRepository + UserRepository + ?Sized, Audit: AuditRepository + ?Sized>
+    Audited<AdminApplication<Repo>, Audit>
+{
+    pub async fn set_user_disabled(
+        &self,
+        user_id: Uuid,
+        disabled: bool,
+        admin_id: Uuid,
+        role: Role,
+    ) -> DomainResult<()> {
+        self.inner
+            .set_user_disabled(user_id, disabled, admin_id, role)
+            .await?;
+        let action = if disabled {
+            AuditAction::DisableUser
+        } else {
+            AuditAction::EnableUser
+        };
+        self.audit.record(admin_id, action, Some(user_id)).await;
+        Ok(())
+    }
+}
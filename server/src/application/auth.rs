@@ -1,28 +1,100 @@
-use crate::application::dto::auth::{LoginDto, RegisterDto, TokenDto};
-use crate::domain::entities::{errors::DomainResult, user::User};
+use crate::application::dto::auth::{
+    LoginDto, LoginOutcome, OAuthRedirectDto, RegisterDto, ResetPasswordDto, SessionDto, TokenDto,
+    TotpEnrollmentDto, TotpLoginDto,
+};
+use crate::domain::entities::{
+    errors::DomainResult,
+    oauth::{OAuthProfile, OAuthProviderKind},
+    session::{DeviceInfo, Session},
+    user::{IdentifierKind, Role, User},
+};
+use crate::domain::repositories::email::{EmailMessage, EmailSender};
+use crate::domain::repositories::oauth::OAuthProviders;
 use crate::domain::repositories::repo::UserRepository;
-use crate::domain::services::auth::AuthService;
+use crate::domain::repositories::unit_of_work::UnitOfWork;
+use crate::domain::services::auth::{AuthService, Claims, REFRESH_TOKEN_LIFETIME, TokenType};
+use crate::domain::services::totp;
 use std::sync::Arc;
-use tracing::{debug, info, instrument, warn};
+use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
 
-pub struct AuthApplication<Repo: UserRepository> {
+/// Время жизни токена сброса пароля
+const RESET_TOKEN_TTL_MINUTES: i64 = 60;
+/// Время жизни токена второго шага входа (ввода кода TOTP)
+const LOGIN_CHALLENGE_TTL_MINUTES: i64 = 5;
+/// Название сервиса в приложении-аутентификаторе
+const TOTP_ISSUER: &str = "Blog";
+/// Ограничения длины имени пользователя (совпадают с проверкой при регистрации)
+const USERNAME_MIN_LEN: usize = 3;
+const USERNAME_MAX_LEN: usize = 32;
+/// Сколько раз подбирается свободное имя для пользователя, пришедшего через OAuth
+const OAUTH_USERNAME_ATTEMPTS: usize = 5;
+/// Сколько раз повторяется первый вход через OAuth, столкнувшийся с параллельной регистрацией
+const OAUTH_LINK_ATTEMPTS: usize = 3;
+
+/// Блокировка входа после серии неудачных попыток.
+#[derive(Debug, Clone, Copy)]
+pub struct LoginLockout {
+    /// Сколько неудачных попыток подряд блокируют вход; `0` отключает блокировку
+    pub max_failed_attempts: u32,
+    /// На сколько блокируется вход
+    pub lock_duration: chrono::Duration,
+}
+
+pub struct AuthApplication<Repo: UserRepository + ?Sized> {
     user_repository: Arc<Repo>,
     auth_service: Arc<AuthService>,
+    lockout: LoginLockout,
+    oauth_providers: OAuthProviders,
+    /// Отправка писем со сбросом пароля; без неё токены сброса не выпускаются
+    email_sender: Option<Arc<dyn EmailSender>>,
+    /// Название сайта в теме письма
+    site_name: String,
 }
 
-impl<Repo: UserRepository> AuthApplication<Repo> {
-    pub fn new(user_repository: Arc<Repo>, auth_service: Arc<AuthService>) -> Self {
+impl<Repo: UserRepository + ?Sized> AuthApplication<Repo> {
+    pub fn new(
+        user_repository: Arc<Repo>,
+        auth_service: Arc<AuthService>,
+        lockout: LoginLockout,
+        oauth_providers: OAuthProviders,
+    ) -> Self {
         Self {
             user_repository,
             auth_service,
+            lockout,
+            oauth_providers,
+            email_sender: None,
+            site_name: String::new(),
         }
     }
 
-    #[instrument(skip(self, dto), fields(username = %dto.username, email = %dto.email))]
+    /// Подключает отправку писем: токен сброса пароля приходит пользователю на email.
+    pub fn with_email(mut self, email_sender: Arc<dyn EmailSender>, site_name: String) -> Self {
+        self.email_sender = Some(email_sender);
+        self.site_name = site_name;
+        self
+    }
+
     pub async fn create_user(&self, dto: RegisterDto) -> DomainResult<User> {
+        self.create_user_with_role(dto, Role::User).await
+    }
+
+    /// Создаёт пользователя с ролью `role`; регистрация через API всегда даёт [`Role::User`].
+    #[instrument(skip(self, dto), fields(username = %dto.username, email = %dto.email, role = ?role))]
+    pub async fn create_user_with_role(&self, dto: RegisterDto, role: Role) -> DomainResult<User> {
         debug!("Attempting to create new user");
 
+        // Иначе при входе такое имя было бы принято за email
+        if IdentifierKind::detect(&dto.username) == IdentifierKind::Email {
+            warn!("User registration failed: username contains '@'");
+            return Err(
+                crate::domain::entities::errors::DomainError::InvalidUsername {
+                    reason: "must not contain '@'".to_string(),
+                },
+            );
+        }
+
         if self
             .user_repository
             .exists_by_username(&dto.username)
@@ -51,6 +123,7 @@ impl<Repo: UserRepository> AuthApplication<Repo> {
             dto.username.clone(),
             dto.email,
             password_hash,
+            role,
             chrono::Utc::now(),
         );
 
@@ -60,79 +133,1151 @@ impl<Repo: UserRepository> AuthApplication<Repo> {
         Ok(created_user)
     }
 
-    #[instrument(skip(self, dto), fields(username = %dto.username))]
-    pub async fn login(&self, dto: LoginDto) -> DomainResult<TokenDto> {
+    #[instrument(skip(self), fields(username = %username))]
+    pub async fn is_username_available(&self, username: &str) -> DomainResult<bool> {
+        debug!("Checking username availability");
+        let exists = self.user_repository.exists_by_username(username).await?;
+        Ok(!exists)
+    }
+
+    #[instrument(skip(self, dto), fields(identifier = %dto.identifier, kind = ?dto.kind))]
+    pub async fn login(&self, dto: LoginDto) -> DomainResult<LoginOutcome> {
         debug!("Attempting user login");
 
         // Найти пользователя
-        let user = self
-            .user_repository
-            .find_by_username(&dto.username)
-            .await?
-            .ok_or_else(|| {
-                warn!("Login failed: user not found");
-                crate::domain::entities::errors::DomainError::UserNotFound {
-                    username: dto.username.clone(),
-                }
-            })?;
+        let user = match dto.kind {
+            IdentifierKind::Username => {
+                self.user_repository
+                    .find_by_username(&dto.identifier)
+                    .await?
+            }
+            IdentifierKind::Email => self.user_repository.find_by_email(&dto.identifier).await?,
+        };
+        let user = user.ok_or_else(|| {
+            warn!("Login failed: user not found");
+            crate::domain::entities::errors::DomainError::UserNotFound {
+                username: dto.identifier.clone(),
+            }
+        })?;
+
+        self.check_login_lock(user.id).await?;
 
         if !self
             .auth_service
             .verify_password(&dto.password, &user.password_hash)
         {
             warn!("Login failed: invalid credentials for user");
-            return Err(crate::domain::entities::errors::DomainError::InvalidCredentials);
+            return Err(self
+                .reject_login(
+                    user.id,
+                    crate::domain::entities::errors::DomainError::InvalidCredentials,
+                )
+                .await);
         }
 
-        let access_token = self
-            .auth_service
-            .generate_token(&user.id.to_string(), &user.username);
+        self.complete_first_factor(&user, dto.device).await
+    }
+
+    /// Начинает вход через провайдера OAuth: возвращает адрес его страницы входа.
+    ///
+    /// Значение `state` нужно сохранить на стороне клиента и сверить при возврате
+    /// пользователя (см. [`login_with_oauth`](Self::login_with_oauth)).
+    #[instrument(skip(self), fields(provider = provider.as_str()))]
+    pub fn start_oauth_login(&self, provider: OAuthProviderKind) -> DomainResult<OAuthRedirectDto> {
+        let oauth_provider = self.oauth_provider(provider)?;
+        let state = self.auth_service.generate_oauth_state();
+
+        Ok(OAuthRedirectDto {
+            authorization_url: oauth_provider.authorization_url(&state),
+            state,
+        })
+    }
+
+    /// Завершает вход через провайдера OAuth по коду авторизации.
+    ///
+    /// Аккаунт у провайдера связывается с пользователем при первом входе: с уже
+    /// зарегистрированным, если провайдер подтвердил его email, иначе с новым.
+    /// Для пользователя с включённой 2FA, как и при входе по паролю, нужен код TOTP.
+    #[instrument(skip(self, code), fields(provider = provider.as_str()))]
+    pub async fn login_with_oauth(
+        &self,
+        provider: OAuthProviderKind,
+        code: &str,
+        device: DeviceInfo,
+    ) -> DomainResult<LoginOutcome> {
+        debug!("Attempting OAuth login");
+
+        let profile = self.oauth_provider(provider)?.fetch_profile(code).await?;
+        let user = match self
+            .user_repository
+            .find_by_oauth_identity(provider.as_str(), &profile.subject)
+            .await?
+        {
+            Some(user) => user,
+            None => self.link_oauth_account(provider, profile).await?,
+        };
+
+        self.check_login_lock(user.id).await?;
+        self.complete_first_factor(&user, device).await
+    }
+
+    fn oauth_provider(
+        &self,
+        provider: OAuthProviderKind,
+    ) -> DomainResult<&Arc<dyn crate::domain::repositories::oauth::OAuthProvider>> {
+        self.oauth_providers.get(&provider).ok_or_else(|| {
+            warn!("OAuth login failed: provider is not configured");
+            crate::domain::entities::errors::DomainError::OAuthProviderNotConfigured {
+                provider: provider.as_str().to_string(),
+            }
+        })
+    }
+
+    /// Связывает аккаунт у провайдера с пользователем при первом входе через него.
+    ///
+    /// Пользователь с тем же email находится, только если провайдер подтвердил адрес:
+    /// иначе чужой аккаунт у провайдера мог бы получить доступ к пользователю.
+    /// Новый пользователь и связь сохраняются одной транзакцией, поэтому сбой связывания
+    /// не оставляет пользователя без аккаунта у провайдера.
+    async fn link_oauth_account(
+        &self,
+        provider: OAuthProviderKind,
+        profile: OAuthProfile,
+    ) -> DomainResult<User> {
+        let email = profile.email.clone().ok_or_else(|| {
+            warn!("OAuth login failed: provider did not return an email");
+            crate::domain::entities::errors::DomainError::OAuthError(
+                "provider did not return an email address".to_string(),
+            )
+        })?;
+
+        let mut attempt = 1;
+        loop {
+            match self
+                .try_link_oauth_account(provider, &profile, &email)
+                .await
+            {
+                // Параллельный первый вход успел зарегистрировать пользователя с тем же
+                // email или именем: повторяем, теперь пользователь находится по email
+                Err(crate::domain::entities::errors::DomainError::UserAlreadyExists { .. })
+                    if attempt < OAUTH_LINK_ATTEMPTS =>
+                {
+                    warn!("OAuth registration conflicted with another one, retrying");
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Одна попытка [`link_oauth_account`](Self::link_oauth_account): поиск и
+    /// регистрация пользователя выполняются в той же транзакции, что и связывание.
+    async fn try_link_oauth_account(
+        &self,
+        provider: OAuthProviderKind,
+        profile: &OAuthProfile,
+        email: &str,
+    ) -> DomainResult<User> {
+        let mut unit = self.user_repository.begin().await?;
+        let user = match unit.find_by_email(email).await? {
+            Some(user) if profile.email_verified => {
+                info!("Linking OAuth account to existing user: {}", user.id);
+                user
+            }
+            Some(_) => {
+                warn!("OAuth login failed: unverified email belongs to another user");
+                return Err(
+                    crate::domain::entities::errors::DomainError::UserAlreadyExists {
+                        username: email.to_string(),
+                    },
+                );
+            }
+            None => {
+                self.create_oauth_user(
+                    unit.as_mut(),
+                    profile.username_hint.as_deref(),
+                    email.to_string(),
+                )
+                .await?
+            }
+        };
 
-        let refresh_token = self
+        unit.link_oauth_identity(user.id, provider.as_str(), &profile.subject)
+            .await?;
+        unit.commit().await?;
+        Ok(user)
+    }
+
+    /// Регистрирует пользователя, впервые вошедшего через провайдера.
+    ///
+    /// Имя берётся из подсказки провайдера (при занятости к нему добавляется суффикс),
+    /// пароль - случайный: задать свой можно через сброс пароля.
+    async fn create_oauth_user(
+        &self,
+        unit: &mut dyn UnitOfWork,
+        username_hint: Option<&str>,
+        email: String,
+    ) -> DomainResult<User> {
+        let base = oauth_username_base(username_hint.unwrap_or_default());
+        let mut username = base.clone();
+        let mut attempts = 0;
+        while unit.exists_by_username(&username).await? {
+            attempts += 1;
+            if attempts >= OAUTH_USERNAME_ATTEMPTS {
+                warn!("OAuth registration failed: no free username for {}", base);
+                return Err(
+                    crate::domain::entities::errors::DomainError::UserAlreadyExists {
+                        username: base,
+                    },
+                );
+            }
+            // Последние символы UUID v7 случайные
+            let suffix = Uuid::now_v7().simple().to_string();
+            username = format!("{}-{}", base, &suffix[suffix.len() - 6..]);
+        }
+
+        let password_hash = self
             .auth_service
-            .generate_refresh_token(&user.id.to_string(), &user.username);
+            .hash_password(&self.auth_service.generate_reset_token())
+            .map_err(
+                |e| crate::domain::entities::errors::DomainError::InvalidPassword {
+                    reason: e.to_string(),
+                },
+            )?;
+        let user = User::new(
+            Uuid::now_v7(),
+            username,
+            email,
+            password_hash,
+            Role::User,
+            chrono::Utc::now(),
+        );
+
+        let created_user = unit.create_user(user).await?;
+        info!("User created via OAuth with id: {}", created_user.id);
+        Ok(created_user)
+    }
+
+    /// Завершает вход после проверки первого фактора (пароля или провайдера OAuth).
+    ///
+    /// С включённой 2FA вместо токенов выдаётся токен второго шага.
+    async fn complete_first_factor(
+        &self,
+        user: &User,
+        device: DeviceInfo,
+    ) -> DomainResult<LoginOutcome> {
+        self.check_not_disabled(user.id).await?;
+
+        // Счётчик неудачных попыток сбрасывается только после входа целиком: иначе
+        // повторный ввод пароля обнулял бы попытки подобрать код TOTP
+        if self
+            .user_repository
+            .get_user_totp(user.id)
+            .await?
+            .is_some_and(|totp| totp.enabled)
+        {
+            let challenge_token = self.auth_service.generate_login_challenge();
+            let fingerprint = self.auth_service.token_fingerprint(&challenge_token);
+            let expires_at =
+                chrono::Utc::now() + chrono::Duration::minutes(LOGIN_CHALLENGE_TTL_MINUTES);
+            self.user_repository
+                .create_login_challenge(user.id, &fingerprint, expires_at)
+                .await?;
+
+            info!("First factor accepted, waiting for two-factor code");
+            return Ok(LoginOutcome::TwoFactorRequired {
+                challenge_token,
+                expires_in: LOGIN_CHALLENGE_TTL_MINUTES * 60,
+            });
+        }
 
+        if self.lockout.max_failed_attempts > 0 {
+            self.user_repository.clear_failed_logins(user.id).await?;
+        }
         info!("User logged in successfully");
+        Ok(LoginOutcome::Authenticated(
+            self.issue_tokens(user, device).await?,
+        ))
+    }
+
+    /// Второй шаг входа: обменивает токен, выданный после проверки пароля, и код TOTP на токены.
+    ///
+    /// Неверный код учитывается как неудачная попытка входа (см. [`LoginLockout`]).
+    #[instrument(skip(self, dto))]
+    pub async fn login_with_totp(&self, dto: TotpLoginDto) -> DomainResult<TokenDto> {
+        debug!("Attempting second login step");
+
+        let fingerprint = self.auth_service.token_fingerprint(&dto.challenge_token);
+        let user = match self
+            .user_repository
+            .find_login_challenge(&fingerprint)
+            .await?
+        {
+            Some(user_id) => self.user_repository.find_by_id(user_id).await?,
+            None => None,
+        };
+        let user = user.ok_or_else(|| {
+            warn!("Two-factor login failed: invalid or expired challenge");
+            crate::domain::entities::errors::DomainError::TokenValidationError(
+                "Invalid or expired login challenge".to_string(),
+            )
+        })?;
+
+        self.check_login_lock(user.id).await?;
+        self.check_not_disabled(user.id).await?;
+
+        let totp = self
+            .user_repository
+            .get_user_totp(user.id)
+            .await?
+            .filter(|totp| totp.enabled)
+            .ok_or(crate::domain::entities::errors::DomainError::TwoFactorNotEnrolled)?;
+        if !totp::verify_code(&totp.secret, &dto.code, chrono::Utc::now()) {
+            warn!("Two-factor login failed: invalid code");
+            return Err(self
+                .reject_login(
+                    user.id,
+                    crate::domain::entities::errors::DomainError::InvalidTotpCode,
+                )
+                .await);
+        }
+
+        self.user_repository
+            .delete_login_challenge(&fingerprint)
+            .await?;
+        if self.lockout.max_failed_attempts > 0 {
+            self.user_repository.clear_failed_logins(user.id).await?;
+        }
+
+        info!("User logged in successfully with two-factor code");
+        self.issue_tokens(&user, dto.device).await
+    }
+
+    /// Подключает 2FA: создаёт новый секрет TOTP, который нужно подтвердить кодом.
+    ///
+    /// Повторный вызов до подтверждения заменяет секрет.
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn enroll_totp(&self, user_id: Uuid) -> DomainResult<TotpEnrollmentDto> {
+        debug!("Enrolling TOTP");
+
+        if self
+            .user_repository
+            .get_user_totp(user_id)
+            .await?
+            .is_some_and(|totp| totp.enabled)
+        {
+            warn!("TOTP enrollment failed: already enabled");
+            return Err(crate::domain::entities::errors::DomainError::TwoFactorAlreadyEnabled);
+        }
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(
+                || crate::domain::entities::errors::DomainError::UserNotFound {
+                    username: user_id.to_string(),
+                },
+            )?;
+
+        let secret = totp::generate_secret();
+        self.user_repository
+            .save_user_totp(user_id, &secret)
+            .await?;
+
+        info!("TOTP secret issued, waiting for confirmation");
+        Ok(TotpEnrollmentDto {
+            provisioning_uri: totp::provisioning_uri(&secret, &user.username, TOTP_ISSUER),
+            secret,
+        })
+    }
+
+    /// Подтверждает подключение 2FA первым кодом из приложения-аутентификатора.
+    #[instrument(skip(self, code), fields(user_id = %user_id))]
+    pub async fn verify_totp(&self, user_id: Uuid, code: &str) -> DomainResult<()> {
+        debug!("Verifying TOTP enrollment");
+
+        let totp = self
+            .user_repository
+            .get_user_totp(user_id)
+            .await?
+            .ok_or(crate::domain::entities::errors::DomainError::TwoFactorNotEnrolled)?;
+        if totp.enabled {
+            return Err(crate::domain::entities::errors::DomainError::TwoFactorAlreadyEnabled);
+        }
+        if !totp::verify_code(&totp.secret, code, chrono::Utc::now()) {
+            warn!("TOTP enrollment failed: invalid code");
+            return Err(crate::domain::entities::errors::DomainError::InvalidTotpCode);
+        }
+
+        self.user_repository.enable_user_totp(user_id).await?;
+        info!("Two-factor authentication enabled");
+        Ok(())
+    }
+
+    /// Возвращает ошибку, если вход пользователя временно заблокирован
+    async fn check_login_lock(&self, user_id: Uuid) -> DomainResult<()> {
+        if self.lockout.max_failed_attempts > 0
+            && let Some(locked_until) = self.user_repository.get_login_lock(user_id).await?
+            && locked_until > chrono::Utc::now()
+        {
+            warn!("Login failed: account is locked until {}", locked_until);
+            return Err(
+                crate::domain::entities::errors::DomainError::AccountLocked { locked_until },
+            );
+        }
+        Ok(())
+    }
+
+    /// Возвращает ошибку, если пользователь отключён администратором
+    async fn check_not_disabled(&self, user_id: Uuid) -> DomainResult<()> {
+        if self.user_repository.is_user_disabled(user_id).await? {
+            warn!("Login failed: account is disabled");
+            return Err(crate::domain::entities::errors::DomainError::AccountDisabled);
+        }
+        Ok(())
+    }
+
+    /// Учитывает неудачную попытку входа и возвращает ошибку для ответа.
+    ///
+    /// Если попытка исчерпала лимит, вместо `error` возвращается `AccountLocked`.
+    async fn reject_login(
+        &self,
+        user_id: Uuid,
+        error: crate::domain::entities::errors::DomainError,
+    ) -> crate::domain::entities::errors::DomainError {
+        if self.lockout.max_failed_attempts == 0 {
+            return error;
+        }
+
+        let locked_until = chrono::Utc::now() + self.lockout.lock_duration;
+        match self
+            .user_repository
+            .record_failed_login(user_id, self.lockout.max_failed_attempts, locked_until)
+            .await
+        {
+            Ok(true) => {
+                warn!(
+                    "Too many failed logins, account locked until {}",
+                    locked_until
+                );
+                crate::domain::entities::errors::DomainError::AccountLocked { locked_until }
+            }
+            Ok(false) => error,
+            Err(e) => e,
+        }
+    }
+
+    /// Открывает сеанс входа и выпускает для него пару токенов с текущей версией
+    /// токенов пользователя
+    async fn issue_tokens(&self, user: &User, device: DeviceInfo) -> DomainResult<TokenDto> {
+        let token_version = self
+            .user_repository
+            .get_token_version(user.id)
+            .await?
+            .unwrap_or_default();
+        let session_id = self.create_session(user.id, device).await?;
+
+        let access_token = self.auth_service.generate_token(
+            &user.id.to_string(),
+            &user.username,
+            user.role,
+            token_version,
+            Some(session_id),
+        );
+
+        let refresh_token = self.auth_service.generate_refresh_token(
+            &user.id.to_string(),
+            &user.username,
+            user.role,
+            token_version,
+            Some(session_id),
+        );
 
         Ok(TokenDto {
+            user_id: user.id,
             access_token,
             refresh_token,
             expires_in: 86400,
         })
     }
 
-    #[instrument(skip(self, refresh_token))]
-    pub async fn refresh_token(&self, refresh_token: String) -> DomainResult<TokenDto> {
+    /// Сохраняет новый сеанс входа пользователя и возвращает его ID
+    async fn create_session(&self, user_id: Uuid, device: DeviceInfo) -> DomainResult<Uuid> {
+        let now = chrono::Utc::now();
+        let session = Session {
+            id: Uuid::now_v7(),
+            user_id,
+            user_agent: device.user_agent,
+            ip_address: device.ip_address,
+            created_at: now,
+            last_used_at: now,
+            expires_at: now + REFRESH_TOKEN_LIFETIME,
+        };
+        self.user_repository.create_session(&session).await?;
+        Ok(session.id)
+    }
+
+    /// Проверяет access токен: подпись, срок действия, то, что после его выдачи
+    /// пользователь не выходил со всех устройств, и то, что его сеанс не закрыт.
+    ///
+    /// Refresh токены и токены без claim `typ` (выданные до его появления) не
+    /// принимаются: клиент получает новую пару токенов через refresh токен.
+    pub async fn authenticate(&self, token: &str) -> DomainResult<Claims> {
+        let claims = self.auth_service.verify_token(token).ok_or_else(|| {
+            warn!("Token validation failed");
+            crate::domain::entities::errors::DomainError::TokenValidationError(
+                "Invalid or expired token".to_string(),
+            )
+        })?;
+        if claims.typ != Some(TokenType::Access) {
+            warn!("Token rejected: not an access token");
+            return Err(
+                crate::domain::entities::errors::DomainError::TokenValidationError(
+                    "Not an access token".to_string(),
+                ),
+            );
+        }
+        self.check_token_version(&claims).await?;
+        self.check_session(&claims).await?;
+        Ok(claims)
+    }
+
+    /// Отклоняет токен закрытого сеанса входа; токены без сеанса проверяются только
+    /// по версии токенов.
+    async fn check_session(&self, claims: &Claims) -> DomainResult<()> {
+        let (Some(session_id), Ok(user_id)) = (claims.sid, Uuid::parse_str(&claims.sub)) else {
+            return Ok(());
+        };
+        if !self
+            .user_repository
+            .is_session_active(session_id, user_id)
+            .await?
+        {
+            warn!("Token rejected: session has been revoked");
+            return Err(
+                crate::domain::entities::errors::DomainError::TokenValidationError(
+                    "Session has been revoked".to_string(),
+                ),
+            );
+        }
+        Ok(())
+    }
+
+    /// Проверяет refresh токен: подпись и срок действия, а также то, что это не access токен
+    fn verify_refresh_token(&self, refresh_token: &str) -> Option<Claims> {
+        self.auth_service
+            .verify_token(refresh_token)
+            .filter(|claims| claims.typ != Some(TokenType::Access))
+    }
+
+    /// Отклоняет токен, выданный до последнего выхода со всех устройств
+    /// (или пользователю, которого больше нет).
+    async fn check_token_version(&self, claims: &Claims) -> DomainResult<()> {
+        let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+            warn!("Invalid UUID in token claims: {}", claims.sub);
+            crate::domain::entities::errors::DomainError::TokenValidationError(
+                "Invalid token format".to_string(),
+            )
+        })?;
+        let current = self.user_repository.get_token_version(user_id).await?;
+        if current != Some(claims.ver) {
+            warn!("Token rejected: issued before the last logout from all devices");
+            return Err(
+                crate::domain::entities::errors::DomainError::TokenValidationError(
+                    "Token has been revoked".to_string(),
+                ),
+            );
+        }
+        Ok(())
+    }
+
+    /// Выход со всех устройств: все выданные пользователю access и refresh токены
+    /// перестают действовать.
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn logout_all(&self, user_id: Uuid) -> DomainResult<()> {
+        debug!("Revoking all tokens of user");
+
+        self.user_repository
+            .increment_token_version(user_id)
+            .await?;
+        self.user_repository.delete_user_sessions(user_id).await?;
+
+        info!("All tokens revoked");
+        Ok(())
+    }
+
+    /// Обменивает refresh токен на новую пару токенов того же сеанса входа.
+    ///
+    /// Токену отозванного сеанса отказывается; для токена, выданного до появления
+    /// сеансов, открывается новый сеанс.
+    #[instrument(skip(self, refresh_token, device))]
+    pub async fn refresh_token(
+        &self,
+        refresh_token: String,
+        device: DeviceInfo,
+    ) -> DomainResult<TokenDto> {
         debug!("Attempting to refresh token");
 
-        let claims = self
-            .auth_service
-            .verify_token(&refresh_token)
+        let claims = self.verify_refresh_token(&refresh_token).ok_or_else(|| {
+            warn!("Token refresh failed: invalid refresh token");
+            crate::domain::entities::errors::DomainError::TokenValidationError(
+                "Invalid refresh token".to_string(),
+            )
+        })?;
+
+        let fingerprint = self.auth_service.token_fingerprint(&refresh_token);
+        if self.user_repository.is_token_revoked(&fingerprint).await? {
+            warn!("Token refresh failed: refresh token has been revoked");
+            return Err(
+                crate::domain::entities::errors::DomainError::TokenValidationError(
+                    "Refresh token has been revoked".to_string(),
+                ),
+            );
+        }
+        self.check_token_version(&claims).await?;
+
+        // Пользователя ищем по id из `sub`: роль и имя берём из базы, чтобы их
+        // изменение вступало в силу при обновлении токена
+        let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+            warn!("Invalid UUID in token claims: {}", claims.sub);
+            crate::domain::entities::errors::DomainError::TokenValidationError(
+                "Invalid token format".to_string(),
+            )
+        })?;
+        let user = self
+            .user_repository
+            .find_by_id(user_id)
+            .await?
             .ok_or_else(|| {
-                warn!("Token refresh failed: invalid refresh token");
+                warn!("Token refresh failed: user no longer exists");
                 crate::domain::entities::errors::DomainError::TokenValidationError(
-                    "Invalid refresh token".to_string(),
+                    "User not found".to_string(),
                 )
             })?;
 
-        let access_token = self
-            .auth_service
-            .generate_token(&claims.sub, &claims.user_name);
+        let session_id = match claims.sid {
+            Some(session_id) => {
+                let expires_at = chrono::Utc::now() + REFRESH_TOKEN_LIFETIME;
+                if !self
+                    .user_repository
+                    .touch_session(session_id, user.id, &device, expires_at)
+                    .await?
+                {
+                    warn!("Token refresh failed: session has been revoked");
+                    return Err(
+                        crate::domain::entities::errors::DomainError::TokenValidationError(
+                            "Session has been revoked".to_string(),
+                        ),
+                    );
+                }
+                session_id
+            }
+            None => self.create_session(user.id, device).await?,
+        };
 
-        // Генерируем новый refresh token
-        let new_refresh_token = self
-            .auth_service
-            .generate_refresh_token(&claims.sub, &claims.user_name);
+        let access_token = self.auth_service.generate_token(
+            &claims.sub,
+            &user.username,
+            user.role,
+            claims.ver,
+            Some(session_id),
+        );
 
-        info!(
-            "Token refreshed successfully for user: {}",
-            claims.user_name
+        // Генерируем новый refresh token
+        let new_refresh_token = self.auth_service.generate_refresh_token(
+            &claims.sub,
+            &user.username,
+            user.role,
+            claims.ver,
+            Some(session_id),
         );
 
+        info!("Token refreshed successfully for user: {}", user.username);
+
         Ok(TokenDto {
+            user_id: user.id,
             access_token,
             refresh_token: new_refresh_token,
             expires_in: 86400,
         })
     }
+
+    /// Отзывает refresh токен и закрывает его сеанс: после выхода токен нельзя
+    /// использовать для обновления, а access токены сеанса больше не принимаются.
+    ///
+    /// Access токены, выданные без сеанса, продолжают действовать до истечения срока.
+    #[instrument(skip(self, refresh_token))]
+    pub async fn logout(&self, refresh_token: String) -> DomainResult<()> {
+        debug!("Attempting to revoke refresh token");
+
+        let claims = self.verify_refresh_token(&refresh_token).ok_or_else(|| {
+            warn!("Logout failed: invalid refresh token");
+            crate::domain::entities::errors::DomainError::TokenValidationError(
+                "Invalid refresh token".to_string(),
+            )
+        })?;
+
+        let expires_at =
+            chrono::DateTime::from_timestamp(claims.exp as i64, 0).unwrap_or_else(chrono::Utc::now);
+        let fingerprint = self.auth_service.token_fingerprint(&refresh_token);
+        self.user_repository
+            .revoke_token(&fingerprint, expires_at)
+            .await?;
+        if let (Some(session_id), Ok(user_id)) = (claims.sid, Uuid::parse_str(&claims.sub)) {
+            self.user_repository
+                .delete_session(session_id, user_id)
+                .await?;
+        }
+
+        info!("Refresh token revoked for user: {}", claims.user_name);
+        Ok(())
+    }
+
+    /// Возвращает действующие сеансы входа пользователя.
+    ///
+    /// `current` - сеанс токена, с которым выполнен запрос; он помечается в ответе.
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn list_sessions(
+        &self,
+        user_id: Uuid,
+        current: Option<Uuid>,
+    ) -> DomainResult<Vec<SessionDto>> {
+        debug!("Listing sessions");
+
+        let sessions = self.user_repository.list_sessions(user_id).await?;
+        Ok(sessions
+            .into_iter()
+            .map(|session| SessionDto {
+                current: current == Some(session.id),
+                id: session.id,
+                user_agent: session.user_agent,
+                ip_address: session.ip_address,
+                created_at: session.created_at,
+                last_used_at: session.last_used_at,
+            })
+            .collect())
+    }
+
+    /// Закрывает сеанс входа пользователя: его refresh токен больше не обновляется,
+    /// а access токены сеанса сразу перестают приниматься.
+    #[instrument(skip(self), fields(user_id = %user_id, session_id = %session_id))]
+    pub async fn revoke_session(&self, user_id: Uuid, session_id: Uuid) -> DomainResult<()> {
+        debug!("Revoking session");
+
+        if !self
+            .user_repository
+            .delete_session(session_id, user_id)
+            .await?
+        {
+            warn!("Session not found");
+            return Err(
+                crate::domain::entities::errors::DomainError::SessionNotFound { session_id },
+            );
+        }
+
+        info!("Session revoked");
+        Ok(())
+    }
+
+    /// Выпускает токен сброса пароля для пользователя с указанным email.
+    ///
+    /// Токен отправляется письмом на email пользователя; в лог попадает только его
+    /// отпечаток. Если пользователь не найден или письмо не отправилось, метод всё
+    /// равно завершается успешно, чтобы по ответу нельзя было узнать, зарегистрирован
+    /// ли email.
+    #[instrument(skip(self, email))]
+    pub async fn request_password_reset(&self, email: &str) -> DomainResult<()> {
+        debug!("Password reset requested");
+
+        let Some(user) = self.user_repository.find_by_email(email).await? else {
+            warn!("Password reset requested for unknown email");
+            return Ok(());
+        };
+        let Some(email_sender) = &self.email_sender else {
+            warn!("Password reset requested, but email sending is not configured");
+            return Ok(());
+        };
+
+        let token = self.auth_service.generate_reset_token();
+        let fingerprint = self.auth_service.token_fingerprint(&token);
+        let expires_at = chrono::Utc::now() + chrono::Duration::minutes(RESET_TOKEN_TTL_MINUTES);
+        self.user_repository
+            .create_password_reset_token(user.id, &fingerprint, expires_at)
+            .await?;
+
+        let message = EmailMessage {
+            to: user.email.clone(),
+            subject: format!("Reset your {} password", self.site_name),
+            body: format!(
+                "Hi {},\n\nSomeone requested a password reset for your account on {}.\n\n\
+                 Reset token: {}\n\n\
+                 The token is valid for {} minutes and can be used once. \
+                 If you did not request the reset, ignore this email.\n",
+                user.username, self.site_name, token, RESET_TOKEN_TTL_MINUTES
+            ),
+            unsubscribe_url: None,
+            confidential: true,
+        };
+        if let Err(e) = email_sender.send(message).await {
+            error!(
+                "Failed to send password reset email to user {}: {}",
+                user.id, e
+            );
+            return Ok(());
+        }
+
+        info!(
+            "Password reset token issued for user {} (fingerprint {})",
+            user.id,
+            &fingerprint[..12]
+        );
+        Ok(())
+    }
+
+    /// Устанавливает новый пароль по токену сброса; токен одноразовый.
+    ///
+    /// Как и выход со всех устройств, сброс отзывает все выданные пользователю токены
+    /// и закрывает его сеансы: сброс - основной способ вернуть взломанный аккаунт.
+    #[instrument(skip(self, dto))]
+    pub async fn reset_password(&self, dto: ResetPasswordDto) -> DomainResult<()> {
+        debug!("Attempting to reset password");
+
+        let password_hash = self
+            .auth_service
+            .hash_password(&dto.new_password)
+            .map_err(|e| {
+                warn!("Password hashing failed: {}", e);
+                crate::domain::entities::errors::DomainError::InvalidPassword {
+                    reason: e.to_string(),
+                }
+            })?;
+
+        let fingerprint = self.auth_service.token_fingerprint(&dto.token);
+        let user_id = self
+            .user_repository
+            .consume_password_reset_token(&fingerprint)
+            .await?
+            .ok_or_else(|| {
+                warn!("Password reset failed: invalid or expired token");
+                crate::domain::entities::errors::DomainError::InvalidResetToken
+            })?;
+
+        self.user_repository
+            .update_password(user_id, &password_hash)
+            .await?;
+        self.user_repository
+            .increment_token_version(user_id)
+            .await?;
+        self.user_repository.delete_user_sessions(user_id).await?;
+
+        info!("Password reset successfully for user: {}", user_id);
+        Ok(())
+    }
+}
+
+/// Приводит подсказку провайдера к допустимому имени пользователя.
+///
+/// Остаются латинские буквы, цифры, `_`, `-` и `.`; имя укорачивается так, чтобы
+/// поместился суффикс для занятых имён, а слишком короткое заменяется на `user`.
+fn oauth_username_base(hint: &str) -> String {
+    let base: String = hint
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        .take(USERNAME_MAX_LEN - 7)
+        .collect();
+    if base.len() < USERNAME_MIN_LEN {
+        "user".to_string()
+    } else {
+        base
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::dto::auth::TokenDto;
+    use crate::data::memrepo::InMemoryRepository;
+    use crate::domain::entities::errors::DomainError;
+    use std::sync::Mutex;
+
+    const PASSWORD: &str = "correct-horse-battery";
+    const EMAIL: &str = "alice@example.com";
+
+    /// Сохраняет письма вместо отправки
+    #[derive(Default)]
+    struct CapturingEmailSender {
+        sent: Mutex<Vec<EmailMessage>>,
+    }
+
+    #[async_trait::async_trait]
+    impl EmailSender for CapturingEmailSender {
+        async fn send(&self, message: EmailMessage) -> DomainResult<()> {
+            self.sent.lock().unwrap().push(message);
+            Ok(())
+        }
+    }
+
+    fn app(max_failed_attempts: u32) -> AuthApplication<InMemoryRepository> {
+        AuthApplication::new(
+            Arc::new(InMemoryRepository::new()),
+            Arc::new(AuthService::new(
+                chrono::Duration::minutes(5),
+                b"test-secret-test-secret-test-secret-test-secret-test-secret-1234",
+            )),
+            LoginLockout {
+                max_failed_attempts,
+                lock_duration: chrono::Duration::minutes(15),
+            },
+            OAuthProviders::new(),
+        )
+    }
+
+    async fn register(app: &AuthApplication<InMemoryRepository>) -> User {
+        app.create_user(RegisterDto {
+            username: "alice".to_string(),
+            password: PASSWORD.to_string(),
+            email: EMAIL.to_string(),
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn login_outcome(
+        app: &AuthApplication<InMemoryRepository>,
+        password: &str,
+    ) -> DomainResult<LoginOutcome> {
+        app.login(LoginDto {
+            identifier: "alice".to_string(),
+            kind: IdentifierKind::Username,
+            password: password.to_string(),
+            device: DeviceInfo::new(Some("test"), None),
+        })
+        .await
+    }
+
+    async fn login(
+        app: &AuthApplication<InMemoryRepository>,
+        password: &str,
+    ) -> DomainResult<TokenDto> {
+        match login_outcome(app, password).await? {
+            LoginOutcome::Authenticated(tokens) => Ok(tokens),
+            LoginOutcome::TwoFactorRequired { .. } => panic!("2FA is not enabled"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_issues_tokens_for_valid_password() {
+        let app = app(0);
+        let user = register(&app).await;
+
+        let tokens = login(&app, PASSWORD).await.unwrap();
+        assert_eq!(tokens.user_id, user.id);
+        let claims = app.authenticate(&tokens.access_token).await.unwrap();
+        assert_eq!(claims.sub, user.id.to_string());
+
+        assert!(matches!(
+            login(&app, "wrong-password").await,
+            Err(DomainError::InvalidCredentials)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_login_locks_account_after_failed_attempts() {
+        let app = app(2);
+        register(&app).await;
+
+        assert!(matches!(
+            login(&app, "wrong-password").await,
+            Err(DomainError::InvalidCredentials)
+        ));
+        assert!(matches!(
+            login(&app, "wrong-password").await,
+            Err(DomainError::AccountLocked { .. })
+        ));
+        // Пока блокировка действует, не помогает и верный пароль
+        assert!(matches!(
+            login(&app, PASSWORD).await,
+            Err(DomainError::AccountLocked { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_password_does_not_reset_failed_totp_attempts() {
+        let app = app(2);
+        let user = register(&app).await;
+        app.enroll_totp(user.id).await.unwrap();
+        app.user_repository.enable_user_totp(user.id).await.unwrap();
+
+        // Клиент вводит верный пароль заново перед каждой попыткой кода
+        let totp_attempt = async || {
+            let challenge_token = match login_outcome(&app, PASSWORD).await? {
+                LoginOutcome::TwoFactorRequired {
+                    challenge_token, ..
+                } => challenge_token,
+                LoginOutcome::Authenticated(_) => panic!("2FA is enabled"),
+            };
+            app.login_with_totp(TotpLoginDto {
+                challenge_token,
+                code: "000000".to_string(),
+                device: DeviceInfo::new(None, None),
+            })
+            .await
+        };
+
+        assert!(matches!(
+            totp_attempt().await,
+            Err(DomainError::InvalidTotpCode)
+        ));
+        assert!(matches!(
+            totp_attempt().await,
+            Err(DomainError::AccountLocked { .. })
+        ));
+        assert!(matches!(
+            login_outcome(&app, PASSWORD).await,
+            Err(DomainError::AccountLocked { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_is_rejected_after_logout() {
+        let app = app(0);
+        register(&app).await;
+        let tokens = login(&app, PASSWORD).await.unwrap();
+
+        let refreshed = app
+            .refresh_token(tokens.refresh_token, DeviceInfo::new(Some("test"), None))
+            .await
+            .unwrap();
+        app.authenticate(&refreshed.access_token).await.unwrap();
+
+        app.logout(refreshed.refresh_token.clone()).await.unwrap();
+        assert!(matches!(
+            app.refresh_token(refreshed.refresh_token, DeviceInfo::new(None, None))
+                .await,
+            Err(DomainError::TokenValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_revoked_session_rejects_its_access_token() {
+        let app = app(0);
+        let user = register(&app).await;
+        let tokens = login(&app, PASSWORD).await.unwrap();
+        let other = login(&app, PASSWORD).await.unwrap();
+
+        let claims = app.authenticate(&tokens.access_token).await.unwrap();
+        app.revoke_session(user.id, claims.sid.unwrap())
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            app.authenticate(&tokens.access_token).await,
+            Err(DomainError::TokenValidationError(_))
+        ));
+        // Другие сеансы пользователя не затронуты
+        app.authenticate(&other.access_token).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_token_types_are_not_interchangeable() {
+        let app = app(0);
+        register(&app).await;
+        let tokens = login(&app, PASSWORD).await.unwrap();
+
+        assert!(matches!(
+            app.authenticate(&tokens.refresh_token).await,
+            Err(DomainError::TokenValidationError(_))
+        ));
+        assert!(matches!(
+            app.refresh_token(tokens.access_token.clone(), DeviceInfo::new(None, None))
+                .await,
+            Err(DomainError::TokenValidationError(_))
+        ));
+        assert!(matches!(
+            app.logout(tokens.access_token).await,
+            Err(DomainError::TokenValidationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_token_is_emailed_and_single_use() {
+        let sender = Arc::new(CapturingEmailSender::default());
+        let app = app(0).with_email(sender.clone(), "Blog".to_string());
+        register(&app).await;
+
+        app.request_password_reset(EMAIL).await.unwrap();
+        let message = sender.sent.lock().unwrap().pop().unwrap();
+        assert_eq!(message.to, EMAIL);
+        assert!(message.confidential);
+        let token = message
+            .body
+            .lines()
+            .find_map(|line| line.strip_prefix("Reset token: "))
+            .unwrap()
+            .to_string();
+
+        let reset = |token: String| {
+            app.reset_password(ResetPasswordDto {
+                token,
+                new_password: "new-horse-battery".to_string(),
+            })
+        };
+        reset(token.clone()).await.unwrap();
+        assert!(matches!(
+            reset(token).await,
+            Err(DomainError::InvalidResetToken)
+        ));
+
+        assert!(login(&app, "new-horse-battery").await.is_ok());
+        assert!(matches!(
+            login(&app, PASSWORD).await,
+            Err(DomainError::InvalidCredentials)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_revokes_existing_tokens() {
+        let sender = Arc::new(CapturingEmailSender::default());
+        let app = app(0).with_email(sender.clone(), "Blog".to_string());
+        register(&app).await;
+        let tokens = login(&app, PASSWORD).await.unwrap();
+
+        app.request_password_reset(EMAIL).await.unwrap();
+        let message = sender.sent.lock().unwrap().pop().unwrap();
+        let token = message
+            .body
+            .lines()
+            .find_map(|line| line.strip_prefix("Reset token: "))
+            .unwrap()
+            .to_string();
+        app.reset_password(ResetPasswordDto {
+            token,
+            new_password: "new-horse-battery".to_string(),
+        })
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            app.refresh_token(tokens.refresh_token, DeviceInfo::new(None, None))
+                .await,
+            Err(DomainError::TokenValidationError(_))
+        ));
+        assert!(matches!(
+            app.authenticate(&tokens.access_token).await,
+            Err(DomainError::TokenValidationError(_))
+        ));
+        // Вход с новым паролем выдаёт действующие токены
+        let tokens = login(&app, "new-horse-battery").await.unwrap();
+        app.authenticate(&tokens.access_token).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_for_unknown_email_sends_nothing() {
+        let sender = Arc::new(CapturingEmailSender::default());
+        let app = app(0).with_email(sender.clone(), "Blog".to_string());
+        register(&app).await;
+
+        app.request_password_reset("bob@example.com").await.unwrap();
+        assert!(sender.sent.lock().unwrap().is_empty());
+    }
 }
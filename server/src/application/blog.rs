@@ -0,0 +1,173 @@
+use crate::application::dto::blog::{BlogDto, CreateBlogDto};
+use crate::domain::entities::blog::{Blog, normalize_blog_name, normalize_blog_slug};
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::user::Role;
+use crate::domain::repositories::blog::BlogRepository;
+use std::sync::Arc;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+/// Блоги сервера. Список и блоги доступны всем, создать блог может любой
+/// пользователь, менять и удалять - владелец или администратор. Посты блога возвращает
+/// [`PostApplication::get_posts`](crate::application::post::PostApplication::get_posts).
+pub struct BlogApplication<Repo: BlogRepository + ?Sized> {
+    repository: Arc<Repo>,
+}
+
+impl<Repo: BlogRepository + ?Sized> BlogApplication<Repo> {
+    pub fn new(repository: Arc<Repo>) -> Self {
+        Self { repository }
+    }
+
+    /// Возвращает все блоги, упорядоченные по адресу.
+    #[instrument(skip(self))]
+    pub async fn list_blogs(&self) -> DomainResult<Vec<BlogDto>> {
+        debug!("Fetching blogs");
+        let blogs = self.repository.list_blogs().await?;
+        info!("Retrieved {} blogs", blogs.len());
+        Ok(blogs.into_iter().map(BlogDto::from_entity).collect())
+    }
+
+    /// Возвращает блог по адресу; адрес сравнивается без учёта регистра.
+    #[instrument(skip(self))]
+    pub async fn get_blog(&self, slug: &str) -> DomainResult<BlogDto> {
+        debug!("Fetching blog");
+        let blog = self.find_blog(slug).await?;
+        Ok(BlogDto::from_entity(blog))
+    }
+
+    /// Создаёт блог с владельцем `dto.owner_id`; адрес должен быть свободен.
+    #[instrument(skip(self, dto), fields(slug = %dto.slug, owner_id = %dto.owner_id))]
+    pub async fn create_blog(&self, dto: CreateBlogDto) -> DomainResult<BlogDto> {
+        debug!("Creating blog");
+        let slug = normalize_blog_slug(&dto.slug)?;
+        let name = normalize_blog_name(&dto.name)?;
+        if self.repository.get_blog_by_slug(&slug).await?.is_some() {
+            warn!("Blog already exists");
+            return Err(DomainError::BlogAlreadyExists { slug });
+        }
+
+        let blog = Blog {
+            id: Uuid::now_v7(),
+            slug,
+            name,
+            owner_id: dto.owner_id,
+            created_at: chrono::Utc::now(),
+        };
+        self.repository.create_blog(&blog).await?;
+        info!("Blog created with id: {}", blog.id);
+        Ok(BlogDto::from_entity(blog))
+    }
+
+    /// Меняет название блога; доступно владельцу и администратору.
+    #[instrument(skip(self, name), fields(user_id = %user_id))]
+    pub async fn update_blog(
+        &self,
+        slug: &str,
+        name: &str,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<BlogDto> {
+        debug!("Updating blog");
+        let mut blog = self.find_blog(slug).await?;
+        check_owner(&blog, user_id, role)?;
+        blog.name = normalize_blog_name(name)?;
+        self.repository
+            .update_blog_name(blog.id, &blog.name)
+            .await?;
+        info!("Blog updated successfully");
+        Ok(BlogDto::from_entity(blog))
+    }
+
+    /// Удаляет блог; доступно владельцу и администратору. Посты блога не удаляются,
+    /// а переходят в общую ленту сервера.
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn delete_blog(&self, slug: &str, user_id: Uuid, role: Role) -> DomainResult<()> {
+        debug!("Deleting blog");
+        let blog = self.find_blog(slug).await?;
+        check_owner(&blog, user_id, role)?;
+        self.repository.delete_blog(blog.id).await?;
+        info!("Blog deleted successfully");
+        Ok(())
+    }
+
+    async fn find_blog(&self, slug: &str) -> DomainResult<Blog> {
+        let slug = slug.trim().to_lowercase();
+        self.repository
+            .get_blog_by_slug(&slug)
+            .await?
+            .ok_or_else(|| {
+                warn!("Blog {} not found", slug);
+                DomainError::BlogNotFound { slug }
+            })
+    }
+}
+
+/// Проверяет, что менять блог может пользователь `user_id`
+fn check_owner(blog: &Blog, user_id: Uuid, role: Role) -> DomainResult<()> {
+    if blog.owner_id != user_id && !role.can_administer() {
+        warn!("User {} is not the owner of blog {}", user_id, blog.slug);
+        return Err(DomainError::Forbidden {
+            reason: "only the blog owner or an administrator can change the blog".to_string(),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::memrepo::InMemoryRepository;
+    use crate::domain::entities::user::User;
+    use crate::domain::repositories::repo::UserRepository;
+
+    async fn user(repo: &InMemoryRepository, username: &str) -> Uuid {
+        let user = User::new(
+            Uuid::now_v7(),
+            username.to_string(),
+            format!("{username}@example.com"),
+            "hash".to_string(),
+            Role::User,
+            chrono::Utc::now(),
+        );
+        repo.create_user(user).await.unwrap().id
+    }
+
+    #[tokio::test]
+    async fn test_only_owner_or_admin_changes_blog() {
+        let repo = Arc::new(InMemoryRepository::new());
+        let app = BlogApplication::new(repo.clone());
+        let owner = user(&repo, "alice").await;
+        let stranger = user(&repo, "bob").await;
+        app.create_blog(CreateBlogDto {
+            slug: "alpha".to_string(),
+            name: "Alpha".to_string(),
+            owner_id: owner,
+        })
+        .await
+        .unwrap();
+
+        for role in [Role::User, Role::Moderator] {
+            assert!(matches!(
+                app.update_blog("alpha", "Stolen", stranger, role).await,
+                Err(DomainError::Forbidden { .. })
+            ));
+            assert!(matches!(
+                app.delete_blog("alpha", stranger, role).await,
+                Err(DomainError::Forbidden { .. })
+            ));
+        }
+        assert_eq!(app.get_blog("alpha").await.unwrap().name, "Alpha");
+
+        let renamed = app
+            .update_blog("alpha", "Renamed", stranger, Role::Admin)
+            .await
+            .unwrap();
+        assert_eq!(renamed.name, "Renamed");
+        app.delete_blog("ALPHA", owner, Role::User).await.unwrap();
+        assert!(matches!(
+            app.get_blog("alpha").await,
+            Err(DomainError::BlogNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::application::dto::category::{CategoryDto, CreateCategoryDto};
+use crate::domain::entities::category::{Category, normalize_category_name};
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::user::Role;
+use crate::domain::repositories::category::CategoryRepository;
+use std::sync::Arc;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+/// Категории постов. Список доступен всем, создавать категории может только
+/// администратор. Посты категории возвращает
+/// [`PostApplication::get_posts`](crate::application::post::PostApplication::get_posts).
+pub struct CategoryApplication<Repo: CategoryRepository + ?Sized> {
+    repository: Arc<Repo>,
+}
+
+impl<Repo: CategoryRepository + ?Sized> CategoryApplication<Repo> {
+    pub fn new(repository: Arc<Repo>) -> Self {
+        Self { repository }
+    }
+
+    /// Возвращает все категории, упорядоченные по имени; дерево собирается по `parent_id`.
+    #[instrument(skip(self))]
+    pub async fn list_categories(&self) -> DomainResult<Vec<CategoryDto>> {
+        debug!("Fetching categories");
+        let categories = self.repository.list_categories().await?;
+        info!("Retrieved {} categories", categories.len());
+        Ok(categories
+            .into_iter()
+            .map(CategoryDto::from_entity)
+            .collect())
+    }
+
+    /// Создаёт категорию; с `parent_id` - подкатегорию существующей категории.
+    ///
+    /// Имена категорий с общим родителем не должны совпадать без учёта регистра.
+    #[instrument(skip(self, dto), fields(name = %dto.name))]
+    pub async fn create_category(
+        &self,
+        dto: CreateCategoryDto,
+        role: Role,
+    ) -> DomainResult<CategoryDto> {
+        debug!("Creating category");
+        if !role.can_administer() {
+            warn!("Create category denied");
+            return Err(DomainError::Forbidden {
+                reason: "only administrators can create categories".to_string(),
+            });
+        }
+        let name = normalize_category_name(&dto.name)?;
+
+        let categories = self.repository.list_categories().await?;
+        if let Some(parent_id) = dto.parent_id
+            && !categories.iter().any(|category| category.id == parent_id)
+        {
+            warn!("Parent category {} not found", parent_id);
+            return Err(DomainError::CategoryNotFound {
+                category_id: parent_id,
+            });
+        }
+        let duplicate = categories.iter().any(|category| {
+            category.parent_id == dto.parent_id
+                && category.name.to_lowercase() == name.to_lowercase()
+        });
+        if duplicate {
+            warn!("Category already exists");
+            return Err(DomainError::CategoryAlreadyExists { name });
+        }
+
+        let category = Category {
+            id: Uuid::now_v7(),
+            name,
+            parent_id: dto.parent_id,
+            created_at: chrono::Utc::now(),
+        };
+        self.repository.create_category(&category).await?;
+        info!("Category created with id: {}", category.id);
+        Ok(CategoryDto::from_entity(category))
+    }
+}
@@ -0,0 +1,413 @@
+use std::fmt::Write;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tracing::{debug, error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::application::dto::digest::DigestSettingsDto;
+use crate::domain::entities::digest::{DigestFrequency, DigestSubscription};
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::post::Post;
+use crate::domain::repositories::digest::DigestRepository;
+use crate::domain::repositories::email::{EmailMessage, EmailSender};
+use crate::domain::repositories::post::PostRepository;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Подпись ссылки отписки отделена от других применений секрета этим префиксом
+const UNSUBSCRIBE_TOKEN_CONTEXT: &[u8] = b"digest-unsubscribe:";
+
+/// Метка, с которой ключ подписи ссылок отписки выводится из секрета другого назначения
+const UNSUBSCRIBE_KEY_LABEL: &[u8] = b"blog/digest-unsubscribe-key/v1";
+
+/// Выводит ключ подписи ссылок отписки из `secret` (например, `jwt_secret`).
+///
+/// Ключ - HMAC-SHA256 секрета над [`UNSUBSCRIBE_KEY_LABEL`], поэтому сам секрет
+/// напрямую ссылки отписки не подписывает.
+pub fn derive_unsubscribe_secret(secret: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(UNSUBSCRIBE_KEY_LABEL);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Содержимое писем дайджеста.
+#[derive(Debug, Clone)]
+pub struct DigestOptions {
+    /// Название сайта в теме и тексте письма
+    pub site_name: String,
+    /// Адрес веб-приложения, на который ведут ссылки на посты
+    pub app_url: String,
+    /// Публичный адрес HTTP сервера, на котором открывается ссылка отписки
+    pub public_url: String,
+    /// Наибольшее число постов в одном письме
+    pub max_posts: u32,
+}
+
+/// Email-дайджесты новых постов: настройки подписки и периодическая рассылка.
+///
+/// Дайджест включает посты, опубликованные после предыдущего письма подписчику.
+/// Ссылка отписки в письме подписана секретом сервера, поэтому работает без входа.
+pub struct DigestApplication<Repo: DigestRepository + PostRepository + ?Sized> {
+    repository: Arc<Repo>,
+    email_sender: Arc<dyn EmailSender>,
+    unsubscribe_secret: Vec<u8>,
+    options: DigestOptions,
+}
+
+impl<Repo: DigestRepository + PostRepository + ?Sized> DigestApplication<Repo> {
+    pub fn new(
+        repository: Arc<Repo>,
+        email_sender: Arc<dyn EmailSender>,
+        unsubscribe_secret: &[u8],
+        options: DigestOptions,
+    ) -> Self {
+        Self {
+            repository,
+            email_sender,
+            unsubscribe_secret: unsubscribe_secret.to_vec(),
+            options,
+        }
+    }
+
+    /// Возвращает настройки дайджеста пользователя.
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn get_settings(&self, user_id: Uuid) -> DomainResult<DigestSettingsDto> {
+        debug!("Fetching digest settings");
+        let subscription = self.repository.get_digest_subscription(user_id).await?;
+        Ok(DigestSettingsDto::from_entity(subscription))
+    }
+
+    /// Подписывает пользователя на дайджест с частотой `frequency`, меняет частоту
+    /// подписки или, при `None`, отписывает.
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn update_settings(
+        &self,
+        user_id: Uuid,
+        frequency: Option<DigestFrequency>,
+    ) -> DomainResult<DigestSettingsDto> {
+        debug!("Updating digest settings");
+        match frequency {
+            Some(frequency) => {
+                self.repository
+                    .save_digest_subscription(user_id, frequency, Utc::now())
+                    .await?;
+                info!("Digest frequency set to {}", frequency.as_str());
+            }
+            None => {
+                self.repository.delete_digest_subscription(user_id).await?;
+                info!("Unsubscribed from digest");
+            }
+        }
+        self.get_settings(user_id).await
+    }
+
+    /// Отписывает пользователя по ссылке из письма.
+    ///
+    /// Возвращает `InvalidUnsubscribeToken`, если подпись ссылки неверна. Повторная
+    /// отписка не считается ошибкой.
+    #[instrument(skip(self, token), fields(user_id = %user_id))]
+    pub async fn unsubscribe(&self, user_id: Uuid, token: &str) -> DomainResult<()> {
+        debug!("Unsubscribing from digest by link");
+        let valid = hex::decode(token).is_ok_and(|signature| {
+            self.unsubscribe_mac(user_id)
+                .verify_slice(&signature)
+                .is_ok()
+        });
+        if !valid {
+            warn!("Invalid unsubscribe token");
+            return Err(DomainError::InvalidUnsubscribeToken);
+        }
+
+        if self.repository.delete_digest_subscription(user_id).await? {
+            info!("Unsubscribed from digest by link");
+        }
+        Ok(())
+    }
+
+    /// Отправляет дайджесты всем подписчикам, которым они положены к моменту `now`,
+    /// и возвращает число отправленных писем.
+    ///
+    /// Если за период не опубликовано ни одного поста, письмо не отправляется, но
+    /// период считается закрытым. Ошибка отправки одному подписчику не останавливает
+    /// рассылку: его дайджест будет отправлен при следующей проверке.
+    #[instrument(skip(self))]
+    pub async fn send_due_digests(&self, now: DateTime<Utc>) -> DomainResult<u32> {
+        debug!("Sending due digests");
+        let mut sent = 0;
+        for frequency in DigestFrequency::ALL {
+            let subscriptions = self
+                .repository
+                .get_due_digest_subscriptions(frequency, now - frequency.period())
+                .await?;
+            for subscription in subscriptions {
+                match self.send_digest(&subscription, now).await {
+                    Ok(true) => sent += 1,
+                    Ok(false) => {}
+                    Err(e) => {
+                        error!(
+                            "Failed to send digest to user {}: {}",
+                            subscription.user_id, e
+                        );
+                        continue;
+                    }
+                }
+                self.repository
+                    .mark_digest_sent(subscription.user_id, now)
+                    .await?;
+            }
+        }
+        if sent > 0 {
+            info!("Sent {} digests", sent);
+        }
+        Ok(sent)
+    }
+
+    /// Периодически рассылает дайджесты; не завершается.
+    pub async fn run(&self, check_interval: std::time::Duration) {
+        let mut ticker = tokio::time::interval(check_interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.send_due_digests(Utc::now()).await {
+                error!("Digest delivery failed: {}", e);
+            }
+        }
+    }
+
+    /// Отправляет дайджест одному подписчику; возвращает `false`, если новых постов нет
+    async fn send_digest(
+        &self,
+        subscription: &DigestSubscription,
+        now: DateTime<Utc>,
+    ) -> DomainResult<bool> {
+        let max_posts = self.options.max_posts;
+        // Один лишний пост показывает, что в письмо попали не все
+        let mut posts = self
+            .repository
+            .get_posts_published_between(subscription.last_sent_at, now, max_posts + 1)
+            .await?;
+        let has_more = posts.len() > max_posts as usize;
+        posts.truncate(max_posts as usize);
+        if posts.is_empty() {
+            debug!("No new posts for user {}", subscription.user_id);
+            return Ok(false);
+        }
+
+        let unsubscribe_url = self.unsubscribe_url(subscription.user_id);
+        let message = EmailMessage {
+            to: subscription.email.clone(),
+            subject: format!(
+                "Your {} digest from {}: {} new {}",
+                subscription.frequency.as_str(),
+                self.options.site_name,
+                posts.len(),
+                if posts.len() == 1 { "post" } else { "posts" }
+            ),
+            body: self.render_digest(subscription, &posts, has_more, &unsubscribe_url),
+            unsubscribe_url: Some(unsubscribe_url),
+            confidential: false,
+        };
+        self.email_sender.send(message).await?;
+        debug!("Digest sent to user {}", subscription.user_id);
+        Ok(true)
+    }
+
+    /// Текст письма со списком постов и ссылкой отписки
+    fn render_digest(
+        &self,
+        subscription: &DigestSubscription,
+        posts: &[Post],
+        has_more: bool,
+        unsubscribe_url: &str,
+    ) -> String {
+        let app_url = self.options.app_url.trim_end_matches('/');
+        let mut body = format!(
+            "Hi {},\n\nNew posts on {} since {}:\n\n",
+            subscription.username,
+            self.options.site_name,
+            subscription.last_sent_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        for post in posts {
+            let _ = writeln!(
+                body,
+                "- {} by {}\n  {}/posts/{}\n",
+                post.title, post.author_username, app_url, post.uuid
+            );
+        }
+        if has_more {
+            let _ = writeln!(body, "More posts: {app_url}/\n");
+        }
+        let _ = write!(
+            body,
+            "You receive this {} digest because you subscribed to it.\nUnsubscribe: {}\n",
+            subscription.frequency.as_str(),
+            unsubscribe_url
+        );
+        body
+    }
+
+    /// Ссылка отписки от дайджеста, подписанная секретом сервера
+    fn unsubscribe_url(&self, user_id: Uuid) -> String {
+        let token = hex::encode(self.unsubscribe_mac(user_id).finalize().into_bytes());
+        format!(
+            "{}/digest/unsubscribe?user_id={}&token={}",
+            self.options.public_url.trim_end_matches('/'),
+            user_id,
+            token
+        )
+    }
+
+    fn unsubscribe_mac(&self, user_id: Uuid) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.unsubscribe_secret)
+            .expect("HMAC accepts keys of any length");
+        mac.update(UNSUBSCRIBE_TOKEN_CONTEXT);
+        mac.update(user_id.as_bytes());
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::dto::post::CreatePostDto;
+    use crate::application::post::PostApplication;
+    use crate::data::memrepo::InMemoryRepository;
+    use crate::domain::entities::post::PostStatus;
+    use crate::domain::entities::user::{Role, User};
+    use crate::domain::repositories::repo::UserRepository;
+    use crate::infrastructure::email::LogEmailSender;
+
+    type App = DigestApplication<InMemoryRepository>;
+
+    fn app() -> (App, Arc<InMemoryRepository>) {
+        app_with_max_posts(10)
+    }
+
+    fn app_with_max_posts(max_posts: u32) -> (App, Arc<InMemoryRepository>) {
+        let repo = Arc::new(InMemoryRepository::new());
+        let app = DigestApplication::new(
+            repo.clone(),
+            Arc::new(LogEmailSender),
+            b"test-secret",
+            DigestOptions {
+                site_name: "Blog".to_string(),
+                app_url: "http://localhost".to_string(),
+                public_url: "http://localhost".to_string(),
+                max_posts,
+            },
+        );
+        (app, repo)
+    }
+
+    /// Создаёт пользователя с недельной подпиской на дайджест
+    async fn subscriber(app: &App, repo: &InMemoryRepository, username: &str) -> Uuid {
+        let user = User::new(
+            Uuid::now_v7(),
+            username.to_string(),
+            format!("{username}@example.com"),
+            "hash".to_string(),
+            Role::User,
+            Utc::now(),
+        );
+        let user_id = repo.create_user(user).await.unwrap().id;
+        app.update_settings(user_id, Some(DigestFrequency::Weekly))
+            .await
+            .unwrap();
+        user_id
+    }
+
+    /// Токен из ссылки отписки в письме пользователю `user_id`
+    fn token(app: &App, user_id: Uuid) -> String {
+        let url = app.unsubscribe_url(user_id);
+        url.split_once("token=").unwrap().1.to_string()
+    }
+
+    async fn is_subscribed(app: &App, user_id: Uuid) -> bool {
+        app.get_settings(user_id).await.unwrap().frequency.is_some()
+    }
+
+    #[tokio::test]
+    async fn test_valid_token_unsubscribes() {
+        let (app, repo) = app();
+        let alice = subscriber(&app, &repo, "alice").await;
+
+        app.unsubscribe(alice, &token(&app, alice)).await.unwrap();
+        assert!(!is_subscribed(&app, alice).await);
+        // Повторный переход по ссылке не считается ошибкой
+        app.unsubscribe(alice, &token(&app, alice)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_forged_token_is_rejected() {
+        let (app, repo) = app();
+        let alice = subscriber(&app, &repo, "alice").await;
+
+        let zeros = "00".repeat(32);
+        for forged in ["", "forged", zeros.as_str()] {
+            assert!(matches!(
+                app.unsubscribe(alice, forged).await,
+                Err(DomainError::InvalidUnsubscribeToken)
+            ));
+        }
+        // Подпись с другим секретом тоже не подходит
+        let other_app: App = DigestApplication::new(
+            repo.clone(),
+            Arc::new(LogEmailSender),
+            b"other-secret",
+            app.options.clone(),
+        );
+        assert!(matches!(
+            app.unsubscribe(alice, &token(&other_app, alice)).await,
+            Err(DomainError::InvalidUnsubscribeToken)
+        ));
+        assert!(is_subscribed(&app, alice).await);
+    }
+
+    #[tokio::test]
+    async fn test_token_of_another_user_is_rejected() {
+        let (app, repo) = app();
+        let alice = subscriber(&app, &repo, "alice").await;
+        let bob = subscriber(&app, &repo, "bob").await;
+
+        assert!(matches!(
+            app.unsubscribe(alice, &token(&app, bob)).await,
+            Err(DomainError::InvalidUnsubscribeToken)
+        ));
+        assert!(is_subscribed(&app, alice).await);
+        assert!(is_subscribed(&app, bob).await);
+    }
+
+    #[tokio::test]
+    async fn test_digest_without_posts_to_show_is_not_sent() {
+        let (app, repo) = app_with_max_posts(0);
+        let alice = subscriber(&app, &repo, "alice").await;
+        PostApplication::new(repo.clone(), repo.clone(), false)
+            .create_post(CreatePostDto {
+                title: "Title".to_string(),
+                content: "Content".to_string(),
+                author_id: alice,
+                author_username: String::new(),
+                tags: vec![],
+                category_id: None,
+                blog_id: None,
+                summary: None,
+                attachment_ids: vec![],
+                status: PostStatus::Published,
+            })
+            .await
+            .unwrap();
+
+        let now = Utc::now() + chrono::Duration::days(8);
+        assert_eq!(app.send_due_digests(now).await.unwrap(), 0);
+    }
+
+    #[test]
+    fn test_derived_unsubscribe_secret_differs_from_source() {
+        let derived = derive_unsubscribe_secret(b"jwt-secret");
+        assert_ne!(derived, b"jwt-secret");
+        assert_eq!(derived, derive_unsubscribe_secret(b"jwt-secret"));
+        assert_ne!(derived, derive_unsubscribe_secret(b"other-secret"));
+    }
+}
@@ -0,0 +1,55 @@
+use uuid::Uuid;
+
+use crate::domain::entities::admin::{SystemStats, UserSummary};
+use crate::domain::entities::user::Role;
+
+#[derive(Debug, Clone)]
+pub struct UserSummaryDto {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: Role,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserPageDto {
+    pub users: Vec<UserSummaryDto>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SystemStatsDto {
+    pub users: u64,
+    pub posts: u64,
+    pub recent_signups: u64,
+    /// За сколько последних дней считаются новые регистрации
+    pub recent_signups_days: u32,
+}
+
+impl UserSummaryDto {
+    pub fn from_entity(user: UserSummary) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            role: user.role,
+            created_at: user.created_at,
+            disabled_at: user.disabled_at,
+        }
+    }
+}
+
+impl SystemStatsDto {
+    pub fn from_entity(stats: SystemStats, recent_signups_days: u32) -> Self {
+        Self {
+            users: stats.users,
+            posts: stats.posts,
+            recent_signups: stats.recent_signups,
+            recent_signups_days,
+        }
+    }
+}
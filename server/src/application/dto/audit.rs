@@ -0,0 +1,32 @@
+use uuid::Uuid;
+
+use crate::domain::entities::audit::{AuditAction, AuditEntry};
+
+#[derive(Debug, Clone)]
+pub struct AuditEntryDto {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub action: AuditAction,
+    pub target_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditPageDto {
+    pub entries: Vec<AuditEntryDto>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u32,
+}
+
+impl AuditEntryDto {
+    pub fn from_entity(entry: AuditEntry) -> Self {
+        Self {
+            id: entry.id,
+            user_id: entry.user_id,
+            action: entry.action,
+            target_id: entry.target_id,
+            created_at: entry.created_at,
+        }
+    }
+}
@@ -1,3 +1,9 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::session::DeviceInfo;
+use crate::domain::entities::user::IdentifierKind;
+
 #[derive(Debug, Clone)]
 pub struct RegisterDto {
     pub username: String,
@@ -7,17 +13,82 @@ pub struct RegisterDto {
 
 #[derive(Debug, Clone)]
 pub struct LoginDto {
-    pub username: String,
+    /// Имя пользователя или email
+    pub identifier: String,
+    /// Вид идентификатора: явно указанный клиентом или определённый по `identifier`
+    pub kind: IdentifierKind,
     pub password: String,
+    /// Клиент, с которого выполняется вход
+    pub device: DeviceInfo,
+}
+
+/// Второй шаг входа: код TOTP для токена, выданного после проверки пароля
+#[derive(Debug, Clone)]
+pub struct TotpLoginDto {
+    pub challenge_token: String,
+    pub code: String,
+    /// Клиент, с которого выполняется вход
+    pub device: DeviceInfo,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResetPasswordDto {
+    pub token: String,
+    pub new_password: String,
 }
 
 #[derive(Debug, Clone)]
 pub struct TokenDto {
+    /// ID пользователя, которому выданы токены
+    pub user_id: Uuid,
     pub access_token: String,
     pub refresh_token: String,
     pub expires_in: i64,
 }
 
+/// Результат проверки пароля при входе
+#[derive(Debug, Clone)]
+pub enum LoginOutcome {
+    /// Вход выполнен
+    Authenticated(TokenDto),
+    /// Включена 2FA: токены выдаются после кода TOTP для `challenge_token`
+    TwoFactorRequired {
+        challenge_token: String,
+        /// Время жизни `challenge_token` в секундах
+        expires_in: i64,
+    },
+}
+
+/// Данные для добавления секрета TOTP в приложение-аутентификатор
+#[derive(Debug, Clone)]
+pub struct TotpEnrollmentDto {
+    /// Секрет в кодировке Base32 для ручного ввода
+    pub secret: String,
+    /// `otpauth://` URI (обычно показывается QR-кодом)
+    pub provisioning_uri: String,
+}
+
+/// Начало входа через провайдера OAuth
+#[derive(Debug, Clone)]
+pub struct OAuthRedirectDto {
+    /// Адрес страницы входа провайдера
+    pub authorization_url: String,
+    /// Значение `state`, которое провайдер вернёт вместе с кодом авторизации
+    pub state: String,
+}
+
+/// Сеанс входа пользователя
+#[derive(Debug, Clone)]
+pub struct SessionDto {
+    pub id: Uuid,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+    /// Сеанс, которому принадлежит токен запроса
+    pub current: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct UserInfoDto {
     pub user_id: String,
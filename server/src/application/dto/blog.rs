@@ -0,0 +1,31 @@
+use uuid::Uuid;
+
+use crate::domain::entities::blog::Blog;
+
+#[derive(Debug, Clone)]
+pub struct CreateBlogDto {
+    pub slug: String,
+    pub name: String,
+    pub owner_id: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub struct BlogDto {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub owner_id: Uuid,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl BlogDto {
+    pub fn from_entity(blog: Blog) -> Self {
+        Self {
+            id: blog.id,
+            slug: blog.slug,
+            name: blog.name,
+            owner_id: blog.owner_id,
+            created_at: blog.created_at,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use uuid::Uuid;
+
+use crate::domain::entities::category::Category;
+
+#[derive(Debug, Clone)]
+pub struct CreateCategoryDto {
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CategoryDto {
+    pub id: Uuid,
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl CategoryDto {
+    pub fn from_entity(category: Category) -> Self {
+        Self {
+            id: category.id,
+            name: category.name,
+            parent_id: category.parent_id,
+            created_at: category.created_at,
+        }
+    }
+}
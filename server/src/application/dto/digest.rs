@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::entities::digest::{DigestFrequency, DigestSubscription};
+
+/// Настройки email-дайджеста пользователя.
+#[derive(Debug, Clone)]
+pub struct DigestSettingsDto {
+    /// Частота писем; `None` - пользователь не подписан
+    pub frequency: Option<DigestFrequency>,
+    /// Время предыдущего дайджеста (для новой подписки - время подписки)
+    pub last_sent_at: Option<DateTime<Utc>>,
+}
+
+impl DigestSettingsDto {
+    pub fn from_entity(subscription: Option<DigestSubscription>) -> Self {
+        Self {
+            frequency: subscription.as_ref().map(|s| s.frequency),
+            last_sent_at: subscription.map(|s| s.last_sent_at),
+        }
+    }
+}
@@ -1,2 +1,10 @@
+pub mod admin;
+pub mod audit;
 pub mod auth;
+pub mod blog;
+pub mod category;
+pub mod digest;
 pub mod post;
+pub mod profile;
+pub mod report;
+pub mod upload;
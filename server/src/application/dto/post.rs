@@ -1,10 +1,21 @@
 use uuid::Uuid;
 
+use crate::domain::entities::post::PostStatus;
+
 #[derive(Debug, Clone)]
 pub struct CreatePostDto {
     pub title: String,
     pub content: String,
     pub author_id: Uuid,
+    pub author_username: String,
+    pub tags: Vec<String>,
+    pub category_id: Option<Uuid>,
+    /// Блог, в котором публикуется пост; `None` - общая лента сервера
+    pub blog_id: Option<Uuid>,
+    /// Описание поста; без него описание составляется из начала содержимого
+    pub summary: Option<String>,
+    pub attachment_ids: Vec<Uuid>,
+    pub status: PostStatus,
 }
 
 #[derive(Debug, Clone)]
@@ -12,6 +23,13 @@ pub struct UpdatePostDto {
     pub uuid: Uuid,
     pub title: String,
     pub content: String,
+    pub tags: Vec<String>,
+    /// Новая категория: `Some(None)` убирает категорию, `None` оставляет её без изменений
+    pub category_id: Option<Option<Uuid>>,
+    /// Новое описание: `Some(None)` убирает описание автора, `None` оставляет его без изменений
+    pub summary: Option<Option<String>>,
+    /// Новый набор вложений; `None` оставляет вложения без изменений
+    pub attachment_ids: Option<Vec<Uuid>>,
 }
 
 #[derive(Debug, Clone)]
@@ -19,18 +37,61 @@ pub struct PostDto {
     pub uuid: Uuid,
     pub title: String,
     pub content: String,
+    pub content_html: Option<String>,
+    /// Описание автора или начало содержимого
+    pub summary: String,
     pub author_id: Uuid,
+    pub author_username: String,
+    pub author_avatar_url: Option<String>,
+    pub tags: Vec<String>,
+    pub category_id: Option<Uuid>,
+    pub blog_id: Option<Uuid>,
+    pub attachments: Vec<String>,
+    pub status: PostStatus,
+    pub like_count: i64,
+    pub liked_by_me: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Debug, Clone)]
+pub struct PostPageDto {
+    pub posts: Vec<PostDto>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u32,
+    /// Есть ли посты после текущей страницы
+    pub has_next: bool,
+    /// Курсор следующей страницы для [`PostApplication::get_posts_after`](crate::application::post::PostApplication::get_posts_after)
+    pub next_cursor: Option<String>,
+}
+
+/// Изменение опубликованного поста для подписчиков живой ленты.
+#[derive(Debug, Clone)]
+pub enum PostEventDto {
+    Created(PostDto),
+    Updated(PostDto),
+    Deleted { post_id: Uuid },
+}
+
 impl PostDto {
     pub fn from_entity(post: crate::domain::entities::post::Post) -> Self {
         Self {
+            summary: post.summary_or_excerpt(),
             uuid: post.uuid,
             title: post.title,
             content: post.content,
+            content_html: post.content_html,
             author_id: post.author_id,
+            author_username: post.author_username,
+            author_avatar_url: post.author_avatar_url,
+            tags: post.tags,
+            category_id: post.category_id,
+            blog_id: post.blog_id,
+            attachments: post.attachments,
+            status: post.status,
+            like_count: post.like_count,
+            liked_by_me: post.liked_by_me,
             created_at: post.created_at,
             updated_at: post.updated_at,
         }
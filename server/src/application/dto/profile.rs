@@ -0,0 +1,34 @@
+use uuid::Uuid;
+
+use crate::domain::entities::user::UserProfile;
+
+#[derive(Debug, Clone)]
+pub struct UpdateProfileDto {
+    pub user_id: Uuid,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UserProfileDto {
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl UserProfileDto {
+    pub fn from_entity(profile: UserProfile) -> Self {
+        Self {
+            user_id: profile.id,
+            username: profile.username,
+            display_name: profile.display_name,
+            bio: profile.bio,
+            avatar_url: profile.avatar_url,
+            created_at: profile.created_at,
+        }
+    }
+}
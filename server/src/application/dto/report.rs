@@ -0,0 +1,48 @@
+use uuid::Uuid;
+
+use crate::domain::entities::report::{Report, ReportReason, ReportStatus};
+
+#[derive(Debug, Clone)]
+pub struct CreateReportDto {
+    pub post_id: Uuid,
+    pub reporter_id: Uuid,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportDto {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub reporter_id: Uuid,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub status: ReportStatus,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportPageDto {
+    pub reports: Vec<ReportDto>,
+    pub total: u64,
+    pub page: u32,
+    pub page_size: u32,
+}
+
+impl ReportDto {
+    pub fn from_entity(report: Report) -> Self {
+        Self {
+            id: report.id,
+            post_id: report.post_id,
+            reporter_id: report.reporter_id,
+            reason: report.reason,
+            details: report.details,
+            status: report.status,
+            resolved_by: report.resolved_by,
+            resolved_at: report.resolved_at,
+            created_at: report.created_at,
+        }
+    }
+}
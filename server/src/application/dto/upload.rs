@@ -0,0 +1,26 @@
+use uuid::Uuid;
+
+use crate::domain::entities::attachment::Attachment;
+
+#[derive(Debug, Clone)]
+pub struct AttachmentDto {
+    pub id: Uuid,
+    pub url: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl AttachmentDto {
+    pub fn from_entity(attachment: Attachment) -> Self {
+        Self {
+            id: attachment.id,
+            url: attachment.url,
+            file_name: attachment.file_name,
+            content_type: attachment.content_type,
+            size_bytes: attachment.size_bytes,
+            created_at: attachment.created_at,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::repositories::follow::FollowRepository;
+use crate::domain::repositories::repo::UserRepository;
+use std::sync::Arc;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+/// Подписки на авторов. Ленту постов из подписок возвращает
+/// [`PostApplication::get_feed`](crate::application::post::PostApplication::get_feed).
+pub struct FollowApplication<Repo: FollowRepository + UserRepository + ?Sized> {
+    repository: Arc<Repo>,
+}
+
+impl<Repo: FollowRepository + UserRepository + ?Sized> FollowApplication<Repo> {
+    pub fn new(repository: Arc<Repo>) -> Self {
+        Self { repository }
+    }
+
+    /// Подписывает пользователя на автора; повторная подписка ничего не меняет.
+    ///
+    /// Подписаться на самого себя нельзя.
+    #[instrument(skip(self), fields(follower_id = %follower_id, followee_id = %followee_id))]
+    pub async fn follow_user(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<()> {
+        debug!("Following user");
+        if follower_id == followee_id {
+            warn!("User tried to follow themselves");
+            return Err(DomainError::InvalidFollow {
+                reason: "users cannot follow themselves".to_string(),
+            });
+        }
+        self.repository.get_user_profile(followee_id).await?;
+
+        self.repository
+            .follow_user(follower_id, followee_id)
+            .await?;
+        info!("User followed successfully");
+        Ok(())
+    }
+
+    /// Отменяет подписку пользователя на автора.
+    #[instrument(skip(self), fields(follower_id = %follower_id, followee_id = %followee_id))]
+    pub async fn unfollow_user(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<()> {
+        debug!("Unfollowing user");
+        self.repository.get_user_profile(followee_id).await?;
+
+        self.repository
+            .unfollow_user(follower_id, followee_id)
+            .await?;
+        info!("User unfollowed successfully");
+        Ok(())
+    }
+
+    /// Подписан ли пользователь на автора.
+    #[instrument(skip(self), fields(follower_id = %follower_id, followee_id = %followee_id))]
+    pub async fn is_following(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<bool> {
+        debug!("Checking follow");
+        self.repository.get_user_profile(followee_id).await?;
+
+        self.repository.is_following(follower_id, followee_id).await
+    }
+}
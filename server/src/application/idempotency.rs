@@ -0,0 +1,84 @@
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::idempotency::{
+    IdempotencyRecord, StoredResponse, validate_idempotency_key,
+};
+use crate::domain::repositories::repo::UserRepository;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{debug, info, instrument, warn};
+
+/// Сколько часов хранится ответ на запрос с ключом идемпотентности
+const IDEMPOTENCY_KEY_TTL_HOURS: i64 = 24;
+
+pub struct IdempotencyApplication<Repo: UserRepository + ?Sized> {
+    user_repository: Arc<Repo>,
+}
+
+impl<Repo: UserRepository + ?Sized> IdempotencyApplication<Repo> {
+    pub fn new(user_repository: Arc<Repo>) -> Self {
+        Self { user_repository }
+    }
+
+    /// Резервирует ключ за запросом с телом `request_body`.
+    ///
+    /// Возвращает `None`, если запрос нужно выполнить, или сохранённый ответ, если
+    /// такой же запрос с этим ключом уже выполнен. Ключ действует 24 часа в пределах `scope`.
+    #[instrument(skip(self, request_body))]
+    pub async fn begin(
+        &self,
+        scope: &str,
+        key: &str,
+        request_body: &[u8],
+    ) -> DomainResult<Option<StoredResponse>> {
+        debug!("Starting idempotent request");
+        validate_idempotency_key(key)?;
+
+        let request_hash = hex::encode(Sha256::digest(request_body));
+        let expired_before =
+            chrono::Utc::now() - chrono::Duration::hours(IDEMPOTENCY_KEY_TTL_HOURS);
+        let record = self
+            .user_repository
+            .reserve_idempotency_key(scope, key, &request_hash, expired_before)
+            .await?;
+
+        match record {
+            None => Ok(None),
+            Some(IdempotencyRecord {
+                request_hash: stored_hash,
+                ..
+            }) if stored_hash != request_hash => {
+                warn!("Idempotency key reused with a different request body");
+                Err(DomainError::IdempotencyKeyReused)
+            }
+            Some(IdempotencyRecord {
+                response: Some(response),
+                ..
+            }) => {
+                info!("Replaying stored response");
+                Ok(Some(response))
+            }
+            Some(_) => Err(DomainError::IdempotencyKeyInProgress),
+        }
+    }
+
+    /// Сохраняет ответ для повторов запроса с тем же ключом.
+    #[instrument(skip(self, response))]
+    pub async fn complete(
+        &self,
+        scope: &str,
+        key: &str,
+        response: StoredResponse,
+    ) -> DomainResult<()> {
+        self.user_repository
+            .complete_idempotency_key(scope, key, &response)
+            .await
+    }
+
+    /// Освобождает ключ неудавшегося запроса, чтобы клиент мог его повторить.
+    #[instrument(skip(self))]
+    pub async fn release(&self, scope: &str, key: &str) -> DomainResult<()> {
+        self.user_repository
+            .release_idempotency_key(scope, key)
+            .await
+    }
+}
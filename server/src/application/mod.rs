@@ -1,3 +1,13 @@
+pub mod admin;
+pub mod audit;
 pub mod auth;
+pub mod blog;
+pub mod category;
+pub mod digest;
 pub mod dto;
+pub mod follow;
+pub mod idempotency;
 pub mod post;
+pub mod profile;
+pub mod report;
+pub mod upload;
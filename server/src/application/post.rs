@@ -1,32 +1,340 @@
-use crate::application::dto::post::{CreatePostDto, PostDto, UpdatePostDto};
-use crate::domain::entities::errors::DomainResult;
-use crate::domain::entities::post::Post;
+use crate::application::dto::post::{
+    CreatePostDto, PostDto, PostEventDto, PostPageDto, UpdatePostDto,
+};
+use crate::domain::entities::attachment::normalize_attachment_ids;
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::post::{
+    Post, PostCursor, PostFields, PostSort, PostStatus, check_batch_size, normalize_summary,
+    normalize_tags,
+};
+use crate::domain::entities::user::Role;
+use crate::domain::repositories::blog::BlogRepository;
+use crate::domain::repositories::category::CategoryRepository;
+use crate::domain::repositories::post::PostRepository;
 use crate::domain::repositories::repo::UserRepository;
+use crate::domain::repositories::report::ReportRepository;
+use crate::domain::services::markdown::render_markdown;
+use chrono::Timelike;
 use std::sync::Arc;
-use tracing::{debug, info, instrument};
+use tokio::sync::broadcast;
+use tracing::{debug, info, instrument, warn};
 use uuid::Uuid;
 
-pub struct PostApplication<Repo: UserRepository> {
-    user_repository: Arc<Repo>,
+/// Сколько событий может накопить отстающий подписчик, прежде чем начнёт их терять
+const POST_EVENTS_CAPACITY: usize = 256;
+/// Окно подсчёта лайков для популярных постов по умолчанию: неделя
+const DEFAULT_TRENDING_WINDOW_HOURS: i64 = 24 * 7;
+
+/// Ограничения на создание и размер постов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostLimits {
+    /// Сколько постов пользователь может создать за сутки (UTC); `0` отключает квоту
+    pub max_posts_per_day: u32,
+    /// Максимальный размер содержимого поста в байтах UTF-8
+    pub max_content_bytes: usize,
+    /// Максимальная длина заголовка в символах
+    pub max_title_chars: usize,
+}
+
+impl Default for PostLimits {
+    fn default() -> Self {
+        Self {
+            max_posts_per_day: 0,
+            max_content_bytes: 400_000,
+            max_title_chars: 500,
+        }
+    }
 }
 
-impl<Repo: UserRepository> PostApplication<Repo> {
-    pub fn new(user_repository: Arc<Repo>) -> Self {
-        Self { user_repository }
+impl PostLimits {
+    /// Проверяет размер заголовка и содержимого поста
+    fn check_size(&self, title: &str, content: &str) -> DomainResult<()> {
+        if title.chars().count() > self.max_title_chars {
+            return Err(DomainError::QuotaExceeded {
+                reason: format!("title is longer than {} characters", self.max_title_chars),
+                retry_at: None,
+            });
+        }
+        if content.len() > self.max_content_bytes {
+            return Err(DomainError::QuotaExceeded {
+                reason: format!("content is larger than {} bytes", self.max_content_bytes),
+                retry_at: None,
+            });
+        }
+        Ok(())
     }
+}
 
+pub struct PostApplication<Posts: PostRepository + ?Sized, Users: UserRepository + ?Sized> {
+    post_repository: Arc<Posts>,
+    user_repository: Arc<Users>,
+    events: broadcast::Sender<PostEventDto>,
+    render_markdown: bool,
+    trending_window: chrono::Duration,
+    reports: Option<Arc<dyn ReportRepository>>,
+    categories: Option<Arc<dyn CategoryRepository>>,
+    blogs: Option<Arc<dyn BlogRepository>>,
+    limits: PostLimits,
+}
+
+impl<Posts: PostRepository + ?Sized, Users: UserRepository + ?Sized> PostApplication<Posts, Users> {
+    /// Создаёт сервис постов; при `render_markdown` содержимое постов рендерится в HTML.
+    pub fn new(
+        post_repository: Arc<Posts>,
+        user_repository: Arc<Users>,
+        render_markdown: bool,
+    ) -> Self {
+        let (events, _) = broadcast::channel(POST_EVENTS_CAPACITY);
+        Self {
+            post_repository,
+            user_repository,
+            events,
+            render_markdown,
+            trending_window: chrono::Duration::hours(DEFAULT_TRENDING_WINDOW_HOURS),
+            reports: None,
+            categories: None,
+            blogs: None,
+            limits: PostLimits::default(),
+        }
+    }
+
+    /// Квота постов в сутки и ограничения размера поста
+    pub fn with_limits(mut self, limits: PostLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// За какой период считать лайки популярных постов
+    pub fn with_trending_window(mut self, window: chrono::Duration) -> Self {
+        self.trending_window = window;
+        self
+    }
+
+    /// Хранилище жалоб: по нему проверяется, что автор не восстанавливает скрытый
+    /// модератором пост
+    pub fn with_reports(mut self, reports: Arc<dyn ReportRepository>) -> Self {
+        self.reports = Some(reports);
+        self
+    }
+
+    /// Хранилище категорий: по нему проверяется, что категория поста существует
+    pub fn with_categories(mut self, categories: Arc<dyn CategoryRepository>) -> Self {
+        self.categories = Some(categories);
+        self
+    }
+
+    /// Хранилище блогов: по нему проверяется, что блог поста существует и принадлежит автору
+    pub fn with_blogs(mut self, blogs: Arc<dyn BlogRepository>) -> Self {
+        self.blogs = Some(blogs);
+        self
+    }
+
+    /// Подписывает на изменения опубликованных постов.
+    ///
+    /// Черновики в ленту не попадают: публикация черновика приходит как
+    /// [`PostEventDto::Created`], возврат поста в черновики - как [`PostEventDto::Deleted`].
+    pub fn subscribe(&self) -> broadcast::Receiver<PostEventDto> {
+        self.events.subscribe()
+    }
+
+    /// Возвращает страницу постов, при указании `tag` - только посты с этим тегом,
+    /// при указании `category` - только посты этой категории и её подкатегорий,
+    /// при указании `blog` - только посты этого блога.
+    ///
+    /// В список попадают опубликованные посты и черновики пользователя `viewer`.
+    /// При порядке по умолчанию страница содержит курсор, с которого можно
+    /// продолжить обход через [`get_posts_after`](Self::get_posts_after).
+    /// Части постов, не выбранные в `fields`, могут остаться пустыми.
     #[instrument(skip(self))]
-    pub async fn get_posts(&self, page: u32, page_size: u32) -> DomainResult<Vec<PostDto>> {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_posts(
+        &self,
+        page: u32,
+        page_size: u32,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+        sort: PostSort,
+        fields: PostFields,
+    ) -> DomainResult<PostPageDto> {
         debug!("Fetching all posts");
-        let posts = self.user_repository.get_posts(page, page_size).await?;
-        info!("Retrieved {} posts", posts.len());
+        let tag = tag.map(|tag| tag.trim().to_lowercase());
+        let tag = tag.as_deref().filter(|tag| !tag.is_empty());
+        let posts = self
+            .post_repository
+            .get_posts(page, page_size, tag, category, blog, viewer, sort, fields)
+            .await?;
+        let total = self
+            .post_repository
+            .count_posts(tag, category, blog, viewer)
+            .await?;
+        info!("Retrieved {} of {} posts", posts.len(), total);
+        let has_next = (page as u64 + 1) * (page_size as u64) < total;
+        let next_cursor = posts
+            .last()
+            .filter(|_| has_next && sort == PostSort::default())
+            .map(|post| PostCursor::after(post).encode());
+        Ok(PostPageDto {
+            posts: posts.into_iter().map(PostDto::from_entity).collect(),
+            total,
+            page,
+            page_size,
+            has_next,
+            next_cursor,
+        })
+    }
+
+    /// Возвращает до `limit` популярных постов: больше всего лайков за последнее окно
+    /// (см. [`with_trending_window`](Self::with_trending_window)).
+    ///
+    /// Начало окна округляется до минуты, чтобы запросы в пределах минуты совпадали в кэше.
+    #[instrument(skip(self))]
+    pub async fn get_trending_posts(
+        &self,
+        limit: u32,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<Vec<PostDto>> {
+        debug!("Fetching trending posts");
+        let since = chrono::Utc::now() - self.trending_window;
+        let since = since
+            .with_second(0)
+            .and_then(|since| since.with_nanosecond(0))
+            .unwrap_or(since);
+        let posts = self
+            .post_repository
+            .get_trending_posts(since, limit, viewer)
+            .await?;
+        info!("Retrieved {} trending posts", posts.len());
         Ok(posts.into_iter().map(PostDto::from_entity).collect())
     }
 
+    /// Возвращает до `limit` постов после курсора `cursor` (keyset-пагинация).
+    ///
+    /// Курсор берётся из `next_cursor` предыдущей страницы; без курсора возвращается
+    /// первая страница. Обход по курсору возможен только в порядке по умолчанию
+    /// (сначала новые), для другого `sort` возвращается ошибка.
+    #[instrument(skip(self))]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_posts_after(
+        &self,
+        cursor: Option<&str>,
+        limit: u32,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+        sort: PostSort,
+        fields: PostFields,
+    ) -> DomainResult<PostPageDto> {
+        debug!("Fetching posts after cursor");
+        if sort != PostSort::default() {
+            return Err(DomainError::InvalidCursor {
+                reason: "cursor pagination supports only the default order".to_string(),
+            });
+        }
+        let cursor = cursor.map(PostCursor::decode).transpose()?;
+        let tag = tag.map(|tag| tag.trim().to_lowercase());
+        let tag = tag.as_deref().filter(|tag| !tag.is_empty());
+
+        // Лишний пост показывает, есть ли следующая страница
+        let mut posts = self
+            .post_repository
+            .get_posts_after(cursor, limit + 1, tag, category, blog, viewer, fields)
+            .await?;
+        let has_next = posts.len() > limit as usize;
+        posts.truncate(limit as usize);
+        let next_cursor = posts
+            .last()
+            .filter(|_| has_next)
+            .map(|post| PostCursor::after(post).encode());
+
+        let total = self
+            .post_repository
+            .count_posts(tag, category, blog, viewer)
+            .await?;
+        info!("Retrieved {} of {} posts after cursor", posts.len(), total);
+        Ok(PostPageDto {
+            posts: posts.into_iter().map(PostDto::from_entity).collect(),
+            total,
+            page: 0,
+            page_size: limit,
+            has_next,
+            next_cursor,
+        })
+    }
+
+    /// Возвращает страницу постов автора `author_id`.
+    ///
+    /// Черновики автора видны только ему самому; для неизвестного автора возвращается ошибка.
+    #[instrument(skip(self), fields(author_id = %author_id))]
+    pub async fn get_posts_by_author(
+        &self,
+        author_id: Uuid,
+        page: u32,
+        page_size: u32,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<PostPageDto> {
+        debug!("Fetching author posts");
+        self.user_repository.get_user_profile(author_id).await?;
+        let posts = self
+            .post_repository
+            .get_posts_by_author(author_id, page, page_size, viewer)
+            .await?;
+        let total = self
+            .post_repository
+            .count_posts_by_author(author_id, viewer)
+            .await?;
+        info!("Retrieved {} of {} author posts", posts.len(), total);
+        Ok(PostPageDto {
+            posts: posts.into_iter().map(PostDto::from_entity).collect(),
+            total,
+            page,
+            page_size,
+            has_next: (page as u64 + 1) * (page_size as u64) < total,
+            next_cursor: None,
+        })
+    }
+
+    /// Возвращает ленту: опубликованные посты авторов из подписок пользователя,
+    /// от новых к старым.
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn get_feed(
+        &self,
+        user_id: Uuid,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<PostPageDto> {
+        debug!("Fetching feed");
+        let posts = self
+            .post_repository
+            .get_feed_posts(user_id, page, page_size)
+            .await?;
+        let total = self.post_repository.count_feed_posts(user_id).await?;
+        info!("Retrieved {} of {} feed posts", posts.len(), total);
+        Ok(PostPageDto {
+            posts: posts.into_iter().map(PostDto::from_entity).collect(),
+            total,
+            page,
+            page_size,
+            has_next: (page as u64 + 1) * (page_size as u64) < total,
+            next_cursor: None,
+        })
+    }
+
+    /// Возвращает пост по ID; чужой черновик считается несуществующим.
     #[instrument(skip(self), fields(post_id = %post_id))]
-    pub async fn get_post_by_id(&self, post_id: Uuid) -> DomainResult<PostDto> {
+    pub async fn get_post_by_id(
+        &self,
+        post_id: Uuid,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<PostDto> {
         debug!("Fetching post by id");
-        let post = self.user_repository.get_post_by_id(post_id).await?;
+        let post = self.post_repository.get_post_by_id(post_id, viewer).await?;
+        if !post.is_visible_to(viewer) {
+            debug!("Draft is hidden from the requester");
+            return Err(DomainError::PostNotFound { post_id });
+        }
         info!("Post retrieved successfully");
         Ok(PostDto::from_entity(post))
     }
@@ -35,47 +343,875 @@ impl<Repo: UserRepository> PostApplication<Repo> {
     pub async fn create_post(&self, dto: CreatePostDto) -> DomainResult<PostDto> {
         debug!("Creating new post");
 
-        let now = chrono::Utc::now();
-        let post = Post {
-            uuid: Uuid::now_v7(),
-            title: dto.title,
-            content: dto.content,
-            author_id: dto.author_id,
-            created_at: now,
-            updated_at: now,
-        };
-
-        let created_post = self.user_repository.create_post(post).await?;
+        self.check_daily_quota(dto.author_id, 1).await?;
+        self.check_category(dto.category_id).await?;
+        self.check_blog(dto.blog_id, dto.author_id).await?;
+        let (post, attachment_ids) = self.new_post(dto)?;
+        let created_post = self
+            .post_repository
+            .create_post(post, &attachment_ids)
+            .await?;
         info!("Post created successfully with id: {}", created_post.uuid);
-        Ok(PostDto::from_entity(created_post))
+        let created_post = PostDto::from_entity(created_post);
+        if created_post.status == PostStatus::Published {
+            self.notify(PostEventDto::Created(created_post.clone()));
+        }
+        Ok(created_post)
     }
 
+    /// Создаёт несколько постов одной транзакцией: при ошибке не создаётся ни один.
+    #[instrument(skip(self, dtos), fields(count = dtos.len()))]
+    pub async fn create_posts(&self, dtos: Vec<CreatePostDto>) -> DomainResult<Vec<PostDto>> {
+        debug!("Creating posts in batch");
+        check_batch_size(dtos.len())?;
+        // Пакет создаёт посты одного автора
+        if let Some(dto) = dtos.first() {
+            self.check_daily_quota(dto.author_id, dtos.len()).await?;
+        }
+        for dto in &dtos {
+            self.check_category(dto.category_id).await?;
+            self.check_blog(dto.blog_id, dto.author_id).await?;
+        }
+
+        let posts = dtos
+            .into_iter()
+            .map(|dto| self.new_post(dto))
+            .collect::<DomainResult<Vec<_>>>()?;
+        let created_posts = self.post_repository.create_posts(posts).await?;
+        info!("Created {} posts", created_posts.len());
+
+        let created_posts: Vec<PostDto> = created_posts
+            .into_iter()
+            .map(PostDto::from_entity)
+            .collect();
+        for post in &created_posts {
+            if post.status == PostStatus::Published {
+                self.notify(PostEventDto::Created(post.clone()));
+            }
+        }
+        Ok(created_posts)
+    }
+
+    /// Обновляет пост; изменять пост может автор или администратор.
     #[instrument(skip(self, dto), fields(post_id = %dto.uuid, title = %dto.title))]
-    pub async fn update_post(&self, dto: UpdatePostDto) -> DomainResult<PostDto> {
+    pub async fn update_post(
+        &self,
+        dto: UpdatePostDto,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<PostDto> {
         debug!("Updating post");
 
-        // Проверяем, существует ли пост
-        let existing_post = self.user_repository.get_post_by_id(dto.uuid).await?;
+        self.limits.check_size(&dto.title, &dto.content)?;
+        let tags = normalize_tags(dto.tags)?;
+        let summary = dto.summary.map(normalize_summary).transpose()?;
+        let attachment_ids = dto
+            .attachment_ids
+            .map(normalize_attachment_ids)
+            .transpose()?;
+
+        let existing_post = self.modifiable_post(dto.uuid, user_id, role).await?;
+        if let Some(category_id) = dto.category_id {
+            self.check_category(category_id).await?;
+        }
 
         let updated_post = Post {
             uuid: dto.uuid,
             title: dto.title,
+            content_html: self.render_content(&dto.content),
             content: dto.content,
+            summary: summary.unwrap_or(existing_post.summary),
             author_id: existing_post.author_id,
+            author_username: existing_post.author_username,
+            author_avatar_url: existing_post.author_avatar_url,
+            tags,
+            category_id: dto.category_id.unwrap_or(existing_post.category_id),
+            blog_id: existing_post.blog_id,
+            attachments: existing_post.attachments,
+            status: existing_post.status,
+            like_count: existing_post.like_count,
+            liked_by_me: existing_post.liked_by_me,
             created_at: existing_post.created_at,
             updated_at: chrono::Utc::now(),
         };
 
-        let result = self.user_repository.update_post(updated_post).await?;
+        let result = self
+            .post_repository
+            .update_post(updated_post, attachment_ids.as_deref())
+            .await?;
         info!("Post updated successfully");
-        Ok(PostDto::from_entity(result))
+        let result = PostDto::from_entity(result);
+        if result.status == PostStatus::Published {
+            self.notify(PostEventDto::Updated(result.clone()));
+        }
+        Ok(result)
     }
 
+    /// Публикует пост; менять статус может автор или администратор.
+    pub async fn publish_post(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<PostDto> {
+        self.set_status(post_id, user_id, role, PostStatus::Published)
+            .await
+    }
+
+    /// Возвращает пост в черновики; менять статус может автор или администратор.
+    pub async fn unpublish_post(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<PostDto> {
+        self.set_status(post_id, user_id, role, PostStatus::Draft)
+            .await
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id, status = status.as_str()))]
+    async fn set_status(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        role: Role,
+        status: PostStatus,
+    ) -> DomainResult<PostDto> {
+        debug!("Changing post status");
+
+        let existing_post = self.modifiable_post(post_id, user_id, role).await?;
+
+        self.post_repository
+            .set_post_status(post_id, status)
+            .await?;
+        let post = self
+            .post_repository
+            .get_post_by_id(post_id, Some(user_id))
+            .await?;
+        info!("Post status changed successfully");
+        let post = PostDto::from_entity(post);
+        if existing_post.status != status {
+            match status {
+                PostStatus::Published => self.notify(PostEventDto::Created(post.clone())),
+                PostStatus::Draft => self.notify(PostEventDto::Deleted { post_id }),
+            }
+        }
+        Ok(post)
+    }
+
+    /// Ставит лайк посту от имени пользователя и возвращает обновлённый пост.
+    ///
+    /// Повторный лайк не меняет счётчик; лайкнуть можно только видимый пользователю пост.
+    #[instrument(skip(self), fields(post_id = %post_id, user_id = %user_id))]
+    pub async fn like_post(&self, post_id: Uuid, user_id: Uuid) -> DomainResult<PostDto> {
+        debug!("Liking post");
+        self.get_post_by_id(post_id, Some(user_id)).await?;
+        self.post_repository.like_post(post_id, user_id).await?;
+        info!("Post liked successfully");
+        self.get_post_by_id(post_id, Some(user_id)).await
+    }
+
+    /// Снимает лайк пользователя с поста и возвращает обновлённый пост.
+    #[instrument(skip(self), fields(post_id = %post_id, user_id = %user_id))]
+    pub async fn unlike_post(&self, post_id: Uuid, user_id: Uuid) -> DomainResult<PostDto> {
+        debug!("Unliking post");
+        self.get_post_by_id(post_id, Some(user_id)).await?;
+        self.post_repository.unlike_post(post_id, user_id).await?;
+        info!("Post unliked successfully");
+        self.get_post_by_id(post_id, Some(user_id)).await
+    }
+
+    /// Переносит пост в корзину; удалить пост может автор или администратор.
     #[instrument(skip(self), fields(post_id = %post_id))]
-    pub async fn delete_post(&self, post_id: Uuid) -> DomainResult<()> {
+    pub async fn delete_post(&self, post_id: Uuid, user_id: Uuid, role: Role) -> DomainResult<()> {
         debug!("Deleting post");
-        self.user_repository.delete_post(post_id).await?;
-        info!("Post deleted successfully");
+        let existing_post = self.modifiable_post(post_id, user_id, role).await?;
+        self.post_repository.delete_post(post_id).await?;
+        info!("Post moved to trash successfully");
+        if existing_post.status == PostStatus::Published {
+            self.notify(PostEventDto::Deleted { post_id });
+        }
+        Ok(())
+    }
+
+    /// Переносит посты в корзину одной транзакцией.
+    ///
+    /// Права проверяются для каждого поста заранее: если хотя бы один пост не найден
+    /// или недоступен пользователю, не удаляется ни один.
+    #[instrument(skip(self, post_ids), fields(count = post_ids.len()))]
+    pub async fn delete_posts(
+        &self,
+        post_ids: Vec<Uuid>,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<Vec<Uuid>> {
+        debug!("Deleting posts in batch");
+        let mut unique_ids: Vec<Uuid> = Vec::with_capacity(post_ids.len());
+        for post_id in post_ids {
+            if !unique_ids.contains(&post_id) {
+                unique_ids.push(post_id);
+            }
+        }
+        check_batch_size(unique_ids.len())?;
+
+        let mut existing_posts = Vec::with_capacity(unique_ids.len());
+        for post_id in &unique_ids {
+            existing_posts.push(self.modifiable_post(*post_id, user_id, role).await?);
+        }
+        self.post_repository.delete_posts(&unique_ids).await?;
+        info!("Moved {} posts to trash", unique_ids.len());
+
+        for post in existing_posts {
+            if post.status == PostStatus::Published {
+                self.notify(PostEventDto::Deleted { post_id: post.uuid });
+            }
+        }
+        Ok(unique_ids)
+    }
+
+    /// Возвращает пост из корзины; восстановить пост может автор или администратор.
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    pub async fn restore_post(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<PostDto> {
+        debug!("Restoring post");
+        self.trashed_post(post_id, user_id, role).await?;
+        if let Some(reports) = &self.reports
+            && !role.can_manage_any_post()
+            && reports.is_post_hidden(post_id).await?
+        {
+            warn!("Attempt to restore a post hidden by a moderator");
+            return Err(DomainError::Forbidden {
+                reason: "this post was hidden by a moderator".to_string(),
+            });
+        }
+        self.post_repository.restore_post(post_id).await?;
+        let post = self
+            .post_repository
+            .get_post_by_id(post_id, Some(user_id))
+            .await?;
+        info!("Post restored successfully");
+        let post = PostDto::from_entity(post);
+        if post.status == PostStatus::Published {
+            self.notify(PostEventDto::Created(post.clone()));
+        }
+        Ok(post)
+    }
+
+    /// Окончательно удаляет пост из корзины; доступно автору или администратору.
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    pub async fn purge_post(&self, post_id: Uuid, user_id: Uuid, role: Role) -> DomainResult<()> {
+        debug!("Purging post");
+        self.trashed_post(post_id, user_id, role).await?;
+        self.post_repository.purge_post(post_id).await?;
+        info!("Post purged successfully");
+        Ok(())
+    }
+
+    /// Окончательно удаляет любой пост, в том числе черновик или пост из корзины;
+    /// доступно только администратору.
+    #[instrument(skip(self), fields(post_id = %post_id, user_id = %user_id))]
+    pub async fn force_delete_post(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<()> {
+        debug!("Force deleting post");
+        if !role.can_administer() {
+            warn!("Force delete denied");
+            return Err(DomainError::Forbidden {
+                reason: "only administrators can force delete posts".to_string(),
+            });
+        }
+
+        let was_published = match self.post_repository.get_post_by_id(post_id, None).await {
+            Ok(post) => post.status == PostStatus::Published,
+            Err(DomainError::PostNotFound { .. }) => {
+                self.post_repository.get_deleted_post(post_id, None).await?;
+                false
+            }
+            Err(e) => return Err(e),
+        };
+        self.post_repository.purge_post(post_id).await?;
+        info!("Post force deleted successfully");
+        if was_published {
+            self.notify(PostEventDto::Deleted { post_id });
+        }
+        Ok(())
+    }
+
+    /// Скрывает пост по жалобе: переносит его в корзину. Доступно модератору и администратору.
+    ///
+    /// Пост, уже находящийся в корзине, остаётся там.
+    #[instrument(skip(self), fields(post_id = %post_id, user_id = %user_id))]
+    pub async fn hide_post(&self, post_id: Uuid, user_id: Uuid, role: Role) -> DomainResult<()> {
+        debug!("Hiding post");
+        if !role.can_moderate() {
+            warn!("Hide post denied");
+            return Err(DomainError::Forbidden {
+                reason: "only moderators can hide posts".to_string(),
+            });
+        }
+
+        let post = match self.post_repository.get_post_by_id(post_id, None).await {
+            Ok(post) => post,
+            Err(DomainError::PostNotFound { .. }) => {
+                self.post_repository.get_deleted_post(post_id, None).await?;
+                info!("Post is already in trash");
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        self.post_repository.delete_post(post_id).await?;
+        info!("Post hidden successfully");
+        if post.status == PostStatus::Published {
+            self.notify(PostEventDto::Deleted { post_id });
+        }
         Ok(())
     }
+
+    /// Собирает новый пост из DTO и проверяет его теги и вложения
+    fn new_post(&self, dto: CreatePostDto) -> DomainResult<(Post, Vec<Uuid>)> {
+        self.limits.check_size(&dto.title, &dto.content)?;
+        let tags = normalize_tags(dto.tags)?;
+        let summary = normalize_summary(dto.summary)?;
+        let attachment_ids = normalize_attachment_ids(dto.attachment_ids)?;
+        let now = chrono::Utc::now();
+        let post = Post {
+            uuid: Uuid::now_v7(),
+            title: dto.title,
+            content_html: self.render_content(&dto.content),
+            content: dto.content,
+            summary,
+            author_id: dto.author_id,
+            author_username: dto.author_username,
+            author_avatar_url: None,
+            tags,
+            category_id: dto.category_id,
+            blog_id: dto.blog_id,
+            attachments: Vec::new(),
+            status: dto.status,
+            like_count: 0,
+            liked_by_me: false,
+            created_at: now,
+            updated_at: now,
+        };
+        Ok((post, attachment_ids))
+    }
+
+    /// Проверяет, что автор может создать ещё `count` постов за текущие сутки (UTC).
+    ///
+    /// Считаются все посты, созданные с начала суток, включая черновики и удалённые,
+    /// поэтому удаление поста квоту не возвращает.
+    async fn check_daily_quota(&self, author_id: Uuid, count: usize) -> DomainResult<()> {
+        let max_posts = self.limits.max_posts_per_day;
+        if max_posts == 0 {
+            return Ok(());
+        }
+        let day_start = chrono::Utc::now()
+            .date_naive()
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is a valid time")
+            .and_utc();
+        let created = self
+            .post_repository
+            .count_posts_created_since(author_id, day_start)
+            .await?;
+        if created + count as u64 > u64::from(max_posts) {
+            warn!(
+                "User {} reached the daily quota of {} posts",
+                author_id, max_posts
+            );
+            return Err(DomainError::QuotaExceeded {
+                reason: format!("at most {max_posts} posts can be created per day"),
+                retry_at: Some(day_start + chrono::Duration::days(1)),
+            });
+        }
+        Ok(())
+    }
+
+    /// Проверяет, что категория поста существует
+    async fn check_category(&self, category_id: Option<Uuid>) -> DomainResult<()> {
+        if let (Some(categories), Some(category_id)) = (&self.categories, category_id)
+            && categories.get_category(category_id).await?.is_none()
+        {
+            warn!("Category {} not found", category_id);
+            return Err(DomainError::CategoryNotFound { category_id });
+        }
+        Ok(())
+    }
+
+    /// Проверяет, что блог поста существует и автор - его владелец
+    async fn check_blog(&self, blog_id: Option<Uuid>, author_id: Uuid) -> DomainResult<()> {
+        let (Some(blogs), Some(blog_id)) = (&self.blogs, blog_id) else {
+            return Ok(());
+        };
+        let Some(blog) = blogs.get_blog(blog_id).await? else {
+            warn!("Blog {} not found", blog_id);
+            return Err(DomainError::BlogNotFound {
+                slug: blog_id.to_string(),
+            });
+        };
+        if blog.owner_id != author_id {
+            warn!("User {} is not the owner of blog {}", author_id, blog.slug);
+            return Err(DomainError::Forbidden {
+                reason: "only the blog owner can publish posts in it".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Рендерит Markdown-содержимое поста в безопасный HTML, если рендеринг включён
+    fn render_content(&self, content: &str) -> Option<String> {
+        self.render_markdown.then(|| render_markdown(content))
+    }
+
+    /// Рассылает событие подписчикам живой ленты.
+    ///
+    /// Отметка `liked_by_me` относится к автору изменения, поэтому в ленту не передаётся.
+    fn notify(&self, event: PostEventDto) {
+        let event = match event {
+            PostEventDto::Created(post) => PostEventDto::Created(PostDto {
+                liked_by_me: false,
+                ..post
+            }),
+            PostEventDto::Updated(post) => PostEventDto::Updated(PostDto {
+                liked_by_me: false,
+                ..post
+            }),
+            deleted => deleted,
+        };
+        // Ошибка отправки означает только отсутствие подписчиков
+        let _ = self.events.send(event);
+    }
+
+    /// Возвращает пост, который пользователь вправе изменять: свой или любой для администратора.
+    ///
+    /// Чужой черновик для обычного пользователя считается несуществующим.
+    async fn modifiable_post(
+        &self,
+        post_id: Uuid,
+        user_id: Uuid,
+        role: Role,
+    ) -> DomainResult<Post> {
+        let post = self
+            .post_repository
+            .get_post_by_id(post_id, Some(user_id))
+            .await?;
+        if role.can_manage_any_post() || post.author_id == user_id {
+            return Ok(post);
+        }
+        if !post.is_visible_to(Some(user_id)) {
+            return Err(DomainError::PostNotFound { post_id });
+        }
+        warn!(
+            "User {} attempted to modify post {} owned by {}",
+            user_id, post_id, post.author_id
+        );
+        Err(DomainError::Forbidden {
+            reason: "only the author or an administrator can modify this post".to_string(),
+        })
+    }
+
+    /// Возвращает пост из корзины, если он принадлежит пользователю или пользователь — администратор.
+    ///
+    /// Корзина видна только её владельцу, поэтому чужой удалённый пост считается несуществующим.
+    async fn trashed_post(&self, post_id: Uuid, user_id: Uuid, role: Role) -> DomainResult<Post> {
+        let post = self
+            .post_repository
+            .get_deleted_post(post_id, Some(user_id))
+            .await?;
+        if role.can_manage_any_post() || post.author_id == user_id {
+            return Ok(post);
+        }
+        Err(DomainError::PostNotFound { post_id })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::blog::BlogApplication;
+    use crate::application::dto::blog::CreateBlogDto;
+    use crate::data::memrepo::InMemoryRepository;
+    use crate::domain::entities::user::User;
+
+    type App = PostApplication<InMemoryRepository, InMemoryRepository>;
+
+    fn app() -> (App, Arc<InMemoryRepository>) {
+        let repo = Arc::new(InMemoryRepository::new());
+        (
+            PostApplication::new(repo.clone(), repo.clone(), false),
+            repo,
+        )
+    }
+
+    async fn user(repo: &InMemoryRepository, username: &str) -> Uuid {
+        let user = User::new(
+            Uuid::now_v7(),
+            username.to_string(),
+            format!("{username}@example.com"),
+            "hash".to_string(),
+            Role::User,
+            chrono::Utc::now(),
+        );
+        repo.create_user(user).await.unwrap().id
+    }
+
+    fn post(author_id: Uuid, title: &str, status: PostStatus) -> CreatePostDto {
+        CreatePostDto {
+            title: title.to_string(),
+            content: "Content".to_string(),
+            author_id,
+            author_username: String::new(),
+            tags: vec![],
+            category_id: None,
+            blog_id: None,
+            summary: None,
+            attachment_ids: vec![],
+            status,
+        }
+    }
+
+    async fn titles(app: &App, viewer: Option<Uuid>) -> Vec<String> {
+        let page = app
+            .get_posts(
+                0,
+                10,
+                None,
+                None,
+                None,
+                viewer,
+                PostSort::default(),
+                PostFields::ALL,
+            )
+            .await
+            .unwrap();
+        let mut titles: Vec<_> = page.posts.into_iter().map(|post| post.title).collect();
+        titles.sort();
+        titles
+    }
+
+    #[tokio::test]
+    async fn test_page_far_past_the_end_is_empty() {
+        let (app, repo) = app();
+        let author = user(&repo, "alice").await;
+        app.create_post(post(author, "Published", PostStatus::Published))
+            .await
+            .unwrap();
+
+        // Смещение `page * page_size` не помещается в u32
+        let page = app
+            .get_posts(
+                50_000_000,
+                100,
+                None,
+                None,
+                None,
+                None,
+                PostSort::default(),
+                PostFields::ALL,
+            )
+            .await
+            .unwrap();
+        assert!(page.posts.is_empty());
+        assert_eq!(page.total, 1);
+        assert!(!page.has_next);
+    }
+
+    #[tokio::test]
+    async fn test_drafts_are_visible_only_to_author() {
+        let (app, repo) = app();
+        let author = user(&repo, "alice").await;
+        let reader = user(&repo, "bob").await;
+        app.create_post(post(author, "Published", PostStatus::Published))
+            .await
+            .unwrap();
+        let draft = app
+            .create_post(post(author, "Draft", PostStatus::Draft))
+            .await
+            .unwrap();
+
+        assert_eq!(titles(&app, Some(author)).await, ["Draft", "Published"]);
+        assert_eq!(titles(&app, Some(reader)).await, ["Published"]);
+        assert_eq!(titles(&app, None).await, ["Published"]);
+
+        assert!(app.get_post_by_id(draft.uuid, Some(author)).await.is_ok());
+        assert!(matches!(
+            app.get_post_by_id(draft.uuid, Some(reader)).await,
+            Err(DomainError::PostNotFound { .. })
+        ));
+        assert!(matches!(
+            app.get_post_by_id(draft.uuid, None).await,
+            Err(DomainError::PostNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_publish_and_unpublish_change_visibility() {
+        let (app, repo) = app();
+        let author = user(&repo, "alice").await;
+        let reader = user(&repo, "bob").await;
+        let draft = app
+            .create_post(post(author, "Draft", PostStatus::Draft))
+            .await
+            .unwrap();
+
+        // Чужой черновик для читателя не существует, поэтому и опубликовать его нельзя
+        assert!(matches!(
+            app.publish_post(draft.uuid, reader, Role::User).await,
+            Err(DomainError::PostNotFound { .. })
+        ));
+
+        let published = app
+            .publish_post(draft.uuid, author, Role::User)
+            .await
+            .unwrap();
+        assert_eq!(published.status, PostStatus::Published);
+        assert!(app.get_post_by_id(draft.uuid, None).await.is_ok());
+        assert!(matches!(
+            app.unpublish_post(draft.uuid, reader, Role::User).await,
+            Err(DomainError::Forbidden { .. })
+        ));
+
+        app.unpublish_post(draft.uuid, author, Role::User)
+            .await
+            .unwrap();
+        assert!(titles(&app, Some(reader)).await.is_empty());
+        assert_eq!(titles(&app, Some(author)).await, ["Draft"]);
+    }
+
+    #[tokio::test]
+    async fn test_publishing_notifies_subscribers() {
+        let (app, repo) = app();
+        let author = user(&repo, "alice").await;
+        let mut events = app.subscribe();
+
+        let draft = app
+            .create_post(post(author, "Draft", PostStatus::Draft))
+            .await
+            .unwrap();
+        app.publish_post(draft.uuid, author, Role::User)
+            .await
+            .unwrap();
+
+        // Создание черновика не видно подписчикам, первое событие - публикация
+        assert!(matches!(
+            events.try_recv(),
+            Ok(PostEventDto::Created(post)) if post.uuid == draft.uuid
+        ));
+        assert!(events.try_recv().is_err());
+    }
+
+    /// Создаёт блог пользователя `owner_id` и возвращает его ID
+    async fn blog(repo: &Arc<InMemoryRepository>, slug: &str, owner_id: Uuid) -> Uuid {
+        BlogApplication::new(repo.clone())
+            .create_blog(CreateBlogDto {
+                slug: slug.to_string(),
+                name: slug.to_string(),
+                owner_id,
+            })
+            .await
+            .unwrap()
+            .id
+    }
+
+    fn blog_post(author_id: Uuid, title: &str, blog_id: Uuid) -> CreatePostDto {
+        CreatePostDto {
+            blog_id: Some(blog_id),
+            ..post(author_id, title, PostStatus::Published)
+        }
+    }
+
+    async fn blog_titles(app: &App, blog: Uuid) -> Vec<String> {
+        let page = app
+            .get_posts(
+                0,
+                10,
+                None,
+                None,
+                Some(blog),
+                None,
+                PostSort::default(),
+                PostFields::ALL,
+            )
+            .await
+            .unwrap();
+        let mut titles: Vec<_> = page.posts.into_iter().map(|post| post.title).collect();
+        titles.sort();
+        titles
+    }
+
+    #[tokio::test]
+    async fn test_blog_lists_only_its_own_posts() {
+        let (app, repo) = app();
+        let app = app.with_blogs(repo.clone());
+        let alice = user(&repo, "alice").await;
+        let bob = user(&repo, "bob").await;
+        let alpha = blog(&repo, "alpha", alice).await;
+        let beta = blog(&repo, "beta", bob).await;
+        app.create_post(blog_post(alice, "Alpha", alpha))
+            .await
+            .unwrap();
+        app.create_post(blog_post(bob, "Beta", beta)).await.unwrap();
+        app.create_post(post(alice, "Main", PostStatus::Published))
+            .await
+            .unwrap();
+
+        assert_eq!(blog_titles(&app, alpha).await, ["Alpha"]);
+        assert_eq!(blog_titles(&app, beta).await, ["Beta"]);
+        assert_eq!(titles(&app, None).await, ["Alpha", "Beta", "Main"]);
+
+        // Обход по курсору тоже не выходит за пределы блога
+        let page = app
+            .get_posts_after(
+                None,
+                10,
+                None,
+                None,
+                Some(beta),
+                None,
+                PostSort::default(),
+                PostFields::ALL,
+            )
+            .await
+            .unwrap();
+        let titles: Vec<_> = page.posts.into_iter().map(|post| post.title).collect();
+        assert_eq!(titles, ["Beta"]);
+        assert_eq!(page.total, 1);
+    }
+
+    #[tokio::test]
+    async fn test_posting_to_another_users_blog_is_rejected() {
+        let (app, repo) = app();
+        let app = app.with_blogs(repo.clone());
+        let alice = user(&repo, "alice").await;
+        let bob = user(&repo, "bob").await;
+        let alpha = blog(&repo, "alpha", alice).await;
+
+        assert!(matches!(
+            app.create_post(blog_post(bob, "Intruder", alpha)).await,
+            Err(DomainError::Forbidden { .. })
+        ));
+        assert!(matches!(
+            app.create_posts(vec![
+                blog_post(bob, "First", alpha),
+                post(bob, "Second", PostStatus::Published),
+            ])
+            .await,
+            Err(DomainError::Forbidden { .. })
+        ));
+        assert!(matches!(
+            app.create_post(blog_post(bob, "Nowhere", Uuid::now_v7()))
+                .await,
+            Err(DomainError::BlogNotFound { .. })
+        ));
+        assert!(blog_titles(&app, alpha).await.is_empty());
+        assert!(titles(&app, None).await.is_empty());
+    }
+
+    fn limited_app(limits: PostLimits) -> (App, Arc<InMemoryRepository>) {
+        let (app, repo) = app();
+        (app.with_limits(limits), repo)
+    }
+
+    #[tokio::test]
+    async fn test_daily_quota_rejects_extra_posts() {
+        let (app, repo) = limited_app(PostLimits {
+            max_posts_per_day: 2,
+            ..PostLimits::default()
+        });
+        let alice = user(&repo, "alice").await;
+        let bob = user(&repo, "bob").await;
+        app.create_post(post(alice, "First", PostStatus::Published))
+            .await
+            .unwrap();
+
+        // Пакет, который не помещается в остаток квоты, отклоняется целиком
+        assert!(matches!(
+            app.create_posts(vec![
+                post(alice, "Second", PostStatus::Published),
+                post(alice, "Third", PostStatus::Draft),
+            ])
+            .await,
+            Err(DomainError::QuotaExceeded { .. })
+        ));
+        app.create_post(post(alice, "Second", PostStatus::Draft))
+            .await
+            .unwrap();
+        match app
+            .create_post(post(alice, "Third", PostStatus::Published))
+            .await
+        {
+            Err(DomainError::QuotaExceeded { retry_at, .. }) => {
+                assert!(retry_at.unwrap() > chrono::Utc::now());
+            }
+            other => panic!("expected QuotaExceeded, got {other:?}"),
+        }
+        assert_eq!(titles(&app, Some(alice)).await, ["First", "Second"]);
+
+        // Квота считается для каждого пользователя отдельно
+        app.create_post(post(bob, "Bob", PostStatus::Published))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_oversized_posts_are_rejected() {
+        let (app, repo) = limited_app(PostLimits {
+            max_posts_per_day: 0,
+            max_content_bytes: 8,
+            max_title_chars: 5,
+        });
+        let alice = user(&repo, "alice").await;
+
+        // Длина заголовка считается в символах, размер содержимого - в байтах
+        assert!(matches!(
+            app.create_post(CreatePostDto {
+                content: "ёёёё".to_string(),
+                ..post(alice, "Привет", PostStatus::Published)
+            })
+            .await,
+            Err(DomainError::QuotaExceeded { .. })
+        ));
+        let existing = app
+            .create_post(CreatePostDto {
+                content: "ёёёё".to_string(),
+                ..post(alice, "Ёжик", PostStatus::Published)
+            })
+            .await
+            .unwrap();
+        assert!(matches!(
+            app.create_post(CreatePostDto {
+                content: "ёёёёё".to_string(),
+                ..post(alice, "Ёжик", PostStatus::Published)
+            })
+            .await,
+            Err(DomainError::QuotaExceeded { retry_at: None, .. })
+        ));
+
+        let update = UpdatePostDto {
+            uuid: existing.uuid,
+            title: "Ёжик".to_string(),
+            content: "content!!".to_string(),
+            tags: vec![],
+            category_id: None,
+            summary: None,
+            attachment_ids: None,
+        };
+        assert!(matches!(
+            app.update_post(update, alice, Role::User).await,
+            Err(DomainError::QuotaExceeded { .. })
+        ));
+        let stored = app
+            .get_post_by_id(existing.uuid, Some(alice))
+            .await
+            .unwrap();
+        assert_eq!(stored.content, "ёёёё");
+        assert_eq!(titles(&app, None).await, ["Ёжик"]);
+    }
 }
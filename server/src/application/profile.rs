@@ -0,0 +1,39 @@
+use crate::application::dto::profile::{UpdateProfileDto, UserProfileDto};
+use crate::domain::entities::errors::DomainResult;
+use crate::domain::entities::user::ProfileUpdate;
+use crate::domain::repositories::repo::UserRepository;
+use std::sync::Arc;
+use tracing::{debug, info, instrument};
+use uuid::Uuid;
+
+pub struct ProfileApplication<Repo: UserRepository + ?Sized> {
+    user_repository: Arc<Repo>,
+}
+
+impl<Repo: UserRepository + ?Sized> ProfileApplication<Repo> {
+    pub fn new(user_repository: Arc<Repo>) -> Self {
+        Self { user_repository }
+    }
+
+    /// Возвращает публичный профиль пользователя.
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    pub async fn get_profile(&self, user_id: Uuid) -> DomainResult<UserProfileDto> {
+        debug!("Fetching user profile");
+        let profile = self.user_repository.get_user_profile(user_id).await?;
+        info!("User profile retrieved successfully");
+        Ok(UserProfileDto::from_entity(profile))
+    }
+
+    /// Заменяет поля профиля пользователя; отсутствующее поле очищается.
+    #[instrument(skip(self, dto), fields(user_id = %dto.user_id))]
+    pub async fn update_profile(&self, dto: UpdateProfileDto) -> DomainResult<UserProfileDto> {
+        debug!("Updating user profile");
+        let update = ProfileUpdate::normalize(dto.display_name, dto.bio, dto.avatar_url)?;
+        let profile = self
+            .user_repository
+            .update_user_profile(dto.user_id, update)
+            .await?;
+        info!("User profile updated successfully");
+        Ok(UserProfileDto::from_entity(profile))
+    }
+}
@@ -0,0 +1,165 @@
+use crate::application::dto::report::{CreateReportDto, ReportDto, ReportPageDto};
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::report::{
+    Report, ReportResolution, ReportStatus, normalize_report_details,
+};
+use crate::domain::entities::user::Role;
+use crate::domain::repositories::post::PostRepository;
+use crate::domain::repositories::report::ReportRepository;
+use std::sync::Arc;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+/// Жалобы на посты: подача жалоб пользователями и их разбор модераторами.
+///
+/// Сам пост при решении [`ReportResolution::HidePost`] скрывает
+/// [`PostApplication::hide_post`](crate::application::post::PostApplication::hide_post)
+/// до закрытия жалоб: если скрыть пост не удалось, жалобы остаются открытыми и решение
+/// можно повторить.
+pub struct ReportApplication<Repo: ReportRepository + PostRepository + ?Sized> {
+    repository: Arc<Repo>,
+}
+
+impl<Repo: ReportRepository + PostRepository + ?Sized> ReportApplication<Repo> {
+    pub fn new(repository: Arc<Repo>) -> Self {
+        Self { repository }
+    }
+
+    /// Подаёт жалобу на пост.
+    ///
+    /// Жаловаться можно на видимый пользователю чужой пост, и только один раз.
+    #[instrument(skip(self, dto), fields(post_id = %dto.post_id, reporter_id = %dto.reporter_id))]
+    pub async fn report_post(&self, dto: CreateReportDto) -> DomainResult<ReportDto> {
+        debug!("Reporting post");
+        let details = normalize_report_details(dto.details)?;
+
+        let post = self
+            .repository
+            .get_post_by_id(dto.post_id, Some(dto.reporter_id))
+            .await?;
+        if !post.is_visible_to(Some(dto.reporter_id)) {
+            debug!("Draft is hidden from the reporter");
+            return Err(DomainError::PostNotFound {
+                post_id: dto.post_id,
+            });
+        }
+        if post.author_id == dto.reporter_id {
+            warn!("User attempted to report their own post");
+            return Err(DomainError::Forbidden {
+                reason: "you cannot report your own post".to_string(),
+            });
+        }
+
+        let report = Report {
+            id: Uuid::now_v7(),
+            post_id: dto.post_id,
+            reporter_id: dto.reporter_id,
+            reason: dto.reason,
+            details,
+            status: ReportStatus::Open,
+            resolved_by: None,
+            resolved_at: None,
+            created_at: chrono::Utc::now(),
+        };
+        self.repository.create_report(&report).await?;
+        info!("Post reported successfully");
+        Ok(ReportDto::from_entity(report))
+    }
+
+    /// Возвращает страницу жалоб от старых к новым; доступно модератору и администратору.
+    #[instrument(skip(self))]
+    pub async fn list_reports(
+        &self,
+        status: Option<ReportStatus>,
+        page: u32,
+        page_size: u32,
+        role: Role,
+    ) -> DomainResult<ReportPageDto> {
+        debug!("Fetching reports");
+        ensure_moderator(role)?;
+
+        let reports = self.repository.get_reports(status, page, page_size).await?;
+        let total = self.repository.count_reports(status).await?;
+        info!("Retrieved {} of {} reports", reports.len(), total);
+        Ok(ReportPageDto {
+            reports: reports.into_iter().map(ReportDto::from_entity).collect(),
+            total,
+            page,
+            page_size,
+        })
+    }
+
+    /// Возвращает открытую жалобу; доступно модератору и администратору.
+    ///
+    /// Проверяется перед скрытием поста, чтобы не скрыть пост по уже разобранной жалобе.
+    #[instrument(skip(self), fields(report_id = %report_id))]
+    pub async fn get_open_report(&self, report_id: Uuid, role: Role) -> DomainResult<ReportDto> {
+        debug!("Fetching open report");
+        ensure_moderator(role)?;
+        let report = self.open_report(report_id).await?;
+        Ok(ReportDto::from_entity(report))
+    }
+
+    /// Закрывает открытую жалобу решением модератора.
+    ///
+    /// Решение [`ReportResolution::HidePost`] закрывает все открытые жалобы на пост.
+    #[instrument(skip(self), fields(report_id = %report_id, moderator_id = %moderator_id))]
+    pub async fn resolve_report(
+        &self,
+        report_id: Uuid,
+        resolution: ReportResolution,
+        moderator_id: Uuid,
+        role: Role,
+    ) -> DomainResult<ReportDto> {
+        debug!("Resolving report");
+        ensure_moderator(role)?;
+
+        let report = self.open_report(report_id).await?;
+        let now = chrono::Utc::now();
+        let status = resolution.status();
+        match resolution {
+            ReportResolution::HidePost => {
+                let resolved = self
+                    .repository
+                    .resolve_post_reports(report.post_id, status, moderator_id, now)
+                    .await?;
+                info!("Post hidden, {} reports resolved", resolved);
+            }
+            ReportResolution::Dismiss => {
+                if !self
+                    .repository
+                    .resolve_report(report_id, status, moderator_id, now)
+                    .await?
+                {
+                    warn!("Report was resolved concurrently");
+                    return Err(DomainError::ReportAlreadyResolved { report_id });
+                }
+                info!("Report dismissed");
+            }
+        }
+
+        let report = self.repository.get_report(report_id).await?;
+        Ok(ReportDto::from_entity(report))
+    }
+
+    /// Загружает жалобу, возвращая `ReportAlreadyResolved`, если она уже разобрана
+    async fn open_report(&self, report_id: Uuid) -> DomainResult<Report> {
+        let report = self.repository.get_report(report_id).await?;
+        if report.status != ReportStatus::Open {
+            warn!("Report is already resolved");
+            return Err(DomainError::ReportAlreadyResolved { report_id });
+        }
+        Ok(report)
+    }
+}
+
+/// Возвращает `Forbidden`, если роль не даёт прав модератора
+fn ensure_moderator(role: Role) -> DomainResult<()> {
+    if !role.can_moderate() {
+        warn!("Moderation access denied");
+        return Err(DomainError::Forbidden {
+            reason: "only moderators can review reports".to_string(),
+        });
+    }
+    Ok(())
+}
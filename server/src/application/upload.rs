@@ -0,0 +1,89 @@
+use crate::application::dto::upload::AttachmentDto;
+use crate::domain::entities::attachment::{
+    Attachment, extension_for_content_type, sanitize_file_name,
+};
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::repositories::post::PostRepository;
+use crate::domain::repositories::storage::BlobStorage;
+use std::sync::Arc;
+use tracing::{debug, info, instrument, warn};
+use uuid::Uuid;
+
+pub struct UploadApplication<Repo: PostRepository + ?Sized> {
+    post_repository: Arc<Repo>,
+    storage: Arc<dyn BlobStorage>,
+    max_size_bytes: usize,
+}
+
+impl<Repo: PostRepository + ?Sized> UploadApplication<Repo> {
+    pub fn new(
+        post_repository: Arc<Repo>,
+        storage: Arc<dyn BlobStorage>,
+        max_size_bytes: usize,
+    ) -> Self {
+        Self {
+            post_repository,
+            storage,
+            max_size_bytes,
+        }
+    }
+
+    /// Максимальный размер загружаемого файла в байтах
+    pub fn max_size_bytes(&self) -> usize {
+        self.max_size_bytes
+    }
+
+    /// Сохраняет файл пользователя `owner_id` в хранилище.
+    ///
+    /// Файл ещё не привязан к посту: его ID передаётся при создании или обновлении поста.
+    #[instrument(skip(self, data), fields(owner_id = %owner_id, size = data.len()))]
+    pub async fn upload(
+        &self,
+        owner_id: Uuid,
+        file_name: &str,
+        content_type: &str,
+        data: Vec<u8>,
+    ) -> DomainResult<AttachmentDto> {
+        debug!("Uploading file");
+
+        if data.is_empty() {
+            return Err(DomainError::InvalidUpload {
+                reason: "file is empty".to_string(),
+            });
+        }
+        if data.len() > self.max_size_bytes {
+            return Err(DomainError::InvalidUpload {
+                reason: format!("file is larger than {} bytes", self.max_size_bytes),
+            });
+        }
+        let extension = extension_for_content_type(content_type)?;
+
+        // Ключ не зависит от имени файла: пользовательский ввод не попадает в путь
+        let id = Uuid::now_v7();
+        let storage_key = format!("{id}.{extension}");
+        let size_bytes = data.len() as i64;
+        let url = self.storage.put(&storage_key, content_type, data).await?;
+
+        let attachment = Attachment {
+            id,
+            owner_id,
+            post_id: None,
+            storage_key,
+            url,
+            file_name: sanitize_file_name(file_name),
+            content_type: content_type.to_string(),
+            size_bytes,
+            created_at: chrono::Utc::now(),
+        };
+        if let Err(e) = self.post_repository.create_attachment(&attachment).await {
+            // Без записи в БД файл недостижим, поэтому удаляем его из хранилища
+            if let Err(delete_error) = self.storage.delete(&attachment.storage_key).await {
+                warn!("Failed to remove orphaned file: {}", delete_error);
+            }
+            return Err(e);
+        }
+
+        info!("File uploaded successfully with id: {}", attachment.id);
+        Ok(AttachmentDto::from_entity(attachment))
+    }
+}
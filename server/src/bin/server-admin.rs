@@ -0,0 +1,286 @@
+//! Служебные команды для операторов сервера: миграции, создание администратора,
+//! смена секрета JWT и проверка базы данных без psql.
+//!
+//! Конфигурация собирается так же, как у сервера: файл, переменные окружения и `--set`.
+
+use std::fs;
+use std::sync::Arc;
+
+use anyhow::{Context, bail};
+use api::rest::{RegisterRequest, Validate};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use clap::{Parser, Subcommand};
+use server::{
+    application::{
+        auth::{AuthApplication, LoginLockout},
+        dto::auth::RegisterDto,
+    },
+    data::{
+        migrations::{self, MigrationStatus},
+        pgrepo::PgUserRepository,
+    },
+    domain::{
+        entities::user::Role, repositories::oauth::OAuthProviders, services::auth::AuthService,
+    },
+    infrastructure::config::{Config, DEFAULT_CONFIG_PATH},
+};
+
+/// Сколько случайных байт в новом `jwt_secret`
+const JWT_SECRET_BYTES: usize = 48;
+
+/// Maintenance commands for the blog server
+///
+/// Settings are read the same way as by the server: configuration file,
+/// environment variables and `--set` flags.
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// Path to the configuration file [default: config.yaml, if present]
+    #[arg(short, long, global = true)]
+    config: Option<String>,
+    /// Override a setting, e.g. `--set db_connection_string=postgres://...`
+    #[arg(long = "set", value_name = "KEY=VALUE", global = true)]
+    overrides: Vec<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Apply pending database migrations
+    Migrate,
+    /// Create a user with the administrator role
+    CreateAdminUser {
+        #[arg(short, long)]
+        username: String,
+        #[arg(short, long)]
+        email: String,
+        /// Password; prompted for without echo if omitted
+        #[arg(short, long)]
+        password: Option<String>,
+    },
+    /// Generate a new jwt_secret, invalidating all issued tokens and unsubscribe links
+    RotateJwtSecret {
+        /// Replace jwt_secret in the configuration file instead of printing the new value
+        #[arg(long)]
+        write: bool,
+    },
+    /// Check the database connection and that all migrations are applied
+    DbCheck,
+}
+
+impl Args {
+    fn load_config(&self) -> anyhow::Result<Config> {
+        Config::load(self.config.as_deref(), &self.overrides)
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    match &args.command {
+        Command::Migrate => migrate(&args.load_config()?).await,
+        Command::CreateAdminUser {
+            username,
+            email,
+            password,
+        } => {
+            create_admin_user(
+                &args.load_config()?,
+                username.clone(),
+                email.clone(),
+                password.clone(),
+            )
+            .await
+        }
+        Command::RotateJwtSecret { write } => rotate_jwt_secret(&args, *write),
+        Command::DbCheck => db_check(&args.load_config()?).await,
+    }
+}
+
+async fn migrate(cfg: &Config) -> anyhow::Result<()> {
+    let pool = migrations::connect(&cfg.db_connection_string)
+        .await
+        .context("Failed to connect to the database")?;
+    let status = migrations::status(&pool).await?;
+    if status.pending.is_empty() {
+        println!("Database is up to date, nothing to apply");
+        return Ok(());
+    }
+
+    for (version, description) in &status.pending {
+        println!("Applying {version}: {description}");
+    }
+    migrations::run(&pool)
+        .await
+        .context("Failed to apply migrations")?;
+    println!("Applied {} migration(s)", status.pending.len());
+    Ok(())
+}
+
+async fn create_admin_user(
+    cfg: &Config,
+    username: String,
+    email: String,
+    password: Option<String>,
+) -> anyhow::Result<()> {
+    let password = match password {
+        Some(password) => password,
+        None => {
+            let password = rpassword::prompt_password("Password: ")?;
+            if rpassword::prompt_password("Repeat password: ")? != password {
+                bail!("Passwords do not match");
+            }
+            password
+        }
+    };
+
+    // Те же ограничения на имя, email и пароль, что и при регистрации через API
+    let request = RegisterRequest {
+        username,
+        password,
+        email,
+    };
+    request
+        .validate()
+        .map_err(|e| anyhow::anyhow!("Invalid user data: {e}"))?;
+
+    let repo = PgUserRepository::new(&cfg.db_connection_string)
+        .await
+        .context("Failed to connect to the database")?;
+    let auth_service = AuthService::new(
+        chrono::Duration::seconds(cfg.jwt_expiration_seconds),
+        cfg.jwt_secret.as_bytes(),
+    );
+    let auth_app = AuthApplication::new(
+        Arc::new(repo),
+        Arc::new(auth_service),
+        LoginLockout {
+            max_failed_attempts: cfg.login_lockout.max_failed_attempts,
+            lock_duration: chrono::Duration::seconds(cfg.login_lockout.lock_seconds),
+        },
+        OAuthProviders::new(),
+    );
+
+    let user = auth_app
+        .create_user_with_role(
+            RegisterDto {
+                username: request.username,
+                password: request.password,
+                email: request.email,
+            },
+            Role::Admin,
+        )
+        .await?;
+    println!("Created administrator {} ({})", user.username, user.id);
+    Ok(())
+}
+
+fn rotate_jwt_secret(args: &Args, write: bool) -> anyhow::Result<()> {
+    let secret = generate_secret();
+    if !write {
+        println!("{secret}");
+        eprintln!(
+            "Set jwt_secret to this value and restart the server. All issued tokens and \
+             digest unsubscribe links will stop working; jwt_keys are not affected."
+        );
+        return Ok(());
+    }
+
+    let path = args.config.as_deref().unwrap_or(DEFAULT_CONFIG_PATH);
+    let original = fs::read_to_string(path).with_context(|| format!("Failed to read {path}"))?;
+    let Some(updated) = replace_jwt_secret(&original, &secret) else {
+        bail!(
+            "{path} has no top-level jwt_secret with a literal value; \
+             set the secret where it is defined (e.g. JWT_SECRET)"
+        );
+    };
+    fs::write(path, updated).with_context(|| format!("Failed to write {path}"))?;
+
+    // Сервер не должен отказаться запускаться с изменённым файлом
+    if let Err(e) = Config::load(Some(path), &args.overrides) {
+        fs::write(path, original).with_context(|| format!("Failed to restore {path}"))?;
+        return Err(e.context(format!("{path} is invalid after the update, restored it")));
+    }
+
+    println!("Replaced jwt_secret in {path}; restart the server to apply it.");
+    println!(
+        "All issued tokens and digest unsubscribe links are no longer valid; \
+         jwt_keys are not affected."
+    );
+    if std::env::var_os("JWT_SECRET").is_some() {
+        eprintln!("Warning: JWT_SECRET is set in the environment and overrides the file");
+    }
+    Ok(())
+}
+
+/// Случайный секрет в hex
+fn generate_secret() -> String {
+    let mut bytes = [0u8; JWT_SECRET_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Заменяет значение `jwt_secret` верхнего уровня; `None`, если его нет
+/// или оно подставляется из переменной окружения.
+fn replace_jwt_secret(text: &str, secret: &str) -> Option<String> {
+    let mut replaced = false;
+    let mut lines: Vec<String> = text
+        .lines()
+        .map(|line| match line.strip_prefix("jwt_secret:") {
+            Some(value) if !replaced && !value.contains("${") => {
+                replaced = true;
+                format!("jwt_secret: \"{secret}\"")
+            }
+            _ => line.to_string(),
+        })
+        .collect();
+    if !replaced {
+        return None;
+    }
+    if text.ends_with('\n') {
+        lines.push(String::new());
+    }
+    Some(lines.join("\n"))
+}
+
+async fn db_check(cfg: &Config) -> anyhow::Result<()> {
+    let pool = migrations::connect(&cfg.db_connection_string)
+        .await
+        .context("Database is unreachable")?;
+    let version = migrations::server_version(&pool).await?;
+    println!("Connected to PostgreSQL {version}");
+
+    let status = migrations::status(&pool).await?;
+    print_status(&status);
+    if !status.is_up_to_date() {
+        bail!("Database schema does not match this server version");
+    }
+    Ok(())
+}
+
+fn print_status(status: &MigrationStatus) {
+    println!(
+        "Migrations: {} applied, {} pending",
+        status.applied,
+        status.pending.len()
+    );
+    for (version, description) in &status.pending {
+        println!("  pending {version}: {description}");
+    }
+    for version in &status.modified {
+        println!("  {version} was changed after it had been applied");
+    }
+    for version in &status.unknown {
+        println!("  {version} is applied but unknown to this server (newer version?)");
+    }
+    if let Some(version) = status.dirty {
+        println!("  {version} failed and left the schema partially applied");
+    }
+    if status.is_up_to_date() {
+        println!("Database is up to date");
+    } else if !status.pending.is_empty() {
+        println!("Run `server-admin migrate` to apply pending migrations");
+    }
+}
@@ -0,0 +1,372 @@
+//! Кэширующая обёртка над [`PostRepository`].
+//!
+//! Кэшируются только анонимные чтения: для них ответ не зависит от пользователя
+//! (черновики скрыты, `liked_by_me` всегда `false`). Любое изменение поста удаляет его
+//! запись и сбрасывает все закэшированные страницы списка.
+
+use std::sync::Arc;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tracing::{debug, error, instrument, warn};
+use uuid::Uuid;
+
+use crate::domain::entities::attachment::Attachment;
+use crate::domain::entities::errors::DomainResult;
+use crate::domain::entities::post::{Post, PostCursor, PostFields, PostSort, PostStatus};
+use crate::domain::repositories::cache::CacheRepository;
+use crate::domain::repositories::post::PostRepository;
+
+/// Сколько первых страниц списка постов кэшируется
+const CACHED_PAGES: u32 = 3;
+/// Ключ счётчика поколений списков: его увеличение делает недоступными все страницы
+const LIST_GENERATION_KEY: &str = "posts:list:generation";
+
+fn post_key(post_id: Uuid) -> String {
+    format!("posts:item:{post_id}")
+}
+
+/// Часть ключа страницы с набором прочитанных частей постов, по флагу на часть
+fn fields_key(fields: PostFields) -> String {
+    [
+        fields.content,
+        fields.tags,
+        fields.attachments,
+        fields.likes,
+    ]
+    .map(|field| if field { '1' } else { '0' })
+    .iter()
+    .collect()
+}
+
+pub struct CachedPostRepository<Posts: PostRepository + ?Sized> {
+    inner: Arc<Posts>,
+    cache: Arc<dyn CacheRepository>,
+}
+
+impl<Posts: PostRepository + ?Sized> CachedPostRepository<Posts> {
+    pub fn new(inner: Arc<Posts>, cache: Arc<dyn CacheRepository>) -> Self {
+        Self { inner, cache }
+    }
+
+    /// Читает значение из кэша; ошибка кэша считается промахом.
+    async fn read<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        match self.cache.get(key).await {
+            Ok(Some(value)) => serde_json::from_str(&value)
+                .inspect_err(|e| warn!("Discarding malformed cache entry {}: {}", key, e))
+                .ok(),
+            Ok(None) => None,
+            Err(e) => {
+                warn!("Cache read failed, falling back to repository: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Записывает значение в кэш; ошибка кэша только логируется.
+    async fn write<T: Serialize>(&self, key: &str, value: &T) {
+        let result = match serde_json::to_string(value) {
+            Ok(value) => self.cache.set(key, &value).await,
+            Err(e) => {
+                warn!("Failed to encode cache entry {}: {}", key, e);
+                return;
+            }
+        };
+        if let Err(e) = result {
+            warn!("Cache write failed: {}", e);
+        }
+    }
+
+    /// Текущее поколение страниц списка; `None`, если кэш недоступен.
+    async fn list_generation(&self) -> Option<i64> {
+        match self.cache.get(LIST_GENERATION_KEY).await {
+            Ok(value) => Some(value.and_then(|value| value.parse().ok()).unwrap_or(0)),
+            Err(e) => {
+                warn!("Cache read failed, falling back to repository: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Удаляет запись поста и сбрасывает страницы списка.
+    ///
+    /// Изменение уже сохранено, поэтому ошибка кэша только логируется: устаревшие
+    /// данные пропадут не позже чем через TTL.
+    async fn invalidate(&self, post_id: Option<Uuid>) {
+        if let Some(post_id) = post_id
+            && let Err(e) = self.cache.delete(&[post_key(post_id)]).await
+        {
+            error!("Failed to invalidate cached post {}: {}", post_id, e);
+        }
+        if let Err(e) = self.cache.increment(LIST_GENERATION_KEY).await {
+            error!("Failed to invalidate cached post lists: {}", e);
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<Posts: PostRepository + ?Sized> PostRepository for CachedPostRepository<Posts> {
+    #[instrument(skip(self))]
+    async fn get_posts(
+        &self,
+        page: u32,
+        page_size: u32,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+        sort: PostSort,
+        fields: PostFields,
+    ) -> DomainResult<Vec<Post>> {
+        if viewer.is_some() || page >= CACHED_PAGES {
+            return self
+                .inner
+                .get_posts(page, page_size, tag, category, blog, viewer, sort, fields)
+                .await;
+        }
+        let Some(generation) = self.list_generation().await else {
+            return self
+                .inner
+                .get_posts(page, page_size, tag, category, blog, viewer, sort, fields)
+                .await;
+        };
+
+        // Тег стоит последним, чтобы любые его символы не смешивались с другими частями ключа
+        let key = format!(
+            "posts:list:{generation}:{page}:{page_size}:{}:{}:{}:{}:{}:{}",
+            sort.field.as_str(),
+            sort.order.as_str(),
+            fields_key(fields),
+            category.map(|id| id.to_string()).unwrap_or_default(),
+            blog.map(|id| id.to_string()).unwrap_or_default(),
+            tag.unwrap_or_default()
+        );
+        if let Some(posts) = self.read(&key).await {
+            debug!("Serving posts page from cache");
+            return Ok(posts);
+        }
+
+        let posts = self
+            .inner
+            .get_posts(page, page_size, tag, category, blog, viewer, sort, fields)
+            .await?;
+        self.write(&key, &posts).await;
+        Ok(posts)
+    }
+
+    async fn get_posts_after(
+        &self,
+        cursor: Option<PostCursor>,
+        limit: u32,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+        fields: PostFields,
+    ) -> DomainResult<Vec<Post>> {
+        // Позиции курсоров у клиентов разные, поэтому такие страницы не кэшируются
+        self.inner
+            .get_posts_after(cursor, limit, tag, category, blog, viewer, fields)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_trending_posts(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<Vec<Post>> {
+        if viewer.is_some() {
+            return self.inner.get_trending_posts(since, limit, viewer).await;
+        }
+        let Some(generation) = self.list_generation().await else {
+            return self.inner.get_trending_posts(since, limit, viewer).await;
+        };
+
+        // Лайк сбрасывает поколение списков, поэтому рейтинг не отстаёт от лайков
+        let key = format!("posts:trending:{generation}:{limit}:{}", since.timestamp());
+        if let Some(posts) = self.read(&key).await {
+            debug!("Serving trending posts from cache");
+            return Ok(posts);
+        }
+
+        let posts = self.inner.get_trending_posts(since, limit, viewer).await?;
+        self.write(&key, &posts).await;
+        Ok(posts)
+    }
+
+    #[instrument(skip(self))]
+    async fn count_posts(
+        &self,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<u64> {
+        if viewer.is_some() {
+            return self.inner.count_posts(tag, category, blog, viewer).await;
+        }
+        let Some(generation) = self.list_generation().await else {
+            return self.inner.count_posts(tag, category, blog, viewer).await;
+        };
+
+        let key = format!(
+            "posts:count:{generation}:{}:{}:{}",
+            category.map(|id| id.to_string()).unwrap_or_default(),
+            blog.map(|id| id.to_string()).unwrap_or_default(),
+            tag.unwrap_or_default()
+        );
+        if let Some(count) = self.read(&key).await {
+            debug!("Serving posts count from cache");
+            return Ok(count);
+        }
+
+        let count = self.inner.count_posts(tag, category, blog, viewer).await?;
+        self.write(&key, &count).await;
+        Ok(count)
+    }
+
+    async fn get_posts_by_author(
+        &self,
+        author_id: Uuid,
+        page: u32,
+        page_size: u32,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<Vec<Post>> {
+        self.inner
+            .get_posts_by_author(author_id, page, page_size, viewer)
+            .await
+    }
+
+    async fn count_posts_by_author(
+        &self,
+        author_id: Uuid,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<u64> {
+        self.inner.count_posts_by_author(author_id, viewer).await
+    }
+
+    async fn count_posts_created_since(
+        &self,
+        author_id: Uuid,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<u64> {
+        self.inner.count_posts_created_since(author_id, since).await
+    }
+
+    async fn get_posts_published_between(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        until: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> DomainResult<Vec<Post>> {
+        self.inner
+            .get_posts_published_between(since, until, limit)
+            .await
+    }
+
+    async fn get_feed_posts(
+        &self,
+        follower_id: Uuid,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<Vec<Post>> {
+        self.inner
+            .get_feed_posts(follower_id, page, page_size)
+            .await
+    }
+
+    async fn count_feed_posts(&self, follower_id: Uuid) -> DomainResult<u64> {
+        self.inner.count_feed_posts(follower_id).await
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn get_post_by_id(&self, post_id: Uuid, viewer: Option<Uuid>) -> DomainResult<Post> {
+        if viewer.is_some() {
+            return self.inner.get_post_by_id(post_id, viewer).await;
+        }
+
+        let key = post_key(post_id);
+        if let Some(post) = self.read(&key).await {
+            debug!("Serving post from cache");
+            return Ok(post);
+        }
+
+        let post = self.inner.get_post_by_id(post_id, viewer).await?;
+        self.write(&key, &post).await;
+        Ok(post)
+    }
+
+    async fn create_post(&self, post: Post, attachment_ids: &[Uuid]) -> DomainResult<Post> {
+        let post = self.inner.create_post(post, attachment_ids).await?;
+        self.invalidate(None).await;
+        Ok(post)
+    }
+
+    async fn create_posts(&self, posts: Vec<(Post, Vec<Uuid>)>) -> DomainResult<Vec<Post>> {
+        let posts = self.inner.create_posts(posts).await?;
+        self.invalidate(None).await;
+        Ok(posts)
+    }
+
+    async fn update_post(&self, post: Post, attachment_ids: Option<&[Uuid]>) -> DomainResult<Post> {
+        let post = self.inner.update_post(post, attachment_ids).await?;
+        self.invalidate(Some(post.uuid)).await;
+        Ok(post)
+    }
+
+    async fn set_post_status(&self, post_id: Uuid, status: PostStatus) -> DomainResult<()> {
+        self.inner.set_post_status(post_id, status).await?;
+        self.invalidate(Some(post_id)).await;
+        Ok(())
+    }
+
+    async fn like_post(&self, post_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        self.inner.like_post(post_id, user_id).await?;
+        self.invalidate(Some(post_id)).await;
+        Ok(())
+    }
+
+    async fn unlike_post(&self, post_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        self.inner.unlike_post(post_id, user_id).await?;
+        self.invalidate(Some(post_id)).await;
+        Ok(())
+    }
+
+    async fn delete_post(&self, post_id: Uuid) -> DomainResult<()> {
+        self.inner.delete_post(post_id).await?;
+        self.invalidate(Some(post_id)).await;
+        Ok(())
+    }
+
+    async fn delete_posts(&self, post_ids: &[Uuid]) -> DomainResult<()> {
+        self.inner.delete_posts(post_ids).await?;
+        let keys: Vec<String> = post_ids.iter().copied().map(post_key).collect();
+        if let Err(e) = self.cache.delete(&keys).await {
+            error!("Failed to invalidate cached posts: {}", e);
+        }
+        self.invalidate(None).await;
+        Ok(())
+    }
+
+    async fn get_deleted_post(&self, post_id: Uuid, viewer: Option<Uuid>) -> DomainResult<Post> {
+        self.inner.get_deleted_post(post_id, viewer).await
+    }
+
+    async fn restore_post(&self, post_id: Uuid) -> DomainResult<()> {
+        self.inner.restore_post(post_id).await?;
+        self.invalidate(Some(post_id)).await;
+        Ok(())
+    }
+
+    async fn purge_post(&self, post_id: Uuid) -> DomainResult<()> {
+        self.inner.purge_post(post_id).await?;
+        self.invalidate(Some(post_id)).await;
+        Ok(())
+    }
+
+    async fn create_attachment(&self, attachment: &Attachment) -> DomainResult<()> {
+        self.inner.create_attachment(attachment).await
+    }
+}
@@ -0,0 +1,1760 @@
+//! Хранилище в памяти процесса.
+//!
+//! Повторяет поведение [`super::pgrepo::PgUserRepository`] без PostgreSQL: подходит для
+//! тестов слоя приложения и обработчиков и для демонстрационного запуска сервера
+//! (`--in-memory`). Данные теряются при остановке процесса.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use chrono::{DateTime, Utc};
+use tracing::{debug, instrument};
+use uuid::Uuid;
+
+use crate::domain::entities::admin::{SystemStats, UserSummary};
+use crate::domain::entities::attachment::Attachment;
+use crate::domain::entities::audit::{AuditEntry, AuditFilter};
+use crate::domain::entities::blog::Blog;
+use crate::domain::entities::category::Category;
+use crate::domain::entities::digest::{DigestFrequency, DigestSubscription};
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::idempotency::{IdempotencyRecord, StoredResponse};
+use crate::domain::entities::post::{
+    Post, PostCursor, PostFields, PostSort, PostSortField, PostStatus, SortOrder,
+};
+use crate::domain::entities::report::{Report, ReportStatus};
+use crate::domain::entities::session::{DeviceInfo, Session};
+use crate::domain::entities::user::{ProfileUpdate, User, UserProfile, UserTotp};
+use crate::domain::repositories::admin::AdminRepository;
+use crate::domain::repositories::audit::AuditRepository;
+use crate::domain::repositories::blog::BlogRepository;
+use crate::domain::repositories::category::CategoryRepository;
+use crate::domain::repositories::digest::DigestRepository;
+use crate::domain::repositories::follow::FollowRepository;
+use crate::domain::repositories::post::PostRepository;
+use crate::domain::repositories::repo::UserRepository;
+use crate::domain::repositories::report::ReportRepository;
+use crate::domain::repositories::unit_of_work::UnitOfWork;
+
+/// Пользователь вместе с полями профиля (строка таблицы `users`)
+struct StoredUser {
+    user: User,
+    display_name: Option<String>,
+    bio: Option<String>,
+    avatar_url: Option<String>,
+    token_version: i32,
+    disabled_at: Option<DateTime<Utc>>,
+}
+
+/// Пост без вычисляемых полей (строка таблицы `posts` и её теги)
+struct StoredPost {
+    id: Uuid,
+    title: String,
+    content: String,
+    content_html: Option<String>,
+    summary: Option<String>,
+    author_id: Uuid,
+    tags: Vec<String>,
+    category_id: Option<Uuid>,
+    blog_id: Option<Uuid>,
+    status: PostStatus,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    /// Время первой публикации; у черновика, ни разу не опубликованного, - `None`
+    published_at: Option<DateTime<Utc>>,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl StoredPost {
+    /// Видит ли пользователь `viewer` неудалённый пост в списках
+    fn is_listed_for(&self, viewer: Option<Uuid>) -> bool {
+        self.deleted_at.is_none()
+            && (self.status == PostStatus::Published || viewer == Some(self.author_id))
+    }
+}
+
+/// Подписка на дайджест (строка таблицы `digest_subscriptions`)
+struct StoredDigestSubscription {
+    frequency: DigestFrequency,
+    last_sent_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+}
+
+/// Ключ идемпотентности (строка таблицы `idempotency_keys`)
+struct StoredIdempotencyKey {
+    request_hash: String,
+    response: Option<StoredResponse>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct State {
+    users: HashMap<Uuid, StoredUser>,
+    posts: HashMap<Uuid, StoredPost>,
+    /// Пара `(post_id, user_id)` -> время лайка
+    likes: HashMap<(Uuid, Uuid), DateTime<Utc>>,
+    attachments: HashMap<Uuid, Attachment>,
+    revoked_tokens: HashMap<String, DateTime<Utc>>,
+    /// Отпечаток токена -> (ID пользователя, срок действия)
+    password_reset_tokens: HashMap<String, (Uuid, DateTime<Utc>)>,
+    /// (scope, ключ) -> запрос
+    idempotency_keys: HashMap<(String, String), StoredIdempotencyKey>,
+    audit_log: Vec<AuditEntry>,
+    /// ID пользователя -> (неудачных попыток подряд, вход заблокирован до)
+    login_failures: HashMap<Uuid, (u32, Option<DateTime<Utc>>)>,
+    user_totp: HashMap<Uuid, UserTotp>,
+    /// Отпечаток токена второго шага входа -> (ID пользователя, срок действия)
+    login_challenges: HashMap<String, (Uuid, DateTime<Utc>)>,
+    /// (провайдер, ID у провайдера) -> ID пользователя
+    oauth_identities: HashMap<(String, String), Uuid>,
+    sessions: HashMap<Uuid, Session>,
+    reports: HashMap<Uuid, Report>,
+    /// Пара `(follower_id, followee_id)` -> время подписки
+    follows: HashMap<(Uuid, Uuid), DateTime<Utc>>,
+    /// ID подписчика -> подписка на дайджест (без email и имени, они берутся из `users`)
+    digest_subscriptions: HashMap<Uuid, StoredDigestSubscription>,
+    categories: HashMap<Uuid, Category>,
+    blogs: HashMap<Uuid, Blog>,
+}
+
+impl State {
+    /// Собирает подписку на дайджест с адресом и именем подписчика
+    fn to_digest_subscription(
+        &self,
+        user_id: Uuid,
+        stored: &StoredDigestSubscription,
+    ) -> Option<DigestSubscription> {
+        let user = &self.users.get(&user_id)?.user;
+        Some(DigestSubscription {
+            user_id,
+            email: user.email.clone(),
+            username: user.username.clone(),
+            frequency: stored.frequency,
+            last_sent_at: stored.last_sent_at,
+            created_at: stored.created_at,
+        })
+    }
+
+    /// Относится ли категория `category_id` к категории `root` или к одной из её подкатегорий
+    fn is_in_category(&self, category_id: Option<Uuid>, root: Uuid) -> bool {
+        let mut current = category_id;
+        while let Some(id) = current {
+            if id == root {
+                return true;
+            }
+            current = self
+                .categories
+                .get(&id)
+                .and_then(|category| category.parent_id);
+        }
+        false
+    }
+
+    /// Подходит ли пост под фильтры списка по тегу, категории и блогу
+    fn matches_filter(
+        &self,
+        post: &StoredPost,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+    ) -> bool {
+        tag.is_none_or(|tag| post.tags.iter().any(|post_tag| post_tag == tag))
+            && category.is_none_or(|category| self.is_in_category(post.category_id, category))
+            && blog.is_none_or(|blog| post.blog_id == Some(blog))
+    }
+
+    /// Собирает доменный пост с автором, вложениями и лайками.
+    fn to_post(&self, post: &StoredPost, viewer: Option<Uuid>) -> Post {
+        let author = self.users.get(&post.author_id);
+        let mut attachments: Vec<&Attachment> = self
+            .attachments
+            .values()
+            .filter(|attachment| attachment.post_id == Some(post.id))
+            .collect();
+        attachments.sort_by_key(|attachment| (attachment.created_at, attachment.id));
+
+        Post {
+            uuid: post.id,
+            title: post.title.clone(),
+            content: post.content.clone(),
+            content_html: post.content_html.clone(),
+            summary: post.summary.clone(),
+            author_id: post.author_id,
+            author_username: author
+                .map(|author| author.user.username.clone())
+                .unwrap_or_default(),
+            author_avatar_url: author.and_then(|author| author.avatar_url.clone()),
+            tags: post.tags.clone(),
+            category_id: post.category_id,
+            blog_id: post.blog_id,
+            attachments: attachments
+                .into_iter()
+                .map(|attachment| attachment.url.clone())
+                .collect(),
+            status: post.status,
+            like_count: self
+                .likes
+                .keys()
+                .filter(|(post_id, _)| *post_id == post.id)
+                .count() as i64,
+            liked_by_me: viewer.is_some_and(|viewer| self.likes.contains_key(&(post.id, viewer))),
+            created_at: post.created_at,
+            updated_at: post.updated_at,
+        }
+    }
+
+    /// Возвращает страницу постов, подходящих под `filter`, в порядке `sort`.
+    fn page_posts(
+        &self,
+        page: u32,
+        page_size: u32,
+        viewer: Option<Uuid>,
+        sort: PostSort,
+        filter: impl Fn(&StoredPost) -> bool,
+    ) -> Vec<Post> {
+        let mut posts: Vec<&StoredPost> = self
+            .posts
+            .values()
+            .filter(|post| post.is_listed_for(viewer) && filter(post))
+            .collect();
+        posts.sort_by(|a, b| compare_posts(a, b, sort));
+        posts
+            .into_iter()
+            .skip(page as usize * page_size as usize)
+            .take(page_size as usize)
+            .map(|post| self.to_post(post, viewer))
+            .collect()
+    }
+
+    /// Опубликован ли пост автором, на которого подписан `follower_id`
+    fn is_in_feed(&self, post: &StoredPost, follower_id: Uuid) -> bool {
+        post.status == PostStatus::Published
+            && self.follows.contains_key(&(follower_id, post.author_id))
+    }
+
+    /// Сохраняет нового пользователя, если его ID, имя и email ещё не заняты.
+    fn insert_user(&mut self, user: User) -> DomainResult<User> {
+        let duplicate = self.users.values().any(|stored| {
+            stored.user.id == user.id
+                || stored.user.username == user.username
+                || stored.user.email == user.email
+        });
+        if duplicate {
+            return Err(DomainError::UserAlreadyExists {
+                username: user.username,
+            });
+        }
+
+        self.users.insert(
+            user.id,
+            StoredUser {
+                user: user.clone(),
+                display_name: None,
+                bio: None,
+                avatar_url: None,
+                token_version: 0,
+                disabled_at: None,
+            },
+        );
+        Ok(user)
+    }
+
+    /// Связывает аккаунт у провайдера с существующим пользователем.
+    ///
+    /// Возвращает `false`, если аккаунт уже был связан (связь не меняется).
+    fn insert_oauth_identity(
+        &mut self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<bool> {
+        if !self.users.contains_key(&user_id) {
+            return Err(foreign_key_violation());
+        }
+        match self
+            .oauth_identities
+            .entry((provider.to_string(), subject.to_string()))
+        {
+            Entry::Occupied(_) => Ok(false),
+            Entry::Vacant(entry) => {
+                entry.insert(user_id);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Сохраняет новый пост и прикрепляет к нему вложения автора.
+    fn insert_post(&mut self, post: Post, attachment_ids: &[Uuid]) -> DomainResult<()> {
+        if !self.users.contains_key(&post.author_id)
+            || post
+                .category_id
+                .is_some_and(|category_id| !self.categories.contains_key(&category_id))
+            || post
+                .blog_id
+                .is_some_and(|blog_id| !self.blogs.contains_key(&blog_id))
+        {
+            return Err(foreign_key_violation());
+        }
+        if self.posts.contains_key(&post.uuid) {
+            return Err(DomainError::RepositoryError(
+                "Duplicate entry: constraint violation".to_string(),
+            ));
+        }
+        self.replace_attachments(post.uuid, post.author_id, attachment_ids)?;
+        self.posts.insert(
+            post.uuid,
+            StoredPost {
+                id: post.uuid,
+                title: post.title,
+                content: post.content,
+                content_html: post.content_html,
+                summary: post.summary,
+                author_id: post.author_id,
+                tags: post.tags,
+                category_id: post.category_id,
+                blog_id: post.blog_id,
+                status: post.status,
+                created_at: post.created_at,
+                updated_at: post.created_at,
+                published_at: (post.status == PostStatus::Published).then_some(post.created_at),
+                deleted_at: None,
+            },
+        );
+        Ok(())
+    }
+
+    /// Заменяет вложения поста.
+    ///
+    /// Прикрепить можно только собственные файлы автора, ещё не привязанные к другому посту.
+    /// Набор проверяется до изменений, поэтому при ошибке вложения остаются прежними.
+    fn replace_attachments(
+        &mut self,
+        post_id: Uuid,
+        owner_id: Uuid,
+        attachment_ids: &[Uuid],
+    ) -> DomainResult<()> {
+        let available = attachment_ids.iter().all(|id| {
+            self.attachments.get(id).is_some_and(|attachment| {
+                attachment.owner_id == owner_id
+                    && attachment
+                        .post_id
+                        .is_none_or(|attached_to| attached_to == post_id)
+            })
+        });
+        if !available {
+            return Err(DomainError::InvalidAttachments {
+                reason: "attachments must be your own uploads not used by another post".to_string(),
+            });
+        }
+
+        for attachment in self.attachments.values_mut() {
+            if attachment_ids.contains(&attachment.id) {
+                attachment.post_id = Some(post_id);
+            } else if attachment.post_id == Some(post_id) {
+                attachment.post_id = None;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Порядок постов как в `PgUserRepository::get_posts`: выбранное поле, затем новые
+/// посты раньше старых, затем ID.
+fn compare_posts(a: &StoredPost, b: &StoredPost, sort: PostSort) -> Ordering {
+    let ordering = match sort.field {
+        PostSortField::CreatedAt => a.created_at.cmp(&b.created_at),
+        PostSortField::UpdatedAt => a.updated_at.cmp(&b.updated_at),
+        PostSortField::Title => a.title.cmp(&b.title),
+    };
+    let ordering = match sort.order {
+        SortOrder::Asc => ordering,
+        SortOrder::Desc => ordering.reverse(),
+    };
+    ordering
+        .then_with(|| b.created_at.cmp(&a.created_at))
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+/// Подходит ли запись журнала аудита под условия выборки
+fn matches_audit_filter(entry: &AuditEntry, filter: &AuditFilter) -> bool {
+    filter
+        .user_id
+        .is_none_or(|user_id| entry.user_id == user_id)
+        && filter.from.is_none_or(|from| entry.created_at >= from)
+        && filter.to.is_none_or(|to| entry.created_at < to)
+}
+
+/// Ошибка нарушения внешнего ключа в формате ошибок PostgreSQL репозитория
+fn foreign_key_violation() -> DomainError {
+    DomainError::RepositoryError("Foreign key constraint violation".to_string())
+}
+
+/// Хранилище всех данных приложения в памяти процесса.
+#[derive(Default)]
+pub struct InMemoryRepository {
+    state: Arc<Mutex<State>>,
+}
+
+impl InMemoryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Блокирует состояние хранилища.
+    ///
+    /// Состояние меняется целиком под блокировкой, поэтому после паники другого
+    /// потока им можно продолжать пользоваться.
+    fn state(&self) -> MutexGuard<'_, State> {
+        lock_state(&self.state)
+    }
+}
+
+fn lock_state(state: &Mutex<State>) -> MutexGuard<'_, State> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Изменение, отложенное единицей работы до `commit`
+enum PendingChange {
+    CreateUser(User),
+    LinkOAuthIdentity {
+        user_id: Uuid,
+        provider: String,
+        subject: String,
+    },
+}
+
+/// Единица работы хранилища в памяти.
+///
+/// Изменения копятся до `commit` и применяются под одной блокировкой состояния;
+/// при ошибке уже применённые изменения откатываются, как откатилась бы транзакция.
+pub struct InMemoryUnitOfWork {
+    state: Arc<Mutex<State>>,
+    pending: Vec<PendingChange>,
+}
+
+#[async_trait::async_trait]
+impl UnitOfWork for InMemoryUnitOfWork {
+    async fn create_user(&mut self, user: User) -> DomainResult<User> {
+        self.pending.push(PendingChange::CreateUser(user.clone()));
+        Ok(user)
+    }
+
+    async fn find_by_email(&mut self, email: &str) -> DomainResult<Option<User>> {
+        Ok(lock_state(&self.state)
+            .users
+            .values()
+            .find(|stored| stored.user.email == email)
+            .map(|stored| stored.user.clone()))
+    }
+
+    async fn exists_by_username(&mut self, username: &str) -> DomainResult<bool> {
+        Ok(lock_state(&self.state)
+            .users
+            .values()
+            .any(|stored| stored.user.username == username || stored.user.email == username))
+    }
+
+    async fn link_oauth_identity(
+        &mut self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<()> {
+        self.pending.push(PendingChange::LinkOAuthIdentity {
+            user_id,
+            provider: provider.to_string(),
+            subject: subject.to_string(),
+        });
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(changes = self.pending.len()))]
+    async fn commit(self: Box<Self>) -> DomainResult<()> {
+        debug!("Committing changes to memory");
+        let mut state = lock_state(&self.state);
+        let mut created_users = Vec::new();
+        let mut linked_identities = Vec::new();
+        for change in self.pending {
+            let applied = match change {
+                PendingChange::CreateUser(user) => state
+                    .insert_user(user)
+                    .map(|user| created_users.push(user.id)),
+                PendingChange::LinkOAuthIdentity {
+                    user_id,
+                    provider,
+                    subject,
+                } => state
+                    .insert_oauth_identity(user_id, &provider, &subject)
+                    .map(|inserted| {
+                        if inserted {
+                            linked_identities.push((provider, subject));
+                        }
+                    }),
+            };
+            if let Err(e) = applied {
+                for key in &linked_identities {
+                    state.oauth_identities.remove(key);
+                }
+                for user_id in &created_users {
+                    state.users.remove(user_id);
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> DomainResult<()> {
+        debug!("Discarding pending changes");
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for InMemoryRepository {
+    async fn begin(&self) -> DomainResult<Box<dyn UnitOfWork>> {
+        Ok(Box::new(InMemoryUnitOfWork {
+            state: self.state.clone(),
+            pending: Vec::new(),
+        }))
+    }
+
+    #[instrument(skip(self, user), fields(username = %user.username, user_id = %user.id))]
+    async fn create_user(&self, user: User) -> DomainResult<User> {
+        debug!("Inserting user into memory");
+        self.state().insert_user(user)
+    }
+
+    #[instrument(skip(self), fields(username = %username))]
+    async fn find_by_username(&self, username: &str) -> DomainResult<Option<User>> {
+        debug!("Querying user by username");
+        Ok(self
+            .state()
+            .users
+            .values()
+            .find(|stored| stored.user.username == username || stored.user.email == username)
+            .map(|stored| stored.user.clone()))
+    }
+
+    #[instrument(skip(self, email))]
+    async fn find_by_email(&self, email: &str) -> DomainResult<Option<User>> {
+        debug!("Querying user by email");
+        Ok(self
+            .state()
+            .users
+            .values()
+            .find(|stored| stored.user.email == email)
+            .map(|stored| stored.user.clone()))
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_id(&self, user_id: Uuid) -> DomainResult<Option<User>> {
+        debug!("Querying user by id");
+        Ok(self
+            .state()
+            .users
+            .get(&user_id)
+            .map(|stored| stored.user.clone()))
+    }
+
+    #[instrument(skip(self), fields(username = %username))]
+    async fn exists_by_username(&self, username: &str) -> DomainResult<bool> {
+        Ok(self.find_by_username(username).await?.is_some())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn get_user_profile(&self, user_id: Uuid) -> DomainResult<UserProfile> {
+        debug!("Querying user profile");
+        let state = self.state();
+        let stored = state
+            .users
+            .get(&user_id)
+            .ok_or(DomainError::ProfileNotFound { user_id })?;
+        Ok(UserProfile {
+            id: stored.user.id,
+            username: stored.user.username.clone(),
+            display_name: stored.display_name.clone(),
+            bio: stored.bio.clone(),
+            avatar_url: stored.avatar_url.clone(),
+            created_at: stored.user.created_at,
+        })
+    }
+
+    #[instrument(skip(self, update), fields(user_id = %user_id))]
+    async fn update_user_profile(
+        &self,
+        user_id: Uuid,
+        update: ProfileUpdate,
+    ) -> DomainResult<UserProfile> {
+        debug!("Updating user profile in memory");
+        {
+            let mut state = self.state();
+            let stored = state
+                .users
+                .get_mut(&user_id)
+                .ok_or(DomainError::ProfileNotFound { user_id })?;
+            stored.display_name = update.display_name;
+            stored.bio = update.bio;
+            stored.avatar_url = update.avatar_url;
+        }
+        self.get_user_profile(user_id).await
+    }
+
+    #[instrument(skip(self, token_hash))]
+    async fn revoke_token(&self, token_hash: &str, expires_at: DateTime<Utc>) -> DomainResult<()> {
+        debug!("Revoking token");
+        self.state()
+            .revoked_tokens
+            .entry(token_hash.to_string())
+            .or_insert(expires_at);
+        Ok(())
+    }
+
+    #[instrument(skip(self, token_hash))]
+    async fn is_token_revoked(&self, token_hash: &str) -> DomainResult<bool> {
+        Ok(self.state().revoked_tokens.contains_key(token_hash))
+    }
+
+    #[instrument(skip(self, token_hash), fields(user_id = %user_id))]
+    async fn create_password_reset_token(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        debug!("Saving password reset token");
+        let mut state = self.state();
+        if !state.users.contains_key(&user_id) {
+            return Err(foreign_key_violation());
+        }
+        state
+            .password_reset_tokens
+            .insert(token_hash.to_string(), (user_id, expires_at));
+        Ok(())
+    }
+
+    #[instrument(skip(self, token_hash))]
+    async fn consume_password_reset_token(&self, token_hash: &str) -> DomainResult<Option<Uuid>> {
+        debug!("Consuming password reset token");
+        let mut state = self.state();
+        // Просроченный токен не удаляется, как и в PostgreSQL репозитории
+        let valid = state
+            .password_reset_tokens
+            .get(token_hash)
+            .is_some_and(|(_, expires_at)| *expires_at > Utc::now());
+        if !valid {
+            return Ok(None);
+        }
+        Ok(state
+            .password_reset_tokens
+            .remove(token_hash)
+            .map(|(user_id, _)| user_id))
+    }
+
+    #[instrument(skip(self, password_hash), fields(user_id = %user_id))]
+    async fn update_password(&self, user_id: Uuid, password_hash: &str) -> DomainResult<()> {
+        debug!("Updating user password");
+        let mut state = self.state();
+        if let Some(stored) = state.users.get_mut(&user_id) {
+            stored.user.password_hash = password_hash.to_string();
+        }
+        state
+            .password_reset_tokens
+            .retain(|_, (owner_id, _)| *owner_id != user_id);
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn get_token_version(&self, user_id: Uuid) -> DomainResult<Option<i32>> {
+        Ok(self
+            .state()
+            .users
+            .get(&user_id)
+            .map(|stored| stored.token_version))
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn increment_token_version(&self, user_id: Uuid) -> DomainResult<()> {
+        debug!("Incrementing token version");
+        if let Some(stored) = self.state().users.get_mut(&user_id) {
+            stored.token_version += 1;
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn is_user_disabled(&self, user_id: Uuid) -> DomainResult<bool> {
+        Ok(self
+            .state()
+            .users
+            .get(&user_id)
+            .is_some_and(|stored| stored.disabled_at.is_some()))
+    }
+
+    #[instrument(skip(self, session), fields(session_id = %session.id))]
+    async fn create_session(&self, session: &Session) -> DomainResult<()> {
+        debug!("Creating session");
+        self.state().sessions.insert(session.id, session.clone());
+        Ok(())
+    }
+
+    #[instrument(skip(self, device), fields(session_id = %session_id))]
+    async fn touch_session(
+        &self,
+        session_id: Uuid,
+        user_id: Uuid,
+        device: &DeviceInfo,
+        expires_at: DateTime<Utc>,
+    ) -> DomainResult<bool> {
+        debug!("Updating session");
+        let mut state = self.state();
+        let Some(session) = state
+            .sessions
+            .get_mut(&session_id)
+            .filter(|session| session.user_id == user_id)
+        else {
+            return Ok(false);
+        };
+        session.user_agent = device.user_agent.clone();
+        session.ip_address = device.ip_address.clone();
+        session.last_used_at = Utc::now();
+        session.expires_at = expires_at;
+        Ok(true)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn is_session_active(&self, session_id: Uuid, user_id: Uuid) -> DomainResult<bool> {
+        Ok(self
+            .state()
+            .sessions
+            .get(&session_id)
+            .is_some_and(|session| session.user_id == user_id && session.expires_at > Utc::now()))
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn list_sessions(&self, user_id: Uuid) -> DomainResult<Vec<Session>> {
+        let now = Utc::now();
+        let mut sessions: Vec<Session> = self
+            .state()
+            .sessions
+            .values()
+            .filter(|session| session.user_id == user_id && session.expires_at > now)
+            .cloned()
+            .collect();
+        sessions.sort_by_key(|session| Reverse(session.last_used_at));
+        Ok(sessions)
+    }
+
+    #[instrument(skip(self), fields(session_id = %session_id))]
+    async fn delete_session(&self, session_id: Uuid, user_id: Uuid) -> DomainResult<bool> {
+        debug!("Deleting session");
+        let mut state = self.state();
+        if state
+            .sessions
+            .get(&session_id)
+            .is_none_or(|session| session.user_id != user_id)
+        {
+            return Ok(false);
+        }
+        state.sessions.remove(&session_id);
+        Ok(true)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn delete_user_sessions(&self, user_id: Uuid) -> DomainResult<()> {
+        debug!("Deleting all sessions");
+        self.state()
+            .sessions
+            .retain(|_, session| session.user_id != user_id);
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn get_login_lock(&self, user_id: Uuid) -> DomainResult<Option<DateTime<Utc>>> {
+        Ok(self
+            .state()
+            .login_failures
+            .get(&user_id)
+            .and_then(|(_, locked_until)| *locked_until))
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn record_failed_login(
+        &self,
+        user_id: Uuid,
+        max_attempts: u32,
+        locked_until: DateTime<Utc>,
+    ) -> DomainResult<bool> {
+        debug!("Recording failed login attempt");
+        let mut state = self.state();
+        let (failed_attempts, lock) = state.login_failures.entry(user_id).or_default();
+        *failed_attempts += 1;
+        let locked = *failed_attempts >= max_attempts;
+        if locked {
+            *failed_attempts = 0;
+            *lock = Some(locked_until);
+        }
+        Ok(locked)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn clear_failed_logins(&self, user_id: Uuid) -> DomainResult<()> {
+        self.state().login_failures.remove(&user_id);
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn get_user_totp(&self, user_id: Uuid) -> DomainResult<Option<UserTotp>> {
+        Ok(self.state().user_totp.get(&user_id).cloned())
+    }
+
+    #[instrument(skip(self, secret), fields(user_id = %user_id))]
+    async fn save_user_totp(&self, user_id: Uuid, secret: &str) -> DomainResult<()> {
+        debug!("Saving TOTP secret");
+        self.state().user_totp.insert(
+            user_id,
+            UserTotp {
+                user_id,
+                secret: secret.to_string(),
+                enabled: false,
+            },
+        );
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn enable_user_totp(&self, user_id: Uuid) -> DomainResult<()> {
+        debug!("Enabling TOTP");
+        if let Some(totp) = self.state().user_totp.get_mut(&user_id) {
+            totp.enabled = true;
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self, token_hash), fields(user_id = %user_id))]
+    async fn create_login_challenge(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        debug!("Saving login challenge");
+        self.state()
+            .login_challenges
+            .insert(token_hash.to_string(), (user_id, expires_at));
+        Ok(())
+    }
+
+    #[instrument(skip(self, token_hash))]
+    async fn find_login_challenge(&self, token_hash: &str) -> DomainResult<Option<Uuid>> {
+        Ok(self
+            .state()
+            .login_challenges
+            .get(token_hash)
+            .filter(|(_, expires_at)| *expires_at > Utc::now())
+            .map(|(user_id, _)| *user_id))
+    }
+
+    #[instrument(skip(self, token_hash))]
+    async fn delete_login_challenge(&self, token_hash: &str) -> DomainResult<()> {
+        let now = Utc::now();
+        self.state()
+            .login_challenges
+            .retain(|hash, (_, expires_at)| hash != token_hash && *expires_at > now);
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn find_by_oauth_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<Option<User>> {
+        debug!("Querying user by OAuth identity");
+        let state = self.state();
+        Ok(state
+            .oauth_identities
+            .get(&(provider.to_string(), subject.to_string()))
+            .and_then(|user_id| state.users.get(user_id))
+            .map(|stored| stored.user.clone()))
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn link_oauth_identity(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<()> {
+        debug!("Linking OAuth identity");
+        self.state()
+            .insert_oauth_identity(user_id, provider, subject)
+            .map(|_| ())
+    }
+
+    #[instrument(skip(self, request_hash))]
+    async fn reserve_idempotency_key(
+        &self,
+        scope: &str,
+        key: &str,
+        request_hash: &str,
+        expired_before: DateTime<Utc>,
+    ) -> DomainResult<Option<IdempotencyRecord>> {
+        debug!("Reserving idempotency key");
+        let mut state = self.state();
+        state
+            .idempotency_keys
+            .retain(|_, stored| stored.created_at >= expired_before);
+
+        let stored = state
+            .idempotency_keys
+            .entry((scope.to_string(), key.to_string()));
+        match stored {
+            Entry::Occupied(entry) => {
+                debug!("Idempotency key already used");
+                Ok(Some(IdempotencyRecord {
+                    request_hash: entry.get().request_hash.clone(),
+                    response: entry.get().response.clone(),
+                }))
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(StoredIdempotencyKey {
+                    request_hash: request_hash.to_string(),
+                    response: None,
+                    created_at: Utc::now(),
+                });
+                debug!("Idempotency key reserved");
+                Ok(None)
+            }
+        }
+    }
+
+    #[instrument(skip(self, response), fields(status = response.status))]
+    async fn complete_idempotency_key(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &StoredResponse,
+    ) -> DomainResult<()> {
+        debug!("Saving idempotent response");
+        if let Some(stored) = self
+            .state()
+            .idempotency_keys
+            .get_mut(&(scope.to_string(), key.to_string()))
+        {
+            stored.response = Some(response.clone());
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn release_idempotency_key(&self, scope: &str, key: &str) -> DomainResult<()> {
+        debug!("Releasing idempotency key");
+        let mut state = self.state();
+        let id = (scope.to_string(), key.to_string());
+        if state
+            .idempotency_keys
+            .get(&id)
+            .is_some_and(|stored| stored.response.is_none())
+        {
+            state.idempotency_keys.remove(&id);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl PostRepository for InMemoryRepository {
+    #[instrument(skip(self))]
+    async fn get_posts(
+        &self,
+        page: u32,
+        page_size: u32,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+        sort: PostSort,
+        _fields: PostFields,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching all posts from memory");
+        let state = self.state();
+        Ok(state.page_posts(page, page_size, viewer, sort, |post| {
+            state.matches_filter(post, tag, category, blog)
+        }))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_posts_after(
+        &self,
+        cursor: Option<PostCursor>,
+        limit: u32,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+        _fields: PostFields,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching posts after cursor from memory");
+        // Курсор хранит время с точностью до микросекунд, как колонка `posts.created_at`
+        let is_after = |post: &StoredPost| {
+            cursor.is_none_or(|cursor| {
+                let created_at = post.created_at.timestamp_micros();
+                let cursor_at = cursor.created_at.timestamp_micros();
+                created_at < cursor_at || (created_at == cursor_at && post.id > cursor.id)
+            })
+        };
+        let state = self.state();
+        Ok(
+            state.page_posts(0, limit, viewer, PostSort::default(), |post| {
+                is_after(post) && state.matches_filter(post, tag, category, blog)
+            }),
+        )
+    }
+
+    #[instrument(skip(self))]
+    async fn get_trending_posts(
+        &self,
+        since: DateTime<Utc>,
+        limit: u32,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching trending posts from memory");
+        let state = self.state();
+        let recent_likes = |post: &StoredPost| {
+            state
+                .likes
+                .iter()
+                .filter(|((post_id, _), liked_at)| *post_id == post.id && **liked_at >= since)
+                .count()
+        };
+        let mut posts: Vec<(usize, &StoredPost)> = state
+            .posts
+            .values()
+            .filter(|post| post.deleted_at.is_none() && post.status == PostStatus::Published)
+            .map(|post| (recent_likes(post), post))
+            .collect();
+        posts.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| compare_posts(a, b, PostSort::default()))
+        });
+        Ok(posts
+            .into_iter()
+            .take(limit as usize)
+            .map(|(_, post)| state.to_post(post, viewer))
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn count_posts(
+        &self,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<u64> {
+        let state = self.state();
+        Ok(state
+            .posts
+            .values()
+            .filter(|post| {
+                post.is_listed_for(viewer) && state.matches_filter(post, tag, category, blog)
+            })
+            .count() as u64)
+    }
+
+    #[instrument(skip(self), fields(author_id = %author_id))]
+    async fn get_posts_by_author(
+        &self,
+        author_id: Uuid,
+        page: u32,
+        page_size: u32,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching author posts from memory");
+        Ok(self
+            .state()
+            .page_posts(page, page_size, viewer, PostSort::default(), |post| {
+                post.author_id == author_id
+            }))
+    }
+
+    #[instrument(skip(self), fields(author_id = %author_id))]
+    async fn count_posts_by_author(
+        &self,
+        author_id: Uuid,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<u64> {
+        Ok(self
+            .state()
+            .posts
+            .values()
+            .filter(|post| post.author_id == author_id && post.is_listed_for(viewer))
+            .count() as u64)
+    }
+
+    async fn count_posts_created_since(
+        &self,
+        author_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> DomainResult<u64> {
+        Ok(self
+            .state()
+            .posts
+            .values()
+            .filter(|post| post.author_id == author_id && post.created_at >= since)
+            .count() as u64)
+    }
+
+    #[instrument(skip(self), fields(follower_id = %follower_id))]
+    async fn get_feed_posts(
+        &self,
+        follower_id: Uuid,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching feed posts from memory");
+        let state = self.state();
+        Ok(state.page_posts(
+            page,
+            page_size,
+            Some(follower_id),
+            PostSort::default(),
+            |post| state.is_in_feed(post, follower_id),
+        ))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_posts_published_between(
+        &self,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+        limit: u32,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching recently published posts from memory");
+        let state = self.state();
+        let mut posts: Vec<(DateTime<Utc>, &StoredPost)> = state
+            .posts
+            .values()
+            .filter(|post| post.deleted_at.is_none() && post.status == PostStatus::Published)
+            .filter_map(|post| Some((post.published_at?, post)))
+            .filter(|(published_at, _)| *published_at > since && *published_at <= until)
+            .collect();
+        posts.sort_by(|(a_published, a), (b_published, b)| {
+            b_published.cmp(a_published).then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(posts
+            .into_iter()
+            .take(limit as usize)
+            .map(|(_, post)| state.to_post(post, None))
+            .collect())
+    }
+
+    #[instrument(skip(self), fields(follower_id = %follower_id))]
+    async fn count_feed_posts(&self, follower_id: Uuid) -> DomainResult<u64> {
+        let state = self.state();
+        Ok(state
+            .posts
+            .values()
+            .filter(|post| post.deleted_at.is_none() && state.is_in_feed(post, follower_id))
+            .count() as u64)
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn get_post_by_id(&self, post_id: Uuid, viewer: Option<Uuid>) -> DomainResult<Post> {
+        let state = self.state();
+        state
+            .posts
+            .get(&post_id)
+            .filter(|post| post.deleted_at.is_none())
+            .map(|post| state.to_post(post, viewer))
+            .ok_or(DomainError::PostNotFound { post_id })
+    }
+
+    #[instrument(skip(self, post), fields(post_id = %post.uuid, title = %post.title))]
+    async fn create_post(&self, post: Post, attachment_ids: &[Uuid]) -> DomainResult<Post> {
+        debug!("Inserting post into memory");
+        let (post_id, author_id) = (post.uuid, post.author_id);
+        self.state().insert_post(post, attachment_ids)?;
+        self.get_post_by_id(post_id, Some(author_id)).await
+    }
+
+    #[instrument(skip(self, posts), fields(count = posts.len()))]
+    async fn create_posts(&self, posts: Vec<(Post, Vec<Uuid>)>) -> DomainResult<Vec<Post>> {
+        debug!("Inserting posts into memory");
+        let mut state = self.state();
+        // При ошибке откатываем уже вставленные посты, как откатилась бы транзакция
+        let attachments = state.attachments.clone();
+        let mut inserted = Vec::with_capacity(posts.len());
+        for (post, attachment_ids) in posts {
+            let (post_id, author_id) = (post.uuid, post.author_id);
+            if let Err(e) = state.insert_post(post, &attachment_ids) {
+                for (post_id, _) in &inserted {
+                    state.posts.remove(post_id);
+                }
+                state.attachments = attachments;
+                return Err(e);
+            }
+            inserted.push((post_id, author_id));
+        }
+        Ok(inserted
+            .into_iter()
+            .map(|(post_id, author_id)| state.to_post(&state.posts[&post_id], Some(author_id)))
+            .collect())
+    }
+
+    #[instrument(skip(self, post), fields(post_id = %post.uuid))]
+    async fn update_post(&self, post: Post, attachment_ids: Option<&[Uuid]>) -> DomainResult<Post> {
+        debug!("Updating post in memory");
+        {
+            let mut state = self.state();
+            if post
+                .category_id
+                .is_some_and(|category_id| !state.categories.contains_key(&category_id))
+            {
+                return Err(foreign_key_violation());
+            }
+            if let Some(attachment_ids) = attachment_ids {
+                state.replace_attachments(post.uuid, post.author_id, attachment_ids)?;
+            }
+            if let Some(stored) = state.posts.get_mut(&post.uuid) {
+                stored.title = post.title;
+                stored.content = post.content;
+                stored.content_html = post.content_html;
+                stored.summary = post.summary;
+                stored.tags = post.tags;
+                stored.category_id = post.category_id;
+                stored.updated_at = Utc::now();
+            }
+        }
+        self.get_post_by_id(post.uuid, Some(post.author_id)).await
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id, status = status.as_str()))]
+    async fn set_post_status(&self, post_id: Uuid, status: PostStatus) -> DomainResult<()> {
+        if let Some(stored) = self.state().posts.get_mut(&post_id) {
+            stored.status = status;
+            if status == PostStatus::Published && stored.published_at.is_none() {
+                stored.published_at = Some(Utc::now());
+            }
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id, user_id = %user_id))]
+    async fn like_post(&self, post_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        let mut state = self.state();
+        if !state.posts.contains_key(&post_id) || !state.users.contains_key(&user_id) {
+            return Err(foreign_key_violation());
+        }
+        state
+            .likes
+            .entry((post_id, user_id))
+            .or_insert_with(Utc::now);
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id, user_id = %user_id))]
+    async fn unlike_post(&self, post_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        self.state().likes.remove(&(post_id, user_id));
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn delete_post(&self, post_id: Uuid) -> DomainResult<()> {
+        debug!("Moving post to trash");
+        if let Some(stored) = self.state().posts.get_mut(&post_id) {
+            stored.deleted_at.get_or_insert_with(Utc::now);
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self, post_ids), fields(count = post_ids.len()))]
+    async fn delete_posts(&self, post_ids: &[Uuid]) -> DomainResult<()> {
+        debug!("Moving posts to trash");
+        let mut state = self.state();
+        for post_id in post_ids {
+            if let Some(stored) = state.posts.get_mut(post_id) {
+                stored.deleted_at.get_or_insert_with(Utc::now);
+            }
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn get_deleted_post(&self, post_id: Uuid, viewer: Option<Uuid>) -> DomainResult<Post> {
+        let state = self.state();
+        state
+            .posts
+            .get(&post_id)
+            .filter(|post| post.deleted_at.is_some())
+            .map(|post| state.to_post(post, viewer))
+            .ok_or(DomainError::PostNotFound { post_id })
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn restore_post(&self, post_id: Uuid) -> DomainResult<()> {
+        debug!("Restoring post from trash");
+        if let Some(stored) = self.state().posts.get_mut(&post_id) {
+            stored.deleted_at = None;
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn purge_post(&self, post_id: Uuid) -> DomainResult<()> {
+        debug!("Purging post");
+        let mut state = self.state();
+        state.posts.remove(&post_id);
+        state
+            .likes
+            .retain(|(liked_post_id, _), _| *liked_post_id != post_id);
+        state.reports.retain(|_, report| report.post_id != post_id);
+        for attachment in state.attachments.values_mut() {
+            if attachment.post_id == Some(post_id) {
+                attachment.post_id = None;
+            }
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self, attachment), fields(attachment_id = %attachment.id, owner_id = %attachment.owner_id))]
+    async fn create_attachment(&self, attachment: &Attachment) -> DomainResult<()> {
+        debug!("Inserting attachment into memory");
+        let mut state = self.state();
+        if !state.users.contains_key(&attachment.owner_id) {
+            return Err(foreign_key_violation());
+        }
+        state.attachments.insert(attachment.id, attachment.clone());
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditRepository for InMemoryRepository {
+    #[instrument(skip(self, entry), fields(user_id = %entry.user_id, action = entry.action.as_str()))]
+    async fn record_audit_entry(&self, entry: &AuditEntry) -> DomainResult<()> {
+        self.state().audit_log.push(entry.clone());
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_audit_entries(
+        &self,
+        filter: &AuditFilter,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<Vec<AuditEntry>> {
+        let state = self.state();
+        let mut entries: Vec<&AuditEntry> = state
+            .audit_log
+            .iter()
+            .filter(|entry| matches_audit_filter(entry, filter))
+            .collect();
+        entries.sort_by_key(|entry| Reverse((entry.created_at, entry.id)));
+        Ok(entries
+            .into_iter()
+            .skip(page as usize * page_size as usize)
+            .take(page_size as usize)
+            .cloned()
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn count_audit_entries(&self, filter: &AuditFilter) -> DomainResult<u64> {
+        Ok(self
+            .state()
+            .audit_log
+            .iter()
+            .filter(|entry| matches_audit_filter(entry, filter))
+            .count() as u64)
+    }
+}
+
+#[async_trait::async_trait]
+impl AdminRepository for InMemoryRepository {
+    #[instrument(skip(self))]
+    async fn list_users(&self, page: u32, page_size: u32) -> DomainResult<Vec<UserSummary>> {
+        let state = self.state();
+        let mut users: Vec<&StoredUser> = state.users.values().collect();
+        users.sort_by_key(|stored| (stored.user.created_at, stored.user.id));
+        Ok(users
+            .into_iter()
+            .skip(page as usize * page_size as usize)
+            .take(page_size as usize)
+            .map(|stored| UserSummary {
+                id: stored.user.id,
+                username: stored.user.username.clone(),
+                email: stored.user.email.clone(),
+                role: stored.user.role,
+                created_at: stored.user.created_at,
+                disabled_at: stored.disabled_at,
+            })
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn count_users(&self) -> DomainResult<u64> {
+        Ok(self.state().users.len() as u64)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn set_user_disabled(
+        &self,
+        user_id: Uuid,
+        disabled_at: Option<DateTime<Utc>>,
+    ) -> DomainResult<bool> {
+        debug!("Changing user disabled state");
+        match self.state().users.get_mut(&user_id) {
+            Some(stored) => {
+                stored.disabled_at = disabled_at;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn get_system_stats(&self, signups_since: DateTime<Utc>) -> DomainResult<SystemStats> {
+        let state = self.state();
+        Ok(SystemStats {
+            users: state.users.len() as u64,
+            posts: state
+                .posts
+                .values()
+                .filter(|post| post.deleted_at.is_none())
+                .count() as u64,
+            recent_signups: state
+                .users
+                .values()
+                .filter(|stored| stored.user.created_at >= signups_since)
+                .count() as u64,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ReportRepository for InMemoryRepository {
+    #[instrument(skip(self, report), fields(report_id = %report.id, post_id = %report.post_id))]
+    async fn create_report(&self, report: &Report) -> DomainResult<()> {
+        debug!("Inserting report into memory");
+        let mut state = self.state();
+        if !state.posts.contains_key(&report.post_id)
+            || !state.users.contains_key(&report.reporter_id)
+        {
+            return Err(foreign_key_violation());
+        }
+        let duplicate = state.reports.values().any(|existing| {
+            existing.post_id == report.post_id && existing.reporter_id == report.reporter_id
+        });
+        if duplicate {
+            return Err(DomainError::ReportAlreadyExists {
+                post_id: report.post_id,
+            });
+        }
+        state.reports.insert(report.id, report.clone());
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(report_id = %report_id))]
+    async fn get_report(&self, report_id: Uuid) -> DomainResult<Report> {
+        self.state()
+            .reports
+            .get(&report_id)
+            .cloned()
+            .ok_or(DomainError::ReportNotFound { report_id })
+    }
+
+    #[instrument(skip(self))]
+    async fn get_reports(
+        &self,
+        status: Option<ReportStatus>,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<Vec<Report>> {
+        let state = self.state();
+        let mut reports: Vec<&Report> = state
+            .reports
+            .values()
+            .filter(|report| status.is_none_or(|status| report.status == status))
+            .collect();
+        reports.sort_by_key(|report| (report.created_at, report.id));
+        Ok(reports
+            .into_iter()
+            .skip(page as usize * page_size as usize)
+            .take(page_size as usize)
+            .cloned()
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn count_reports(&self, status: Option<ReportStatus>) -> DomainResult<u64> {
+        Ok(self
+            .state()
+            .reports
+            .values()
+            .filter(|report| status.is_none_or(|status| report.status == status))
+            .count() as u64)
+    }
+
+    #[instrument(skip(self), fields(report_id = %report_id))]
+    async fn resolve_report(
+        &self,
+        report_id: Uuid,
+        status: ReportStatus,
+        resolved_by: Uuid,
+        resolved_at: DateTime<Utc>,
+    ) -> DomainResult<bool> {
+        debug!("Resolving report");
+        match self.state().reports.get_mut(&report_id) {
+            Some(report) if report.status == ReportStatus::Open => {
+                report.status = status;
+                report.resolved_by = Some(resolved_by);
+                report.resolved_at = Some(resolved_at);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn resolve_post_reports(
+        &self,
+        post_id: Uuid,
+        status: ReportStatus,
+        resolved_by: Uuid,
+        resolved_at: DateTime<Utc>,
+    ) -> DomainResult<u64> {
+        debug!("Resolving post reports");
+        let mut resolved = 0;
+        for report in self.state().reports.values_mut() {
+            if report.post_id == post_id && report.status == ReportStatus::Open {
+                report.status = status;
+                report.resolved_by = Some(resolved_by);
+                report.resolved_at = Some(resolved_at);
+                resolved += 1;
+            }
+        }
+        Ok(resolved)
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn is_post_hidden(&self, post_id: Uuid) -> DomainResult<bool> {
+        Ok(self
+            .state()
+            .reports
+            .values()
+            .any(|report| report.post_id == post_id && report.status == ReportStatus::Hidden))
+    }
+}
+
+#[async_trait::async_trait]
+impl FollowRepository for InMemoryRepository {
+    #[instrument(skip(self), fields(follower_id = %follower_id, followee_id = %followee_id))]
+    async fn follow_user(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<()> {
+        let mut state = self.state();
+        if !state.users.contains_key(&follower_id) || !state.users.contains_key(&followee_id) {
+            return Err(foreign_key_violation());
+        }
+        state
+            .follows
+            .entry((follower_id, followee_id))
+            .or_insert_with(Utc::now);
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(follower_id = %follower_id, followee_id = %followee_id))]
+    async fn unfollow_user(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<()> {
+        self.state().follows.remove(&(follower_id, followee_id));
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(follower_id = %follower_id, followee_id = %followee_id))]
+    async fn is_following(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<bool> {
+        Ok(self
+            .state()
+            .follows
+            .contains_key(&(follower_id, followee_id)))
+    }
+}
+
+#[async_trait::async_trait]
+impl DigestRepository for InMemoryRepository {
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn get_digest_subscription(
+        &self,
+        user_id: Uuid,
+    ) -> DomainResult<Option<DigestSubscription>> {
+        let state = self.state();
+        Ok(state
+            .digest_subscriptions
+            .get(&user_id)
+            .and_then(|stored| state.to_digest_subscription(user_id, stored)))
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn save_digest_subscription(
+        &self,
+        user_id: Uuid,
+        frequency: DigestFrequency,
+        now: DateTime<Utc>,
+    ) -> DomainResult<()> {
+        let mut state = self.state();
+        if !state.users.contains_key(&user_id) {
+            return Err(foreign_key_violation());
+        }
+        state
+            .digest_subscriptions
+            .entry(user_id)
+            .and_modify(|stored| stored.frequency = frequency)
+            .or_insert(StoredDigestSubscription {
+                frequency,
+                last_sent_at: now,
+                created_at: now,
+            });
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn delete_digest_subscription(&self, user_id: Uuid) -> DomainResult<bool> {
+        Ok(self.state().digest_subscriptions.remove(&user_id).is_some())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_due_digest_subscriptions(
+        &self,
+        frequency: DigestFrequency,
+        sent_before: DateTime<Utc>,
+    ) -> DomainResult<Vec<DigestSubscription>> {
+        let state = self.state();
+        let mut subscriptions: Vec<DigestSubscription> = state
+            .digest_subscriptions
+            .iter()
+            .filter(|(user_id, stored)| {
+                stored.frequency == frequency
+                    && stored.last_sent_at <= sent_before
+                    && state
+                        .users
+                        .get(user_id)
+                        .is_some_and(|user| user.disabled_at.is_none())
+            })
+            .filter_map(|(user_id, stored)| state.to_digest_subscription(*user_id, stored))
+            .collect();
+        subscriptions.sort_by_key(|subscription| (subscription.last_sent_at, subscription.user_id));
+        Ok(subscriptions)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn mark_digest_sent(&self, user_id: Uuid, sent_at: DateTime<Utc>) -> DomainResult<()> {
+        if let Some(stored) = self.state().digest_subscriptions.get_mut(&user_id) {
+            stored.last_sent_at = sent_at;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CategoryRepository for InMemoryRepository {
+    #[instrument(skip(self))]
+    async fn list_categories(&self) -> DomainResult<Vec<Category>> {
+        let mut categories: Vec<Category> = self.state().categories.values().cloned().collect();
+        categories.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+        Ok(categories)
+    }
+
+    #[instrument(skip(self), fields(category_id = %category_id))]
+    async fn get_category(&self, category_id: Uuid) -> DomainResult<Option<Category>> {
+        Ok(self.state().categories.get(&category_id).cloned())
+    }
+
+    #[instrument(skip(self, category), fields(category_id = %category.id, name = %category.name))]
+    async fn create_category(&self, category: &Category) -> DomainResult<()> {
+        let mut state = self.state();
+        if category
+            .parent_id
+            .is_some_and(|parent_id| !state.categories.contains_key(&parent_id))
+        {
+            return Err(foreign_key_violation());
+        }
+        let duplicate = state.categories.values().any(|stored| {
+            stored.id == category.id
+                || (stored.parent_id == category.parent_id
+                    && stored.name.to_lowercase() == category.name.to_lowercase())
+        });
+        if duplicate {
+            return Err(DomainError::RepositoryError(
+                "Duplicate entry: constraint violation".to_string(),
+            ));
+        }
+        state.categories.insert(category.id, category.clone());
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl BlogRepository for InMemoryRepository {
+    #[instrument(skip(self))]
+    async fn list_blogs(&self) -> DomainResult<Vec<Blog>> {
+        let mut blogs: Vec<Blog> = self.state().blogs.values().cloned().collect();
+        blogs.sort_by(|a, b| a.slug.cmp(&b.slug));
+        Ok(blogs)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_blog_by_slug(&self, slug: &str) -> DomainResult<Option<Blog>> {
+        Ok(self
+            .state()
+            .blogs
+            .values()
+            .find(|blog| blog.slug == slug)
+            .cloned())
+    }
+
+    #[instrument(skip(self), fields(blog_id = %blog_id))]
+    async fn get_blog(&self, blog_id: Uuid) -> DomainResult<Option<Blog>> {
+        Ok(self.state().blogs.get(&blog_id).cloned())
+    }
+
+    #[instrument(skip(self, blog), fields(blog_id = %blog.id, slug = %blog.slug))]
+    async fn create_blog(&self, blog: &Blog) -> DomainResult<()> {
+        let mut state = self.state();
+        if !state.users.contains_key(&blog.owner_id) {
+            return Err(foreign_key_violation());
+        }
+        let duplicate = state
+            .blogs
+            .values()
+            .any(|stored| stored.id == blog.id || stored.slug == blog.slug);
+        if duplicate {
+            return Err(DomainError::RepositoryError(
+                "Duplicate entry: constraint violation".to_string(),
+            ));
+        }
+        state.blogs.insert(blog.id, blog.clone());
+        Ok(())
+    }
+
+    #[instrument(skip(self, name), fields(blog_id = %blog_id))]
+    async fn update_blog_name(&self, blog_id: Uuid, name: &str) -> DomainResult<()> {
+        if let Some(blog) = self.state().blogs.get_mut(&blog_id) {
+            blog.name = name.to_string();
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(blog_id = %blog_id))]
+    async fn delete_blog(&self, blog_id: Uuid) -> DomainResult<()> {
+        let mut state = self.state();
+        state.blogs.remove(&blog_id);
+        // Как `ON DELETE SET NULL` в PostgreSQL: посты остаются без блога
+        for post in state.posts.values_mut() {
+            if post.blog_id == Some(blog_id) {
+                post.blog_id = None;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,104 @@
+//! Миграции схемы PostgreSQL из `migrations/`, встроенные в бинарник при сборке.
+//!
+//! Применяются тем же способом, что и `sqlx migrate run`, поэтому база, обновлённая
+//! sqlx-cli, продолжает обновляться `server-admin migrate` и наоборот.
+
+use sqlx::PgPool;
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+use sqlx::postgres::PgPoolOptions;
+use tracing::{debug, instrument};
+
+/// Все миграции сервера
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// Состояние миграций базы данных относительно встроенных в сервер.
+#[derive(Debug, Default)]
+pub struct MigrationStatus {
+    /// Сколько миграций применено
+    pub applied: usize,
+    /// Ещё не применённые миграции: версия и описание
+    pub pending: Vec<(i64, String)>,
+    /// Применённые миграции, файлы которых изменились после применения
+    pub modified: Vec<i64>,
+    /// Применённые миграции, которых нет в сервере (база обновлена более новой версией)
+    pub unknown: Vec<i64>,
+    /// Миграция, применение которой прервалось с ошибкой
+    pub dirty: Option<i64>,
+}
+
+impl MigrationStatus {
+    /// Схема совпадает с той, которую ожидает сервер
+    pub fn is_up_to_date(&self) -> bool {
+        self.pending.is_empty()
+            && self.modified.is_empty()
+            && self.unknown.is_empty()
+            && self.dirty.is_none()
+    }
+}
+
+/// Пул из одного соединения для служебных команд
+#[instrument(skip(connection_string))]
+pub async fn connect(connection_string: &str) -> Result<PgPool, sqlx::Error> {
+    debug!("Connecting to database");
+    PgPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(std::time::Duration::from_secs(5))
+        .connect(connection_string)
+        .await
+}
+
+/// Версия сервера PostgreSQL
+#[instrument(skip(pool))]
+pub async fn server_version(pool: &PgPool) -> Result<String, sqlx::Error> {
+    sqlx::query_scalar("SHOW server_version")
+        .fetch_one(pool)
+        .await
+}
+
+/// Применяет все ещё не применённые миграции
+#[instrument(skip(pool))]
+pub async fn run(pool: &PgPool) -> Result<(), MigrateError> {
+    MIGRATOR.run(pool).await
+}
+
+/// Сравнивает применённые миграции со встроенными, ничего не изменяя в базе
+#[instrument(skip(pool))]
+pub async fn status(pool: &PgPool) -> Result<MigrationStatus, MigrateError> {
+    let mut conn = pool.acquire().await?;
+    // Таблицу миграций создаёт первый `migrate`; до него не применено ничего
+    let initialized: bool =
+        sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+            .fetch_one(&mut *conn)
+            .await?;
+    let applied = if initialized {
+        conn.list_applied_migrations().await?
+    } else {
+        Vec::new()
+    };
+    let dirty = if initialized {
+        conn.dirty_version().await?
+    } else {
+        None
+    };
+
+    let mut status = MigrationStatus {
+        applied: applied.len(),
+        dirty,
+        ..MigrationStatus::default()
+    };
+    for migration in MIGRATOR.iter() {
+        match applied.iter().find(|a| a.version == migration.version) {
+            Some(a) if a.checksum != migration.checksum => status.modified.push(migration.version),
+            Some(_) => {}
+            None => status
+                .pending
+                .push((migration.version, migration.description.to_string())),
+        }
+    }
+    status.unknown = applied
+        .iter()
+        .filter(|a| !MIGRATOR.version_exists(a.version))
+        .map(|a| a.version)
+        .collect();
+    Ok(status)
+}
@@ -1,6 +1,128 @@
+pub mod cached;
+pub mod memrepo;
+pub mod migrations;
 pub mod pgrepo;
 
+use sqlx::Postgres;
+use sqlx::error::BoxDynError;
+use sqlx::postgres::{PgTypeInfo, PgValueRef};
+
+use crate::domain::entities::audit::AuditAction;
+use crate::domain::entities::digest::DigestFrequency;
 use crate::domain::entities::errors::DomainError;
+use crate::domain::entities::post::PostStatus;
+use crate::domain::entities::report::{ReportReason, ReportStatus};
+use crate::domain::entities::user::Role;
+
+// Статус хранится в колонке `posts.status` строкой (см. `PostStatus::as_str`)
+impl sqlx::Type<Postgres> for PostStatus {
+    fn type_info() -> PgTypeInfo {
+        <str as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <str as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for PostStatus {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <&str as sqlx::Decode<Postgres>>::decode(value)?;
+        PostStatus::parse(value).ok_or_else(|| format!("Unknown post status: {value}").into())
+    }
+}
+
+// Роль хранится в колонке `users.role` строкой (см. `Role::as_str`)
+impl sqlx::Type<Postgres> for Role {
+    fn type_info() -> PgTypeInfo {
+        <str as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <str as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for Role {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <&str as sqlx::Decode<Postgres>>::decode(value)?;
+        Role::parse(value).ok_or_else(|| format!("Unknown user role: {value}").into())
+    }
+}
+
+// Действие хранится в колонке `audit_log.action` строкой (см. `AuditAction::as_str`)
+impl sqlx::Type<Postgres> for AuditAction {
+    fn type_info() -> PgTypeInfo {
+        <str as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <str as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for AuditAction {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <&str as sqlx::Decode<Postgres>>::decode(value)?;
+        AuditAction::parse(value).ok_or_else(|| format!("Unknown audit action: {value}").into())
+    }
+}
+
+// Частота дайджеста хранится в колонке `digest_subscriptions.frequency` строкой
+// (см. `DigestFrequency::as_str`)
+impl sqlx::Type<Postgres> for DigestFrequency {
+    fn type_info() -> PgTypeInfo {
+        <str as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <str as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for DigestFrequency {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <&str as sqlx::Decode<Postgres>>::decode(value)?;
+        DigestFrequency::parse(value)
+            .ok_or_else(|| format!("Unknown digest frequency: {value}").into())
+    }
+}
+
+// Причина жалобы хранится в колонке `reports.reason` строкой (см. `ReportReason::as_str`)
+impl sqlx::Type<Postgres> for ReportReason {
+    fn type_info() -> PgTypeInfo {
+        <str as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <str as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for ReportReason {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <&str as sqlx::Decode<Postgres>>::decode(value)?;
+        ReportReason::parse(value).ok_or_else(|| format!("Unknown report reason: {value}").into())
+    }
+}
+
+// Состояние жалобы хранится в колонке `reports.status` строкой (см. `ReportStatus::as_str`)
+impl sqlx::Type<Postgres> for ReportStatus {
+    fn type_info() -> PgTypeInfo {
+        <str as sqlx::Type<Postgres>>::type_info()
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <str as sqlx::Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for ReportStatus {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = <&str as sqlx::Decode<Postgres>>::decode(value)?;
+        ReportStatus::parse(value).ok_or_else(|| format!("Unknown report status: {value}").into())
+    }
+}
 
 impl From<sqlx::Error> for DomainError {
     fn from(error: sqlx::Error) -> Self {
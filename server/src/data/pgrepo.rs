@@ -1,9 +1,27 @@
-use crate::domain::entities::errors::DomainResult;
-use crate::domain::entities::post::Post;
-use crate::domain::entities::user::User;
+use crate::domain::entities::admin::{SystemStats, UserSummary};
+use crate::domain::entities::attachment::Attachment;
+use crate::domain::entities::audit::{AuditAction, AuditEntry, AuditFilter};
+use crate::domain::entities::blog::Blog;
+use crate::domain::entities::category::Category;
+use crate::domain::entities::digest::{DigestFrequency, DigestSubscription};
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::idempotency::{IdempotencyRecord, StoredResponse};
+use crate::domain::entities::post::{Post, PostCursor, PostFields, PostSort, PostStatus};
+use crate::domain::entities::report::{Report, ReportReason, ReportStatus};
+use crate::domain::entities::session::{DeviceInfo, Session};
+use crate::domain::entities::user::{ProfileUpdate, Role, User, UserProfile, UserTotp};
+use crate::domain::repositories::admin::AdminRepository;
+use crate::domain::repositories::audit::AuditRepository;
+use crate::domain::repositories::blog::BlogRepository;
+use crate::domain::repositories::category::CategoryRepository;
+use crate::domain::repositories::digest::DigestRepository;
+use crate::domain::repositories::follow::FollowRepository;
+use crate::domain::repositories::post::PostRepository;
 use crate::domain::repositories::repo::UserRepository;
-use sqlx::{PgPool, postgres::PgPoolOptions};
-use tracing::{debug, error, instrument};
+use crate::domain::repositories::report::ReportRepository;
+use crate::domain::repositories::unit_of_work::UnitOfWork;
+use sqlx::{PgExecutor, PgPool, Postgres, Transaction, postgres::PgPoolOptions};
+use tracing::{debug, error, instrument, warn};
 use uuid::Uuid;
 
 #[instrument(skip(connection_string))]
@@ -35,33 +53,304 @@ impl PgUserRepository {
         let pool = create_pool(connection_string).await?;
         Ok(Self { pool })
     }
-}
 
-#[async_trait::async_trait]
-impl UserRepository for PgUserRepository {
-    #[instrument(skip(self, user), fields(username = %user.username, user_id = %user.id))]
-    async fn create_user(&self, user: User) -> DomainResult<User> {
-        debug!("Inserting user into database");
+    /// Ищет пользователя по email через `executor` (пул или транзакцию).
+    async fn select_user_by_email(
+        executor: impl PgExecutor<'_>,
+        email: &str,
+    ) -> DomainResult<Option<User>> {
+        let result = sqlx::query_as!(
+            User,
+            r#"
+            SELECT id, username, email, password_hash, role AS "role: Role", created_at
+            FROM users
+            WHERE email = $1;
+            "#,
+            email
+        )
+        .fetch_optional(executor)
+        .await
+        .map_err(|e| {
+            error!("Database error while finding user: {}", e);
+            e
+        })?;
+
+        Ok(result)
+    }
+
+    /// Проверяет через `executor` (пул или транзакцию), занято ли имя пользователя или email.
+    async fn user_exists(executor: impl PgExecutor<'_>, username: &str) -> DomainResult<bool> {
+        let exists = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM users
+                WHERE username = $1 OR email = $1
+            ) AS "exists!"
+            "#,
+            username
+        )
+        .fetch_one(executor)
+        .await
+        .map_err(|e| {
+            error!("Database error while checking user existence: {}", e);
+            e
+        })?;
 
+        Ok(exists)
+    }
+
+    /// Вставляет пользователя через `executor` (пул или транзакцию).
+    async fn insert_user(executor: impl PgExecutor<'_>, user: &User) -> DomainResult<User> {
         let result = sqlx::query_as!(
             User,
             r#"
-            INSERT INTO users (id, username, email, password_hash, created_at)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, username, email, password_hash, created_at
+            INSERT INTO users (id, username, email, password_hash, role, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, username, email, password_hash, role AS "role: Role", created_at
             "#,
             user.id,
             user.username,
             user.email,
             user.password_hash,
+            user.role.as_str(),
             user.created_at
         )
-        .fetch_one(&self.pool)
+        .fetch_one(executor)
+        .await
+        .map_err(|e| match e {
+            // Параллельная регистрация успела занять имя или email
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+                warn!("User already exists: {}", db_err);
+                DomainError::UserAlreadyExists {
+                    username: user.username.clone(),
+                }
+            }
+            e => {
+                error!("Database error while creating user: {}", e);
+                e.into()
+            }
+        })?;
+
+        Ok(result)
+    }
+
+    /// Связывает аккаунт у провайдера с пользователем через `executor` (пул или транзакцию).
+    async fn insert_oauth_identity(
+        executor: impl PgExecutor<'_>,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO oauth_identities (provider, subject, user_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (provider, subject) DO NOTHING
+            "#,
+            provider,
+            subject,
+            user_id
+        )
+        .execute(executor)
+        .await
+        .map_err(|e| {
+            error!("Database error while linking OAuth identity: {}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    /// Вставляет пост с тегами и вложениями внутри транзакции.
+    async fn insert_post(
+        tx: &mut Transaction<'_, Postgres>,
+        post: &Post,
+        attachment_ids: &[Uuid],
+    ) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO posts (id, title, content, content_html, author_id, status, created_at, updated_at,
+                               published_at, category_id, summary, blog_id)
+            VALUES ($1, $2, $3, $4, $5, $6::varchar, $7::timestamptz, $7,
+                    CASE WHEN $6::varchar = 'published' THEN $7::timestamptz END, $8, $9, $10)
+            "#,
+            post.uuid,
+            post.title,
+            post.content,
+            post.content_html,
+            post.author_id,
+            post.status.as_str(),
+            post.created_at,
+            post.category_id,
+            post.summary,
+            post.blog_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            error!("Database error while creating post: {}", e);
+            e
+        })?;
+
+        Self::replace_tags(tx, post.uuid, &post.tags).await?;
+        Self::replace_attachments(tx, post.uuid, post.author_id, attachment_ids).await
+    }
+
+    /// Заменяет теги поста внутри транзакции.
+    async fn replace_tags(
+        tx: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+        tags: &[String],
+    ) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM post_tags
+            WHERE post_id = $1
+            "#,
+            post_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+            INSERT INTO post_tags (post_id, tag)
+            SELECT $1, UNNEST($2::text[])
+            "#,
+            post_id,
+            tags
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            error!("Database error while saving post tags: {}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    /// Заменяет вложения поста внутри транзакции.
+    ///
+    /// Прикрепить можно только собственные файлы автора, ещё не привязанные к другому посту.
+    async fn replace_attachments(
+        tx: &mut Transaction<'_, Postgres>,
+        post_id: Uuid,
+        owner_id: Uuid,
+        attachment_ids: &[Uuid],
+    ) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE attachments
+            SET post_id = NULL
+            WHERE post_id = $1 AND id <> ALL($2)
+            "#,
+            post_id,
+            attachment_ids
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        let attached = sqlx::query!(
+            r#"
+            UPDATE attachments
+            SET post_id = $1
+            WHERE id = ANY($2) AND owner_id = $3 AND (post_id IS NULL OR post_id = $1)
+            "#,
+            post_id,
+            attachment_ids,
+            owner_id
+        )
+        .execute(&mut **tx)
         .await
         .map_err(|e| {
-            error!("Database error while creating user: {}", e);
+            error!("Database error while attaching files to post: {}", e);
+            e
+        })?
+        .rows_affected();
+
+        if attached != attachment_ids.len() as u64 {
+            return Err(DomainError::InvalidAttachments {
+                reason: "attachments must be your own uploads not used by another post".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Единица работы поверх транзакции PostgreSQL.
+///
+/// Транзакция `sqlx` откатывается при удалении без `commit`.
+pub struct PgUnitOfWork {
+    tx: Transaction<'static, Postgres>,
+}
+
+#[async_trait::async_trait]
+impl UnitOfWork for PgUnitOfWork {
+    #[instrument(skip(self, user), fields(username = %user.username, user_id = %user.id))]
+    async fn create_user(&mut self, user: User) -> DomainResult<User> {
+        debug!("Inserting user in transaction");
+        PgUserRepository::insert_user(&mut *self.tx, &user).await
+    }
+
+    #[instrument(skip(self, email))]
+    async fn find_by_email(&mut self, email: &str) -> DomainResult<Option<User>> {
+        debug!("Querying user by email in transaction");
+        PgUserRepository::select_user_by_email(&mut *self.tx, email).await
+    }
+
+    #[instrument(skip(self), fields(username = %username))]
+    async fn exists_by_username(&mut self, username: &str) -> DomainResult<bool> {
+        debug!("Checking if user exists in transaction");
+        PgUserRepository::user_exists(&mut *self.tx, username).await
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn link_oauth_identity(
+        &mut self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<()> {
+        debug!("Linking OAuth identity in transaction");
+        PgUserRepository::insert_oauth_identity(&mut *self.tx, user_id, provider, subject).await
+    }
+
+    async fn commit(self: Box<Self>) -> DomainResult<()> {
+        debug!("Committing transaction");
+        self.tx.commit().await.map_err(|e| {
+            error!("Database error while committing transaction: {}", e);
+            e
+        })?;
+        Ok(())
+    }
+
+    async fn rollback(self: Box<Self>) -> DomainResult<()> {
+        debug!("Rolling back transaction");
+        self.tx.rollback().await?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRepository for PgUserRepository {
+    #[instrument(skip(self))]
+    async fn begin(&self) -> DomainResult<Box<dyn UnitOfWork>> {
+        debug!("Beginning transaction");
+        let tx = self.pool.begin().await.map_err(|e| {
+            error!("Database error while beginning transaction: {}", e);
             e
         })?;
+        Ok(Box::new(PgUnitOfWork { tx }))
+    }
+
+    #[instrument(skip(self, user), fields(username = %user.username, user_id = %user.id))]
+    async fn create_user(&self, user: User) -> DomainResult<User> {
+        debug!("Inserting user into database");
+
+        let result = Self::insert_user(&self.pool, &user).await?;
 
         debug!("User inserted into database successfully");
         Ok(result)
@@ -74,7 +363,7 @@ impl UserRepository for PgUserRepository {
         let result = sqlx::query_as!(
             User,
             r#"
-            SELECT id, username, email, password_hash, created_at
+            SELECT id, username, email, password_hash, role AS "role: Role", created_at
             FROM users
             WHERE username = $1 OR email = $1;
             "#,
@@ -96,156 +385,2562 @@ impl UserRepository for PgUserRepository {
         Ok(result)
     }
 
-    #[instrument(skip(self), fields(username = %username))]
-    async fn exists_by_username(&self, username: &str) -> DomainResult<bool> {
-        debug!("Checking if user exists");
+    #[instrument(skip(self, email))]
+    async fn find_by_email(&self, email: &str) -> DomainResult<Option<User>> {
+        debug!("Querying user by email");
 
-        let exists = sqlx::query_scalar!(
+        let result = Self::select_user_by_email(&self.pool, email).await?;
+
+        if result.is_some() {
+            debug!("User found in database");
+        } else {
+            debug!("User not found in database");
+        }
+
+        Ok(result)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn find_by_id(&self, user_id: Uuid) -> DomainResult<Option<User>> {
+        debug!("Querying user by id");
+
+        let result = sqlx::query_as!(
+            User,
             r#"
-            SELECT EXISTS (
-                SELECT 1
-                FROM users
-                WHERE username = $1 OR email = $1
-            ) AS "exists!"
+            SELECT id, username, email, password_hash, role AS "role: Role", created_at
+            FROM users
+            WHERE id = $1;
             "#,
-            username
+            user_id
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await
         .map_err(|e| {
-            error!("Database error while checking user existence: {}", e);
+            error!("Database error while finding user: {}", e);
             e
         })?;
 
+        Ok(result)
+    }
+
+    #[instrument(skip(self), fields(username = %username))]
+    async fn exists_by_username(&self, username: &str) -> DomainResult<bool> {
+        debug!("Checking if user exists");
+
+        let exists = Self::user_exists(&self.pool, username).await?;
+
         debug!("User existence check result: {}", exists);
         Ok(exists)
     }
 
-    #[instrument(skip(self))]
-    async fn get_posts(&self, page: u32, page_size: u32) -> DomainResult<Vec<Post>> {
-        debug!("Fetching all posts from database");
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn get_user_profile(&self, user_id: Uuid) -> DomainResult<UserProfile> {
+        debug!("Querying user profile");
 
-        let posts = sqlx::query_as!(
-            Post,
+        let profile = sqlx::query_as!(
+            UserProfile,
             r#"
-            SELECT id AS uuid, title, content, author_id, created_at, updated_at
-            FROM posts
-            ORDER BY created_at DESC
-            LIMIT $1 OFFSET $2
+            SELECT id, username, display_name, bio, avatar_url, created_at
+            FROM users
+            WHERE id = $1
             "#,
-            page_size as i64,
-            (page * page_size) as i64
+            user_id
         )
-        .fetch_all(&self.pool)
+        .fetch_optional(&self.pool)
         .await
         .map_err(|e| {
-            error!("Database error while fetching posts: {}", e);
+            error!("Database error while fetching user profile: {}", e);
             e
-        })?;
+        })?
+        .ok_or(DomainError::ProfileNotFound { user_id })?;
 
-        debug!("Fetched {} posts from database", posts.len());
-        Ok(posts)
+        debug!("User profile found in database");
+        Ok(profile)
     }
 
-    #[instrument(skip(self), fields(post_id = %post_id))]
-    async fn get_post_by_id(&self, post_id: Uuid) -> DomainResult<Post> {
-        debug!("Fetching post by id from database");
+    #[instrument(skip(self, update), fields(user_id = %user_id))]
+    async fn update_user_profile(
+        &self,
+        user_id: Uuid,
+        update: ProfileUpdate,
+    ) -> DomainResult<UserProfile> {
+        debug!("Updating user profile in database");
 
-        let post = sqlx::query_as!(
-            Post,
+        let profile = sqlx::query_as!(
+            UserProfile,
             r#"
-            SELECT id AS uuid, title, content, author_id, created_at, updated_at
-            FROM posts
-            WHERE id = $1
+            UPDATE users
+            SET display_name = $1, bio = $2, avatar_url = $3
+            WHERE id = $4
+            RETURNING id, username, display_name, bio, avatar_url, created_at
             "#,
-            post_id
+            update.display_name,
+            update.bio,
+            update.avatar_url,
+            user_id
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await
         .map_err(|e| {
-            error!("Database error while fetching post: {}", e);
+            error!("Database error while updating user profile: {}", e);
             e
-        })?;
+        })?
+        .ok_or(DomainError::ProfileNotFound { user_id })?;
 
-        debug!("Post fetched from database successfully");
-        Ok(post)
+        debug!("User profile updated in database successfully");
+        Ok(profile)
     }
 
-    #[instrument(skip(self, post), fields(post_id = %post.uuid, title = %post.title))]
-    async fn create_post(&self, post: Post) -> DomainResult<Post> {
-        debug!("Inserting post into database");
+    #[instrument(skip(self, token_hash))]
+    async fn revoke_token(
+        &self,
+        token_hash: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<()> {
+        debug!("Revoking token");
 
-        let result = sqlx::query_as!(
-            Post,
+        sqlx::query!(
             r#"
-            INSERT INTO posts (id, title, content, author_id, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $5)
-            RETURNING id AS uuid, title, content, author_id, created_at, updated_at
+            INSERT INTO revoked_tokens (token_hash, expires_at)
+            VALUES ($1, $2)
+            ON CONFLICT (token_hash) DO NOTHING
             "#,
-            post.uuid,
-            post.title,
-            post.content,
-            post.author_id,
-            post.created_at
+            token_hash,
+            expires_at
         )
-        .fetch_one(&self.pool)
+        .execute(&self.pool)
         .await
         .map_err(|e| {
-            error!("Database error while creating post: {}", e);
+            error!("Database error while revoking token: {}", e);
             e
         })?;
 
-        debug!("Post inserted into database successfully");
-        Ok(result)
+        debug!("Token revoked successfully");
+        Ok(())
     }
 
-    #[instrument(skip(self, post), fields(post_id = %post.uuid))]
-    async fn update_post(&self, post: Post) -> DomainResult<Post> {
-        debug!("Updating post in database");
+    #[instrument(skip(self, token_hash))]
+    async fn is_token_revoked(&self, token_hash: &str) -> DomainResult<bool> {
+        debug!("Checking if token is revoked");
 
-        let result = sqlx::query_as!(
-            Post,
+        let revoked = sqlx::query_scalar!(
             r#"
-            UPDATE posts
-            SET title = $1, content = $2, updated_at = $3
-            WHERE id = $4
-            RETURNING id AS uuid, title, content, author_id, created_at, updated_at
+            SELECT EXISTS (
+                SELECT 1
+                FROM revoked_tokens
+                WHERE token_hash = $1
+            ) AS "revoked!"
             "#,
-            post.title,
-            post.content,
-            chrono::Utc::now(),
-            post.uuid
+            token_hash
         )
         .fetch_one(&self.pool)
         .await
         .map_err(|e| {
-            error!("Database error while updating post: {}", e);
+            error!("Database error while checking token revocation: {}", e);
             e
         })?;
 
-        debug!("Post updated in database successfully");
-        Ok(result)
+        debug!("Token revocation check result: {}", revoked);
+        Ok(revoked)
     }
 
-    #[instrument(skip(self), fields(post_id = %post_id))]
-    async fn delete_post(&self, post_id: Uuid) -> DomainResult<()> {
-        debug!("Deleting post from database");
+    #[instrument(skip(self, token_hash), fields(user_id = %user_id))]
+    async fn create_password_reset_token(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<()> {
+        debug!("Saving password reset token");
 
         sqlx::query!(
             r#"
-            DELETE FROM posts
-            WHERE id = $1
+            INSERT INTO password_reset_tokens (token_hash, user_id, expires_at)
+            VALUES ($1, $2, $3)
             "#,
-            post_id
+            token_hash,
+            user_id,
+            expires_at
         )
         .execute(&self.pool)
         .await
         .map_err(|e| {
-            error!("Database error while deleting post: {}", e);
+            error!("Database error while saving password reset token: {}", e);
+            e
+        })?;
+
+        debug!("Password reset token saved successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self, token_hash))]
+    async fn consume_password_reset_token(&self, token_hash: &str) -> DomainResult<Option<Uuid>> {
+        debug!("Consuming password reset token");
+
+        let user_id = sqlx::query_scalar!(
+            r#"
+            DELETE FROM password_reset_tokens
+            WHERE token_hash = $1 AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while consuming password reset token: {}", e);
             e
         })?;
 
-        debug!("Post deleted from database successfully");
+        debug!("Password reset token found: {}", user_id.is_some());
+        Ok(user_id)
+    }
+
+    #[instrument(skip(self, password_hash), fields(user_id = %user_id))]
+    async fn update_password(&self, user_id: Uuid, password_hash: &str) -> DomainResult<()> {
+        debug!("Updating user password");
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET password_hash = $1
+            WHERE id = $2
+            "#,
+            password_hash,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Database error while updating password: {}", e);
+            e
+        })?;
+
+        sqlx::query!(
+            r#"
+            DELETE FROM password_reset_tokens
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        debug!("User password updated successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn get_token_version(&self, user_id: Uuid) -> DomainResult<Option<i32>> {
+        let version = sqlx::query_scalar!(
+            r#"
+            SELECT token_version
+            FROM users
+            WHERE id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while reading token version: {}", e);
+            e
+        })?;
+
+        Ok(version)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn increment_token_version(&self, user_id: Uuid) -> DomainResult<()> {
+        debug!("Incrementing token version");
+
+        sqlx::query!(
+            r#"
+            UPDATE users
+            SET token_version = token_version + 1
+            WHERE id = $1
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while incrementing token version: {}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn is_user_disabled(&self, user_id: Uuid) -> DomainResult<bool> {
+        let disabled = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM users
+                WHERE id = $1 AND disabled_at IS NOT NULL
+            ) AS "exists!"
+            "#,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while checking if user is disabled: {}", e);
+            e
+        })?;
+
+        Ok(disabled)
+    }
+
+    #[instrument(skip(self, session), fields(session_id = %session.id))]
+    async fn create_session(&self, session: &Session) -> DomainResult<()> {
+        debug!("Creating session");
+
+        sqlx::query!(
+            r#"
+            INSERT INTO sessions (id, user_id, user_agent, ip_address, created_at, last_used_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            session.id,
+            session.user_id,
+            session.user_agent,
+            session.ip_address,
+            session.created_at,
+            session.last_used_at,
+            session.expires_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while creating session: {}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, device), fields(session_id = %session_id))]
+    async fn touch_session(
+        &self,
+        session_id: Uuid,
+        user_id: Uuid,
+        device: &DeviceInfo,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<bool> {
+        debug!("Updating session");
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE sessions
+            SET user_agent = $3, ip_address = $4, last_used_at = NOW(), expires_at = $5
+            WHERE id = $1 AND user_id = $2
+            "#,
+            session_id,
+            user_id,
+            device.user_agent,
+            device.ip_address,
+            expires_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while updating session: {}", e);
+            e
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn is_session_active(&self, session_id: Uuid, user_id: Uuid) -> DomainResult<bool> {
+        let active = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM sessions
+                WHERE id = $1 AND user_id = $2 AND expires_at > NOW()
+            ) AS "exists!"
+            "#,
+            session_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while checking session: {}", e);
+            e
+        })?;
+
+        Ok(active)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn list_sessions(&self, user_id: Uuid) -> DomainResult<Vec<Session>> {
+        let sessions = sqlx::query_as!(
+            Session,
+            r#"
+            SELECT id, user_id, user_agent, ip_address, created_at, last_used_at, expires_at
+            FROM sessions
+            WHERE user_id = $1 AND expires_at > NOW()
+            ORDER BY last_used_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while listing sessions: {}", e);
+            e
+        })?;
+
+        Ok(sessions)
+    }
+
+    #[instrument(skip(self), fields(session_id = %session_id))]
+    async fn delete_session(&self, session_id: Uuid, user_id: Uuid) -> DomainResult<bool> {
+        debug!("Deleting session");
+
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM sessions
+            WHERE id = $1 AND user_id = $2
+            "#,
+            session_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while deleting session: {}", e);
+            e
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn delete_user_sessions(&self, user_id: Uuid) -> DomainResult<()> {
+        debug!("Deleting all sessions");
+
+        sqlx::query!(
+            r#"
+            DELETE FROM sessions
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while deleting sessions: {}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn get_login_lock(
+        &self,
+        user_id: Uuid,
+    ) -> DomainResult<Option<chrono::DateTime<chrono::Utc>>> {
+        let locked_until = sqlx::query_scalar!(
+            r#"
+            SELECT locked_until
+            FROM login_failures
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while reading login lock: {}", e);
+            e
+        })?;
+
+        Ok(locked_until.flatten())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn record_failed_login(
+        &self,
+        user_id: Uuid,
+        max_attempts: u32,
+        locked_until: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<bool> {
+        debug!("Recording failed login attempt");
+
+        let mut tx = self.pool.begin().await?;
+
+        let failed_attempts = sqlx::query_scalar!(
+            r#"
+            INSERT INTO login_failures (user_id, failed_attempts)
+            VALUES ($1, 1)
+            ON CONFLICT (user_id) DO UPDATE
+            SET failed_attempts = login_failures.failed_attempts + 1
+            RETURNING failed_attempts
+            "#,
+            user_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Database error while recording failed login: {}", e);
+            e
+        })?;
+
+        let locked = failed_attempts as i64 >= max_attempts as i64;
+        if locked {
+            sqlx::query!(
+                r#"
+                UPDATE login_failures
+                SET failed_attempts = 0, locked_until = $2
+                WHERE user_id = $1
+                "#,
+                user_id,
+                locked_until
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        debug!(
+            "Failed login attempts: {}, locked: {}",
+            failed_attempts, locked
+        );
+        Ok(locked)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn clear_failed_logins(&self, user_id: Uuid) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM login_failures
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while clearing failed logins: {}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn get_user_totp(&self, user_id: Uuid) -> DomainResult<Option<UserTotp>> {
+        let totp = sqlx::query_as!(
+            UserTotp,
+            r#"
+            SELECT user_id, secret, enabled
+            FROM user_totp
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while reading TOTP secret: {}", e);
+            e
+        })?;
+
+        Ok(totp)
+    }
+
+    #[instrument(skip(self, secret), fields(user_id = %user_id))]
+    async fn save_user_totp(&self, user_id: Uuid, secret: &str) -> DomainResult<()> {
+        debug!("Saving TOTP secret");
+
+        sqlx::query!(
+            r#"
+            INSERT INTO user_totp (user_id, secret, enabled)
+            VALUES ($1, $2, FALSE)
+            ON CONFLICT (user_id) DO UPDATE
+            SET secret = EXCLUDED.secret, enabled = FALSE, created_at = NOW()
+            "#,
+            user_id,
+            secret
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while saving TOTP secret: {}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn enable_user_totp(&self, user_id: Uuid) -> DomainResult<()> {
+        debug!("Enabling TOTP");
+
+        sqlx::query!(
+            r#"
+            UPDATE user_totp
+            SET enabled = TRUE
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while enabling TOTP: {}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, token_hash), fields(user_id = %user_id))]
+    async fn create_login_challenge(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<()> {
+        debug!("Saving login challenge");
+
+        sqlx::query!(
+            r#"
+            INSERT INTO login_challenges (token_hash, user_id, expires_at)
+            VALUES ($1, $2, $3)
+            "#,
+            token_hash,
+            user_id,
+            expires_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while saving login challenge: {}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self, token_hash))]
+    async fn find_login_challenge(&self, token_hash: &str) -> DomainResult<Option<Uuid>> {
+        let user_id = sqlx::query_scalar!(
+            r#"
+            SELECT user_id
+            FROM login_challenges
+            WHERE token_hash = $1 AND expires_at > NOW()
+            "#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while reading login challenge: {}", e);
+            e
+        })?;
+
+        Ok(user_id)
+    }
+
+    #[instrument(skip(self, token_hash))]
+    async fn delete_login_challenge(&self, token_hash: &str) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            DELETE FROM login_challenges
+            WHERE token_hash = $1 OR expires_at <= NOW()
+            "#,
+            token_hash
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while deleting login challenge: {}", e);
+            e
+        })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn find_by_oauth_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<Option<User>> {
+        debug!("Querying user by OAuth identity");
+
+        let result = sqlx::query_as!(
+            User,
+            r#"
+            SELECT u.id, u.username, u.email, u.password_hash, u.role AS "role: Role", u.created_at
+            FROM oauth_identities oi
+            JOIN users u ON u.id = oi.user_id
+            WHERE oi.provider = $1 AND oi.subject = $2
+            "#,
+            provider,
+            subject
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while finding OAuth identity: {}", e);
+            e
+        })?;
+
+        Ok(result)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn link_oauth_identity(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<()> {
+        debug!("Linking OAuth identity");
+        Self::insert_oauth_identity(&self.pool, user_id, provider, subject).await
+    }
+
+    #[instrument(skip(self, request_hash))]
+    async fn reserve_idempotency_key(
+        &self,
+        scope: &str,
+        key: &str,
+        request_hash: &str,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<Option<IdempotencyRecord>> {
+        debug!("Reserving idempotency key");
+
+        sqlx::query!(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE created_at < $1
+            "#,
+            expired_before
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(
+                "Database error while deleting expired idempotency keys: {}",
+                e
+            );
+            e
+        })?;
+
+        let reserved = sqlx::query!(
+            r#"
+            INSERT INTO idempotency_keys (scope, idempotency_key, request_hash)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (scope, idempotency_key) DO NOTHING
+            "#,
+            scope,
+            key,
+            request_hash
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while reserving idempotency key: {}", e);
+            e
+        })?
+        .rows_affected()
+            > 0;
+
+        if reserved {
+            debug!("Idempotency key reserved");
+            return Ok(None);
+        }
+
+        let row = sqlx::query!(
+            r#"
+            SELECT request_hash, response_status, response_body
+            FROM idempotency_keys
+            WHERE scope = $1 AND idempotency_key = $2
+            "#,
+            scope,
+            key
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while reading idempotency key: {}", e);
+            e
+        })?;
+
+        let response = match (row.response_status, row.response_body) {
+            (Some(status), Some(body)) => Some(StoredResponse {
+                status: status as u16,
+                body,
+            }),
+            _ => None,
+        };
+
+        debug!("Idempotency key already used");
+        Ok(Some(IdempotencyRecord {
+            request_hash: row.request_hash,
+            response,
+        }))
+    }
+
+    #[instrument(skip(self, response), fields(status = response.status))]
+    async fn complete_idempotency_key(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &StoredResponse,
+    ) -> DomainResult<()> {
+        debug!("Saving idempotent response");
+
+        sqlx::query!(
+            r#"
+            UPDATE idempotency_keys
+            SET response_status = $3, response_body = $4
+            WHERE scope = $1 AND idempotency_key = $2
+            "#,
+            scope,
+            key,
+            response.status as i16,
+            response.body
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while saving idempotent response: {}", e);
+            e
+        })?;
+
+        debug!("Idempotent response saved successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn release_idempotency_key(&self, scope: &str, key: &str) -> DomainResult<()> {
+        debug!("Releasing idempotency key");
+
+        sqlx::query!(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE scope = $1 AND idempotency_key = $2 AND response_status IS NULL
+            "#,
+            scope,
+            key
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while releasing idempotency key: {}", e);
+            e
+        })?;
+
+        debug!("Idempotency key released");
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl PostRepository for PgUserRepository {
+    #[instrument(skip(self))]
+    async fn get_posts(
+        &self,
+        page: u32,
+        page_size: u32,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+        sort: PostSort,
+        fields: PostFields,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching all posts from database");
+
+        // Колонка сортировки не подставляется в текст запроса: запрос сравнивает
+        // параметры только с известными значениями, остальные дают порядок по умолчанию.
+        // Подзапросы частей, не выбранных в `fields`, под CASE не выполняются
+
+        let posts = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id AS uuid, p.title,
+                   CASE WHEN $8 THEN p.content ELSE '' END AS "content!",
+                   CASE WHEN $8 THEN p.content_html END AS content_html,
+                   p.summary, p.author_id,
+                   u.username AS author_username, u.avatar_url AS author_avatar_url,
+                   CASE WHEN $9 THEN ARRAY(
+                       SELECT t.tag::text FROM post_tags t
+                       WHERE t.post_id = p.id
+                       ORDER BY t.tag
+                   ) ELSE '{}' END AS "tags!",
+                   p.category_id, p.blog_id,
+                   CASE WHEN $10 THEN ARRAY(
+                       SELECT a.url::text FROM attachments a
+                       WHERE a.post_id = p.id
+                       ORDER BY a.created_at, a.id
+                   ) ELSE '{}' END AS "attachments!",
+                   p.status AS "status: PostStatus",
+                   CASE WHEN $11 THEN (
+                       SELECT COUNT(*) FROM post_likes l WHERE l.post_id = p.id
+                   ) ELSE 0 END AS "like_count!",
+                   CASE WHEN $11 THEN EXISTS (
+                       SELECT 1 FROM post_likes l
+                       WHERE l.post_id = p.id AND l.user_id = $4
+                   ) ELSE FALSE END AS "liked_by_me!",
+                   p.created_at, p.updated_at
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.deleted_at IS NULL
+              AND (p.status = 'published' OR p.author_id = $4)
+              AND ($3::text IS NULL
+                   OR EXISTS (SELECT 1 FROM post_tags t WHERE t.post_id = p.id AND t.tag = $3))
+              AND ($7::uuid IS NULL
+                   OR p.category_id IN (
+                       WITH RECURSIVE subcategories AS (
+                           SELECT c.id FROM categories c WHERE c.id = $7
+                           UNION ALL
+                           SELECT c.id FROM categories c
+                           JOIN subcategories s ON c.parent_id = s.id
+                       )
+                       SELECT id FROM subcategories
+                   ))
+              AND ($12::uuid IS NULL OR p.blog_id = $12)
+            ORDER BY
+                CASE WHEN $5 = 'title' AND $6 = 'asc' THEN p.title END ASC,
+                CASE WHEN $5 = 'title' AND $6 = 'desc' THEN p.title END DESC,
+                CASE WHEN $5 = 'updated_at' AND $6 = 'asc' THEN p.updated_at END ASC,
+                CASE WHEN $5 = 'updated_at' AND $6 = 'desc' THEN p.updated_at END DESC,
+                CASE WHEN $5 = 'created_at' AND $6 = 'asc' THEN p.created_at END ASC,
+                p.created_at DESC,
+                p.id
+            LIMIT $1 OFFSET $2
+            "#,
+            page_size as i64,
+            page as i64 * page_size as i64,
+            tag,
+            viewer,
+            sort.field.as_str(),
+            sort.order.as_str(),
+            category,
+            fields.content,
+            fields.tags,
+            fields.attachments,
+            fields.likes,
+            blog
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching posts: {}", e);
+            e
+        })?;
+
+        debug!("Fetched {} posts from database", posts.len());
+        Ok(posts)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_posts_after(
+        &self,
+        cursor: Option<PostCursor>,
+        limit: u32,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+        fields: PostFields,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching posts after cursor from database");
+
+        // Условие по паре (created_at, id) использует порядок по умолчанию и не зависит
+        // от количества уже пройденных постов, в отличие от OFFSET
+        let posts = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id AS uuid, p.title,
+                   CASE WHEN $7 THEN p.content ELSE '' END AS "content!",
+                   CASE WHEN $7 THEN p.content_html END AS content_html,
+                   p.summary, p.author_id,
+                   u.username AS author_username, u.avatar_url AS author_avatar_url,
+                   CASE WHEN $8 THEN ARRAY(
+                       SELECT t.tag::text FROM post_tags t
+                       WHERE t.post_id = p.id
+                       ORDER BY t.tag
+                   ) ELSE '{}' END AS "tags!",
+                   p.category_id, p.blog_id,
+                   CASE WHEN $9 THEN ARRAY(
+                       SELECT a.url::text FROM attachments a
+                       WHERE a.post_id = p.id
+                       ORDER BY a.created_at, a.id
+                   ) ELSE '{}' END AS "attachments!",
+                   p.status AS "status: PostStatus",
+                   CASE WHEN $10 THEN (
+                       SELECT COUNT(*) FROM post_likes l WHERE l.post_id = p.id
+                   ) ELSE 0 END AS "like_count!",
+                   CASE WHEN $10 THEN EXISTS (
+                       SELECT 1 FROM post_likes l
+                       WHERE l.post_id = p.id AND l.user_id = $3
+                   ) ELSE FALSE END AS "liked_by_me!",
+                   p.created_at, p.updated_at
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.deleted_at IS NULL
+              AND (p.status = 'published' OR p.author_id = $3)
+              AND ($2::text IS NULL
+                   OR EXISTS (SELECT 1 FROM post_tags t WHERE t.post_id = p.id AND t.tag = $2))
+              AND ($6::uuid IS NULL
+                   OR p.category_id IN (
+                       WITH RECURSIVE subcategories AS (
+                           SELECT c.id FROM categories c WHERE c.id = $6
+                           UNION ALL
+                           SELECT c.id FROM categories c
+                           JOIN subcategories s ON c.parent_id = s.id
+                       )
+                       SELECT id FROM subcategories
+                   ))
+              AND ($11::uuid IS NULL OR p.blog_id = $11)
+              AND ($4::timestamptz IS NULL
+                   OR p.created_at < $4
+                   OR (p.created_at = $4 AND p.id > $5))
+            ORDER BY p.created_at DESC, p.id
+            LIMIT $1
+            "#,
+            limit as i64,
+            tag,
+            viewer,
+            cursor.map(|cursor| cursor.created_at),
+            cursor.map(|cursor| cursor.id),
+            category,
+            fields.content,
+            fields.tags,
+            fields.attachments,
+            fields.likes,
+            blog
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching posts after cursor: {}", e);
+            e
+        })?;
+
+        debug!("Fetched {} posts from database", posts.len());
+        Ok(posts)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_trending_posts(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching trending posts from database");
+
+        let posts = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id AS uuid, p.title, p.content, p.content_html, p.summary, p.author_id,
+                   u.username AS author_username, u.avatar_url AS author_avatar_url,
+                   ARRAY(
+                       SELECT t.tag::text FROM post_tags t
+                       WHERE t.post_id = p.id
+                       ORDER BY t.tag
+                   ) AS "tags!",
+                   p.category_id, p.blog_id,
+                   ARRAY(
+                       SELECT a.url::text FROM attachments a
+                       WHERE a.post_id = p.id
+                       ORDER BY a.created_at, a.id
+                   ) AS "attachments!",
+                   p.status AS "status: PostStatus",
+                   (SELECT COUNT(*) FROM post_likes l WHERE l.post_id = p.id) AS "like_count!",
+                   EXISTS (
+                       SELECT 1 FROM post_likes l
+                       WHERE l.post_id = p.id AND l.user_id = $3
+                   ) AS "liked_by_me!",
+                   p.created_at, p.updated_at
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.deleted_at IS NULL
+              AND p.status = 'published'
+            ORDER BY (
+                         SELECT COUNT(*) FROM post_likes l
+                         WHERE l.post_id = p.id AND l.created_at >= $2
+                     ) DESC,
+                     p.created_at DESC, p.id
+            LIMIT $1
+            "#,
+            limit as i64,
+            since,
+            viewer
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching trending posts: {}", e);
+            e
+        })?;
+
+        debug!("Fetched {} trending posts from database", posts.len());
+        Ok(posts)
+    }
+
+    #[instrument(skip(self))]
+    async fn count_posts(
+        &self,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<u64> {
+        debug!("Counting posts in database");
+
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM posts p
+            WHERE p.deleted_at IS NULL
+              AND (p.status = 'published' OR p.author_id = $2)
+              AND ($1::text IS NULL
+                   OR EXISTS (SELECT 1 FROM post_tags t WHERE t.post_id = p.id AND t.tag = $1))
+              AND ($3::uuid IS NULL
+                   OR p.category_id IN (
+                       WITH RECURSIVE subcategories AS (
+                           SELECT c.id FROM categories c WHERE c.id = $3
+                           UNION ALL
+                           SELECT c.id FROM categories c
+                           JOIN subcategories s ON c.parent_id = s.id
+                       )
+                       SELECT id FROM subcategories
+                   ))
+              AND ($4::uuid IS NULL OR p.blog_id = $4)
+            "#,
+            tag,
+            viewer,
+            category,
+            blog
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while counting posts: {}", e);
+            e
+        })?;
+
+        debug!("Counted {} posts in database", count);
+        Ok(count as u64)
+    }
+
+    #[instrument(skip(self), fields(author_id = %author_id))]
+    async fn get_posts_by_author(
+        &self,
+        author_id: Uuid,
+        page: u32,
+        page_size: u32,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching author posts from database");
+
+        let posts = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id AS uuid, p.title, p.content, p.content_html, p.summary, p.author_id,
+                   u.username AS author_username, u.avatar_url AS author_avatar_url,
+                   ARRAY(
+                       SELECT t.tag::text FROM post_tags t
+                       WHERE t.post_id = p.id
+                       ORDER BY t.tag
+                   ) AS "tags!",
+                   p.category_id, p.blog_id,
+                   ARRAY(
+                       SELECT a.url::text FROM attachments a
+                       WHERE a.post_id = p.id
+                       ORDER BY a.created_at, a.id
+                   ) AS "attachments!",
+                   p.status AS "status: PostStatus",
+                   (SELECT COUNT(*) FROM post_likes l WHERE l.post_id = p.id) AS "like_count!",
+                   EXISTS (
+                       SELECT 1 FROM post_likes l
+                       WHERE l.post_id = p.id AND l.user_id = $4
+                   ) AS "liked_by_me!",
+                   p.created_at, p.updated_at
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.deleted_at IS NULL
+              AND p.author_id = $3
+              AND (p.status = 'published' OR p.author_id = $4)
+            ORDER BY p.created_at DESC
+            LIMIT $1 OFFSET $2
+            "#,
+            page_size as i64,
+            page as i64 * page_size as i64,
+            author_id,
+            viewer
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching author posts: {}", e);
+            e
+        })?;
+
+        debug!("Fetched {} author posts from database", posts.len());
+        Ok(posts)
+    }
+
+    #[instrument(skip(self), fields(author_id = %author_id))]
+    async fn count_posts_by_author(
+        &self,
+        author_id: Uuid,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<u64> {
+        debug!("Counting author posts in database");
+
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM posts p
+            WHERE p.deleted_at IS NULL
+              AND p.author_id = $1
+              AND (p.status = 'published' OR p.author_id = $2)
+            "#,
+            author_id,
+            viewer
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while counting author posts: {}", e);
+            e
+        })?;
+
+        debug!("Counted {} author posts in database", count);
+        Ok(count as u64)
+    }
+
+    #[instrument(skip(self), fields(author_id = %author_id))]
+    async fn count_posts_created_since(
+        &self,
+        author_id: Uuid,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<u64> {
+        debug!("Counting recently created author posts in database");
+
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM posts
+            WHERE author_id = $1
+              AND created_at >= $2
+            "#,
+            author_id,
+            since
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while counting recent author posts: {}", e);
+            e
+        })?;
+
+        debug!("Counted {} recent author posts in database", count);
+        Ok(count as u64)
+    }
+
+    #[instrument(skip(self), fields(follower_id = %follower_id))]
+    async fn get_feed_posts(
+        &self,
+        follower_id: Uuid,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching feed posts from database");
+
+        let posts = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id AS uuid, p.title, p.content, p.content_html, p.summary, p.author_id,
+                   u.username AS author_username, u.avatar_url AS author_avatar_url,
+                   ARRAY(
+                       SELECT t.tag::text FROM post_tags t
+                       WHERE t.post_id = p.id
+                       ORDER BY t.tag
+                   ) AS "tags!",
+                   p.category_id, p.blog_id,
+                   ARRAY(
+                       SELECT a.url::text FROM attachments a
+                       WHERE a.post_id = p.id
+                       ORDER BY a.created_at, a.id
+                   ) AS "attachments!",
+                   p.status AS "status: PostStatus",
+                   (SELECT COUNT(*) FROM post_likes l WHERE l.post_id = p.id) AS "like_count!",
+                   EXISTS (
+                       SELECT 1 FROM post_likes l
+                       WHERE l.post_id = p.id AND l.user_id = $3
+                   ) AS "liked_by_me!",
+                   p.created_at, p.updated_at
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            JOIN follows f ON f.followee_id = p.author_id AND f.follower_id = $3
+            WHERE p.deleted_at IS NULL
+              AND p.status = 'published'
+            ORDER BY p.created_at DESC
+            LIMIT $1 OFFSET $2
+            "#,
+            page_size as i64,
+            page as i64 * page_size as i64,
+            follower_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching feed posts: {}", e);
+            e
+        })?;
+
+        debug!("Fetched {} feed posts from database", posts.len());
+        Ok(posts)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_posts_published_between(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        until: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> DomainResult<Vec<Post>> {
+        debug!("Fetching recently published posts from database");
+
+        let posts = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id AS uuid, p.title, p.content, p.content_html, p.summary, p.author_id,
+                   u.username AS author_username, u.avatar_url AS author_avatar_url,
+                   ARRAY(
+                       SELECT t.tag::text FROM post_tags t
+                       WHERE t.post_id = p.id
+                       ORDER BY t.tag
+                   ) AS "tags!",
+                   p.category_id, p.blog_id,
+                   ARRAY(
+                       SELECT a.url::text FROM attachments a
+                       WHERE a.post_id = p.id
+                       ORDER BY a.created_at, a.id
+                   ) AS "attachments!",
+                   p.status AS "status: PostStatus",
+                   (SELECT COUNT(*) FROM post_likes l WHERE l.post_id = p.id) AS "like_count!",
+                   FALSE AS "liked_by_me!",
+                   p.created_at, p.updated_at
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.deleted_at IS NULL
+              AND p.status = 'published'
+              AND p.published_at > $1
+              AND p.published_at <= $2
+            ORDER BY p.published_at DESC, p.id
+            LIMIT $3
+            "#,
+            since,
+            until,
+            limit as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(
+                "Database error while fetching recently published posts: {}",
+                e
+            );
+            e
+        })?;
+
+        debug!(
+            "Fetched {} recently published posts from database",
+            posts.len()
+        );
+        Ok(posts)
+    }
+
+    #[instrument(skip(self), fields(follower_id = %follower_id))]
+    async fn count_feed_posts(&self, follower_id: Uuid) -> DomainResult<u64> {
+        debug!("Counting feed posts in database");
+
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM posts p
+            JOIN follows f ON f.followee_id = p.author_id AND f.follower_id = $1
+            WHERE p.deleted_at IS NULL
+              AND p.status = 'published'
+            "#,
+            follower_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while counting feed posts: {}", e);
+            e
+        })?;
+
+        debug!("Counted {} feed posts in database", count);
+        Ok(count as u64)
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn get_post_by_id(&self, post_id: Uuid, viewer: Option<Uuid>) -> DomainResult<Post> {
+        debug!("Fetching post by id from database");
+
+        let post = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id AS uuid, p.title, p.content, p.content_html, p.summary, p.author_id,
+                   u.username AS author_username, u.avatar_url AS author_avatar_url,
+                   ARRAY(
+                       SELECT t.tag::text FROM post_tags t
+                       WHERE t.post_id = p.id
+                       ORDER BY t.tag
+                   ) AS "tags!",
+                   p.category_id, p.blog_id,
+                   ARRAY(
+                       SELECT a.url::text FROM attachments a
+                       WHERE a.post_id = p.id
+                       ORDER BY a.created_at, a.id
+                   ) AS "attachments!",
+                   p.status AS "status: PostStatus",
+                   (SELECT COUNT(*) FROM post_likes l WHERE l.post_id = p.id) AS "like_count!",
+                   EXISTS (
+                       SELECT 1 FROM post_likes l
+                       WHERE l.post_id = p.id AND l.user_id = $2
+                   ) AS "liked_by_me!",
+                   p.created_at, p.updated_at
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.id = $1 AND p.deleted_at IS NULL
+            "#,
+            post_id,
+            viewer
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching post: {}", e);
+            e
+        })?
+        .ok_or(DomainError::PostNotFound { post_id })?;
+
+        debug!("Post fetched from database successfully");
+        Ok(post)
+    }
+
+    #[instrument(skip(self, post), fields(post_id = %post.uuid, title = %post.title))]
+    async fn create_post(&self, post: Post, attachment_ids: &[Uuid]) -> DomainResult<Post> {
+        debug!("Inserting post into database");
+
+        let mut tx = self.pool.begin().await?;
+        Self::insert_post(&mut tx, &post, attachment_ids).await?;
+        tx.commit().await?;
+
+        debug!("Post inserted into database successfully");
+        self.get_post_by_id(post.uuid, Some(post.author_id)).await
+    }
+
+    #[instrument(skip(self, posts), fields(count = posts.len()))]
+    async fn create_posts(&self, posts: Vec<(Post, Vec<Uuid>)>) -> DomainResult<Vec<Post>> {
+        debug!("Inserting posts into database");
+
+        let mut tx = self.pool.begin().await?;
+        for (post, attachment_ids) in &posts {
+            Self::insert_post(&mut tx, post, attachment_ids).await?;
+        }
+        tx.commit().await?;
+
+        debug!("Posts inserted into database successfully");
+        let mut created = Vec::with_capacity(posts.len());
+        for (post, _) in posts {
+            created.push(self.get_post_by_id(post.uuid, Some(post.author_id)).await?);
+        }
+        Ok(created)
+    }
+
+    #[instrument(skip(self, post), fields(post_id = %post.uuid))]
+    async fn update_post(&self, post: Post, attachment_ids: Option<&[Uuid]>) -> DomainResult<Post> {
+        debug!("Updating post in database");
+
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE posts
+            SET title = $1, content = $2, content_html = $3, updated_at = $4, category_id = $5,
+                summary = $6
+            WHERE id = $7
+            "#,
+            post.title,
+            post.content,
+            post.content_html,
+            chrono::Utc::now(),
+            post.category_id,
+            post.summary,
+            post.uuid
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("Database error while updating post: {}", e);
+            e
+        })?;
+
+        Self::replace_tags(&mut tx, post.uuid, &post.tags).await?;
+        if let Some(attachment_ids) = attachment_ids {
+            Self::replace_attachments(&mut tx, post.uuid, post.author_id, attachment_ids).await?;
+        }
+        tx.commit().await?;
+
+        debug!("Post updated in database successfully");
+        self.get_post_by_id(post.uuid, Some(post.author_id)).await
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id, status = status.as_str()))]
+    async fn set_post_status(&self, post_id: Uuid, status: PostStatus) -> DomainResult<()> {
+        debug!("Changing post status in database");
+
+        sqlx::query!(
+            r#"
+            UPDATE posts
+            SET status = $1::varchar,
+                published_at = COALESCE(published_at, CASE WHEN $1::varchar = 'published' THEN NOW() END)
+            WHERE id = $2
+            "#,
+            status.as_str(),
+            post_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while changing post status: {}", e);
+            e
+        })?;
+
+        debug!("Post status changed in database successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id, user_id = %user_id))]
+    async fn like_post(&self, post_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        debug!("Adding like to post");
+
+        sqlx::query!(
+            r#"
+            INSERT INTO post_likes (post_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT (post_id, user_id) DO NOTHING
+            "#,
+            post_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while liking post: {}", e);
+            e
+        })?;
+
+        debug!("Post liked successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id, user_id = %user_id))]
+    async fn unlike_post(&self, post_id: Uuid, user_id: Uuid) -> DomainResult<()> {
+        debug!("Removing like from post");
+
+        sqlx::query!(
+            r#"
+            DELETE FROM post_likes
+            WHERE post_id = $1 AND user_id = $2
+            "#,
+            post_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while unliking post: {}", e);
+            e
+        })?;
+
+        debug!("Post unliked successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn delete_post(&self, post_id: Uuid) -> DomainResult<()> {
+        debug!("Moving post to trash");
+
+        sqlx::query!(
+            r#"
+            UPDATE posts
+            SET deleted_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+            "#,
+            post_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while deleting post: {}", e);
+            e
+        })?;
+
+        debug!("Post moved to trash successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self, post_ids), fields(count = post_ids.len()))]
+    async fn delete_posts(&self, post_ids: &[Uuid]) -> DomainResult<()> {
+        debug!("Moving posts to trash");
+
+        sqlx::query!(
+            r#"
+            UPDATE posts
+            SET deleted_at = NOW()
+            WHERE id = ANY($1) AND deleted_at IS NULL
+            "#,
+            post_ids
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while deleting posts: {}", e);
+            e
+        })?;
+
+        debug!("Posts moved to trash successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn get_deleted_post(&self, post_id: Uuid, viewer: Option<Uuid>) -> DomainResult<Post> {
+        debug!("Fetching deleted post by id from database");
+
+        let post = sqlx::query_as!(
+            Post,
+            r#"
+            SELECT p.id AS uuid, p.title, p.content, p.content_html, p.summary, p.author_id,
+                   u.username AS author_username, u.avatar_url AS author_avatar_url,
+                   ARRAY(
+                       SELECT t.tag::text FROM post_tags t
+                       WHERE t.post_id = p.id
+                       ORDER BY t.tag
+                   ) AS "tags!",
+                   p.category_id, p.blog_id,
+                   ARRAY(
+                       SELECT a.url::text FROM attachments a
+                       WHERE a.post_id = p.id
+                       ORDER BY a.created_at, a.id
+                   ) AS "attachments!",
+                   p.status AS "status: PostStatus",
+                   (SELECT COUNT(*) FROM post_likes l WHERE l.post_id = p.id) AS "like_count!",
+                   EXISTS (
+                       SELECT 1 FROM post_likes l
+                       WHERE l.post_id = p.id AND l.user_id = $2
+                   ) AS "liked_by_me!",
+                   p.created_at, p.updated_at
+            FROM posts p
+            JOIN users u ON u.id = p.author_id
+            WHERE p.id = $1 AND p.deleted_at IS NOT NULL
+            "#,
+            post_id,
+            viewer
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching deleted post: {}", e);
+            e
+        })?
+        .ok_or(DomainError::PostNotFound { post_id })?;
+
+        debug!("Deleted post fetched from database successfully");
+        Ok(post)
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn restore_post(&self, post_id: Uuid) -> DomainResult<()> {
+        debug!("Restoring post from trash");
+
+        sqlx::query!(
+            r#"
+            UPDATE posts
+            SET deleted_at = NULL
+            WHERE id = $1
+            "#,
+            post_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while restoring post: {}", e);
+            e
+        })?;
+
+        debug!("Post restored successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn purge_post(&self, post_id: Uuid) -> DomainResult<()> {
+        debug!("Purging post from database");
+
+        sqlx::query!(
+            r#"
+            DELETE FROM posts
+            WHERE id = $1
+            "#,
+            post_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while purging post: {}", e);
+            e
+        })?;
+
+        debug!("Post purged from database successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self, attachment), fields(attachment_id = %attachment.id, owner_id = %attachment.owner_id))]
+    async fn create_attachment(&self, attachment: &Attachment) -> DomainResult<()> {
+        debug!("Inserting attachment into database");
+
+        sqlx::query!(
+            r#"
+            INSERT INTO attachments
+                (id, owner_id, post_id, storage_key, url, file_name, content_type, size_bytes, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+            attachment.id,
+            attachment.owner_id,
+            attachment.post_id,
+            attachment.storage_key,
+            attachment.url,
+            attachment.file_name,
+            attachment.content_type,
+            attachment.size_bytes,
+            attachment.created_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while creating attachment: {}", e);
+            e
+        })?;
+
+        debug!("Attachment inserted into database successfully");
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditRepository for PgUserRepository {
+    #[instrument(skip(self, entry), fields(user_id = %entry.user_id, action = entry.action.as_str()))]
+    async fn record_audit_entry(&self, entry: &AuditEntry) -> DomainResult<()> {
+        debug!("Inserting audit entry into database");
+
+        sqlx::query!(
+            r#"
+            INSERT INTO audit_log (id, user_id, action, target_id, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            entry.id,
+            entry.user_id,
+            entry.action.as_str(),
+            entry.target_id,
+            entry.created_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while recording audit entry: {}", e);
+            e
+        })?;
+
+        debug!("Audit entry inserted into database successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn get_audit_entries(
+        &self,
+        filter: &AuditFilter,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<Vec<AuditEntry>> {
+        debug!("Fetching audit entries from database");
+
+        let entries = sqlx::query_as!(
+            AuditEntry,
+            r#"
+            SELECT id, user_id, action AS "action: AuditAction", target_id, created_at
+            FROM audit_log
+            WHERE ($3::uuid IS NULL OR user_id = $3)
+              AND ($4::timestamptz IS NULL OR created_at >= $4)
+              AND ($5::timestamptz IS NULL OR created_at < $5)
+            ORDER BY created_at DESC, id DESC
+            LIMIT $1 OFFSET $2
+            "#,
+            page_size as i64,
+            page as i64 * page_size as i64,
+            filter.user_id,
+            filter.from,
+            filter.to
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching audit entries: {}", e);
+            e
+        })?;
+
+        debug!("Fetched {} audit entries from database", entries.len());
+        Ok(entries)
+    }
+
+    #[instrument(skip(self))]
+    async fn count_audit_entries(&self, filter: &AuditFilter) -> DomainResult<u64> {
+        debug!("Counting audit entries in database");
+
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM audit_log
+            WHERE ($1::uuid IS NULL OR user_id = $1)
+              AND ($2::timestamptz IS NULL OR created_at >= $2)
+              AND ($3::timestamptz IS NULL OR created_at < $3)
+            "#,
+            filter.user_id,
+            filter.from,
+            filter.to
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while counting audit entries: {}", e);
+            e
+        })?;
+
+        debug!("Counted {} audit entries in database", count);
+        Ok(count as u64)
+    }
+}
+
+#[async_trait::async_trait]
+impl AdminRepository for PgUserRepository {
+    #[instrument(skip(self))]
+    async fn list_users(&self, page: u32, page_size: u32) -> DomainResult<Vec<UserSummary>> {
+        debug!("Fetching users from database");
+
+        let users = sqlx::query_as!(
+            UserSummary,
+            r#"
+            SELECT id, username, email, role AS "role: Role", created_at, disabled_at
+            FROM users
+            ORDER BY created_at, id
+            LIMIT $1 OFFSET $2
+            "#,
+            page_size as i64,
+            page as i64 * page_size as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching users: {}", e);
+            e
+        })?;
+
+        debug!("Fetched {} users from database", users.len());
+        Ok(users)
+    }
+
+    #[instrument(skip(self))]
+    async fn count_users(&self) -> DomainResult<u64> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM users
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while counting users: {}", e);
+            e
+        })?;
+
+        Ok(count as u64)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn set_user_disabled(
+        &self,
+        user_id: Uuid,
+        disabled_at: Option<chrono::DateTime<chrono::Utc>>,
+    ) -> DomainResult<bool> {
+        debug!("Changing user disabled state");
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE users
+            SET disabled_at = $2
+            WHERE id = $1
+            "#,
+            user_id,
+            disabled_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while changing user disabled state: {}", e);
+            e
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_system_stats(
+        &self,
+        signups_since: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<SystemStats> {
+        debug!("Collecting system stats from database");
+
+        let stats = sqlx::query!(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM users) AS "users!",
+                (SELECT COUNT(*) FROM posts WHERE deleted_at IS NULL) AS "posts!",
+                (SELECT COUNT(*) FROM users WHERE created_at >= $1) AS "recent_signups!"
+            "#,
+            signups_since
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while collecting system stats: {}", e);
+            e
+        })?;
+
+        Ok(SystemStats {
+            users: stats.users as u64,
+            posts: stats.posts as u64,
+            recent_signups: stats.recent_signups as u64,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl ReportRepository for PgUserRepository {
+    #[instrument(skip(self, report), fields(report_id = %report.id, post_id = %report.post_id))]
+    async fn create_report(&self, report: &Report) -> DomainResult<()> {
+        debug!("Inserting report into database");
+
+        let result = sqlx::query!(
+            r#"
+            INSERT INTO reports (id, post_id, reporter_id, reason, details, status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (post_id, reporter_id) DO NOTHING
+            "#,
+            report.id,
+            report.post_id,
+            report.reporter_id,
+            report.reason.as_str(),
+            report.details,
+            report.status.as_str(),
+            report.created_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while inserting report: {}", e);
+            e
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DomainError::ReportAlreadyExists {
+                post_id: report.post_id,
+            });
+        }
+
+        debug!("Report inserted into database successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(report_id = %report_id))]
+    async fn get_report(&self, report_id: Uuid) -> DomainResult<Report> {
+        debug!("Fetching report from database");
+
+        let report = sqlx::query_as!(
+            Report,
+            r#"
+            SELECT id, post_id, reporter_id, reason AS "reason: ReportReason", details,
+                   status AS "status: ReportStatus", resolved_by, resolved_at, created_at
+            FROM reports
+            WHERE id = $1
+            "#,
+            report_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching report: {}", e);
+            e
+        })?;
+
+        report.ok_or(DomainError::ReportNotFound { report_id })
+    }
+
+    #[instrument(skip(self))]
+    async fn get_reports(
+        &self,
+        status: Option<ReportStatus>,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<Vec<Report>> {
+        debug!("Fetching reports from database");
+
+        let reports = sqlx::query_as!(
+            Report,
+            r#"
+            SELECT id, post_id, reporter_id, reason AS "reason: ReportReason", details,
+                   status AS "status: ReportStatus", resolved_by, resolved_at, created_at
+            FROM reports
+            WHERE ($3::text IS NULL OR status = $3)
+            ORDER BY created_at, id
+            LIMIT $1 OFFSET $2
+            "#,
+            page_size as i64,
+            page as i64 * page_size as i64,
+            status.map(|status| status.as_str())
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching reports: {}", e);
+            e
+        })?;
+
+        debug!("Fetched {} reports from database", reports.len());
+        Ok(reports)
+    }
+
+    #[instrument(skip(self))]
+    async fn count_reports(&self, status: Option<ReportStatus>) -> DomainResult<u64> {
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM reports
+            WHERE ($1::text IS NULL OR status = $1)
+            "#,
+            status.map(|status| status.as_str())
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while counting reports: {}", e);
+            e
+        })?;
+
+        Ok(count as u64)
+    }
+
+    #[instrument(skip(self), fields(report_id = %report_id))]
+    async fn resolve_report(
+        &self,
+        report_id: Uuid,
+        status: ReportStatus,
+        resolved_by: Uuid,
+        resolved_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<bool> {
+        debug!("Resolving report");
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE reports
+            SET status = $2, resolved_by = $3, resolved_at = $4
+            WHERE id = $1 AND status = 'open'
+            "#,
+            report_id,
+            status.as_str(),
+            resolved_by,
+            resolved_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while resolving report: {}", e);
+            e
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn resolve_post_reports(
+        &self,
+        post_id: Uuid,
+        status: ReportStatus,
+        resolved_by: Uuid,
+        resolved_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<u64> {
+        debug!("Resolving post reports");
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE reports
+            SET status = $2, resolved_by = $3, resolved_at = $4
+            WHERE post_id = $1 AND status = 'open'
+            "#,
+            post_id,
+            status.as_str(),
+            resolved_by,
+            resolved_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while resolving post reports: {}", e);
+            e
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    #[instrument(skip(self), fields(post_id = %post_id))]
+    async fn is_post_hidden(&self, post_id: Uuid) -> DomainResult<bool> {
+        let hidden = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM reports WHERE post_id = $1 AND status = 'hidden'
+            ) AS "hidden!"
+            "#,
+            post_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while checking hidden post: {}", e);
+            e
+        })?;
+
+        Ok(hidden)
+    }
+}
+
+#[async_trait::async_trait]
+impl FollowRepository for PgUserRepository {
+    #[instrument(skip(self), fields(follower_id = %follower_id, followee_id = %followee_id))]
+    async fn follow_user(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<()> {
+        debug!("Adding follow");
+
+        sqlx::query!(
+            r#"
+            INSERT INTO follows (follower_id, followee_id)
+            VALUES ($1, $2)
+            ON CONFLICT (follower_id, followee_id) DO NOTHING
+            "#,
+            follower_id,
+            followee_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while following user: {}", e);
+            e
+        })?;
+
+        debug!("User followed successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(follower_id = %follower_id, followee_id = %followee_id))]
+    async fn unfollow_user(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<()> {
+        debug!("Removing follow");
+
+        sqlx::query!(
+            r#"
+            DELETE FROM follows
+            WHERE follower_id = $1 AND followee_id = $2
+            "#,
+            follower_id,
+            followee_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while unfollowing user: {}", e);
+            e
+        })?;
+
+        debug!("User unfollowed successfully");
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(follower_id = %follower_id, followee_id = %followee_id))]
+    async fn is_following(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<bool> {
+        let following = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM follows
+                WHERE follower_id = $1 AND followee_id = $2
+            ) AS "following!"
+            "#,
+            follower_id,
+            followee_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while checking follow: {}", e);
+            e
+        })?;
+
+        Ok(following)
+    }
+}
+
+#[async_trait::async_trait]
+impl DigestRepository for PgUserRepository {
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn get_digest_subscription(
+        &self,
+        user_id: Uuid,
+    ) -> DomainResult<Option<DigestSubscription>> {
+        let subscription = sqlx::query_as!(
+            DigestSubscription,
+            r#"
+            SELECT d.user_id, u.email, u.username,
+                   d.frequency AS "frequency: DigestFrequency",
+                   d.last_sent_at, d.created_at
+            FROM digest_subscriptions d
+            JOIN users u ON u.id = d.user_id
+            WHERE d.user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching digest subscription: {}", e);
+            e
+        })?;
+        Ok(subscription)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn save_digest_subscription(
+        &self,
+        user_id: Uuid,
+        frequency: DigestFrequency,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<()> {
+        debug!("Saving digest subscription in database");
+        sqlx::query!(
+            r#"
+            INSERT INTO digest_subscriptions (user_id, frequency, last_sent_at, created_at)
+            VALUES ($1, $2, $3, $3)
+            ON CONFLICT (user_id) DO UPDATE SET frequency = EXCLUDED.frequency
+            "#,
+            user_id,
+            frequency.as_str(),
+            now
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while saving digest subscription: {}", e);
+            e
+        })?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn delete_digest_subscription(&self, user_id: Uuid) -> DomainResult<bool> {
+        debug!("Deleting digest subscription from database");
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM digest_subscriptions
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while deleting digest subscription: {}", e);
+            e
+        })?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_due_digest_subscriptions(
+        &self,
+        frequency: DigestFrequency,
+        sent_before: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<Vec<DigestSubscription>> {
+        debug!("Fetching due digest subscriptions from database");
+        let subscriptions = sqlx::query_as!(
+            DigestSubscription,
+            r#"
+            SELECT d.user_id, u.email, u.username,
+                   d.frequency AS "frequency: DigestFrequency",
+                   d.last_sent_at, d.created_at
+            FROM digest_subscriptions d
+            JOIN users u ON u.id = d.user_id
+            WHERE d.frequency = $1
+              AND d.last_sent_at <= $2
+              AND u.disabled_at IS NULL
+            ORDER BY d.last_sent_at, d.user_id
+            "#,
+            frequency.as_str(),
+            sent_before
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!(
+                "Database error while fetching due digest subscriptions: {}",
+                e
+            );
+            e
+        })?;
+        debug!("Fetched {} due digest subscriptions", subscriptions.len());
+        Ok(subscriptions)
+    }
+
+    #[instrument(skip(self), fields(user_id = %user_id))]
+    async fn mark_digest_sent(
+        &self,
+        user_id: Uuid,
+        sent_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<()> {
+        sqlx::query!(
+            r#"
+            UPDATE digest_subscriptions
+            SET last_sent_at = $2
+            WHERE user_id = $1
+            "#,
+            user_id,
+            sent_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while marking digest as sent: {}", e);
+            e
+        })?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl CategoryRepository for PgUserRepository {
+    #[instrument(skip(self))]
+    async fn list_categories(&self) -> DomainResult<Vec<Category>> {
+        debug!("Fetching categories from database");
+        let categories = sqlx::query_as!(
+            Category,
+            r#"
+            SELECT id, name, parent_id, created_at
+            FROM categories
+            ORDER BY name, id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching categories: {}", e);
+            e
+        })?;
+        Ok(categories)
+    }
+
+    #[instrument(skip(self), fields(category_id = %category_id))]
+    async fn get_category(&self, category_id: Uuid) -> DomainResult<Option<Category>> {
+        let category = sqlx::query_as!(
+            Category,
+            r#"
+            SELECT id, name, parent_id, created_at
+            FROM categories
+            WHERE id = $1
+            "#,
+            category_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching category: {}", e);
+            e
+        })?;
+        Ok(category)
+    }
+
+    #[instrument(skip(self, category), fields(category_id = %category.id, name = %category.name))]
+    async fn create_category(&self, category: &Category) -> DomainResult<()> {
+        debug!("Inserting category into database");
+        sqlx::query!(
+            r#"
+            INSERT INTO categories (id, name, parent_id, created_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+            category.id,
+            category.name,
+            category.parent_id,
+            category.created_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while creating category: {}", e);
+            e
+        })?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl BlogRepository for PgUserRepository {
+    #[instrument(skip(self))]
+    async fn list_blogs(&self) -> DomainResult<Vec<Blog>> {
+        debug!("Fetching blogs from database");
+        let blogs = sqlx::query_as!(
+            Blog,
+            r#"
+            SELECT id, slug, name, owner_id, created_at
+            FROM blogs
+            ORDER BY slug
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching blogs: {}", e);
+            e
+        })?;
+        Ok(blogs)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_blog_by_slug(&self, slug: &str) -> DomainResult<Option<Blog>> {
+        let blog = sqlx::query_as!(
+            Blog,
+            r#"
+            SELECT id, slug, name, owner_id, created_at
+            FROM blogs
+            WHERE slug = $1
+            "#,
+            slug
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching blog: {}", e);
+            e
+        })?;
+        Ok(blog)
+    }
+
+    #[instrument(skip(self), fields(blog_id = %blog_id))]
+    async fn get_blog(&self, blog_id: Uuid) -> DomainResult<Option<Blog>> {
+        let blog = sqlx::query_as!(
+            Blog,
+            r#"
+            SELECT id, slug, name, owner_id, created_at
+            FROM blogs
+            WHERE id = $1
+            "#,
+            blog_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while fetching blog: {}", e);
+            e
+        })?;
+        Ok(blog)
+    }
+
+    #[instrument(skip(self, blog), fields(blog_id = %blog.id, slug = %blog.slug))]
+    async fn create_blog(&self, blog: &Blog) -> DomainResult<()> {
+        debug!("Inserting blog into database");
+        sqlx::query!(
+            r#"
+            INSERT INTO blogs (id, slug, name, owner_id, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+            blog.id,
+            blog.slug,
+            blog.name,
+            blog.owner_id,
+            blog.created_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while creating blog: {}", e);
+            e
+        })?;
+        Ok(())
+    }
+
+    #[instrument(skip(self, name), fields(blog_id = %blog_id))]
+    async fn update_blog_name(&self, blog_id: Uuid, name: &str) -> DomainResult<()> {
+        debug!("Updating blog in database");
+        sqlx::query!(
+            r#"
+            UPDATE blogs
+            SET name = $1
+            WHERE id = $2
+            "#,
+            name,
+            blog_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while updating blog: {}", e);
+            e
+        })?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), fields(blog_id = %blog_id))]
+    async fn delete_blog(&self, blog_id: Uuid) -> DomainResult<()> {
+        debug!("Deleting blog from database");
+        sqlx::query!(
+            r#"
+            DELETE FROM blogs
+            WHERE id = $1
+            "#,
+            blog_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("Database error while deleting blog: {}", e);
+            e
+        })?;
         Ok(())
     }
 }
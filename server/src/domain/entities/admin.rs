@@ -0,0 +1,35 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::user::Role;
+
+/// Пользователь в списке пользователей для администратора.
+///
+/// # Поля
+///
+/// * `id` - Уникальный идентификатор пользователя
+/// * `username` - Имя пользователя
+/// * `email` - Email пользователя
+/// * `role` - Роль пользователя
+/// * `created_at` - Временная метка регистрации
+/// * `disabled_at` - Когда пользователь был отключён; `None` для активных
+#[derive(Debug, Clone)]
+pub struct UserSummary {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub role: Role,
+    pub created_at: DateTime<Utc>,
+    pub disabled_at: Option<DateTime<Utc>>,
+}
+
+/// Сводные показатели системы.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemStats {
+    /// Количество пользователей
+    pub users: u64,
+    /// Количество постов, кроме перенесённых в корзину (включая черновики)
+    pub posts: u64,
+    /// Количество пользователей, зарегистрированных с начала окна
+    pub recent_signups: u64,
+}
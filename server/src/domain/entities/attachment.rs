@@ -0,0 +1,101 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::errors::{DomainError, DomainResult};
+
+/// Максимальная длина имени загруженного файла (ограничение колонки `attachments.file_name`)
+pub const FILE_NAME_MAX_LEN: usize = 255;
+/// Максимальное количество вложений у поста
+pub const MAX_ATTACHMENTS_PER_POST: usize = 10;
+
+/// Загруженный файл.
+///
+/// # Поля
+///
+/// * `id` - Уникальный идентификатор вложения
+/// * `owner_id` - ID пользователя, загрузившего файл
+/// * `post_id` - ID поста, к которому прикреплён файл
+/// * `storage_key` - Ключ объекта в хранилище файлов
+/// * `url` - Публичная ссылка на файл
+/// * `file_name` - Исходное имя файла
+/// * `content_type` - MIME-тип файла
+/// * `size_bytes` - Размер файла в байтах
+/// * `created_at` - Временная метка загрузки
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub post_id: Option<Uuid>,
+    pub storage_key: String,
+    pub url: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Возвращает расширение файла для разрешённого MIME-типа.
+///
+/// Загружать можно только изображения, PDF и простой текст: такие файлы безопасно
+/// отдавать с домена блога.
+pub fn extension_for_content_type(content_type: &str) -> DomainResult<&'static str> {
+    let extension = match content_type {
+        "image/png" => "png",
+        "image/jpeg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "application/pdf" => "pdf",
+        "text/plain" => "txt",
+        _ => {
+            return Err(DomainError::InvalidUpload {
+                reason: format!("content type '{content_type}' is not allowed"),
+            });
+        }
+    };
+    Ok(extension)
+}
+
+/// Приводит имя загруженного файла к безопасному виду.
+///
+/// Отбрасывает путь, управляющие символы и обрезает имя до [`FILE_NAME_MAX_LEN`] символов;
+/// пустое имя заменяется на `file`.
+pub fn sanitize_file_name(file_name: &str) -> String {
+    let name = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(FILE_NAME_MAX_LEN)
+        .collect::<String>();
+    let name = name.trim();
+    if name.is_empty() || name == "." || name == ".." {
+        "file".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Проверяет список вложений, прикрепляемых к посту.
+///
+/// Дубликаты удаляются с сохранением порядка.
+///
+/// # Ошибки
+///
+/// [`DomainError::InvalidAttachments`], если вложений больше [`MAX_ATTACHMENTS_PER_POST`].
+pub fn normalize_attachment_ids(ids: Vec<Uuid>) -> DomainResult<Vec<Uuid>> {
+    let mut normalized: Vec<Uuid> = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !normalized.contains(&id) {
+            normalized.push(id);
+        }
+    }
+
+    if normalized.len() > MAX_ATTACHMENTS_PER_POST {
+        return Err(DomainError::InvalidAttachments {
+            reason: format!("a post can have at most {MAX_ATTACHMENTS_PER_POST} attachments"),
+        });
+    }
+
+    Ok(normalized)
+}
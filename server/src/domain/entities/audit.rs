@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Действие пользователя, записываемое в журнал аудита.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    /// Регистрация
+    Register,
+    /// Вход по паролю
+    Login,
+    /// Создание поста
+    CreatePost,
+    /// Изменение поста
+    UpdatePost,
+    /// Перенос поста в корзину
+    DeletePost,
+    /// Восстановление поста из корзины
+    RestorePost,
+    /// Окончательное удаление поста
+    PurgePost,
+    /// Скрытие поста модератором по жалобе
+    HidePost,
+    /// Отключение пользователя администратором
+    DisableUser,
+    /// Включение пользователя администратором
+    EnableUser,
+}
+
+impl AuditAction {
+    /// Строковое представление действия (значение колонки `audit_log.action`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Register => "register",
+            AuditAction::Login => "login",
+            AuditAction::CreatePost => "create_post",
+            AuditAction::UpdatePost => "update_post",
+            AuditAction::DeletePost => "delete_post",
+            AuditAction::RestorePost => "restore_post",
+            AuditAction::PurgePost => "purge_post",
+            AuditAction::HidePost => "hide_post",
+            AuditAction::DisableUser => "disable_user",
+            AuditAction::EnableUser => "enable_user",
+        }
+    }
+
+    /// Разбирает действие из строкового представления
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "register" => Some(AuditAction::Register),
+            "login" => Some(AuditAction::Login),
+            "create_post" => Some(AuditAction::CreatePost),
+            "update_post" => Some(AuditAction::UpdatePost),
+            "delete_post" => Some(AuditAction::DeletePost),
+            "restore_post" => Some(AuditAction::RestorePost),
+            "purge_post" => Some(AuditAction::PurgePost),
+            "hide_post" => Some(AuditAction::HidePost),
+            "disable_user" => Some(AuditAction::DisableUser),
+            "enable_user" => Some(AuditAction::EnableUser),
+            _ => None,
+        }
+    }
+}
+
+/// Запись журнала аудита.
+///
+/// # Поля
+///
+/// * `id` - Уникальный идентификатор записи (UUID v7)
+/// * `user_id` - ID пользователя, выполнившего действие
+/// * `action` - Выполненное действие
+/// * `target_id` - ID объекта действия (поста или пользователя); `None` для входа и регистрации
+/// * `created_at` - Временная метка действия
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub action: AuditAction,
+    pub target_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Условия выборки журнала аудита; незаданные условия не ограничивают выборку.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    /// Только действия этого пользователя
+    pub user_id: Option<Uuid>,
+    /// Только действия не раньше этого момента
+    pub from: Option<DateTime<Utc>>,
+    /// Только действия раньше этого момента
+    pub to: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::errors::{DomainError, DomainResult};
+
+/// Максимальная длина адреса блога (ограничение колонки `blogs.slug`)
+pub const BLOG_SLUG_MAX_LEN: usize = 64;
+/// Минимальная длина адреса блога
+pub const BLOG_SLUG_MIN_LEN: usize = 3;
+/// Максимальная длина названия блога (ограничение колонки `blogs.name`)
+pub const BLOG_NAME_MAX_LEN: usize = 128;
+
+/// Блог на сервере.
+///
+/// Один сервер может обслуживать несколько блогов: у каждого свой адрес, владелец
+/// и посты. Посты без блога относятся к общей ленте сервера.
+///
+/// # Поля
+///
+/// * `id` - Уникальный идентификатор блога (UUID v7)
+/// * `slug` - Адрес блога в путях API (`/api/v1/blogs/{slug}/posts`), уникальный на сервере
+/// * `name` - Название блога
+/// * `owner_id` - ID владельца; публиковать посты в блоге может только он
+/// * `created_at` - Временная метка создания
+#[derive(Debug, Clone)]
+pub struct Blog {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub owner_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Приводит адрес блога к нижнему регистру и проверяет его.
+///
+/// # Ошибки
+///
+/// [`DomainError::InvalidBlog`], если адрес короче [`BLOG_SLUG_MIN_LEN`] или длиннее
+/// [`BLOG_SLUG_MAX_LEN`] символов, содержит что-то кроме латинских букв, цифр и дефиса
+/// или начинается либо заканчивается дефисом.
+pub fn normalize_blog_slug(slug: &str) -> DomainResult<String> {
+    let slug = slug.trim().to_lowercase();
+    let len = slug.chars().count();
+    if !(BLOG_SLUG_MIN_LEN..=BLOG_SLUG_MAX_LEN).contains(&len) {
+        return Err(DomainError::InvalidBlog {
+            reason: format!(
+                "blog slug must be between {BLOG_SLUG_MIN_LEN} and {BLOG_SLUG_MAX_LEN} characters"
+            ),
+        });
+    }
+    if !slug
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        || slug.starts_with('-')
+        || slug.ends_with('-')
+    {
+        return Err(DomainError::InvalidBlog {
+            reason: "blog slug may contain only latin letters, digits and inner hyphens"
+                .to_string(),
+        });
+    }
+    Ok(slug)
+}
+
+/// Убирает пробелы по краям названия блога и проверяет его длину.
+///
+/// # Ошибки
+///
+/// [`DomainError::InvalidBlog`], если название пустое или длиннее
+/// [`BLOG_NAME_MAX_LEN`] символов.
+pub fn normalize_blog_name(name: &str) -> DomainResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DomainError::InvalidBlog {
+            reason: "blog name must not be empty".to_string(),
+        });
+    }
+    if name.chars().count() > BLOG_NAME_MAX_LEN {
+        return Err(DomainError::InvalidBlog {
+            reason: format!("blog name must be at most {BLOG_NAME_MAX_LEN} characters"),
+        });
+    }
+    Ok(name.to_string())
+}
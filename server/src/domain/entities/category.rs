@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::errors::{DomainError, DomainResult};
+
+/// Максимальная длина имени категории (ограничение колонки `categories.name`)
+pub const CATEGORY_NAME_MAX_LEN: usize = 64;
+
+/// Категория постов.
+///
+/// Категории образуют дерево: у подкатегории указана родительская категория.
+/// Пост из подкатегории попадает и в список постов родительской категории.
+///
+/// # Поля
+///
+/// * `id` - Уникальный идентификатор категории (UUID v7)
+/// * `name` - Имя категории, уникальное среди категорий с общим родителем
+/// * `parent_id` - ID родительской категории; `None` у категорий верхнего уровня
+/// * `created_at` - Временная метка создания
+#[derive(Debug, Clone)]
+pub struct Category {
+    pub id: Uuid,
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Убирает пробелы по краям имени категории и проверяет его длину.
+///
+/// # Ошибки
+///
+/// [`DomainError::InvalidCategory`], если имя пустое или длиннее
+/// [`CATEGORY_NAME_MAX_LEN`] символов.
+pub fn normalize_category_name(name: &str) -> DomainResult<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(DomainError::InvalidCategory {
+            reason: "category name must not be empty".to_string(),
+        });
+    }
+    if name.chars().count() > CATEGORY_NAME_MAX_LEN {
+        return Err(DomainError::InvalidCategory {
+            reason: format!("category name must be at most {CATEGORY_NAME_MAX_LEN} characters"),
+        });
+    }
+    Ok(name.to_string())
+}
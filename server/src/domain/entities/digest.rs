@@ -0,0 +1,61 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+/// Как часто пользователь получает дайджест новых постов.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestFrequency {
+    /// Раз в сутки
+    Daily,
+    /// Раз в неделю
+    Weekly,
+}
+
+impl DigestFrequency {
+    /// Все значения, в порядке проверки планировщиком рассылки
+    pub const ALL: [DigestFrequency; 2] = [DigestFrequency::Daily, DigestFrequency::Weekly];
+
+    /// Строковое представление частоты (значение колонки `digest_subscriptions.frequency`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestFrequency::Daily => "daily",
+            DigestFrequency::Weekly => "weekly",
+        }
+    }
+
+    /// Разбирает частоту из строкового представления
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "daily" => Some(DigestFrequency::Daily),
+            "weekly" => Some(DigestFrequency::Weekly),
+            _ => None,
+        }
+    }
+
+    /// Промежуток между двумя дайджестами
+    pub fn period(&self) -> Duration {
+        match self {
+            DigestFrequency::Daily => Duration::days(1),
+            DigestFrequency::Weekly => Duration::weeks(1),
+        }
+    }
+}
+
+/// Подписка пользователя на email-дайджест.
+///
+/// # Поля
+///
+/// * `user_id` - ID подписчика
+/// * `email`, `username` - адрес и имя подписчика для письма
+/// * `frequency` - частота писем
+/// * `last_sent_at` - время предыдущего дайджеста; следующий включает посты,
+///   опубликованные позже. Для новой подписки - время подписки
+/// * `created_at` - время подписки
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigestSubscription {
+    pub user_id: Uuid,
+    pub email: String,
+    pub username: String,
+    pub frequency: DigestFrequency,
+    pub last_sent_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
@@ -15,22 +15,176 @@ pub enum DomainError {
     #[error("User not found: {username}")]
     UserNotFound { username: String },
 
+    /// Невалидное имя пользователя (например, содержит `@`)
+    #[error("Invalid username: {reason}")]
+    InvalidUsername { reason: String },
+
+    /// Профиль пользователя с таким ID не найден
+    #[error("User profile not found: {user_id}")]
+    ProfileNotFound { user_id: Uuid },
+
+    /// Сеанс входа с таким ID у пользователя не найден
+    #[error("Session not found: {session_id}")]
+    SessionNotFound { session_id: Uuid },
+
+    /// Невалидные данные профиля (слишком длинные поля или некорректная ссылка)
+    #[error("Invalid profile: {reason}")]
+    InvalidProfile { reason: String },
+
     /// Неверные учётные данные (пароль или логин)
     #[error("Invalid credentials")]
     InvalidCredentials,
 
+    /// Вход временно заблокирован после серии неудачных попыток
+    #[error("Account is locked until {locked_until}")]
+    AccountLocked {
+        locked_until: chrono::DateTime<chrono::Utc>,
+    },
+
+    /// Пользователь отключён администратором
+    #[error("Account is disabled")]
+    AccountDisabled,
+
+    /// Неверный или просроченный код двухфакторной аутентификации
+    #[error("Invalid two-factor authentication code")]
+    InvalidTotpCode,
+
+    /// Двухфакторная аутентификация уже включена
+    #[error("Two-factor authentication is already enabled")]
+    TwoFactorAlreadyEnabled,
+
+    /// Двухфакторная аутентификация не подключена (нет секрета для подтверждения)
+    #[error("Two-factor authentication is not enrolled")]
+    TwoFactorNotEnrolled,
+
+    /// Вход через этого провайдера не настроен
+    #[error("OAuth provider is not configured: {provider}")]
+    OAuthProviderNotConfigured { provider: String },
+
+    /// Провайдер OAuth отклонил вход или вернул неполные данные
+    #[error("OAuth login failed: {0}")]
+    OAuthError(String),
+
     /// Невалидный пароль (не соответствует требованиям)
     #[error("Invalid password: {reason}")]
     InvalidPassword { reason: String },
 
+    /// Токен сброса пароля не найден, уже использован или истёк
+    #[error("Invalid or expired password reset token")]
+    InvalidResetToken,
+
+    /// Подпись ссылки отписки от дайджеста неверна
+    #[error("Invalid unsubscribe link")]
+    InvalidUnsubscribeToken,
+
     /// Пост не найден
     #[error("Post not found: {post_id}")]
     PostNotFound { post_id: Uuid },
 
+    /// Категория не найдена
+    #[error("Category not found: {category_id}")]
+    CategoryNotFound { category_id: Uuid },
+
+    /// Категория с таким именем у этого родителя уже есть
+    #[error("Category already exists: {name}")]
+    CategoryAlreadyExists { name: String },
+
+    /// Невалидная категория (пустое или слишком длинное имя)
+    #[error("Invalid category: {reason}")]
+    InvalidCategory { reason: String },
+
+    /// Блог не найден
+    #[error("Blog not found: {slug}")]
+    BlogNotFound { slug: String },
+
+    /// Блог с таким адресом уже есть
+    #[error("Blog already exists: {slug}")]
+    BlogAlreadyExists { slug: String },
+
+    /// Невалидный блог (адрес или название)
+    #[error("Invalid blog: {reason}")]
+    InvalidBlog { reason: String },
+
+    /// Невалидные теги поста (слишком много или слишком длинные)
+    #[error("Invalid tags: {reason}")]
+    InvalidTags { reason: String },
+
+    /// Невалидное описание поста (слишком длинное)
+    #[error("Invalid summary: {reason}")]
+    InvalidSummary { reason: String },
+
+    /// Загруженный файл отклонён (пустой, слишком большой или недопустимого типа)
+    #[error("Invalid upload: {reason}")]
+    InvalidUpload { reason: String },
+
+    /// Вложения поста не найдены или принадлежат другому пользователю
+    #[error("Invalid attachments: {reason}")]
+    InvalidAttachments { reason: String },
+
+    /// Пользователь уже жаловался на этот пост
+    #[error("Post already reported: {post_id}")]
+    ReportAlreadyExists { post_id: Uuid },
+
+    /// Жалоба не найдена
+    #[error("Report not found: {report_id}")]
+    ReportNotFound { report_id: Uuid },
+
+    /// По жалобе уже принято решение
+    #[error("Report already resolved: {report_id}")]
+    ReportAlreadyResolved { report_id: Uuid },
+
+    /// Невалидная жалоба (слишком длинное пояснение)
+    #[error("Invalid report: {reason}")]
+    InvalidReport { reason: String },
+
+    /// Недопустимая подписка (на самого себя)
+    #[error("Invalid follow: {reason}")]
+    InvalidFollow { reason: String },
+
+    /// Пакетная операция над постами недопустимого размера
+    #[error("Invalid batch: {reason}")]
+    InvalidBatch { reason: String },
+
+    /// Курсор списка постов не разобран или не подходит к запросу
+    #[error("Invalid cursor: {reason}")]
+    InvalidCursor { reason: String },
+
+    /// Невалидный ключ идемпотентности (пустой, слишком длинный или с недопустимыми символами)
+    #[error("Invalid idempotency key: {reason}")]
+    InvalidIdempotencyKey { reason: String },
+
+    /// Запрос с этим ключом идемпотентности ещё выполняется
+    #[error("A request with this idempotency key is still in progress")]
+    IdempotencyKeyInProgress,
+
+    /// Ключ идемпотентности уже использован для запроса с другим телом
+    #[error("Idempotency key was already used for a different request")]
+    IdempotencyKeyReused,
+
     /// Запрещённое действие (например, редактирование чужого поста)
     #[error("Forbidden: {reason}")]
     Forbidden { reason: String },
 
+    /// Превышена квота пользователя или ограничение размера поста; `retry_at` - когда
+    /// квота восстановится, если ожидание поможет
+    #[error("Quota exceeded: {reason}")]
+    QuotaExceeded {
+        reason: String,
+        retry_at: Option<chrono::DateTime<chrono::Utc>>,
+    },
+
+    /// Ошибка хранилища файлов
+    #[error("Storage error: {0}")]
+    StorageError(String),
+
+    /// Ошибка отправки письма
+    #[error("Email error: {0}")]
+    EmailError(String),
+
+    /// Ошибка кэша
+    #[error("Cache error: {0}")]
+    CacheError(String),
+
     /// Ошибка на уровне репозитория (БД)
     #[error("Repository error: {0}")]
     RepositoryError(String),
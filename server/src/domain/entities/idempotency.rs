@@ -0,0 +1,37 @@
+use crate::domain::entities::errors::{DomainError, DomainResult};
+
+/// Максимальная длина ключа идемпотентности
+pub const IDEMPOTENCY_KEY_MAX_LEN: usize = 255;
+
+/// Ответ, сохранённый для повторов запроса с тем же ключом идемпотентности.
+#[derive(Debug, Clone)]
+pub struct StoredResponse {
+    /// HTTP статус ответа
+    pub status: u16,
+    /// Тело ответа (JSON)
+    pub body: String,
+}
+
+/// Запрос, выполненный или выполняющийся с ключом идемпотентности.
+#[derive(Debug, Clone)]
+pub struct IdempotencyRecord {
+    /// SHA-256 отпечаток тела запроса
+    pub request_hash: String,
+    /// Сохранённый ответ; `None`, пока первый запрос ещё выполняется
+    pub response: Option<StoredResponse>,
+}
+
+/// Проверяет ключ идемпотентности: от 1 до [`IDEMPOTENCY_KEY_MAX_LEN`] видимых ASCII-символов.
+pub fn validate_idempotency_key(key: &str) -> DomainResult<()> {
+    if key.is_empty() || key.len() > IDEMPOTENCY_KEY_MAX_LEN {
+        return Err(DomainError::InvalidIdempotencyKey {
+            reason: format!("key must be between 1 and {IDEMPOTENCY_KEY_MAX_LEN} characters"),
+        });
+    }
+    if !key.bytes().all(|byte| byte.is_ascii_graphic()) {
+        return Err(DomainError::InvalidIdempotencyKey {
+            reason: "key may contain only visible ASCII characters".to_string(),
+        });
+    }
+    Ok(())
+}
@@ -1,3 +1,13 @@
+pub mod admin;
+pub mod attachment;
+pub mod audit;
+pub mod blog;
+pub mod category;
+pub mod digest;
 pub mod errors;
+pub mod idempotency;
+pub mod oauth;
 pub mod post;
+pub mod report;
+pub mod session;
 pub mod user;
@@ -0,0 +1,40 @@
+/// Внешний провайдер входа (OAuth2 / OpenID Connect).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OAuthProviderKind {
+    /// Google (OpenID Connect)
+    Google,
+    /// GitHub (OAuth2)
+    Github,
+}
+
+impl OAuthProviderKind {
+    /// Строковое представление провайдера (сегмент пути и значение `oauth_identities.provider`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OAuthProviderKind::Google => "google",
+            OAuthProviderKind::Github => "github",
+        }
+    }
+
+    /// Разбирает провайдера из строкового представления
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "google" => Some(OAuthProviderKind::Google),
+            "github" => Some(OAuthProviderKind::Github),
+            _ => None,
+        }
+    }
+}
+
+/// Пользователь в системе провайдера, полученный после входа у него.
+#[derive(Debug, Clone)]
+pub struct OAuthProfile {
+    /// Постоянный ID пользователя у провайдера (`sub` в OpenID Connect)
+    pub subject: String,
+    /// Email пользователя, если провайдер его сообщил
+    pub email: Option<String>,
+    /// Подтвердил ли провайдер, что email принадлежит пользователю
+    pub email_verified: bool,
+    /// Желаемое имя пользователя (логин у провайдера или часть email до `@`)
+    pub username_hint: Option<String>,
+}
@@ -1,5 +1,183 @@
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::errors::{DomainError, DomainResult};
+
+/// Максимальное количество тегов у поста
+pub const MAX_TAGS_PER_POST: usize = 10;
+/// Максимальная длина тега (ограничение колонки `post_tags.tag`)
+pub const TAG_MAX_LEN: usize = 32;
+/// Максимальное количество постов в одной пакетной операции
+pub const MAX_POSTS_PER_BATCH: usize = 100;
+/// Максимальная длина описания поста, заданного автором
+pub const SUMMARY_MAX_LEN: usize = 500;
+/// Длина описания, составленного из начала содержимого, в символах
+pub const GENERATED_SUMMARY_LEN: usize = 200;
+
+/// Статус публикации поста.
+///
+/// Черновики видны только автору, опубликованные посты - всем.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PostStatus {
+    /// Черновик
+    Draft,
+    /// Опубликованный пост
+    Published,
+}
+
+impl PostStatus {
+    /// Строковое представление статуса (значение колонки `posts.status`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostStatus::Draft => "draft",
+            PostStatus::Published => "published",
+        }
+    }
+
+    /// Разбирает статус из строкового представления
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "draft" => Some(PostStatus::Draft),
+            "published" => Some(PostStatus::Published),
+            _ => None,
+        }
+    }
+}
+
+/// Поле, по которому сортируется список постов.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PostSortField {
+    /// Время создания
+    #[default]
+    CreatedAt,
+    /// Время последнего обновления
+    UpdatedAt,
+    /// Заголовок
+    Title,
+}
+
+impl PostSortField {
+    /// Строковое представление поля (имя колонки `posts`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PostSortField::CreatedAt => "created_at",
+            PostSortField::UpdatedAt => "updated_at",
+            PostSortField::Title => "title",
+        }
+    }
+}
+
+/// Направление сортировки.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    /// По возрастанию
+    Asc,
+    /// По убыванию
+    #[default]
+    Desc,
+}
+
+impl SortOrder {
+    /// Строковое представление направления
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+}
+
+/// Порядок сортировки списка постов; по умолчанию сначала идут новые посты.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PostSort {
+    pub field: PostSortField,
+    pub order: SortOrder,
+}
+
+/// Необязательные части постов, которые читаются из хранилища для списка.
+///
+/// Клиенту, которому нужны, например, только заголовки, не нужно содержимое, теги
+/// и лайки: хранилище может не читать их и оставить пустыми. По умолчанию читается всё.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostFields {
+    /// Содержимое и его HTML; нужно и для описания, составляемого из содержимого
+    pub content: bool,
+    pub tags: bool,
+    pub attachments: bool,
+    /// Число лайков и отметка лайка зрителя
+    pub likes: bool,
+}
+
+impl PostFields {
+    /// Все части поста
+    pub const ALL: Self = Self {
+        content: true,
+        tags: true,
+        attachments: true,
+        likes: true,
+    };
+    /// Только заголовок, автор, статус и даты
+    pub const NONE: Self = Self {
+        content: false,
+        tags: false,
+        attachments: false,
+        likes: false,
+    };
+}
+
+impl Default for PostFields {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// Позиция в списке постов для постраничного обхода по курсору (keyset).
+///
+/// Указывает на последний полученный пост в порядке по умолчанию (сначала новые,
+/// при равном времени - по возрастанию ID). В отличие от номера страницы позиция
+/// не сдвигается, когда в начало списка добавляются новые посты.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostCursor {
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub id: Uuid,
+}
+
+impl PostCursor {
+    /// Позиция сразу после поста `post`
+    pub fn after(post: &Post) -> Self {
+        Self {
+            created_at: post.created_at,
+            id: post.uuid,
+        }
+    }
+
+    /// Непрозрачная строка курсора для клиента: время создания в микросекундах и ID в hex
+    pub fn encode(&self) -> String {
+        let mut bytes = self.created_at.timestamp_micros().to_be_bytes().to_vec();
+        bytes.extend_from_slice(self.id.as_bytes());
+        hex::encode(bytes)
+    }
+
+    /// Разбирает строку, полученную из [`PostCursor::encode`].
+    ///
+    /// # Ошибки
+    ///
+    /// [`DomainError::InvalidCursor`], если строка повреждена.
+    pub fn decode(value: &str) -> DomainResult<Self> {
+        let invalid = || DomainError::InvalidCursor {
+            reason: "malformed cursor".to_string(),
+        };
+        let bytes = hex::decode(value).map_err(|_| invalid())?;
+        let (micros, id) = bytes.split_at_checked(8).ok_or_else(invalid)?;
+        let micros = i64::from_be_bytes(micros.try_into().map_err(|_| invalid())?);
+        Ok(Self {
+            created_at: chrono::DateTime::from_timestamp_micros(micros).ok_or_else(invalid)?,
+            id: Uuid::from_slice(id).map_err(|_| invalid())?,
+        })
+    }
+}
+
 /// Доменная сущность поста блога.
 ///
 /// Представляет публикацию в блоге с заголовком, содержимым и метаданными.
@@ -8,16 +186,193 @@ use uuid::Uuid;
 ///
 /// * `uuid` - Уникальный идентификатор поста
 /// * `title` - Заголовок поста
-/// * `content` - Содержимое поста
+/// * `content` - Содержимое поста (Markdown)
+/// * `content_html` - Содержимое, отрендеренное в HTML; `None`, если рендеринг отключён
+/// * `summary` - Краткое описание, заданное автором; `None`, если автор его не задал
 /// * `author_id` - ID автора (ссылка на User)
+/// * `author_username` - Имя автора (заполняется репозиторием из таблицы пользователей)
+/// * `author_avatar_url` - Ссылка на аватар автора, если она указана в профиле
+/// * `tags` - Теги поста в каноническом виде (см. [`normalize_tags`])
+/// * `category_id` - ID категории поста; `None`, если категория не выбрана
+/// * `attachments` - Ссылки на прикреплённые файлы в порядке загрузки
+/// * `status` - Статус публикации (черновик или опубликован)
+/// * `like_count` - Количество лайков
+/// * `liked_by_me` - Лайкнул ли пост пользователь, от имени которого он запрошен
 /// * `created_at` - Временная метка создания
 /// * `updated_at` - Временная метка последнего обновления
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Post {
     pub uuid: Uuid,
     pub title: String,
     pub content: String,
+    pub content_html: Option<String>,
+    /// Записи кэша, сохранённые до появления описаний, читаются как посты без описания
+    #[serde(default)]
+    pub summary: Option<String>,
     pub author_id: Uuid,
+    pub author_username: String,
+    pub author_avatar_url: Option<String>,
+    pub tags: Vec<String>,
+    /// Записи кэша, сохранённые до появления категорий, читаются как посты без категории
+    #[serde(default)]
+    pub category_id: Option<Uuid>,
+    /// Блог поста; `None` - общая лента сервера. Записи кэша, сохранённые до появления
+    /// блогов, читаются как посты общей ленты
+    #[serde(default)]
+    pub blog_id: Option<Uuid>,
+    pub attachments: Vec<String>,
+    pub status: PostStatus,
+    pub like_count: i64,
+    pub liked_by_me: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
+
+impl Post {
+    /// Может ли пользователь `viewer` видеть пост: черновик доступен только автору
+    pub fn is_visible_to(&self, viewer: Option<Uuid>) -> bool {
+        self.status == PostStatus::Published || viewer == Some(self.author_id)
+    }
+
+    /// Описание поста для списков: заданное автором или начало содержимого
+    pub fn summary_or_excerpt(&self) -> String {
+        match &self.summary {
+            Some(summary) => summary.clone(),
+            None => excerpt(&self.content, GENERATED_SUMMARY_LEN),
+        }
+    }
+}
+
+/// Первые `len` символов текста одной строкой: пробелы и переводы строк схлопываются,
+/// обрезанный текст заканчивается многоточием
+pub fn excerpt(text: &str, len: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(len) {
+        Some((end, _)) => format!("{}…", text[..end].trim_end()),
+        None => text,
+    }
+}
+
+/// Проверяет описание поста, заданное автором.
+///
+/// Описание обрезается по краям; пустое описание означает, что его нет.
+///
+/// # Ошибки
+///
+/// [`DomainError::InvalidSummary`], если описание длиннее [`SUMMARY_MAX_LEN`] символов.
+pub fn normalize_summary(summary: Option<String>) -> DomainResult<Option<String>> {
+    let Some(summary) = summary else {
+        return Ok(None);
+    };
+    let summary = summary.trim();
+    if summary.chars().count() > SUMMARY_MAX_LEN {
+        return Err(DomainError::InvalidSummary {
+            reason: format!("summary is longer than {SUMMARY_MAX_LEN} characters"),
+        });
+    }
+    Ok((!summary.is_empty()).then(|| summary.to_string()))
+}
+
+/// Приводит теги к каноническому виду и проверяет ограничения.
+///
+/// Теги обрезаются по краям и переводятся в нижний регистр, пустые теги
+/// отбрасываются, дубликаты удаляются с сохранением порядка.
+///
+/// # Ошибки
+///
+/// [`DomainError::InvalidTags`], если тегов больше [`MAX_TAGS_PER_POST`]
+/// или какой-либо тег длиннее [`TAG_MAX_LEN`] символов.
+pub fn normalize_tags(tags: Vec<String>) -> DomainResult<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || normalized.contains(&tag) {
+            continue;
+        }
+        if tag.chars().count() > TAG_MAX_LEN {
+            return Err(DomainError::InvalidTags {
+                reason: format!("tag '{tag}' is longer than {TAG_MAX_LEN} characters"),
+            });
+        }
+        normalized.push(tag);
+    }
+
+    if normalized.len() > MAX_TAGS_PER_POST {
+        return Err(DomainError::InvalidTags {
+            reason: format!("a post can have at most {MAX_TAGS_PER_POST} tags"),
+        });
+    }
+
+    Ok(normalized)
+}
+
+/// Проверяет размер пакетной операции над постами.
+///
+/// # Ошибки
+///
+/// [`DomainError::InvalidBatch`], если пакет пуст или содержит больше
+/// [`MAX_POSTS_PER_BATCH`] постов.
+pub fn check_batch_size(count: usize) -> DomainResult<()> {
+    if count == 0 || count > MAX_POSTS_PER_BATCH {
+        return Err(DomainError::InvalidBatch {
+            reason: format!("a batch must contain between 1 and {MAX_POSTS_PER_BATCH} posts"),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor() -> PostCursor {
+        PostCursor {
+            created_at: chrono::DateTime::from_timestamp_micros(1_700_000_000_123_456).unwrap(),
+            id: Uuid::now_v7(),
+        }
+    }
+
+    fn assert_invalid(value: &str) {
+        assert!(
+            matches!(
+                PostCursor::decode(value),
+                Err(DomainError::InvalidCursor { .. })
+            ),
+            "cursor {value:?} must be rejected"
+        );
+    }
+
+    #[test]
+    fn test_cursor_round_trips() {
+        let cursor = cursor();
+        assert_eq!(PostCursor::decode(&cursor.encode()).unwrap(), cursor);
+    }
+
+    #[test]
+    fn test_garbage_cursor_is_rejected() {
+        assert_invalid("");
+        assert_invalid("not-a-cursor");
+        assert_invalid("eyJpZCI6MX0=");
+        assert_invalid("курсор");
+    }
+
+    #[test]
+    fn test_truncated_cursor_is_rejected() {
+        let encoded = cursor().encode();
+        // Нечётная длина hex, только время без ID и неполный ID
+        assert_invalid(&encoded[..encoded.len() - 1]);
+        assert_invalid(&encoded[..16]);
+        assert_invalid(&encoded[..encoded.len() - 2]);
+    }
+
+    #[test]
+    fn test_tampered_cursor_is_rejected() {
+        let encoded = cursor().encode();
+        // Лишний байт после ID
+        assert_invalid(&format!("{encoded}00"));
+        // Время за пределами представимого диапазона
+        assert_invalid(&format!("7fffffffffffffff{}", &encoded[16..]));
+        // Не-hex символ вместо одной цифры
+        assert_invalid(&format!("{}z", &encoded[..encoded.len() - 1]));
+    }
+}
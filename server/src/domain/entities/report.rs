@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::errors::{DomainError, DomainResult};
+
+/// Максимальная длина пояснения к жалобе (ограничение колонки `reports.details`)
+pub const REPORT_DETAILS_MAX_LEN: usize = 1000;
+
+/// Причина жалобы на пост.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportReason {
+    /// Спам или реклама
+    Spam,
+    /// Оскорбления и травля
+    Harassment,
+    /// Язык вражды
+    HateSpeech,
+    /// Заведомо ложная информация
+    Misinformation,
+    /// Неприемлемое содержимое
+    Inappropriate,
+    /// Другая причина (описывается в пояснении)
+    Other,
+}
+
+impl ReportReason {
+    /// Строковое представление причины (значение колонки `reports.reason`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportReason::Spam => "spam",
+            ReportReason::Harassment => "harassment",
+            ReportReason::HateSpeech => "hate_speech",
+            ReportReason::Misinformation => "misinformation",
+            ReportReason::Inappropriate => "inappropriate",
+            ReportReason::Other => "other",
+        }
+    }
+
+    /// Разбирает причину из строкового представления
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "spam" => Some(ReportReason::Spam),
+            "harassment" => Some(ReportReason::Harassment),
+            "hate_speech" => Some(ReportReason::HateSpeech),
+            "misinformation" => Some(ReportReason::Misinformation),
+            "inappropriate" => Some(ReportReason::Inappropriate),
+            "other" => Some(ReportReason::Other),
+            _ => None,
+        }
+    }
+}
+
+/// Состояние жалобы.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportStatus {
+    /// Ждёт решения модератора
+    Open,
+    /// Модератор скрыл пост
+    Hidden,
+    /// Модератор отклонил жалобу
+    Dismissed,
+}
+
+impl ReportStatus {
+    /// Строковое представление состояния (значение колонки `reports.status`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportStatus::Open => "open",
+            ReportStatus::Hidden => "hidden",
+            ReportStatus::Dismissed => "dismissed",
+        }
+    }
+
+    /// Разбирает состояние из строкового представления
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "open" => Some(ReportStatus::Open),
+            "hidden" => Some(ReportStatus::Hidden),
+            "dismissed" => Some(ReportStatus::Dismissed),
+            _ => None,
+        }
+    }
+}
+
+/// Решение модератора по жалобе.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportResolution {
+    /// Скрыть пост: он переносится в корзину, и автор не может его восстановить.
+    /// Закрывает все открытые жалобы на пост.
+    HidePost,
+    /// Отклонить жалобу, оставив пост как есть
+    Dismiss,
+}
+
+impl ReportResolution {
+    /// Состояние, в которое решение переводит жалобу
+    pub fn status(&self) -> ReportStatus {
+        match self {
+            ReportResolution::HidePost => ReportStatus::Hidden,
+            ReportResolution::Dismiss => ReportStatus::Dismissed,
+        }
+    }
+}
+
+/// Жалоба пользователя на пост.
+///
+/// # Поля
+///
+/// * `id` - Уникальный идентификатор жалобы (UUID v7)
+/// * `post_id` - ID поста
+/// * `reporter_id` - ID пожаловавшегося пользователя
+/// * `reason` - Причина жалобы
+/// * `details` - Необязательное пояснение
+/// * `status` - Состояние жалобы
+/// * `resolved_by` - ID модератора, принявшего решение
+/// * `resolved_at` - Время решения; `None` для открытых жалоб
+/// * `created_at` - Временная метка жалобы
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub id: Uuid,
+    pub post_id: Uuid,
+    pub reporter_id: Uuid,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub status: ReportStatus,
+    pub resolved_by: Option<Uuid>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Убирает пробелы по краям пояснения к жалобе и проверяет его длину.
+///
+/// Пустое пояснение считается отсутствующим.
+pub fn normalize_report_details(details: Option<String>) -> DomainResult<Option<String>> {
+    let Some(details) = details else {
+        return Ok(None);
+    };
+    let details = details.trim();
+    if details.is_empty() {
+        return Ok(None);
+    }
+    if details.chars().count() > REPORT_DETAILS_MAX_LEN {
+        return Err(DomainError::InvalidReport {
+            reason: format!("details must be at most {REPORT_DETAILS_MAX_LEN} characters"),
+        });
+    }
+    Ok(Some(details.to_string()))
+}
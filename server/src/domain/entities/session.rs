@@ -0,0 +1,43 @@
+use std::net::IpAddr;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Сколько символов `User-Agent` сохраняется (длина колонки `sessions.user_agent`)
+const USER_AGENT_MAX_CHARS: usize = 512;
+
+/// Сеанс входа: устройство, которому выдан refresh токен.
+///
+/// Сеанс создаётся при входе и продлевается при каждом обновлении токена.
+/// После удаления сеанса его refresh токены больше не обновляются.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// `User-Agent` клиента при последнем использовании
+    pub user_agent: Option<String>,
+    /// IP-адрес клиента при последнем использовании
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Время последнего входа или обновления токена
+    pub last_used_at: DateTime<Utc>,
+    /// Когда истекает последний выданный refresh токен
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Сведения о клиенте, от которого пришёл запрос на вход или обновление токена.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceInfo {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl DeviceInfo {
+    /// Собирает сведения о клиенте; слишком длинный `User-Agent` обрезается.
+    pub fn new(user_agent: Option<&str>, ip_address: Option<IpAddr>) -> Self {
+        Self {
+            user_agent: user_agent.map(|value| value.chars().take(USER_AGENT_MAX_CHARS).collect()),
+            ip_address: ip_address.map(|ip| ip.to_string()),
+        }
+    }
+}
@@ -1,5 +1,95 @@
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::errors::{DomainError, DomainResult};
+
+/// Максимальная длина отображаемого имени (ограничение колонки `users.display_name`)
+pub const DISPLAY_NAME_MAX_LEN: usize = 64;
+/// Максимальная длина описания профиля (ограничение колонки `users.bio`)
+pub const BIO_MAX_LEN: usize = 1000;
+/// Максимальная длина ссылки на аватар (ограничение колонки `users.avatar_url`)
+pub const AVATAR_URL_MAX_LEN: usize = 512;
+
+/// Роль пользователя.
+///
+/// Роль передаётся в claims JWT токена; токены без роли считаются
+/// выданными обычному пользователю.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Обычный пользователь
+    #[default]
+    User,
+    /// Модератор
+    Moderator,
+    /// Администратор
+    Admin,
+}
+
+impl Role {
+    /// Строковое представление роли (значение колонки `users.role`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::User => "user",
+            Role::Moderator => "moderator",
+            Role::Admin => "admin",
+        }
+    }
+
+    /// Разбирает роль из строкового представления
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "user" => Some(Role::User),
+            "moderator" => Some(Role::Moderator),
+            "admin" => Some(Role::Admin),
+            _ => None,
+        }
+    }
+
+    /// Может ли пользователь с этой ролью изменять и удалять чужие посты
+    pub fn can_manage_any_post(&self) -> bool {
+        *self == Role::Admin
+    }
+
+    /// Может ли пользователь с этой ролью просматривать журнал аудита
+    pub fn can_view_audit_log(&self) -> bool {
+        *self == Role::Admin
+    }
+
+    /// Может ли пользователь с этой ролью разбирать жалобы и скрывать посты
+    pub fn can_moderate(&self) -> bool {
+        matches!(self, Role::Moderator | Role::Admin)
+    }
+
+    /// Может ли пользователь с этой ролью управлять пользователями и видеть статистику системы
+    pub fn can_administer(&self) -> bool {
+        *self == Role::Admin
+    }
+}
+
+/// Вид идентификатора, которым пользователь назвал себя при входе.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierKind {
+    /// Имя пользователя
+    Username,
+    /// Email адрес
+    Email,
+}
+
+impl IdentifierKind {
+    /// Определяет вид идентификатора.
+    ///
+    /// Имена пользователей не могут содержать `@`, поэтому любой идентификатор
+    /// с этим символом считается email.
+    pub fn detect(identifier: &str) -> Self {
+        if identifier.contains('@') {
+            IdentifierKind::Email
+        } else {
+            IdentifierKind::Username
+        }
+    }
+}
+
 /// Доменная сущность пользователя системы.
 ///
 /// # Поля
@@ -8,6 +98,7 @@ use uuid::Uuid;
 /// * `username` - Уникальное имя пользователя для аутентификации и отображения
 /// * `email` - Email пользователя (уникальный, используется для входа)
 /// * `password_hash` - Хэш пароля (Argon2id)
+/// * `role` - Роль пользователя
 /// * `created_at` - Временная метка создания пользователя
 #[derive(Debug, Clone)]
 pub struct User {
@@ -15,6 +106,7 @@ pub struct User {
     pub username: String,
     pub email: String,
     pub password_hash: String,
+    pub role: Role,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -27,12 +119,14 @@ impl User {
     /// * `username` - Имя пользователя (должно быть уникальным в системе)
     /// * `email` - Email адрес (должен быть уникальным в системе)
     /// * `password_hash` - Предварительно захэшированный пароль (с использованием Argon2id)
+    /// * `role` - Роль пользователя
     /// * `created_at` - Временная метка создания
     pub fn new(
         id: Uuid,
         username: String,
         email: String,
         password_hash: String,
+        role: Role,
         created_at: chrono::DateTime<chrono::Utc>,
     ) -> Self {
         Self {
@@ -40,7 +134,101 @@ impl User {
             username,
             email,
             password_hash,
+            role,
             created_at,
         }
     }
 }
+
+/// Секрет TOTP пользователя для двухфакторной аутентификации.
+///
+/// Секрет сохраняется при подключении 2FA, но проверяется при входе только
+/// после подтверждения первым кодом (`enabled`).
+#[derive(Debug, Clone)]
+pub struct UserTotp {
+    pub user_id: Uuid,
+    /// Секрет в кодировке Base32
+    pub secret: String,
+    pub enabled: bool,
+}
+
+/// Публичный профиль пользователя.
+///
+/// # Поля
+///
+/// * `id` - Идентификатор пользователя
+/// * `username` - Имя пользователя
+/// * `display_name` - Отображаемое имя (если задано)
+/// * `bio` - Описание профиля (если задано)
+/// * `avatar_url` - Ссылка на изображение аватара (если задана)
+/// * `created_at` - Временная метка регистрации
+#[derive(Debug, Clone)]
+pub struct UserProfile {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Редактируемые поля профиля в каноническом виде (см. [`ProfileUpdate::normalize`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileUpdate {
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+impl ProfileUpdate {
+    /// Приводит поля профиля к каноническому виду и проверяет ограничения.
+    ///
+    /// Значения обрезаются по краям, пустые строки считаются отсутствующим значением.
+    ///
+    /// # Ошибки
+    ///
+    /// [`DomainError::InvalidProfile`], если поле длиннее допустимого
+    /// или ссылка на аватар не начинается с `http://` или `https://`.
+    pub fn normalize(
+        display_name: Option<String>,
+        bio: Option<String>,
+        avatar_url: Option<String>,
+    ) -> DomainResult<Self> {
+        let display_name = normalize_field("display_name", display_name, DISPLAY_NAME_MAX_LEN)?;
+        let bio = normalize_field("bio", bio, BIO_MAX_LEN)?;
+        let avatar_url = normalize_field("avatar_url", avatar_url, AVATAR_URL_MAX_LEN)?;
+
+        if let Some(url) = &avatar_url
+            && !(url.starts_with("http://") || url.starts_with("https://"))
+        {
+            return Err(DomainError::InvalidProfile {
+                reason: "avatar_url must be an http(s) URL".to_string(),
+            });
+        }
+
+        Ok(Self {
+            display_name,
+            bio,
+            avatar_url,
+        })
+    }
+}
+
+fn normalize_field(
+    name: &str,
+    value: Option<String>,
+    max_len: usize,
+) -> DomainResult<Option<String>> {
+    let Some(value) = value.map(|value| value.trim().to_string()) else {
+        return Ok(None);
+    };
+    if value.is_empty() {
+        return Ok(None);
+    }
+    if value.chars().count() > max_len {
+        return Err(DomainError::InvalidProfile {
+            reason: format!("{name} must be at most {max_len} characters"),
+        });
+    }
+    Ok(Some(value))
+}
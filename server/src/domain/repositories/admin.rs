@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    admin::{SystemStats, UserSummary},
+    errors::DomainResult,
+};
+
+/// Хранилище данных для администрирования.
+#[async_trait::async_trait]
+pub trait AdminRepository: Send + Sync {
+    /// Возвращает страницу пользователей в порядке регистрации
+    async fn list_users(&self, page: u32, page_size: u32) -> DomainResult<Vec<UserSummary>>;
+    /// Возвращает количество пользователей
+    async fn count_users(&self) -> DomainResult<u64>;
+    /// Отключает пользователя (`disabled_at` задан) или включает его снова (`None`).
+    ///
+    /// Возвращает `false`, если пользователя нет.
+    async fn set_user_disabled(
+        &self,
+        user_id: Uuid,
+        disabled_at: Option<DateTime<Utc>>,
+    ) -> DomainResult<bool>;
+    /// Возвращает сводные показатели; новыми считаются регистрации не раньше `signups_since`
+    async fn get_system_stats(&self, signups_since: DateTime<Utc>) -> DomainResult<SystemStats>;
+}
@@ -0,0 +1,20 @@
+use crate::domain::entities::{
+    audit::{AuditEntry, AuditFilter},
+    errors::DomainResult,
+};
+
+/// Хранилище журнала аудита.
+#[async_trait::async_trait]
+pub trait AuditRepository: Send + Sync {
+    /// Добавляет запись в журнал
+    async fn record_audit_entry(&self, entry: &AuditEntry) -> DomainResult<()>;
+    /// Возвращает страницу записей, подходящих под `filter`, от новых к старым
+    async fn get_audit_entries(
+        &self,
+        filter: &AuditFilter,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<Vec<AuditEntry>>;
+    /// Возвращает количество записей, подходящих под `filter`
+    async fn count_audit_entries(&self, filter: &AuditFilter) -> DomainResult<u64>;
+}
@@ -0,0 +1,20 @@
+use uuid::Uuid;
+
+use crate::domain::entities::{blog::Blog, errors::DomainResult};
+
+/// Хранилище блогов.
+#[async_trait::async_trait]
+pub trait BlogRepository: Send + Sync {
+    /// Возвращает все блоги, упорядоченные по адресу
+    async fn list_blogs(&self) -> DomainResult<Vec<Blog>>;
+    /// Возвращает блог по адресу
+    async fn get_blog_by_slug(&self, slug: &str) -> DomainResult<Option<Blog>>;
+    /// Возвращает блог по ID
+    async fn get_blog(&self, blog_id: Uuid) -> DomainResult<Option<Blog>>;
+    /// Сохраняет новый блог
+    async fn create_blog(&self, blog: &Blog) -> DomainResult<()>;
+    /// Меняет название блога
+    async fn update_blog_name(&self, blog_id: Uuid, name: &str) -> DomainResult<()>;
+    /// Удаляет блог; его посты остаются в общей ленте сервера
+    async fn delete_blog(&self, blog_id: Uuid) -> DomainResult<()>;
+}
@@ -0,0 +1,17 @@
+use crate::domain::entities::errors::DomainResult;
+
+/// Хранилище кэша "ключ - строка" с ограниченным временем жизни записей.
+///
+/// Реализации находятся в [`crate::infrastructure::cache`]: Redis и пустой кэш,
+/// который используется, если Redis не настроен.
+#[async_trait::async_trait]
+pub trait CacheRepository: Send + Sync {
+    /// Возвращает значение по ключу; `None`, если записи нет или её срок истёк
+    async fn get(&self, key: &str) -> DomainResult<Option<String>>;
+    /// Сохраняет значение на время жизни, заданное в конфигурации кэша
+    async fn set(&self, key: &str, value: &str) -> DomainResult<()>;
+    /// Удаляет записи по ключам
+    async fn delete(&self, keys: &[String]) -> DomainResult<()>;
+    /// Увеличивает счётчик на единицу и возвращает новое значение
+    async fn increment(&self, key: &str) -> DomainResult<i64>;
+}
@@ -0,0 +1,14 @@
+use uuid::Uuid;
+
+use crate::domain::entities::{category::Category, errors::DomainResult};
+
+/// Хранилище категорий постов.
+#[async_trait::async_trait]
+pub trait CategoryRepository: Send + Sync {
+    /// Возвращает все категории, упорядоченные по имени
+    async fn list_categories(&self) -> DomainResult<Vec<Category>>;
+    /// Возвращает категорию по ID
+    async fn get_category(&self, category_id: Uuid) -> DomainResult<Option<Category>>;
+    /// Сохраняет новую категорию
+    async fn create_category(&self, category: &Category) -> DomainResult<()>;
+}
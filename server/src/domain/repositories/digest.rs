@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    digest::{DigestFrequency, DigestSubscription},
+    errors::DomainResult,
+};
+
+/// Хранилище подписок на email-дайджест.
+#[async_trait::async_trait]
+pub trait DigestRepository: Send + Sync {
+    /// Возвращает подписку пользователя, если он подписан
+    async fn get_digest_subscription(
+        &self,
+        user_id: Uuid,
+    ) -> DomainResult<Option<DigestSubscription>>;
+    /// Подписывает пользователя или меняет частоту существующей подписки.
+    ///
+    /// Новая подписка считается отправленной в `now`: первый дайджест включает посты,
+    /// опубликованные после подписки. У существующей меняется только частота.
+    async fn save_digest_subscription(
+        &self,
+        user_id: Uuid,
+        frequency: DigestFrequency,
+        now: DateTime<Utc>,
+    ) -> DomainResult<()>;
+    /// Удаляет подписку; возвращает `false`, если пользователь не был подписан
+    async fn delete_digest_subscription(&self, user_id: Uuid) -> DomainResult<bool>;
+    /// Возвращает подписки с частотой `frequency`, предыдущий дайджест которых отправлен
+    /// не позже `sent_before`. Подписки отключённых пользователей пропускаются
+    async fn get_due_digest_subscriptions(
+        &self,
+        frequency: DigestFrequency,
+        sent_before: DateTime<Utc>,
+    ) -> DomainResult<Vec<DigestSubscription>>;
+    /// Запоминает время отправки дайджеста
+    async fn mark_digest_sent(&self, user_id: Uuid, sent_at: DateTime<Utc>) -> DomainResult<()>;
+}
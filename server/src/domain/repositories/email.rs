@@ -0,0 +1,26 @@
+use crate::domain::entities::errors::DomainResult;
+
+/// Письмо с текстом без разметки.
+#[derive(Debug, Clone)]
+pub struct EmailMessage {
+    /// Адрес получателя
+    pub to: String,
+    /// Тема письма
+    pub subject: String,
+    /// Текст письма
+    pub body: String,
+    /// Ссылка для отписки (заголовок `List-Unsubscribe`), если письмо - рассылка
+    pub unsubscribe_url: Option<String>,
+    /// Текст содержит секрет (например, токен сброса пароля) и не должен попадать в лог
+    pub confidential: bool,
+}
+
+/// Отправка писем.
+///
+/// Реализации находятся в [`crate::infrastructure::email`]: SMTP и запись писем в лог
+/// для разработки.
+#[async_trait::async_trait]
+pub trait EmailSender: Send + Sync {
+    /// Отправляет письмо; ошибка доставки возвращается как `EmailError`
+    async fn send(&self, message: EmailMessage) -> DomainResult<()>;
+}
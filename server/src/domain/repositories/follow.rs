@@ -0,0 +1,14 @@
+use uuid::Uuid;
+
+use crate::domain::entities::errors::DomainResult;
+
+/// Хранилище подписок пользователей на авторов.
+#[async_trait::async_trait]
+pub trait FollowRepository: Send + Sync {
+    /// Подписывает `follower_id` на `followee_id`; повторная подписка игнорируется
+    async fn follow_user(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<()>;
+    /// Отменяет подписку `follower_id` на `followee_id`
+    async fn unfollow_user(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<()>;
+    /// Подписан ли `follower_id` на `followee_id`
+    async fn is_following(&self, follower_id: Uuid, followee_id: Uuid) -> DomainResult<bool>;
+}
@@ -1 +1,55 @@
+pub mod admin;
+pub mod audit;
+pub mod blog;
+pub mod cache;
+pub mod category;
+pub mod digest;
+pub mod email;
+pub mod follow;
+pub mod oauth;
+pub mod post;
 pub mod repo;
+pub mod report;
+pub mod storage;
+pub mod unit_of_work;
+
+use admin::AdminRepository;
+use audit::AuditRepository;
+use blog::BlogRepository;
+use category::CategoryRepository;
+use digest::DigestRepository;
+use follow::FollowRepository;
+use post::PostRepository;
+use repo::UserRepository;
+use report::ReportRepository;
+
+/// Все хранилища данных приложения в одном объекте.
+///
+/// Сервер работает с `Arc<dyn Repository>`, поэтому реализацию (PostgreSQL или память)
+/// можно выбрать при запуске.
+pub trait Repository:
+    UserRepository
+    + PostRepository
+    + AuditRepository
+    + AdminRepository
+    + ReportRepository
+    + FollowRepository
+    + DigestRepository
+    + CategoryRepository
+    + BlogRepository
+{
+}
+
+impl<
+    T: UserRepository
+        + PostRepository
+        + AuditRepository
+        + AdminRepository
+        + ReportRepository
+        + FollowRepository
+        + DigestRepository
+        + CategoryRepository
+        + BlogRepository,
+> Repository for T
+{
+}
@@ -0,0 +1,19 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::domain::entities::errors::DomainResult;
+use crate::domain::entities::oauth::{OAuthProfile, OAuthProviderKind};
+
+/// Настроенные провайдеры входа
+pub type OAuthProviders = HashMap<OAuthProviderKind, Arc<dyn OAuthProvider>>;
+
+/// Провайдер входа OAuth2 / OpenID Connect.
+///
+/// Реализации находятся в [`crate::infrastructure::oauth`]: Google и GitHub.
+#[async_trait::async_trait]
+pub trait OAuthProvider: Send + Sync {
+    /// Адрес страницы входа провайдера, на который перенаправляется пользователь
+    fn authorization_url(&self, state: &str) -> String;
+    /// Обменивает код авторизации на токен провайдера и возвращает профиль пользователя
+    async fn fetch_profile(&self, code: &str) -> DomainResult<OAuthProfile>;
+}
@@ -0,0 +1,120 @@
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    attachment::Attachment,
+    errors::DomainResult,
+    post::{Post, PostCursor, PostFields, PostSort, PostStatus},
+};
+
+#[async_trait::async_trait]
+pub trait PostRepository: Send + Sync {
+    /// Возвращает опубликованные посты и черновики пользователя `viewer` в порядке `sort`;
+    /// `category` выбирает посты категории и всех её подкатегорий, `blog` - посты одного
+    /// блога. Части поста, не выбранные в `fields`, хранилище может не читать и оставить
+    /// пустыми
+    #[allow(clippy::too_many_arguments)]
+    async fn get_posts(
+        &self,
+        page: u32,
+        page_size: u32,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+        sort: PostSort,
+        fields: PostFields,
+    ) -> DomainResult<Vec<Post>>;
+    /// Возвращает до `limit` постов после позиции `cursor` (без неё - с начала списка)
+    /// в порядке по умолчанию: сначала новые, при равном времени - по возрастанию ID
+    #[allow(clippy::too_many_arguments)]
+    async fn get_posts_after(
+        &self,
+        cursor: Option<PostCursor>,
+        limit: u32,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+        fields: PostFields,
+    ) -> DomainResult<Vec<Post>>;
+    /// Возвращает до `limit` опубликованных постов, набравших больше всего лайков начиная
+    /// с `since`; при равном числе лайков сначала идут новые посты
+    async fn get_trending_posts(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<Vec<Post>>;
+    /// Возвращает до `limit` неудалённых постов, впервые опубликованных после `since`
+    /// и не позже `until`, от новых к старым
+    async fn get_posts_published_between(
+        &self,
+        since: chrono::DateTime<chrono::Utc>,
+        until: chrono::DateTime<chrono::Utc>,
+        limit: u32,
+    ) -> DomainResult<Vec<Post>>;
+    /// Возвращает опубликованные посты авторов, на которых подписан `follower_id`,
+    /// от новых к старым
+    async fn get_feed_posts(
+        &self,
+        follower_id: Uuid,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<Vec<Post>>;
+    /// Считает опубликованные посты авторов, на которых подписан `follower_id`
+    async fn count_feed_posts(&self, follower_id: Uuid) -> DomainResult<u64>;
+    /// Считает посты, видимые пользователю `viewer`
+    async fn count_posts(
+        &self,
+        tag: Option<&str>,
+        category: Option<Uuid>,
+        blog: Option<Uuid>,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<u64>;
+    /// Возвращает посты автора `author_id`, видимые пользователю `viewer`
+    async fn get_posts_by_author(
+        &self,
+        author_id: Uuid,
+        page: u32,
+        page_size: u32,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<Vec<Post>>;
+    /// Считает посты автора `author_id`, видимые пользователю `viewer`
+    async fn count_posts_by_author(
+        &self,
+        author_id: Uuid,
+        viewer: Option<Uuid>,
+    ) -> DomainResult<u64>;
+    /// Считает посты автора, созданные начиная с `since`, включая черновики и удалённые
+    async fn count_posts_created_since(
+        &self,
+        author_id: Uuid,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<u64>;
+    /// Возвращает неудалённый пост по ID; `viewer` нужен для вычисления `liked_by_me`
+    async fn get_post_by_id(&self, post_id: Uuid, viewer: Option<Uuid>) -> DomainResult<Post>;
+    /// Создаёт пост и прикрепляет к нему вложения `attachment_ids` автора
+    async fn create_post(&self, post: Post, attachment_ids: &[Uuid]) -> DomainResult<Post>;
+    /// Создаёт посты с их вложениями в одной транзакции: при ошибке не создаётся ни один
+    async fn create_posts(&self, posts: Vec<(Post, Vec<Uuid>)>) -> DomainResult<Vec<Post>>;
+    /// Обновляет пост; если передан `attachment_ids`, заменяет набор вложений
+    async fn update_post(&self, post: Post, attachment_ids: Option<&[Uuid]>) -> DomainResult<Post>;
+    async fn set_post_status(&self, post_id: Uuid, status: PostStatus) -> DomainResult<()>;
+    /// Ставит лайк посту; повторный лайк того же пользователя игнорируется
+    async fn like_post(&self, post_id: Uuid, user_id: Uuid) -> DomainResult<()>;
+    /// Снимает лайк пользователя с поста
+    async fn unlike_post(&self, post_id: Uuid, user_id: Uuid) -> DomainResult<()>;
+    /// Переносит пост в корзину, проставляя `deleted_at`
+    async fn delete_post(&self, post_id: Uuid) -> DomainResult<()>;
+    /// Переносит посты в корзину одной транзакцией
+    async fn delete_posts(&self, post_ids: &[Uuid]) -> DomainResult<()>;
+    /// Возвращает пост из корзины по ID
+    async fn get_deleted_post(&self, post_id: Uuid, viewer: Option<Uuid>) -> DomainResult<Post>;
+    /// Возвращает пост из корзины
+    async fn restore_post(&self, post_id: Uuid) -> DomainResult<()>;
+    /// Окончательно удаляет пост вместе с тегами и лайками
+    async fn purge_post(&self, post_id: Uuid) -> DomainResult<()>;
+
+    /// Сохраняет сведения о загруженном файле
+    async fn create_attachment(&self, attachment: &Attachment) -> DomainResult<()>;
+}
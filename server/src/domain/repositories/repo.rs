@@ -1,16 +1,148 @@
 use uuid::Uuid;
 
-use crate::domain::entities::{errors::DomainResult, post::Post, user::User};
+use crate::domain::entities::{
+    errors::DomainResult,
+    idempotency::{IdempotencyRecord, StoredResponse},
+    session::{DeviceInfo, Session},
+    user::{ProfileUpdate, User, UserProfile, UserTotp},
+};
+
+use super::unit_of_work::UnitOfWork;
 
 #[async_trait::async_trait]
 pub trait UserRepository: Send + Sync {
+    /// Открывает единицу работы для изменений, которые должны примениться вместе
+    async fn begin(&self) -> DomainResult<Box<dyn UnitOfWork>>;
+
     async fn create_user(&self, user: User) -> DomainResult<User>;
     async fn find_by_username(&self, username: &str) -> DomainResult<Option<User>>;
+    /// Ищет пользователя только по email
+    async fn find_by_email(&self, email: &str) -> DomainResult<Option<User>>;
+    async fn find_by_id(&self, user_id: Uuid) -> DomainResult<Option<User>>;
     async fn exists_by_username(&self, username: &str) -> DomainResult<bool>;
+    /// Возвращает публичный профиль пользователя
+    async fn get_user_profile(&self, user_id: Uuid) -> DomainResult<UserProfile>;
+    /// Заменяет редактируемые поля профиля и возвращает обновлённый профиль
+    async fn update_user_profile(
+        &self,
+        user_id: Uuid,
+        update: ProfileUpdate,
+    ) -> DomainResult<UserProfile>;
+
+    async fn revoke_token(
+        &self,
+        token_hash: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<()>;
+    async fn is_token_revoked(&self, token_hash: &str) -> DomainResult<bool>;
+
+    /// Сохраняет отпечаток токена сброса пароля пользователя
+    async fn create_password_reset_token(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<()>;
+    /// Удаляет действующий токен сброса пароля и возвращает ID его владельца
+    async fn consume_password_reset_token(&self, token_hash: &str) -> DomainResult<Option<Uuid>>;
+    /// Заменяет хэш пароля пользователя и удаляет его оставшиеся токены сброса
+    async fn update_password(&self, user_id: Uuid, password_hash: &str) -> DomainResult<()>;
+    /// Возвращает версию токенов пользователя (`None`, если пользователя нет)
+    async fn get_token_version(&self, user_id: Uuid) -> DomainResult<Option<i32>>;
+    /// Увеличивает версию токенов пользователя: все выданные ранее токены перестают действовать
+    async fn increment_token_version(&self, user_id: Uuid) -> DomainResult<()>;
+    /// Отключён ли пользователь администратором (`false`, если пользователя нет)
+    async fn is_user_disabled(&self, user_id: Uuid) -> DomainResult<bool>;
+
+    /// Сохраняет новый сеанс входа
+    async fn create_session(&self, session: &Session) -> DomainResult<()>;
+    /// Отмечает обновление токена в сеансе пользователя: время, данные клиента и новый срок.
+    ///
+    /// Возвращает `false`, если сеанса нет (он отозван)
+    async fn touch_session(
+        &self,
+        session_id: Uuid,
+        user_id: Uuid,
+        device: &DeviceInfo,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<bool>;
+    /// Действует ли сеанс пользователя: не удалён и не истёк
+    async fn is_session_active(&self, session_id: Uuid, user_id: Uuid) -> DomainResult<bool>;
+    /// Возвращает действующие сеансы пользователя, начиная с последнего использованного
+    async fn list_sessions(&self, user_id: Uuid) -> DomainResult<Vec<Session>>;
+    /// Удаляет сеанс пользователя; возвращает `false`, если его не было
+    async fn delete_session(&self, session_id: Uuid, user_id: Uuid) -> DomainResult<bool>;
+    /// Удаляет все сеансы пользователя
+    async fn delete_user_sessions(&self, user_id: Uuid) -> DomainResult<()>;
+
+    /// Возвращает время, до которого вход пользователя заблокирован (может быть в прошлом)
+    async fn get_login_lock(
+        &self,
+        user_id: Uuid,
+    ) -> DomainResult<Option<chrono::DateTime<chrono::Utc>>>;
+    /// Учитывает неудачную попытку входа.
+    ///
+    /// После `max_attempts` неудач подряд блокирует вход до `locked_until` и сбрасывает
+    /// счётчик. Возвращает `true`, если вход заблокирован этим вызовом.
+    async fn record_failed_login(
+        &self,
+        user_id: Uuid,
+        max_attempts: u32,
+        locked_until: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<bool>;
+    /// Сбрасывает счётчик неудачных попыток входа и блокировку
+    async fn clear_failed_logins(&self, user_id: Uuid) -> DomainResult<()>;
+
+    /// Возвращает секрет TOTP пользователя, если 2FA подключена
+    async fn get_user_totp(&self, user_id: Uuid) -> DomainResult<Option<UserTotp>>;
+    /// Сохраняет новый неподтверждённый секрет TOTP, заменяя прежний
+    async fn save_user_totp(&self, user_id: Uuid, secret: &str) -> DomainResult<()>;
+    /// Включает проверку TOTP при входе
+    async fn enable_user_totp(&self, user_id: Uuid) -> DomainResult<()>;
+    /// Сохраняет отпечаток токена второго шага входа
+    async fn create_login_challenge(
+        &self,
+        user_id: Uuid,
+        token_hash: &str,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<()>;
+    /// Возвращает владельца действующего токена второго шага входа
+    async fn find_login_challenge(&self, token_hash: &str) -> DomainResult<Option<Uuid>>;
+    /// Удаляет токен второго шага входа после успешного входа
+    async fn delete_login_challenge(&self, token_hash: &str) -> DomainResult<()>;
+
+    /// Ищет пользователя, связанного с аккаунтом у провайдера OAuth
+    async fn find_by_oauth_identity(
+        &self,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<Option<User>>;
+    /// Связывает аккаунт у провайдера OAuth с пользователем; повторная связь игнорируется
+    async fn link_oauth_identity(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<()>;
 
-    async fn get_posts(&self, page: u32, page_size: u32) -> DomainResult<Vec<Post>>;
-    async fn get_post_by_id(&self, post_id: Uuid) -> DomainResult<Post>;
-    async fn create_post(&self, post: Post) -> DomainResult<Post>;
-    async fn update_post(&self, post: Post) -> DomainResult<Post>;
-    async fn delete_post(&self, post_id: Uuid) -> DomainResult<()>;
+    /// Резервирует ключ идемпотентности за новым запросом.
+    ///
+    /// Предварительно удаляет ключи, созданные раньше `expired_before`. Если ключ
+    /// уже занят, возвращает существующую запись и ничего не резервирует.
+    async fn reserve_idempotency_key(
+        &self,
+        scope: &str,
+        key: &str,
+        request_hash: &str,
+        expired_before: chrono::DateTime<chrono::Utc>,
+    ) -> DomainResult<Option<IdempotencyRecord>>;
+    /// Сохраняет ответ на запрос с зарезервированным ключом
+    async fn complete_idempotency_key(
+        &self,
+        scope: &str,
+        key: &str,
+        response: &StoredResponse,
+    ) -> DomainResult<()>;
+    /// Освобождает ключ неудавшегося запроса, чтобы его можно было повторить
+    async fn release_idempotency_key(&self, scope: &str, key: &str) -> DomainResult<()>;
 }
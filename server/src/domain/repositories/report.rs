@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::entities::{
+    errors::DomainResult,
+    report::{Report, ReportStatus},
+};
+
+/// Хранилище жалоб на посты.
+#[async_trait::async_trait]
+pub trait ReportRepository: Send + Sync {
+    /// Сохраняет жалобу.
+    ///
+    /// Возвращает `ReportAlreadyExists`, если пользователь уже жаловался на этот пост.
+    async fn create_report(&self, report: &Report) -> DomainResult<()>;
+    /// Возвращает жалобу по ID
+    async fn get_report(&self, report_id: Uuid) -> DomainResult<Report>;
+    /// Возвращает страницу жалоб от старых к новым, при указании `status` - только в этом состоянии
+    async fn get_reports(
+        &self,
+        status: Option<ReportStatus>,
+        page: u32,
+        page_size: u32,
+    ) -> DomainResult<Vec<Report>>;
+    /// Считает жалобы, при указании `status` - только в этом состоянии
+    async fn count_reports(&self, status: Option<ReportStatus>) -> DomainResult<u64>;
+    /// Закрывает открытую жалобу с решением `status`.
+    ///
+    /// Возвращает `false`, если жалоба уже закрыта.
+    async fn resolve_report(
+        &self,
+        report_id: Uuid,
+        status: ReportStatus,
+        resolved_by: Uuid,
+        resolved_at: DateTime<Utc>,
+    ) -> DomainResult<bool>;
+    /// Закрывает все открытые жалобы на пост с решением `status` и возвращает их количество
+    async fn resolve_post_reports(
+        &self,
+        post_id: Uuid,
+        status: ReportStatus,
+        resolved_by: Uuid,
+        resolved_at: DateTime<Utc>,
+    ) -> DomainResult<u64>;
+    /// Скрыт ли пост модератором
+    async fn is_post_hidden(&self, post_id: Uuid) -> DomainResult<bool>;
+}
@@ -0,0 +1,13 @@
+use crate::domain::entities::errors::DomainResult;
+
+/// Хранилище загруженных файлов.
+///
+/// Реализации находятся в [`crate::infrastructure::storage`]: локальная файловая
+/// система и S3-совместимое объектное хранилище.
+#[async_trait::async_trait]
+pub trait BlobStorage: Send + Sync {
+    /// Сохраняет объект под ключом `key` и возвращает его публичный URL
+    async fn put(&self, key: &str, content_type: &str, data: Vec<u8>) -> DomainResult<String>;
+    /// Удаляет объект; отсутствие объекта не считается ошибкой
+    async fn delete(&self, key: &str) -> DomainResult<()>;
+}
@@ -0,0 +1,30 @@
+use uuid::Uuid;
+
+use crate::domain::entities::{errors::DomainResult, user::User};
+
+/// Несколько изменений хранилища, применяемых атомарно (транзакция).
+///
+/// Открывается через [`UserRepository::begin`](super::repo::UserRepository::begin).
+/// Изменения видны другим запросам только после [`commit`](UnitOfWork::commit);
+/// если единица работы удалена без `commit`, они отбрасываются, как при `rollback`.
+#[async_trait::async_trait]
+pub trait UnitOfWork: Send {
+    /// Сохраняет нового пользователя
+    async fn create_user(&mut self, user: User) -> DomainResult<User>;
+    /// Находит пользователя по email
+    async fn find_by_email(&mut self, email: &str) -> DomainResult<Option<User>>;
+    /// Проверяет, занято ли имя пользователя (или совпадающий с ним email)
+    async fn exists_by_username(&mut self, username: &str) -> DomainResult<bool>;
+    /// Связывает аккаунт у провайдера OAuth с пользователем; повторная связь игнорируется
+    async fn link_oauth_identity(
+        &mut self,
+        user_id: Uuid,
+        provider: &str,
+        subject: &str,
+    ) -> DomainResult<()>;
+
+    /// Применяет все изменения единицы работы
+    async fn commit(self: Box<Self>) -> DomainResult<()>;
+    /// Отменяет все изменения единицы работы
+    async fn rollback(self: Box<Self>) -> DomainResult<()>;
+}
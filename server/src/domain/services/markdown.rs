@@ -0,0 +1,69 @@
+use pulldown_cmark::{Options, Parser, html};
+
+/// Рендерит Markdown в HTML, безопасный для вставки на страницу.
+///
+/// Поддерживаются таблицы, зачёркивание и списки задач. Результат проходит через
+/// санитайзер: скрипты, обработчики событий и опасные ссылки (`javascript:` и т.п.)
+/// удаляются, ссылкам добавляется `rel="noopener noreferrer"`.
+pub fn render_markdown(content: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let parser = Parser::new_ext(content, options);
+
+    let mut unsafe_html = String::with_capacity(content.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, parser);
+
+    ammonia::clean(&unsafe_html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ordinary_markdown_survives() {
+        let html = render_markdown(
+            "# Title\n\n**bold**, ~~gone~~ and [link](https://example.com)\n\n\
+             | a | b |\n|---|---|\n| 1 | 2 |\n",
+        );
+
+        assert!(html.contains("<h1>Title</h1>"));
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("<del>gone</del>"));
+        assert!(html.contains(r#"href="https://example.com""#));
+        assert!(html.contains(r#"rel="noopener noreferrer""#));
+        assert!(html.contains("<td>1</td>"));
+    }
+
+    #[test]
+    fn test_script_tags_are_removed() {
+        let html = render_markdown("Before\n\n<script>alert(1)</script>\n\nAfter");
+
+        assert!(!html.contains("<script"));
+        assert!(!html.contains("alert(1)"));
+        assert!(html.contains("Before"));
+        assert!(html.contains("After"));
+    }
+
+    #[test]
+    fn test_event_handler_attributes_are_removed() {
+        let html = render_markdown(
+            r#"<img src="/uploads/cat.png" onerror="alert(1)"> <a href="/" onclick="alert(2)">home</a>"#,
+        );
+
+        assert!(!html.contains("onerror"));
+        assert!(!html.contains("onclick"));
+        assert!(!html.contains("alert"));
+        assert!(html.contains(r#"src="/uploads/cat.png""#));
+    }
+
+    #[test]
+    fn test_javascript_links_are_removed() {
+        let html =
+            render_markdown("[click](javascript:alert(1)) <a href=\"JavaScript:alert(2)\">raw</a>");
+
+        assert!(!html.to_lowercase().contains("javascript:"));
+        assert!(html.contains("click"));
+        assert!(html.contains("raw"));
+    }
+}
@@ -1 +1,3 @@
 pub mod auth;
+pub mod markdown;
+pub mod totp;
@@ -0,0 +1,152 @@
+//! Одноразовые коды по времени (TOTP, RFC 6238) для двухфакторной аутентификации.
+//!
+//! Используются параметры, которые понимают все приложения-аутентификаторы:
+//! HMAC-SHA1, 6 цифр, шаг 30 секунд.
+
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+type HmacSha1 = Hmac<Sha1>;
+
+/// Количество цифр в коде
+const DIGITS: usize = 6;
+/// Длительность шага в секундах
+const STEP_SECONDS: i64 = 30;
+/// Длина секрета в байтах (рекомендация RFC 4226)
+const SECRET_LEN: usize = 20;
+/// Сколько соседних шагов принимается, чтобы сгладить расхождение часов
+const ALLOWED_DRIFT_STEPS: i64 = 1;
+
+/// Генерирует случайный секрет в кодировке Base32 без выравнивания.
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; SECRET_LEN];
+    OsRng.fill_bytes(&mut bytes);
+    BASE32_NOPAD.encode(&bytes)
+}
+
+/// Строит `otpauth://` URI для добавления секрета в приложение-аутентификатор.
+pub fn provisioning_uri(secret: &str, account: &str, issuer: &str) -> String {
+    format!(
+        "otpauth://totp/{issuer_label}:{account}?secret={secret}&issuer={issuer_label}&algorithm=SHA1&digits={DIGITS}&period={STEP_SECONDS}",
+        issuer_label = percent_encode(issuer),
+        account = percent_encode(account),
+    )
+}
+
+/// Проверяет код на момент `now` с учётом соседних шагов.
+///
+/// Возвращает `false` для кода неверного формата и для повреждённого секрета.
+pub fn verify_code(secret: &str, code: &str, now: chrono::DateTime<chrono::Utc>) -> bool {
+    if code.len() != DIGITS || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return false;
+    }
+    let Ok(key) = BASE32_NOPAD.decode(secret.as_bytes()) else {
+        return false;
+    };
+
+    let counter = now.timestamp() / STEP_SECONDS;
+    (-ALLOWED_DRIFT_STEPS..=ALLOWED_DRIFT_STEPS)
+        .filter_map(|drift| u64::try_from(counter + drift).ok())
+        .any(|counter| code_at(&key, counter) == code)
+}
+
+/// Вычисляет код для значения счётчика (HOTP, RFC 4226).
+fn code_at(key: &[u8], counter: u64) -> String {
+    let mut mac = HmacSha1::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    // Динамическое усечение: 4 байта со смещения из младших бит последнего байта
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let value = u32::from_be_bytes([
+        hash[offset] & 0x7f,
+        hash[offset + 1],
+        hash[offset + 2],
+        hash[offset + 3],
+    ]);
+    format!(
+        "{:0width$}",
+        value % 10u32.pow(DIGITS as u32),
+        width = DIGITS
+    )
+}
+
+/// Кодирует строку для метки и параметров `otpauth://` URI.
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Секрет из приложения B RFC 6238 (ASCII "12345678901234567890") в Base32
+    const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    fn at(timestamp: i64) -> chrono::DateTime<chrono::Utc> {
+        chrono::DateTime::from_timestamp(timestamp, 0).unwrap()
+    }
+
+    #[test]
+    fn test_rfc6238_sha1_vectors() {
+        // В RFC коды из 8 цифр; 6-значный код - их последние 6 цифр
+        let vectors = [
+            (59, "287082"),
+            (1_111_111_109, "081804"),
+            (1_111_111_111, "050471"),
+            (1_234_567_890, "005924"),
+            (2_000_000_000, "279037"),
+            (20_000_000_000, "353130"),
+        ];
+        let key = BASE32_NOPAD.decode(RFC_SECRET.as_bytes()).unwrap();
+        for (timestamp, code) in vectors {
+            assert_eq!(code_at(&key, (timestamp / STEP_SECONDS) as u64), code);
+            assert!(verify_code(RFC_SECRET, code, at(timestamp)));
+        }
+    }
+
+    #[test]
+    fn test_drift_window_edges() {
+        // Код шага 1 (секунды 30-59) принимается с 0-й по 89-ю секунду
+        assert!(verify_code(RFC_SECRET, "287082", at(0)));
+        assert!(verify_code(RFC_SECRET, "287082", at(89)));
+        assert!(!verify_code(RFC_SECRET, "287082", at(90)));
+
+        // Код шага 37037036 (1111111080-1111111109) вне окна на шаге 37037038
+        assert!(verify_code(RFC_SECRET, "081804", at(1_111_111_139)));
+        assert!(!verify_code(RFC_SECRET, "081804", at(1_111_111_140)));
+        assert!(verify_code(RFC_SECRET, "081804", at(1_111_111_050)));
+        assert!(!verify_code(RFC_SECRET, "081804", at(1_111_111_049)));
+    }
+
+    #[test]
+    fn test_rejects_malformed_input() {
+        assert!(!verify_code(RFC_SECRET, "28708", at(59)));
+        assert!(!verify_code(RFC_SECRET, "2870821", at(59)));
+        assert!(!verify_code(RFC_SECRET, "28708a", at(59)));
+        assert!(!verify_code("not base32!", "287082", at(59)));
+    }
+
+    #[test]
+    fn test_generated_secret_round_trips() {
+        let secret = generate_secret();
+        assert_eq!(
+            BASE32_NOPAD.decode(secret.as_bytes()).unwrap().len(),
+            SECRET_LEN
+        );
+        assert!(
+            provisioning_uri(&secret, "alice smith", "Blog")
+                .starts_with("otpauth://totp/Blog:alice%20smith?secret=")
+        );
+    }
+}
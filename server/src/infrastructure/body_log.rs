@@ -0,0 +1,215 @@
+//! Отладочное логирование тел запросов и ответов REST и gRPC.
+//!
+//! По умолчанию выключено (`log_bodies` в конфигурации); администратор включает
+//! и выключает его без перезапуска через `PUT /api/v1/admin/debug/body-logging`.
+//! Значения паролей, токенов и секретов заменяются на `[REDACTED]` до записи в лог.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde_json::Value;
+
+/// Сколько байт тела попадает в лог; остаток отбрасывается
+pub const MAX_LOGGED_BODY_BYTES: usize = 16 * 1024;
+
+/// Замена значения чувствительного поля
+const REDACTED: &str = "[REDACTED]";
+
+/// Части имён полей, значения которых не пишутся в лог (в запросах и в ответах).
+///
+/// Имя поля сравнивается без учёта регистра, `_` и `-`: `apiKey`, `api_key` и
+/// `X-Api-Key` совпадают с `apikey`.
+const SENSITIVE_FIELDS: &[&str] = &[
+    "password",
+    "token",
+    "secret",
+    "challenge",
+    "provisioninguri",
+    "authorization",
+    "apikey",
+    "cookie",
+    "credential",
+    "privatekey",
+];
+
+/// Имена полей, которые скрываются только целиком: как часть имени они слишком общие.
+///
+/// `code` - код двухфакторной аутентификации или OAuth; в ответах с ошибкой он тоже
+/// скрывается, тип ошибки виден по `error` и статусу ответа.
+const SENSITIVE_EXACT_FIELDS: &[&str] = &["code", "otp"];
+
+/// Переключатель логирования тел, общий для HTTP и gRPC серверов.
+#[derive(Debug, Clone, Default)]
+pub struct BodyLogging(Arc<AtomicBool>);
+
+impl BodyLogging {
+    pub fn new(enabled: bool) -> Self {
+        Self(Arc::new(AtomicBool::new(enabled)))
+    }
+
+    /// Включено ли логирование тел
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Включает или выключает логирование тел для всех следующих запросов
+    pub fn set(&self, enabled: bool) {
+        self.0.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Чьё тело логируется.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Request,
+    Response,
+}
+
+impl Direction {
+    /// Название для сообщения лога
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Request => "Request",
+            Direction::Response => "Response",
+        }
+    }
+}
+
+/// Нужно ли скрыть значение поля `field`; список один для запросов и ответов.
+pub fn is_sensitive(field: &str) -> bool {
+    let field: String = field
+        .chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    SENSITIVE_FIELDS.iter().any(|part| field.contains(part))
+        || SENSITIVE_EXACT_FIELDS.contains(&field.as_str())
+}
+
+/// Заменяет значения чувствительных полей на `[REDACTED]` на любой глубине
+pub fn redact(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (field, value) in fields.iter_mut() {
+                if is_sensitive(field) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                redact(item);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// JSON-тело для лога: без чувствительных полей и не длиннее [`MAX_LOGGED_BODY_BYTES`].
+///
+/// Тело, которое не разбирается как JSON, не показывается: скрыть в нём секреты нельзя.
+pub fn describe_json(body: &[u8]) -> String {
+    if body.is_empty() {
+        return "<empty>".to_string();
+    }
+    match serde_json::from_slice::<Value>(body) {
+        Ok(mut value) => {
+            redact(&mut value);
+            truncate(value.to_string())
+        }
+        Err(_) => format!("<{} bytes of invalid JSON>", body.len()),
+    }
+}
+
+/// Обрезает `text` до [`MAX_LOGGED_BODY_BYTES`] по границе символа
+pub fn truncate(mut text: String) -> String {
+    if text.len() <= MAX_LOGGED_BODY_BYTES {
+        return text;
+    }
+    let total = text.len();
+    let mut end = MAX_LOGGED_BODY_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str(&format!("... ({total} bytes)"));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sensitive_fields() {
+        for field in [
+            "password",
+            "new_password",
+            "access_token",
+            "refreshToken",
+            "client_secret",
+            "challenge_token",
+            "provisioning_uri",
+            "Authorization",
+            "api_key",
+            "apiKey",
+            "X-Api-Key",
+            "cookie",
+            "credentials",
+            "private_key",
+            "code",
+            "CODE",
+            "otp",
+        ] {
+            assert!(is_sensitive(field), "{field} must be redacted");
+        }
+        for field in [
+            "username",
+            "title",
+            "content",
+            "error",
+            "postcode",
+            "expires_in",
+        ] {
+            assert!(!is_sensitive(field), "{field} must be logged");
+        }
+    }
+
+    #[test]
+    fn test_redact_nested_values() {
+        let mut value = json!({
+            "username": "alice",
+            "code": "123456",
+            "sessions": [{"id": 1, "refresh_token": "r"}],
+            "auth": {"api_key": "k", "scope": "read"},
+        });
+        redact(&mut value);
+        assert_eq!(
+            value,
+            json!({
+                "username": "alice",
+                "code": REDACTED,
+                "sessions": [{"id": 1, "refresh_token": REDACTED}],
+                "auth": {"api_key": REDACTED, "scope": "read"},
+            })
+        );
+    }
+
+    #[test]
+    fn test_describe_json() {
+        assert_eq!(describe_json(b""), "<empty>");
+        assert_eq!(describe_json(b"{not json"), "<9 bytes of invalid JSON>");
+        assert_eq!(
+            describe_json(br#"{"password":"p","title":"t"}"#),
+            r#"{"password":"[REDACTED]","title":"t"}"#
+        );
+
+        let long = format!(r#"{{"content":"{}"}}"#, "я".repeat(MAX_LOGGED_BODY_BYTES));
+        let described = describe_json(long.as_bytes());
+        assert!(described.len() < MAX_LOGGED_BODY_BYTES + 32);
+        assert!(described.ends_with(&format!("... ({} bytes)", long.len())));
+    }
+}
@@ -0,0 +1,24 @@
+//! Реализации [`CacheRepository`] для кэша чтения постов.
+
+mod noop;
+mod redis;
+
+pub use self::noop::NoopCache;
+pub use self::redis::RedisCache;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::domain::repositories::cache::CacheRepository;
+use crate::infrastructure::config::CacheConfig;
+
+/// Создаёт кэш по настройкам из конфигурации; без `redis_url` кэш ничего не хранит.
+pub async fn from_config(config: &CacheConfig) -> anyhow::Result<Arc<dyn CacheRepository>> {
+    let cache: Arc<dyn CacheRepository> = match &config.redis_url {
+        Some(url) => {
+            Arc::new(RedisCache::connect(url, Duration::from_secs(config.ttl_seconds)).await?)
+        }
+        None => Arc::new(NoopCache),
+    };
+    Ok(cache)
+}
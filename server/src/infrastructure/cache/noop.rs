@@ -0,0 +1,24 @@
+use crate::domain::entities::errors::DomainResult;
+use crate::domain::repositories::cache::CacheRepository;
+
+/// Кэш, который ничего не хранит: каждое чтение идёт в основное хранилище.
+pub struct NoopCache;
+
+#[async_trait::async_trait]
+impl CacheRepository for NoopCache {
+    async fn get(&self, _key: &str) -> DomainResult<Option<String>> {
+        Ok(None)
+    }
+
+    async fn set(&self, _key: &str, _value: &str) -> DomainResult<()> {
+        Ok(())
+    }
+
+    async fn delete(&self, _keys: &[String]) -> DomainResult<()> {
+        Ok(())
+    }
+
+    async fn increment(&self, _key: &str) -> DomainResult<i64> {
+        Ok(0)
+    }
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use redis::AsyncCommands;
+use redis::aio::ConnectionManager;
+use tracing::{debug, error, instrument};
+
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::repositories::cache::CacheRepository;
+
+/// Кэш в Redis.
+///
+/// Соединение переустанавливается автоматически, поэтому недоступность Redis
+/// проявляется ошибками отдельных операций, а не падением сервера.
+pub struct RedisCache {
+    connection: ConnectionManager,
+    ttl: Duration,
+}
+
+impl RedisCache {
+    /// Подключается к Redis по адресу `url`; записи живут `ttl`.
+    pub async fn connect(url: &str, ttl: Duration) -> anyhow::Result<Self> {
+        let client = redis::Client::open(url)?;
+        let connection = ConnectionManager::new(client).await?;
+        Ok(Self { connection, ttl })
+    }
+}
+
+fn cache_error(error: redis::RedisError) -> DomainError {
+    error!("Redis error: {}", error);
+    DomainError::CacheError(error.to_string())
+}
+
+#[async_trait::async_trait]
+impl CacheRepository for RedisCache {
+    #[instrument(skip(self))]
+    async fn get(&self, key: &str) -> DomainResult<Option<String>> {
+        let mut connection = self.connection.clone();
+        let value: Option<String> = connection.get(key).await.map_err(cache_error)?;
+        debug!("Cache {}", if value.is_some() { "hit" } else { "miss" });
+        Ok(value)
+    }
+
+    #[instrument(skip(self, value))]
+    async fn set(&self, key: &str, value: &str) -> DomainResult<()> {
+        let mut connection = self.connection.clone();
+        connection
+            .set_ex::<_, _, ()>(key, value, self.ttl.as_secs())
+            .await
+            .map_err(cache_error)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, keys: &[String]) -> DomainResult<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let mut connection = self.connection.clone();
+        connection.del::<_, ()>(keys).await.map_err(cache_error)
+    }
+
+    #[instrument(skip(self))]
+    async fn increment(&self, key: &str) -> DomainResult<i64> {
+        let mut connection = self.connection.clone();
+        connection.incr(key, 1).await.map_err(cache_error)
+    }
+}
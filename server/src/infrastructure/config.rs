@@ -1,5 +1,7 @@
+use anyhow::Context;
 use serde::Deserialize;
-use serde_yml;
+use serde_yml::{Mapping, Value};
+use std::num::{NonZeroU32, NonZeroU64};
 
 /// Конфигурация сервера.
 ///
@@ -12,71 +14,827 @@ pub struct Config {
     pub jwt_secret: String,
     /// Время жизни JWT access токена в секундах
     pub jwt_expiration_seconds: i64,
+    /// Асимметричные ключи подписи JWT; без них токены подписываются `jwt_secret` (HS256)
+    #[serde(default)]
+    pub jwt_keys: Option<JwtKeysConfig>,
+    /// Издатель JWT (`iss`); если задан, токены без него или с другим значением отклоняются
+    #[serde(default)]
+    pub jwt_issuer: Option<String>,
+    /// Аудитория JWT (`aud`); если задана, токены без неё или с другим значением отклоняются
+    #[serde(default)]
+    pub jwt_audience: Option<String>,
+    /// Допустимое расхождение часов при проверке срока действия JWT, в секундах
+    #[serde(default = "default_jwt_leeway_seconds")]
+    pub jwt_leeway_seconds: u64,
     /// Порт HTTP сервера
     pub server_port: u16,
     /// Порт gRPC сервера
+    #[serde(default = "default_grpc_port")]
     pub grpc_port: u16,
-    /// Разрешённый CORS origin
+    /// Разрешённый CORS origin; меняется без перезапуска по SIGHUP
     pub cors_origin: String,
-    /// Уровень логирования (trace, debug, info, warn, error)
+    /// Уровень логирования (trace, debug, info, warn, error); меняется без перезапуска по SIGHUP
+    #[serde(default = "default_log_level")]
     pub log_level: String,
+    /// Формат логов в консоли: `pretty` для чтения глазами, `json` для сборщиков логов
+    #[serde(default)]
+    pub log_format: LogFormat,
+    /// Писать в лог тела запросов и ответов REST и gRPC без паролей и токенов; только для отладки,
+    /// администратор переключает его без перезапуска
+    #[serde(default)]
+    pub log_bodies: bool,
+    /// Ограничение частоты входов, регистраций и сброса пароля с одного IP
+    #[serde(default)]
+    pub auth_rate_limit: RateLimitConfig,
+    /// Блокировка входа после серии неудачных попыток
+    #[serde(default)]
+    pub login_lockout: LoginLockoutConfig,
+    /// Сертификат и ключ для HTTPS и gRPC поверх TLS; без них серверы работают без шифрования
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+    /// Загрузка файлов и хранилище вложений
+    #[serde(default)]
+    pub uploads: UploadsConfig,
+    /// Рендерить Markdown-содержимое постов в HTML (`content_html`)
+    #[serde(default = "default_render_markdown")]
+    pub render_markdown: bool,
+    /// Квота постов в сутки и ограничения размера поста
+    #[serde(default)]
+    pub post_limits: PostLimitsConfig,
+    /// За сколько часов считать лайки популярных постов (`/api/v1/posts/trending`)
+    #[serde(default = "default_trending_window_hours")]
+    pub trending_window_hours: u32,
+    /// Кэш чтения постов; без `redis_url` кэширование отключено
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// Адреса и название сайта для HTML-страниц постов и `sitemap.xml`
+    #[serde(default)]
+    pub site: SiteConfig,
+    /// Отправка писем; без `smtp` письма только пишутся в лог
+    #[serde(default)]
+    pub email: EmailConfig,
+    /// Рассылка email-дайджестов новых постов
+    #[serde(default)]
+    pub digests: DigestConfig,
+    /// Вход через внешних провайдеров; провайдер без настроек недоступен
+    #[serde(default)]
+    pub oauth: OAuthConfig,
+    /// Экспорт трассировки; без `otlp_endpoint` спаны только пишутся в лог
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
+}
+
+fn default_jwt_leeway_seconds() -> u64 {
+    60
+}
+
+fn default_grpc_port() -> u16 {
+    50051
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_render_markdown() -> bool {
+    true
+}
+
+fn default_trending_window_hours() -> u32 {
+    24 * 7
+}
+
+/// Формат записей лога.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Человекочитаемые строки
+    #[default]
+    Pretty,
+    /// Одна JSON-запись на строку вместе с полями текущего спана и его родителей
+    Json,
+}
+
+/// Настройки кэша чтения постов.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CacheConfig {
+    /// Адрес Redis, например `redis://127.0.0.1:6379`
+    #[serde(default)]
+    pub redis_url: Option<String>,
+    /// Время жизни записей кэша в секундах
+    #[serde(default = "default_cache_ttl_seconds")]
+    pub ttl_seconds: u64,
+}
+
+fn default_cache_ttl_seconds() -> u64 {
+    60
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            redis_url: None,
+            ttl_seconds: default_cache_ttl_seconds(),
+        }
+    }
+}
+
+/// Настройки отправки писем.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailConfig {
+    /// Адрес отправителя, например `Blog <noreply@example.com>`
+    #[serde(default = "default_email_from")]
+    pub from: String,
+    /// SMTP сервер; без него письма не отправляются, а пишутся в лог (уровень `INFO`)
+    #[serde(default)]
+    pub smtp: Option<SmtpConfig>,
+}
+
+fn default_email_from() -> String {
+    "Blog <noreply@localhost>".to_string()
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            from: default_email_from(),
+            smtp: None,
+        }
+    }
+}
+
+/// Подключение к SMTP серверу.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmtpConfig {
+    /// Адрес SMTP сервера
+    pub host: String,
+    /// Порт SMTP сервера
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    /// Имя пользователя; без него сервер используется без аутентификации
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Пароль пользователя
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Шифровать соединение через STARTTLS; отключается только для локального
+    /// тестового сервера (например, Mailpit)
+    #[serde(default = "default_smtp_starttls")]
+    pub starttls: bool,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+fn default_smtp_starttls() -> bool {
+    true
+}
+
+/// Настройки рассылки email-дайджестов.
+///
+/// Нулевые `check_interval_seconds` и `max_posts` отклоняются при загрузке конфигурации.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DigestConfig {
+    /// Как часто проверять, кому пора отправить дайджест, в секундах
+    #[serde(default = "default_digest_check_interval_seconds")]
+    pub check_interval_seconds: NonZeroU64,
+    /// Наибольшее число постов в одном письме
+    #[serde(default = "default_digest_max_posts")]
+    pub max_posts: NonZeroU32,
+    /// Секрет подписи ссылок отписки; без него ключ выводится из `jwt_secret`
+    #[serde(default)]
+    pub unsubscribe_secret: Option<String>,
+}
+
+fn default_digest_check_interval_seconds() -> NonZeroU64 {
+    NonZeroU64::new(600).expect("default is non-zero")
+}
+
+fn default_digest_max_posts() -> NonZeroU32 {
+    NonZeroU32::new(20).expect("default is non-zero")
+}
+
+impl Default for DigestConfig {
+    fn default() -> Self {
+        Self {
+            check_interval_seconds: default_digest_check_interval_seconds(),
+            max_posts: default_digest_max_posts(),
+            unsubscribe_secret: None,
+        }
+    }
+}
+
+/// Настройки HTML-страниц постов, которые сервер отдаёт поисковикам и превью ссылок.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SiteConfig {
+    /// Публичный адрес HTTP сервера: от него строятся ссылки в `sitemap.xml` и `og:url`
+    #[serde(default = "default_site_public_url")]
+    pub public_url: String,
+    /// Адрес веб-приложения (WASM), куда ведёт ссылка со страницы поста
+    #[serde(default = "default_site_app_url")]
+    pub app_url: String,
+    /// Название сайта (`og:site_name` и заголовки страниц)
+    #[serde(default = "default_site_name")]
+    pub name: String,
+}
+
+fn default_site_public_url() -> String {
+    "http://localhost:8080".to_string()
+}
+
+fn default_site_app_url() -> String {
+    "http://localhost:3000".to_string()
+}
+
+fn default_site_name() -> String {
+    "Blog".to_string()
+}
+
+impl Default for SiteConfig {
+    fn default() -> Self {
+        Self {
+            public_url: default_site_public_url(),
+            app_url: default_site_app_url(),
+            name: default_site_name(),
+        }
+    }
+}
+
+/// Настройки экспорта трассировки по OpenTelemetry.
+///
+/// Экспорт доступен, только если сервер собран с feature `otel`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelemetryConfig {
+    /// Адрес OTLP/gRPC коллектора, например `http://localhost:4317`
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+    /// Имя сервиса в спанах (`service.name`)
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+}
+
+fn default_service_name() -> String {
+    "blog-server".to_string()
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            otlp_endpoint: None,
+            service_name: default_service_name(),
+        }
+    }
+}
+
+/// Настройки входа через провайдеров OAuth2 / OpenID Connect.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OAuthConfig {
+    /// Публичный адрес HTTP сервера; провайдер возвращает пользователя на
+    /// `{public_url}/api/v1/auth/oauth/{provider}/callback`
+    #[serde(default = "default_oauth_public_url")]
+    pub public_url: String,
+    /// Приложение в Google Cloud Console
+    #[serde(default)]
+    pub google: Option<OAuthClientConfig>,
+    /// OAuth App в настройках GitHub
+    #[serde(default)]
+    pub github: Option<OAuthClientConfig>,
+}
+
+fn default_oauth_public_url() -> String {
+    "http://localhost:8080".to_string()
+}
+
+impl Default for OAuthConfig {
+    fn default() -> Self {
+        Self {
+            public_url: default_oauth_public_url(),
+            google: None,
+            github: None,
+        }
+    }
+}
+
+/// Учётные данные приложения, зарегистрированного у провайдера.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OAuthClientConfig {
+    pub client_id: String,
+    pub client_secret: String,
+}
+
+/// Набор асимметричных ключей JWT.
+///
+/// Для ротации новый ключ добавляется в `keys` и становится активным, а прежний
+/// остаётся в списке без закрытого ключа, пока не истекут подписанные им токены.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JwtKeysConfig {
+    /// `kid` ключа, которым подписываются новые токены
+    pub active_kid: String,
+    /// Все ключи, которыми проверяются токены
+    pub keys: Vec<JwtKeyConfig>,
+    /// До какого момента (RFC 3339) ещё принимаются токены без `kid`, подписанные
+    /// `jwt_secret`; без значения такие токены отклоняются сразу
+    #[serde(default)]
+    pub legacy_secret_until: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Пути к PEM-файлам одного ключа JWT.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JwtKeyConfig {
+    /// Идентификатор ключа (`kid`)
+    pub kid: String,
+    /// `RS256` или `EdDSA`
+    pub algorithm: jsonwebtoken::Algorithm,
+    /// Открытый ключ (PEM, SPKI)
+    pub public_key_path: String,
+    /// Закрытый ключ (PEM, PKCS#8 или PKCS#1); обязателен только для активного ключа
+    #[serde(default)]
+    pub private_key_path: Option<String>,
+}
+
+/// Настройки блокировки входа после серии неудачных попыток.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LoginLockoutConfig {
+    /// Сколько неудачных попыток подряд блокируют вход; `0` отключает блокировку
+    pub max_failed_attempts: u32,
+    /// На сколько секунд блокируется вход
+    pub lock_seconds: i64,
+}
+
+impl Default for LoginLockoutConfig {
+    fn default() -> Self {
+        Self {
+            max_failed_attempts: 5,
+            lock_seconds: 900,
+        }
+    }
+}
+
+/// Ограничения на создание постов.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PostLimitsConfig {
+    /// Сколько постов пользователь может создать за сутки (UTC); `0` отключает квоту
+    #[serde(default)]
+    pub max_posts_per_day: u32,
+    /// Максимальный размер содержимого поста в байтах UTF-8
+    #[serde(default = "default_max_content_bytes")]
+    pub max_content_bytes: usize,
+    /// Максимальная длина заголовка в символах; больше 500 не позволяет колонка `posts.title`
+    #[serde(default = "default_max_title_chars")]
+    pub max_title_chars: usize,
+}
+
+fn default_max_content_bytes() -> usize {
+    400_000
+}
+
+fn default_max_title_chars() -> usize {
+    500
+}
+
+impl Default for PostLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_posts_per_day: 0,
+            max_content_bytes: default_max_content_bytes(),
+            max_title_chars: default_max_title_chars(),
+        }
+    }
+}
+
+/// Пути к PEM-файлам сертификата и закрытого ключа сервера.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TlsConfig {
+    /// Цепочка сертификатов сервера (PEM)
+    pub cert_path: String,
+    /// Закрытый ключ сервера (PEM, PKCS#8, PKCS#1 или SEC1)
+    pub key_path: String,
+}
+
+/// Ограничение частоты запросов с одного IP-адреса (token bucket).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RateLimitConfig {
+    /// Сколько запросов в минуту восстанавливается в корзине; `0` отключает ограничение
+    pub requests_per_minute: u32,
+    /// Ёмкость корзины: сколько запросов можно сделать подряд
+    pub burst: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_minute: 10,
+            burst: 5,
+        }
+    }
+}
+
+/// Настройки загрузки файлов.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UploadsConfig {
+    /// Максимальный размер одного файла в байтах
+    #[serde(default = "default_max_upload_size")]
+    pub max_size_bytes: usize,
+    /// Где хранятся загруженные файлы
+    #[serde(default)]
+    pub storage: StorageConfig,
+}
+
+fn default_max_upload_size() -> usize {
+    4 * 1024 * 1024
+}
+
+impl Default for UploadsConfig {
+    fn default() -> Self {
+        Self {
+            max_size_bytes: default_max_upload_size(),
+            storage: StorageConfig::default(),
+        }
+    }
+}
+
+/// Хранилище загруженных файлов.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum StorageConfig {
+    /// Локальная директория, которую раздаёт сам HTTP сервер
+    Local {
+        /// Директория для файлов
+        path: String,
+        /// Префикс URL, по которому файлы доступны клиентам
+        public_url: String,
+    },
+    /// S3-совместимое объектное хранилище
+    S3(S3Config),
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self::Local {
+            path: "./uploads".to_string(),
+            public_url: "/uploads".to_string(),
+        }
+    }
+}
+
+/// Параметры доступа к S3-совместимому хранилищу.
+#[derive(Debug, Clone, Deserialize)]
+pub struct S3Config {
+    /// Адрес сервиса, например `https://s3.eu-central-1.amazonaws.com`
+    pub endpoint: String,
+    /// Имя бакета
+    pub bucket: String,
+    /// Регион, участвующий в подписи запросов
+    pub region: String,
+    /// Идентификатор ключа доступа
+    pub access_key_id: String,
+    /// Секретный ключ доступа
+    pub secret_access_key: String,
+    /// Публичный адрес бакета (например, CDN); по умолчанию `{endpoint}/{bucket}`
+    #[serde(default)]
+    pub public_url: Option<String>,
 }
 
 impl Config {
-    /// Загружает конфигурацию из YAML файла.
-    ///
-    /// # Аргументы
+    /// Собирает конфигурацию из слоёв, каждый следующий переопределяет предыдущие:
     ///
-    /// * `path` - Путь к файлу конфигурации
+    /// 1. YAML файл `path`; без `path` - [`DEFAULT_CONFIG_PATH`], если такой файл есть.
+    ///    Вместо `${VAR}` в файл подставляется значение переменной окружения, вместо
+    ///    `${VAR:-default}` - значение или `default`, если переменная не задана
+    /// 2. Переменные окружения из [`ENV_OVERRIDES`]
+    /// 3. `overrides` из командной строки вида `путь.к.параметру=значение`; значение
+    ///    разбирается как YAML, поэтому строку из цифр нужно взять в кавычки
     ///
     /// # Ошибки
     ///
-    /// Возвращает ошибку если файл не найден или содержит невалидный YAML
-    pub fn from_file(path: &str) -> anyhow::Result<Self> {
-        let config_str = std::fs::read_to_string(path)?;
-        let config: Config = serde_yml::from_str(&config_str)?;
-        Ok(config)
+    /// Возвращает ошибку, если файл `path` не читается, содержит невалидный YAML или
+    /// неизвестную переменную в `${VAR}`, а также если после всех слоёв не хватает
+    /// обязательных параметров или значение не подходит по типу
+    pub fn load(path: Option<&str>, overrides: &[String]) -> anyhow::Result<Self> {
+        let mut config = match path {
+            Some(path) => read_layer(path)?,
+            None if std::path::Path::new(DEFAULT_CONFIG_PATH).exists() => {
+                read_layer(DEFAULT_CONFIG_PATH)?
+            }
+            None => Value::Null,
+        };
+
+        for &(var, key, kind) in ENV_OVERRIDES {
+            if let Ok(value) = std::env::var(var) {
+                let value = match kind {
+                    EnvValue::Text => Value::String(value),
+                    EnvValue::Scalar => serde_yml::from_str(&value)
+                        .with_context(|| format!("invalid value of {var}"))?,
+                };
+                set_key(&mut config, key, value);
+            }
+        }
+        if std::env::var("S3_BUCKET").is_ok() {
+            set_key(&mut config, "uploads.storage.type", Value::from("s3"));
+        }
+        // Один асимметричный ключ из окружения заменяет набор ключей из файла
+        if let (Ok(kid), Ok(public_key_path), Ok(private_key_path)) = (
+            std::env::var("JWT_KEY_ID"),
+            std::env::var("JWT_PUBLIC_KEY_PATH"),
+            std::env::var("JWT_PRIVATE_KEY_PATH"),
+        ) {
+            let algorithm = std::env::var("JWT_ALGORITHM").unwrap_or_else(|_| "RS256".to_string());
+            let mut key = Value::Null;
+            set_key(&mut key, "kid", Value::from(kid.clone()));
+            set_key(&mut key, "algorithm", Value::from(algorithm));
+            set_key(&mut key, "public_key_path", Value::from(public_key_path));
+            set_key(&mut key, "private_key_path", Value::from(private_key_path));
+            set_key(&mut config, "jwt_keys.active_kid", Value::from(kid));
+            set_key(&mut config, "jwt_keys.keys", Value::Sequence(vec![key]));
+        }
+
+        for item in overrides {
+            let (key, value) = item
+                .split_once('=')
+                .with_context(|| format!("override `{item}` must look like key=value"))?;
+            let value = serde_yml::from_str(value)
+                .with_context(|| format!("invalid value in override `{item}`"))?;
+            set_key(&mut config, key.trim(), value);
+        }
+
+        Ok(serde_yml::from_value(config)?)
     }
+}
 
-    /// Загружает конфигурацию из переменных окружения.
-    ///
-    /// # Переменные окружения
-    ///
-    /// - `DB_CONNECTION_STRING` - строка подключения к БД (обязательна)
-    /// - `JWT_SECRET` - секрет для JWT (обязательна)
-    /// - `JWT_EXPIRATION_SECONDS` - время жизни токена (обязательна)
-    /// - `SERVER_PORT` - порт HTTP сервера (обязательна)
-    /// - `GRPC_PORT` - порт gRPC сервера (по умолчанию: 50051)
-    /// - `CORS_ORIGIN` - разрешённый origin (обязательна)
-    /// - `LOG_LEVEL` - уровень логов (по умолчанию: info)
-    ///
-    /// # Ошибки
-    ///
-    /// Паникует если обязательные переменные не установлены
-    pub fn from_env() -> anyhow::Result<Self> {
-        let db_connection_string =
-            std::env::var("DB_CONNECTION_STRING").expect("DB_CONNECTION_STRING must be set");
-        let jwt_secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
-        let jwt_expiration_seconds = std::env::var("JWT_EXPIRATION_SECONDS")
-            .expect("JWT_EXPIRATION_SECONDS must be set")
-            .parse::<i64>()?;
-        let server_port = std::env::var("SERVER_PORT")
-            .expect("SERVER_PORT must be set")
-            .parse::<u16>()?;
-        let grpc_port = std::env::var("GRPC_PORT")
-            .unwrap_or_else(|_| "50051".to_string())
-            .parse::<u16>()?;
-        let cors_origin = std::env::var("CORS_ORIGIN").expect("CORS_ORIGIN must be set");
-        let log_level = std::env::var("LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
-
-        Ok(Self {
-            db_connection_string,
-            jwt_secret,
-            jwt_expiration_seconds,
-            server_port,
-            grpc_port,
-            cors_origin,
-            log_level,
-        })
+/// Путь к файлу конфигурации по умолчанию; без него сервер настраивается только
+/// переменными окружения и флагами командной строки
+pub const DEFAULT_CONFIG_PATH: &str = "config.yaml";
+
+/// Как значение переменной окружения попадает в конфигурацию
+#[derive(Debug, Clone, Copy)]
+enum EnvValue {
+    /// Строка как есть: секреты, адреса и пути
+    Text,
+    /// YAML-скаляр: число или `true`/`false`
+    Scalar,
+}
+
+/// Переменные окружения и параметры конфигурации, которые они переопределяют.
+///
+/// Кроме них `S3_BUCKET` переключает хранилище файлов на S3, а `JWT_KEY_ID`,
+/// `JWT_PUBLIC_KEY_PATH`, `JWT_PRIVATE_KEY_PATH` и необязательный `JWT_ALGORITHM`
+/// (по умолчанию RS256) вместе задают единственный ключ подписи JWT.
+const ENV_OVERRIDES: &[(&str, &str, EnvValue)] = &[
+    (
+        "DB_CONNECTION_STRING",
+        "db_connection_string",
+        EnvValue::Text,
+    ),
+    ("JWT_SECRET", "jwt_secret", EnvValue::Text),
+    (
+        "JWT_EXPIRATION_SECONDS",
+        "jwt_expiration_seconds",
+        EnvValue::Scalar,
+    ),
+    ("JWT_ISSUER", "jwt_issuer", EnvValue::Text),
+    ("JWT_AUDIENCE", "jwt_audience", EnvValue::Text),
+    ("JWT_LEEWAY_SECONDS", "jwt_leeway_seconds", EnvValue::Scalar),
+    ("SERVER_PORT", "server_port", EnvValue::Scalar),
+    ("GRPC_PORT", "grpc_port", EnvValue::Scalar),
+    ("CORS_ORIGIN", "cors_origin", EnvValue::Text),
+    ("LOG_LEVEL", "log_level", EnvValue::Text),
+    ("LOG_FORMAT", "log_format", EnvValue::Text),
+    ("LOG_BODIES", "log_bodies", EnvValue::Scalar),
+    (
+        "AUTH_RATE_LIMIT_PER_MINUTE",
+        "auth_rate_limit.requests_per_minute",
+        EnvValue::Scalar,
+    ),
+    (
+        "AUTH_RATE_LIMIT_BURST",
+        "auth_rate_limit.burst",
+        EnvValue::Scalar,
+    ),
+    (
+        "LOGIN_LOCKOUT_MAX_ATTEMPTS",
+        "login_lockout.max_failed_attempts",
+        EnvValue::Scalar,
+    ),
+    (
+        "LOGIN_LOCKOUT_SECONDS",
+        "login_lockout.lock_seconds",
+        EnvValue::Scalar,
+    ),
+    ("TLS_CERT_PATH", "tls.cert_path", EnvValue::Text),
+    ("TLS_KEY_PATH", "tls.key_path", EnvValue::Text),
+    (
+        "UPLOADS_MAX_SIZE_BYTES",
+        "uploads.max_size_bytes",
+        EnvValue::Scalar,
+    ),
+    ("UPLOADS_DIR", "uploads.storage.path", EnvValue::Text),
+    (
+        "UPLOADS_PUBLIC_URL",
+        "uploads.storage.public_url",
+        EnvValue::Text,
+    ),
+    ("S3_BUCKET", "uploads.storage.bucket", EnvValue::Text),
+    ("S3_ENDPOINT", "uploads.storage.endpoint", EnvValue::Text),
+    ("S3_REGION", "uploads.storage.region", EnvValue::Text),
+    (
+        "S3_ACCESS_KEY_ID",
+        "uploads.storage.access_key_id",
+        EnvValue::Text,
+    ),
+    (
+        "S3_SECRET_ACCESS_KEY",
+        "uploads.storage.secret_access_key",
+        EnvValue::Text,
+    ),
+    (
+        "S3_PUBLIC_URL",
+        "uploads.storage.public_url",
+        EnvValue::Text,
+    ),
+    ("RENDER_MARKDOWN", "render_markdown", EnvValue::Scalar),
+    (
+        "POST_LIMIT_PER_DAY",
+        "post_limits.max_posts_per_day",
+        EnvValue::Scalar,
+    ),
+    (
+        "POST_MAX_CONTENT_BYTES",
+        "post_limits.max_content_bytes",
+        EnvValue::Scalar,
+    ),
+    (
+        "POST_MAX_TITLE_CHARS",
+        "post_limits.max_title_chars",
+        EnvValue::Scalar,
+    ),
+    (
+        "TRENDING_WINDOW_HOURS",
+        "trending_window_hours",
+        EnvValue::Scalar,
+    ),
+    ("REDIS_URL", "cache.redis_url", EnvValue::Text),
+    ("CACHE_TTL_SECONDS", "cache.ttl_seconds", EnvValue::Scalar),
+    ("SITE_PUBLIC_URL", "site.public_url", EnvValue::Text),
+    ("SITE_APP_URL", "site.app_url", EnvValue::Text),
+    ("SITE_NAME", "site.name", EnvValue::Text),
+    ("EMAIL_FROM", "email.from", EnvValue::Text),
+    ("SMTP_HOST", "email.smtp.host", EnvValue::Text),
+    ("SMTP_PORT", "email.smtp.port", EnvValue::Scalar),
+    ("SMTP_USERNAME", "email.smtp.username", EnvValue::Text),
+    ("SMTP_PASSWORD", "email.smtp.password", EnvValue::Text),
+    ("SMTP_STARTTLS", "email.smtp.starttls", EnvValue::Scalar),
+    (
+        "DIGEST_CHECK_INTERVAL_SECONDS",
+        "digests.check_interval_seconds",
+        EnvValue::Scalar,
+    ),
+    ("DIGEST_MAX_POSTS", "digests.max_posts", EnvValue::Scalar),
+    (
+        "DIGEST_UNSUBSCRIBE_SECRET",
+        "digests.unsubscribe_secret",
+        EnvValue::Text,
+    ),
+    ("OAUTH_PUBLIC_URL", "oauth.public_url", EnvValue::Text),
+    ("GOOGLE_CLIENT_ID", "oauth.google.client_id", EnvValue::Text),
+    (
+        "GOOGLE_CLIENT_SECRET",
+        "oauth.google.client_secret",
+        EnvValue::Text,
+    ),
+    ("GITHUB_CLIENT_ID", "oauth.github.client_id", EnvValue::Text),
+    (
+        "GITHUB_CLIENT_SECRET",
+        "oauth.github.client_secret",
+        EnvValue::Text,
+    ),
+    // Стандартные переменные OpenTelemetry SDK
+    (
+        "OTEL_EXPORTER_OTLP_ENDPOINT",
+        "telemetry.otlp_endpoint",
+        EnvValue::Text,
+    ),
+    (
+        "OTEL_SERVICE_NAME",
+        "telemetry.service_name",
+        EnvValue::Text,
+    ),
+];
+
+/// Читает YAML файл конфигурации, подставляя переменные окружения в `${VAR}`
+fn read_layer(path: &str) -> anyhow::Result<Value> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read configuration file {path}"))?;
+    let text = interpolate(&text, |name| std::env::var(name).ok())
+        .with_context(|| format!("failed to interpolate {path}"))?;
+    Ok(serde_yml::from_str(&text)?)
+}
+
+/// Подставляет `lookup(VAR)` вместо `${VAR}` и `${VAR:-default}`.
+///
+/// Строки-комментарии остаются как есть, чтобы закомментированные параметры
+/// не требовали переменных. Значение вставляется в текст без экранирования:
+/// строку из цифр или со спецсимволами YAML нужно взять в кавычки в самом файле.
+fn interpolate(text: &str, lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<String> {
+    let mut result = String::with_capacity(text.len());
+    for line in text.split_inclusive('\n') {
+        if line.trim_start().starts_with('#') {
+            result.push_str(line);
+            continue;
+        }
+        let mut rest = line;
+        while let Some(start) = rest.find("${") {
+            let end = rest[start..]
+                .find('}')
+                .map(|end| start + end)
+                .with_context(|| format!("unclosed `${{` in line `{}`", line.trim_end()))?;
+            let (name, default) = match rest[start + 2..end].split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (&rest[start + 2..end], None),
+            };
+            let value = match (lookup(name), default) {
+                (Some(value), _) => value,
+                (None, Some(default)) => default.to_string(),
+                (None, None) => anyhow::bail!("environment variable {name} is not set"),
+            };
+            result.push_str(&rest[..start]);
+            result.push_str(&value);
+            rest = &rest[end + 1..];
+        }
+        result.push_str(rest);
+    }
+    Ok(result)
+}
+
+/// Записывает `value` по пути `a.b.c`, создавая недостающие разделы
+fn set_key(root: &mut Value, key: &str, value: Value) {
+    let mut node = root;
+    for part in key.split('.') {
+        if !node.is_mapping() {
+            *node = Value::Mapping(Mapping::new());
+        }
+        let Value::Mapping(map) = node else {
+            unreachable!("node was replaced with a mapping");
+        };
+        node = map.entry(Value::from(part)).or_insert(Value::Null);
+    }
+    *node = value;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(name: &str) -> Option<String> {
+        match name {
+            "DB_HOST" => Some("db.internal".to_string()),
+            "DB_PORT" => Some("5432".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_interpolate_replaces_every_variable_in_a_line() {
+        let text = "database_url: postgres://${DB_HOST}:${DB_PORT}/blog\n";
+        assert_eq!(
+            interpolate(text, env).unwrap(),
+            "database_url: postgres://db.internal:5432/blog\n"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_uses_default_for_missing_variable() {
+        let text = "host: ${HTTP_HOST:-0.0.0.0}\n";
+        assert_eq!(interpolate(text, env).unwrap(), "host: 0.0.0.0\n");
+    }
+
+    #[test]
+    fn test_interpolate_rejects_missing_variable() {
+        let err = interpolate("jwt_secret: ${JWT_SECRET}\n", env).unwrap_err();
+        assert!(err.to_string().contains("JWT_SECRET"));
+    }
+
+    #[test]
+    fn test_interpolate_rejects_unterminated_reference() {
+        let err = interpolate("host: ${DB_HOST\n", env).unwrap_err();
+        assert!(err.to_string().contains("unclosed"));
+    }
+
+    #[test]
+    fn test_interpolate_keeps_literal_dollar_signs() {
+        let text = "password: pa$$word $DB_HOST\n# secret: ${UNSET}\n";
+        assert_eq!(interpolate(text, env).unwrap(), text);
+    }
+
+    #[test]
+    fn test_zero_digest_settings_are_rejected() {
+        for yaml in ["check_interval_seconds: 0", "max_posts: 0"] {
+            assert!(serde_yml::from_str::<DigestConfig>(yaml).is_err(), "{yaml}");
+        }
+        let config: DigestConfig = serde_yml::from_str("max_posts: 5").unwrap();
+        assert_eq!(config.max_posts.get(), 5);
+        assert_eq!(config.check_interval_seconds.get(), 600);
     }
 }
@@ -0,0 +1,28 @@
+use tracing::{info, warn};
+
+use crate::domain::entities::errors::DomainResult;
+use crate::domain::repositories::email::{EmailMessage, EmailSender};
+
+/// Отправитель, который только пишет письма в лог: для разработки без SMTP сервера.
+///
+/// Текст конфиденциальных писем в лог не пишется, поэтому, например, сброс пароля
+/// без SMTP не работает.
+pub struct LogEmailSender;
+
+#[async_trait::async_trait]
+impl EmailSender for LogEmailSender {
+    async fn send(&self, message: EmailMessage) -> DomainResult<()> {
+        if message.confidential {
+            warn!(
+                "Email to {} (not sent, SMTP is not configured): {}\n<confidential body omitted>",
+                message.to, message.subject
+            );
+            return Ok(());
+        }
+        info!(
+            "Email to {} (not sent, SMTP is not configured): {}\n{}",
+            message.to, message.subject, message.body
+        );
+        Ok(())
+    }
+}
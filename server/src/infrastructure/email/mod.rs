@@ -0,0 +1,22 @@
+//! Реализации [`EmailSender`] для отправки писем.
+
+mod log;
+mod smtp;
+
+pub use self::log::LogEmailSender;
+pub use self::smtp::SmtpEmailSender;
+
+use std::sync::Arc;
+
+use crate::domain::repositories::email::EmailSender;
+use crate::infrastructure::config::EmailConfig;
+
+/// Создаёт отправителя писем по настройкам из конфигурации; без `smtp` письма
+/// пишутся в лог.
+pub fn from_config(config: &EmailConfig) -> anyhow::Result<Arc<dyn EmailSender>> {
+    let sender: Arc<dyn EmailSender> = match &config.smtp {
+        Some(smtp) => Arc::new(SmtpEmailSender::new(smtp, &config.from)?),
+        None => Arc::new(LogEmailSender),
+    };
+    Ok(sender)
+}
@@ -0,0 +1,69 @@
+use lettre::message::header::{ContentType, HeaderName, HeaderValue};
+use lettre::message::{Mailbox, Message};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Tokio1Executor};
+use tracing::{debug, error, instrument};
+
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::repositories::email::{EmailMessage, EmailSender};
+use crate::infrastructure::config::SmtpConfig;
+
+/// Отправка писем через SMTP сервер.
+pub struct SmtpEmailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpEmailSender {
+    pub fn new(config: &SmtpConfig, from: &str) -> anyhow::Result<Self> {
+        let mut builder = if config.starttls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?
+        } else {
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+        }
+        .port(config.port);
+        if let (Some(username), Some(password)) = (&config.username, &config.password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: from.parse()?,
+        })
+    }
+
+    /// Собирает письмо в формате, который принимает SMTP сервер
+    fn build_message(&self, message: EmailMessage) -> DomainResult<Message> {
+        let to: Mailbox = message.to.parse().map_err(|e| {
+            DomainError::EmailError(format!("invalid recipient {}: {}", message.to, e))
+        })?;
+        let mut builder = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(message.subject)
+            .header(ContentType::TEXT_PLAIN);
+        if let Some(url) = message.unsubscribe_url {
+            builder = builder.raw_header(HeaderValue::new(
+                HeaderName::new_from_ascii_str("List-Unsubscribe"),
+                format!("<{url}>"),
+            ));
+        }
+        builder
+            .body(message.body)
+            .map_err(|e| DomainError::EmailError(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailSender for SmtpEmailSender {
+    #[instrument(skip(self, message), fields(to = %message.to))]
+    async fn send(&self, message: EmailMessage) -> DomainResult<()> {
+        debug!("Sending email over SMTP");
+        let message = self.build_message(message)?;
+        self.transport.send(message).await.map_err(|e| {
+            error!("Failed to send email: {}", e);
+            DomainError::EmailError(e.to_string())
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use anyhow::Context;
+
+use crate::domain::services::auth::JwtKeyPem;
+use crate::infrastructure::config::JwtKeysConfig;
+
+/// Читает PEM-файлы ключей JWT, перечисленных в конфигурации.
+///
+/// # Ошибки
+///
+/// Возвращает ошибку, если какой-либо файл не читается
+pub fn load(config: &JwtKeysConfig) -> anyhow::Result<Vec<JwtKeyPem>> {
+    let read = |path: &str| std::fs::read(path).with_context(|| format!("Failed to read {}", path));
+    config
+        .keys
+        .iter()
+        .map(|key| {
+            Ok(JwtKeyPem {
+                kid: key.kid.clone(),
+                algorithm: key.algorithm,
+                public_key: read(&key.public_key_path)?,
+                private_key: key.private_key_path.as_deref().map(read).transpose()?,
+            })
+        })
+        .collect()
+}
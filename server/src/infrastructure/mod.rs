@@ -1 +1,11 @@
+pub mod body_log;
+pub mod cache;
 pub mod config;
+pub mod email;
+pub mod jwt_keys;
+pub mod oauth;
+pub mod rate_limit;
+pub mod reload;
+pub mod storage;
+pub mod telemetry;
+pub mod tls;
@@ -0,0 +1,89 @@
+use serde::Deserialize;
+use tracing::{debug, instrument};
+
+use super::{authorization_url, exchange_code, get_json, http_client};
+use crate::domain::entities::errors::DomainResult;
+use crate::domain::entities::oauth::OAuthProfile;
+use crate::domain::repositories::oauth::OAuthProvider;
+use crate::infrastructure::config::OAuthClientConfig;
+
+const AUTHORIZATION_ENDPOINT: &str = "https://github.com/login/oauth/authorize";
+const TOKEN_ENDPOINT: &str = "https://github.com/login/oauth/access_token";
+const USER_ENDPOINT: &str = "https://api.github.com/user";
+const EMAILS_ENDPOINT: &str = "https://api.github.com/user/emails";
+const SCOPES: &str = "read:user user:email";
+
+/// Вход через GitHub (OAuth2).
+///
+/// Публичный email в профиле GitHub необязателен и не проверен, поэтому email
+/// берётся из списка подтверждённых адресов пользователя.
+pub struct GithubProvider {
+    http: reqwest::Client,
+    client: OAuthClientConfig,
+    redirect_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubUser {
+    id: u64,
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubEmail {
+    email: String,
+    primary: bool,
+    verified: bool,
+}
+
+impl GithubProvider {
+    pub fn new(client: OAuthClientConfig, redirect_uri: String) -> anyhow::Result<Self> {
+        Ok(Self {
+            http: http_client()?,
+            client,
+            redirect_uri,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl OAuthProvider for GithubProvider {
+    fn authorization_url(&self, state: &str) -> String {
+        authorization_url(
+            AUTHORIZATION_ENDPOINT,
+            &self.client,
+            &self.redirect_uri,
+            SCOPES,
+            state,
+        )
+    }
+
+    #[instrument(skip(self, code))]
+    async fn fetch_profile(&self, code: &str) -> DomainResult<OAuthProfile> {
+        let access_token = exchange_code(
+            &self.http,
+            TOKEN_ENDPOINT,
+            &self.client,
+            &self.redirect_uri,
+            code,
+        )
+        .await?;
+        let user: GithubUser = get_json(&self.http, USER_ENDPOINT, &access_token).await?;
+        let emails: Vec<GithubEmail> = get_json(&self.http, EMAILS_ENDPOINT, &access_token).await?;
+        debug!("GitHub profile received");
+
+        // Основной подтверждённый адрес, иначе любой подтверждённый
+        let email = emails
+            .iter()
+            .filter(|email| email.verified)
+            .max_by_key(|email| email.primary)
+            .map(|email| email.email.clone());
+
+        Ok(OAuthProfile {
+            subject: user.id.to_string(),
+            email_verified: email.is_some(),
+            email,
+            username_hint: Some(user.login),
+        })
+    }
+}
@@ -0,0 +1,80 @@
+use serde::Deserialize;
+use tracing::{debug, instrument};
+
+use super::{authorization_url, exchange_code, get_json, http_client};
+use crate::domain::entities::errors::DomainResult;
+use crate::domain::entities::oauth::OAuthProfile;
+use crate::domain::repositories::oauth::OAuthProvider;
+use crate::infrastructure::config::OAuthClientConfig;
+
+const AUTHORIZATION_ENDPOINT: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const TOKEN_ENDPOINT: &str = "https://oauth2.googleapis.com/token";
+const USERINFO_ENDPOINT: &str = "https://openidconnect.googleapis.com/v1/userinfo";
+const SCOPES: &str = "openid email profile";
+
+/// Вход через Google (OpenID Connect).
+///
+/// Профиль читается из userinfo endpoint по access токену, поэтому проверять
+/// подпись `id_token` не нужно.
+pub struct GoogleProvider {
+    http: reqwest::Client,
+    client: OAuthClientConfig,
+    redirect_uri: String,
+}
+
+/// Стандартные claims OpenID Connect из userinfo endpoint.
+#[derive(Debug, Deserialize)]
+struct UserInfo {
+    sub: String,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+}
+
+impl GoogleProvider {
+    pub fn new(client: OAuthClientConfig, redirect_uri: String) -> anyhow::Result<Self> {
+        Ok(Self {
+            http: http_client()?,
+            client,
+            redirect_uri,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl OAuthProvider for GoogleProvider {
+    fn authorization_url(&self, state: &str) -> String {
+        authorization_url(
+            AUTHORIZATION_ENDPOINT,
+            &self.client,
+            &self.redirect_uri,
+            SCOPES,
+            state,
+        )
+    }
+
+    #[instrument(skip(self, code))]
+    async fn fetch_profile(&self, code: &str) -> DomainResult<OAuthProfile> {
+        let access_token = exchange_code(
+            &self.http,
+            TOKEN_ENDPOINT,
+            &self.client,
+            &self.redirect_uri,
+            code,
+        )
+        .await?;
+        let info: UserInfo = get_json(&self.http, USERINFO_ENDPOINT, &access_token).await?;
+        debug!("Google profile received");
+
+        Ok(OAuthProfile {
+            username_hint: info
+                .email
+                .as_deref()
+                .and_then(|email| email.split('@').next())
+                .map(str::to_string),
+            subject: info.sub,
+            email: info.email,
+            email_verified: info.email_verified,
+        })
+    }
+}
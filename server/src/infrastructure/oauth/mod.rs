@@ -0,0 +1,155 @@
+//! Реализации [`OAuthProvider`] для входа через Google и GitHub.
+
+mod github;
+mod google;
+
+pub use github::GithubProvider;
+pub use google::GoogleProvider;
+
+use std::sync::Arc;
+
+use reqwest::Url;
+use reqwest::header::ACCEPT;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tracing::error;
+
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::entities::oauth::OAuthProviderKind;
+use crate::domain::repositories::oauth::{OAuthProvider, OAuthProviders};
+use crate::infrastructure::config::{OAuthClientConfig, OAuthConfig};
+
+/// Создаёт провайдеров, для которых в конфигурации заданы учётные данные приложения.
+pub fn from_config(config: &OAuthConfig) -> anyhow::Result<OAuthProviders> {
+    // Проверяем адрес заранее, чтобы ошибка конфигурации была видна при запуске
+    Url::parse(&config.public_url)?;
+
+    let mut providers = OAuthProviders::new();
+    if let Some(client) = &config.google {
+        let redirect_uri = callback_url(&config.public_url, OAuthProviderKind::Google);
+        let provider: Arc<dyn OAuthProvider> =
+            Arc::new(GoogleProvider::new(client.clone(), redirect_uri)?);
+        providers.insert(OAuthProviderKind::Google, provider);
+    }
+    if let Some(client) = &config.github {
+        let redirect_uri = callback_url(&config.public_url, OAuthProviderKind::Github);
+        let provider: Arc<dyn OAuthProvider> =
+            Arc::new(GithubProvider::new(client.clone(), redirect_uri)?);
+        providers.insert(OAuthProviderKind::Github, provider);
+    }
+    Ok(providers)
+}
+
+/// Адрес, на который провайдер возвращает пользователя после входа.
+fn callback_url(public_url: &str, provider: OAuthProviderKind) -> String {
+    format!(
+        "{}/api/v1/auth/oauth/{}/callback",
+        public_url.trim_end_matches('/'),
+        provider.as_str()
+    )
+}
+
+/// HTTP клиент для запросов к провайдерам (API GitHub отклоняет запросы без `User-Agent`).
+fn http_client() -> anyhow::Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .user_agent(concat!("blog-server/", env!("CARGO_PKG_VERSION")))
+        .build()?)
+}
+
+/// Адрес страницы входа провайдера с параметрами запроса кода авторизации.
+fn authorization_url(
+    endpoint: &str,
+    client: &OAuthClientConfig,
+    redirect_uri: &str,
+    scopes: &str,
+    state: &str,
+) -> String {
+    Url::parse_with_params(
+        endpoint,
+        [
+            ("response_type", "code"),
+            ("client_id", client.client_id.as_str()),
+            ("redirect_uri", redirect_uri),
+            ("scope", scopes),
+            ("state", state),
+        ],
+    )
+    .expect("authorization endpoint is a valid URL")
+    .into()
+}
+
+/// Ответ token endpoint: access токен или описание ошибки.
+///
+/// GitHub сообщает об ошибке со статусом 200, поэтому статус ответа не проверяется.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: Option<String>,
+    error: Option<String>,
+    error_description: Option<String>,
+}
+
+/// Обменивает код авторизации на access токен провайдера (RFC 6749, раздел 4.1.3).
+async fn exchange_code(
+    http: &reqwest::Client,
+    endpoint: &str,
+    client: &OAuthClientConfig,
+    redirect_uri: &str,
+    code: &str,
+) -> DomainResult<String> {
+    let response: TokenResponse = http
+        .post(endpoint)
+        .header(ACCEPT, "application/json")
+        .form(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("client_id", client.client_id.as_str()),
+            ("client_secret", client.client_secret.as_str()),
+        ])
+        .send()
+        .await
+        .map_err(request_error)?
+        .json()
+        .await
+        .map_err(request_error)?;
+
+    match response {
+        TokenResponse {
+            access_token: Some(access_token),
+            ..
+        } => Ok(access_token),
+        TokenResponse {
+            error,
+            error_description,
+            ..
+        } => Err(DomainError::OAuthError(
+            error_description
+                .or(error)
+                .unwrap_or_else(|| "no access token in response".to_string()),
+        )),
+    }
+}
+
+/// Запрашивает данные пользователя у API провайдера.
+async fn get_json<T: DeserializeOwned>(
+    http: &reqwest::Client,
+    url: &str,
+    access_token: &str,
+) -> DomainResult<T> {
+    http.get(url)
+        .bearer_auth(access_token)
+        .header(ACCEPT, "application/json")
+        .send()
+        .await
+        .map_err(request_error)?
+        .error_for_status()
+        .map_err(request_error)?
+        .json()
+        .await
+        .map_err(request_error)
+}
+
+fn request_error(e: reqwest::Error) -> DomainError {
+    error!("OAuth provider request failed: {}", e);
+    DomainError::OAuthError("provider request failed".to_string())
+}
@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Mutex, PoisonError};
+use std::time::Instant;
+
+use crate::infrastructure::config::RateLimitConfig;
+
+/// Сколько адресов хранится, прежде чем забываются адреса с полной корзиной
+const MAX_TRACKED_ADDRESSES: usize = 10_000;
+
+/// Корзина токенов одного адреса.
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Ограничитель частоты запросов по IP-адресу (token bucket).
+///
+/// Каждый адрес получает корзину на `burst` запросов, которая пополняется
+/// со скоростью `requests_per_minute`.
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_second: f64,
+    buckets: Mutex<HashMap<IpAddr, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            capacity: config.burst.max(1) as f64,
+            refill_per_second: config.requests_per_minute as f64 / 60.0,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Списывает запрос с корзины адреса `ip`.
+    ///
+    /// Если корзина пуста, возвращает число секунд до появления следующего токена.
+    pub fn check(&self, ip: IpAddr) -> Result<(), u64> {
+        self.check_at(ip, Instant::now())
+    }
+
+    /// [`check`](Self::check) на момент `now`
+    fn check_at(&self, ip: IpAddr, now: Instant) -> Result<(), u64> {
+        if self.refill_per_second == 0.0 {
+            return Ok(());
+        }
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if buckets.len() >= MAX_TRACKED_ADDRESSES {
+            buckets.retain(|_, bucket| self.refill(bucket, now) < self.capacity);
+        }
+
+        let bucket = buckets.entry(ip).or_insert(Bucket {
+            tokens: self.capacity,
+            updated_at: now,
+        });
+        bucket.tokens = self.refill(bucket, now);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        let retry_after = (1.0 - bucket.tokens) / self.refill_per_second;
+        Err(retry_after.ceil().max(1.0) as u64)
+    }
+
+    /// Количество токенов в корзине на момент `now`
+    fn refill(&self, bucket: &Bucket, now: Instant) -> f64 {
+        let elapsed = now.duration_since(bucket.updated_at).as_secs_f64();
+        (bucket.tokens + elapsed * self.refill_per_second).min(self.capacity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+    use std::time::Duration;
+
+    const CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+    const OTHER_CLIENT: IpAddr = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2));
+
+    /// 3 запроса подряд, затем один запрос в 10 секунд
+    fn limiter() -> RateLimiter {
+        RateLimiter::new(&RateLimitConfig {
+            requests_per_minute: 6,
+            burst: 3,
+        })
+    }
+
+    #[test]
+    fn test_allows_requests_up_to_burst() {
+        let limiter = limiter();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at(CLIENT, now), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_rejects_requests_after_burst() {
+        let limiter = limiter();
+        let now = Instant::now();
+        for _ in 0..3 {
+            limiter.check_at(CLIENT, now).unwrap();
+        }
+
+        assert_eq!(limiter.check_at(CLIENT, now), Err(10));
+        // Отклонённый запрос не списывается с корзины
+        assert_eq!(
+            limiter.check_at(CLIENT, now + Duration::from_secs(4)),
+            Err(6)
+        );
+    }
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let limiter = limiter();
+        let now = Instant::now();
+        for _ in 0..3 {
+            limiter.check_at(CLIENT, now).unwrap();
+        }
+
+        let later = now + Duration::from_secs(10);
+        assert_eq!(limiter.check_at(CLIENT, later), Ok(()));
+        assert!(limiter.check_at(CLIENT, later).is_err());
+
+        // За минуту корзина наполняется полностью, но не сверх `burst`
+        let much_later = later + Duration::from_secs(600);
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at(CLIENT, much_later), Ok(()));
+        }
+        assert!(limiter.check_at(CLIENT, much_later).is_err());
+    }
+
+    #[test]
+    fn test_addresses_have_separate_buckets() {
+        let limiter = limiter();
+        let now = Instant::now();
+        for _ in 0..3 {
+            limiter.check_at(CLIENT, now).unwrap();
+        }
+
+        assert!(limiter.check_at(CLIENT, now).is_err());
+        for _ in 0..3 {
+            assert_eq!(limiter.check_at(OTHER_CLIENT, now), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_zero_rate_disables_limit() {
+        let limiter = RateLimiter::new(&RateLimitConfig {
+            requests_per_minute: 0,
+            burst: 1,
+        });
+        let now = Instant::now();
+
+        for _ in 0..100 {
+            assert_eq!(limiter.check_at(CLIENT, now), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,71 @@
+//! Перезагрузка конфигурации по SIGHUP.
+//!
+//! Без перезапуска меняются только уровень логирования и разрешённый CORS origin;
+//! порты, база данных, ключи и остальные параметры применяются при следующем запуске.
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+use tracing::{error, info};
+
+use crate::infrastructure::config::Config;
+use crate::infrastructure::telemetry::LogLevel;
+
+/// Разрешённый CORS origin, общий для HTTP и gRPC-Web серверов.
+#[derive(Debug, Clone)]
+pub struct AllowedOrigin(Arc<RwLock<String>>);
+
+impl AllowedOrigin {
+    pub fn new(origin: String) -> Self {
+        Self(Arc::new(RwLock::new(origin)))
+    }
+
+    /// Совпадает ли заголовок `Origin` запроса с разрешённым origin
+    pub fn matches(&self, origin: &[u8]) -> bool {
+        self.0
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_bytes()
+            == origin
+    }
+
+    /// Заменяет разрешённый origin
+    pub fn set(&self, origin: String) {
+        *self.0.write().unwrap_or_else(PoisonError::into_inner) = origin;
+    }
+}
+
+/// Перечитывает конфигурацию через `load` при каждом SIGHUP и применяет параметры,
+/// которые меняются без перезапуска.
+///
+/// Если конфигурация не загрузилась, сервер продолжает работать с прежними настройками.
+#[cfg(unix)]
+pub async fn reload_on_sighup(
+    load: impl Fn() -> anyhow::Result<Config>,
+    log_level: LogLevel,
+    cors_origin: AllowedOrigin,
+) -> anyhow::Result<()> {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut hangup = signal(SignalKind::hangup())?;
+    while hangup.recv().await.is_some() {
+        info!("SIGHUP received, reloading configuration");
+        let cfg = match load() {
+            Ok(cfg) => cfg,
+            Err(e) => {
+                error!(
+                    "Failed to reload configuration, keeping the current one: {:#}",
+                    e
+                );
+                continue;
+            }
+        };
+
+        match log_level.set(&cfg.log_level) {
+            Ok(()) => info!("Log level set to {}", cfg.log_level),
+            Err(e) => error!("Invalid log level {}: {}", cfg.log_level, e),
+        }
+        info!("CORS origin set to {}", cfg.cors_origin);
+        cors_origin.set(cfg.cors_origin);
+    }
+    Ok(())
+}
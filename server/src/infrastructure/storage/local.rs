@@ -0,0 +1,56 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use tracing::{debug, error, instrument};
+
+use super::join_url;
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::repositories::storage::BlobStorage;
+
+/// Хранилище файлов в каталоге на диске.
+///
+/// Файлы раздаёт HTTP сервер, поэтому `public_url` должен указывать на маршрут,
+/// обслуживающий каталог `root`.
+pub struct LocalStorage {
+    root: PathBuf,
+    public_url: String,
+}
+
+impl LocalStorage {
+    pub fn new(root: PathBuf, public_url: String) -> Self {
+        Self { root, public_url }
+    }
+}
+
+#[async_trait::async_trait]
+impl BlobStorage for LocalStorage {
+    #[instrument(skip(self, data), fields(size = data.len()))]
+    async fn put(&self, key: &str, _content_type: &str, data: Vec<u8>) -> DomainResult<String> {
+        debug!("Writing file to local storage");
+
+        let written = match tokio::fs::create_dir_all(&self.root).await {
+            Ok(()) => tokio::fs::write(self.root.join(key), data).await,
+            Err(e) => Err(e),
+        };
+        written.map_err(|e| {
+            error!("Failed to write file {}: {}", key, e);
+            DomainError::StorageError(e.to_string())
+        })?;
+
+        Ok(join_url(&self.public_url, key))
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, key: &str) -> DomainResult<()> {
+        debug!("Removing file from local storage");
+
+        match tokio::fs::remove_file(self.root.join(key)).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => {
+                error!("Failed to remove file {}: {}", key, e);
+                Err(DomainError::StorageError(e.to_string()))
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Реализации [`BlobStorage`] для хранения загруженных файлов.
+
+mod local;
+mod s3;
+
+pub use local::LocalStorage;
+pub use s3::S3Storage;
+
+use std::sync::Arc;
+
+use crate::domain::repositories::storage::BlobStorage;
+use crate::infrastructure::config::StorageConfig;
+
+/// Создаёт хранилище файлов по настройкам из конфигурации.
+pub fn from_config(config: &StorageConfig) -> anyhow::Result<Arc<dyn BlobStorage>> {
+    let storage: Arc<dyn BlobStorage> = match config {
+        StorageConfig::Local { path, public_url } => {
+            Arc::new(LocalStorage::new(path.into(), public_url.clone()))
+        }
+        StorageConfig::S3(s3_config) => Arc::new(S3Storage::new(s3_config.clone())?),
+    };
+    Ok(storage)
+}
+
+/// Склеивает базовый URL и ключ объекта.
+fn join_url(base: &str, key: &str) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), key)
+}
@@ -0,0 +1,168 @@
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{Method, StatusCode, Url};
+use sha2::{Digest, Sha256};
+use tracing::{debug, error, instrument};
+
+use super::join_url;
+use crate::domain::entities::errors::{DomainError, DomainResult};
+use crate::domain::repositories::storage::BlobStorage;
+use crate::infrastructure::config::S3Config;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// S3-совместимое объектное хранилище (AWS S3, MinIO и т.п.).
+///
+/// Объекты адресуются в path-style (`{endpoint}/{bucket}/{key}`), запросы
+/// подписываются AWS Signature Version 4.
+pub struct S3Storage {
+    http: reqwest::Client,
+    config: S3Config,
+}
+
+impl S3Storage {
+    pub fn new(config: S3Config) -> anyhow::Result<Self> {
+        // Проверяем адрес заранее, чтобы ошибка конфигурации была видна при запуске
+        Url::parse(&config.endpoint)?;
+        Ok(Self {
+            http: reqwest::Client::new(),
+            config,
+        })
+    }
+
+    fn object_url(&self, key: &str) -> String {
+        join_url(&join_url(&self.config.endpoint, &self.config.bucket), key)
+    }
+
+    /// Выполняет подписанный запрос к объекту `key` и возвращает статус ответа.
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        content_type: Option<&str>,
+        body: Vec<u8>,
+    ) -> DomainResult<StatusCode> {
+        let url = Url::parse(&self.object_url(key))
+            .map_err(|e| DomainError::StorageError(e.to_string()))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            (None, _) => {
+                return Err(DomainError::StorageError(
+                    "S3 endpoint has no host".to_string(),
+                ));
+            }
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        // Заголовки, входящие в подпись, в алфавитном порядке
+        let mut signed: Vec<(&str, String)> = Vec::with_capacity(4);
+        if let Some(content_type) = content_type {
+            signed.push(("content-type", content_type.to_string()));
+        }
+        signed.push(("host", host));
+        signed.push(("x-amz-content-sha256", payload_hash.clone()));
+        signed.push(("x-amz-date", amz_date.clone()));
+
+        let canonical_headers: String = signed
+            .iter()
+            .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+            .collect();
+        let signed_headers = signed
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+        let canonical_request = format!(
+            "{}\n{}\n\n{}\n{}\n{}",
+            method.as_str(),
+            url.path(),
+            canonical_headers,
+            signed_headers,
+            payload_hash
+        );
+
+        let scope = format!("{date}/{}/s3/aws4_request", self.config.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let secret = format!("AWS4{}", self.config.secret_access_key);
+        let key_date = hmac_sha256(secret.as_bytes(), date.as_bytes());
+        let key_region = hmac_sha256(&key_date, self.config.region.as_bytes());
+        let key_service = hmac_sha256(&key_region, b"s3");
+        let signing_key = hmac_sha256(&key_service, b"aws4_request");
+        let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+            self.config.access_key_id
+        );
+
+        let mut request = self
+            .http
+            .request(method, url)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date)
+            .header("authorization", authorization)
+            .body(body);
+        if let Some(content_type) = content_type {
+            request = request.header("content-type", content_type);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            error!("S3 request failed: {}", e);
+            DomainError::StorageError(e.to_string())
+        })?;
+        Ok(response.status())
+    }
+}
+
+/// Вычисляет HMAC-SHA256 от `data` с ключом `key`.
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[async_trait::async_trait]
+impl BlobStorage for S3Storage {
+    #[instrument(skip(self, data), fields(size = data.len()))]
+    async fn put(&self, key: &str, content_type: &str, data: Vec<u8>) -> DomainResult<String> {
+        debug!("Uploading object to S3");
+
+        let status = self
+            .send(Method::PUT, key, Some(content_type), data)
+            .await?;
+        if !status.is_success() {
+            error!("S3 rejected upload of {}: {}", key, status);
+            return Err(DomainError::StorageError(format!(
+                "S3 responded with {status}"
+            )));
+        }
+
+        Ok(match &self.config.public_url {
+            Some(public_url) => join_url(public_url, key),
+            None => self.object_url(key),
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn delete(&self, key: &str) -> DomainResult<()> {
+        debug!("Deleting object from S3");
+
+        let status = self.send(Method::DELETE, key, None, Vec::new()).await?;
+        if !status.is_success() && status != StatusCode::NOT_FOUND {
+            error!("S3 rejected deletion of {}: {}", key, status);
+            return Err(DomainError::StorageError(format!(
+                "S3 responded with {status}"
+            )));
+        }
+        Ok(())
+    }
+}
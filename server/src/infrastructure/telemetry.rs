@@ -0,0 +1,150 @@
+//! Логирование и трассировка.
+//!
+//! Спаны всегда пишутся в консоль, построчно или в JSON (`log_format`). С feature `otel` и заданным
+//! `telemetry.otlp_endpoint` они дополнительно экспортируются в OTLP коллектор,
+//! а контекст трассировки из заголовка `traceparent` входящих HTTP и gRPC
+//! запросов становится родителем спанов сервера.
+
+use tracing::Level;
+use tracing_subscriber::{
+    Registry, filter::LevelFilter, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
+
+use crate::infrastructure::config::{LogFormat, TelemetryConfig};
+
+/// Экспорт трассировки, работающий до остановки сервера.
+pub struct Telemetry {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+    log_level: LogLevel,
+}
+
+/// Уровень логирования, который можно сменить без перезапуска сервера.
+#[derive(Clone)]
+pub struct LogLevel(reload::Handle<LevelFilter, Registry>);
+
+impl LogLevel {
+    /// Устанавливает уровень `log_level` (trace, debug, info, warn, error)
+    pub fn set(&self, log_level: &str) -> anyhow::Result<()> {
+        let level: Level = log_level.parse()?;
+        self.0.reload(LevelFilter::from_level(level))?;
+        Ok(())
+    }
+}
+
+impl Telemetry {
+    /// Управление уровнем логирования
+    pub fn log_level(&self) -> LogLevel {
+        self.log_level.clone()
+    }
+
+    /// Отправляет накопленные спаны и останавливает экспорт
+    pub fn shutdown(self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = self.provider
+            && let Err(e) = provider.shutdown()
+        {
+            tracing::error!("Failed to flush traces: {}", e);
+        }
+    }
+}
+
+/// Устанавливает глобальный subscriber с уровнем `log_level` (по умолчанию `info`)
+/// и форматом записей `log_format`.
+pub fn init(
+    log_level: &str,
+    log_format: LogFormat,
+    cfg: &TelemetryConfig,
+) -> anyhow::Result<Telemetry> {
+    let level = log_level.parse().unwrap_or(Level::INFO);
+    let (filter, handle) = reload::Layer::new(LevelFilter::from_level(level));
+    let log_level = LogLevel(handle);
+    // В JSON попадают поля всех спанов, в том числе `request_id` корневого спана запроса
+    let (pretty, json) = match log_format {
+        LogFormat::Pretty => (
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .with_target(true)
+                    .with_thread_ids(false)
+                    .with_line_number(true),
+            ),
+            None,
+        ),
+        LogFormat::Json => (
+            None,
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .with_current_span(true)
+                    .with_span_list(true)
+                    .with_target(true)
+                    .with_line_number(true),
+            ),
+        ),
+    };
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(pretty)
+        .with(json);
+
+    #[cfg(feature = "otel")]
+    {
+        use opentelemetry::trace::TracerProvider as _;
+
+        let provider = cfg
+            .otlp_endpoint
+            .as_deref()
+            .map(|endpoint| otlp_provider(endpoint, &cfg.service_name))
+            .transpose()?;
+        let layer = provider
+            .as_ref()
+            .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer("server")));
+        registry.with(layer).init();
+
+        if let Some(endpoint) = &cfg.otlp_endpoint {
+            tracing::info!("Exporting traces to {}", endpoint);
+        }
+        Ok(Telemetry {
+            provider,
+            log_level,
+        })
+    }
+
+    #[cfg(not(feature = "otel"))]
+    {
+        registry.init();
+
+        if cfg.otlp_endpoint.is_some() {
+            tracing::warn!(
+                "telemetry.otlp_endpoint is set, but the server is built without the `otel` feature: traces are not exported"
+            );
+        }
+        Ok(Telemetry { log_level })
+    }
+}
+
+/// Экспорт спанов пакетами по OTLP/gRPC; входящий `traceparent` разбирается по W3C Trace Context
+#[cfg(feature = "otel")]
+fn otlp_provider(
+    endpoint: &str,
+    service_name: &str,
+) -> anyhow::Result<opentelemetry_sdk::trace::SdkTracerProvider> {
+    use opentelemetry_otlp::WithExportConfig;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(endpoint)
+        .build()?;
+    opentelemetry::global::set_text_map_propagator(
+        opentelemetry_sdk::propagation::TraceContextPropagator::new(),
+    );
+
+    Ok(opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            opentelemetry_sdk::Resource::builder()
+                .with_service_name(service_name.to_string())
+                .build(),
+        )
+        .build())
+}
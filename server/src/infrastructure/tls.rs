@@ -0,0 +1,46 @@
+use anyhow::Context;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
+use std::sync::Arc;
+use tonic::transport::{Identity, ServerTlsConfig};
+
+use crate::infrastructure::config::TlsConfig;
+
+/// Собирает конфигурацию rustls для HTTPS сервера.
+///
+/// # Ошибки
+///
+/// Возвращает ошибку, если файлы не читаются, не содержат сертификатов или ключа,
+/// либо ключ не подходит к сертификату
+pub fn http_server_config(config: &TlsConfig) -> anyhow::Result<rustls::ServerConfig> {
+    let certs = CertificateDer::pem_file_iter(&config.cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("Failed to read certificates from {}", config.cert_path))?;
+    anyhow::ensure!(
+        !certs.is_empty(),
+        "No certificates found in {}",
+        config.cert_path
+    );
+    let key = PrivateKeyDer::from_pem_file(&config.key_path)
+        .with_context(|| format!("Failed to read private key from {}", config.key_path))?;
+
+    // Провайдер указан явно, чтобы не зависеть от набора features rustls в дереве зависимостей
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let server_config = rustls::ServerConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)?;
+    Ok(server_config)
+}
+
+/// Собирает конфигурацию TLS для gRPC сервера из тех же файлов.
+///
+/// # Ошибки
+///
+/// Возвращает ошибку, если файлы сертификата или ключа не читаются
+pub fn grpc_server_config(config: &TlsConfig) -> anyhow::Result<ServerTlsConfig> {
+    let cert = std::fs::read(&config.cert_path)
+        .with_context(|| format!("Failed to read {}", config.cert_path))?;
+    let key = std::fs::read(&config.key_path)
+        .with_context(|| format!("Failed to read {}", config.key_path))?;
+    Ok(ServerTlsConfig::new().identity(Identity::from_pem(cert, key)))
+}
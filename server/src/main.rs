@@ -1,92 +1,351 @@
 use std::sync::Arc;
 
 use actix_cors::Cors;
-use actix_web::{App, HttpServer, web};
+use actix_files::Files;
+use actix_web::{App, HttpServer, middleware, web};
 use actix_web_httpauth::middleware::HttpAuthentication;
 use api::blog_server::BlogServer;
 use clap::Parser;
 use server::{
-    application::{auth::AuthApplication, post::PostApplication},
-    data::pgrepo::PgUserRepository,
-    domain::services::auth::AuthService,
-    infrastructure::config::Config,
-    presentation::grpc::BlogServiceImpl,
+    application::{
+        admin::AdminApplication,
+        audit::{AuditApplication, Audited},
+        auth::{AuthApplication, LoginLockout},
+        blog::BlogApplication,
+        category::CategoryApplication,
+        digest::{DigestApplication, DigestOptions, derive_unsubscribe_secret},
+        follow::FollowApplication,
+        idempotency::IdempotencyApplication,
+        post::{PostApplication, PostLimits},
+        profile::ProfileApplication,
+        report::ReportApplication,
+        upload::UploadApplication,
+    },
+    data::{cached::CachedPostRepository, memrepo::InMemoryRepository, pgrepo::PgUserRepository},
+    domain::{
+        repositories::{Repository, post::PostRepository},
+        services::auth::AuthService,
+    },
+    infrastructure::{
+        body_log::BodyLogging,
+        cache,
+        config::{Config, StorageConfig},
+        email, jwt_keys, oauth,
+        rate_limit::RateLimiter,
+        reload::{self, AllowedOrigin},
+        storage, telemetry, tls,
+    },
+    presentation::error::ApiError,
+    presentation::grpc::{BlogServiceImpl, BodyLogLayer, TraceLayer, web::cors_layer},
     presentation::http::handlers::{
-        AppState, create_post, delete_post, get_post, list_posts, login, refresh_token, register,
-        update_post,
+        AppState, create_blog, create_blog_post, create_blog_posts, create_category, create_post,
+        create_posts, delete_blog, delete_post, delete_posts, disable_user, enable_user,
+        enroll_totp, follow_user, force_delete_post, forgot_password, get_blog, get_body_logging,
+        get_digest_settings, get_feed, get_follow_status, get_post, get_server_info,
+        get_system_stats, get_user_profile, jwks, like_post, list_audit_log, list_blog_posts,
+        list_blogs, list_categories, list_posts, list_reports, list_sessions, list_trending_posts,
+        list_user_posts, list_users, login, login_totp, logout, logout_all, oauth_authorize,
+        oauth_callback, post_events, publish_post, purge_post, refresh_token, register,
+        report_post, reset_password, resolve_report, restore_post, revoke_session,
+        set_body_logging, unfollow_user, unlike_post, unpublish_post, update_blog,
+        update_digest_settings, update_my_profile, update_post, upload_file, username_available,
+        verify_totp,
     },
-    presentation::http::middleware::jwt_validator,
+    presentation::http::middleware::{
+        BlogRootSpanBuilder, api_v2_alias, jwt_validator, log_bodies, trace_id,
+    },
+    presentation::http::openapi::ApiDoc,
+    presentation::http::pages::{SitePages, post_page, sitemap, unsubscribe_digest},
+    presentation::server_info::ServerInfo,
 };
+use tonic::codec::CompressionEncoding;
 use tonic::transport::Server;
-use tracing::{error, info};
+use tonic_web::GrpcWebLayer;
+use tracing::{error, info, warn};
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
 /// Blog server with HTTP and gRPC APIs
-#[derive(Parser, Debug)]
+///
+/// Settings are merged from the configuration file, environment variables and
+/// command-line flags, later sources overriding earlier ones. SIGHUP reloads
+/// `log_level` and `cors_origin` without a restart.
+#[derive(Parser, Debug, Clone)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Path to the configuration file
-    #[arg(short, long, default_value = "config.yaml")]
-    config: String,
+    /// Path to the configuration file [default: config.yaml, if present]
+    #[arg(short, long)]
+    config: Option<String>,
+    /// Override a setting, e.g. `--set log_level=debug --set uploads.max_size_bytes=1048576`
+    #[arg(long = "set", value_name = "KEY=VALUE")]
+    overrides: Vec<String>,
+    /// Log level (trace, debug, info, warn, error); same as `--set log_level=...`
+    #[arg(long)]
+    log_level: Option<String>,
+    /// Keep all data in memory instead of PostgreSQL (for demos; data is lost on exit)
+    #[arg(long)]
+    in_memory: bool,
+}
+
+impl Args {
+    /// Собирает конфигурацию из файла, окружения и флагов командной строки
+    fn load_config(&self) -> anyhow::Result<Config> {
+        let mut overrides = self.overrides.clone();
+        if let Some(log_level) = &self.log_level {
+            overrides.push(format!("log_level={log_level}"));
+        }
+        Config::load(self.config.as_deref(), &overrides)
+    }
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
-    let cfg = Config::from_file(&args.config).expect("Failed to load configuration");
+    let cfg = args.load_config().expect("Failed to load configuration");
 
-    // Инициализация tracing
-    tracing_subscriber::fmt()
-        .with_max_level(cfg.log_level.parse().unwrap_or(tracing::Level::INFO))
-        .with_target(true)
-        .with_thread_ids(false)
-        .with_line_number(true)
-        .init();
+    // Инициализация tracing и, если настроен, экспорта спанов по OTLP
+    let telemetry = telemetry::init(&cfg.log_level, cfg.log_format, &cfg.telemetry)?;
 
     info!("Starting server initialization");
     info!("Configuration loaded successfully");
 
-    let repo = PgUserRepository::new(&cfg.db_connection_string)
-        .await
-        .map_err(|e| {
-            error!("Failed to create repository: {}", e);
-            e
-        })
-        .expect("Failed to create repository");
-    let repo = Arc::new(repo);
-    info!("Database repository initialized");
-
-    let auth_service = AuthService::new(
+    let repo: Arc<dyn Repository> = if args.in_memory {
+        warn!("Using in-memory repository: data will be lost on shutdown");
+        Arc::new(InMemoryRepository::new())
+    } else {
+        let repo = PgUserRepository::new(&cfg.db_connection_string)
+            .await
+            .map_err(|e| {
+                error!("Failed to create repository: {}", e);
+                e
+            })
+            .expect("Failed to create repository");
+        info!("Database repository initialized");
+        Arc::new(repo)
+    };
+
+    // Анонимные чтения постов обслуживаются из кэша, если настроен Redis
+    let cache = cache::from_config(&cfg.cache).await?;
+    let post_repository: Arc<dyn PostRepository> =
+        Arc::new(CachedPostRepository::new(repo.clone(), cache));
+    info!("Post cache initialized");
+
+    let mut auth_service = AuthService::new(
         chrono::Duration::seconds(cfg.jwt_expiration_seconds),
         cfg.jwt_secret.as_bytes(),
+    )
+    .with_claims_validation(
+        cfg.jwt_issuer.clone(),
+        cfg.jwt_audience.clone(),
+        cfg.jwt_leeway_seconds,
     );
+    // С асимметричными ключами токены без `kid` проверяются секретом только до
+    // `legacy_secret_until`
+    if let Some(keys) = &cfg.jwt_keys {
+        auth_service = auth_service.with_keys(
+            jwt_keys::load(keys)?,
+            &keys.active_kid,
+            keys.legacy_secret_until,
+        )?;
+        info!("JWT tokens are signed with key {}", keys.active_kid);
+        match keys.legacy_secret_until {
+            Some(until) => info!("Tokens signed with jwt_secret are accepted until {until}"),
+            None => info!("Tokens signed with jwt_secret are rejected"),
+        }
+    }
     let auth_service = Arc::new(auth_service);
     info!("Auth service initialized");
 
-    let auth_app = Arc::new(AuthApplication::new(repo.clone(), auth_service.clone()));
-    let post_app = Arc::new(PostApplication::new(repo.clone()));
+    let oauth_providers = oauth::from_config(&cfg.oauth)?;
+    let post_limits = PostLimits {
+        max_posts_per_day: cfg.post_limits.max_posts_per_day,
+        max_content_bytes: cfg.post_limits.max_content_bytes,
+        max_title_chars: cfg.post_limits.max_title_chars,
+    };
+    let server_info = Arc::new(ServerInfo::new(
+        !oauth_providers.is_empty(),
+        cfg.render_markdown,
+        post_limits,
+    ));
+    info!(
+        "API versions: {:?}, features: {:?}",
+        server_info.api_versions, server_info.features
+    );
+    info!(
+        "OAuth providers configured: {:?}",
+        oauth_providers
+            .keys()
+            .map(|provider| provider.as_str())
+            .collect::<Vec<_>>()
+    );
+
+    let email_sender = email::from_config(&cfg.email)?;
+    if cfg.email.smtp.is_none() {
+        warn!(
+            "SMTP is not configured: emails will be written to the log, password reset is unavailable"
+        );
+    }
+
+    // Вход, регистрация и изменения постов записываются в журнал аудита
+    let audit_app = Arc::new(AuditApplication::new(repo.clone()));
+    let auth_app = Arc::new(Audited::new(
+        AuthApplication::new(
+            repo.clone(),
+            auth_service.clone(),
+            LoginLockout {
+                max_failed_attempts: cfg.login_lockout.max_failed_attempts,
+                lock_duration: chrono::Duration::seconds(cfg.login_lockout.lock_seconds),
+            },
+            oauth_providers,
+        )
+        .with_email(email_sender.clone(), cfg.site.name.clone()),
+        audit_app.clone(),
+    ));
+    let post_app = Arc::new(Audited::new(
+        PostApplication::new(post_repository, repo.clone(), cfg.render_markdown)
+            .with_trending_window(chrono::Duration::hours(cfg.trending_window_hours.into()))
+            .with_reports(repo.clone())
+            .with_categories(repo.clone())
+            .with_blogs(repo.clone())
+            .with_limits(post_limits),
+        audit_app.clone(),
+    ));
+    let profile_app = Arc::new(ProfileApplication::new(repo.clone()));
+    // Один переключатель на оба транспорта, администратор меняет его без перезапуска
+    let body_logging = BodyLogging::new(cfg.log_bodies);
+    if cfg.log_bodies {
+        warn!("Request and response bodies will be written to the log");
+    }
+    let admin_app = Arc::new(Audited::new(
+        AdminApplication::new(repo.clone()).with_body_logging(body_logging.clone()),
+        audit_app.clone(),
+    ));
+    let report_app = Arc::new(ReportApplication::new(repo.clone()));
+    let follow_app = Arc::new(FollowApplication::new(repo.clone()));
+    let category_app = Arc::new(CategoryApplication::new(repo.clone()));
+    let blog_app = Arc::new(BlogApplication::new(repo.clone()));
+    let idempotency_app = Arc::new(IdempotencyApplication::new(repo.clone()));
+
+    let unsubscribe_secret = match &cfg.digests.unsubscribe_secret {
+        Some(secret) => secret.as_bytes().to_vec(),
+        None => derive_unsubscribe_secret(cfg.jwt_secret.as_bytes()),
+    };
+    let digest_app = Arc::new(DigestApplication::new(
+        repo.clone(),
+        email_sender,
+        &unsubscribe_secret,
+        DigestOptions {
+            site_name: cfg.site.name.clone(),
+            app_url: cfg.site.app_url.clone(),
+            public_url: cfg.site.public_url.clone(),
+            max_posts: cfg.digests.max_posts.get(),
+        },
+    ));
+    // Дайджесты рассылаются в фоне: проверка, кому пора отправить письмо, - по таймеру
+    let digest_job = digest_app.clone();
+    let digest_check_interval =
+        std::time::Duration::from_secs(cfg.digests.check_interval_seconds.get());
+    tokio::spawn(async move { digest_job.run(digest_check_interval).await });
+    info!("Digest delivery scheduled");
+
+    let blob_storage = storage::from_config(&cfg.uploads.storage)?;
+    let upload_app = Arc::new(UploadApplication::new(
+        repo.clone(),
+        blob_storage,
+        cfg.uploads.max_size_bytes,
+    ));
+    info!("File storage initialized");
+    // Локальное хранилище раздаёт сам HTTP сервер, если файлы доступны по пути на нём же
+    let local_uploads = match &cfg.uploads.storage {
+        StorageConfig::Local { path, public_url } if public_url.starts_with('/') => {
+            Some((public_url.clone(), path.clone()))
+        }
+        _ => None,
+    };
 
     let app_state = web::Data::new(AppState {
         auth_app: auth_app.clone(),
         post_app: post_app.clone(),
+        profile_app: profile_app.clone(),
+        idempotency_app,
+        upload_app: upload_app.clone(),
+        audit_app,
+        admin_app: admin_app.clone(),
+        report_app: report_app.clone(),
+        follow_app: follow_app.clone(),
+        digest_app: digest_app.clone(),
+        category_app: category_app.clone(),
+        blog_app: blog_app.clone(),
     });
     let auth_service_data = web::Data::from(auth_service.clone());
+    let server_info_data = web::Data::from(server_info.clone());
+    let site_pages_data = web::Data::new(SitePages::new(&cfg.site));
+
+    // Один лимитер на оба транспорта: лимит действует на IP-адрес целиком
+    let auth_rate_limiter = Arc::new(RateLimiter::new(&cfg.auth_rate_limit));
+    let auth_rate_limiter_data = web::Data::from(auth_rate_limiter.clone());
+
+    let body_logging_data = web::Data::new(body_logging.clone());
 
     let http_addr = format!("127.0.0.1:{}", cfg.server_port);
     let grpc_addr = format!("127.0.0.1:{}", cfg.grpc_port)
         .parse()
         .expect("Invalid gRPC address");
 
-    info!("Starting HTTP server at http://{}", http_addr);
-    info!("Starting gRPC server at {}", grpc_addr);
+    let scheme = if cfg.tls.is_some() { "https" } else { "http" };
+    info!("Starting HTTP server at {}://{}", scheme, http_addr);
+    info!("Starting gRPC server at {}://{}", scheme, grpc_addr);
 
-    let cors_origin = cfg.cors_origin.clone();
+    // Оба сервера используют один сертификат
+    let mut grpc_builder = Server::builder();
+    let http_tls_config = match &cfg.tls {
+        Some(tls_cfg) => {
+            grpc_builder = grpc_builder.tls_config(tls::grpc_server_config(tls_cfg)?)?;
+            Some(tls::http_server_config(tls_cfg)?)
+        }
+        None => None,
+    };
+
+    // Origin меняется по SIGHUP без перезапуска, поэтому CORS проверяет его при каждом запросе
+    let cors_origin = AllowedOrigin::new(cfg.cors_origin.clone());
+    let openapi = ApiDoc::openapi();
 
     // Запускаем gRPC сервер в отдельной задаче
-    let grpc_service = BlogServiceImpl::new(auth_app, post_app, auth_service);
+    let grpc_service = BlogServiceImpl::new(
+        auth_app,
+        post_app,
+        profile_app,
+        upload_app,
+        admin_app,
+        report_app,
+        follow_app,
+        digest_app,
+        category_app,
+        blog_app,
+        auth_rate_limiter,
+        server_info,
+    );
+    // Файл передаётся одним сообщением, поэтому лимит сообщения должен вмещать его с запасом
+    let grpc_max_message_size = cfg.uploads.max_size_bytes + 64 * 1024;
+    let grpc_cors = cors_layer(cors_origin.clone());
+    let grpc_body_log = BodyLogLayer::new(body_logging)?;
     let grpc_server = tokio::spawn(async move {
-        Server::builder()
-            .add_service(BlogServer::new(grpc_service))
+        // HTTP/1.1 и gRPC-Web нужны браузерному клиенту, обычные gRPC клиенты работают как раньше
+        grpc_builder
+            .accept_http1(true)
+            .layer(TraceLayer)
+            .layer(grpc_cors)
+            .layer(GrpcWebLayer::new())
+            .layer(grpc_body_log)
+            .add_service(
+                BlogServer::new(grpc_service)
+                    .max_decoding_message_size(grpc_max_message_size)
+                    // Сжатые запросы принимаются всегда, ответы сжимаются для клиентов,
+                    // указавших gzip в grpc-accept-encoding
+                    .accept_compressed(CompressionEncoding::Gzip)
+                    .send_compressed(CompressionEncoding::Gzip),
+            )
             .serve(grpc_addr)
             .await
             .map_err(|e| {
@@ -95,10 +354,24 @@ async fn main() -> anyhow::Result<()> {
             })
     });
 
+    // SIGHUP перечитывает конфигурацию и применяет параметры, не требующие перезапуска
+    #[cfg(unix)]
+    {
+        let log_level = telemetry.log_level();
+        let cors_origin = cors_origin.clone();
+        tokio::spawn(async move {
+            let reload = reload::reload_on_sighup(|| args.load_config(), log_level, cors_origin);
+            if let Err(e) = reload.await {
+                error!("Configuration reload on SIGHUP is unavailable: {}", e);
+            }
+        });
+    }
+
     // Запускаем HTTP сервер
     let http_server = HttpServer::new(move || {
+        let cors_origin = cors_origin.clone();
         let cors = Cors::default()
-            .allowed_origin(&cors_origin)
+            .allowed_origin_fn(move |origin, _req| cors_origin.matches(origin.as_bytes()))
             .allowed_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"])
             .allow_any_header()
             .max_age(3600);
@@ -109,24 +382,119 @@ async fn main() -> anyhow::Result<()> {
         App::new()
             .app_data(app_state.clone())
             .app_data(auth_service_data.clone())
-            .wrap(tracing_actix_web::TracingLogger::default())
+            .app_data(server_info_data.clone())
+            .app_data(site_pages_data.clone())
+            .app_data(auth_rate_limiter_data.clone())
+            .app_data(body_logging_data.clone())
+            // Ошибки разбора тела и query string возвращаются в формате ErrorResponse
+            .app_data(
+                web::JsonConfig::default()
+                    .error_handler(|err, _req| ApiError::bad_request(err.to_string()).into()),
+            )
+            .app_data(
+                web::QueryConfig::default()
+                    .error_handler(|err, _req| ApiError::bad_request(err.to_string()).into()),
+            )
+            // `/api/v2/...` обслуживается маршрутами `/api/v1/...`; в логах остаётся исходный путь
+            .wrap(middleware::from_fn(api_v2_alias))
+            // Тела логируются до сжатия ответов
+            .wrap(middleware::from_fn(log_bodies))
+            // Выполняется внутри TracingLogger, чтобы видеть ID запроса
+            // Ответы сжимаются gzip, brotli или zstd по заголовку Accept-Encoding клиента
+            .wrap(middleware::Compress::default())
+            .wrap(middleware::from_fn(trace_id))
+            .wrap(tracing_actix_web::TracingLogger::<BlogRootSpanBuilder>::new())
             .wrap(cors)
             // Публичные маршруты (без аутентификации)
+            .service(get_server_info)
             .service(register)
             .service(login)
+            .service(login_totp)
+            .service(oauth_authorize)
+            .service(oauth_callback)
             .service(refresh_token)
+            .service(logout)
+            .service(forgot_password)
+            .service(reset_password)
+            .service(jwks)
+            .service(username_available)
+            .service(get_user_profile)
+            .service(list_user_posts)
             .service(list_posts)
+            // Регистрируются до get_post, иначе `events` и `trending` будут разобраны как ID поста
+            .service(post_events)
+            .service(list_trending_posts)
+            .service(list_categories)
+            .service(list_blogs)
+            .service(get_blog)
+            .service(list_blog_posts)
             .service(get_post)
+            // HTML-страницы постов и карта сайта для поисковиков
+            .service(post_page)
+            .service(sitemap)
+            .service(unsubscribe_digest)
+            // Спецификация OpenAPI и Swagger UI для неё
+            .service(
+                SwaggerUi::new("/swagger-ui/{_:.*}").url("/api/v1/openapi.json", openapi.clone()),
+            )
+            // Загруженные файлы, если они хранятся локально
+            .configure(|cfg| {
+                if let Some((public_url, path)) = &local_uploads {
+                    cfg.service(Files::new(public_url, path));
+                }
+            })
             // Защищённые маршруты (требуют JWT токен)
             .service(
                 web::scope("")
                     .wrap(auth_middleware)
+                    .service(logout_all)
+                    .service(list_sessions)
+                    .service(revoke_session)
+                    .service(update_my_profile)
+                    .service(get_digest_settings)
+                    .service(update_digest_settings)
+                    .service(follow_user)
+                    .service(unfollow_user)
+                    .service(get_follow_status)
+                    .service(get_feed)
+                    .service(enroll_totp)
+                    .service(verify_totp)
+                    // Пакетные маршруты регистрируются раньше `/api/v1/posts/{id}`
+                    .service(create_posts)
+                    .service(delete_posts)
                     .service(create_post)
                     .service(update_post)
-                    .service(delete_post),
+                    .service(delete_post)
+                    .service(restore_post)
+                    .service(purge_post)
+                    .service(publish_post)
+                    .service(unpublish_post)
+                    .service(like_post)
+                    .service(unlike_post)
+                    .service(report_post)
+                    .service(upload_file)
+                    .service(list_audit_log)
+                    .service(list_users)
+                    .service(disable_user)
+                    .service(enable_user)
+                    .service(force_delete_post)
+                    .service(get_system_stats)
+                    .service(get_body_logging)
+                    .service(set_body_logging)
+                    .service(create_category)
+                    .service(create_blog)
+                    .service(update_blog)
+                    .service(delete_blog)
+                    .service(create_blog_posts)
+                    .service(create_blog_post)
+                    .service(list_reports)
+                    .service(resolve_report),
             )
-    })
-    .bind(&http_addr)
+    });
+    let http_server = match http_tls_config {
+        Some(tls_config) => http_server.bind_rustls_0_23(&http_addr, tls_config),
+        None => http_server.bind(&http_addr),
+    }
     .map_err(|e| {
         error!("Failed to bind to {}: {}", http_addr, e);
         e
@@ -134,14 +502,11 @@ async fn main() -> anyhow::Result<()> {
     .run();
 
     // Ждем завершения обоих серверов
-    tokio::select! {
-        res = http_server => {
-            res?;
-        }
-        res = grpc_server => {
-            res??;
-        }
-    }
+    let result: anyhow::Result<()> = tokio::select! {
+        res = http_server => res.map_err(Into::into),
+        res = grpc_server => res?.map_err(Into::into),
+    };
 
-    Ok(())
+    telemetry.shutdown();
+    result
 }
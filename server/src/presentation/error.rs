@@ -1,5 +1,9 @@
 use crate::domain::entities::errors::DomainError;
+use crate::presentation::request_id::current_trace_id;
 use actix_web::HttpResponse;
+use actix_web::http::header::RETRY_AFTER;
+use api::rest::{ErrorCode, ErrorResponse, ValidationErrors, field_errors};
+use std::collections::BTreeMap;
 use thiserror::Error;
 use tracing::error;
 
@@ -17,8 +21,27 @@ pub enum ApiError {
     #[error("Not found: {0}")]
     NotFound(String),
 
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Too many requests, retry in {retry_after_secs} s")]
+    RateLimited { retry_after_secs: u64 },
+
+    #[error("Account is locked, retry in {retry_after_secs} s")]
+    AccountLocked { retry_after_secs: u64 },
+
+    /// Превышена квота: с `retry_after_secs` - суточная (429), без него - размер поста (403)
+    #[error("{message}")]
+    QuotaExceeded {
+        message: String,
+        retry_after_secs: Option<u64>,
+    },
+
     #[error("Internal server error: {0}")]
     InternalServerError(String),
+
+    #[error("Validation failed")]
+    Validation(BTreeMap<String, Vec<String>>),
 }
 
 impl ApiError {
@@ -38,26 +61,81 @@ impl ApiError {
         Self::NotFound(message)
     }
 
+    pub fn conflict(message: String) -> Self {
+        Self::Conflict(message)
+    }
+
+    pub fn rate_limited(retry_after_secs: u64) -> Self {
+        Self::RateLimited { retry_after_secs }
+    }
+
+    pub fn account_locked(retry_after_secs: u64) -> Self {
+        Self::AccountLocked { retry_after_secs }
+    }
+
     pub fn internal_server_error(message: String) -> Self {
         Self::InternalServerError(message)
     }
+
+    /// Машиночитаемый код ошибки для [`ErrorResponse`]
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ApiError::BadRequest(_) => ErrorCode::BadRequest,
+            ApiError::Unauthorized(_) => ErrorCode::Unauthorized,
+            ApiError::Forbidden(_) => ErrorCode::Forbidden,
+            ApiError::NotFound(_) => ErrorCode::NotFound,
+            ApiError::Conflict(_) => ErrorCode::Conflict,
+            ApiError::RateLimited { .. } => ErrorCode::RateLimited,
+            ApiError::AccountLocked { .. } => ErrorCode::AccountLocked,
+            ApiError::QuotaExceeded { .. } => ErrorCode::QuotaExceeded,
+            ApiError::InternalServerError(_) => ErrorCode::InternalError,
+            ApiError::Validation(_) => ErrorCode::ValidationFailed,
+        }
+    }
 }
 
 impl actix_web::error::ResponseError for ApiError {
     fn error_response(&self) -> HttpResponse {
         error!("API Error: {}", self);
         let status = self.status_code();
-        HttpResponse::build(status).json(serde_json::json!({
-            "error": self.to_string()
-        }))
+        let details = match self {
+            ApiError::Validation(fields) => fields.clone(),
+            _ => BTreeMap::new(),
+        };
+        let mut response = HttpResponse::build(status);
+        if let ApiError::RateLimited { retry_after_secs }
+        | ApiError::AccountLocked { retry_after_secs }
+        | ApiError::QuotaExceeded {
+            retry_after_secs: Some(retry_after_secs),
+            ..
+        } = self
+        {
+            response.insert_header((RETRY_AFTER, retry_after_secs.to_string()));
+        }
+        response.json(ErrorResponse {
+            code: self.code(),
+            message: self.to_string(),
+            details,
+            trace_id: current_trace_id(),
+        })
     }
 
     fn status_code(&self) -> actix_web::http::StatusCode {
         match self {
-            ApiError::BadRequest(_) => actix_web::http::StatusCode::BAD_REQUEST,
+            ApiError::BadRequest(_) | ApiError::Validation(_) => {
+                actix_web::http::StatusCode::BAD_REQUEST
+            }
             ApiError::Unauthorized(_) => actix_web::http::StatusCode::UNAUTHORIZED,
             ApiError::Forbidden(_) => actix_web::http::StatusCode::FORBIDDEN,
             ApiError::NotFound(_) => actix_web::http::StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => actix_web::http::StatusCode::CONFLICT,
+            ApiError::RateLimited { .. } => actix_web::http::StatusCode::TOO_MANY_REQUESTS,
+            ApiError::AccountLocked { .. } => actix_web::http::StatusCode::LOCKED,
+            ApiError::QuotaExceeded {
+                retry_after_secs: Some(_),
+                ..
+            } => actix_web::http::StatusCode::TOO_MANY_REQUESTS,
+            ApiError::QuotaExceeded { .. } => actix_web::http::StatusCode::FORBIDDEN,
             ApiError::InternalServerError(_) => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
@@ -68,13 +146,67 @@ impl From<DomainError> for ApiError {
         match err {
             DomainError::UserAlreadyExists { .. } => Self::bad_request(err.to_string()),
             DomainError::UserNotFound { .. } => Self::not_found(err.to_string()),
+            DomainError::InvalidUsername { .. } => Self::bad_request(err.to_string()),
             DomainError::InvalidCredentials => Self::unauthorized(err.to_string()),
+            DomainError::AccountLocked { locked_until } => {
+                // Округляем вверх, чтобы повтор через Retry-After не попал в блокировку
+                let remaining = (locked_until - chrono::Utc::now()).num_seconds() + 1;
+                Self::account_locked(remaining.max(1) as u64)
+            }
+            DomainError::AccountDisabled => Self::forbidden(err.to_string()),
+            DomainError::OAuthProviderNotConfigured { .. } => Self::not_found(err.to_string()),
+            DomainError::OAuthError(_) => Self::unauthorized(err.to_string()),
             DomainError::InvalidPassword { .. } => Self::bad_request(err.to_string()),
+            DomainError::InvalidTotpCode => Self::bad_request(err.to_string()),
+            DomainError::TwoFactorAlreadyEnabled => Self::conflict(err.to_string()),
+            DomainError::TwoFactorNotEnrolled => Self::bad_request(err.to_string()),
+            DomainError::InvalidResetToken => Self::bad_request(err.to_string()),
             DomainError::PostNotFound { .. } => Self::not_found(err.to_string()),
+            DomainError::InvalidTags { .. } => Self::bad_request(err.to_string()),
+            DomainError::InvalidSummary { .. } => Self::bad_request(err.to_string()),
+            DomainError::CategoryNotFound { .. } => Self::not_found(err.to_string()),
+            DomainError::CategoryAlreadyExists { .. } => Self::conflict(err.to_string()),
+            DomainError::InvalidCategory { .. } => Self::bad_request(err.to_string()),
+            DomainError::BlogNotFound { .. } => Self::not_found(err.to_string()),
+            DomainError::BlogAlreadyExists { .. } => Self::conflict(err.to_string()),
+            DomainError::InvalidBlog { .. } => Self::bad_request(err.to_string()),
+            DomainError::ProfileNotFound { .. } => Self::not_found(err.to_string()),
+            DomainError::SessionNotFound { .. } => Self::not_found(err.to_string()),
+            DomainError::InvalidProfile { .. } => Self::bad_request(err.to_string()),
+            DomainError::InvalidUpload { .. } => Self::bad_request(err.to_string()),
+            DomainError::InvalidAttachments { .. } => Self::bad_request(err.to_string()),
+            DomainError::ReportAlreadyExists { .. } => Self::conflict(err.to_string()),
+            DomainError::ReportNotFound { .. } => Self::not_found(err.to_string()),
+            DomainError::ReportAlreadyResolved { .. } => Self::conflict(err.to_string()),
+            DomainError::InvalidReport { .. } => Self::bad_request(err.to_string()),
+            DomainError::InvalidFollow { .. } => Self::bad_request(err.to_string()),
+            DomainError::InvalidUnsubscribeToken => Self::bad_request(err.to_string()),
+            DomainError::InvalidBatch { .. } => Self::bad_request(err.to_string()),
+            DomainError::InvalidCursor { .. } => Self::bad_request(err.to_string()),
+            DomainError::InvalidIdempotencyKey { .. } => Self::bad_request(err.to_string()),
+            DomainError::IdempotencyKeyInProgress => Self::conflict(err.to_string()),
+            DomainError::IdempotencyKeyReused => Self::conflict(err.to_string()),
             DomainError::Forbidden { .. } => Self::forbidden(err.to_string()),
+            DomainError::QuotaExceeded { retry_at, .. } => Self::QuotaExceeded {
+                message: err.to_string(),
+                retry_after_secs: retry_at.map(|retry_at| {
+                    // Округляем вверх, как для блокировки входа
+                    let remaining = (retry_at - chrono::Utc::now()).num_seconds() + 1;
+                    remaining.max(1) as u64
+                }),
+            },
+            DomainError::StorageError(_) => Self::internal_server_error(err.to_string()),
+            DomainError::EmailError(_) => Self::internal_server_error(err.to_string()),
+            DomainError::CacheError(_) => Self::internal_server_error(err.to_string()),
             DomainError::RepositoryError(_) => Self::internal_server_error(err.to_string()),
             DomainError::TokenGenerationError(_) => Self::internal_server_error(err.to_string()),
             DomainError::TokenValidationError(_) => Self::unauthorized(err.to_string()),
         }
     }
 }
+
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        Self::Validation(field_errors(&errors))
+    }
+}
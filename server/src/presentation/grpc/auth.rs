@@ -1,6 +1,12 @@
-use crate::domain::services::auth::{AuthService, Claims};
+use crate::application::audit::Audited;
+use crate::application::auth::AuthApplication;
+use crate::domain::entities::session::DeviceInfo;
+use crate::domain::repositories::Repository;
+use crate::domain::services::auth::Claims;
+use crate::presentation::grpc::trace::RequestSpan;
 use std::sync::Arc;
 use tonic::{Request, Status};
+use uuid::Uuid;
 
 /// Извлекает JWT токен из metadata запроса
 pub fn extract_token_from_metadata<T>(request: &Request<T>) -> Result<String, Status> {
@@ -24,23 +30,47 @@ pub fn extract_token_from_metadata<T>(request: &Request<T>) -> Result<String, St
     Ok(token)
 }
 
+/// Сведения о клиенте для сеанса входа: `user-agent` из metadata и адрес подключения
+pub fn device_info<T>(request: &Request<T>) -> DeviceInfo {
+    DeviceInfo::new(
+        request
+            .metadata()
+            .get("user-agent")
+            .and_then(|value| value.to_str().ok()),
+        request.remote_addr().map(|addr| addr.ip()),
+    )
+}
+
 /// Interceptor для проверки JWT токена
-#[derive(Clone)]
-pub struct AuthInterceptor {
-    auth_service: Arc<AuthService>,
+pub struct AuthInterceptor<Repo: Repository + ?Sized> {
+    auth_app: Arc<Audited<AuthApplication<Repo>, Repo>>,
 }
 
-impl AuthInterceptor {
-    pub fn new(auth_service: Arc<AuthService>) -> Self {
-        Self { auth_service }
+impl<Repo: Repository + ?Sized> AuthInterceptor<Repo> {
+    pub fn new(auth_app: Arc<Audited<AuthApplication<Repo>, Repo>>) -> Self {
+        Self { auth_app }
     }
 
-    /// Проверяет JWT токен и возвращает Claims
-    pub fn verify_token<T>(&self, request: &Request<T>) -> Result<Claims, Status> {
+    /// Проверяет JWT токен (включая отзыв выходом со всех устройств) и возвращает Claims
+    pub async fn verify_token<T>(&self, request: &Request<T>) -> Result<Claims, Status> {
         let token = extract_token_from_metadata(request)?;
 
-        self.auth_service
-            .verify_token(&token)
-            .ok_or_else(|| Status::unauthenticated("Invalid or expired token"))
+        let claims = self
+            .auth_app
+            .authenticate(&token)
+            .await
+            .map_err(|e| Status::unauthenticated(e.to_string()))?;
+        if let Some(RequestSpan(span)) = request.extensions().get::<RequestSpan>() {
+            span.record("user_id", tracing::field::display(&claims.sub));
+        }
+        Ok(claims)
+    }
+
+    /// Возвращает ID пользователя, если запрос содержит валидный токен.
+    ///
+    /// Используется публичными методами: без токена запрос считается анонимным.
+    pub async fn optional_user_id<T>(&self, request: &Request<T>) -> Option<Uuid> {
+        let claims = self.verify_token(request).await.ok()?;
+        Uuid::parse_str(&claims.sub).ok()
     }
 }
@@ -0,0 +1,344 @@
+use std::io::Read;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use bytes::{Buf, Bytes, BytesMut};
+use flate2::read::GzDecoder;
+use http_body::{Frame, SizeHint};
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, Kind, MapKey, MessageDescriptor, MethodDescriptor, Value,
+};
+use tonic::body::Body;
+use tonic::codegen::{Service, http};
+use tower_layer::Layer;
+use tracing::{Span, info};
+
+use crate::infrastructure::body_log::{BodyLogging, Direction, redact, truncate};
+
+/// Сообщения больше этого размера не разбираются: в лог попадает только их размер
+const MAX_DECODED_MESSAGE_BYTES: usize = 1024 * 1024;
+
+/// Длина заголовка сообщения gRPC: флаг сжатия и длина
+const MESSAGE_HEADER_LEN: usize = 5;
+
+/// Пишет в лог сообщения gRPC вызовов в виде JSON, пока включено логирование тел.
+///
+/// Сообщения разбираются по описанию `proto/blog.proto`, поэтому слой добавляется
+/// после `GrpcWebLayer` и видит сообщения gRPC-Web уже в обычном формате.
+/// Сжатые gzip сообщения распаковываются для лога.
+#[derive(Clone)]
+pub struct BodyLogLayer {
+    body_logging: BodyLogging,
+    pool: DescriptorPool,
+}
+
+impl BodyLogLayer {
+    pub fn new(body_logging: BodyLogging) -> anyhow::Result<Self> {
+        Ok(Self {
+            body_logging,
+            pool: DescriptorPool::decode(api::FILE_DESCRIPTOR_SET)?,
+        })
+    }
+}
+
+impl<S> Layer<S> for BodyLogLayer {
+    type Service = BodyLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        BodyLogService {
+            inner,
+            body_logging: self.body_logging.clone(),
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BodyLogService<S> {
+    inner: S,
+    body_logging: BodyLogging,
+    pool: DescriptorPool,
+}
+
+impl<S> BodyLogService<S> {
+    /// Метод по пути запроса вида `/blog.Blog/Login`
+    fn method(&self, path: &str) -> Option<MethodDescriptor> {
+        let (service, method) = path.trim_start_matches('/').split_once('/')?;
+        self.pool
+            .get_service_by_name(service)?
+            .methods()
+            .find(|descriptor| descriptor.name() == method)
+    }
+}
+
+impl<S> Service<http::Request<Body>> for BodyLogService<S>
+where
+    S: Service<http::Request<Body>, Response = http::Response<Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<Body>;
+    type Error = S::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: http::Request<Body>) -> Self::Future {
+        if !self.body_logging.is_enabled() {
+            return Box::pin(self.inner.call(request));
+        }
+
+        let rpc = request.uri().path().to_string();
+        let method = self.method(&rpc);
+        // Ответ читается уже после выхода из спана вызова, поэтому спан запоминается
+        let span = Span::current();
+        let request_log = MessageLog::new(
+            Direction::Request,
+            &rpc,
+            method.as_ref().map(MethodDescriptor::input),
+            is_gzip(request.headers()),
+            span.clone(),
+        );
+        let request = request.map(|body| Body::new(LoggedBody::new(body, request_log)));
+
+        let response = self.inner.call(request);
+        Box::pin(async move {
+            let response = response.await?;
+            let response_log = MessageLog::new(
+                Direction::Response,
+                &rpc,
+                method.as_ref().map(MethodDescriptor::output),
+                is_gzip(response.headers()),
+                span,
+            );
+            Ok(response.map(|body| Body::new(LoggedBody::new(body, response_log))))
+        })
+    }
+}
+
+/// Сжаты ли сообщения gzip (заголовок `grpc-encoding`)
+fn is_gzip(headers: &http::HeaderMap) -> bool {
+    headers
+        .get("grpc-encoding")
+        .is_some_and(|encoding| encoding == "gzip")
+}
+
+/// Тело запроса или ответа, которое передаёт данные дальше без изменений
+/// и пишет в лог каждое полученное сообщение.
+struct LoggedBody {
+    inner: Body,
+    log: MessageLog,
+}
+
+impl LoggedBody {
+    fn new(inner: Body, log: MessageLog) -> Self {
+        Self { inner, log }
+    }
+}
+
+impl http_body::Body for LoggedBody {
+    type Data = Bytes;
+    type Error = tonic::Status;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let frame = ready!(Pin::new(&mut self.inner).poll_frame(cx));
+        if let Some(Ok(frame)) = &frame
+            && let Some(data) = frame.data_ref()
+        {
+            self.log.push(data);
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Собирает сообщения gRPC из фрагментов тела и пишет их в лог.
+struct MessageLog {
+    direction: Direction,
+    rpc: String,
+    /// Тип сообщений; у неизвестного метода в лог попадает только размер
+    message: Option<MessageDescriptor>,
+    gzip: bool,
+    span: Span,
+    buffer: BytesMut,
+    /// Сколько байт слишком большого сообщения ещё нужно пропустить
+    skip: usize,
+}
+
+impl MessageLog {
+    fn new(
+        direction: Direction,
+        rpc: &str,
+        message: Option<MessageDescriptor>,
+        gzip: bool,
+        span: Span,
+    ) -> Self {
+        Self {
+            direction,
+            rpc: rpc.to_string(),
+            message,
+            gzip,
+            span,
+            buffer: BytesMut::new(),
+            skip: 0,
+        }
+    }
+
+    fn push(&mut self, mut data: &[u8]) {
+        let skipped = self.skip.min(data.len());
+        self.skip -= skipped;
+        data = &data[skipped..];
+        self.buffer.extend_from_slice(data);
+
+        while self.buffer.len() >= MESSAGE_HEADER_LEN {
+            let compressed = self.buffer[0] & 1 == 1;
+            let len = u32::from_be_bytes([
+                self.buffer[1],
+                self.buffer[2],
+                self.buffer[3],
+                self.buffer[4],
+            ]) as usize;
+
+            if len > MAX_DECODED_MESSAGE_BYTES {
+                self.log(format!("<{len} bytes>"));
+                self.buffer.advance(MESSAGE_HEADER_LEN);
+                let buffered = len.min(self.buffer.len());
+                self.buffer.advance(buffered);
+                self.skip = len - buffered;
+                continue;
+            }
+            if self.buffer.len() < MESSAGE_HEADER_LEN + len {
+                return;
+            }
+
+            self.buffer.advance(MESSAGE_HEADER_LEN);
+            let message = self.buffer.split_to(len);
+            let text = self.describe(&message, compressed);
+            self.log(text);
+        }
+    }
+
+    /// Сообщение в виде JSON без чувствительных полей
+    fn describe(&self, message: &[u8], compressed: bool) -> String {
+        let Some(descriptor) = &self.message else {
+            return format!("<{} bytes>", message.len());
+        };
+        let decompressed;
+        let message = if compressed {
+            match self.gzip.then(|| gunzip(message)).flatten() {
+                Some(bytes) => {
+                    decompressed = bytes;
+                    decompressed.as_slice()
+                }
+                None => return format!("<{} bytes, compressed>", message.len()),
+            }
+        } else {
+            message
+        };
+
+        match DynamicMessage::decode(descriptor.clone(), message) {
+            Ok(message) => {
+                let mut value = message_to_json(&message);
+                redact(&mut value);
+                truncate(value.to_string())
+            }
+            Err(_) => format!(
+                "<{} bytes of invalid {}>",
+                message.len(),
+                descriptor.full_name()
+            ),
+        }
+    }
+
+    fn log(&self, text: String) {
+        let _entered = self.span.enter();
+        info!(
+            "{} message of {}: {}",
+            self.direction.as_str(),
+            self.rpc,
+            text
+        );
+    }
+}
+
+/// Распаковывает сообщение, если оно не больше [`MAX_DECODED_MESSAGE_BYTES`]
+fn gunzip(message: &[u8]) -> Option<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    GzDecoder::new(message)
+        .take(MAX_DECODED_MESSAGE_BYTES as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .ok()?;
+    (decompressed.len() <= MAX_DECODED_MESSAGE_BYTES).then_some(decompressed)
+}
+
+/// Заданные поля сообщения; enum записываются именами значений, `bytes` - размером
+fn message_to_json(message: &DynamicMessage) -> serde_json::Value {
+    message
+        .fields()
+        .map(|(field, value)| {
+            (
+                field.name().to_string(),
+                value_to_json(value, &field.kind()),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+fn value_to_json(value: &Value, kind: &Kind) -> serde_json::Value {
+    match value {
+        Value::Bool(value) => (*value).into(),
+        Value::I32(value) => (*value).into(),
+        Value::I64(value) => (*value).into(),
+        Value::U32(value) => (*value).into(),
+        Value::U64(value) => (*value).into(),
+        Value::F32(value) => f64::from(*value).into(),
+        Value::F64(value) => (*value).into(),
+        Value::String(value) => value.clone().into(),
+        Value::Bytes(value) => format!("<{} bytes>", value.len()).into(),
+        Value::EnumNumber(number) => kind
+            .as_enum()
+            .and_then(|descriptor| descriptor.get_value(*number))
+            .map_or_else(|| (*number).into(), |value| value.name().into()),
+        Value::Message(message) => message_to_json(message),
+        Value::List(items) => items.iter().map(|item| value_to_json(item, kind)).collect(),
+        Value::Map(entries) => {
+            let value_kind = kind
+                .as_message()
+                .map(|entry| entry.map_entry_value_field().kind());
+            entries
+                .iter()
+                .map(|(key, value)| {
+                    let value = value_kind
+                        .as_ref()
+                        .map_or(serde_json::Value::Null, |kind| value_to_json(value, kind));
+                    (map_key(key), value)
+                })
+                .collect::<serde_json::Map<_, _>>()
+                .into()
+        }
+    }
+}
+
+fn map_key(key: &MapKey) -> String {
+    match key {
+        MapKey::Bool(key) => key.to_string(),
+        MapKey::I32(key) => key.to_string(),
+        MapKey::I64(key) => key.to_string(),
+        MapKey::U32(key) => key.to_string(),
+        MapKey::U64(key) => key.to_string(),
+        MapKey::String(key) => key.clone(),
+    }
+}
@@ -1,5 +1,12 @@
 pub mod auth;
+pub mod body_log;
+pub mod rate_limit;
 pub mod service;
+pub mod trace;
+pub mod web;
 
 pub use auth::AuthInterceptor;
+pub use body_log::BodyLogLayer;
+pub use rate_limit::RateLimitInterceptor;
 pub use service::BlogServiceImpl;
+pub use trace::TraceLayer;
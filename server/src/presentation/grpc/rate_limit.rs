@@ -0,0 +1,38 @@
+use crate::infrastructure::rate_limit::RateLimiter;
+use std::sync::Arc;
+use tonic::metadata::MetadataValue;
+use tonic::{Request, Status};
+use tracing::warn;
+
+/// Interceptor для ограничения частоты запросов с одного IP-адреса
+#[derive(Clone)]
+pub struct RateLimitInterceptor {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitInterceptor {
+    pub fn new(limiter: Arc<RateLimiter>) -> Self {
+        Self { limiter }
+    }
+
+    /// Списывает запрос с корзины адреса клиента.
+    ///
+    /// При превышении лимита возвращает `RESOURCE_EXHAUSTED` с метаданными `retry-after`
+    /// (в секундах), аналогично заголовку `Retry-After` HTTP API.
+    pub fn check<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let Some(addr) = request.remote_addr() else {
+            return Ok(());
+        };
+
+        self.limiter.check(addr.ip()).map_err(|retry_after_secs| {
+            warn!("Rate limit exceeded for {}", addr.ip());
+            let mut status = Status::resource_exhausted(format!(
+                "Too many requests, retry in {retry_after_secs} s"
+            ));
+            status
+                .metadata_mut()
+                .insert("retry-after", MetadataValue::from(retry_after_secs));
+            status
+        })
+    }
+}
@@ -1,82 +1,527 @@
 use std::sync::Arc;
 
 use api::blog_server::Blog;
+use api::rest::{self, MAX_PAGE_SIZE};
 use api::{
-    CreatePostRequest, DeletePostRequest, DeletePostResponse, GetPostRequest, JwtContainer,
-    ListPostsRequest, ListPostsResponse, LoginRequest, LoginResponse, Post as ProtoPost,
-    PostResponse, RefreshTokenRequest, RefreshTokenResponse, RegisterRequest, RegisterResponse,
-    Response as ProtoResponse, Status as ProtoStatus, UpdatePostRequest,
+    AdminUser as ProtoAdminUser, Attachment as ProtoAttachment, BlogInfo as ProtoBlog,
+    BlogResponse, Category as ProtoCategory, CategoryResponse, CheckUsernameRequest,
+    CheckUsernameResponse, CreateBlogRequest, CreateCategoryRequest, CreatePostRequest,
+    CreatePostsResponse, DeleteBlogRequest, DeleteBlogResponse, DeletePostRequest,
+    DeletePostResponse, DeletePostsRequest, DeletePostsResponse,
+    DigestFrequency as ProtoDigestFrequency, DigestSettingsResponse, FollowResponse,
+    FollowUserRequest, ForceDeletePostRequest, ForceDeletePostResponse, ForgotPasswordRequest,
+    ForgotPasswordResponse, GetBlogRequest, GetDigestSettingsRequest, GetFeedRequest,
+    GetFollowStatusRequest, GetPostRequest, GetServerInfoRequest, GetSystemStatsRequest,
+    GetUserProfileRequest, JwtContainer, LikePostRequest, ListBlogsRequest, ListBlogsResponse,
+    ListCategoriesRequest, ListCategoriesResponse, ListPostsByAuthorRequest, ListPostsRequest,
+    ListPostsResponse, ListReportsRequest, ListReportsResponse, ListSessionsRequest,
+    ListSessionsResponse, ListTrendingPostsRequest, ListTrendingPostsResponse, ListUsersRequest,
+    ListUsersResponse, LoginRequest, LoginResponse, LoginWithTotpRequest, LogoutAllRequest,
+    LogoutAllResponse, LogoutRequest, LogoutResponse, Post as ProtoPost,
+    PostLimits as ProtoPostLimits, PostResponse, PostSortField as ProtoPostSortField,
+    PostStatus as ProtoPostStatus, PublishPostRequest, PurgePostRequest, PurgePostResponse,
+    RefreshTokenRequest, RefreshTokenResponse, RegisterRequest, RegisterResponse,
+    Report as ProtoReport, ReportPostRequest, ReportResponse, ReportStatus as ProtoReportStatus,
+    ResetPasswordRequest, ResetPasswordResponse, ResolveReportRequest, Response as ProtoResponse,
+    RestorePostRequest, RevokeSessionRequest, RevokeSessionResponse, ServerInfoResponse,
+    Session as ProtoSession, SetUserDisabledRequest, SetUserDisabledResponse,
+    SortOrder as ProtoSortOrder, Status as ProtoStatus, StreamPostsRequest, SystemStatsResponse,
+    UnfollowUserRequest, UnlikePostRequest, UnpublishPostRequest, UpdateBlogRequest,
+    UpdateDigestSettingsRequest, UpdateMyProfileRequest, UpdatePostRequest, UploadFileRequest,
+    UploadFileResponse, UserProfile as ProtoUserProfile, UserProfileResponse,
 };
-use prost_types::Timestamp;
-use tonic::{Request, Response, Status};
+use prost_types::{FieldMask, Timestamp};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status, Streaming};
 use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
 
-use super::auth::AuthInterceptor;
+use super::auth::{AuthInterceptor, device_info};
+use super::rate_limit::RateLimitInterceptor;
+use crate::application::admin::AdminApplication;
+use crate::application::audit::Audited;
 use crate::application::auth::AuthApplication;
-use crate::application::dto::auth::{LoginDto, RegisterDto};
-use crate::application::dto::post::{CreatePostDto, UpdatePostDto};
+use crate::application::blog::BlogApplication;
+use crate::application::category::CategoryApplication;
+use crate::application::digest::DigestApplication;
+use crate::application::dto::admin::UserSummaryDto;
+use crate::application::dto::auth::{
+    LoginDto, LoginOutcome, RegisterDto, ResetPasswordDto, SessionDto, TokenDto, TotpLoginDto,
+};
+use crate::application::dto::blog::{BlogDto, CreateBlogDto};
+use crate::application::dto::category::{CategoryDto, CreateCategoryDto};
+use crate::application::dto::post::{CreatePostDto, PostDto, UpdatePostDto};
+use crate::application::dto::profile::{UpdateProfileDto, UserProfileDto};
+use crate::application::dto::report::{CreateReportDto, ReportDto};
+use crate::application::dto::upload::AttachmentDto;
+use crate::application::follow::FollowApplication;
 use crate::application::post::PostApplication;
+use crate::application::profile::ProfileApplication;
+use crate::application::report::ReportApplication;
+use crate::application::upload::UploadApplication;
 use crate::domain::entities::errors::DomainError;
-use crate::domain::repositories::repo::UserRepository;
-use crate::domain::services::auth::AuthService;
+use crate::domain::entities::post::{PostFields, PostSort, PostStatus, check_batch_size};
+use crate::domain::entities::report::ReportResolution;
+use crate::domain::entities::user::IdentifierKind;
+use crate::domain::repositories::Repository;
+use crate::domain::repositories::post::PostRepository;
+use crate::domain::services::auth::Claims;
+use crate::infrastructure::rate_limit::RateLimiter;
+use crate::presentation::request_id::current_trace_id;
+use crate::presentation::server_info::ServerInfo;
+
+/// Размер порции `StreamPosts` по умолчанию
+const STREAM_CHUNK_SIZE_DEFAULT: u32 = 50;
+/// Максимальный размер порции `StreamPosts`
+const STREAM_CHUNK_SIZE_MAX: u32 = 100;
+
+/// Преобразует статус публикации из protobuf в доменный.
+fn status_from_proto(status: ProtoPostStatus) -> PostStatus {
+    rest::PostStatus::from(status).into()
+}
+
+/// Преобразует порядок сортировки из protobuf в доменный.
+fn sort_from_proto(field: ProtoPostSortField, order: ProtoSortOrder) -> PostSort {
+    PostSort {
+        field: rest::PostSortField::from(field).into(),
+        order: rest::SortOrder::from(order).into(),
+    }
+}
+
+/// Преобразует пост прикладного слоя в сообщение protobuf.
+///
+/// Поля сопоставляются так же, как в ответе REST API (см. [`api::convert`]).
+fn post_to_proto(post_dto: PostDto) -> ProtoPost {
+    rest::PostResponse::from(post_dto).into()
+}
+
+/// Поля `Post`, которые можно указать в маске `field_mask`
+const POST_MASK_PATHS: &[&str] = &[
+    "id",
+    "title",
+    "data",
+    "created_ts",
+    "last_updated_ts",
+    "author_id",
+    "author_username",
+    "tags",
+    "status",
+    "like_count",
+    "liked_by_me",
+    "author_avatar_url",
+    "attachment_urls",
+    "content_html",
+    "category_id",
+    "summary",
+    "blog_id",
+];
+
+/// Поля поста, выбранные маской `field_mask`; `None` - все поля.
+struct PostMask(Option<Vec<&'static str>>);
+
+impl PostMask {
+    /// Разбирает маску; без маски или с пустой маской выбираются все поля.
+    ///
+    /// `id` выбирается всегда, неизвестный путь - ошибка запроса.
+    fn parse(mask: Option<FieldMask>) -> Result<Self, Status> {
+        let mut selected = vec!["id"];
+        for path in mask.map(|mask| mask.paths).unwrap_or_default() {
+            let Some(&name) = POST_MASK_PATHS.iter().find(|&&name| name == path) else {
+                return Err(Status::invalid_argument(format!(
+                    "Unknown post field: {path}"
+                )));
+            };
+            if !selected.contains(&name) {
+                selected.push(name);
+            }
+        }
+        if selected.len() == 1 {
+            return Ok(Self(None));
+        }
+        Ok(Self(Some(selected)))
+    }
+
+    /// Части постов, которые нужно прочитать из хранилища для выбранных полей
+    fn storage_fields(&self) -> PostFields {
+        let Some(selected) = &self.0 else {
+            return PostFields::ALL;
+        };
+        let any = |names: &[&str]| names.iter().any(|name| selected.contains(name));
+        PostFields {
+            // Описание без заданного автором текста составляется из содержимого
+            content: any(&["data", "content_html", "summary"]),
+            tags: any(&["tags"]),
+            attachments: any(&["attachment_urls"]),
+            likes: any(&["like_count", "liked_by_me"]),
+        }
+    }
+
+    /// Сообщение поста только с выбранными полями; остальные имеют значения по умолчанию
+    fn apply(&self, post_dto: PostDto) -> ProtoPost {
+        let mut full = post_to_proto(post_dto);
+        let Some(selected) = &self.0 else {
+            return full;
+        };
+        let mut post = ProtoPost {
+            id: std::mem::take(&mut full.id),
+            ..ProtoPost::default()
+        };
+        for &name in selected {
+            match name {
+                "title" => post.title = std::mem::take(&mut full.title),
+                "data" => post.data = std::mem::take(&mut full.data),
+                "created_ts" => post.created_ts = full.created_ts.take(),
+                "last_updated_ts" => post.last_updated_ts = full.last_updated_ts.take(),
+                "author_id" => post.author_id = std::mem::take(&mut full.author_id),
+                "author_username" => {
+                    post.author_username = std::mem::take(&mut full.author_username)
+                }
+                "tags" => post.tags = std::mem::take(&mut full.tags),
+                "status" => post.status = full.status,
+                "like_count" => post.like_count = full.like_count,
+                "liked_by_me" => post.liked_by_me = full.liked_by_me,
+                "author_avatar_url" => post.author_avatar_url = full.author_avatar_url.take(),
+                "attachment_urls" => {
+                    post.attachment_urls = std::mem::take(&mut full.attachment_urls)
+                }
+                "content_html" => post.content_html = full.content_html.take(),
+                "category_id" => post.category_id = full.category_id.take(),
+                "summary" => post.summary = std::mem::take(&mut full.summary),
+                "blog_id" => post.blog_id = full.blog_id.take(),
+                _ => {}
+            }
+        }
+        post
+    }
+}
+
+/// Преобразует вложение прикладного слоя в сообщение protobuf.
+fn attachment_to_proto(attachment_dto: AttachmentDto) -> ProtoAttachment {
+    rest::UploadResponse::from(attachment_dto).into()
+}
+
+/// Проверяет размер страницы списка постов по правилам REST (`PaginationQuery`):
+/// от 1 до [`MAX_PAGE_SIZE`]. Иначе возвращает готовый ответ с ошибкой.
+fn check_page_size(page_size: u32) -> Result<(), ListPostsResponse> {
+    if (1..=MAX_PAGE_SIZE).contains(&page_size) {
+        return Ok(());
+    }
+    Err(ListPostsResponse {
+        status: Some(ProtoResponse {
+            code: ProtoStatus::InvalidRequest as i32,
+            details: Some(format!("page_size must be between 1 and {MAX_PAGE_SIZE}")),
+            trace_id: current_trace_id(),
+        }),
+        posts: vec![],
+        total: 0,
+        has_next: false,
+        next_cursor: None,
+    })
+}
 
-pub struct BlogServiceImpl<Repo: UserRepository> {
-    auth_app: Arc<AuthApplication<Repo>>,
-    post_app: Arc<PostApplication<Repo>>,
-    auth_interceptor: AuthInterceptor,
+/// Разбирает UUID вложений из запроса.
+fn parse_attachment_ids(ids: Vec<String>) -> Result<Vec<Uuid>, Status> {
+    ids.iter()
+        .map(|id| Uuid::parse_str(id))
+        .collect::<Result<_, _>>()
+        .map_err(|_| Status::invalid_argument("Invalid attachment UUID format"))
 }
 
-impl<Repo: UserRepository> BlogServiceImpl<Repo> {
+/// Разбирает UUID категории из запроса; пустая строка означает «без категории».
+fn parse_category_id(id: &str) -> Result<Option<Uuid>, Status> {
+    if id.is_empty() {
+        return Ok(None);
+    }
+    Uuid::parse_str(id)
+        .map(Some)
+        .map_err(|_| Status::invalid_argument("Invalid category UUID format"))
+}
+
+/// Преобразует категорию прикладного слоя в сообщение protobuf.
+fn category_to_proto(category_dto: CategoryDto) -> ProtoCategory {
+    rest::CategoryResponse::from(category_dto).into()
+}
+
+/// Преобразует блог прикладного слоя в сообщение protobuf.
+fn blog_to_proto(blog_dto: BlogDto) -> ProtoBlog {
+    rest::BlogResponse::from(blog_dto).into()
+}
+
+/// Преобразует профиль прикладного слоя в сообщение protobuf.
+fn profile_to_proto(profile_dto: UserProfileDto) -> ProtoUserProfile {
+    rest::UserProfileResponse::from(profile_dto).into()
+}
+
+/// Преобразует пользователя из списка администратора в сообщение protobuf.
+fn admin_user_to_proto(user_dto: UserSummaryDto) -> ProtoAdminUser {
+    rest::AdminUserResponse::from(user_dto).into()
+}
+
+/// Преобразует жалобу прикладного слоя в сообщение protobuf.
+fn report_to_proto(report_dto: ReportDto) -> ProtoReport {
+    rest::ReportResponse::from(report_dto).into()
+}
+
+/// Преобразует сеанс входа прикладного слоя в сообщение protobuf.
+fn session_to_proto(session_dto: SessionDto) -> ProtoSession {
+    rest::SessionResponse::from(session_dto).into()
+}
+
+pub struct BlogServiceImpl<Repo: Repository + ?Sized> {
+    auth_app: Arc<Audited<AuthApplication<Repo>, Repo>>,
+    post_app: Arc<Audited<PostApplication<dyn PostRepository, Repo>, Repo>>,
+    profile_app: Arc<ProfileApplication<Repo>>,
+    upload_app: Arc<UploadApplication<Repo>>,
+    admin_app: Arc<Audited<AdminApplication<Repo>, Repo>>,
+    report_app: Arc<ReportApplication<Repo>>,
+    follow_app: Arc<FollowApplication<Repo>>,
+    digest_app: Arc<DigestApplication<Repo>>,
+    category_app: Arc<CategoryApplication<Repo>>,
+    blog_app: Arc<BlogApplication<Repo>>,
+    auth_interceptor: AuthInterceptor<Repo>,
+    auth_rate_limit: RateLimitInterceptor,
+    server_info: Arc<ServerInfo>,
+}
+
+impl<Repo: Repository + ?Sized> BlogServiceImpl<Repo> {
+    // Каждый прикладной сервис передаётся отдельно, как поля `AppState` HTTP сервера
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
-        auth_app: Arc<AuthApplication<Repo>>,
-        post_app: Arc<PostApplication<Repo>>,
-        auth_service: Arc<AuthService>,
+        auth_app: Arc<Audited<AuthApplication<Repo>, Repo>>,
+        post_app: Arc<Audited<PostApplication<dyn PostRepository, Repo>, Repo>>,
+        profile_app: Arc<ProfileApplication<Repo>>,
+        upload_app: Arc<UploadApplication<Repo>>,
+        admin_app: Arc<Audited<AdminApplication<Repo>, Repo>>,
+        report_app: Arc<ReportApplication<Repo>>,
+        follow_app: Arc<FollowApplication<Repo>>,
+        digest_app: Arc<DigestApplication<Repo>>,
+        category_app: Arc<CategoryApplication<Repo>>,
+        blog_app: Arc<BlogApplication<Repo>>,
+        auth_rate_limiter: Arc<RateLimiter>,
+        server_info: Arc<ServerInfo>,
     ) -> Self {
         Self {
+            auth_interceptor: AuthInterceptor::new(auth_app.clone()),
             auth_app,
             post_app,
-            auth_interceptor: AuthInterceptor::new(auth_service),
+            profile_app,
+            upload_app,
+            admin_app,
+            report_app,
+            follow_app,
+            digest_app,
+            category_app,
+            blog_app,
+            auth_rate_limit: RateLimitInterceptor::new(auth_rate_limiter),
+            server_info,
+        }
+    }
+
+    /// Находит ID блога по адресу из запроса; без адреса - общая лента сервера
+    async fn blog_id(&self, slug: Option<&str>) -> Result<Option<Uuid>, DomainError> {
+        match slug.filter(|slug| !slug.is_empty()) {
+            Some(slug) => Ok(Some(self.blog_app.get_blog(slug).await?.id)),
+            None => Ok(None),
         }
     }
 
+    /// Ответ с ошибкой; `trace_id` совпадает с `request_id` в логах сервера
     fn map_domain_error(error: DomainError) -> ProtoResponse {
-        match error {
-            DomainError::UserAlreadyExists { .. } => ProtoResponse {
-                code: ProtoStatus::InvalidRequest as i32,
-                details: Some(error.to_string()),
-            },
-            DomainError::UserNotFound { .. } => ProtoResponse {
-                code: ProtoStatus::Unauthorized as i32,
-                details: Some(error.to_string()),
-            },
-            DomainError::InvalidCredentials => ProtoResponse {
-                code: ProtoStatus::Unauthorized as i32,
-                details: Some(error.to_string()),
-            },
-            DomainError::PostNotFound { .. } => ProtoResponse {
-                code: ProtoStatus::InvalidRequest as i32,
-                details: Some(error.to_string()),
-            },
-            DomainError::Forbidden { .. } => ProtoResponse {
-                code: ProtoStatus::Unauthorized as i32,
-                details: Some(error.to_string()),
-            },
-            _ => ProtoResponse {
-                code: ProtoStatus::InternalError as i32,
-                details: Some(error.to_string()),
-            },
+        let code = match error {
+            DomainError::UserAlreadyExists { .. } => ProtoStatus::InvalidRequest,
+            // Неизвестный пользователь при входе - те же неверные учётные данные
+            DomainError::UserNotFound { .. } => ProtoStatus::Unauthorized,
+            DomainError::InvalidUsername { .. } => ProtoStatus::InvalidRequest,
+            DomainError::InvalidCredentials => ProtoStatus::Unauthorized,
+            DomainError::AccountLocked { .. } => ProtoStatus::AccountLocked,
+            DomainError::OAuthProviderNotConfigured { .. } => ProtoStatus::NotFound,
+            DomainError::OAuthError(_) => ProtoStatus::Unauthorized,
+            DomainError::InvalidPassword { .. } => ProtoStatus::InvalidRequest,
+            DomainError::InvalidResetToken => ProtoStatus::InvalidRequest,
+            DomainError::InvalidUnsubscribeToken => ProtoStatus::InvalidRequest,
+            DomainError::InvalidTotpCode
+            | DomainError::TwoFactorAlreadyEnabled
+            | DomainError::TwoFactorNotEnrolled => ProtoStatus::InvalidRequest,
+            DomainError::PostNotFound { .. } => ProtoStatus::NotFound,
+            DomainError::InvalidTags { .. } | DomainError::InvalidSummary { .. } => {
+                ProtoStatus::InvalidRequest
+            }
+            DomainError::ProfileNotFound { .. } => ProtoStatus::NotFound,
+            DomainError::SessionNotFound { .. } => ProtoStatus::NotFound,
+            DomainError::InvalidProfile { .. } => ProtoStatus::InvalidRequest,
+            DomainError::InvalidUpload { .. } => ProtoStatus::InvalidRequest,
+            DomainError::InvalidAttachments { .. } => ProtoStatus::InvalidRequest,
+            DomainError::InvalidBatch { .. } => ProtoStatus::InvalidRequest,
+            DomainError::InvalidCursor { .. } => ProtoStatus::InvalidRequest,
+            DomainError::InvalidIdempotencyKey { .. }
+            | DomainError::IdempotencyKeyInProgress
+            | DomainError::IdempotencyKeyReused => ProtoStatus::InvalidRequest,
+            DomainError::CategoryAlreadyExists { .. } | DomainError::InvalidCategory { .. } => {
+                ProtoStatus::InvalidRequest
+            }
+            DomainError::BlogAlreadyExists { .. } | DomainError::InvalidBlog { .. } => {
+                ProtoStatus::InvalidRequest
+            }
+            DomainError::BlogNotFound { .. } => ProtoStatus::NotFound,
+            DomainError::CategoryNotFound { .. } => ProtoStatus::NotFound,
+            DomainError::ReportAlreadyExists { .. }
+            | DomainError::ReportAlreadyResolved { .. }
+            | DomainError::InvalidReport { .. }
+            | DomainError::InvalidFollow { .. } => ProtoStatus::InvalidRequest,
+            DomainError::ReportNotFound { .. } => ProtoStatus::NotFound,
+            DomainError::Forbidden { .. } => ProtoStatus::Forbidden,
+            DomainError::AccountDisabled => ProtoStatus::Forbidden,
+            DomainError::QuotaExceeded { .. } => ProtoStatus::QuotaExceeded,
+            // Отозванный или недействительный токен: клиент должен войти заново
+            DomainError::TokenValidationError(_) => ProtoStatus::Unauthorized,
+            DomainError::StorageError(_)
+            | DomainError::EmailError(_)
+            | DomainError::CacheError(_)
+            | DomainError::RepositoryError(_)
+            | DomainError::TokenGenerationError(_) => ProtoStatus::InternalError,
+        };
+        ProtoResponse {
+            code: code as i32,
+            details: Some(error.to_string()),
+            trace_id: current_trace_id(),
+        }
+    }
+
+    /// Успешный ответ на вход с парой токенов.
+    fn login_response(token_dto: TokenDto) -> LoginResponse {
+        let expires_at = chrono::Utc::now() + chrono::Duration::seconds(token_dto.expires_in);
+
+        LoginResponse {
+            status: Some(ProtoResponse {
+                code: ProtoStatus::Ok as i32,
+                details: Some("Login successful".to_string()),
+                trace_id: None,
+            }),
+            token: Some(JwtContainer {
+                access_token: token_dto.access_token,
+                refresh_token: token_dto.refresh_token,
+                expires_in: Some(Timestamp {
+                    seconds: expires_at.timestamp(),
+                    nanos: expires_at.timestamp_subsec_nanos() as i32,
+                }),
+            }),
+            two_factor_challenge: None,
+        }
+    }
+
+    /// Меняет статус поста от имени пользователя из claims токена.
+    async fn change_post_status(
+        &self,
+        claims: &Claims,
+        post_id: &str,
+        status: PostStatus,
+    ) -> Result<Response<PostResponse>, Status> {
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+        let uuid = Uuid::parse_str(post_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+
+        let result = match status {
+            PostStatus::Published => self.post_app.publish_post(uuid, user_id, claims.role).await,
+            PostStatus::Draft => {
+                self.post_app
+                    .unpublish_post(uuid, user_id, claims.role)
+                    .await
+            }
+        };
+
+        match result {
+            Ok(post_dto) => {
+                info!("Post status changed to {}", status.as_str());
+                Ok(Response::new(PostResponse {
+                    response: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Post status changed successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    post: Some(post_to_proto(post_dto)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to change post status: {}", e);
+                Ok(Response::new(PostResponse {
+                    response: Some(Self::map_domain_error(e)),
+                    post: None,
+                }))
+            }
+        }
+    }
+
+    /// Ставит (`like = true`) или снимает лайк от имени пользователя `user_sub`.
+    async fn toggle_like(
+        &self,
+        user_sub: &str,
+        post_id: &str,
+        like: bool,
+    ) -> Result<Response<PostResponse>, Status> {
+        let user_id =
+            Uuid::parse_str(user_sub).map_err(|_| Status::internal("Invalid user ID in token"))?;
+        let uuid = Uuid::parse_str(post_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+
+        let result = if like {
+            self.post_app.like_post(uuid, user_id).await
+        } else {
+            self.post_app.unlike_post(uuid, user_id).await
+        };
+
+        match result {
+            Ok(post_dto) => {
+                info!("Post like toggled (like = {})", like);
+                Ok(Response::new(PostResponse {
+                    response: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Post like updated successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    post: Some(post_to_proto(post_dto)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to update post like: {}", e);
+                Ok(Response::new(PostResponse {
+                    response: Some(Self::map_domain_error(e)),
+                    post: None,
+                }))
+            }
         }
     }
 }
 
 #[tonic::async_trait]
-impl<Repo: UserRepository + Send + Sync + 'static> Blog for BlogServiceImpl<Repo> {
+impl<Repo: Repository + ?Sized + 'static> Blog for BlogServiceImpl<Repo> {
+    type StreamPostsStream = ReceiverStream<Result<ProtoPost, Status>>;
+
+    #[instrument(skip(self, _request))]
+    async fn get_server_info(
+        &self,
+        _request: Request<GetServerInfoRequest>,
+    ) -> Result<Response<ServerInfoResponse>, Status> {
+        Ok(Response::new(ServerInfoResponse {
+            status: Some(ProtoResponse {
+                code: ProtoStatus::Ok as i32,
+                details: None,
+                trace_id: None,
+            }),
+            version: self.server_info.version.clone(),
+            api_versions: self.server_info.api_versions.clone(),
+            features: self.server_info.features.clone(),
+            post_limits: Some(ProtoPostLimits::from(self.server_info.post_limits)),
+        }))
+    }
+
     #[instrument(skip(self, request))]
     async fn register(
         &self,
         request: Request<RegisterRequest>,
     ) -> Result<Response<RegisterResponse>, Status> {
+        self.auth_rate_limit.check(&request)?;
         let req = request.into_inner();
         debug!("Register request received for login: {}", req.login);
 
@@ -93,6 +538,7 @@ impl<Repo: UserRepository + Send + Sync + 'static> Blog for BlogServiceImpl<Repo
                     status: Some(ProtoResponse {
                         code: ProtoStatus::Ok as i32,
                         details: Some("User registered successfully".to_string()),
+                        trace_id: None,
                     }),
                 }))
             }
@@ -110,34 +556,35 @@ impl<Repo: UserRepository + Send + Sync + 'static> Blog for BlogServiceImpl<Repo
         &self,
         request: Request<LoginRequest>,
     ) -> Result<Response<LoginResponse>, Status> {
+        self.auth_rate_limit.check(&request)?;
+        let device = device_info(&request);
         let req = request.into_inner();
         debug!("Login request received for: {}", req.email_or_login);
 
         let dto = LoginDto {
-            username: req.email_or_login,
+            kind: IdentifierKind::detect(&req.email_or_login),
+            identifier: req.email_or_login,
             password: req.password,
+            device,
         };
 
         match self.auth_app.login(dto).await {
-            Ok(token_dto) => {
+            Ok(LoginOutcome::Authenticated(token_dto)) => {
                 info!("User logged in successfully");
-
-                let expires_at =
-                    chrono::Utc::now() + chrono::Duration::seconds(token_dto.expires_in);
-
+                Ok(Response::new(Self::login_response(token_dto)))
+            }
+            Ok(LoginOutcome::TwoFactorRequired {
+                challenge_token, ..
+            }) => {
+                info!("Two-factor code required");
                 Ok(Response::new(LoginResponse {
                     status: Some(ProtoResponse {
                         code: ProtoStatus::Ok as i32,
-                        details: Some("Login successful".to_string()),
-                    }),
-                    token: Some(JwtContainer {
-                        access_token: token_dto.access_token,
-                        refresh_token: token_dto.refresh_token,
-                        expires_in: Some(Timestamp {
-                            seconds: expires_at.timestamp(),
-                            nanos: expires_at.timestamp_subsec_nanos() as i32,
-                        }),
+                        details: Some("Two-factor authentication code required".to_string()),
+                        trace_id: None,
                     }),
+                    token: None,
+                    two_factor_challenge: Some(challenge_token),
                 }))
             }
             Err(e) => {
@@ -145,6 +592,39 @@ impl<Repo: UserRepository + Send + Sync + 'static> Blog for BlogServiceImpl<Repo
                 Ok(Response::new(LoginResponse {
                     status: Some(Self::map_domain_error(e)),
                     token: None,
+                    two_factor_challenge: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn login_with_totp(
+        &self,
+        request: Request<LoginWithTotpRequest>,
+    ) -> Result<Response<LoginResponse>, Status> {
+        self.auth_rate_limit.check(&request)?;
+        let device = device_info(&request);
+        let req = request.into_inner();
+        debug!("Two-factor login request received");
+
+        let dto = TotpLoginDto {
+            challenge_token: req.challenge_token,
+            code: req.code,
+            device,
+        };
+
+        match self.auth_app.login_with_totp(dto).await {
+            Ok(token_dto) => {
+                info!("User logged in successfully with two-factor code");
+                Ok(Response::new(Self::login_response(token_dto)))
+            }
+            Err(e) => {
+                warn!("Two-factor login failed: {}", e);
+                Ok(Response::new(LoginResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    token: None,
+                    two_factor_challenge: None,
                 }))
             }
         }
@@ -155,10 +635,11 @@ impl<Repo: UserRepository + Send + Sync + 'static> Blog for BlogServiceImpl<Repo
         &self,
         request: Request<RefreshTokenRequest>,
     ) -> Result<Response<RefreshTokenResponse>, Status> {
+        let device = device_info(&request);
         let req = request.into_inner();
         debug!("Refresh token request received");
 
-        match self.auth_app.refresh_token(req.refresh_token).await {
+        match self.auth_app.refresh_token(req.refresh_token, device).await {
             Ok(token_dto) => {
                 info!("Token refreshed successfully");
 
@@ -169,6 +650,7 @@ impl<Repo: UserRepository + Send + Sync + 'static> Blog for BlogServiceImpl<Repo
                     status: Some(ProtoResponse {
                         code: ProtoStatus::Ok as i32,
                         details: Some("Token refreshed successfully".to_string()),
+                        trace_id: None,
                     }),
                     token: Some(JwtContainer {
                         access_token: token_dto.access_token,
@@ -191,242 +673,2099 @@ impl<Repo: UserRepository + Send + Sync + 'static> Blog for BlogServiceImpl<Repo
     }
 
     #[instrument(skip(self, request))]
-    async fn create_post(
+    async fn check_username(
         &self,
-        request: Request<CreatePostRequest>,
-    ) -> Result<Response<PostResponse>, Status> {
-        // Проверяем JWT токен и извлекаем claims
-        let claims = self.auth_interceptor.verify_token(&request)?;
-        debug!("Authenticated user: {}", claims.user_name);
-
+        request: Request<CheckUsernameRequest>,
+    ) -> Result<Response<CheckUsernameResponse>, Status> {
         let req = request.into_inner();
-        debug!("Create post request received");
+        debug!("Check username request received for login: {}", req.login);
 
-        // Используем user_id из токена
-        let author_id = Uuid::parse_str(&claims.sub)
-            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+        match self.auth_app.is_username_available(&req.login).await {
+            Ok(available) => Ok(Response::new(CheckUsernameResponse {
+                status: Some(ProtoResponse {
+                    code: ProtoStatus::Ok as i32,
+                    details: None,
+                    trace_id: None,
+                }),
+                available,
+            })),
+            Err(e) => {
+                error!("Username availability check failed: {}", e);
+                Ok(Response::new(CheckUsernameResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    available: false,
+                }))
+            }
+        }
+    }
 
-        let dto = CreatePostDto {
-            title: req.title,
-            content: req.data,
-            author_id,
-        };
+    #[instrument(skip(self, request))]
+    async fn logout(
+        &self,
+        request: Request<LogoutRequest>,
+    ) -> Result<Response<LogoutResponse>, Status> {
+        let req = request.into_inner();
+        debug!("Logout request received");
 
-        match self.post_app.create_post(dto).await {
-            Ok(post_dto) => {
-                info!("Post created successfully with id: {}", post_dto.uuid);
-                Ok(Response::new(PostResponse {
-                    response: Some(ProtoResponse {
+        match self.auth_app.logout(req.refresh_token).await {
+            Ok(()) => {
+                info!("User logged out successfully");
+                Ok(Response::new(LogoutResponse {
+                    status: Some(ProtoResponse {
                         code: ProtoStatus::Ok as i32,
-                        details: Some("Post created successfully".to_string()),
-                    }),
-                    post: Some(ProtoPost {
-                        id: post_dto.uuid.to_string(),
-                        title: post_dto.title,
-                        data: post_dto.content,
-                        created_ts: Some(Timestamp {
-                            seconds: post_dto.created_at.timestamp(),
-                            nanos: post_dto.created_at.timestamp_subsec_nanos() as i32,
-                        }),
-                        last_updated_ts: Some(Timestamp {
-                            seconds: post_dto.updated_at.timestamp(),
-                            nanos: post_dto.updated_at.timestamp_subsec_nanos() as i32,
-                        }),
+                        details: Some("Logged out successfully".to_string()),
+                        trace_id: None,
                     }),
                 }))
             }
             Err(e) => {
-                error!("Failed to create post: {}", e);
-                Ok(Response::new(PostResponse {
-                    response: Some(Self::map_domain_error(e)),
-                    post: None,
+                warn!("Logout failed: {}", e);
+                Ok(Response::new(LogoutResponse {
+                    status: Some(Self::map_domain_error(e)),
                 }))
             }
         }
     }
 
     #[instrument(skip(self, request))]
-    async fn get_post(
+    async fn logout_all(
         &self,
-        request: Request<GetPostRequest>,
-    ) -> Result<Response<PostResponse>, Status> {
-        // GetPost - публичный метод, не требует аутентификации
-        let req = request.into_inner();
-        debug!("Get post request received for id: {}", req.id);
+        request: Request<LogoutAllRequest>,
+    ) -> Result<Response<LogoutAllResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        debug!("Logout from all devices request received");
 
-        // Конвертируем строку в UUID
-        let uuid = Uuid::parse_str(&req.id)
-            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
 
-        match self.post_app.get_post_by_id(uuid).await {
-            Ok(post_dto) => {
-                info!("Post retrieved successfully");
-                Ok(Response::new(PostResponse {
-                    response: Some(ProtoResponse {
+        match self.auth_app.logout_all(user_id).await {
+            Ok(()) => {
+                info!("User logged out from all devices");
+                Ok(Response::new(LogoutAllResponse {
+                    status: Some(ProtoResponse {
                         code: ProtoStatus::Ok as i32,
-                        details: Some("Post retrieved successfully".to_string()),
-                    }),
-                    post: Some(ProtoPost {
-                        id: post_dto.uuid.to_string(),
-                        title: post_dto.title,
-                        data: post_dto.content,
-                        created_ts: Some(Timestamp {
-                            seconds: post_dto.created_at.timestamp(),
-                            nanos: post_dto.created_at.timestamp_subsec_nanos() as i32,
-                        }),
-                        last_updated_ts: Some(Timestamp {
-                            seconds: post_dto.updated_at.timestamp(),
-                            nanos: post_dto.updated_at.timestamp_subsec_nanos() as i32,
-                        }),
+                        details: Some("Logged out from all devices".to_string()),
+                        trace_id: None,
                     }),
                 }))
             }
             Err(e) => {
-                warn!("Failed to retrieve post: {}", e);
-                Ok(Response::new(PostResponse {
-                    response: Some(Self::map_domain_error(e)),
-                    post: None,
+                warn!("Logout from all devices failed: {}", e);
+                Ok(Response::new(LogoutAllResponse {
+                    status: Some(Self::map_domain_error(e)),
                 }))
             }
         }
     }
 
     #[instrument(skip(self, request))]
-    async fn update_post(
+    async fn list_sessions(
         &self,
-        request: Request<UpdatePostRequest>,
-    ) -> Result<Response<PostResponse>, Status> {
-        // Проверяем JWT токен
-        let claims = self.auth_interceptor.verify_token(&request)?;
-        debug!("Authenticated user: {}", claims.user_name);
-
-        let req = request.into_inner();
-
-        let post = req
-            .post
-            .ok_or_else(|| Status::invalid_argument("Post data is required"))?;
-
-        debug!("Update post request received for id: {}", post.id);
+        request: Request<ListSessionsRequest>,
+    ) -> Result<Response<ListSessionsResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        debug!("List sessions request received");
 
-        let uuid = Uuid::parse_str(&post.id)
-            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
-
-        let dto = UpdatePostDto {
-            uuid,
-            title: post.title,
-            content: post.data,
-        };
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
 
-        match self.post_app.update_post(dto).await {
-            Ok(post_dto) => {
-                info!("Post updated successfully");
-                Ok(Response::new(PostResponse {
-                    response: Some(ProtoResponse {
+        match self.auth_app.list_sessions(user_id, claims.sid).await {
+            Ok(sessions) => {
+                info!("Listed {} sessions", sessions.len());
+                Ok(Response::new(ListSessionsResponse {
+                    status: Some(ProtoResponse {
                         code: ProtoStatus::Ok as i32,
-                        details: Some("Post updated successfully".to_string()),
-                    }),
-                    post: Some(ProtoPost {
-                        id: post_dto.uuid.to_string(),
-                        title: post_dto.title,
-                        data: post_dto.content,
-                        created_ts: Some(Timestamp {
-                            seconds: post_dto.created_at.timestamp(),
-                            nanos: post_dto.created_at.timestamp_subsec_nanos() as i32,
-                        }),
-                        last_updated_ts: Some(Timestamp {
-                            seconds: post_dto.updated_at.timestamp(),
-                            nanos: post_dto.updated_at.timestamp_subsec_nanos() as i32,
-                        }),
+                        details: Some("Sessions retrieved successfully".to_string()),
+                        trace_id: None,
                     }),
+                    sessions: sessions.into_iter().map(session_to_proto).collect(),
                 }))
             }
             Err(e) => {
-                error!("Failed to update post: {}", e);
-                Ok(Response::new(PostResponse {
-                    response: Some(Self::map_domain_error(e)),
-                    post: None,
+                warn!("Failed to list sessions: {}", e);
+                Ok(Response::new(ListSessionsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    sessions: Vec::new(),
                 }))
             }
         }
     }
 
     #[instrument(skip(self, request))]
-    async fn delete_post(
+    async fn revoke_session(
         &self,
-        request: Request<DeletePostRequest>,
-    ) -> Result<Response<DeletePostResponse>, Status> {
-        // Проверяем JWT токен
-        let claims = self.auth_interceptor.verify_token(&request)?;
-        debug!("Authenticated user: {}", claims.user_name);
-
+        request: Request<RevokeSessionRequest>,
+    ) -> Result<Response<RevokeSessionResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
         let req = request.into_inner();
-        debug!("Delete post request received for id: {}", req.post_id);
+        debug!("Revoke session request received for: {}", req.session_id);
 
-        let uuid = Uuid::parse_str(&req.post_id)
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+        let session_id = Uuid::parse_str(&req.session_id)
             .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
 
-        match self.post_app.delete_post(uuid).await {
+        match self.auth_app.revoke_session(user_id, session_id).await {
+            Ok(()) => {
+                info!("Session revoked: {}", session_id);
+                Ok(Response::new(RevokeSessionResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Session revoked".to_string()),
+                        trace_id: None,
+                    }),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to revoke session: {}", e);
+                Ok(Response::new(RevokeSessionResponse {
+                    status: Some(Self::map_domain_error(e)),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn forgot_password(
+        &self,
+        request: Request<ForgotPasswordRequest>,
+    ) -> Result<Response<ForgotPasswordResponse>, Status> {
+        self.auth_rate_limit.check(&request)?;
+        let req = request.into_inner();
+        debug!("Forgot password request received");
+
+        match self.auth_app.request_password_reset(&req.email).await {
+            Ok(()) => Ok(Response::new(ForgotPasswordResponse {
+                status: Some(ProtoResponse {
+                    code: ProtoStatus::Ok as i32,
+                    details: Some(
+                        "If the email is registered, a reset token has been sent".to_string(),
+                    ),
+                    trace_id: None,
+                }),
+            })),
+            Err(e) => {
+                error!("Password reset request failed: {}", e);
+                Ok(Response::new(ForgotPasswordResponse {
+                    status: Some(Self::map_domain_error(e)),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn reset_password(
+        &self,
+        request: Request<ResetPasswordRequest>,
+    ) -> Result<Response<ResetPasswordResponse>, Status> {
+        self.auth_rate_limit.check(&request)?;
+        let req = request.into_inner();
+        debug!("Reset password request received");
+
+        let dto = ResetPasswordDto {
+            token: req.token,
+            new_password: req.new_password,
+        };
+
+        match self.auth_app.reset_password(dto).await {
+            Ok(()) => {
+                info!("Password reset successfully");
+                Ok(Response::new(ResetPasswordResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Password reset successfully".to_string()),
+                        trace_id: None,
+                    }),
+                }))
+            }
+            Err(e) => {
+                warn!("Password reset failed: {}", e);
+                Ok(Response::new(ResetPasswordResponse {
+                    status: Some(Self::map_domain_error(e)),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn get_user_profile(
+        &self,
+        request: Request<GetUserProfileRequest>,
+    ) -> Result<Response<UserProfileResponse>, Status> {
+        let req = request.into_inner();
+        debug!("Get user profile request received for id: {}", req.user_id);
+
+        let user_id = Uuid::parse_str(&req.user_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+
+        match self.profile_app.get_profile(user_id).await {
+            Ok(profile_dto) => {
+                info!("User profile retrieved successfully");
+                Ok(Response::new(UserProfileResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("User profile retrieved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    profile: Some(profile_to_proto(profile_dto)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to retrieve user profile: {}", e);
+                Ok(Response::new(UserProfileResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    profile: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn update_my_profile(
+        &self,
+        request: Request<UpdateMyProfileRequest>,
+    ) -> Result<Response<UserProfileResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!(
+            "Update profile request received for user: {}",
+            claims.user_name
+        );
+
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        let dto = UpdateProfileDto {
+            user_id,
+            display_name: req.display_name,
+            bio: req.bio,
+            avatar_url: req.avatar_url,
+        };
+
+        match self.profile_app.update_profile(dto).await {
+            Ok(profile_dto) => {
+                info!("User profile updated successfully");
+                Ok(Response::new(UserProfileResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("User profile updated successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    profile: Some(profile_to_proto(profile_dto)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to update user profile: {}", e);
+                Ok(Response::new(UserProfileResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    profile: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn create_post(
+        &self,
+        request: Request<CreatePostRequest>,
+    ) -> Result<Response<PostResponse>, Status> {
+        // Проверяем JWT токен и извлекаем claims
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        debug!("Authenticated user: {}", claims.user_name);
+
+        let req = request.into_inner();
+        debug!("Create post request received");
+
+        let status = status_from_proto(req.status());
+
+        // Используем user_id из токена
+        let author_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+        let attachment_ids = parse_attachment_ids(req.attachment_ids)?;
+        let category_id = parse_category_id(req.category_id.as_deref().unwrap_or_default())?;
+        let blog_id = match self.blog_id(req.blog_slug.as_deref()).await {
+            Ok(blog_id) => blog_id,
+            Err(e) => {
+                warn!("Failed to find blog: {}", e);
+                return Ok(Response::new(PostResponse {
+                    response: Some(Self::map_domain_error(e)),
+                    post: None,
+                }));
+            }
+        };
+
+        let dto = CreatePostDto {
+            title: req.title,
+            content: req.data,
+            author_id,
+            author_username: claims.user_name,
+            tags: req.tags,
+            attachment_ids,
+            category_id,
+            blog_id,
+            summary: req.summary,
+            status,
+        };
+
+        match self.post_app.create_post(dto).await {
+            Ok(post_dto) => {
+                info!("Post created successfully with id: {}", post_dto.uuid);
+                Ok(Response::new(PostResponse {
+                    response: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Post created successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    post: Some(post_to_proto(post_dto)),
+                }))
+            }
+            Err(e) => {
+                error!("Failed to create post: {}", e);
+                Ok(Response::new(PostResponse {
+                    response: Some(Self::map_domain_error(e)),
+                    post: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn get_post(
+        &self,
+        request: Request<GetPostRequest>,
+    ) -> Result<Response<PostResponse>, Status> {
+        // GetPost - публичный метод, токен нужен только чтобы автор видел свои черновики
+        let viewer = self.auth_interceptor.optional_user_id(&request).await;
+        let req = request.into_inner();
+        debug!("Get post request received for id: {}", req.id);
+
+        // Конвертируем строку в UUID
+        let uuid = Uuid::parse_str(&req.id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let mask = PostMask::parse(req.field_mask)?;
+
+        match self.post_app.get_post_by_id(uuid, viewer).await {
+            Ok(post_dto) => {
+                info!("Post retrieved successfully");
+                Ok(Response::new(PostResponse {
+                    response: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Post retrieved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    post: Some(mask.apply(post_dto)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to retrieve post: {}", e);
+                Ok(Response::new(PostResponse {
+                    response: Some(Self::map_domain_error(e)),
+                    post: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn update_post(
+        &self,
+        request: Request<UpdatePostRequest>,
+    ) -> Result<Response<PostResponse>, Status> {
+        // Проверяем JWT токен
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        debug!("Authenticated user: {}", claims.user_name);
+
+        let req = request.into_inner();
+
+        let post = req
+            .post
+            .ok_or_else(|| Status::invalid_argument("Post data is required"))?;
+
+        debug!("Update post request received for id: {}", post.id);
+
+        let uuid = Uuid::parse_str(&post.id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+        let attachment_ids = req
+            .attachment_ids
+            .map(|attachment_ids| parse_attachment_ids(attachment_ids.ids))
+            .transpose()?;
+        let category_id = req
+            .category_id
+            .as_deref()
+            .map(parse_category_id)
+            .transpose()?;
+
+        let dto = UpdatePostDto {
+            uuid,
+            title: post.title,
+            content: post.data,
+            tags: post.tags,
+            attachment_ids,
+            category_id,
+            summary: req.summary.map(Some),
+        };
+
+        // Изменять пост может автор или администратор
+        match self.post_app.update_post(dto, user_id, claims.role).await {
+            Ok(post_dto) => {
+                info!("Post updated successfully");
+                Ok(Response::new(PostResponse {
+                    response: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Post updated successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    post: Some(post_to_proto(post_dto)),
+                }))
+            }
+            Err(e) => {
+                error!("Failed to update post: {}", e);
+                Ok(Response::new(PostResponse {
+                    response: Some(Self::map_domain_error(e)),
+                    post: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn delete_post(
+        &self,
+        request: Request<DeletePostRequest>,
+    ) -> Result<Response<DeletePostResponse>, Status> {
+        // Проверяем JWT токен
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        debug!("Authenticated user: {}", claims.user_name);
+
+        let req = request.into_inner();
+        debug!("Delete post request received for id: {}", req.post_id);
+
+        let uuid = Uuid::parse_str(&req.post_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        // Удалять пост может автор или администратор
+        match self.post_app.delete_post(uuid, user_id, claims.role).await {
             Ok(_) => {
                 info!("Post deleted successfully");
                 Ok(Response::new(DeletePostResponse {
                     status: Some(ProtoResponse {
                         code: ProtoStatus::Ok as i32,
-                        details: Some("Post deleted successfully".to_string()),
+                        details: Some("Post deleted successfully".to_string()),
+                        trace_id: None,
+                    }),
+                }))
+            }
+            Err(e) => {
+                error!("Failed to delete post: {}", e);
+                Ok(Response::new(DeletePostResponse {
+                    status: Some(Self::map_domain_error(e)),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn create_posts(
+        &self,
+        request: Request<Streaming<CreatePostRequest>>,
+    ) -> Result<Response<CreatePostsResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        debug!("Authenticated user: {}", claims.user_name);
+
+        let author_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        // Посты создаются одной транзакцией, поэтому поток сначала читается целиком
+        let mut stream = request.into_inner();
+        let mut dtos = Vec::new();
+        while let Some(req) = stream.message().await? {
+            // Не накапливаем в памяти больше постов, чем допускает пакет
+            if let Err(e) = check_batch_size(dtos.len() + 1) {
+                warn!("Create posts stream exceeds the batch limit");
+                return Ok(Response::new(CreatePostsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    posts: Vec::new(),
+                }));
+            }
+            let status = status_from_proto(req.status());
+            let blog_id = match self.blog_id(req.blog_slug.as_deref()).await {
+                Ok(blog_id) => blog_id,
+                Err(e) => {
+                    warn!("Failed to find blog: {}", e);
+                    return Ok(Response::new(CreatePostsResponse {
+                        status: Some(Self::map_domain_error(e)),
+                        posts: Vec::new(),
+                    }));
+                }
+            };
+            dtos.push(CreatePostDto {
+                title: req.title,
+                content: req.data,
+                author_id,
+                author_username: claims.user_name.clone(),
+                tags: req.tags,
+                attachment_ids: parse_attachment_ids(req.attachment_ids)?,
+                category_id: parse_category_id(req.category_id.as_deref().unwrap_or_default())?,
+                blog_id,
+                summary: req.summary,
+                status,
+            });
+        }
+        debug!("Create posts request received with {} posts", dtos.len());
+
+        match self.post_app.create_posts(dtos).await {
+            Ok(posts) => {
+                info!("Created {} posts", posts.len());
+                Ok(Response::new(CreatePostsResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Posts created successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    posts: posts.into_iter().map(post_to_proto).collect(),
+                }))
+            }
+            Err(e) => {
+                error!("Failed to create posts: {}", e);
+                Ok(Response::new(CreatePostsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    posts: Vec::new(),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn delete_posts(
+        &self,
+        request: Request<DeletePostsRequest>,
+    ) -> Result<Response<DeletePostsResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        debug!("Authenticated user: {}", claims.user_name);
+
+        let req = request.into_inner();
+        debug!(
+            "Delete posts request received for {} posts",
+            req.post_ids.len()
+        );
+
+        let post_ids = req
+            .post_ids
+            .iter()
+            .map(|id| Uuid::parse_str(id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        // Удалять посты может автор или администратор
+        match self
+            .post_app
+            .delete_posts(post_ids, user_id, claims.role)
+            .await
+        {
+            Ok(deleted) => {
+                info!("Deleted {} posts", deleted.len());
+                Ok(Response::new(DeletePostsResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Posts deleted successfully".to_string()),
+                        trace_id: None,
+                    }),
+                }))
+            }
+            Err(e) => {
+                error!("Failed to delete posts: {}", e);
+                Ok(Response::new(DeletePostsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn restore_post(
+        &self,
+        request: Request<RestorePostRequest>,
+    ) -> Result<Response<PostResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Restore post request received for id: {}", req.post_id);
+
+        let uuid = Uuid::parse_str(&req.post_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        match self.post_app.restore_post(uuid, user_id, claims.role).await {
+            Ok(post_dto) => {
+                info!("Post restored successfully");
+                Ok(Response::new(PostResponse {
+                    response: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Post restored successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    post: Some(post_to_proto(post_dto)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to restore post: {}", e);
+                Ok(Response::new(PostResponse {
+                    response: Some(Self::map_domain_error(e)),
+                    post: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn purge_post(
+        &self,
+        request: Request<PurgePostRequest>,
+    ) -> Result<Response<PurgePostResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Purge post request received for id: {}", req.post_id);
+
+        let uuid = Uuid::parse_str(&req.post_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        match self.post_app.purge_post(uuid, user_id, claims.role).await {
+            Ok(()) => {
+                info!("Post purged successfully");
+                Ok(Response::new(PurgePostResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Post purged successfully".to_string()),
+                        trace_id: None,
+                    }),
+                }))
+            }
+            Err(e) => {
+                error!("Failed to purge post: {}", e);
+                Ok(Response::new(PurgePostResponse {
+                    status: Some(Self::map_domain_error(e)),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn list_posts(
+        &self,
+        request: Request<ListPostsRequest>,
+    ) -> Result<Response<ListPostsResponse>, Status> {
+        let viewer = self.auth_interceptor.optional_user_id(&request).await;
+        let req = request.into_inner();
+        debug!("List posts request received");
+
+        if let Err(response) = check_page_size(req.page_size) {
+            return Ok(Response::new(response));
+        }
+
+        let sort = sort_from_proto(req.sort_by(), req.order());
+        let category = parse_category_id(req.category_id.as_deref().unwrap_or_default())?;
+        let mask = PostMask::parse(req.field_mask)?;
+        let blog = match self.blog_id(req.blog_slug.as_deref()).await {
+            Ok(blog) => blog,
+            Err(e) => {
+                warn!("Failed to find blog: {}", e);
+                return Ok(Response::new(ListPostsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    posts: vec![],
+                    total: 0,
+                    has_next: false,
+                    next_cursor: None,
+                }));
+            }
+        };
+
+        let result = match req.cursor.as_deref() {
+            Some(cursor) => {
+                self.post_app
+                    .get_posts_after(
+                        Some(cursor),
+                        req.page_size,
+                        req.tag.as_deref(),
+                        category,
+                        blog,
+                        viewer,
+                        sort,
+                        mask.storage_fields(),
+                    )
+                    .await
+            }
+            None => {
+                self.post_app
+                    .get_posts(
+                        req.page_count,
+                        req.page_size,
+                        req.tag.as_deref(),
+                        category,
+                        blog,
+                        viewer,
+                        sort,
+                        mask.storage_fields(),
+                    )
+                    .await
+            }
+        };
+
+        match result {
+            Ok(page) => {
+                info!("Retrieved {} posts", page.posts.len());
+                let total = page.total;
+                let has_next = page.has_next;
+                let next_cursor = page.next_cursor;
+                let proto_posts = page
+                    .posts
+                    .into_iter()
+                    .map(|post| mask.apply(post))
+                    .collect();
+
+                Ok(Response::new(ListPostsResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Posts retrieved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    posts: proto_posts,
+                    total,
+                    has_next,
+                    next_cursor,
+                }))
+            }
+            Err(e) => {
+                error!("Failed to retrieve posts: {}", e);
+                Ok(Response::new(ListPostsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    posts: vec![],
+                    total: 0,
+                    has_next: false,
+                    next_cursor: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn list_posts_by_author(
+        &self,
+        request: Request<ListPostsByAuthorRequest>,
+    ) -> Result<Response<ListPostsResponse>, Status> {
+        let viewer = self.auth_interceptor.optional_user_id(&request).await;
+        let req = request.into_inner();
+        debug!(
+            "List posts by author request received for id: {}",
+            req.author_id
+        );
+
+        let author_id = Uuid::parse_str(&req.author_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let mask = PostMask::parse(req.field_mask)?;
+        if let Err(response) = check_page_size(req.page_size) {
+            return Ok(Response::new(response));
+        }
+
+        match self
+            .post_app
+            .get_posts_by_author(author_id, req.page_count, req.page_size, viewer)
+            .await
+        {
+            Ok(page) => {
+                info!("Retrieved {} author posts", page.posts.len());
+                let total = page.total;
+                let has_next = page.has_next;
+                let proto_posts = page
+                    .posts
+                    .into_iter()
+                    .map(|post| mask.apply(post))
+                    .collect();
+
+                Ok(Response::new(ListPostsResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Posts retrieved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    posts: proto_posts,
+                    total,
+                    has_next,
+                    next_cursor: page.next_cursor,
+                }))
+            }
+            Err(e) => {
+                error!("Failed to retrieve author posts: {}", e);
+                Ok(Response::new(ListPostsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    posts: vec![],
+                    total: 0,
+                    has_next: false,
+                    next_cursor: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn list_trending_posts(
+        &self,
+        request: Request<ListTrendingPostsRequest>,
+    ) -> Result<Response<ListTrendingPostsResponse>, Status> {
+        let viewer = self.auth_interceptor.optional_user_id(&request).await;
+        let req = request.into_inner();
+        debug!("List trending posts request received (limit {})", req.limit);
+
+        let mask = PostMask::parse(req.field_mask)?;
+        if !(1..=MAX_PAGE_SIZE).contains(&req.limit) {
+            return Ok(Response::new(ListTrendingPostsResponse {
+                status: Some(ProtoResponse {
+                    code: ProtoStatus::InvalidRequest as i32,
+                    details: Some(format!("limit must be between 1 and {MAX_PAGE_SIZE}")),
+                    trace_id: current_trace_id(),
+                }),
+                posts: vec![],
+            }));
+        }
+
+        match self.post_app.get_trending_posts(req.limit, viewer).await {
+            Ok(posts) => {
+                info!("Retrieved {} trending posts", posts.len());
+                Ok(Response::new(ListTrendingPostsResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Posts retrieved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    posts: posts.into_iter().map(|post| mask.apply(post)).collect(),
+                }))
+            }
+            Err(e) => {
+                error!("Failed to retrieve trending posts: {}", e);
+                Ok(Response::new(ListTrendingPostsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    posts: vec![],
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn stream_posts(
+        &self,
+        request: Request<StreamPostsRequest>,
+    ) -> Result<Response<Self::StreamPostsStream>, Status> {
+        let viewer = self.auth_interceptor.optional_user_id(&request).await;
+        let req = request.into_inner();
+        let chunk_size = match req.chunk_size {
+            0 => STREAM_CHUNK_SIZE_DEFAULT,
+            size => size.min(STREAM_CHUNK_SIZE_MAX),
+        };
+        debug!("Stream posts request received (chunk size {})", chunk_size);
+
+        let post_app = self.post_app.clone();
+        let (tx, rx) = mpsc::channel(chunk_size as usize);
+
+        // Читаем посты постранично и отправляем по одному, пока клиент слушает поток
+        tokio::spawn(async move {
+            let mut page = 0;
+            loop {
+                let posts = match post_app
+                    .get_posts(
+                        page,
+                        chunk_size,
+                        req.tag.as_deref(),
+                        None,
+                        None,
+                        viewer,
+                        PostSort::default(),
+                        PostFields::ALL,
+                    )
+                    .await
+                {
+                    Ok(page) => page.posts,
+                    Err(e) => {
+                        error!("Failed to stream posts: {}", e);
+                        let _ = tx.send(Err(Status::internal(e.to_string()))).await;
+                        return;
+                    }
+                };
+
+                let is_last = posts.len() < chunk_size as usize;
+                for post in posts {
+                    if tx.send(Ok(post_to_proto(post))).await.is_err() {
+                        debug!("Client closed the posts stream");
+                        return;
+                    }
+                }
+                if is_last {
+                    info!("Posts stream finished after {} pages", page + 1);
+                    return;
+                }
+                page += 1;
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    #[instrument(skip(self, request))]
+    async fn publish_post(
+        &self,
+        request: Request<PublishPostRequest>,
+    ) -> Result<Response<PostResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Publish post request received for id: {}", req.post_id);
+
+        self.change_post_status(&claims, &req.post_id, PostStatus::Published)
+            .await
+    }
+
+    #[instrument(skip(self, request))]
+    async fn unpublish_post(
+        &self,
+        request: Request<UnpublishPostRequest>,
+    ) -> Result<Response<PostResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Unpublish post request received for id: {}", req.post_id);
+
+        self.change_post_status(&claims, &req.post_id, PostStatus::Draft)
+            .await
+    }
+
+    #[instrument(skip(self, request))]
+    async fn like_post(
+        &self,
+        request: Request<LikePostRequest>,
+    ) -> Result<Response<PostResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Like post request received for id: {}", req.post_id);
+
+        self.toggle_like(&claims.sub, &req.post_id, true).await
+    }
+
+    #[instrument(skip(self, request))]
+    async fn unlike_post(
+        &self,
+        request: Request<UnlikePostRequest>,
+    ) -> Result<Response<PostResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Unlike post request received for id: {}", req.post_id);
+
+        self.toggle_like(&claims.sub, &req.post_id, false).await
+    }
+
+    #[instrument(skip(self, request))]
+    async fn upload_file(
+        &self,
+        request: Request<UploadFileRequest>,
+    ) -> Result<Response<UploadFileResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Upload file request received: {}", req.file_name);
+
+        let owner_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        match self
+            .upload_app
+            .upload(owner_id, &req.file_name, &req.content_type, req.data)
+            .await
+        {
+            Ok(attachment_dto) => {
+                info!("File uploaded successfully with id: {}", attachment_dto.id);
+                Ok(Response::new(UploadFileResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("File uploaded successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    attachment: Some(attachment_to_proto(attachment_dto)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to upload file: {}", e);
+                Ok(Response::new(UploadFileResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    attachment: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn list_users(
+        &self,
+        request: Request<ListUsersRequest>,
+    ) -> Result<Response<ListUsersResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!(
+            "List users request received: page {}, size {}",
+            req.page_count, req.page_size
+        );
+
+        if !(1..=MAX_PAGE_SIZE).contains(&req.page_size) {
+            return Ok(Response::new(ListUsersResponse {
+                status: Some(ProtoResponse {
+                    code: ProtoStatus::InvalidRequest as i32,
+                    details: Some(format!("page_size must be between 1 and {MAX_PAGE_SIZE}")),
+                    trace_id: current_trace_id(),
+                }),
+                users: vec![],
+                total: 0,
+            }));
+        }
+
+        match self
+            .admin_app
+            .list_users(req.page_count, req.page_size, claims.role)
+            .await
+        {
+            Ok(page) => {
+                info!("Retrieved {} users", page.users.len());
+                Ok(Response::new(ListUsersResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Users retrieved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    users: page.users.into_iter().map(admin_user_to_proto).collect(),
+                    total: page.total,
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to list users: {}", e);
+                Ok(Response::new(ListUsersResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    users: vec![],
+                    total: 0,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn set_user_disabled(
+        &self,
+        request: Request<SetUserDisabledRequest>,
+    ) -> Result<Response<SetUserDisabledResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!(
+            "Set user disabled request received for id: {} ({})",
+            req.user_id, req.disabled
+        );
+
+        let user_id = Uuid::parse_str(&req.user_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let admin_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        match self
+            .admin_app
+            .set_user_disabled(user_id, req.disabled, admin_id, claims.role)
+            .await
+        {
+            Ok(()) => {
+                info!("User disabled state changed: {}", user_id);
+                Ok(Response::new(SetUserDisabledResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("User updated successfully".to_string()),
+                        trace_id: None,
+                    }),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to change user disabled state: {}", e);
+                Ok(Response::new(SetUserDisabledResponse {
+                    status: Some(Self::map_domain_error(e)),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn force_delete_post(
+        &self,
+        request: Request<ForceDeletePostRequest>,
+    ) -> Result<Response<ForceDeletePostResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Force delete post request received for id: {}", req.post_id);
+
+        let uuid = Uuid::parse_str(&req.post_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        match self
+            .post_app
+            .force_delete_post(uuid, user_id, claims.role)
+            .await
+        {
+            Ok(()) => {
+                info!("Post force deleted successfully");
+                Ok(Response::new(ForceDeletePostResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Post deleted successfully".to_string()),
+                        trace_id: None,
+                    }),
+                }))
+            }
+            Err(e) => {
+                error!("Failed to force delete post: {}", e);
+                Ok(Response::new(ForceDeletePostResponse {
+                    status: Some(Self::map_domain_error(e)),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn get_system_stats(
+        &self,
+        request: Request<GetSystemStatsRequest>,
+    ) -> Result<Response<SystemStatsResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        debug!("Get system stats request received");
+
+        match self.admin_app.get_system_stats(claims.role).await {
+            Ok(stats) => {
+                info!("System stats collected");
+                Ok(Response::new(SystemStatsResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Stats retrieved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    ..rest::SystemStatsResponse::from(stats).into()
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to collect system stats: {}", e);
+                Ok(Response::new(SystemStatsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    ..Default::default()
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn create_category(
+        &self,
+        request: Request<CreateCategoryRequest>,
+    ) -> Result<Response<CategoryResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Create category request received: {}", req.name);
+
+        let parent_id = parse_category_id(req.parent_id.as_deref().unwrap_or_default())?;
+
+        match self
+            .category_app
+            .create_category(
+                CreateCategoryDto {
+                    name: req.name,
+                    parent_id,
+                },
+                claims.role,
+            )
+            .await
+        {
+            Ok(category) => {
+                info!("Category created successfully: {}", category.id);
+                Ok(Response::new(CategoryResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Category created successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    category: Some(category_to_proto(category)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to create category: {}", e);
+                Ok(Response::new(CategoryResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    category: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, _request))]
+    async fn list_blogs(
+        &self,
+        _request: Request<ListBlogsRequest>,
+    ) -> Result<Response<ListBlogsResponse>, Status> {
+        debug!("List blogs request received");
+
+        match self.blog_app.list_blogs().await {
+            Ok(blogs) => {
+                info!("Retrieved {} blogs", blogs.len());
+                Ok(Response::new(ListBlogsResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Blogs retrieved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    blogs: blogs.into_iter().map(blog_to_proto).collect(),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to list blogs: {}", e);
+                Ok(Response::new(ListBlogsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    blogs: Vec::new(),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn get_blog(
+        &self,
+        request: Request<GetBlogRequest>,
+    ) -> Result<Response<BlogResponse>, Status> {
+        let req = request.into_inner();
+        debug!("Get blog request received: {}", req.slug);
+
+        match self.blog_app.get_blog(&req.slug).await {
+            Ok(blog) => {
+                info!("Blog retrieved successfully");
+                Ok(Response::new(BlogResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Blog retrieved successfully".to_string()),
+                        trace_id: None,
                     }),
+                    blog: Some(blog_to_proto(blog)),
                 }))
             }
             Err(e) => {
-                error!("Failed to delete post: {}", e);
-                Ok(Response::new(DeletePostResponse {
+                warn!("Failed to retrieve blog: {}", e);
+                Ok(Response::new(BlogResponse {
                     status: Some(Self::map_domain_error(e)),
+                    blog: None,
                 }))
             }
         }
     }
 
     #[instrument(skip(self, request))]
-    async fn list_posts(
+    async fn create_blog(
         &self,
-        request: Request<ListPostsRequest>,
+        request: Request<CreateBlogRequest>,
+    ) -> Result<Response<BlogResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Create blog request received: {}", req.slug);
+
+        let owner_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        match self
+            .blog_app
+            .create_blog(CreateBlogDto {
+                slug: req.slug,
+                name: req.name,
+                owner_id,
+            })
+            .await
+        {
+            Ok(blog) => {
+                info!("Blog created successfully: {}", blog.slug);
+                Ok(Response::new(BlogResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Blog created successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    blog: Some(blog_to_proto(blog)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to create blog: {}", e);
+                Ok(Response::new(BlogResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    blog: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn update_blog(
+        &self,
+        request: Request<UpdateBlogRequest>,
+    ) -> Result<Response<BlogResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Update blog request received: {}", req.slug);
+
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        // Изменять блог может владелец или администратор
+        match self
+            .blog_app
+            .update_blog(&req.slug, &req.name, user_id, claims.role)
+            .await
+        {
+            Ok(blog) => {
+                info!("Blog updated successfully");
+                Ok(Response::new(BlogResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Blog updated successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    blog: Some(blog_to_proto(blog)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to update blog: {}", e);
+                Ok(Response::new(BlogResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    blog: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn delete_blog(
+        &self,
+        request: Request<DeleteBlogRequest>,
+    ) -> Result<Response<DeleteBlogResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Delete blog request received: {}", req.slug);
+
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        // Удалять блог может владелец или администратор; посты остаются в общей ленте
+        match self
+            .blog_app
+            .delete_blog(&req.slug, user_id, claims.role)
+            .await
+        {
+            Ok(()) => {
+                info!("Blog deleted successfully");
+                Ok(Response::new(DeleteBlogResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Blog deleted successfully".to_string()),
+                        trace_id: None,
+                    }),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to delete blog: {}", e);
+                Ok(Response::new(DeleteBlogResponse {
+                    status: Some(Self::map_domain_error(e)),
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn report_post(
+        &self,
+        request: Request<ReportPostRequest>,
+    ) -> Result<Response<ReportResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Report post request received for id: {}", req.post_id);
+
+        let post_id = Uuid::parse_str(&req.post_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let reporter_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+        let reason = rest::ReportReason::from(req.reason()).into();
+
+        match self
+            .report_app
+            .report_post(CreateReportDto {
+                post_id,
+                reporter_id,
+                reason,
+                details: req.details,
+            })
+            .await
+        {
+            Ok(report) => {
+                info!("Post reported successfully: {}", post_id);
+                Ok(Response::new(ReportResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Report created successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    report: Some(report_to_proto(report)),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to report post: {}", e);
+                Ok(Response::new(ReportResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    report: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn list_reports(
+        &self,
+        request: Request<ListReportsRequest>,
+    ) -> Result<Response<ListReportsResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!(
+            "List reports request received: page {}, size {}",
+            req.page_count, req.page_size
+        );
+
+        if !(1..=MAX_PAGE_SIZE).contains(&req.page_size) {
+            return Ok(Response::new(ListReportsResponse {
+                status: Some(ProtoResponse {
+                    code: ProtoStatus::InvalidRequest as i32,
+                    details: Some(format!("page_size must be between 1 and {MAX_PAGE_SIZE}")),
+                    trace_id: current_trace_id(),
+                }),
+                reports: vec![],
+                total: 0,
+            }));
+        }
+        let status = req
+            .status
+            .map(|status| {
+                ProtoReportStatus::try_from(status)
+                    .map_err(|_| Status::invalid_argument("Invalid report status"))
+            })
+            .transpose()?
+            .map(|status| rest::ReportStatus::from(status).into());
+
+        match self
+            .report_app
+            .list_reports(status, req.page_count, req.page_size, claims.role)
+            .await
+        {
+            Ok(page) => {
+                info!("Retrieved {} reports", page.reports.len());
+                Ok(Response::new(ListReportsResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Reports retrieved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    reports: page.reports.into_iter().map(report_to_proto).collect(),
+                    total: page.total,
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to list reports: {}", e);
+                Ok(Response::new(ListReportsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    reports: vec![],
+                    total: 0,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn resolve_report(
+        &self,
+        request: Request<ResolveReportRequest>,
+    ) -> Result<Response<ReportResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Resolve report request received for id: {}", req.report_id);
+
+        let report_id = Uuid::parse_str(&req.report_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let moderator_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+        let resolution = ReportResolution::from(rest::ReportAction::from(req.action()));
+
+        // Пост скрывается до закрытия жалоб, чтобы при сбое решение можно было повторить
+        let hidden = match resolution {
+            ReportResolution::HidePost => match self
+                .report_app
+                .get_open_report(report_id, claims.role)
+                .await
+            {
+                Ok(report) => {
+                    self.post_app
+                        .hide_post(report.post_id, moderator_id, claims.role)
+                        .await
+                }
+                Err(e) => Err(e),
+            },
+            ReportResolution::Dismiss => Ok(()),
+        };
+        let result = match hidden {
+            Ok(()) => {
+                self.report_app
+                    .resolve_report(report_id, resolution, moderator_id, claims.role)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(report) => {
+                info!("Report resolved successfully: {}", report_id);
+                Ok(Response::new(ReportResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Report resolved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    report: Some(report_to_proto(report)),
+                }))
+            }
+            Err(e) => {
+                error!("Failed to resolve report: {}", e);
+                Ok(Response::new(ReportResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    report: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn follow_user(
+        &self,
+        request: Request<FollowUserRequest>,
+    ) -> Result<Response<FollowResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Follow user request received for id: {}", req.user_id);
+
+        let followee_id = Uuid::parse_str(&req.user_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let follower_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        match self.follow_app.follow_user(follower_id, followee_id).await {
+            Ok(()) => {
+                info!("User followed successfully: {}", followee_id);
+                Ok(Response::new(FollowResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("User followed successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    following: true,
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to follow user: {}", e);
+                Ok(Response::new(FollowResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    following: false,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn unfollow_user(
+        &self,
+        request: Request<UnfollowUserRequest>,
+    ) -> Result<Response<FollowResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Unfollow user request received for id: {}", req.user_id);
+
+        let followee_id = Uuid::parse_str(&req.user_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let follower_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        match self
+            .follow_app
+            .unfollow_user(follower_id, followee_id)
+            .await
+        {
+            Ok(()) => {
+                info!("User unfollowed successfully: {}", followee_id);
+                Ok(Response::new(FollowResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("User unfollowed successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    following: false,
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to unfollow user: {}", e);
+                Ok(Response::new(FollowResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    following: false,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn get_follow_status(
+        &self,
+        request: Request<GetFollowStatusRequest>,
+    ) -> Result<Response<FollowResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Get follow status request received for id: {}", req.user_id);
+
+        let followee_id = Uuid::parse_str(&req.user_id)
+            .map_err(|_| Status::invalid_argument("Invalid UUID format"))?;
+        let follower_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        match self.follow_app.is_following(follower_id, followee_id).await {
+            Ok(following) => Ok(Response::new(FollowResponse {
+                status: Some(ProtoResponse {
+                    code: ProtoStatus::Ok as i32,
+                    details: Some("Follow status retrieved successfully".to_string()),
+                    trace_id: None,
+                }),
+                following,
+            })),
+            Err(e) => {
+                warn!("Failed to check follow: {}", e);
+                Ok(Response::new(FollowResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    following: false,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn get_feed(
+        &self,
+        request: Request<GetFeedRequest>,
     ) -> Result<Response<ListPostsResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
         let req = request.into_inner();
-        debug!("List posts request received");
+        debug!(
+            "Get feed request received: page {}, size {}",
+            req.page_count, req.page_size
+        );
 
-        match self.post_app.get_posts(req.page_count, req.page_size).await {
-            Ok(posts) => {
-                info!("Retrieved {} posts", posts.len());
-                let proto_posts = posts
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+        let mask = PostMask::parse(req.field_mask)?;
+        if let Err(response) = check_page_size(req.page_size) {
+            return Ok(Response::new(response));
+        }
+
+        match self
+            .post_app
+            .get_feed(user_id, req.page_count, req.page_size)
+            .await
+        {
+            Ok(page) => {
+                info!("Retrieved {} feed posts", page.posts.len());
+                let total = page.total;
+                let has_next = page.has_next;
+                let proto_posts = page
+                    .posts
                     .into_iter()
-                    .map(|post_dto| ProtoPost {
-                        id: post_dto.uuid.to_string(),
-                        title: post_dto.title,
-                        data: post_dto.content,
-                        created_ts: Some(Timestamp {
-                            seconds: post_dto.created_at.timestamp(),
-                            nanos: post_dto.created_at.timestamp_subsec_nanos() as i32,
-                        }),
-                        last_updated_ts: Some(Timestamp {
-                            seconds: post_dto.updated_at.timestamp(),
-                            nanos: post_dto.updated_at.timestamp_subsec_nanos() as i32,
-                        }),
-                    })
+                    .map(|post| mask.apply(post))
                     .collect();
 
                 Ok(Response::new(ListPostsResponse {
                     status: Some(ProtoResponse {
                         code: ProtoStatus::Ok as i32,
                         details: Some("Posts retrieved successfully".to_string()),
+                        trace_id: None,
                     }),
                     posts: proto_posts,
+                    total,
+                    has_next,
+                    next_cursor: page.next_cursor,
                 }))
             }
             Err(e) => {
-                error!("Failed to retrieve posts: {}", e);
+                error!("Failed to retrieve feed: {}", e);
                 Ok(Response::new(ListPostsResponse {
                     status: Some(Self::map_domain_error(e)),
                     posts: vec![],
+                    total: 0,
+                    has_next: false,
+                    next_cursor: None,
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn get_digest_settings(
+        &self,
+        request: Request<GetDigestSettingsRequest>,
+    ) -> Result<Response<DigestSettingsResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        debug!("Get digest settings request received");
+
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+
+        match self.digest_app.get_settings(user_id).await {
+            Ok(settings) => Ok(Response::new(DigestSettingsResponse {
+                status: Some(ProtoResponse {
+                    code: ProtoStatus::Ok as i32,
+                    details: Some("Digest settings retrieved successfully".to_string()),
+                    trace_id: None,
+                }),
+                ..rest::DigestSettingsResponse::from(settings).into()
+            })),
+            Err(e) => {
+                warn!("Failed to get digest settings: {}", e);
+                Ok(Response::new(DigestSettingsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    ..Default::default()
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, request))]
+    async fn update_digest_settings(
+        &self,
+        request: Request<UpdateDigestSettingsRequest>,
+    ) -> Result<Response<DigestSettingsResponse>, Status> {
+        let claims = self.auth_interceptor.verify_token(&request).await?;
+        let req = request.into_inner();
+        debug!("Update digest settings request received");
+
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| Status::internal("Invalid user ID in token"))?;
+        let frequency = req
+            .frequency
+            .map(ProtoDigestFrequency::try_from)
+            .transpose()
+            .map_err(|_| Status::invalid_argument("Unknown digest frequency"))?
+            .map(|frequency| rest::DigestFrequency::from(frequency).into());
+
+        match self.digest_app.update_settings(user_id, frequency).await {
+            Ok(settings) => {
+                info!("Digest settings updated");
+                Ok(Response::new(DigestSettingsResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Digest settings updated successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    ..rest::DigestSettingsResponse::from(settings).into()
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to update digest settings: {}", e);
+                Ok(Response::new(DigestSettingsResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    ..Default::default()
+                }))
+            }
+        }
+    }
+
+    #[instrument(skip(self, _request))]
+    async fn list_categories(
+        &self,
+        _request: Request<ListCategoriesRequest>,
+    ) -> Result<Response<ListCategoriesResponse>, Status> {
+        debug!("List categories request received");
+
+        match self.category_app.list_categories().await {
+            Ok(categories) => {
+                info!("Retrieved {} categories", categories.len());
+                Ok(Response::new(ListCategoriesResponse {
+                    status: Some(ProtoResponse {
+                        code: ProtoStatus::Ok as i32,
+                        details: Some("Categories retrieved successfully".to_string()),
+                        trace_id: None,
+                    }),
+                    categories: categories.into_iter().map(category_to_proto).collect(),
+                }))
+            }
+            Err(e) => {
+                warn!("Failed to list categories: {}", e);
+                Ok(Response::new(ListCategoriesResponse {
+                    status: Some(Self::map_domain_error(e)),
+                    categories: Vec::new(),
                 }))
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::audit::AuditApplication;
+    use crate::application::auth::LoginLockout;
+    use crate::application::digest::DigestOptions;
+    use crate::application::post::PostLimits;
+    use crate::data::memrepo::InMemoryRepository;
+    use crate::domain::entities::report::{Report, ReportReason, ReportStatus};
+    use crate::domain::entities::session::DeviceInfo;
+    use crate::domain::entities::user::{Role, User};
+    use crate::domain::repositories::oauth::OAuthProviders;
+    use crate::domain::repositories::repo::UserRepository;
+    use crate::domain::repositories::report::ReportRepository;
+    use crate::domain::services::auth::AuthService;
+    use crate::infrastructure::config::RateLimitConfig;
+    use crate::infrastructure::email::LogEmailSender;
+    use crate::infrastructure::storage::LocalStorage;
+
+    const PASSWORD: &str = "correct-horse-battery";
+
+    /// gRPC сервис поверх репозитория в памяти, как при запуске с `--in-memory`
+    fn service() -> BlogServiceImpl<dyn Repository> {
+        let repo = Arc::new(InMemoryRepository::new());
+        service_with(repo.clone(), repo)
+    }
+
+    /// gRPC сервис, в котором посты читаются и пишутся через отдельный репозиторий `posts`
+    fn service_with(
+        repo: Arc<InMemoryRepository>,
+        posts: Arc<dyn PostRepository>,
+    ) -> BlogServiceImpl<dyn Repository> {
+        let repo: Arc<dyn Repository> = repo;
+        let audit_app = Arc::new(AuditApplication::new(repo.clone()));
+        let auth_service = Arc::new(AuthService::new(
+            chrono::Duration::minutes(5),
+            b"test-secret-test-secret-test-secret-test-secret-test-secret-1234",
+        ));
+        let lockout = LoginLockout {
+            max_failed_attempts: 0,
+            lock_duration: chrono::Duration::zero(),
+        };
+        BlogServiceImpl::new(
+            Arc::new(Audited::new(
+                AuthApplication::new(repo.clone(), auth_service, lockout, OAuthProviders::new()),
+                audit_app.clone(),
+            )),
+            Arc::new(Audited::new(
+                PostApplication::new(posts, repo.clone(), false),
+                audit_app.clone(),
+            )),
+            Arc::new(ProfileApplication::new(repo.clone())),
+            Arc::new(UploadApplication::new(
+                repo.clone(),
+                Arc::new(LocalStorage::new(
+                    std::env::temp_dir(),
+                    "/uploads".to_string(),
+                )),
+                1024,
+            )),
+            Arc::new(Audited::new(AdminApplication::new(repo.clone()), audit_app)),
+            Arc::new(ReportApplication::new(repo.clone())),
+            Arc::new(FollowApplication::new(repo.clone())),
+            Arc::new(DigestApplication::new(
+                repo.clone(),
+                Arc::new(LogEmailSender),
+                b"test-secret",
+                DigestOptions {
+                    site_name: "Blog".to_string(),
+                    app_url: "http://localhost".to_string(),
+                    public_url: "http://localhost".to_string(),
+                    max_posts: 10,
+                },
+            )),
+            Arc::new(CategoryApplication::new(repo.clone())),
+            Arc::new(BlogApplication::new(repo)),
+            Arc::new(RateLimiter::new(&RateLimitConfig::default())),
+            Arc::new(ServerInfo::new(false, false, PostLimits::default())),
+        )
+    }
+
+    /// Регистрирует пользователя и входит, возвращая пару токенов
+    async fn login(service: &BlogServiceImpl<dyn Repository>) -> TokenDto {
+        service
+            .auth_app
+            .create_user(RegisterDto {
+                username: "alice".to_string(),
+                password: PASSWORD.to_string(),
+                email: "alice@example.com".to_string(),
+            })
+            .await
+            .unwrap();
+        let outcome = service
+            .auth_app
+            .login(LoginDto {
+                identifier: "alice".to_string(),
+                kind: IdentifierKind::Username,
+                password: PASSWORD.to_string(),
+                device: DeviceInfo::new(Some("test"), None),
+            })
+            .await
+            .unwrap();
+        match outcome {
+            LoginOutcome::Authenticated(tokens) => tokens,
+            LoginOutcome::TwoFactorRequired { .. } => panic!("2FA is not enabled"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_revoked_refresh_token_is_unauthorized() {
+        let service = service();
+        let tokens = login(&service).await;
+
+        let logout = service
+            .logout(Request::new(LogoutRequest {
+                refresh_token: tokens.refresh_token.clone(),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(logout.status.unwrap().code, ProtoStatus::Ok as i32);
+
+        // Клиент по UNAUTHORIZED завершает сеанс, а не считает ответ сбоем сервера
+        let refresh = service
+            .refresh_token(Request::new(RefreshTokenRequest {
+                refresh_token: tokens.refresh_token,
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            refresh.status.unwrap().code,
+            ProtoStatus::Unauthorized as i32
+        );
+        assert!(refresh.token.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_hide_keeps_report_open() {
+        let repo = Arc::new(InMemoryRepository::new());
+        // Хранилище постов модерации пусто: скрыть пост по жалобе не получится
+        let service = service_with(repo.clone(), Arc::new(InMemoryRepository::new()));
+        let auth = AuthService::new(chrono::Duration::minutes(5), b"test-secret");
+
+        let author = User::new(
+            Uuid::now_v7(),
+            "bob".to_string(),
+            "bob@example.com".to_string(),
+            "hash".to_string(),
+            Role::User,
+            chrono::Utc::now(),
+        );
+        let author_id = repo.create_user(author).await.unwrap().id;
+        let moderator = User::new(
+            Uuid::now_v7(),
+            "alice".to_string(),
+            "alice@example.com".to_string(),
+            auth.hash_password(PASSWORD).unwrap(),
+            Role::Moderator,
+            chrono::Utc::now(),
+        );
+        let moderator_id = repo.create_user(moderator).await.unwrap().id;
+        let post = PostApplication::new(repo.clone(), repo.clone(), false)
+            .create_post(CreatePostDto {
+                title: "Title".to_string(),
+                content: "Content".to_string(),
+                author_id,
+                author_username: String::new(),
+                tags: vec![],
+                category_id: None,
+                blog_id: None,
+                summary: None,
+                attachment_ids: vec![],
+                status: PostStatus::Published,
+            })
+            .await
+            .unwrap();
+        let report = Report {
+            id: Uuid::now_v7(),
+            post_id: post.uuid,
+            reporter_id: moderator_id,
+            reason: ReportReason::Spam,
+            details: None,
+            status: ReportStatus::Open,
+            resolved_by: None,
+            resolved_at: None,
+            created_at: chrono::Utc::now(),
+        };
+        repo.create_report(&report).await.unwrap();
+
+        let tokens = match service
+            .auth_app
+            .login(LoginDto {
+                identifier: "alice".to_string(),
+                kind: IdentifierKind::Username,
+                password: PASSWORD.to_string(),
+                device: DeviceInfo::new(Some("test"), None),
+            })
+            .await
+            .unwrap()
+        {
+            LoginOutcome::Authenticated(tokens) => tokens,
+            LoginOutcome::TwoFactorRequired { .. } => panic!("2FA is not enabled"),
+        };
+        let mut request = Request::new(ResolveReportRequest {
+            report_id: report.id.to_string(),
+            action: api::ReportAction::HidePost as i32,
+        });
+        request.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {}", tokens.access_token).parse().unwrap(),
+        );
+        let response = service.resolve_report(request).await.unwrap().into_inner();
+        assert_eq!(response.status.unwrap().code, ProtoStatus::NotFound as i32);
+
+        // Жалоба осталась открытой, и модератор может повторить решение
+        let report = repo.get_report(report.id).await.unwrap();
+        assert_eq!(report.status, ReportStatus::Open);
+        assert!(repo.get_post_by_id(post.uuid, None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_forgot_password_is_rate_limited() {
+        let service = service();
+        let request = || {
+            let mut request = Request::new(ForgotPasswordRequest {
+                email: "alice@example.com".to_string(),
+            });
+            request
+                .extensions_mut()
+                .insert(tonic::transport::server::TcpConnectInfo {
+                    local_addr: None,
+                    remote_addr: Some(([203, 0, 113, 7], 40000).into()),
+                });
+            request
+        };
+
+        // Лимит по умолчанию пропускает 5 запросов подряд
+        for _ in 0..5 {
+            service.forgot_password(request()).await.unwrap();
+        }
+        let status = service.forgot_password(request()).await.unwrap_err();
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        assert!(status.metadata().get("retry-after").is_some());
+    }
+}
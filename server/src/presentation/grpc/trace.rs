@@ -0,0 +1,89 @@
+use std::task::{Context, Poll};
+
+use tokio::task::futures::TaskLocalFuture;
+use tonic::codegen::{Service, http};
+use tower_layer::Layer;
+use tracing::{Instrument, Span, instrument::Instrumented};
+use uuid::Uuid;
+
+use crate::presentation::request_id;
+
+/// Спан gRPC вызова, доступный обработчику через расширения запроса.
+///
+/// [`AuthInterceptor`](super::AuthInterceptor) записывает в него `user_id`.
+#[derive(Debug, Clone)]
+pub struct RequestSpan(pub Span);
+
+/// Оборачивает каждый gRPC вызов в спан `grpc_request` с новым `request_id`.
+///
+/// Этот же ID возвращается клиенту в `trace_id` ответа с ошибкой.
+///
+/// С feature `otel` родителем спана становится контекст из заголовка `traceparent`,
+/// поэтому спаны приложения попадают в трассировку вызывающего сервиса.
+#[derive(Debug, Clone, Default)]
+pub struct TraceLayer;
+
+impl<S> Layer<S> for TraceLayer {
+    type Service = TraceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TraceService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TraceService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<http::Request<B>> for TraceService<S>
+where
+    S: Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Instrumented<TaskLocalFuture<String, S::Future>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: http::Request<B>) -> Self::Future {
+        let request_id = Uuid::now_v7().to_string();
+        let span = tracing::info_span!(
+            "grpc_request",
+            rpc.method = %request.uri().path(),
+            request_id = %request_id,
+            user_id = tracing::field::Empty,
+        );
+        #[cfg(feature = "otel")]
+        set_remote_parent(&span, request.headers());
+
+        request.extensions_mut().insert(RequestSpan(span.clone()));
+        let _entered = span.enter();
+        request_id::scope(request_id, self.inner.call(request)).instrument(span.clone())
+    }
+}
+
+/// Делает родителем спана контекст трассировки из заголовков запроса
+#[cfg(feature = "otel")]
+fn set_remote_parent(span: &Span, headers: &http::HeaderMap) {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    struct HeaderExtractor<'a>(&'a http::HeaderMap);
+
+    impl opentelemetry::propagation::Extractor for HeaderExtractor<'_> {
+        fn get(&self, key: &str) -> Option<&str> {
+            self.0.get(key).and_then(|value| value.to_str().ok())
+        }
+
+        fn keys(&self) -> Vec<&str> {
+            self.0.keys().map(|name| name.as_str()).collect()
+        }
+    }
+
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(headers))
+    });
+    let _ = span.set_parent(parent);
+}
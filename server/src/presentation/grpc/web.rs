@@ -0,0 +1,35 @@
+use std::time::Duration;
+use tonic::codegen::http::{HeaderName, HeaderValue, Method, header};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::infrastructure::reload::AllowedOrigin;
+
+/// Заголовки gRPC-Web, которые браузерный клиент отправляет на сервер
+const ALLOWED_HEADERS: [&str; 3] = ["x-grpc-web", "x-user-agent", "grpc-timeout"];
+/// Заголовки ответа, которые должны быть доступны браузерному клиенту
+const EXPOSED_HEADERS: [&str; 4] = [
+    "grpc-status",
+    "grpc-message",
+    "grpc-status-details-bin",
+    "retry-after",
+];
+
+/// CORS для gRPC-Web запросов из браузера.
+///
+/// Разрешает тот же origin, что и HTTP API; без этого браузер не пропустит
+/// ни preflight-запрос, ни чтение статуса вызова из заголовков ответа.
+pub fn cors_layer(origin: AllowedOrigin) -> CorsLayer {
+    let allowed_headers = ALLOWED_HEADERS
+        .into_iter()
+        .map(HeaderName::from_static)
+        .chain([header::CONTENT_TYPE, header::AUTHORIZATION]);
+
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(move |value: &HeaderValue, _| {
+            origin.matches(value.as_bytes())
+        }))
+        .allow_methods([Method::POST])
+        .allow_headers(allowed_headers.collect::<Vec<_>>())
+        .expose_headers(EXPOSED_HEADERS.map(HeaderName::from_static))
+        .max_age(Duration::from_secs(3600))
+}
@@ -1,126 +1,2989 @@
+use actix_multipart::Multipart;
+use actix_web::cookie::time::Duration as CookieDuration;
+use actix_web::cookie::{Cookie, SameSite};
+use actix_web::http::StatusCode;
+use actix_web::http::header::{CACHE_CONTROL, CONTENT_TYPE, ContentEncoding, LOCATION, USER_AGENT};
+use actix_web::middleware::from_fn;
 use actix_web::{HttpMessage, HttpRequest, HttpResponse, Responder, delete, get, post, put, web};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tracing::{info, warn};
+use std::time::Duration;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::{BroadcastStream, IntervalStream};
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use utoipa::IntoParams;
+
 use api::rest::{
-    CreatePostRequest, LoginRequest, PostResponse, RefreshTokenRequest, RegisterRequest,
-    TokenResponse, UpdatePostRequest,
+    AdminUserResponse, AuditAction as RestAuditAction, AuditEntryResponse, AuditLogQuery,
+    BlogListResponse, BlogResponse, BodyLoggingSettings, CategoryListResponse, CategoryResponse,
+    CreateBlogRequest, CreateCategoryRequest, CreatePostRequest, CreatePostsRequest,
+    CreatePostsResponse, DeletePostsRequest, DigestFrequency as RestDigestFrequency,
+    DigestSettingsResponse, ErrorResponse, FollowResponse, ForgotPasswordRequest, ListPostsQuery,
+    ListReportsQuery, LoginRequest, LogoutRequest, Paginated, PaginationQuery, PostEventResponse,
+    PostFieldsQuery, PostResponse, PostSortField as RestPostSortField,
+    PostStatus as RestPostStatus, RefreshTokenRequest, RegisterRequest, ReportAction,
+    ReportPostRequest, ReportReason as RestReportReason, ReportResponse,
+    ReportStatus as RestReportStatus, ResetPasswordRequest, ResolveReportRequest,
+    ServerInfoResponse, SessionListResponse, SessionResponse, SortOrder as RestSortOrder,
+    SystemStatsResponse, TokenResponse, TotpEnrollmentResponse, TotpLoginRequest,
+    TotpVerifyRequest, TrendingPostsQuery, TwoFactorChallengeResponse, UpdateBlogRequest,
+    UpdateDigestSettingsRequest, UpdatePostRequest, UpdateProfileRequest, UploadResponse,
+    UserProfileResponse, UserResponse, UserRole, UsernameAvailabilityResponse, Validate,
+};
+
+use crate::application::admin::AdminApplication;
+use crate::application::audit::{AuditApplication, Audited};
+use crate::application::auth::AuthApplication;
+use crate::application::blog::BlogApplication;
+use crate::application::category::CategoryApplication;
+use crate::application::digest::DigestApplication;
+use crate::application::dto::admin::{SystemStatsDto, UserSummaryDto};
+use crate::application::dto::audit::AuditEntryDto;
+use crate::application::dto::auth::{
+    LoginDto, LoginOutcome, RegisterDto, ResetPasswordDto, SessionDto, TokenDto, TotpEnrollmentDto,
+    TotpLoginDto,
 };
+use crate::application::dto::blog::{BlogDto, CreateBlogDto};
+use crate::application::dto::category::{CategoryDto, CreateCategoryDto};
+use crate::application::dto::digest::DigestSettingsDto;
+use crate::application::dto::post::{CreatePostDto, PostDto, PostEventDto, UpdatePostDto};
+use crate::application::dto::profile::{UpdateProfileDto, UserProfileDto};
+use crate::application::dto::report::{CreateReportDto, ReportDto};
+use crate::application::dto::upload::AttachmentDto;
+use crate::application::follow::FollowApplication;
+use crate::application::idempotency::IdempotencyApplication;
+use crate::application::post::PostApplication;
+use crate::application::profile::ProfileApplication;
+use crate::application::report::ReportApplication;
+use crate::application::upload::UploadApplication;
+use crate::domain::entities::audit::{AuditAction, AuditFilter};
+use crate::domain::entities::digest::DigestFrequency;
+use crate::domain::entities::idempotency::StoredResponse;
+use crate::domain::entities::oauth::OAuthProviderKind;
+use crate::domain::entities::post::{PostFields, PostSort, PostSortField, PostStatus, SortOrder};
+use crate::domain::entities::report::{ReportReason, ReportResolution, ReportStatus};
+use crate::domain::entities::session::DeviceInfo;
+use crate::domain::entities::user::{IdentifierKind, Role};
+use crate::domain::repositories::Repository;
+use crate::domain::repositories::post::PostRepository;
+use crate::domain::services::auth::AuthService;
+use crate::presentation::error::ApiError;
+use crate::presentation::http::middleware::{AuthenticatedUser, auth_rate_limit, optional_user};
+use crate::presentation::server_info::ServerInfo;
+
+/// Хранилище, выбранное при запуске сервера
+type Repo = dyn Repository;
+
+// Структура для хранения зависимостей приложения
+pub struct AppState {
+    pub auth_app: Arc<Audited<AuthApplication<Repo>, Repo>>,
+    pub post_app: Arc<Audited<PostApplication<dyn PostRepository, Repo>, Repo>>,
+    pub profile_app: Arc<ProfileApplication<Repo>>,
+    pub idempotency_app: Arc<IdempotencyApplication<Repo>>,
+    pub upload_app: Arc<UploadApplication<Repo>>,
+    pub audit_app: Arc<AuditApplication<Repo>>,
+    pub admin_app: Arc<Audited<AdminApplication<Repo>, Repo>>,
+    pub report_app: Arc<ReportApplication<Repo>>,
+    pub follow_app: Arc<FollowApplication<Repo>>,
+    pub digest_app: Arc<DigestApplication<Repo>>,
+    pub category_app: Arc<CategoryApplication<Repo>>,
+    pub blog_app: Arc<BlogApplication<Repo>>,
+}
+
+/// Заголовок запроса с ключом идемпотентности
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+/// Заголовок ответа, повторённого по ключу идемпотентности
+const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+
+/// Выполняет `handler` не более одного раза для каждого заголовка `Idempotency-Key`.
+///
+/// Повтор с тем же ключом и телом запроса получает сохранённый ответ первого запроса.
+/// Ключ действует в пределах метода, пути и пользователя; без заголовка запрос
+/// выполняется как обычно.
+async fn idempotent<B, T>(
+    http_req: &HttpRequest,
+    state: &AppState,
+    body: &B,
+    handler: impl Future<Output = Result<(StatusCode, T), ApiError>>,
+) -> Result<HttpResponse, ApiError>
+where
+    B: Serialize,
+    T: Serialize,
+{
+    let Some(key) = http_req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        let (status, response) = handler.await?;
+        return Ok(HttpResponse::build(status).json(response));
+    };
+    let key = key
+        .to_str()
+        .map_err(|_| ApiError::bad_request("Idempotency-Key must be ASCII".to_string()))?;
+
+    let user_id = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .map(|user| user.user_id.to_string())
+        .unwrap_or_default();
+    let scope = format!("{} {} {}", http_req.method(), http_req.path(), user_id);
+    let request_body = serde_json::to_vec(body)
+        .map_err(|e| ApiError::internal_server_error(format!("Failed to encode request: {e}")))?;
+
+    if let Some(stored) = state
+        .idempotency_app
+        .begin(&scope, key, &request_body)
+        .await?
+    {
+        info!("Replaying response for idempotency key: {}", key);
+        let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+        return Ok(HttpResponse::build(status)
+            .insert_header((CONTENT_TYPE, "application/json"))
+            .insert_header((IDEMPOTENT_REPLAYED_HEADER, "true"))
+            .body(stored.body));
+    }
+
+    match handler.await {
+        Ok((status, response)) => {
+            let body = serde_json::to_string(&response).map_err(|e| {
+                ApiError::internal_server_error(format!("Failed to encode response: {e}"))
+            })?;
+            let stored = StoredResponse {
+                status: status.as_u16(),
+                body: body.clone(),
+            };
+            // Запрос уже выполнен, поэтому ошибка сохранения ответа не должна его отменять
+            if let Err(e) = state.idempotency_app.complete(&scope, key, stored).await {
+                error!("Failed to store idempotent response: {}", e);
+            }
+            Ok(HttpResponse::build(status)
+                .insert_header((CONTENT_TYPE, "application/json"))
+                .body(body))
+        }
+        Err(err) => {
+            // Неудавшийся запрос можно повторить с тем же ключом
+            if let Err(e) = state.idempotency_app.release(&scope, key).await {
+                error!("Failed to release idempotency key: {}", e);
+            }
+            Err(err)
+        }
+    }
+}
+
+impl From<TokenDto> for TokenResponse {
+    fn from(dto: TokenDto) -> Self {
+        Self {
+            access_token: dto.access_token,
+            refresh_token: dto.refresh_token,
+            expires_in: dto.expires_in,
+        }
+    }
+}
+
+impl From<TotpEnrollmentDto> for TotpEnrollmentResponse {
+    fn from(dto: TotpEnrollmentDto) -> Self {
+        Self {
+            secret: dto.secret,
+            provisioning_uri: dto.provisioning_uri,
+        }
+    }
+}
+
+impl From<PostStatus> for RestPostStatus {
+    fn from(status: PostStatus) -> Self {
+        match status {
+            PostStatus::Draft => RestPostStatus::Draft,
+            PostStatus::Published => RestPostStatus::Published,
+        }
+    }
+}
+
+impl From<RestPostStatus> for PostStatus {
+    fn from(status: RestPostStatus) -> Self {
+        match status {
+            RestPostStatus::Draft => PostStatus::Draft,
+            RestPostStatus::Published => PostStatus::Published,
+        }
+    }
+}
+
+impl From<RestPostSortField> for PostSortField {
+    fn from(field: RestPostSortField) -> Self {
+        match field {
+            RestPostSortField::CreatedAt => PostSortField::CreatedAt,
+            RestPostSortField::UpdatedAt => PostSortField::UpdatedAt,
+            RestPostSortField::Title => PostSortField::Title,
+        }
+    }
+}
+
+impl From<RestSortOrder> for SortOrder {
+    fn from(order: RestSortOrder) -> Self {
+        match order {
+            RestSortOrder::Asc => SortOrder::Asc,
+            RestSortOrder::Desc => SortOrder::Desc,
+        }
+    }
+}
+
+impl From<PostDto> for PostResponse {
+    fn from(dto: PostDto) -> Self {
+        Self {
+            uuid: dto.uuid.to_string(),
+            title: dto.title,
+            content: Some(dto.content),
+            content_html: dto.content_html,
+            summary: dto.summary,
+            author_id: dto.author_id.to_string(),
+            author_username: dto.author_username,
+            author_avatar_url: dto.author_avatar_url,
+            tags: dto.tags,
+            category_id: dto.category_id.map(|id| id.to_string()),
+            blog_id: dto.blog_id.map(|id| id.to_string()),
+            attachments: dto.attachments,
+            status: dto.status.into(),
+            like_count: dto.like_count as u64,
+            liked_by_me: dto.liked_by_me,
+            created_at: dto.created_at.to_rfc3339(),
+            updated_at: dto.updated_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<AttachmentDto> for UploadResponse {
+    fn from(dto: AttachmentDto) -> Self {
+        Self {
+            id: dto.id.to_string(),
+            url: dto.url,
+            file_name: dto.file_name,
+            content_type: dto.content_type,
+            size: dto.size_bytes as u64,
+        }
+    }
+}
+
+impl From<AuditAction> for RestAuditAction {
+    fn from(action: AuditAction) -> Self {
+        match action {
+            AuditAction::Register => RestAuditAction::Register,
+            AuditAction::Login => RestAuditAction::Login,
+            AuditAction::CreatePost => RestAuditAction::CreatePost,
+            AuditAction::UpdatePost => RestAuditAction::UpdatePost,
+            AuditAction::DeletePost => RestAuditAction::DeletePost,
+            AuditAction::RestorePost => RestAuditAction::RestorePost,
+            AuditAction::PurgePost => RestAuditAction::PurgePost,
+            AuditAction::HidePost => RestAuditAction::HidePost,
+            AuditAction::DisableUser => RestAuditAction::DisableUser,
+            AuditAction::EnableUser => RestAuditAction::EnableUser,
+        }
+    }
+}
+
+impl From<Role> for UserRole {
+    fn from(role: Role) -> Self {
+        match role {
+            Role::User => UserRole::User,
+            Role::Moderator => UserRole::Moderator,
+            Role::Admin => UserRole::Admin,
+        }
+    }
+}
+
+impl From<UserSummaryDto> for AdminUserResponse {
+    fn from(dto: UserSummaryDto) -> Self {
+        Self {
+            id: dto.id.to_string(),
+            username: dto.username,
+            email: dto.email,
+            role: dto.role.into(),
+            created_at: dto.created_at.to_rfc3339(),
+            disabled_at: dto.disabled_at.map(|disabled_at| disabled_at.to_rfc3339()),
+        }
+    }
+}
+
+impl From<SystemStatsDto> for SystemStatsResponse {
+    fn from(dto: SystemStatsDto) -> Self {
+        Self {
+            users: dto.users,
+            posts: dto.posts,
+            recent_signups: dto.recent_signups,
+            recent_signups_days: dto.recent_signups_days,
+        }
+    }
+}
+
+impl From<RestDigestFrequency> for DigestFrequency {
+    fn from(frequency: RestDigestFrequency) -> Self {
+        match frequency {
+            RestDigestFrequency::Daily => DigestFrequency::Daily,
+            RestDigestFrequency::Weekly => DigestFrequency::Weekly,
+        }
+    }
+}
+
+impl From<DigestFrequency> for RestDigestFrequency {
+    fn from(frequency: DigestFrequency) -> Self {
+        match frequency {
+            DigestFrequency::Daily => RestDigestFrequency::Daily,
+            DigestFrequency::Weekly => RestDigestFrequency::Weekly,
+        }
+    }
+}
+
+impl From<DigestSettingsDto> for DigestSettingsResponse {
+    fn from(dto: DigestSettingsDto) -> Self {
+        Self {
+            frequency: dto.frequency.map(Into::into),
+            last_sent_at: dto
+                .last_sent_at
+                .map(|last_sent_at| last_sent_at.to_rfc3339()),
+        }
+    }
+}
+
+impl From<RestReportReason> for ReportReason {
+    fn from(reason: RestReportReason) -> Self {
+        match reason {
+            RestReportReason::Spam => ReportReason::Spam,
+            RestReportReason::Harassment => ReportReason::Harassment,
+            RestReportReason::HateSpeech => ReportReason::HateSpeech,
+            RestReportReason::Misinformation => ReportReason::Misinformation,
+            RestReportReason::Inappropriate => ReportReason::Inappropriate,
+            RestReportReason::Other => ReportReason::Other,
+        }
+    }
+}
+
+impl From<ReportReason> for RestReportReason {
+    fn from(reason: ReportReason) -> Self {
+        match reason {
+            ReportReason::Spam => RestReportReason::Spam,
+            ReportReason::Harassment => RestReportReason::Harassment,
+            ReportReason::HateSpeech => RestReportReason::HateSpeech,
+            ReportReason::Misinformation => RestReportReason::Misinformation,
+            ReportReason::Inappropriate => RestReportReason::Inappropriate,
+            ReportReason::Other => RestReportReason::Other,
+        }
+    }
+}
+
+impl From<RestReportStatus> for ReportStatus {
+    fn from(status: RestReportStatus) -> Self {
+        match status {
+            RestReportStatus::Open => ReportStatus::Open,
+            RestReportStatus::Hidden => ReportStatus::Hidden,
+            RestReportStatus::Dismissed => ReportStatus::Dismissed,
+        }
+    }
+}
+
+impl From<ReportStatus> for RestReportStatus {
+    fn from(status: ReportStatus) -> Self {
+        match status {
+            ReportStatus::Open => RestReportStatus::Open,
+            ReportStatus::Hidden => RestReportStatus::Hidden,
+            ReportStatus::Dismissed => RestReportStatus::Dismissed,
+        }
+    }
+}
+
+impl From<ReportAction> for ReportResolution {
+    fn from(action: ReportAction) -> Self {
+        match action {
+            ReportAction::HidePost => ReportResolution::HidePost,
+            ReportAction::Dismiss => ReportResolution::Dismiss,
+        }
+    }
+}
+
+impl From<ReportDto> for ReportResponse {
+    fn from(dto: ReportDto) -> Self {
+        Self {
+            id: dto.id.to_string(),
+            post_id: dto.post_id.to_string(),
+            reporter_id: dto.reporter_id.to_string(),
+            reason: dto.reason.into(),
+            details: dto.details,
+            status: dto.status.into(),
+            resolved_by: dto.resolved_by.map(|id| id.to_string()),
+            resolved_at: dto.resolved_at.map(|resolved_at| resolved_at.to_rfc3339()),
+            created_at: dto.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<CategoryDto> for CategoryResponse {
+    fn from(dto: CategoryDto) -> Self {
+        Self {
+            id: dto.id.to_string(),
+            name: dto.name,
+            parent_id: dto.parent_id.map(|id| id.to_string()),
+            created_at: dto.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<BlogDto> for BlogResponse {
+    fn from(dto: BlogDto) -> Self {
+        Self {
+            id: dto.id.to_string(),
+            slug: dto.slug,
+            name: dto.name,
+            owner_id: dto.owner_id.to_string(),
+            created_at: dto.created_at.to_rfc3339(),
+        }
+    }
+}
+
+impl From<AuditEntryDto> for AuditEntryResponse {
+    fn from(dto: AuditEntryDto) -> Self {
+        Self {
+            id: dto.id.to_string(),
+            user_id: dto.user_id.to_string(),
+            action: dto.action.into(),
+            target_id: dto.target_id.map(|id| id.to_string()),
+            created_at: dto.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Разбирает время из query string в формате RFC 3339.
+fn parse_timestamp(name: &str, value: &str) -> Result<chrono::DateTime<chrono::Utc>, ApiError> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&chrono::Utc))
+        .map_err(|_| ApiError::bad_request(format!("{name} must be an RFC 3339 timestamp")))
+}
+
+/// Разбирает UUID вложений из тела запроса.
+fn parse_attachment_ids(ids: &[String]) -> Result<Vec<Uuid>, ApiError> {
+    ids.iter()
+        .map(|id| Uuid::parse_str(id))
+        .collect::<Result<_, _>>()
+        .map_err(|_| ApiError::bad_request("Invalid attachment UUID format".to_string()))
+}
+
+/// Поля [`PostResponse`], которые можно перечислить в параметре `fields`
+const POST_FIELDS: &[&str] = &[
+    "uuid",
+    "title",
+    "content",
+    "content_html",
+    "summary",
+    "author_id",
+    "author_username",
+    "author_avatar_url",
+    "tags",
+    "category_id",
+    "blog_id",
+    "attachments",
+    "status",
+    "like_count",
+    "liked_by_me",
+    "created_at",
+    "updated_at",
+];
+
+/// Поля поста, выбранные параметром `fields`; `None` - все поля.
+#[derive(Debug, Clone, Default)]
+struct PostFieldSelection(Option<Vec<&'static str>>);
+
+impl PostFieldSelection {
+    /// Разбирает список полей через запятую; `uuid` выбирается всегда.
+    ///
+    /// Без параметра или с пустым списком выбираются все поля; неизвестное имя
+    /// поля - ошибка запроса.
+    fn parse(fields: Option<&str>) -> Result<Self, ApiError> {
+        let mut selected = vec!["uuid"];
+        for field in fields
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+        {
+            let Some(&name) = POST_FIELDS.iter().find(|&&name| name == field) else {
+                return Err(ApiError::bad_request(format!(
+                    "Unknown post field: {field}"
+                )));
+            };
+            if !selected.contains(&name) {
+                selected.push(name);
+            }
+        }
+        if selected.len() == 1 {
+            return Ok(Self(None));
+        }
+        Ok(Self(Some(selected)))
+    }
+
+    /// Части постов, которые нужно прочитать из хранилища для выбранных полей
+    fn storage_fields(&self) -> PostFields {
+        let Some(selected) = &self.0 else {
+            return PostFields::ALL;
+        };
+        let any = |names: &[&str]| names.iter().any(|name| selected.contains(name));
+        PostFields {
+            // Описание без заданного автором текста составляется из содержимого
+            content: any(&["content", "content_html", "summary"]),
+            tags: any(&["tags"]),
+            attachments: any(&["attachments"]),
+            likes: any(&["like_count", "liked_by_me"]),
+        }
+    }
+
+    /// JSON поста только с выбранными полями
+    fn apply(&self, post: PostDto) -> serde_json::Value {
+        let mut value = serde_json::to_value(PostResponse::from(post))
+            .expect("PostResponse is always serializable");
+        if let (Some(selected), serde_json::Value::Object(map)) = (&self.0, &mut value) {
+            map.retain(|key, _| selected.contains(&key.as_str()));
+        }
+        value
+    }
+}
+
+/// Разбирает UUID категории из запроса; пустая строка означает «без категории».
+fn parse_category_id(id: &str) -> Result<Option<Uuid>, ApiError> {
+    if id.is_empty() {
+        return Ok(None);
+    }
+    Uuid::parse_str(id)
+        .map(Some)
+        .map_err(|_| ApiError::bad_request("Invalid category UUID format".to_string()))
+}
+
+impl From<PostEventDto> for PostEventResponse {
+    fn from(event: PostEventDto) -> Self {
+        match event {
+            PostEventDto::Created(post) => PostEventResponse::Created { post: post.into() },
+            PostEventDto::Updated(post) => PostEventResponse::Updated { post: post.into() },
+            PostEventDto::Deleted { post_id } => PostEventResponse::Deleted {
+                post_id: post_id.to_string(),
+            },
+        }
+    }
+}
+
+impl From<SessionDto> for SessionResponse {
+    fn from(dto: SessionDto) -> Self {
+        Self {
+            id: dto.id.to_string(),
+            user_agent: dto.user_agent,
+            ip_address: dto.ip_address,
+            created_at: dto.created_at.to_rfc3339(),
+            last_used_at: dto.last_used_at.to_rfc3339(),
+            current: dto.current,
+        }
+    }
+}
+
+impl From<UserProfileDto> for UserProfileResponse {
+    fn from(dto: UserProfileDto) -> Self {
+        Self {
+            id: dto.user_id.to_string(),
+            username: dto.username,
+            display_name: dto.display_name,
+            bio: dto.bio,
+            avatar_url: dto.avatar_url,
+            created_at: dto.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Регистрация пользователя.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/register",
+    tag = "auth",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Ключ идемпотентности")),
+    request_body = RegisterRequest,
+    responses(
+        (status = 201, description = "Пользователь зарегистрирован", body = UserResponse),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 409, description = "Конфликт ключа идемпотентности", body = ErrorResponse),
+        (status = 429, description = "Превышен лимит запросов с IP-адреса", body = ErrorResponse),
+    ),
+)]
+#[post("/api/v1/auth/register", wrap = "from_fn(auth_rate_limit)")]
+pub async fn register(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    req: web::Json<RegisterRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!(
+        "Received registration request for username: {}",
+        req.username
+    );
+
+    req.validate()?;
+
+    idempotent(&http_req, &state, &*req, async {
+        let dto = RegisterDto {
+            username: req.username.clone(),
+            password: req.password.clone(),
+            email: req.email.clone(),
+        };
+
+        let user = state.auth_app.create_user(dto).await?;
+
+        info!("User registered successfully: {}", user.username);
+
+        Ok((
+            StatusCode::CREATED,
+            UserResponse {
+                id: user.id.to_string(),
+                username: user.username,
+                email: user.email,
+                created_at: user.created_at.to_rfc3339(),
+            },
+        ))
+    })
+    .await
+}
+
+/// Запрос на проверку доступности имени пользователя.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UsernameQuery {
+    /// Проверяемое имя пользователя
+    pub username: String,
+}
+
+/// Проверка, свободно ли имя пользователя.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/username-available",
+    tag = "auth",
+    params(UsernameQuery),
+    responses(
+        (status = 200, description = "Результат проверки", body = UsernameAvailabilityResponse),
+    ),
+)]
+#[get("/api/v1/auth/username-available")]
+pub async fn username_available(
+    state: web::Data<AppState>,
+    query: web::Query<UsernameQuery>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received username availability check: {}", query.username);
+
+    let available = state
+        .auth_app
+        .is_username_available(&query.username)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(UsernameAvailabilityResponse {
+        username: query.username.clone(),
+        available,
+    }))
+}
+
+/// Вход по имени пользователя или email и паролю.
+///
+/// Для аккаунта с включённой 2FA вместо токенов возвращается токен второго шага
+/// (см. `POST /api/v1/auth/login/2fa`).
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login",
+    tag = "auth",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Пара токенов", body = TokenResponse),
+        (status = 202, description = "Нужен код двухфакторной аутентификации", body = TwoFactorChallengeResponse),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Неверное имя пользователя или пароль", body = ErrorResponse),
+        (status = 423, description = "Вход заблокирован после серии неудачных попыток", body = ErrorResponse),
+        (status = 429, description = "Превышен лимит запросов с IP-адреса", body = ErrorResponse),
+    ),
+)]
+#[post("/api/v1/auth/login", wrap = "from_fn(auth_rate_limit)")]
+pub async fn login(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    req: web::Json<LoginRequest>,
+) -> Result<impl Responder, ApiError> {
+    req.validate()?;
+
+    let identifier = req
+        .identifier()
+        .ok_or_else(|| ApiError::bad_request("Either username or email is required".to_string()))?;
+    info!("Received login request for: {}", identifier);
+
+    // Явно переданный email не угадываем, имя пользователя может оказаться и email
+    let kind = match req.email {
+        Some(_) => IdentifierKind::Email,
+        None => IdentifierKind::detect(identifier),
+    };
+    let dto = LoginDto {
+        identifier: identifier.to_string(),
+        kind,
+        password: req.password.clone(),
+        device: device_info(&http_req),
+    };
+
+    match state.auth_app.login(dto).await? {
+        LoginOutcome::Authenticated(token_dto) => {
+            info!("User logged in successfully: {}", identifier);
+            Ok(HttpResponse::Ok().json(TokenResponse::from(token_dto)))
+        }
+        LoginOutcome::TwoFactorRequired {
+            challenge_token,
+            expires_in,
+        } => {
+            info!("Two-factor code required for: {}", identifier);
+            Ok(HttpResponse::Accepted().json(TwoFactorChallengeResponse {
+                requires_2fa: true,
+                challenge_token,
+                expires_in,
+            }))
+        }
+    }
+}
+
+/// Cookie со значением `state` незавершённого входа через OAuth
+const OAUTH_STATE_COOKIE: &str = "oauth_state";
+/// Cookie `state` нужна обработчику возврата от провайдера в любой версии API:
+/// провайдер может вернуть пользователя и на `/api/v1/...`, и на `/api/v2/...`
+const OAUTH_COOKIE_PATH: &str = "/api";
+/// Сколько секунд пользователь может провести на странице входа провайдера
+const OAUTH_STATE_MAX_AGE_SECS: i64 = 600;
+
+/// Сведения о клиенте для сеанса входа: `User-Agent` и адрес подключения.
+fn device_info(http_req: &HttpRequest) -> DeviceInfo {
+    DeviceInfo::new(
+        http_req
+            .headers()
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok()),
+        http_req.peer_addr().map(|addr| addr.ip()),
+    )
+}
+
+fn parse_oauth_provider(value: &str) -> Result<OAuthProviderKind, ApiError> {
+    OAuthProviderKind::parse(value)
+        .ok_or_else(|| ApiError::not_found(format!("Unknown OAuth provider: {}", value)))
+}
+
+/// Вход через провайдера OAuth: перенаправление на его страницу входа.
+///
+/// Значение `state` сохраняется в cookie и сверяется при возврате пользователя,
+/// чтобы чужой код авторизации нельзя было подставить в браузер жертвы.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oauth/{provider}",
+    tag = "auth",
+    params(("provider" = String, Path, description = "Провайдер: `google` или `github`")),
+    responses(
+        (status = 302, description = "Перенаправление на страницу входа провайдера"),
+        (status = 404, description = "Провайдер неизвестен или не настроен", body = ErrorResponse),
+    ),
+)]
+#[get("/api/v1/auth/oauth/{provider}")]
+pub async fn oauth_authorize(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received OAuth login request for provider: {}", path);
+
+    let provider = parse_oauth_provider(&path)?;
+    let redirect = state.auth_app.start_oauth_login(provider)?;
+
+    let cookie = Cookie::build(OAUTH_STATE_COOKIE, redirect.state)
+        .path(OAUTH_COOKIE_PATH)
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .secure(http_req.connection_info().scheme() == "https")
+        .max_age(CookieDuration::seconds(OAUTH_STATE_MAX_AGE_SECS))
+        .finish();
+
+    Ok(HttpResponse::Found()
+        .insert_header((LOCATION, redirect.authorization_url))
+        .cookie(cookie)
+        .finish())
+}
+
+/// Параметры, с которыми провайдер возвращает пользователя после входа.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct OAuthCallbackQuery {
+    /// Код авторизации
+    pub code: Option<String>,
+    /// Значение `state` из перенаправления на страницу входа
+    pub state: Option<String>,
+    /// Код ошибки, если пользователь отказался от входа
+    pub error: Option<String>,
+}
+
+/// Возврат от провайдера OAuth: вход по коду авторизации.
+///
+/// При первом входе аккаунт у провайдера связывается с пользователем с тем же
+/// подтверждённым email или с новым пользователем. Ответ такой же, как у
+/// `POST /api/v1/auth/login`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oauth/{provider}/callback",
+    tag = "auth",
+    params(("provider" = String, Path, description = "Провайдер: `google` или `github`"), OAuthCallbackQuery),
+    responses(
+        (status = 200, description = "Пара токенов", body = TokenResponse),
+        (status = 202, description = "Нужен код двухфакторной аутентификации", body = TwoFactorChallengeResponse),
+        (status = 400, description = "Нет кода авторизации или email уже занят", body = ErrorResponse),
+        (status = 401, description = "Вход отклонён провайдером или `state` не совпадает", body = ErrorResponse),
+        (status = 404, description = "Провайдер неизвестен или не настроен", body = ErrorResponse),
+        (status = 423, description = "Вход заблокирован после серии неудачных попыток", body = ErrorResponse),
+        (status = 429, description = "Превышен лимит запросов с IP-адреса", body = ErrorResponse),
+    ),
+)]
+#[get(
+    "/api/v1/auth/oauth/{provider}/callback",
+    wrap = "from_fn(auth_rate_limit)"
+)]
+pub async fn oauth_callback(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<OAuthCallbackQuery>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received OAuth callback for provider: {}", path);
+
+    let provider = parse_oauth_provider(&path)?;
+    if let Some(error) = &query.error {
+        warn!("OAuth login was not completed: {}", error);
+        return Err(ApiError::unauthorized(format!(
+            "OAuth login was not completed: {}",
+            error
+        )));
+    }
+    let (Some(code), Some(returned_state)) = (&query.code, &query.state) else {
+        return Err(ApiError::bad_request(
+            "Both code and state are required".to_string(),
+        ));
+    };
+    let expected_state = http_req.cookie(OAUTH_STATE_COOKIE);
+    if expected_state.as_ref().map(|cookie| cookie.value()) != Some(returned_state.as_str()) {
+        warn!("OAuth login rejected: state mismatch");
+        return Err(ApiError::unauthorized("OAuth state mismatch".to_string()));
+    }
+
+    let outcome = state
+        .auth_app
+        .login_with_oauth(provider, code, device_info(&http_req))
+        .await?;
+
+    // Значение state одноразовое
+    let removal = Cookie::build(OAUTH_STATE_COOKIE, "")
+        .path(OAUTH_COOKIE_PATH)
+        .finish();
+    let mut response = match outcome {
+        LoginOutcome::Authenticated(token_dto) => {
+            info!("User logged in successfully via {}", provider.as_str());
+            HttpResponse::Ok().json(TokenResponse::from(token_dto))
+        }
+        LoginOutcome::TwoFactorRequired {
+            challenge_token,
+            expires_in,
+        } => {
+            info!("Two-factor code required after {} login", provider.as_str());
+            HttpResponse::Accepted().json(TwoFactorChallengeResponse {
+                requires_2fa: true,
+                challenge_token,
+                expires_in,
+            })
+        }
+    };
+    response
+        .add_removal_cookie(&removal)
+        .map_err(|e| ApiError::internal_server_error(e.to_string()))?;
+
+    Ok(response)
+}
+
+/// Второй шаг входа: код двухфакторной аутентификации.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/login/2fa",
+    tag = "auth",
+    request_body = TotpLoginRequest,
+    responses(
+        (status = 200, description = "Пара токенов", body = TokenResponse),
+        (status = 400, description = "Неверный код или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Токен второго шага недействителен или истёк", body = ErrorResponse),
+        (status = 423, description = "Вход заблокирован после серии неудачных попыток", body = ErrorResponse),
+        (status = 429, description = "Превышен лимит запросов с IP-адреса", body = ErrorResponse),
+    ),
+)]
+#[post("/api/v1/auth/login/2fa", wrap = "from_fn(auth_rate_limit)")]
+pub async fn login_totp(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    req: web::Json<TotpLoginRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received two-factor login request");
+
+    req.validate()?;
+
+    let req = req.into_inner();
+    let dto = TotpLoginDto {
+        challenge_token: req.challenge_token,
+        code: req.code,
+        device: device_info(&http_req),
+    };
+    let token_dto = state.auth_app.login_with_totp(dto).await?;
+
+    info!("User logged in successfully with two-factor code");
+
+    Ok(HttpResponse::Ok().json(TokenResponse::from(token_dto)))
+}
+
+/// Подключение двухфакторной аутентификации: выдача секрета TOTP.
+///
+/// 2FA включается после подтверждения кодом (`POST /api/v1/auth/2fa/verify`).
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/2fa/enroll",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Секрет TOTP", body = TotpEnrollmentResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 409, description = "2FA уже включена", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/auth/2fa/enroll")]
+pub async fn enroll_totp(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received two-factor enrollment request");
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let enrollment = state.auth_app.enroll_totp(auth_user.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(TotpEnrollmentResponse::from(enrollment)))
+}
+
+/// Подтверждение подключения двухфакторной аутентификации первым кодом.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/2fa/verify",
+    tag = "auth",
+    request_body = TotpVerifyRequest,
+    responses(
+        (status = 204, description = "2FA включена"),
+        (status = 400, description = "Неверный код или 2FA не подключена", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 409, description = "2FA уже включена", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/auth/2fa/verify")]
+pub async fn verify_totp(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    req: web::Json<TotpVerifyRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received two-factor verification request");
+
+    req.validate()?;
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    state
+        .auth_app
+        .verify_totp(auth_user.user_id, &req.code)
+        .await?;
+
+    info!(
+        "Two-factor authentication enabled for user: {}",
+        auth_user.username
+    );
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Обновление пары токенов по refresh токену.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/refresh",
+    tag = "auth",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Новая пара токенов", body = TokenResponse),
+        (status = 401, description = "Refresh токен недействителен или его сеанс закрыт", body = ErrorResponse),
+    ),
+)]
+#[post("/api/v1/auth/refresh")]
+pub async fn refresh_token(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    req: web::Json<RefreshTokenRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received token refresh request");
+
+    req.validate()?;
+
+    let token_dto = state
+        .auth_app
+        .refresh_token(req.refresh_token.clone(), device_info(&http_req))
+        .await?;
+    let response = TokenResponse::from(token_dto);
+
+    info!("Token refreshed successfully");
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Выход: отзыв refresh токена.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/logout",
+    tag = "auth",
+    request_body = LogoutRequest,
+    responses(
+        (status = 204, description = "Токен отозван"),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+    ),
+)]
+#[post("/api/v1/auth/logout")]
+pub async fn logout(
+    state: web::Data<AppState>,
+    req: web::Json<LogoutRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received logout request");
+
+    req.validate()?;
+
+    state.auth_app.logout(req.refresh_token.clone()).await?;
+
+    info!("User logged out successfully");
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Выход со всех устройств: отзыв всех выданных пользователю токенов.
+///
+/// Текущий access токен тоже перестаёт действовать.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/logout-all",
+    tag = "auth",
+    responses(
+        (status = 204, description = "Все токены отозваны"),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/auth/logout-all")]
+pub async fn logout_all(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received logout from all devices request");
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    state.auth_app.logout_all(auth_user.user_id).await?;
+
+    info!("User logged out from all devices");
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Список действующих сеансов входа текущего пользователя.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/sessions",
+    tag = "auth",
+    responses(
+        (status = 200, description = "Сеансы входа", body = SessionListResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/api/v1/auth/sessions")]
+pub async fn list_sessions(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received list sessions request");
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let sessions = state
+        .auth_app
+        .list_sessions(auth_user.user_id, auth_user.session_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SessionListResponse {
+        sessions: sessions.into_iter().map(SessionResponse::from).collect(),
+    }))
+}
+
+/// Закрытие сеанса входа: его refresh токен больше не обновляется, а access
+/// токены сеанса сразу перестают приниматься.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/sessions/{id}",
+    tag = "auth",
+    params(("id" = String, Path, description = "UUID сеанса")),
+    responses(
+        (status = 204, description = "Сеанс закрыт"),
+        (status = 400, description = "Некорректный UUID", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 404, description = "Сеанс не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/api/v1/auth/sessions/{id}")]
+pub async fn revoke_session(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let session_id_str = path.into_inner();
+    info!("Received revoke session request: {}", session_id_str);
+
+    let session_id = Uuid::parse_str(&session_id_str).map_err(|_| {
+        warn!("Invalid UUID format: {}", session_id_str);
+        ApiError::bad_request("Invalid UUID format".to_string())
+    })?;
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    state
+        .auth_app
+        .revoke_session(auth_user.user_id, session_id)
+        .await?;
+
+    info!("Session revoked: {}", session_id);
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Запрос токена сброса пароля.
+///
+/// Ответ не зависит от того, зарегистрирован ли email.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/forgot-password",
+    tag = "auth",
+    request_body = ForgotPasswordRequest,
+    responses(
+        (status = 202, description = "Запрос принят"),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 429, description = "Превышен лимит запросов с IP-адреса", body = ErrorResponse),
+    ),
+)]
+#[post("/api/v1/auth/forgot-password", wrap = "from_fn(auth_rate_limit)")]
+pub async fn forgot_password(
+    state: web::Data<AppState>,
+    req: web::Json<ForgotPasswordRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received password reset request");
+
+    req.validate()?;
+
+    state.auth_app.request_password_reset(&req.email).await?;
+
+    // Ответ не зависит от того, зарегистрирован ли email
+    Ok(HttpResponse::Accepted().finish())
+}
+
+/// Установка нового пароля по токену сброса.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/reset-password",
+    tag = "auth",
+    request_body = ResetPasswordRequest,
+    responses(
+        (status = 204, description = "Пароль изменён"),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 429, description = "Превышен лимит запросов с IP-адреса", body = ErrorResponse),
+    ),
+)]
+#[post("/api/v1/auth/reset-password", wrap = "from_fn(auth_rate_limit)")]
+pub async fn reset_password(
+    state: web::Data<AppState>,
+    req: web::Json<ResetPasswordRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received password reset confirmation");
+
+    req.validate()?;
+
+    let req = req.into_inner();
+    let dto = ResetPasswordDto {
+        token: req.token,
+        new_password: req.new_password,
+    };
+
+    state.auth_app.reset_password(dto).await?;
+
+    info!("Password reset successfully");
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Версия сервера, обслуживаемые версии API и включённые возможности.
+///
+/// Путь не содержит версии API, чтобы клиент любой версии мог его запросить.
+#[utoipa::path(
+    get,
+    path = "/api/info",
+    tag = "meta",
+    responses(
+        (status = 200, description = "Сведения о сервере", body = ServerInfoResponse),
+    ),
+)]
+#[get("/api/info")]
+pub async fn get_server_info(info: web::Data<ServerInfo>) -> impl Responder {
+    HttpResponse::Ok().json(ServerInfoResponse {
+        version: info.version.clone(),
+        api_versions: info.api_versions.clone(),
+        features: info.features.clone(),
+        post_limits: Some(info.post_limits.into()),
+    })
+}
+
+/// Открытые ключи для проверки подписи JWT (JWK Set, RFC 7517).
+///
+/// Содержит активный ключ и ключи, выведенные из оборота, но ещё принимаемые. Пока
+/// токены подписываются общим секретом, набор пуст.
+#[utoipa::path(
+    get,
+    path = "/.well-known/jwks.json",
+    tag = "auth",
+    responses(
+        (status = 200, description = "JWK Set", content_type = "application/json"),
+    ),
+)]
+#[get("/.well-known/jwks.json")]
+pub async fn jwks(auth_service: web::Data<AuthService>) -> impl Responder {
+    HttpResponse::Ok()
+        // Клиенты кэшируют набор ненадолго, чтобы быстро увидеть новый ключ после ротации
+        .insert_header((CACHE_CONTROL, "public, max-age=300"))
+        .json(auth_service.jwks())
+}
+
+/// Публичный профиль пользователя.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{id}",
+    tag = "users",
+    params(("id" = String, Path, description = "UUID пользователя")),
+    responses(
+        (status = 200, description = "Профиль", body = UserProfileResponse),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 404, description = "Пользователь не найден", body = ErrorResponse),
+    ),
+)]
+#[get("/api/v1/users/{id}")]
+pub async fn get_user_profile(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let user_id_str = path.into_inner();
+    info!("Received request to get user profile: {}", user_id_str);
+
+    let user_id = Uuid::parse_str(&user_id_str).map_err(|_| {
+        warn!("Invalid UUID format: {}", user_id_str);
+        ApiError::bad_request("Invalid UUID format".to_string())
+    })?;
+
+    let profile_dto = state.profile_app.get_profile(user_id).await?;
+
+    info!("User profile retrieved successfully: {}", user_id);
+
+    Ok(HttpResponse::Ok().json(UserProfileResponse::from(profile_dto)))
+}
+
+/// Страница постов пользователя.
+///
+/// С валидным токеном автор дополнительно видит свои черновики.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{id}/posts",
+    tag = "users",
+    params(
+        ("id" = String, Path, description = "UUID пользователя"),
+        PaginationQuery,
+        PostFieldsQuery
+    ),
+    responses(
+        (status = 200, description = "Страница постов пользователя", body = Paginated<PostResponse>),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 404, description = "Пользователь не найден", body = ErrorResponse),
+    ),
+)]
+#[get("/api/v1/users/{id}/posts")]
+pub async fn list_user_posts(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<PaginationQuery>,
+    fields: web::Query<PostFieldsQuery>,
+) -> Result<impl Responder, ApiError> {
+    let user_id_str = path.into_inner();
+    info!("Received request to list posts of user: {}", user_id_str);
+
+    query.validate()?;
+    let fields = PostFieldSelection::parse(fields.fields.as_deref())?;
+
+    let user_id = Uuid::parse_str(&user_id_str).map_err(|_| {
+        warn!("Invalid UUID format: {}", user_id_str);
+        ApiError::bad_request("Invalid UUID format".to_string())
+    })?;
+
+    // Маршрут публичный: автор с токеном дополнительно видит свои черновики
+    let viewer = optional_user(&http_req).await.map(|user| user.user_id);
+
+    let page = state
+        .post_app
+        .get_posts_by_author(user_id, query.page, query.page_size, viewer)
+        .await?;
+    let response = Paginated {
+        items: page
+            .posts
+            .into_iter()
+            .map(|post| fields.apply(post))
+            .collect(),
+        total: page.total,
+        page: page.page,
+        page_size: page.page_size,
+        next_cursor: page.next_cursor,
+    };
+
+    info!(
+        "Returning {} posts of user {}",
+        response.items.len(),
+        user_id
+    );
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Подписка на автора; повторная подписка игнорируется.
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{id}/follow",
+    tag = "users",
+    params(("id" = String, Path, description = "UUID автора")),
+    responses(
+        (status = 200, description = "Состояние подписки", body = FollowResponse),
+        (status = 400, description = "Подписка на самого себя", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 404, description = "Пользователь не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/users/{id}/follow")]
+pub async fn follow_user(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let user_id_str = path.into_inner();
+    info!("Received request to follow user: {}", user_id_str);
+
+    let (auth_user, user_id) = action_target(&http_req, &user_id_str)?;
+
+    state
+        .follow_app
+        .follow_user(auth_user.user_id, user_id)
+        .await?;
+
+    info!("User followed successfully: {}", user_id);
+
+    Ok(HttpResponse::Ok().json(FollowResponse { following: true }))
+}
+
+/// Отмена подписки на автора.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{id}/follow",
+    tag = "users",
+    params(("id" = String, Path, description = "UUID автора")),
+    responses(
+        (status = 200, description = "Состояние подписки", body = FollowResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 404, description = "Пользователь не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/api/v1/users/{id}/follow")]
+pub async fn unfollow_user(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let user_id_str = path.into_inner();
+    info!("Received request to unfollow user: {}", user_id_str);
+
+    let (auth_user, user_id) = action_target(&http_req, &user_id_str)?;
+
+    state
+        .follow_app
+        .unfollow_user(auth_user.user_id, user_id)
+        .await?;
+
+    info!("User unfollowed successfully: {}", user_id);
+
+    Ok(HttpResponse::Ok().json(FollowResponse { following: false }))
+}
+
+/// Подписан ли текущий пользователь на автора.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{id}/follow",
+    tag = "users",
+    params(("id" = String, Path, description = "UUID автора")),
+    responses(
+        (status = 200, description = "Состояние подписки", body = FollowResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 404, description = "Пользователь не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/api/v1/users/{id}/follow")]
+pub async fn get_follow_status(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let user_id_str = path.into_inner();
+    info!("Received request to check follow: {}", user_id_str);
+
+    let (auth_user, user_id) = action_target(&http_req, &user_id_str)?;
+
+    let following = state
+        .follow_app
+        .is_following(auth_user.user_id, user_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(FollowResponse { following }))
+}
+
+/// Лента: опубликованные посты авторов из подписок, от новых к старым.
+#[utoipa::path(
+    get,
+    path = "/api/v1/feed",
+    tag = "posts",
+    params(PaginationQuery, PostFieldsQuery),
+    responses(
+        (status = 200, description = "Страница ленты", body = Paginated<PostResponse>),
+        (status = 400, description = "Ошибка валидации параметров", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/api/v1/feed")]
+pub async fn get_feed(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<PaginationQuery>,
+    fields: web::Query<PostFieldsQuery>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to get feed");
+
+    query.validate()?;
+    let fields = PostFieldSelection::parse(fields.fields.as_deref())?;
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let page = state
+        .post_app
+        .get_feed(auth_user.user_id, query.page, query.page_size)
+        .await?;
+    let response = Paginated {
+        items: page
+            .posts
+            .into_iter()
+            .map(|post| fields.apply(post))
+            .collect(),
+        total: page.total,
+        page: page.page,
+        page_size: page.page_size,
+        next_cursor: page.next_cursor,
+    };
+
+    info!("Returning {} feed posts", response.items.len());
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Обновление своего профиля.
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/me",
+    tag = "users",
+    request_body = UpdateProfileRequest,
+    responses(
+        (status = 200, description = "Обновлённый профиль", body = UserProfileResponse),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/api/v1/users/me")]
+pub async fn update_my_profile(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    req: web::Json<UpdateProfileRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to update profile");
+
+    req.validate()?;
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let req = req.into_inner();
+    let dto = UpdateProfileDto {
+        user_id: auth_user.user_id,
+        display_name: req.display_name,
+        bio: req.bio,
+        avatar_url: req.avatar_url,
+    };
+
+    let profile_dto = state.profile_app.update_profile(dto).await?;
+
+    info!(
+        "Profile updated successfully for user: {}",
+        auth_user.username
+    );
+
+    Ok(HttpResponse::Ok().json(UserProfileResponse::from(profile_dto)))
+}
+
+/// Настройки email-дайджеста новых постов.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/digest",
+    tag = "users",
+    responses(
+        (status = 200, description = "Настройки дайджеста", body = DigestSettingsResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/api/v1/users/me/digest")]
+pub async fn get_digest_settings(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to get digest settings");
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let settings = state.digest_app.get_settings(auth_user.user_id).await?;
+
+    Ok(HttpResponse::Ok().json(DigestSettingsResponse::from(settings)))
+}
+
+/// Подписка на email-дайджест, смена его частоты или отписка (`frequency: null`).
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/me/digest",
+    tag = "users",
+    request_body = UpdateDigestSettingsRequest,
+    responses(
+        (status = 200, description = "Новые настройки дайджеста", body = DigestSettingsResponse),
+        (status = 400, description = "Некорректный запрос", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/api/v1/users/me/digest")]
+pub async fn update_digest_settings(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    req: web::Json<UpdateDigestSettingsRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to update digest settings");
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let settings = state
+        .digest_app
+        .update_settings(
+            auth_user.user_id,
+            req.into_inner().frequency.map(Into::into),
+        )
+        .await?;
+
+    info!("Digest settings updated for user: {}", auth_user.username);
+
+    Ok(HttpResponse::Ok().json(DigestSettingsResponse::from(settings)))
+}
+
+/// Создание поста.
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts",
+    tag = "posts",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Ключ идемпотентности")),
+    request_body = CreatePostRequest,
+    responses(
+        (status = 201, description = "Пост создан", body = PostResponse),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Превышено ограничение размера поста", body = ErrorResponse),
+        (status = 409, description = "Конфликт ключа идемпотентности", body = ErrorResponse),
+        (status = 429, description = "Превышена суточная квота постов", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/posts")]
+pub async fn create_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    req: web::Json<CreatePostRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to create post: {}", req.title);
+
+    create_post_in_blog(&http_req, &state, &req, None).await
+}
+
+/// Создаёт пост в блоге `blog_id` или, без него, в общей ленте сервера.
+async fn create_post_in_blog(
+    http_req: &HttpRequest,
+    state: &web::Data<AppState>,
+    req: &CreatePostRequest,
+    blog_id: Option<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    req.validate()?;
+
+    // Извлекаем информацию об аутентифицированном пользователе из extensions
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    info!(
+        "Creating post for user: {} ({})",
+        auth_user.username, auth_user.user_id
+    );
+
+    let attachment_ids = parse_attachment_ids(&req.attachment_ids)?;
+    let category_id = parse_category_id(req.category_id.as_deref().unwrap_or_default())?;
+
+    idempotent(http_req, state, req, async {
+        let dto = CreatePostDto {
+            title: req.title.clone(),
+            content: req.content.clone(),
+            author_id: auth_user.user_id,
+            author_username: auth_user.username.clone(),
+            tags: req.tags.clone(),
+            attachment_ids,
+            category_id,
+            blog_id,
+            summary: req.summary.clone(),
+            status: req.status.into(),
+        };
+
+        let post_dto = state.post_app.create_post(dto).await?;
+        let response = PostResponse::from(post_dto);
+
+        info!("Post created successfully: {}", req.title);
+
+        Ok((StatusCode::CREATED, response))
+    })
+    .await
+}
+
+/// Пакетное создание постов одной транзакцией.
+///
+/// Если хотя бы один пост не прошёл проверку, не создаётся ни один.
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/batch",
+    tag = "posts",
+    params(("Idempotency-Key" = Option<String>, Header, description = "Ключ идемпотентности")),
+    request_body = CreatePostsRequest,
+    responses(
+        (status = 201, description = "Посты созданы", body = CreatePostsResponse),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Превышено ограничение размера поста", body = ErrorResponse),
+        (status = 409, description = "Конфликт ключа идемпотентности", body = ErrorResponse),
+        (status = 429, description = "Превышена суточная квота постов", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/posts/batch")]
+pub async fn create_posts(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    req: web::Json<CreatePostsRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to create {} posts", req.posts.len());
+
+    create_posts_in_blog(&http_req, &state, &req, None).await
+}
+
+/// Создаёт посты одной транзакцией в блоге `blog_id` или, без него, в общей ленте сервера.
+async fn create_posts_in_blog(
+    http_req: &HttpRequest,
+    state: &web::Data<AppState>,
+    req: &CreatePostsRequest,
+    blog_id: Option<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    req.validate()?;
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let attachment_ids = req
+        .posts
+        .iter()
+        .map(|post| parse_attachment_ids(&post.attachment_ids))
+        .collect::<Result<Vec<_>, _>>()?;
+    let category_ids = req
+        .posts
+        .iter()
+        .map(|post| parse_category_id(post.category_id.as_deref().unwrap_or_default()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    idempotent(http_req, state, req, async {
+        let dtos = req
+            .posts
+            .iter()
+            .zip(attachment_ids.into_iter().zip(category_ids))
+            .map(|(post, (attachment_ids, category_id))| CreatePostDto {
+                title: post.title.clone(),
+                content: post.content.clone(),
+                author_id: auth_user.user_id,
+                author_username: auth_user.username.clone(),
+                tags: post.tags.clone(),
+                attachment_ids,
+                category_id,
+                blog_id,
+                summary: post.summary.clone(),
+                status: post.status.into(),
+            })
+            .collect();
+
+        let posts = state.post_app.create_posts(dtos).await?;
+        info!("Created {} posts", posts.len());
+
+        let response = CreatePostsResponse {
+            posts: posts.into_iter().map(PostResponse::from).collect(),
+        };
+        Ok((StatusCode::CREATED, response))
+    })
+    .await
+}
+
+/// Страница видимых постов.
+///
+/// С валидным токеном автор дополнительно видит свои черновики.
+#[utoipa::path(
+    get,
+    path = "/api/v1/posts",
+    tag = "posts",
+    params(ListPostsQuery),
+    responses(
+        (status = 200, description = "Страница постов", body = Paginated<PostResponse>),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+    ),
+)]
+#[get("/api/v1/posts")]
+pub async fn list_posts(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<ListPostsQuery>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to list all posts");
+
+    list_posts_page(&http_req, &state, &query, None).await
+}
+
+/// Страница постов блога `blog` или, без него, всех постов сервера.
+async fn list_posts_page(
+    http_req: &HttpRequest,
+    state: &web::Data<AppState>,
+    query: &ListPostsQuery,
+    blog: Option<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    query.validate()?;
+
+    // Маршрут публичный: автор с токеном дополнительно видит свои черновики
+    let viewer = optional_user(http_req).await.map(|user| user.user_id);
+
+    let sort = PostSort {
+        field: query.sort_by.unwrap_or_default().into(),
+        order: query.order.unwrap_or_default().into(),
+    };
+    let category = parse_category_id(query.category.as_deref().unwrap_or_default())?;
+    let fields = PostFieldSelection::parse(query.fields.as_deref())?;
+
+    let page = match query.cursor.as_deref() {
+        Some(cursor) => {
+            state
+                .post_app
+                .get_posts_after(
+                    Some(cursor),
+                    query.page_size,
+                    query.tag.as_deref(),
+                    category,
+                    blog,
+                    viewer,
+                    sort,
+                    fields.storage_fields(),
+                )
+                .await?
+        }
+        None => {
+            state
+                .post_app
+                .get_posts(
+                    query.page,
+                    query.page_size,
+                    query.tag.as_deref(),
+                    category,
+                    blog,
+                    viewer,
+                    sort,
+                    fields.storage_fields(),
+                )
+                .await?
+        }
+    };
+    let response = Paginated {
+        items: page
+            .posts
+            .into_iter()
+            .map(|post| fields.apply(post))
+            .collect(),
+        total: page.total,
+        page: page.page,
+        page_size: page.page_size,
+        next_cursor: page.next_cursor,
+    };
+
+    info!("Returning {} posts", response.items.len());
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Популярные посты: больше всего лайков за последнее окно (`trending_window_hours`).
+///
+/// В список попадают только опубликованные посты.
+#[utoipa::path(
+    get,
+    path = "/api/v1/posts/trending",
+    tag = "posts",
+    params(TrendingPostsQuery, PostFieldsQuery),
+    responses(
+        (status = 200, description = "Популярные посты", body = Vec<PostResponse>),
+        (status = 400, description = "Ошибка валидации", body = ErrorResponse),
+    ),
+)]
+#[get("/api/v1/posts/trending")]
+pub async fn list_trending_posts(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<TrendingPostsQuery>,
+    fields: web::Query<PostFieldsQuery>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to list trending posts");
+
+    query.validate()?;
+    let fields = PostFieldSelection::parse(fields.fields.as_deref())?;
+
+    let viewer = optional_user(&http_req).await.map(|user| user.user_id);
+    let posts = state
+        .post_app
+        .get_trending_posts(query.limit, viewer)
+        .await?;
+    let response: Vec<_> = posts.into_iter().map(|post| fields.apply(post)).collect();
+
+    info!("Returning {} trending posts", response.len());
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Интервал комментариев-пингов, не дающих прокси закрыть простаивающее SSE-соединение
+const POST_EVENTS_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Живая лента постов в формате Server-Sent Events.
+///
+/// Каждое событие содержит `PostEventResponse` в поле `data`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/posts/events",
+    tag = "posts",
+    responses(
+        (status = 200, description = "Поток событий `created`, `updated` и `deleted`", content_type = "text/event-stream", body = PostEventResponse),
+    ),
+)]
+#[get("/api/v1/posts/events")]
+pub async fn post_events(state: web::Data<AppState>) -> impl Responder {
+    info!("Client subscribed to post events");
+
+    let events = BroadcastStream::new(state.post_app.subscribe()).filter_map(|event| match event {
+        Ok(event) => Some(sse_event(PostEventResponse::from(event))),
+        Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+            warn!("Post events subscriber lagged, skipped {} events", skipped);
+            None
+        }
+    });
+    let keep_alive = IntervalStream::new(tokio::time::interval(POST_EVENTS_KEEP_ALIVE))
+        .map(|_| web::Bytes::from_static(b": keep-alive\n\n"));
+    let body = events.merge(keep_alive).map(Ok::<_, actix_web::Error>);
+
+    HttpResponse::Ok()
+        .insert_header((CONTENT_TYPE, "text/event-stream"))
+        .insert_header((CACHE_CONTROL, "no-cache"))
+        // Сжатие копило бы события в буфере кодировщика, поэтому поток не сжимается
+        .insert_header(ContentEncoding::Identity)
+        .streaming(body)
+}
+
+/// Кодирует событие ленты в кадр SSE.
+fn sse_event(event: PostEventResponse) -> web::Bytes {
+    let data = serde_json::to_string(&event).expect("PostEventResponse is always serializable");
+    web::Bytes::from(format!("event: {}\ndata: {}\n\n", event.event_name(), data))
+}
+
+/// Получение поста.
+#[utoipa::path(
+    get,
+    path = "/api/v1/posts/{id}",
+    tag = "posts",
+    params(("id" = String, Path, description = "UUID поста"), PostFieldsQuery),
+    responses(
+        (status = 200, description = "Пост", body = PostResponse),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 404, description = "Пост не найден", body = ErrorResponse),
+    ),
+)]
+#[get("/api/v1/posts/{id}")]
+pub async fn get_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    fields: web::Query<PostFieldsQuery>,
+) -> Result<impl Responder, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to get post: {}", post_id_str);
+
+    let fields = PostFieldSelection::parse(fields.fields.as_deref())?;
+
+    let post_id = Uuid::parse_str(&post_id_str).map_err(|_| {
+        warn!("Invalid UUID format: {}", post_id_str);
+        ApiError::bad_request("Invalid UUID format".to_string())
+    })?;
+
+    let viewer = optional_user(&http_req).await.map(|user| user.user_id);
+    let post_dto = state.post_app.get_post_by_id(post_id, viewer).await?;
+    let response = fields.apply(post_dto);
+
+    info!("Post retrieved successfully: {}", post_id);
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Обновление поста (автор или администратор).
+#[utoipa::path(
+    put,
+    path = "/api/v1/posts/{id}",
+    tag = "posts",
+    params(("id" = String, Path, description = "UUID поста"), ("Idempotency-Key" = Option<String>, Header, description = "Ключ идемпотентности")),
+    request_body = UpdatePostRequest,
+    responses(
+        (status = 200, description = "Обновлённый пост", body = PostResponse),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Пост не найден", body = ErrorResponse),
+        (status = 409, description = "Конфликт ключа идемпотентности", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/api/v1/posts/{id}")]
+pub async fn update_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: web::Json<UpdatePostRequest>,
+) -> Result<impl Responder, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to update post: {}", post_id_str);
+
+    req.validate()?;
+
+    // Извлекаем информацию об аутентифицированном пользователе
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let post_id = Uuid::parse_str(&post_id_str).map_err(|_| {
+        warn!("Invalid UUID format: {}", post_id_str);
+        ApiError::bad_request("Invalid UUID format".to_string())
+    })?;
+    let attachment_ids = req
+        .attachment_ids
+        .as_deref()
+        .map(parse_attachment_ids)
+        .transpose()?;
+    let category_id = req
+        .category_id
+        .as_deref()
+        .map(parse_category_id)
+        .transpose()?;
+
+    idempotent(&http_req, &state, &*req, async {
+        let dto = UpdatePostDto {
+            uuid: post_id,
+            title: req.title.clone(),
+            content: req.content.clone(),
+            tags: req.tags.clone(),
+            attachment_ids,
+            category_id,
+            summary: req.summary.clone().map(Some),
+        };
+
+        // Изменять пост может автор или администратор
+        let post_dto = state
+            .post_app
+            .update_post(dto, auth_user.user_id, auth_user.role)
+            .await?;
+        let response = PostResponse::from(post_dto);
+
+        info!("Post updated successfully: {}", post_id);
+
+        Ok((StatusCode::OK, response))
+    })
+    .await
+}
+
+/// Перенос поста в корзину (автор или администратор).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/posts/{id}",
+    tag = "posts",
+    params(("id" = String, Path, description = "UUID поста")),
+    responses(
+        (status = 204, description = "Пост перемещён в корзину"),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Пост не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/api/v1/posts/{id}")]
+pub async fn delete_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to delete post: {}", post_id_str);
+
+    // Извлекаем информацию об аутентифицированном пользователе
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let post_id = Uuid::parse_str(&post_id_str).map_err(|_| {
+        warn!("Invalid UUID format: {}", post_id_str);
+        ApiError::bad_request("Invalid UUID format".to_string())
+    })?;
+
+    // Удалять пост может автор или администратор
+    state
+        .post_app
+        .delete_post(post_id, auth_user.user_id, auth_user.role)
+        .await?;
+
+    info!("Post deleted successfully: {}", post_id);
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Пакетный перенос постов в корзину (автор или администратор).
+///
+/// Если хотя бы один пост не найден или недоступен, не удаляется ни один.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/posts/batch",
+    tag = "posts",
+    request_body = DeletePostsRequest,
+    responses(
+        (status = 204, description = "Посты перемещены в корзину"),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Пост не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/api/v1/posts/batch")]
+pub async fn delete_posts(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    req: web::Json<DeletePostsRequest>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to delete {} posts", req.post_ids.len());
+
+    req.validate()?;
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let post_ids = req
+        .post_ids
+        .iter()
+        .map(|id| Uuid::parse_str(id))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ApiError::bad_request("Invalid UUID format".to_string()))?;
+
+    let deleted = state
+        .post_app
+        .delete_posts(post_ids, auth_user.user_id, auth_user.role)
+        .await?;
+
+    info!("Posts deleted successfully: {}", deleted.len());
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Восстановление поста из корзины (автор или администратор).
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{id}/restore",
+    tag = "posts",
+    params(("id" = String, Path, description = "UUID поста")),
+    responses(
+        (status = 200, description = "Восстановленный пост", body = PostResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 404, description = "Пост не найден в корзине", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/posts/{id}/restore")]
+pub async fn restore_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to restore post: {}", post_id_str);
+
+    let (auth_user, post_id) = action_target(&http_req, &post_id_str)?;
+
+    let post_dto = state
+        .post_app
+        .restore_post(post_id, auth_user.user_id, auth_user.role)
+        .await?;
+
+    info!("Post restored successfully: {}", post_id);
+
+    Ok(HttpResponse::Ok().json(PostResponse::from(post_dto)))
+}
+
+/// Окончательное удаление поста из корзины (автор или администратор).
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{id}/purge",
+    tag = "posts",
+    params(("id" = String, Path, description = "UUID поста")),
+    responses(
+        (status = 204, description = "Пост удалён окончательно"),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 404, description = "Пост не найден в корзине", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/posts/{id}/purge")]
+pub async fn purge_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to purge post: {}", post_id_str);
+
+    let (auth_user, post_id) = action_target(&http_req, &post_id_str)?;
+
+    state
+        .post_app
+        .purge_post(post_id, auth_user.user_id, auth_user.role)
+        .await?;
+
+    info!("Post purged successfully: {}", post_id);
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Публикация черновика.
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{id}/publish",
+    tag = "posts",
+    params(("id" = String, Path, description = "UUID поста")),
+    responses(
+        (status = 200, description = "Опубликованный пост", body = PostResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Пост не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/posts/{id}/publish")]
+pub async fn publish_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to publish post: {}", post_id_str);
+
+    let (auth_user, post_id) = action_target(&http_req, &post_id_str)?;
+
+    let post_dto = state
+        .post_app
+        .publish_post(post_id, auth_user.user_id, auth_user.role)
+        .await?;
+
+    info!("Post published successfully: {}", post_id);
+
+    Ok(HttpResponse::Ok().json(PostResponse::from(post_dto)))
+}
+
+/// Возврат поста в черновики.
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{id}/unpublish",
+    tag = "posts",
+    params(("id" = String, Path, description = "UUID поста")),
+    responses(
+        (status = 200, description = "Пост в черновиках", body = PostResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Пост не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/posts/{id}/unpublish")]
+pub async fn unpublish_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to unpublish post: {}", post_id_str);
+
+    let (auth_user, post_id) = action_target(&http_req, &post_id_str)?;
+
+    let post_dto = state
+        .post_app
+        .unpublish_post(post_id, auth_user.user_id, auth_user.role)
+        .await?;
+
+    info!("Post moved to drafts successfully: {}", post_id);
+
+    Ok(HttpResponse::Ok().json(PostResponse::from(post_dto)))
+}
+
+/// Лайк поста; повторный лайк игнорируется.
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{id}/like",
+    tag = "posts",
+    params(("id" = String, Path, description = "UUID поста")),
+    responses(
+        (status = 200, description = "Пост с обновлённым счётчиком", body = PostResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 404, description = "Пост не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/posts/{id}/like")]
+pub async fn like_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to like post: {}", post_id_str);
+
+    let (auth_user, post_id) = action_target(&http_req, &post_id_str)?;
+
+    let post_dto = state.post_app.like_post(post_id, auth_user.user_id).await?;
+
+    info!("Post liked successfully: {}", post_id);
+
+    Ok(HttpResponse::Ok().json(PostResponse::from(post_dto)))
+}
+
+/// Снятие лайка.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/posts/{id}/like",
+    tag = "posts",
+    params(("id" = String, Path, description = "UUID поста")),
+    responses(
+        (status = 200, description = "Пост с обновлённым счётчиком", body = PostResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 404, description = "Пост не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/api/v1/posts/{id}/like")]
+pub async fn unlike_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to unlike post: {}", post_id_str);
+
+    let (auth_user, post_id) = action_target(&http_req, &post_id_str)?;
+
+    let post_dto = state
+        .post_app
+        .unlike_post(post_id, auth_user.user_id)
+        .await?;
+
+    info!("Post unliked successfully: {}", post_id);
+
+    Ok(HttpResponse::Ok().json(PostResponse::from(post_dto)))
+}
+
+/// Загрузка файла (multipart/form-data, поле `file`).
+///
+/// Возвращённый `id` передаётся в `attachment_ids` при создании или обновлении поста.
+#[utoipa::path(
+    post,
+    path = "/api/v1/uploads",
+    tag = "uploads",
+    request_body(content = String, content_type = "multipart/form-data", description = "Файл в поле `file`"),
+    responses(
+        (status = 201, description = "Файл загружен", body = UploadResponse),
+        (status = 400, description = "Недопустимый тип или размер файла", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/uploads")]
+pub async fn upload_file(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    mut payload: Multipart,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to upload file");
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let max_size = state.upload_app.max_size_bytes();
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| ApiError::bad_request(e.to_string()))?;
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let file_name = field
+            .content_disposition()
+            .and_then(|disposition| disposition.get_filename())
+            .unwrap_or_default()
+            .to_string();
+        let content_type = field
+            .content_type()
+            .map(|mime| mime.essence_str().to_string())
+            .unwrap_or_default();
+
+        // Читаем не больше лимита плюс один байт: этого достаточно, чтобы отклонить большой файл
+        let mut data = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| ApiError::bad_request(e.to_string()))?;
+            data.extend_from_slice(&chunk);
+            if data.len() > max_size {
+                break;
+            }
+        }
+
+        let attachment_dto = state
+            .upload_app
+            .upload(auth_user.user_id, &file_name, &content_type, data)
+            .await?;
+
+        info!("File uploaded successfully: {}", attachment_dto.id);
+
+        return Ok(HttpResponse::Created().json(UploadResponse::from(attachment_dto)));
+    }
+
+    Err(ApiError::bad_request(
+        "Multipart field 'file' is required".to_string(),
+    ))
+}
+
+/// Журнал действий пользователей (только администратор).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/audit",
+    tag = "admin",
+    params(AuditLogQuery),
+    responses(
+        (status = 200, description = "Страница журнала от новых записей к старым", body = Paginated<AuditEntryResponse>),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/api/v1/admin/audit")]
+pub async fn list_audit_log(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<AuditLogQuery>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to list audit log");
+
+    query.validate()?;
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let user_id = query
+        .user_id
+        .as_deref()
+        .map(|id| {
+            Uuid::parse_str(id).map_err(|_| {
+                warn!("Invalid UUID format: {}", id);
+                ApiError::bad_request("Invalid UUID format".to_string())
+            })
+        })
+        .transpose()?;
+    let filter = AuditFilter {
+        user_id,
+        from: query
+            .from
+            .as_deref()
+            .map(|value| parse_timestamp("from", value))
+            .transpose()?,
+        to: query
+            .to
+            .as_deref()
+            .map(|value| parse_timestamp("to", value))
+            .transpose()?,
+    };
+
+    let page = state
+        .audit_app
+        .list_entries(filter, query.page, query.page_size, auth_user.role)
+        .await?;
+    let response = Paginated {
+        items: page
+            .entries
+            .into_iter()
+            .map(AuditEntryResponse::from)
+            .collect(),
+        total: page.total,
+        page: page.page,
+        page_size: page.page_size,
+        next_cursor: None,
+    };
+
+    info!("Returning {} audit entries", response.items.len());
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Список пользователей в порядке регистрации (только администратор).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/users",
+    tag = "admin",
+    params(PaginationQuery),
+    responses(
+        (status = 200, description = "Страница пользователей", body = Paginated<AdminUserResponse>),
+        (status = 400, description = "Ошибка валидации параметров", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/api/v1/admin/users")]
+pub async fn list_users(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<PaginationQuery>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to list users");
+
+    query.validate()?;
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let page = state
+        .admin_app
+        .list_users(query.page, query.page_size, auth_user.role)
+        .await?;
+    let response = Paginated {
+        items: page
+            .users
+            .into_iter()
+            .map(AdminUserResponse::from)
+            .collect(),
+        total: page.total,
+        page: page.page,
+        page_size: page.page_size,
+        next_cursor: None,
+    };
+
+    info!("Returning {} users", response.items.len());
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Отключение пользователя (только администратор).
+///
+/// Отключённый пользователь не может войти; выданные ему токены и сеансы отзываются.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/users/{id}/disable",
+    tag = "admin",
+    params(("id" = String, Path, description = "UUID пользователя")),
+    responses(
+        (status = 204, description = "Пользователь отключён"),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Пользователь не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/admin/users/{id}/disable")]
+pub async fn disable_user(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let user_id_str = path.into_inner();
+    info!("Received request to disable user: {}", user_id_str);
+
+    let (auth_user, user_id) = action_target(&http_req, &user_id_str)?;
+
+    state
+        .admin_app
+        .set_user_disabled(user_id, true, auth_user.user_id, auth_user.role)
+        .await?;
+
+    info!("User disabled successfully: {}", user_id);
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Включение отключённого пользователя (только администратор).
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/users/{id}/enable",
+    tag = "admin",
+    params(("id" = String, Path, description = "UUID пользователя")),
+    responses(
+        (status = 204, description = "Пользователь включён"),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Пользователь не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/admin/users/{id}/enable")]
+pub async fn enable_user(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let user_id_str = path.into_inner();
+    info!("Received request to enable user: {}", user_id_str);
+
+    let (auth_user, user_id) = action_target(&http_req, &user_id_str)?;
+
+    state
+        .admin_app
+        .set_user_disabled(user_id, false, auth_user.user_id, auth_user.role)
+        .await?;
+
+    info!("User enabled successfully: {}", user_id);
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Окончательное удаление любого поста, в том числе черновика или поста из корзины
+/// (только администратор).
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/posts/{id}",
+    tag = "admin",
+    params(("id" = String, Path, description = "UUID поста")),
+    responses(
+        (status = 204, description = "Пост удалён окончательно"),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Пост не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/api/v1/admin/posts/{id}")]
+pub async fn force_delete_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to force delete post: {}", post_id_str);
 
-use crate::application::auth::AuthApplication;
-use crate::application::dto::auth::{LoginDto, RegisterDto, TokenDto};
-use crate::application::dto::post::{CreatePostDto, PostDto, UpdatePostDto};
-use crate::application::post::PostApplication;
-use crate::data::pgrepo::PgUserRepository;
-use crate::presentation::error::ApiError;
-use crate::presentation::http::middleware::AuthenticatedUser;
+    let (auth_user, post_id) = action_target(&http_req, &post_id_str)?;
 
-// Структура для хранения зависимостей приложения
-pub struct AppState {
-    pub auth_app: Arc<AuthApplication<PgUserRepository>>,
-    pub post_app: Arc<PostApplication<PgUserRepository>>,
-}
+    state
+        .post_app
+        .force_delete_post(post_id, auth_user.user_id, auth_user.role)
+        .await?;
 
-impl From<TokenDto> for TokenResponse {
-    fn from(dto: TokenDto) -> Self {
-        Self {
-            access_token: dto.access_token,
-            refresh_token: dto.refresh_token,
-            expires_in: dto.expires_in,
-        }
-    }
-}
+    info!("Post force deleted successfully: {}", post_id);
 
-impl From<PostDto> for PostResponse {
-    fn from(dto: PostDto) -> Self {
-        Self {
-            uuid: dto.uuid.to_string(),
-            title: dto.title,
-            content: dto.content,
-            author_id: dto.author_id.to_string(),
-            created_at: dto.created_at.to_rfc3339(),
-            updated_at: dto.updated_at.to_rfc3339(),
-        }
-    }
+    Ok(HttpResponse::NoContent().finish())
 }
 
-#[post("/api/v1/auth/register")]
-pub async fn register(
+/// Сводные показатели системы: пользователи, посты и новые регистрации
+/// (только администратор).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/stats",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Показатели системы", body = SystemStatsResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/api/v1/admin/stats")]
+pub async fn get_system_stats(
+    http_req: HttpRequest,
     state: web::Data<AppState>,
-    req: web::Json<RegisterRequest>,
 ) -> Result<impl Responder, ApiError> {
-    info!(
-        "Received registration request for username: {}",
-        req.username
-    );
+    info!("Received request for system stats");
 
-    let dto = RegisterDto {
-        username: req.username.clone(),
-        password: req.password.clone(),
-        email: req.email.clone(),
-    };
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let stats = state.admin_app.get_system_stats(auth_user.role).await?;
+
+    Ok(HttpResponse::Ok().json(SystemStatsResponse::from(stats)))
+}
 
-    let user = state.auth_app.create_user(dto).await?;
+/// Включено ли логирование тел запросов и ответов (только администратор).
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/debug/body-logging",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Состояние логирования тел", body = BodyLoggingSettings),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/api/v1/admin/debug/body-logging")]
+pub async fn get_body_logging(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request for body logging settings");
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
 
-    info!("User registered successfully: {}", user.username);
+    let enabled = state.admin_app.body_logging_enabled(auth_user.role)?;
 
-    Ok(HttpResponse::Created().json(serde_json::json!({
-        "id": user.id.to_string(),
-        "username": user.username,
-        "email": user.email,
-        "created_at": user.created_at.to_rfc3339()
-    })))
+    Ok(HttpResponse::Ok().json(BodyLoggingSettings { enabled }))
 }
 
-#[post("/api/v1/auth/login")]
-pub async fn login(
+/// Включает или выключает логирование тел запросов и ответов REST и gRPC
+/// без перезапуска (только администратор).
+///
+/// Пароли, токены и секреты в лог не попадают, но тела содержат данные пользователей,
+/// поэтому логирование стоит выключать сразу после отладки.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/debug/body-logging",
+    tag = "admin",
+    request_body = BodyLoggingSettings,
+    responses(
+        (status = 200, description = "Новое состояние логирования тел", body = BodyLoggingSettings),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/api/v1/admin/debug/body-logging")]
+pub async fn set_body_logging(
+    http_req: HttpRequest,
     state: web::Data<AppState>,
-    req: web::Json<LoginRequest>,
+    req: web::Json<BodyLoggingSettings>,
 ) -> Result<impl Responder, ApiError> {
-    info!("Received login request for username: {}", req.username);
+    info!("Received request to change body logging settings");
 
-    let dto = LoginDto {
-        username: req.username.clone(),
-        password: req.password.clone(),
-    };
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
 
-    let token_dto = state.auth_app.login(dto).await?;
-    let response = TokenResponse::from(token_dto);
+    state
+        .admin_app
+        .set_body_logging(req.enabled, auth_user.user_id, auth_user.role)?;
+
+    Ok(HttpResponse::Ok().json(BodyLoggingSettings {
+        enabled: req.enabled,
+    }))
+}
 
-    info!("User logged in successfully: {}", req.username);
+/// Все категории постов; дерево собирается по `parent_id`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/categories",
+    tag = "categories",
+    responses(
+        (status = 200, description = "Категории постов", body = CategoryListResponse),
+    ),
+)]
+#[get("/api/v1/categories")]
+pub async fn list_categories(state: web::Data<AppState>) -> Result<impl Responder, ApiError> {
+    info!("Received request to list categories");
 
-    Ok(HttpResponse::Ok().json(response))
+    let categories = state.category_app.list_categories().await?;
+
+    Ok(HttpResponse::Ok().json(CategoryListResponse {
+        categories: categories.into_iter().map(CategoryResponse::from).collect(),
+    }))
 }
 
-#[post("/api/v1/auth/refresh")]
-pub async fn refresh_token(
+/// Новая категория постов (только администратор).
+///
+/// С `parent_id` создаётся подкатегория существующей категории.
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/categories",
+    tag = "categories",
+    request_body = CreateCategoryRequest,
+    responses(
+        (status = 201, description = "Категория создана", body = CategoryResponse),
+        (status = 400, description = "Ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Родительская категория не найдена", body = ErrorResponse),
+        (status = 409, description = "Категория с таким именем уже есть", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/admin/categories")]
+pub async fn create_category(
+    http_req: HttpRequest,
     state: web::Data<AppState>,
-    req: web::Json<RefreshTokenRequest>,
+    req: web::Json<CreateCategoryRequest>,
 ) -> Result<impl Responder, ApiError> {
-    info!("Received token refresh request");
+    info!("Received request to create category: {}", req.name);
 
-    let token_dto = state
-        .auth_app
-        .refresh_token(req.refresh_token.clone())
+    req.validate()?;
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+    let req = req.into_inner();
+    let parent_id = parse_category_id(req.parent_id.as_deref().unwrap_or_default())?;
+
+    let category = state
+        .category_app
+        .create_category(
+            CreateCategoryDto {
+                name: req.name,
+                parent_id,
+            },
+            auth_user.role,
+        )
         .await?;
-    let response = TokenResponse::from(token_dto);
 
-    info!("Token refreshed successfully");
+    info!("Category created successfully: {}", category.id);
 
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::Created().json(CategoryResponse::from(category)))
 }
 
-#[post("/api/v1/posts")]
-pub async fn create_post(
+/// Все блоги сервера, упорядоченные по адресу.
+#[utoipa::path(
+    get,
+    path = "/api/v1/blogs",
+    tag = "blogs",
+    responses(
+        (status = 200, description = "Блоги сервера", body = BlogListResponse),
+    ),
+)]
+#[get("/api/v1/blogs")]
+pub async fn list_blogs(state: web::Data<AppState>) -> Result<impl Responder, ApiError> {
+    info!("Received request to list blogs");
+
+    let blogs = state.blog_app.list_blogs().await?;
+
+    Ok(HttpResponse::Ok().json(BlogListResponse {
+        blogs: blogs.into_iter().map(BlogResponse::from).collect(),
+    }))
+}
+
+/// Новый блог; его владельцем становится текущий пользователь.
+#[utoipa::path(
+    post,
+    path = "/api/v1/blogs",
+    tag = "blogs",
+    request_body = CreateBlogRequest,
+    responses(
+        (status = 201, description = "Блог создан", body = BlogResponse),
+        (status = 400, description = "Ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 409, description = "Блог с таким адресом уже есть", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/blogs")]
+pub async fn create_blog(
     http_req: HttpRequest,
     state: web::Data<AppState>,
-    req: web::Json<CreatePostRequest>,
+    req: web::Json<CreateBlogRequest>,
 ) -> Result<impl Responder, ApiError> {
-    info!("Received request to create post: {}", req.title);
+    info!("Received request to create blog: {}", req.slug);
+
+    req.validate()?;
 
-    // Извлекаем информацию об аутентифицированном пользователе из extensions
     let auth_user = http_req
         .extensions()
         .get::<AuthenticatedUser>()
@@ -129,85 +2992,311 @@ pub async fn create_post(
             warn!("AuthenticatedUser not found in request extensions");
             ApiError::unauthorized("Authentication required".to_string())
         })?;
+    let req = req.into_inner();
 
-    info!(
-        "Creating post for user: {} ({})",
-        auth_user.username, auth_user.user_id
-    );
+    let blog = state
+        .blog_app
+        .create_blog(CreateBlogDto {
+            slug: req.slug,
+            name: req.name,
+            owner_id: auth_user.user_id,
+        })
+        .await?;
 
-    let dto = CreatePostDto {
-        title: req.title.clone(),
-        content: req.content.clone(),
-        author_id: auth_user.user_id,
-    };
+    info!("Blog created successfully: {}", blog.slug);
+
+    Ok(HttpResponse::Created().json(BlogResponse::from(blog)))
+}
 
-    let post_dto = state.post_app.create_post(dto).await?;
-    let response = PostResponse::from(post_dto);
+/// Блог по адресу.
+#[utoipa::path(
+    get,
+    path = "/api/v1/blogs/{slug}",
+    tag = "blogs",
+    params(("slug" = String, Path, description = "Адрес блога")),
+    responses(
+        (status = 200, description = "Блог", body = BlogResponse),
+        (status = 404, description = "Блог не найден", body = ErrorResponse),
+    ),
+)]
+#[get("/api/v1/blogs/{slug}")]
+pub async fn get_blog(
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let slug = path.into_inner();
+    info!("Received request to get blog: {}", slug);
 
-    info!("Post created successfully: {}", req.title);
+    let blog = state.blog_app.get_blog(&slug).await?;
 
-    Ok(HttpResponse::Created().json(response))
+    Ok(HttpResponse::Ok().json(BlogResponse::from(blog)))
 }
 
-/// Запрос на получение списка постов с пагинацией.
-#[derive(Debug, Deserialize)]
-pub struct PaginationQuery {
-    /// Количество постов на странице
-    pub page_size: u32,
-    /// Номер страницы (начиная с 0)
-    pub page: u32,
+/// Изменение названия блога (владелец или администратор).
+#[utoipa::path(
+    put,
+    path = "/api/v1/blogs/{slug}",
+    tag = "blogs",
+    params(("slug" = String, Path, description = "Адрес блога")),
+    request_body = UpdateBlogRequest,
+    responses(
+        (status = 200, description = "Блог изменён", body = BlogResponse),
+        (status = 400, description = "Ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Блог не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/api/v1/blogs/{slug}")]
+pub async fn update_blog(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: web::Json<UpdateBlogRequest>,
+) -> Result<impl Responder, ApiError> {
+    let slug = path.into_inner();
+    info!("Received request to update blog: {}", slug);
+
+    req.validate()?;
+
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    let blog = state
+        .blog_app
+        .update_blog(&slug, &req.name, auth_user.user_id, auth_user.role)
+        .await?;
+
+    info!("Blog updated successfully: {}", slug);
+
+    Ok(HttpResponse::Ok().json(BlogResponse::from(blog)))
 }
 
-#[get("/api/v1/posts")]
-pub async fn list_posts(
+/// Удаление блога (владелец или администратор).
+///
+/// Посты блога не удаляются, а остаются в общей ленте сервера.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/blogs/{slug}",
+    tag = "blogs",
+    params(("slug" = String, Path, description = "Адрес блога")),
+    responses(
+        (status = 204, description = "Блог удалён"),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Блог не найден", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/api/v1/blogs/{slug}")]
+pub async fn delete_blog(
+    http_req: HttpRequest,
     state: web::Data<AppState>,
-    query: web::Query<PaginationQuery>,
+    path: web::Path<String>,
 ) -> Result<impl Responder, ApiError> {
-    info!("Received request to list all posts");
+    let slug = path.into_inner();
+    info!("Received request to delete blog: {}", slug);
 
-    let posts = state
-        .post_app
-        .get_posts(query.page, query.page_size)
+    let auth_user = http_req
+        .extensions()
+        .get::<AuthenticatedUser>()
+        .cloned()
+        .ok_or_else(|| {
+            warn!("AuthenticatedUser not found in request extensions");
+            ApiError::unauthorized("Authentication required".to_string())
+        })?;
+
+    state
+        .blog_app
+        .delete_blog(&slug, auth_user.user_id, auth_user.role)
         .await?;
-    let response: Vec<PostResponse> = posts.into_iter().map(PostResponse::from).collect();
 
-    info!("Returning {} posts", response.len());
+    info!("Blog deleted successfully: {}", slug);
 
-    Ok(HttpResponse::Ok().json(response))
+    Ok(HttpResponse::NoContent().finish())
 }
 
-#[get("/api/v1/posts/{id}")]
-pub async fn get_post(
+/// Страница видимых постов блога; параметры те же, что у `GET /api/v1/posts`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/blogs/{slug}/posts",
+    tag = "blogs",
+    params(("slug" = String, Path, description = "Адрес блога"), ListPostsQuery),
+    responses(
+        (status = 200, description = "Страница постов блога", body = Paginated<PostResponse>),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 404, description = "Блог не найден", body = ErrorResponse),
+    ),
+)]
+#[get("/api/v1/blogs/{slug}/posts")]
+pub async fn list_blog_posts(
+    http_req: HttpRequest,
     state: web::Data<AppState>,
     path: web::Path<String>,
+    query: web::Query<ListPostsQuery>,
 ) -> Result<impl Responder, ApiError> {
-    let post_id_str = path.into_inner();
-    info!("Received request to get post: {}", post_id_str);
+    let slug = path.into_inner();
+    info!("Received request to list posts of blog: {}", slug);
 
-    let post_id = Uuid::parse_str(&post_id_str).map_err(|_| {
-        warn!("Invalid UUID format: {}", post_id_str);
-        ApiError::bad_request("Invalid UUID format".to_string())
-    })?;
+    let blog = state.blog_app.get_blog(&slug).await?;
+    list_posts_page(&http_req, &state, &query, Some(blog.id)).await
+}
 
-    let post_dto = state.post_app.get_post_by_id(post_id).await?;
-    let response = PostResponse::from(post_dto);
+/// Создание поста в блоге (только владелец блога).
+#[utoipa::path(
+    post,
+    path = "/api/v1/blogs/{slug}/posts",
+    tag = "blogs",
+    params(
+        ("slug" = String, Path, description = "Адрес блога"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Ключ идемпотентности"),
+    ),
+    request_body = CreatePostRequest,
+    responses(
+        (status = 201, description = "Пост создан", body = PostResponse),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Пользователь не владелец блога", body = ErrorResponse),
+        (status = 404, description = "Блог не найден", body = ErrorResponse),
+        (status = 409, description = "Конфликт ключа идемпотентности", body = ErrorResponse),
+        (status = 429, description = "Превышена суточная квота постов", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/blogs/{slug}/posts")]
+pub async fn create_blog_post(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: web::Json<CreatePostRequest>,
+) -> Result<impl Responder, ApiError> {
+    let slug = path.into_inner();
+    info!(
+        "Received request to create post in blog {}: {}",
+        slug, req.title
+    );
 
-    info!("Post retrieved successfully: {}", post_id);
+    let blog = state.blog_app.get_blog(&slug).await?;
+    create_post_in_blog(&http_req, &state, &req, Some(blog.id)).await
+}
 
-    Ok(HttpResponse::Ok().json(response))
+/// Пакетное создание постов в блоге одной транзакцией (только владелец блога).
+#[utoipa::path(
+    post,
+    path = "/api/v1/blogs/{slug}/posts/batch",
+    tag = "blogs",
+    params(
+        ("slug" = String, Path, description = "Адрес блога"),
+        ("Idempotency-Key" = Option<String>, Header, description = "Ключ идемпотентности"),
+    ),
+    request_body = CreatePostsRequest,
+    responses(
+        (status = 201, description = "Посты созданы", body = CreatePostsResponse),
+        (status = 400, description = "Некорректный запрос или ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Пользователь не владелец блога", body = ErrorResponse),
+        (status = 404, description = "Блог не найден", body = ErrorResponse),
+        (status = 409, description = "Конфликт ключа идемпотентности", body = ErrorResponse),
+        (status = 429, description = "Превышена суточная квота постов", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/blogs/{slug}/posts/batch")]
+pub async fn create_blog_posts(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    path: web::Path<String>,
+    req: web::Json<CreatePostsRequest>,
+) -> Result<impl Responder, ApiError> {
+    let slug = path.into_inner();
+    info!(
+        "Received request to create {} posts in blog {}",
+        req.posts.len(),
+        slug
+    );
+
+    let blog = state.blog_app.get_blog(&slug).await?;
+    create_posts_in_blog(&http_req, &state, &req, Some(blog.id)).await
 }
 
-#[put("/api/v1/posts/{id}")]
-pub async fn update_post(
+/// Жалоба на пост; на один пост пользователь жалуется один раз.
+#[utoipa::path(
+    post,
+    path = "/api/v1/posts/{id}/report",
+    tag = "moderation",
+    params(("id" = String, Path, description = "UUID поста")),
+    request_body = ReportPostRequest,
+    responses(
+        (status = 201, description = "Жалоба принята", body = ReportResponse),
+        (status = 400, description = "Ошибка валидации", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Жалоба на собственный пост", body = ErrorResponse),
+        (status = 404, description = "Пост не найден", body = ErrorResponse),
+        (status = 409, description = "Пользователь уже жаловался на пост", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/posts/{id}/report")]
+pub async fn report_post(
     http_req: HttpRequest,
     state: web::Data<AppState>,
     path: web::Path<String>,
-    req: web::Json<UpdatePostRequest>,
+    req: web::Json<ReportPostRequest>,
 ) -> Result<impl Responder, ApiError> {
     let post_id_str = path.into_inner();
-    info!("Received request to update post: {}", post_id_str);
+    info!("Received request to report post: {}", post_id_str);
+
+    req.validate()?;
+
+    let (auth_user, post_id) = action_target(&http_req, &post_id_str)?;
+    let req = req.into_inner();
+
+    let report = state
+        .report_app
+        .report_post(CreateReportDto {
+            post_id,
+            reporter_id: auth_user.user_id,
+            reason: req.reason.into(),
+            details: req.details,
+        })
+        .await?;
+
+    info!("Post reported successfully: {}", post_id);
+
+    Ok(HttpResponse::Created().json(ReportResponse::from(report)))
+}
+
+/// Жалобы на посты от старых к новым (модератор или администратор).
+#[utoipa::path(
+    get,
+    path = "/api/v1/moderation/reports",
+    tag = "moderation",
+    params(ListReportsQuery),
+    responses(
+        (status = 200, description = "Страница жалоб", body = Paginated<ReportResponse>),
+        (status = 400, description = "Ошибка валидации параметров", body = ErrorResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/api/v1/moderation/reports")]
+pub async fn list_reports(
+    http_req: HttpRequest,
+    state: web::Data<AppState>,
+    query: web::Query<ListReportsQuery>,
+) -> Result<impl Responder, ApiError> {
+    info!("Received request to list reports");
+
+    query.validate()?;
 
-    // Извлекаем информацию об аутентифицированном пользователе
     let auth_user = http_req
         .extensions()
         .get::<AuthenticatedUser>()
@@ -217,47 +3306,87 @@ pub async fn update_post(
             ApiError::unauthorized("Authentication required".to_string())
         })?;
 
-    let post_id = Uuid::parse_str(&post_id_str).map_err(|_| {
-        warn!("Invalid UUID format: {}", post_id_str);
-        ApiError::bad_request("Invalid UUID format".to_string())
-    })?;
-
-    // Проверяем, что пользователь является автором поста
-    let existing_post = state.post_app.get_post_by_id(post_id).await?;
-    if existing_post.author_id != auth_user.user_id {
-        warn!(
-            "User {} attempted to update post {} owned by {}",
-            auth_user.user_id, post_id, existing_post.author_id
-        );
-        return Err(ApiError::forbidden(
-            "You can only update your own posts".to_string(),
-        ));
-    }
-
-    let dto = UpdatePostDto {
-        uuid: post_id,
-        title: req.title.clone(),
-        content: req.content.clone(),
+    let page = state
+        .report_app
+        .list_reports(
+            query.status.map(Into::into),
+            query.page,
+            query.page_size,
+            auth_user.role,
+        )
+        .await?;
+    let response = Paginated {
+        items: page.reports.into_iter().map(ReportResponse::from).collect(),
+        total: page.total,
+        page: page.page,
+        page_size: page.page_size,
+        next_cursor: None,
     };
 
-    let post_dto = state.post_app.update_post(dto).await?;
-    let response = PostResponse::from(post_dto);
-
-    info!("Post updated successfully: {}", post_id);
+    info!("Returning {} reports", response.items.len());
 
     Ok(HttpResponse::Ok().json(response))
 }
 
-#[delete("/api/v1/posts/{id}")]
-pub async fn delete_post(
+/// Решение по жалобе (модератор или администратор).
+///
+/// `hide_post` переносит пост в корзину без права восстановления автором и закрывает
+/// все открытые жалобы на него, `dismiss` отклоняет только эту жалобу.
+#[utoipa::path(
+    post,
+    path = "/api/v1/moderation/reports/{id}/resolve",
+    tag = "moderation",
+    params(("id" = String, Path, description = "UUID жалобы")),
+    request_body = ResolveReportRequest,
+    responses(
+        (status = 200, description = "Закрытая жалоба", body = ReportResponse),
+        (status = 401, description = "Требуется аутентификация", body = ErrorResponse),
+        (status = 403, description = "Действие запрещено", body = ErrorResponse),
+        (status = 404, description = "Жалоба не найдена", body = ErrorResponse),
+        (status = 409, description = "По жалобе уже принято решение", body = ErrorResponse),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/api/v1/moderation/reports/{id}/resolve")]
+pub async fn resolve_report(
     http_req: HttpRequest,
     state: web::Data<AppState>,
     path: web::Path<String>,
+    req: web::Json<ResolveReportRequest>,
 ) -> Result<impl Responder, ApiError> {
-    let post_id_str = path.into_inner();
-    info!("Received request to delete post: {}", post_id_str);
+    let report_id_str = path.into_inner();
+    info!("Received request to resolve report: {}", report_id_str);
 
-    // Извлекаем информацию об аутентифицированном пользователе
+    let (auth_user, report_id) = action_target(&http_req, &report_id_str)?;
+    let resolution = ReportResolution::from(req.action);
+
+    // Пост скрывается до закрытия жалоб, чтобы при сбое решение можно было повторить
+    if resolution == ReportResolution::HidePost {
+        let report = state
+            .report_app
+            .get_open_report(report_id, auth_user.role)
+            .await?;
+        state
+            .post_app
+            .hide_post(report.post_id, auth_user.user_id, auth_user.role)
+            .await?;
+    }
+    let report = state
+        .report_app
+        .resolve_report(report_id, resolution, auth_user.user_id, auth_user.role)
+        .await?;
+
+    info!("Report resolved successfully: {}", report_id);
+
+    Ok(HttpResponse::Ok().json(ReportResponse::from(report)))
+}
+
+/// Извлекает аутентифицированного пользователя и ID поста или пользователя,
+/// над которым выполняется действие.
+fn action_target(
+    http_req: &HttpRequest,
+    target_id_str: &str,
+) -> Result<(AuthenticatedUser, Uuid), ApiError> {
     let auth_user = http_req
         .extensions()
         .get::<AuthenticatedUser>()
@@ -267,26 +3396,210 @@ pub async fn delete_post(
             ApiError::unauthorized("Authentication required".to_string())
         })?;
 
-    let post_id = Uuid::parse_str(&post_id_str).map_err(|_| {
-        warn!("Invalid UUID format: {}", post_id_str);
+    let target_id = Uuid::parse_str(target_id_str).map_err(|_| {
+        warn!("Invalid UUID format: {}", target_id_str);
         ApiError::bad_request("Invalid UUID format".to_string())
     })?;
 
-    // Проверяем, что пользователь является автором поста
-    let existing_post = state.post_app.get_post_by_id(post_id).await?;
-    if existing_post.author_id != auth_user.user_id {
-        warn!(
-            "User {} attempted to delete post {} owned by {}",
-            auth_user.user_id, post_id, existing_post.author_id
-        );
-        return Err(ApiError::forbidden(
-            "You can only delete your own posts".to_string(),
+    Ok((auth_user, target_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::auth::LoginLockout;
+    use crate::application::digest::DigestOptions;
+    use crate::data::memrepo::InMemoryRepository;
+    use crate::domain::repositories::oauth::OAuthProviders;
+    use crate::domain::services::auth::AuthService;
+    use crate::infrastructure::email::LogEmailSender;
+    use crate::infrastructure::storage::LocalStorage;
+    use actix_web::ResponseError;
+    use actix_web::body::to_bytes;
+    use actix_web::test::TestRequest;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Состояние сервера поверх репозитория в памяти, как при запуске с `--in-memory`
+    fn state() -> AppState {
+        let repo: Arc<Repo> = Arc::new(InMemoryRepository::new());
+        let audit_app = Arc::new(AuditApplication::new(repo.clone()));
+        let auth_service = Arc::new(AuthService::new(
+            chrono::Duration::minutes(5),
+            b"test-secret-test-secret-test-secret-test-secret-test-secret-1234",
         ));
+        let lockout = LoginLockout {
+            max_failed_attempts: 0,
+            lock_duration: chrono::Duration::zero(),
+        };
+        let posts: Arc<dyn PostRepository> = repo.clone();
+        AppState {
+            auth_app: Arc::new(Audited::new(
+                AuthApplication::new(repo.clone(), auth_service, lockout, OAuthProviders::new()),
+                audit_app.clone(),
+            )),
+            post_app: Arc::new(Audited::new(
+                PostApplication::new(posts, repo.clone(), false),
+                audit_app.clone(),
+            )),
+            profile_app: Arc::new(ProfileApplication::new(repo.clone())),
+            idempotency_app: Arc::new(IdempotencyApplication::new(repo.clone())),
+            upload_app: Arc::new(UploadApplication::new(
+                repo.clone(),
+                Arc::new(LocalStorage::new(
+                    std::env::temp_dir(),
+                    "/uploads".to_string(),
+                )),
+                1024,
+            )),
+            admin_app: Arc::new(Audited::new(
+                AdminApplication::new(repo.clone()),
+                audit_app.clone(),
+            )),
+            audit_app,
+            report_app: Arc::new(ReportApplication::new(repo.clone())),
+            follow_app: Arc::new(FollowApplication::new(repo.clone())),
+            digest_app: Arc::new(DigestApplication::new(
+                repo.clone(),
+                Arc::new(LogEmailSender),
+                b"test-secret",
+                DigestOptions {
+                    site_name: "Blog".to_string(),
+                    app_url: "http://localhost".to_string(),
+                    public_url: "http://localhost".to_string(),
+                    max_posts: 10,
+                },
+            )),
+            category_app: Arc::new(CategoryApplication::new(repo.clone())),
+            blog_app: Arc::new(BlogApplication::new(repo)),
+        }
+    }
+
+    fn request(key: Option<&str>) -> HttpRequest {
+        let req = TestRequest::post().uri("/api/v1/posts");
+        match key {
+            Some(key) => req.insert_header((IDEMPOTENCY_KEY_HEADER, key)),
+            None => req,
+        }
+        .to_http_request()
     }
 
-    state.post_app.delete_post(post_id).await?;
+    /// Выполняет запрос через `idempotent`, считая вызовы обработчика в `calls`
+    async fn call(
+        state: &AppState,
+        key: Option<&str>,
+        body: &str,
+        calls: &AtomicUsize,
+        result: Result<(StatusCode, u32), ApiError>,
+    ) -> Result<HttpResponse, ApiError> {
+        idempotent(&request(key), state, &body, async {
+            let call = calls.fetch_add(1, Ordering::SeqCst) as u32;
+            result.map(|(status, value)| (status, value + call))
+        })
+        .await
+    }
 
-    info!("Post deleted successfully: {}", post_id);
+    async fn body(response: HttpResponse) -> String {
+        let bytes = to_bytes(response.into_body()).await.unwrap();
+        String::from_utf8(bytes.to_vec()).unwrap()
+    }
 
-    Ok(HttpResponse::NoContent().finish())
+    #[actix_web::test]
+    async fn test_repeated_key_replays_stored_response() {
+        let state = state();
+        let calls = AtomicUsize::new(0);
+
+        let first = call(
+            &state,
+            Some("key-1"),
+            "a",
+            &calls,
+            Ok((StatusCode::CREATED, 10)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert!(!first.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER));
+        assert_eq!(body(first).await, "10");
+
+        let replay = call(
+            &state,
+            Some("key-1"),
+            "a",
+            &calls,
+            Ok((StatusCode::CREATED, 10)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(replay.status(), StatusCode::CREATED);
+        assert_eq!(
+            replay.headers().get(IDEMPOTENT_REPLAYED_HEADER).unwrap(),
+            "true"
+        );
+        assert_eq!(body(replay).await, "10");
+    }
+
+    #[actix_web::test]
+    async fn test_reused_key_with_different_body_is_rejected() {
+        let state = state();
+        let calls = AtomicUsize::new(0);
+
+        call(&state, Some("key-1"), "a", &calls, Ok((StatusCode::OK, 0)))
+            .await
+            .unwrap();
+        let err = call(&state, Some("key-1"), "b", &calls, Ok((StatusCode::OK, 0)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::CONFLICT);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[actix_web::test]
+    async fn test_failed_request_releases_key() {
+        let state = state();
+        let calls = AtomicUsize::new(0);
+
+        let err = call(
+            &state,
+            Some("key-1"),
+            "a",
+            &calls,
+            Err(ApiError::bad_request("invalid".to_string())),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+
+        // Повтор с тем же ключом выполняется заново, а не получает сохранённую ошибку
+        let retry = call(&state, Some("key-1"), "a", &calls, Ok((StatusCode::OK, 0)))
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(!retry.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER));
+    }
+
+    #[actix_web::test]
+    async fn test_request_without_key_runs_every_time() {
+        let state = state();
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            call(&state, None, "a", &calls, Ok((StatusCode::OK, 0)))
+                .await
+                .unwrap();
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[actix_web::test]
+    async fn test_invalid_key_is_rejected() {
+        let state = state();
+        let calls = AtomicUsize::new(0);
+
+        let err = call(&state, Some(""), "a", &calls, Ok((StatusCode::OK, 0)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
 }
@@ -1,22 +1,252 @@
-use actix_web::{HttpMessage, dev::ServiceRequest, error::ErrorUnauthorized, web};
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{Extensions, Payload, ServiceRequest, ServiceResponse};
+use actix_web::error::{ErrorInternalServerError, InternalError};
+use actix_web::http::Uri;
+use actix_web::http::header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{HttpMessage, HttpRequest, web};
 use actix_web_httpauth::extractors::bearer::BearerAuth;
-use tracing::{debug, warn};
+use tokio_stream::StreamExt;
+use tracing::{Span, debug, info, warn};
+use tracing_actix_web::{DefaultRootSpanBuilder, RequestId, RootSpan, RootSpanBuilder};
 use uuid::Uuid;
 
-use crate::domain::services::auth::AuthService;
+use crate::domain::entities::user::Role;
+use crate::infrastructure::body_log::{BodyLogging, MAX_LOGGED_BODY_BYTES, describe_json};
+use crate::infrastructure::rate_limit::RateLimiter;
+use crate::presentation::error::ApiError;
+use crate::presentation::http::handlers::AppState;
+use crate::presentation::request_id;
+
+/// Заголовок ответа с ID запроса, тот же ID приходит в `trace_id` ошибок
+const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Делает ID запроса из `TracingLogger` доступным при формировании ответа с ошибкой
+/// и возвращает его в заголовке `X-Request-Id`.
+///
+/// Ошибки вложенных middleware (например, JWT-аутентификации) преобразуются в ответ
+/// здесь же, чтобы `trace_id` попал и в них.
+pub async fn trace_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let trace_id = req
+        .extensions()
+        .get::<RequestId>()
+        .map(|request_id| request_id.to_string())
+        .unwrap_or_else(|| Uuid::now_v7().to_string());
+
+    let header = HeaderValue::from_str(&trace_id).ok();
+
+    // Клон HttpRequest здесь нельзя держать: маршрутизации нужна единственная ссылка на него
+    request_id::scope(trace_id, async move {
+        match next.call(req).await {
+            Ok(mut response) => {
+                if let Some(header) = header {
+                    response.headers_mut().insert(X_REQUEST_ID, header);
+                }
+                Ok(response.map_into_boxed_body())
+            }
+            Err(err) => {
+                let mut response = err.error_response();
+                if let Some(header) = header {
+                    response.headers_mut().insert(X_REQUEST_ID, header);
+                }
+                Err(InternalError::from_response(err, response).into())
+            }
+        }
+    })
+    .await
+}
+
+/// Префиксы REST маршрутов текущей и следующей версии API
+const API_V1_PREFIX: &str = "/api/v1";
+const API_V2_PREFIX: &str = "/api/v2";
+
+/// Обслуживает область `/api/v2` обработчиками `v1`.
+///
+/// Маршруты `v2` пока не отличаются от `v1`, поэтому путь запроса переписывается до
+/// маршрутизации, а обработчики объявлены один раз. Маршрут, который изменится в `v2`,
+/// регистрируется с путём `/api/v2/...` и исключается отсюда.
+pub async fn api_v2_alias(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    if let Some(rest) = req.path().strip_prefix(API_V2_PREFIX)
+        && (rest.is_empty() || rest.starts_with('/'))
+    {
+        let path_and_query = match req.query_string() {
+            "" => format!("{API_V1_PREFIX}{rest}"),
+            query => format!("{API_V1_PREFIX}{rest}?{query}"),
+        };
+        let mut parts = req.head().uri.clone().into_parts();
+        parts.path_and_query = Some(path_and_query.parse().map_err(ErrorInternalServerError)?);
+        let uri = Uri::from_parts(parts).map_err(ErrorInternalServerError)?;
+        req.match_info_mut().get_mut().update(&uri);
+        req.head_mut().uri = uri;
+    }
+
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_boxed_body)
+}
+
+/// Корневой спан запроса для `TracingLogger`: поля по умолчанию и `user_id`,
+/// который заполняется после аутентификации.
+///
+/// С feature `otel` родителем спана становится контекст из заголовка `traceparent`.
+pub struct BlogRootSpanBuilder;
+
+impl RootSpanBuilder for BlogRootSpanBuilder {
+    fn on_request_start(request: &ServiceRequest) -> Span {
+        tracing_actix_web::root_span!(request, user_id = tracing::field::Empty)
+    }
+
+    fn on_request_end<B: MessageBody>(
+        span: Span,
+        outcome: &Result<ServiceResponse<B>, actix_web::Error>,
+    ) {
+        DefaultRootSpanBuilder::on_request_end(span, outcome);
+    }
+}
+
+/// Отмечает корневой спан запроса ID аутентифицированного пользователя
+fn record_user_id(extensions: &Extensions, user: &AuthenticatedUser) {
+    if let Some(root_span) = extensions.get::<RootSpan>() {
+        root_span.record("user_id", tracing::field::display(user.user_id));
+    }
+}
+
+/// Ограничивает частоту запросов к маршрутам аутентификации с одного IP-адреса.
+///
+/// Лимит берётся из [`RateLimiter`] в app_data; при превышении возвращается
+/// `429 Too Many Requests` с заголовком `Retry-After`.
+pub async fn auth_rate_limit(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let limiter = req.app_data::<web::Data<RateLimiter>>();
+    if let (Some(limiter), Some(addr)) = (limiter, req.peer_addr())
+        && let Err(retry_after_secs) = limiter.check(addr.ip())
+    {
+        warn!("Rate limit exceeded for {} on {}", addr.ip(), req.path());
+        return Err(ApiError::rate_limited(retry_after_secs).into());
+    }
+
+    next.call(req)
+        .await
+        .map(ServiceResponse::map_into_boxed_body)
+}
+
+/// Пишет в лог JSON-тела запросов и ответов, пока включено логирование тел
+/// ([`BodyLogging`] в app_data).
+///
+/// Тела других типов (файлы, HTML, поток событий) не читаются: в лог попадает только
+/// их тип. Тело запроса читается в память не больше чем на [`MAX_LOGGED_BODY_BYTES`]:
+/// более длинное не логируется, а обработчик получает его целиком из исходного потока.
+/// Middleware регистрируется внутри сжатия ответов, чтобы видеть исходные тела.
+pub async fn log_bodies(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, actix_web::Error> {
+    let enabled = req
+        .app_data::<web::Data<BodyLogging>>()
+        .is_some_and(|body_logging| body_logging.is_enabled());
+    if !enabled {
+        return next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_boxed_body);
+    }
+
+    match content_type(req.headers()) {
+        Some(content_type) if is_json(content_type) => {
+            let mut payload = req.take_payload();
+            let mut body = web::BytesMut::new();
+            let mut complete = true;
+            while let Some(chunk) = payload.next().await {
+                body.extend_from_slice(&chunk?);
+                if body.len() > MAX_LOGGED_BODY_BYTES {
+                    complete = false;
+                    break;
+                }
+            }
+            let body = body.freeze();
+            let description = if complete {
+                describe_json(&body)
+            } else {
+                format!("<more than {} bytes of JSON>", MAX_LOGGED_BODY_BYTES)
+            };
+            info!(
+                "Request body of {} {}: {}",
+                req.method(),
+                req.path(),
+                description
+            );
+            if complete {
+                req.set_payload(Payload::from(body));
+            } else {
+                // Прочитанное начало тела идёт перед ещё не прочитанным остатком
+                let rest = tokio_stream::once(Ok(body)).chain(payload);
+                req.set_payload(Payload::Stream {
+                    payload: Box::pin(rest),
+                });
+            }
+        }
+        Some(content_type) => info!(
+            "Request body of {} {}: <{}>",
+            req.method(),
+            req.path(),
+            content_type
+        ),
+        None => {}
+    }
+
+    let response = next.call(req).await?;
+    let Some(content_type) = content_type(response.headers()).map(str::to_string) else {
+        return Ok(response.map_into_boxed_body());
+    };
+    if !is_json(&content_type) {
+        info!("Response body: <{}>", content_type);
+        return Ok(response.map_into_boxed_body());
+    }
+
+    let (req, response) = response.into_parts();
+    let (response, body) = response.into_parts();
+    let body = actix_web::body::to_bytes(body)
+        .await
+        .map_err(|e| ErrorInternalServerError(e.into().to_string()))?;
+    info!("Response body: {}", describe_json(&body));
+    Ok(ServiceResponse::new(req, response.set_body(body)).map_into_boxed_body())
+}
+
+/// Тип тела из заголовка `Content-Type`
+fn content_type(headers: &HeaderMap) -> Option<&str> {
+    headers.get(CONTENT_TYPE)?.to_str().ok()
+}
+
+/// `application/json` и типы с суффиксом `+json`
+fn is_json(content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    essence.eq_ignore_ascii_case("application/json") || essence.ends_with("+json")
+}
 
 /// Структура для хранения информации об аутентифицированном пользователе
 #[derive(Debug, Clone)]
 pub struct AuthenticatedUser {
     pub user_id: Uuid,
     pub username: String,
+    pub role: Role,
+    /// Сеанс входа, которому выдан токен (нет у токенов, выданных до появления сеансов)
+    pub session_id: Option<Uuid>,
 }
 
 /// Валидатор JWT токена для actix-web-httpauth middleware
 ///
 /// Эта функция извлекает токен из заголовка Authorization,
-/// проверяет его через AuthService и добавляет информацию о пользователе
-/// в расширения запроса для использования в хэндлерах.
+/// проверяет его через AuthApplication (подпись, срок действия и отзыв выходом
+/// со всех устройств) и добавляет информацию о пользователе в расширения запроса
+/// для использования в хэндлерах.
 pub async fn jwt_validator(
     req: ServiceRequest,
     credentials: BearerAuth,
@@ -24,49 +254,125 @@ pub async fn jwt_validator(
     let token = credentials.token();
     debug!("Validating JWT token");
 
-    // Получаем AuthService из app_data
-    let auth_service = req
-        .app_data::<web::Data<AuthService>>()
-        .map(|data| data.get_ref());
+    // Получаем состояние приложения из app_data
+    let Some(state) = req.app_data::<web::Data<AppState>>().cloned() else {
+        warn!("AppState not found in app_data");
+        return Err((
+            ApiError::unauthorized("Internal server error".to_string()).into(),
+            req,
+        ));
+    };
 
-    if auth_service.is_none() {
-        warn!("AuthService not found in app_data");
-        return Err((ErrorUnauthorized("Internal server error"), req));
+    match authenticate(&state, token).await {
+        Ok(authenticated_user) => {
+            record_user_id(&req.extensions(), &authenticated_user);
+            // Добавляем информацию о пользователе в расширения запроса
+            req.extensions_mut().insert(authenticated_user);
+            Ok(req)
+        }
+        Err(err) => Err((err.into(), req)),
     }
+}
 
-    let auth_service = auth_service.unwrap();
+/// Проверяет токен через AuthApplication и возвращает пользователя из его claims.
+async fn authenticate(state: &AppState, token: &str) -> Result<AuthenticatedUser, ApiError> {
+    let claims = state.auth_app.authenticate(token).await?;
 
-    // Проверяем токен
-    match auth_service.verify_token(token) {
-        Some(claims) => {
-            debug!(
-                "Token validated successfully for user: {}",
-                claims.user_name
-            );
+    debug!(
+        "Token validated successfully for user: {}",
+        claims.user_name
+    );
 
-            // Парсим user_id из claims.sub
-            let user_id = match Uuid::parse_str(&claims.sub) {
-                Ok(id) => id,
-                Err(_) => {
-                    warn!("Invalid UUID in token claims: {}", claims.sub);
-                    return Err((ErrorUnauthorized("Invalid token format"), req));
-                }
-            };
+    // Парсим user_id из claims.sub
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        warn!("Invalid UUID in token claims: {}", claims.sub);
+        ApiError::unauthorized("Invalid token format".to_string())
+    })?;
 
-            // Создаём структуру аутентифицированного пользователя
-            let authenticated_user = AuthenticatedUser {
-                user_id,
-                username: claims.user_name,
-            };
+    Ok(AuthenticatedUser {
+        user_id,
+        username: claims.user_name,
+        role: claims.role,
+        session_id: claims.sid,
+    })
+}
 
-            // Добавляем информацию о пользователе в расширения запроса
-            req.extensions_mut().insert(authenticated_user);
+/// Определяет пользователя публичного маршрута, если запрос содержит валидный токен.
+///
+/// Публичные маршруты не проходят через [`jwt_validator`], поэтому отсутствующий
+/// или невалидный токен не является ошибкой: запрос обрабатывается как анонимный.
+pub async fn optional_user(req: &HttpRequest) -> Option<AuthenticatedUser> {
+    let state = req.app_data::<web::Data<AppState>>()?;
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")?;
+    let user = authenticate(state, token).await.ok()?;
+    record_user_id(&req.extensions(), &user);
+    Some(user)
+}
 
-            Ok(req)
-        }
-        None => {
-            warn!("Token validation failed");
-            Err((ErrorUnauthorized("Invalid or expired token"), req))
+#[cfg(test)]
+mod tests {
+    use actix_web::{App, HttpResponse, middleware::from_fn, test};
+
+    use super::*;
+
+    async fn echo(req: HttpRequest, id: web::Path<u32>) -> HttpResponse {
+        HttpResponse::Ok().body(format!("{} {} {}", req.path(), id, req.query_string()))
+    }
+
+    #[actix_web::test]
+    async fn test_api_v2_is_served_by_v1_routes() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(api_v2_alias))
+                .route("/api/v1/posts/{id}", web::get().to(echo)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/api/v2/posts/7?fields=title")
+            .to_request();
+        let body = test::call_and_read_body(&app, req).await;
+        assert_eq!(body, "/api/v1/posts/7 7 fields=title");
+
+        let req = test::TestRequest::get().uri("/api/v1/posts/7").to_request();
+        assert!(test::call_service(&app, req).await.status().is_success());
+
+        // Похожий, но другой префикс не переписывается
+        let req = test::TestRequest::get()
+            .uri("/api/v20/posts/7")
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 404);
+    }
+
+    async fn body_length(body: web::Bytes) -> HttpResponse {
+        HttpResponse::Ok().body(body.len().to_string())
+    }
+
+    #[actix_web::test]
+    async fn test_logged_request_body_reaches_handler_intact() {
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(BodyLogging::new(true)))
+                .wrap(from_fn(log_bodies))
+                .route("/echo", web::post().to(body_length)),
+        )
+        .await;
+
+        // Короткое тело логируется целиком, длинное - не читается в память дальше лимита
+        for length in [16, MAX_LOGGED_BODY_BYTES * 3] {
+            let payload = format!(r#"{{"content":"{}"}}"#, "a".repeat(length));
+            let req = test::TestRequest::post()
+                .uri("/echo")
+                .insert_header((CONTENT_TYPE, "application/json"))
+                .set_payload(payload.clone())
+                .to_request();
+            let body = test::call_and_read_body(&app, req).await;
+            assert_eq!(body, payload.len().to_string());
         }
     }
 }
@@ -1,2 +1,4 @@
 pub mod handlers;
 pub mod middleware;
+pub mod openapi;
+pub mod pages;
@@ -0,0 +1,125 @@
+use api::rest::{
+    AuditAction, DigestFrequency, ErrorCode, PostSortField, PostStatus, ReportAction, ReportReason,
+    ReportStatus, SortOrder, UserRole,
+};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::presentation::http::handlers;
+
+/// Описание REST API в формате OpenAPI.
+///
+/// Собирается из аннотаций обработчиков и типов `api::rest`, поэтому
+/// не расходится с кодом. Отдаётся по `/api/v1/openapi.json`.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Blog API", description = "REST API блог-платформы"),
+    paths(
+        handlers::get_server_info,
+        handlers::register,
+        handlers::username_available,
+        handlers::login,
+        handlers::login_totp,
+        handlers::oauth_authorize,
+        handlers::oauth_callback,
+        handlers::enroll_totp,
+        handlers::verify_totp,
+        handlers::refresh_token,
+        handlers::logout,
+        handlers::logout_all,
+        handlers::list_sessions,
+        handlers::revoke_session,
+        handlers::forgot_password,
+        handlers::reset_password,
+        handlers::jwks,
+        handlers::get_user_profile,
+        handlers::list_user_posts,
+        handlers::follow_user,
+        handlers::unfollow_user,
+        handlers::get_follow_status,
+        handlers::get_feed,
+        handlers::update_my_profile,
+        handlers::get_digest_settings,
+        handlers::update_digest_settings,
+        handlers::create_post,
+        handlers::create_posts,
+        handlers::list_posts,
+        handlers::list_trending_posts,
+        handlers::post_events,
+        handlers::get_post,
+        handlers::update_post,
+        handlers::delete_post,
+        handlers::delete_posts,
+        handlers::restore_post,
+        handlers::purge_post,
+        handlers::publish_post,
+        handlers::unpublish_post,
+        handlers::like_post,
+        handlers::unlike_post,
+        handlers::upload_file,
+        handlers::list_audit_log,
+        handlers::list_users,
+        handlers::disable_user,
+        handlers::enable_user,
+        handlers::force_delete_post,
+        handlers::get_system_stats,
+        handlers::get_body_logging,
+        handlers::set_body_logging,
+        handlers::list_categories,
+        handlers::create_category,
+        handlers::list_blogs,
+        handlers::create_blog,
+        handlers::get_blog,
+        handlers::update_blog,
+        handlers::delete_blog,
+        handlers::list_blog_posts,
+        handlers::create_blog_post,
+        handlers::create_blog_posts,
+        handlers::report_post,
+        handlers::list_reports,
+        handlers::resolve_report,
+    ),
+    components(schemas(
+        AuditAction,
+        ErrorCode,
+        PostStatus,
+        PostSortField,
+        SortOrder,
+        UserRole,
+        ReportReason,
+        ReportStatus,
+        ReportAction,
+        DigestFrequency
+    )),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "meta", description = "Версия сервера и возможности"),
+        (name = "auth", description = "Регистрация, вход и токены"),
+        (name = "users", description = "Профили пользователей"),
+        (name = "posts", description = "Посты, черновики и лайки"),
+        (name = "uploads", description = "Загрузка файлов для вложений"),
+        (name = "categories", description = "Категории постов"),
+        (name = "blogs", description = "Блоги сервера и их посты"),
+        (name = "admin", description = "Администрирование"),
+        (name = "moderation", description = "Жалобы на посты и их разбор"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Схема аутентификации `bearer_auth`, на которую ссылаются защищённые маршруты
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
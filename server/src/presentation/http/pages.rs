@@ -0,0 +1,327 @@
+//! HTML-страницы постов и карта сайта для поисковиков.
+//!
+//! Основной интерфейс блога - WASM-приложение: поисковики и превью ссылок в
+//! мессенджерах не выполняют его код и не видят содержимого. Поэтому сервер отдаёт
+//! для каждого опубликованного поста простую страницу с мета-тегами OpenGraph
+//! (`GET /posts/{id}`), а `GET /sitemap.xml` перечисляет все такие страницы.
+//!
+//! Здесь же страница отписки от email-дайджеста: ссылка на неё приходит в письме.
+
+use std::fmt::Write;
+
+use actix_web::http::{StatusCode, header};
+use actix_web::{HttpResponse, get, route, web};
+use serde::Deserialize;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::application::dto::post::PostDto;
+use crate::domain::entities::errors::DomainError;
+use crate::domain::entities::post::{PostFields, PostSort};
+use crate::infrastructure::config::SiteConfig;
+use crate::presentation::error::ApiError;
+use crate::presentation::http::handlers::AppState;
+
+/// Сколько постов читается из хранилища за раз при сборке карты сайта
+const SITEMAP_CHUNK_SIZE: u32 = 100;
+/// Наибольшее число адресов в одном файле карты сайта (ограничение протокола sitemaps.org)
+const SITEMAP_MAX_URLS: usize = 50_000;
+/// Сколько секунд прокси и браузеры могут хранить страницы
+const PAGE_MAX_AGE_SECONDS: u32 = 300;
+
+/// Адреса и название сайта для страниц постов.
+pub struct SitePages {
+    public_url: String,
+    app_url: String,
+    name: String,
+}
+
+impl SitePages {
+    pub fn new(config: &SiteConfig) -> Self {
+        Self {
+            public_url: config.public_url.trim_end_matches('/').to_string(),
+            app_url: config.app_url.trim_end_matches('/').to_string(),
+            name: config.name.clone(),
+        }
+    }
+
+    /// Публичный адрес страницы поста
+    fn post_url(&self, post_id: Uuid) -> String {
+        format!("{}/posts/{}", self.public_url, post_id)
+    }
+
+    /// Страница поста с мета-тегами OpenGraph
+    fn render_post(&self, post: &PostDto) -> String {
+        let url = self.post_url(post.uuid);
+        let title = escape(&post.title);
+        let site_name = escape(&self.name);
+        let description = escape(&post.summary);
+        let author = escape(&post.author_username);
+        let published = post.created_at.to_rfc3339();
+        let modified = post.updated_at.to_rfc3339();
+
+        let mut tags = String::new();
+        for tag in &post.tags {
+            let _ = writeln!(
+                tags,
+                r#"    <meta property="article:tag" content="{}">"#,
+                escape(tag)
+            );
+        }
+        // HTML уже очищен санитайзером при сохранении; без него показывается исходный текст
+        let content = match &post.content_html {
+            Some(html) => html.clone(),
+            None => format!(
+                r#"<div style="white-space: pre-wrap">{}</div>"#,
+                escape(&post.content)
+            ),
+        };
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>{title} - {site_name}</title>
+    <meta name="description" content="{description}">
+    <link rel="canonical" href="{url}">
+    <meta property="og:type" content="article">
+    <meta property="og:site_name" content="{site_name}">
+    <meta property="og:title" content="{title}">
+    <meta property="og:description" content="{description}">
+    <meta property="og:url" content="{url}">
+    <meta property="article:author" content="{author}">
+    <meta property="article:published_time" content="{published}">
+    <meta property="article:modified_time" content="{modified}">
+{tags}    <meta name="twitter:card" content="summary">
+</head>
+<body>
+    <article>
+        <h1>{title}</h1>
+        <p>By {author} · <time datetime="{published}">{date}</time></p>
+        {content}
+    </article>
+    <p><a href="{app_url}/posts/{id}">Open in {site_name}</a></p>
+</body>
+</html>
+"#,
+            date = post.created_at.format("%Y-%m-%d"),
+            app_url = self.app_url,
+            id = post.uuid,
+        )
+    }
+
+    /// Страница с коротким сообщением: результат действия по ссылке из письма
+    fn render_message(&self, title: &str, message: &str) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8">
+    <meta name="robots" content="noindex">
+    <title>{title} - {site_name}</title>
+</head>
+<body>
+    <h1>{title}</h1>
+    <p>{message}</p>
+    <p><a href="{app_url}/">Back to {site_name}</a></p>
+</body>
+</html>
+"#,
+            title = escape(title),
+            message = escape(message),
+            site_name = escape(&self.name),
+            app_url = self.app_url,
+        )
+    }
+
+    /// Страница несуществующего поста
+    fn render_not_found(&self) -> String {
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8">
+    <meta name="robots" content="noindex">
+    <title>Post not found - {site_name}</title>
+</head>
+<body>
+    <h1>Post not found</h1>
+    <p><a href="{app_url}/">Back to {site_name}</a></p>
+</body>
+</html>
+"#,
+            site_name = escape(&self.name),
+            app_url = self.app_url,
+        )
+    }
+}
+
+/// Экранирует текст для вставки в HTML или XML, в том числе в значения атрибутов
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn html_response(status: StatusCode, body: String) -> HttpResponse {
+    HttpResponse::build(status)
+        .content_type("text/html; charset=utf-8")
+        .insert_header((
+            header::CACHE_CONTROL,
+            format!("public, max-age={PAGE_MAX_AGE_SECONDS}"),
+        ))
+        .body(body)
+}
+
+/// HTML-страница опубликованного поста для поисковиков и превью ссылок.
+///
+/// Черновики и удалённые посты не показываются: для них, как и для неизвестного ID,
+/// возвращается страница `404`.
+#[get("/posts/{id}")]
+pub async fn post_page(
+    state: web::Data<AppState>,
+    site: web::Data<SitePages>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let post_id_str = path.into_inner();
+    info!("Received request to render post page: {}", post_id_str);
+
+    let Ok(post_id) = Uuid::parse_str(&post_id_str) else {
+        warn!("Invalid UUID format: {}", post_id_str);
+        return Ok(html_response(
+            StatusCode::NOT_FOUND,
+            site.render_not_found(),
+        ));
+    };
+
+    match state.post_app.get_post_by_id(post_id, None).await {
+        Ok(post) => Ok(html_response(StatusCode::OK, site.render_post(&post))),
+        Err(DomainError::PostNotFound { .. }) => Ok(html_response(
+            StatusCode::NOT_FOUND,
+            site.render_not_found(),
+        )),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Карта сайта со страницами всех опубликованных постов, от новых к старым.
+#[get("/sitemap.xml")]
+pub async fn sitemap(
+    state: web::Data<AppState>,
+    site: web::Data<SitePages>,
+) -> Result<HttpResponse, ApiError> {
+    info!("Received request for sitemap");
+
+    let mut body = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+    );
+    let mut urls = 0;
+    let mut cursor = None;
+    'pages: loop {
+        let page = state
+            .post_app
+            .get_posts_after(
+                cursor.as_deref(),
+                SITEMAP_CHUNK_SIZE,
+                None,
+                None,
+                None,
+                None,
+                PostSort::default(),
+                PostFields::NONE,
+            )
+            .await?;
+        for post in &page.posts {
+            if urls == SITEMAP_MAX_URLS {
+                warn!("Sitemap truncated to {} posts", SITEMAP_MAX_URLS);
+                break 'pages;
+            }
+            let _ = writeln!(
+                body,
+                "  <url><loc>{}</loc><lastmod>{}</lastmod></url>",
+                escape(&site.post_url(post.uuid)),
+                post.updated_at.to_rfc3339()
+            );
+            urls += 1;
+        }
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    body.push_str("</urlset>\n");
+
+    info!("Returning sitemap with {} posts", urls);
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/xml; charset=utf-8")
+        .insert_header((
+            header::CACHE_CONTROL,
+            format!("public, max-age={PAGE_MAX_AGE_SECONDS}"),
+        ))
+        .body(body))
+}
+
+/// Параметры ссылки отписки из письма дайджеста.
+#[derive(Debug, Deserialize)]
+pub struct UnsubscribeQuery {
+    /// ID подписчика
+    pub user_id: String,
+    /// Подпись ссылки
+    pub token: String,
+}
+
+/// Отписка от email-дайджеста по ссылке из письма, без входа.
+///
+/// `POST` принимается для отписки в один клик из почтового клиента (RFC 8058).
+/// Страница результата не кэшируется.
+#[route("/digest/unsubscribe", method = "GET", method = "POST")]
+pub async fn unsubscribe_digest(
+    state: web::Data<AppState>,
+    site: web::Data<SitePages>,
+    query: web::Query<UnsubscribeQuery>,
+) -> Result<HttpResponse, ApiError> {
+    info!("Received request to unsubscribe from digest");
+
+    let result = match Uuid::parse_str(&query.user_id) {
+        Ok(user_id) => state.digest_app.unsubscribe(user_id, &query.token).await,
+        Err(_) => Err(DomainError::InvalidUnsubscribeToken),
+    };
+    let (status, body) = match result {
+        Ok(()) => (
+            StatusCode::OK,
+            site.render_message(
+                "Unsubscribed",
+                "You will no longer receive email digests of new posts.",
+            ),
+        ),
+        Err(DomainError::InvalidUnsubscribeToken) => {
+            warn!("Invalid unsubscribe link for user {}", query.user_id);
+            (
+                StatusCode::BAD_REQUEST,
+                site.render_message(
+                    "Invalid link",
+                    "This unsubscribe link is invalid. You can turn off digests in your profile settings.",
+                ),
+            )
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(HttpResponse::build(status)
+        .content_type("text/html; charset=utf-8")
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .body(body))
+}
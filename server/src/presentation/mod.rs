@@ -1,3 +1,5 @@
 pub mod error;
 pub mod grpc;
 pub mod http;
+pub mod request_id;
+pub mod server_info;
@@ -0,0 +1,22 @@
+//! ID обрабатываемого HTTP или gRPC запроса.
+//!
+//! Тот же ID записывается в поле `request_id` корневого спана запроса и возвращается
+//! клиенту в `trace_id` ответа с ошибкой, чтобы по нему можно было найти запрос в логах.
+
+use std::future::Future;
+
+use tokio::task::futures::TaskLocalFuture;
+
+tokio::task_local! {
+    static TRACE_ID: String;
+}
+
+/// Выполняет `future` с `trace_id` в качестве ID текущего запроса
+pub fn scope<F: Future>(trace_id: String, future: F) -> TaskLocalFuture<String, F> {
+    TRACE_ID.scope(trace_id, future)
+}
+
+/// ID текущего запроса, если он обрабатывается внутри [`scope`]
+pub fn current_trace_id() -> Option<String> {
+    TRACE_ID.try_with(Clone::clone).ok()
+}
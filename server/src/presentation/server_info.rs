@@ -0,0 +1,62 @@
+use api::rest::PostLimitsResponse;
+use api::version::{SUPPORTED_API_VERSIONS, features};
+
+use crate::application::post::PostLimits;
+
+/// Версия сервера, обслуживаемые версии API и включённые возможности.
+///
+/// Отдаётся обоими транспортами: `GET /api/info` и gRPC `GetServerInfo`.
+#[derive(Debug, Clone)]
+pub struct ServerInfo {
+    pub version: String,
+    pub api_versions: Vec<String>,
+    pub features: Vec<String>,
+    pub post_limits: PostLimits,
+}
+
+impl ServerInfo {
+    /// Собирает сведения из конфигурации: часть возможностей зависит от неё.
+    pub fn new(oauth_enabled: bool, render_markdown: bool, post_limits: PostLimits) -> Self {
+        let mut enabled = vec![
+            features::POST_EVENTS,
+            features::TWO_FACTOR,
+            features::UPLOADS,
+        ];
+        if oauth_enabled {
+            enabled.push(features::OAUTH);
+        }
+        if render_markdown {
+            enabled.push(features::CONTENT_HTML);
+        }
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            api_versions: SUPPORTED_API_VERSIONS
+                .iter()
+                .map(|version| version.to_string())
+                .collect(),
+            features: enabled.into_iter().map(str::to_string).collect(),
+            post_limits,
+        }
+    }
+}
+
+impl From<PostLimits> for PostLimitsResponse {
+    fn from(limits: PostLimits) -> Self {
+        Self {
+            max_posts_per_day: (limits.max_posts_per_day > 0).then_some(limits.max_posts_per_day),
+            max_content_bytes: limits.max_content_bytes as u64,
+            max_title_chars: limits.max_title_chars as u32,
+        }
+    }
+}
+
+impl From<PostLimits> for api::PostLimits {
+    fn from(limits: PostLimits) -> Self {
+        Self {
+            max_posts_per_day: (limits.max_posts_per_day > 0).then_some(limits.max_posts_per_day),
+            max_content_bytes: limits.max_content_bytes as u64,
+            max_title_chars: limits.max_title_chars as u32,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// Service worker блог-приложения.
+//
+// - app shell (index.html, wasm, js, css) кэшируется при установке и
+//   обновляется в фоне (stale-while-revalidate); на переход по любому адресу
+//   приложения (/posts/:id, /me, ...) отдаётся тот же index.html;
+// - GET-запросы к постам идут в сеть, а при её отсутствии отдаются из кэша.
+//   Хранятся только последние MAX_POST_ENTRIES ответов и только на запросы без
+//   заголовка Authorization: ответ вошедшему пользователю содержит его черновики
+//   и лайки и не должен достаться следующему пользователю браузера.
+
+const SHELL_CACHE = "blog-shell-v1";
+// v2: в v1 попадали ответы вошедшим пользователям, activate удаляет старый кэш
+const POSTS_CACHE = "blog-posts-v2";
+const MAX_POST_ENTRIES = 50;
+const SHELL_URLS = ["/", "/index.html", "/manifest.webmanifest"];
+
+self.addEventListener("install", (event) => {
+    event.waitUntil(
+        caches
+            .open(SHELL_CACHE)
+            .then((cache) => cache.addAll(SHELL_URLS))
+            .then(() => self.skipWaiting()),
+    );
+});
+
+self.addEventListener("activate", (event) => {
+    const keep = [SHELL_CACHE, POSTS_CACHE];
+    event.waitUntil(
+        caches
+            .keys()
+            .then((keys) =>
+                Promise.all(keys.filter((key) => !keep.includes(key)).map((key) => caches.delete(key))),
+            )
+            .then(() => self.clients.claim()),
+    );
+});
+
+self.addEventListener("fetch", (event) => {
+    const request = event.request;
+    if (request.method !== "GET") {
+        return;
+    }
+
+    const url = new URL(request.url);
+
+    // Живая лента (SSE) не кэшируется: ответ никогда не завершается
+    if (url.pathname === "/api/v1/posts/events") {
+        return;
+    }
+
+    if (url.pathname.startsWith("/api/v1/posts")) {
+        event.respondWith(networkFirst(request));
+        return;
+    }
+
+    if (url.origin !== self.location.origin) {
+        return;
+    }
+
+    // Страницы приложения маршрутизируются на клиенте
+    if (request.mode === "navigate") {
+        event.respondWith(staleWhileRevalidate(new Request("/")));
+        return;
+    }
+
+    event.respondWith(staleWhileRevalidate(request));
+});
+
+async function networkFirst(request) {
+    const cache = await caches.open(POSTS_CACHE);
+    try {
+        const response = await fetch(request);
+        if (response.ok && !request.headers.has("Authorization")) {
+            await cache.put(request, response.clone());
+            await trimCache(cache, MAX_POST_ENTRIES);
+        }
+        return response;
+    } catch (err) {
+        const cached = await cache.match(request);
+        if (cached) {
+            return cached;
+        }
+        throw err;
+    }
+}
+
+async function staleWhileRevalidate(request) {
+    const cache = await caches.open(SHELL_CACHE);
+    const cached = await cache.match(request);
+    const network = fetch(request)
+        .then((response) => {
+            if (response.ok) {
+                cache.put(request, response.clone());
+            }
+            return response;
+        })
+        .catch(() => undefined);
+
+    if (cached) {
+        return cached;
+    }
+
+    const response = await network;
+    if (response) {
+        return response;
+    }
+
+    // Навигация без сети: отдаём закэшированный app shell
+    if (request.mode === "navigate") {
+        const shell = await cache.match("/index.html");
+        if (shell) {
+            return shell;
+        }
+    }
+    return Response.error();
+}
+
+async function trimCache(cache, maxEntries) {
+    const keys = await cache.keys();
+    // cache.keys() возвращает записи в порядке добавления
+    for (let i = 0; i < keys.length - maxEntries; i++) {
+        await cache.delete(keys[i]);
+    }
+}
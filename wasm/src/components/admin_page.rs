@@ -0,0 +1,536 @@
+use crate::route::{Link, Route};
+use crate::transport::{SharedAdminClient, SharedClient};
+use client::types::{AdminUser, Report, ReportAction, ReportReason, ReportStatus, UserRole};
+use dioxus::prelude::*;
+
+use super::post_form::category_options;
+
+const PAGE_SIZE: u32 = 20;
+
+const BUTTON_CLASS: &str = "px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-sm font-medium text-gray-700 dark:text-gray-300 bg-white dark:bg-gray-800 hover:bg-gray-50 dark:hover:bg-gray-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed";
+
+fn role_label(role: UserRole) -> &'static str {
+    match role {
+        UserRole::User => "user",
+        UserRole::Moderator => "moderator",
+        UserRole::Admin => "admin",
+    }
+}
+
+fn reason_label(reason: ReportReason) -> &'static str {
+    match reason {
+        ReportReason::Spam => "Spam",
+        ReportReason::Harassment => "Harassment",
+        ReportReason::HateSpeech => "Hate speech",
+        ReportReason::Misinformation => "Misinformation",
+        ReportReason::Inappropriate => "Inappropriate",
+        ReportReason::Other => "Other",
+    }
+}
+
+fn status_label(status: ReportStatus) -> &'static str {
+    match status {
+        ReportStatus::Open => "open",
+        ReportStatus::Hidden => "hidden",
+        ReportStatus::Dismissed => "dismissed",
+    }
+}
+
+/// Раздел администратора (`/admin`): статистика сервера, пользователи, очередь
+/// жалоб и категории.
+///
+/// Показывается только администратору; сервер всё равно проверяет роль сам.
+#[component]
+pub fn AdminPage(current_user_id: String) -> Element {
+    rsx! {
+        div {
+            class: "max-w-5xl mx-auto p-6 space-y-8",
+            h1 {
+                class: "text-3xl font-bold text-gray-900 dark:text-gray-100",
+                "Administration"
+            }
+            StatsPanel {}
+            UsersTable { current_user_id }
+            ReportsQueue {}
+            CategoryForm {}
+        }
+    }
+}
+
+/// Число пользователей, постов и новых регистраций
+#[component]
+fn StatsPanel() -> Element {
+    let client = use_context::<SharedAdminClient>();
+    let stats_resource = use_resource(move || {
+        let client = client.clone();
+        async move { client.system_stats().await }
+    });
+
+    rsx! {
+        section {
+            match stats_resource.read().as_ref() {
+                None => rsx! {
+                    p { class: "text-gray-600 dark:text-gray-400", "Loading statistics..." }
+                },
+                Some(Err(err)) => rsx! {
+                    div {
+                        class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded",
+                        p { "Failed to load statistics: {err}" }
+                    }
+                },
+                Some(Ok(stats)) => rsx! {
+                    div {
+                        class: "grid grid-cols-1 sm:grid-cols-3 gap-4",
+                        StatCard { label: "Users", value: stats.users }
+                        StatCard { label: "Posts", value: stats.posts }
+                        StatCard {
+                            label: format!("Sign-ups in {} days", stats.recent_signups_days),
+                            value: stats.recent_signups,
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[component]
+fn StatCard(label: String, value: u64) -> Element {
+    rsx! {
+        div {
+            class: "bg-white dark:bg-gray-800 rounded-lg shadow-md p-4",
+            p { class: "text-sm text-gray-600 dark:text-gray-400", "{label}" }
+            p { class: "text-2xl font-bold text-gray-900 dark:text-gray-100", "{value}" }
+        }
+    }
+}
+
+/// Пользователи в порядке регистрации с отключением и включением входа
+#[component]
+fn UsersTable(current_user_id: String) -> Element {
+    let client = use_context::<SharedAdminClient>();
+    let client_for_toggle = client.clone();
+    let mut current_page = use_signal(|| 0u32);
+    let mut error_message = use_signal(|| None::<String>);
+    // Пользователь, для которого выполняется запрос
+    let mut pending_user = use_signal(|| None::<String>);
+
+    let mut users_resource = use_resource(move || {
+        let client = client.clone();
+        let page = current_page();
+        async move { client.list_users(PAGE_SIZE, page).await }
+    });
+
+    let toggle_user = use_callback(move |user: AdminUser| {
+        let client = client_for_toggle.clone();
+        let user_id = user.id.to_string();
+        spawn(async move {
+            pending_user.set(Some(user_id.clone()));
+            error_message.set(None);
+            let result = if user.disabled_at.is_some() {
+                client.enable_user(&user_id).await
+            } else {
+                client.disable_user(&user_id).await
+            };
+            pending_user.set(None);
+            match result {
+                Ok(()) => users_resource.restart(),
+                Err(err) => {
+                    error_message.set(Some(format!("Failed to update {}: {err}", user.username)))
+                }
+            }
+        });
+    });
+
+    rsx! {
+        section {
+            h2 {
+                class: "text-xl font-semibold text-gray-900 dark:text-gray-100 mb-4",
+                "Users"
+            }
+            if let Some(error) = error_message() {
+                div {
+                    class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded mb-4",
+                    "{error}"
+                }
+            }
+            match users_resource.read().as_ref() {
+                None => rsx! {
+                    div {
+                        class: "flex justify-center items-center py-12",
+                        div {
+                            class: "animate-spin rounded-full h-12 w-12 border-b-2 border-blue-500"
+                        }
+                    }
+                },
+                Some(Err(err)) => rsx! {
+                    div {
+                        class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded",
+                        p { "Failed to load users: {err}" }
+                    }
+                },
+                Some(Ok(page)) => {
+                    let has_prev = current_page() > 0;
+                    let has_next = page.has_next;
+                    rsx! {
+                        div {
+                            class: "bg-white dark:bg-gray-800 rounded-lg shadow-md overflow-x-auto",
+                            table {
+                                class: "min-w-full text-sm text-left text-gray-700 dark:text-gray-300",
+                                thead {
+                                    class: "border-b border-gray-200 dark:border-gray-700",
+                                    tr {
+                                        th { class: "px-4 py-2", "Username" }
+                                        th { class: "px-4 py-2", "Email" }
+                                        th { class: "px-4 py-2", "Role" }
+                                        th { class: "px-4 py-2", "Registered" }
+                                        th { class: "px-4 py-2", "Status" }
+                                        th { class: "px-4 py-2" }
+                                    }
+                                }
+                                tbody {
+                                    for user in page.users.iter().cloned() {
+                                        tr {
+                                            key: "{user.id}",
+                                            class: "border-b border-gray-100 dark:border-gray-700",
+                                            td { class: "px-4 py-2 font-medium", "{user.username}" }
+                                            td { class: "px-4 py-2", "{user.email}" }
+                                            td { class: "px-4 py-2", {role_label(user.role)} }
+                                            td {
+                                                class: "px-4 py-2",
+                                                {user.created_at.format("%Y-%m-%d").to_string()}
+                                            }
+                                            td {
+                                                class: "px-4 py-2",
+                                                if let Some(disabled_at) = user.disabled_at {
+                                                    span {
+                                                        class: "text-red-600",
+                                                        "Disabled {disabled_at.format(\"%Y-%m-%d\")}"
+                                                    }
+                                                } else {
+                                                    span { class: "text-green-600", "Active" }
+                                                }
+                                            }
+                                            td {
+                                                class: "px-4 py-2 text-right",
+                                                // Отключить самого себя означало бы потерять доступ к разделу
+                                                if user.id.to_string() != current_user_id {
+                                                    button {
+                                                        class: BUTTON_CLASS,
+                                                        disabled: pending_user.read().is_some(),
+                                                        onclick: {
+                                                            let user = user.clone();
+                                                            move |_| toggle_user.call(user.clone())
+                                                        },
+                                                        if user.disabled_at.is_some() { "Enable" } else { "Disable" }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div {
+                            class: "flex justify-center items-center mt-4 space-x-4",
+                            button {
+                                class: BUTTON_CLASS,
+                                disabled: !has_prev,
+                                onclick: move |_| current_page -= 1,
+                                "← Previous"
+                            }
+                            span {
+                                class: "text-sm",
+                                "Page {current_page() + 1} of {page.total.div_ceil(PAGE_SIZE as u64).max(1)}"
+                            }
+                            button {
+                                class: BUTTON_CLASS,
+                                disabled: !has_next,
+                                onclick: move |_| current_page += 1,
+                                "Next →"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Очередь жалоб на посты: по умолчанию открытые жалобы от старых к новым,
+/// с кнопками скрытия поста и отклонения жалобы
+#[component]
+fn ReportsQueue() -> Element {
+    let client = use_context::<SharedAdminClient>();
+    let client_for_resolve = client.clone();
+    let mut current_page = use_signal(|| 0u32);
+    // `None` - жалобы в любом состоянии
+    let mut status_filter = use_signal(|| Some(ReportStatus::Open));
+    let mut error_message = use_signal(|| None::<String>);
+    // Жалоба, по которой выполняется запрос
+    let mut pending_report = use_signal(|| None::<String>);
+
+    let mut reports_resource = use_resource(move || {
+        let client = client.clone();
+        let page = current_page();
+        let status = status_filter();
+        async move { client.list_reports(status, PAGE_SIZE, page).await }
+    });
+
+    let resolve = use_callback(move |(report, action): (Report, ReportAction)| {
+        let client = client_for_resolve.clone();
+        let report_id = report.id.to_string();
+        spawn(async move {
+            pending_report.set(Some(report_id.clone()));
+            error_message.set(None);
+            let result = client.resolve_report(&report_id, action).await;
+            pending_report.set(None);
+            match result {
+                Ok(_) => reports_resource.restart(),
+                Err(err) => error_message.set(Some(format!("Failed to resolve report: {err}"))),
+            }
+        });
+    });
+
+    rsx! {
+        section {
+            div {
+                class: "flex justify-between items-center mb-4",
+                h2 {
+                    class: "text-xl font-semibold text-gray-900 dark:text-gray-100",
+                    "Reports"
+                }
+                select {
+                    class: "px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-gray-900 dark:text-gray-100 bg-white dark:bg-gray-700 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    onchange: move |evt| {
+                        let status = match evt.value().as_str() {
+                            "open" => Some(ReportStatus::Open),
+                            "hidden" => Some(ReportStatus::Hidden),
+                            "dismissed" => Some(ReportStatus::Dismissed),
+                            _ => None,
+                        };
+                        status_filter.set(status);
+                        current_page.set(0);
+                    },
+                    option { value: "open", selected: status_filter() == Some(ReportStatus::Open), "Open" }
+                    option { value: "hidden", selected: status_filter() == Some(ReportStatus::Hidden), "Hidden" }
+                    option { value: "dismissed", selected: status_filter() == Some(ReportStatus::Dismissed), "Dismissed" }
+                    option { value: "", selected: status_filter().is_none(), "All" }
+                }
+            }
+            if let Some(error) = error_message() {
+                div {
+                    class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded mb-4",
+                    "{error}"
+                }
+            }
+            match reports_resource.read().as_ref() {
+                None => rsx! {
+                    div {
+                        class: "flex justify-center items-center py-12",
+                        div {
+                            class: "animate-spin rounded-full h-12 w-12 border-b-2 border-blue-500"
+                        }
+                    }
+                },
+                Some(Err(err)) => rsx! {
+                    div {
+                        class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded",
+                        p { "Failed to load reports: {err}" }
+                    }
+                },
+                Some(Ok(page)) if page.reports.is_empty() => rsx! {
+                    p { class: "text-gray-600 dark:text-gray-400", "No reports" }
+                },
+                Some(Ok(page)) => {
+                    let has_prev = current_page() > 0;
+                    let has_next = page.has_next;
+                    rsx! {
+                        div {
+                            class: "bg-white dark:bg-gray-800 rounded-lg shadow-md overflow-x-auto",
+                            table {
+                                class: "min-w-full text-sm text-left text-gray-700 dark:text-gray-300",
+                                thead {
+                                    class: "border-b border-gray-200 dark:border-gray-700",
+                                    tr {
+                                        th { class: "px-4 py-2", "Post" }
+                                        th { class: "px-4 py-2", "Reason" }
+                                        th { class: "px-4 py-2", "Details" }
+                                        th { class: "px-4 py-2", "Reported" }
+                                        th { class: "px-4 py-2", "Status" }
+                                        th { class: "px-4 py-2" }
+                                    }
+                                }
+                                tbody {
+                                    for report in page.reports.iter().cloned() {
+                                        tr {
+                                            key: "{report.id}",
+                                            class: "border-b border-gray-100 dark:border-gray-700",
+                                            td {
+                                                class: "px-4 py-2",
+                                                Link {
+                                                    to: Route::Post { id: report.post_id.to_string() },
+                                                    class: "text-blue-600 hover:underline",
+                                                    "View post"
+                                                }
+                                            }
+                                            td { class: "px-4 py-2", {reason_label(report.reason)} }
+                                            td {
+                                                class: "px-4 py-2",
+                                                {report.details.clone().unwrap_or_default()}
+                                            }
+                                            td {
+                                                class: "px-4 py-2",
+                                                {report.created_at.format("%Y-%m-%d %H:%M").to_string()}
+                                            }
+                                            td { class: "px-4 py-2", {status_label(report.status)} }
+                                            td {
+                                                class: "px-4 py-2 text-right space-x-2 whitespace-nowrap",
+                                                if report.status == ReportStatus::Open {
+                                                    button {
+                                                        class: BUTTON_CLASS,
+                                                        disabled: pending_report.read().is_some(),
+                                                        onclick: {
+                                                            let report = report.clone();
+                                                            move |_| resolve.call((report.clone(), ReportAction::HidePost))
+                                                        },
+                                                        "Hide post"
+                                                    }
+                                                    button {
+                                                        class: BUTTON_CLASS,
+                                                        disabled: pending_report.read().is_some(),
+                                                        onclick: {
+                                                            let report = report.clone();
+                                                            move |_| resolve.call((report.clone(), ReportAction::Dismiss))
+                                                        },
+                                                        "Dismiss"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div {
+                            class: "flex justify-center items-center mt-4 space-x-4",
+                            button {
+                                class: BUTTON_CLASS,
+                                disabled: !has_prev,
+                                onclick: move |_| current_page -= 1,
+                                "← Previous"
+                            }
+                            span {
+                                class: "text-sm",
+                                "Page {current_page() + 1} of {page.total.div_ceil(PAGE_SIZE as u64).max(1)}"
+                            }
+                            button {
+                                class: BUTTON_CLASS,
+                                disabled: !has_next,
+                                onclick: move |_| current_page += 1,
+                                "Next →"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Создание категории или подкатегории постов
+#[component]
+fn CategoryForm() -> Element {
+    let client = use_context::<SharedClient>();
+    let admin_client = use_context::<SharedAdminClient>();
+    let mut name = use_signal(String::new);
+    let mut parent = use_signal(String::new);
+    let mut message = use_signal(|| None::<Result<String, String>>);
+    let mut is_saving = use_signal(|| false);
+
+    let mut categories_resource = use_resource(move || {
+        let client = client.clone();
+        async move { client.list_categories().await }
+    });
+    let options = use_memo(move || match categories_resource.read().as_ref() {
+        Some(Ok(categories)) => category_options(categories),
+        _ => Vec::new(),
+    });
+
+    let on_submit = move |evt: Event<FormData>| {
+        evt.prevent_default();
+        let name_val = name.read().trim().to_string();
+        if name_val.is_empty() {
+            message.set(Some(Err("Category name is required".to_string())));
+            return;
+        }
+        let parent_val = parent.read().clone();
+        let client = admin_client.clone();
+        spawn(async move {
+            is_saving.set(true);
+            let parent_id = Some(parent_val.as_str()).filter(|id| !id.is_empty());
+            let result = client.create_category(&name_val, parent_id).await;
+            is_saving.set(false);
+            match result {
+                Ok(category) => {
+                    message.set(Some(Ok(format!("Category \"{}\" created", category.name))));
+                    name.set(String::new());
+                    categories_resource.restart();
+                }
+                Err(err) => message.set(Some(Err(format!("Failed to create category: {err}")))),
+            }
+        });
+    };
+
+    rsx! {
+        section {
+            h2 {
+                class: "text-xl font-semibold text-gray-900 dark:text-gray-100 mb-4",
+                "New category"
+            }
+            form {
+                class: "bg-white dark:bg-gray-800 rounded-lg shadow-md p-4 flex flex-col sm:flex-row gap-4",
+                onsubmit: on_submit,
+                input {
+                    class: "flex-1 px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-gray-900 dark:text-gray-100 bg-white dark:bg-gray-700 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    r#type: "text",
+                    placeholder: "Name",
+                    value: "{name}",
+                    oninput: move |evt| name.set(evt.value()),
+                }
+                select {
+                    class: "flex-1 px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-gray-900 dark:text-gray-100 bg-white dark:bg-gray-700 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    onchange: move |evt| parent.set(evt.value()),
+                    option {
+                        value: "",
+                        selected: parent.read().is_empty(),
+                        "Top level"
+                    }
+                    for (id, label) in options() {
+                        option {
+                            key: "{id}",
+                            value: "{id}",
+                            selected: *parent.read() == id,
+                            "{label}"
+                        }
+                    }
+                }
+                button {
+                    class: "px-4 py-2 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50",
+                    r#type: "submit",
+                    disabled: is_saving(),
+                    "Create"
+                }
+            }
+            match message() {
+                Some(Ok(text)) => rsx! {
+                    p { class: "mt-2 text-sm text-green-600", "{text}" }
+                },
+                Some(Err(text)) => rsx! {
+                    p { class: "mt-2 text-sm text-red-600", "{text}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
@@ -1,86 +1,96 @@
-use super::{LoginForm, PostsList, RegisterForm};
-use crate::storage;
-use client::{blog_client::BlogClient, http_client::HttpClient};
-use dioxus::document::eval;
+use super::{
+    AdminPage, FeedPage, LoginForm, Modal, OfflineBanner, PostPage, PostsList, ProfilePage,
+    RegisterForm, ThemeToggle,
+};
+use crate::route::{use_navigator, Link, Route};
+use crate::session::use_session;
+use crate::transport::SharedClient;
+use client::token_store::{LocalStorageTokenStore, TokenStore};
+use client::types::UserRole;
 use dioxus::prelude::*;
 
-#[derive(Clone, Copy, PartialEq)]
-enum AuthView {
-    Login,
-    Register,
+/// Вошедший пользователь по утверждениям access токена.
+#[derive(Clone, PartialEq)]
+struct CurrentUser {
+    id: String,
+    username: String,
+    /// Администратор видит раздел `/admin`
+    is_admin: bool,
 }
 
 #[component]
 pub fn AuthenticatedApp() -> Element {
-    let client = use_context::<HttpClient>();
-    let mut is_authenticated = use_signal(|| false);
+    let client = use_context::<SharedClient>();
+    let client_for_logout = client.clone();
+    let client_for_count = client.clone();
+    let mut navigator = use_navigator();
+    // Пользователь из сохранённого токена; `None` - гость
+    let mut current_user = use_signal(|| None::<CurrentUser>);
     let mut is_checking_auth = use_signal(|| true);
-    let mut show_auth_modal = use_signal(|| false);
-    let mut current_auth_view = use_signal(|| AuthView::Login);
+    let mut session = use_session();
 
-    use_effect(move || {
+    let load_user = use_callback(move |()| {
         let client = client.clone();
         spawn(async move {
-            if let Some(auth_data) = storage::load_auth_data() {
-                if client.setup_auth_data(&auth_data).await.is_ok() {
-                    is_authenticated.set(true);
-                    is_checking_auth.set(false);
-                    return;
-                }
-            }
-
+            // Клиент уже восстановил токены из localStorage
+            let user = client
+                .get_auth_data()
+                .await
+                .ok()
+                .flatten()
+                .and_then(|auth| auth.claims().ok())
+                .map(|claims| CurrentUser {
+                    is_admin: claims.role() == UserRole::Admin,
+                    id: claims.sub,
+                    username: claims.user_name,
+                });
+            current_user.set(user);
             is_checking_auth.set(false);
         });
     });
 
-    let on_auth_success = move |_| {
-        is_authenticated.set(true);
-        show_auth_modal.set(false);
-    };
-
-    let on_logout = move |_| {
-        // Очищаем данные аутентификации из localStorage
-        storage::clear_auth_data();
-        is_authenticated.set(false);
-    };
-
-    let open_login = move |_| {
-        current_auth_view.set(AuthView::Login);
-        show_auth_modal.set(true);
-    };
-
-    let open_register = move |_| {
-        current_auth_view.set(AuthView::Register);
-        show_auth_modal.set(true);
-    };
+    use_effect(move || load_user.call(()));
 
-    let close_modal = move |_| {
-        show_auth_modal.set(false);
-    };
+    // Количество постов текущего пользователя для значка в навигации
+    let mut my_posts_count = use_resource(move || {
+        let client = client_for_count.clone();
+        let user_id = current_user.read().as_ref().map(|user| user.id.clone());
+        async move {
+            let user_id = user_id?;
+            client
+                .list_posts_by_author(&user_id, 1, 0)
+                .await
+                .ok()
+                .map(|page| page.total)
+        }
+    });
 
-    // Фокусируем модальное окно при открытии для работы ESC
+    // Клиент уже удалил токены истёкшей сессии; остаётся выйти из аккаунта в интерфейсе
     use_effect(move || {
-        if show_auth_modal() {
-            eval(
-                r#"
-                setTimeout(() => {
-                    const modal = document.querySelector('.modal-backdrop');
-                    if (modal) modal.focus();
-                }, 0);
-                "#,
-            );
+        if session.is_expired() {
+            current_user.set(None);
         }
     });
 
-    let switch_to_register = move |_| {
-        current_auth_view.set(AuthView::Register);
+    let on_auth_success = move |_| {
+        load_user.call(());
+        navigator.push(Route::Home);
     };
 
-    let switch_to_login = move |_| {
-        current_auth_view.set(AuthView::Login);
+    let on_logout = move |_| {
+        // Очищаем данные аутентификации из localStorage сразу, не дожидаясь сервера
+        let _ = LocalStorageTokenStore::default().clear();
+        current_user.set(None);
+
+        // Отзываем refresh токен на сервере; локальный выход не зависит от результата
+        let client = client_for_logout.clone();
+        spawn(async move {
+            let _ = client.logout().await;
+        });
     };
 
-    if is_checking_auth() {
+    // Пока не проверен токен и не прочитан адрес страницы, показываем индикатор загрузки
+    let (false, Some(route)) = (is_checking_auth(), navigator.route()) else {
         return rsx! {
             div {
                 class: "flex justify-center items-center min-h-screen",
@@ -89,13 +99,14 @@ pub fn AuthenticatedApp() -> Element {
                 }
             }
         };
-    }
+    };
+    let is_authenticated = current_user.read().is_some();
 
     rsx! {
         div {
-            // Header with auth buttons or logout
+            // Header with navigation and auth links
             nav {
-                class: "bg-white shadow-sm border-b border-gray-200",
+                class: "bg-white dark:bg-gray-800 shadow-sm border-b border-gray-200 dark:border-gray-700",
                 div {
                     class: "max-w-7xl mx-auto px-4 sm:px-6 lg:px-8",
                     div {
@@ -103,27 +114,63 @@ pub fn AuthenticatedApp() -> Element {
                         div {
                             class: "flex items-center",
                             h1 {
-                                class: "text-xl font-bold text-gray-900",
-                                "Blog Application"
+                                class: "text-xl font-bold text-gray-900 dark:text-gray-100",
+                                Link {
+                                    to: Route::Home,
+                                    "Blog Application"
+                                }
                             }
                         }
                         div {
                             class: "flex items-center space-x-4",
-                            if is_authenticated() {
+                            ThemeToggle {}
+                            if let Some(user) = current_user() {
+                                span {
+                                    class: "text-sm text-gray-600 dark:text-gray-400",
+                                    "Signed in as "
+                                    Link {
+                                        to: Route::Profile,
+                                        class: "font-medium text-gray-900 dark:text-gray-100 hover:text-blue-600",
+                                        "{user.username}"
+                                    }
+                                }
+                                if user.is_admin {
+                                    Link {
+                                        to: Route::Admin,
+                                        class: "inline-flex items-center px-4 py-2 text-sm font-medium text-gray-700 dark:text-gray-300 hover:text-blue-600",
+                                        "Admin"
+                                    }
+                                }
+                                Link {
+                                    to: Route::Following,
+                                    class: "inline-flex items-center px-4 py-2 text-sm font-medium text-gray-700 dark:text-gray-300 hover:text-blue-600",
+                                    "Following"
+                                }
+                                Link {
+                                    to: Route::Me,
+                                    class: "inline-flex items-center px-4 py-2 text-sm font-medium text-gray-700 dark:text-gray-300 hover:text-blue-600",
+                                    "My Posts"
+                                    if let Some(Some(count)) = my_posts_count.read().as_ref() {
+                                        span {
+                                            class: "ml-2 px-2 py-0.5 text-xs font-semibold text-blue-700 bg-blue-100 rounded-full",
+                                            "{count}"
+                                        }
+                                    }
+                                }
                                 button {
                                     class: "px-4 py-2 border border-transparent text-sm font-medium rounded-md text-white bg-red-600 hover:bg-red-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-red-500",
                                     onclick: on_logout,
                                     "Logout"
                                 }
                             } else {
-                                button {
-                                    class: "px-4 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500",
-                                    onclick: open_login,
+                                Link {
+                                    to: Route::Login,
+                                    class: "px-4 py-2 border border-gray-300 dark:border-gray-600 text-sm font-medium rounded-md text-gray-700 dark:text-gray-300 bg-white dark:bg-gray-800 hover:bg-gray-50 dark:hover:bg-gray-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500",
                                     "Sign In"
                                 }
-                                button {
+                                Link {
+                                    to: Route::Register,
                                     class: "px-4 py-2 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500",
-                                    onclick: open_register,
                                     "Sign Up"
                                 }
                             }
@@ -132,78 +179,178 @@ pub fn AuthenticatedApp() -> Element {
                 }
             }
 
-            // Main content
-            main {
-                PostsList {
-                    is_authenticated: is_authenticated()
+            OfflineBanner {}
+
+            if session.is_expired() {
+                Modal {
+                    title: "Session expired",
+                    on_close: move |_| session.dismiss(),
+                    p {
+                        class: "text-sm text-gray-600 dark:text-gray-400 mb-6",
+                        "Your session has expired, please sign in again."
+                    }
+                    LoginForm {
+                        on_success: move |_| {
+                            session.dismiss();
+                            load_user.call(());
+                        },
+                        on_switch_to_register: move |_| {
+                            session.dismiss();
+                            navigator.push(Route::Register);
+                        },
+                    }
+                    button {
+                        class: "mt-4 w-full text-sm text-gray-600 dark:text-gray-400 hover:text-blue-600",
+                        r#type: "button",
+                        onclick: move |_| session.dismiss(),
+                        "Continue as guest"
+                    }
                 }
             }
 
-            // Auth Modal
-            if show_auth_modal() {
-                div {
-                    class: "fixed inset-0 modal-backdrop overflow-y-auto h-full w-full z-50 flex items-center justify-center",
-                    onclick: close_modal,
-                    onkeyup: move |evt: Event<KeyboardData>| {
-                        if evt.key() == Key::Escape {
-                            show_auth_modal.set(false);
+            // Main content
+            main {
+                match route {
+                    Route::Home => rsx! {
+                        PostsList {
+                            is_authenticated,
+                            current_user_id: current_user.read().as_ref().map(|user| user.id.clone()),
+                            on_posts_changed: move |_| my_posts_count.restart(),
                         }
                     },
-                    tabindex: 0,
-                    div {
-                        class: "relative bg-white rounded-lg shadow-xl max-w-md w-full mx-4",
-                        onclick: move |evt| evt.stop_propagation(),
-
-                        // Close button
-                        button {
-                            class: "absolute top-4 right-4 text-gray-400 hover:text-gray-600",
-                            onclick: close_modal,
-                            svg {
-                                class: "h-6 w-6",
-                                fill: "none",
-                                view_box: "0 0 24 24",
-                                stroke: "currentColor",
-                                path {
-                                    stroke_linecap: "round",
-                                    stroke_linejoin: "round",
-                                    stroke_width: "2",
-                                    d: "M6 18L18 6M6 6l12 12"
+                    Route::Post { id } => rsx! {
+                        PostPage {
+                            id,
+                            current_user_id: current_user.read().as_ref().map(|user| user.id.clone()),
+                        }
+                    },
+                    Route::Following => match current_user() {
+                        Some(_) => rsx! {
+                            FeedPage {}
+                        },
+                        None => rsx! {
+                            AuthCard {
+                                title: "Sign in to see posts from authors you follow",
+                                LoginForm {
+                                    on_success: move |_| load_user.call(()),
+                                    on_switch_to_register: move |_| navigator.push(Route::Register),
+                                }
+                            }
+                        },
+                    },
+                    Route::Me => match current_user() {
+                        Some(user) => rsx! {
+                            PostsList {
+                                is_authenticated: true,
+                                author_id: user.id.clone(),
+                                current_user_id: user.id,
+                                on_posts_changed: move |_| my_posts_count.restart(),
+                            }
+                        },
+                        // После входа пользователь остаётся на странице своих постов
+                        None => rsx! {
+                            AuthCard {
+                                title: "Sign in to see your posts",
+                                LoginForm {
+                                    on_success: move |_| load_user.call(()),
+                                    on_switch_to_register: move |_| navigator.push(Route::Register),
+                                }
+                            }
+                        },
+                    },
+                    Route::Profile => match current_user() {
+                        Some(user) => rsx! {
+                            ProfilePage { user_id: user.id }
+                        },
+                        None => rsx! {
+                            AuthCard {
+                                title: "Sign in to see your profile",
+                                LoginForm {
+                                    on_success: move |_| load_user.call(()),
+                                    on_switch_to_register: move |_| navigator.push(Route::Register),
                                 }
                             }
+                        },
+                    },
+                    Route::Admin => match current_user() {
+                        Some(user) if user.is_admin => rsx! {
+                            AdminPage { current_user_id: user.id }
+                        },
+                        Some(_) => rsx! {
+                            div {
+                                class: "text-center py-12",
+                                p {
+                                    class: "text-gray-600 dark:text-gray-400 text-lg mb-4",
+                                    "This page is available to administrators only."
+                                }
+                                Link {
+                                    to: Route::Home,
+                                    class: "text-blue-600 hover:text-blue-800",
+                                    "← Back to posts"
+                                }
+                            }
+                        },
+                        None => rsx! {
+                            AuthCard {
+                                title: "Sign in as an administrator",
+                                LoginForm {
+                                    on_success: move |_| load_user.call(()),
+                                    on_switch_to_register: move |_| navigator.push(Route::Register),
+                                }
+                            }
+                        },
+                    },
+                    Route::Login => rsx! {
+                        AuthCard {
+                            title: "Sign In",
+                            LoginForm {
+                                on_success: on_auth_success,
+                                on_switch_to_register: move |_| navigator.push(Route::Register),
+                            }
                         }
-
-                        // Auth form content
+                    },
+                    Route::Register => rsx! {
+                        AuthCard {
+                            title: "Create Account",
+                            RegisterForm {
+                                on_success: on_auth_success,
+                                on_switch_to_login: move |_| navigator.push(Route::Login),
+                            }
+                        }
+                    },
+                    Route::NotFound { path } => rsx! {
                         div {
-                            class: "p-6",
-                            match current_auth_view() {
-                                AuthView::Login => rsx! {
-                                    div {
-                                        h2 {
-                                            class: "text-2xl font-bold text-gray-900 mb-4",
-                                            "Sign In"
-                                        }
-                                        LoginForm {
-                                            on_success: on_auth_success,
-                                            on_switch_to_register: switch_to_register,
-                                        }
-                                    }
-                                },
-                                AuthView::Register => rsx! {
-                                    div {
-                                        h2 {
-                                            class: "text-2xl font-bold text-gray-900 mb-4",
-                                            "Create Account"
-                                        }
-                                        RegisterForm {
-                                            on_success: on_auth_success,
-                                            on_switch_to_login: switch_to_login,
-                                        }
-                                    }
-                                },
+                            class: "text-center py-12",
+                            p {
+                                class: "text-gray-600 dark:text-gray-400 text-lg mb-4",
+                                "Page {path} not found."
+                            }
+                            Link {
+                                to: Route::Home,
+                                class: "text-blue-600 hover:text-blue-800",
+                                "← Back to posts"
                             }
                         }
-                    }
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// Карточка с формой входа или регистрации по центру страницы
+#[component]
+fn AuthCard(title: String, children: Element) -> Element {
+    rsx! {
+        div {
+            class: "flex justify-center px-4 py-12",
+            div {
+                class: "bg-white dark:bg-gray-800 rounded-lg shadow-xl max-w-md w-full p-6",
+                h2 {
+                    class: "text-2xl font-bold text-gray-900 dark:text-gray-100 mb-4",
+                    "{title}"
                 }
+                {children}
             }
         }
     }
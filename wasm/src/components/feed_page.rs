@@ -0,0 +1,109 @@
+use crate::route::{Link, Route};
+use crate::transport::SharedClient;
+use dioxus::prelude::*;
+
+use super::PostCard;
+
+const PAGE_SIZE: u32 = 10;
+
+/// Лента (`/following`): опубликованные посты авторов, на которых подписан пользователь.
+#[component]
+pub fn FeedPage() -> Element {
+    let client = use_context::<SharedClient>();
+    let mut current_page = use_signal(|| 0u32);
+
+    let feed_resource = use_resource(move || {
+        let client = client.clone();
+        let page = current_page();
+        async move { client.get_feed(PAGE_SIZE, page).await }
+    });
+
+    rsx! {
+        div {
+            class: "max-w-4xl mx-auto p-6",
+            h1 {
+                class: "text-3xl font-bold text-gray-900 dark:text-gray-100 mb-6",
+                "Following"
+            }
+
+            match feed_resource.read().as_ref() {
+                None => rsx! {
+                    div {
+                        class: "flex justify-center items-center py-12",
+                        div {
+                            class: "animate-spin rounded-full h-12 w-12 border-b-2 border-blue-500"
+                        }
+                    }
+                },
+                Some(Err(err)) => rsx! {
+                    div {
+                        class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded",
+                        p { "Error: {err}" }
+                    }
+                },
+                Some(Ok(page)) if page.posts.is_empty() && current_page() == 0 => rsx! {
+                    div {
+                        class: "text-center py-12",
+                        p {
+                            class: "text-gray-600 dark:text-gray-400 text-lg mb-4",
+                            "No posts from authors you follow yet. Open a post and follow its author."
+                        }
+                        Link {
+                            to: Route::Home,
+                            class: "text-blue-600 hover:text-blue-800",
+                            "← Browse all posts"
+                        }
+                    }
+                },
+                Some(Ok(page)) => {
+                    let has_prev = current_page() > 0;
+                    let has_next = page.has_next;
+                    rsx! {
+                        div {
+                            class: "posts-grid",
+                            for post in page.posts.iter() {
+                                PostCard {
+                                    key: "{post.id}",
+                                    id: post.id.to_string(),
+                                    title: post.title.clone(),
+                                    content: post.content.clone(),
+                                    summary: post.summary.clone(),
+                                    created_at: post.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                                    updated_at: post.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+                                    tags: post.tags.clone(),
+                                    like_count: post.like_count,
+                                    liked_by_me: post.liked_by_me,
+                                    is_authenticated: true,
+                                    on_edit: None,
+                                    on_delete: None,
+                                    on_tag: None,
+                                    on_publish: None,
+                                    on_like: None,
+                                }
+                            }
+                        }
+                        div {
+                            class: "flex justify-center items-center mt-8 space-x-4",
+                            button {
+                                class: "px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-sm font-medium text-gray-700 dark:text-gray-300 bg-white dark:bg-gray-800 hover:bg-gray-50 dark:hover:bg-gray-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed",
+                                disabled: !has_prev,
+                                onclick: move |_| current_page -= 1,
+                                "← Previous"
+                            }
+                            span {
+                                class: "text-sm",
+                                "Page {current_page() + 1}"
+                            }
+                            button {
+                                class: "px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-sm font-medium text-gray-700 dark:text-gray-300 bg-white dark:bg-gray-800 hover:bg-gray-50 dark:hover:bg-gray-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed",
+                                disabled: !has_next,
+                                onclick: move |_| current_page += 1,
+                                "Next →"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Оформление полей форм с клиентской валидацией.
+
+const INPUT_CLASS: &str = "appearance-none block w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm placeholder-gray-400 text-gray-900 dark:text-gray-100 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm";
+const INPUT_ERROR_CLASS: &str = "appearance-none block w-full px-3 py-2 border border-red-400 rounded-md shadow-sm placeholder-gray-400 text-gray-900 dark:text-gray-100 focus:outline-none focus:ring-red-500 focus:border-red-500 sm:text-sm";
+
+/// Класс поля ввода; поле с ошибкой подсвечивается красной рамкой
+pub(super) fn input_class(error: &Option<String>) -> &'static str {
+    if error.is_some() {
+        INPUT_ERROR_CLASS
+    } else {
+        INPUT_CLASS
+    }
+}
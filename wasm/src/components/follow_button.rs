@@ -0,0 +1,70 @@
+use crate::transport::SharedClient;
+use dioxus::prelude::*;
+
+/// Кнопка подписки на автора; состояние подписки загружается с сервера.
+#[component]
+pub fn FollowButton(author_id: String, author_username: String) -> Element {
+    let client = use_context::<SharedClient>();
+    let client_for_toggle = client.clone();
+    let mut following = use_signal(|| None::<bool>);
+    let mut is_saving = use_signal(|| false);
+    let mut error_message = use_signal(|| None::<String>);
+
+    let author_id_for_load = author_id.clone();
+    use_effect(use_reactive!(|(author_id_for_load,)| {
+        let client = client.clone();
+        spawn(async move {
+            following.set(client.is_following(&author_id_for_load).await.ok());
+        });
+    }));
+
+    let on_toggle = move |_| {
+        let Some(is_following) = following() else {
+            return;
+        };
+        let client = client_for_toggle.clone();
+        let author_id = author_id.clone();
+        spawn(async move {
+            is_saving.set(true);
+            error_message.set(None);
+            let result = if is_following {
+                client.unfollow_user(&author_id).await
+            } else {
+                client.follow_user(&author_id).await
+            };
+            is_saving.set(false);
+            match result {
+                Ok(()) => following.set(Some(!is_following)),
+                Err(err) => error_message.set(Some(format!("Failed to update follow: {err}"))),
+            }
+        });
+    };
+
+    rsx! {
+        div {
+            class: "flex items-center space-x-3",
+            if let Some(is_following) = following() {
+                button {
+                    class: if is_following {
+                        "px-3 py-1 text-sm font-medium text-gray-700 dark:text-gray-300 bg-gray-100 dark:bg-gray-700 rounded hover:bg-gray-200 dark:hover:bg-gray-600 disabled:opacity-50"
+                    } else {
+                        "px-3 py-1 text-sm font-medium text-white bg-blue-600 rounded hover:bg-blue-700 disabled:opacity-50"
+                    },
+                    disabled: is_saving(),
+                    onclick: on_toggle,
+                    if is_following {
+                        "Unfollow {author_username}"
+                    } else {
+                        "Follow {author_username}"
+                    }
+                }
+            }
+            if let Some(err) = error_message() {
+                span {
+                    class: "text-sm text-red-600",
+                    "{err}"
+                }
+            }
+        }
+    }
+}
@@ -1,34 +1,99 @@
-use client::{blog_client::BlogClient, http_client::HttpClient};
+use crate::transport::SharedClient;
+use crate::validation::{
+    server_field_errors, validate_required, validate_totp_code, FieldErrors, TOTP_CODE_LEN,
+};
+use client::error::ClientError;
 use dioxus::prelude::*;
 
+use super::field::input_class;
+
 #[component]
 pub fn LoginForm(on_success: EventHandler<()>, on_switch_to_register: EventHandler<()>) -> Element {
-    let client = use_context::<HttpClient>();
+    let client = use_context::<SharedClient>();
 
     let mut username = use_signal(String::new);
     let mut password = use_signal(String::new);
+    let mut totp_code = use_signal(String::new);
+    // Поле кода показывается после того, как сервер запросил второй фактор
+    let mut totp_required = use_signal(|| false);
     let mut error_message = use_signal(|| None::<String>);
     let mut is_loading = use_signal(|| false);
+    // Пустые поля подсвечиваются только после первой попытки входа
+    let mut submitted = use_signal(|| false);
+    let mut server_errors = use_signal(FieldErrors::default);
+
+    let username_error = use_memo(move || {
+        let value = username();
+        server_errors.read().get("username").cloned().or_else(|| {
+            submitted()
+                .then(|| validate_required(&value, "Username"))
+                .flatten()
+        })
+    });
+
+    let password_error = use_memo(move || {
+        let value = password();
+        server_errors.read().get("password").cloned().or_else(|| {
+            submitted()
+                .then(|| validate_required(&value, "Password"))
+                .flatten()
+        })
+    });
+
+    let totp_error = use_memo(move || {
+        let value = totp_code();
+        if !totp_required() {
+            return None;
+        }
+        server_errors.read().get("code").cloned().or_else(|| {
+            (!value.is_empty())
+                .then(|| validate_totp_code(&value))
+                .flatten()
+        })
+    });
 
     let on_submit = move |evt: Event<FormData>| {
         evt.prevent_default();
 
+        submitted.set(true);
+        let missing_code = totp_required() && validate_totp_code(&totp_code.read()).is_some();
+        if username_error().is_some() || password_error().is_some() || missing_code {
+            return;
+        }
+
         let client = client.clone();
         spawn(async move {
             is_loading.set(true);
             error_message.set(None);
+            server_errors.set(FieldErrors::default());
 
             let username_val = username.read().clone();
             let password_val = password.read().clone();
 
-            match client.login(&username_val, &password_val).await {
+            let result = if totp_required() {
+                let code_val = totp_code.read().trim().to_string();
+                client
+                    .login_with_totp(&username_val, &password_val, &code_val)
+                    .await
+            } else {
+                client.login(&username_val, &password_val).await
+            };
+
+            match result {
                 Ok(_user_id) => {
                     is_loading.set(false);
                     on_success.call(());
                 }
+                Err(ClientError::TwoFactorRequired { .. }) => {
+                    is_loading.set(false);
+                    totp_required.set(true);
+                }
                 Err(err) => {
                     is_loading.set(false);
-                    error_message.set(Some(format!("Login failed: {:?}", err)));
+                    match server_field_errors(&err) {
+                        Some(fields) => server_errors.set(fields),
+                        None => error_message.set(Some(format!("Login failed: {:?}", err))),
+                    }
                 }
             }
         });
@@ -48,31 +113,67 @@ pub fn LoginForm(on_success: EventHandler<()>, on_switch_to_register: EventHandl
 
             div {
                 label {
-                    class: "block text-sm font-medium text-gray-700 mb-1",
+                    class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
                     "Username"
                 }
                 input {
-                    class: "appearance-none block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm placeholder-gray-400 text-gray-900 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    class: input_class(&username_error()),
                     r#type: "text",
                     placeholder: "Enter your username",
-                    required: true,
                     value: "{username}",
-                    oninput: move |evt| username.set(evt.value().clone()),
+                    oninput: move |evt| {
+                        server_errors.write().remove("username");
+                        username.set(evt.value().clone());
+                    },
+                }
+                if let Some(err) = username_error() {
+                    p { class: "mt-1 text-sm text-red-600", "{err}" }
                 }
             }
 
             div {
                 label {
-                    class: "block text-sm font-medium text-gray-700 mb-1",
+                    class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
                     "Password"
                 }
                 input {
-                    class: "appearance-none block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm placeholder-gray-400 text-gray-900 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    class: input_class(&password_error()),
                     r#type: "password",
                     placeholder: "Enter your password",
-                    required: true,
                     value: "{password}",
-                    oninput: move |evt| password.set(evt.value().clone()),
+                    oninput: move |evt| {
+                        server_errors.write().remove("password");
+                        password.set(evt.value().clone());
+                    },
+                }
+                if let Some(err) = password_error() {
+                    p { class: "mt-1 text-sm text-red-600", "{err}" }
+                }
+            }
+
+            if totp_required() {
+                div {
+                    label {
+                        class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                        "Authentication code"
+                    }
+                    input {
+                        class: input_class(&totp_error()),
+                        r#type: "text",
+                        inputmode: "numeric",
+                        autocomplete: "one-time-code",
+                        placeholder: "6-digit code from your authenticator app",
+                        required: true,
+                        maxlength: TOTP_CODE_LEN as i64,
+                        value: "{totp_code}",
+                        oninput: move |evt| {
+                            server_errors.write().remove("code");
+                            totp_code.set(evt.value().clone());
+                        },
+                    }
+                    if let Some(err) = totp_error() {
+                        p { class: "mt-1 text-sm text-red-600", "{err}" }
+                    }
                 }
             }
 
@@ -90,7 +191,7 @@ pub fn LoginForm(on_success: EventHandler<()>, on_switch_to_register: EventHandl
             }
 
             div {
-                class: "text-center text-sm text-gray-600",
+                class: "text-center text-sm text-gray-600 dark:text-gray-400",
                 "Don't have an account? "
                 button {
                     class: "font-medium text-blue-600 hover:text-blue-500",
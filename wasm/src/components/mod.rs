@@ -2,6 +2,11 @@
 //! They can be used to defined common UI elements like buttons, forms, and modals. In this template, we define a Hero
 //! component  to be used in our app.
 
+mod field;
+
+mod modal;
+pub use modal::Modal;
+
 mod post_card;
 pub use post_card::PostCard;
 
@@ -22,3 +27,24 @@ pub use post_form::PostForm;
 
 mod post_view;
 pub use post_view::PostView;
+
+mod post_page;
+pub use post_page::PostPage;
+
+mod profile_page;
+pub use profile_page::ProfilePage;
+
+mod feed_page;
+pub use feed_page::FeedPage;
+
+mod admin_page;
+pub use admin_page::AdminPage;
+
+mod follow_button;
+pub use follow_button::FollowButton;
+
+mod offline_banner;
+pub use offline_banner::OfflineBanner;
+
+mod theme_toggle;
+pub use theme_toggle::ThemeToggle;
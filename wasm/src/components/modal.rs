@@ -0,0 +1,123 @@
+use std::rc::Rc;
+
+use dioxus::document::{eval, Eval};
+use dioxus::prelude::*;
+use uuid::Uuid;
+
+/// Переводит фокус в окно, удерживает его внутри по Tab/Shift+Tab и возвращает
+/// фокус на прежний элемент, когда окно закрывается.
+const FOCUS_TRAP_JS: &str = r#"
+    const id = await dioxus.recv();
+    const dialog = document.getElementById(id);
+    const previous = document.activeElement;
+    const focusable = () => Array.from(dialog.querySelectorAll(
+        'a[href], button:not([disabled]), input:not([disabled]), textarea:not([disabled]), select:not([disabled]), [tabindex]:not([tabindex="-1"])'
+    )).filter((el) => el.offsetParent !== null);
+
+    // Первым получает фокус элемент содержимого, а не кнопка закрытия
+    const initial = focusable().find((el) => !el.hasAttribute("data-modal-close"));
+    (initial || dialog).focus();
+
+    dialog.addEventListener("keydown", (event) => {
+        if (event.key !== "Tab") return;
+        const items = focusable();
+        if (items.length === 0) {
+            event.preventDefault();
+            return;
+        }
+        const first = items[0];
+        const last = items[items.length - 1];
+        if (event.shiftKey && document.activeElement === first) {
+            event.preventDefault();
+            last.focus();
+        } else if (!event.shiftKey && document.activeElement === last) {
+            event.preventDefault();
+            first.focus();
+        }
+    });
+
+    await dioxus.recv();
+    if (previous && document.contains(previous)) previous.focus();
+"#;
+
+/// Модальное окно с заголовком и кнопкой закрытия.
+///
+/// Закрывается по ESC, кнопке «×» и клику по затемнённому фону; пока окно открыто,
+/// фокус клавиатуры не выходит за его пределы.
+#[component]
+pub fn Modal(
+    title: String,
+    on_close: EventHandler<()>,
+    /// Tailwind-класс максимальной ширины окна
+    #[props(default = "max-w-md".to_string())]
+    max_width: String,
+    children: Element,
+) -> Element {
+    let dialog_id = use_hook(|| format!("modal-{}", Uuid::new_v4()));
+    let title_id = format!("{dialog_id}-title");
+
+    let focus_trap = use_hook({
+        let dialog_id = dialog_id.clone();
+        move || {
+            let focus_trap: Rc<Eval> = Rc::new(eval(FOCUS_TRAP_JS));
+            let _ = focus_trap.send(dialog_id);
+            focus_trap
+        }
+    });
+    // Второе сообщение возвращает фокус туда, где он был до открытия окна
+    use_drop(move || {
+        let _ = focus_trap.send(());
+    });
+
+    rsx! {
+        div {
+            class: "fixed inset-0 modal-backdrop overflow-y-auto h-full w-full z-50 flex items-center justify-center",
+            onclick: move |_| on_close.call(()),
+            onkeydown: move |evt: Event<KeyboardData>| {
+                if evt.key() == Key::Escape {
+                    evt.stop_propagation();
+                    on_close.call(());
+                }
+            },
+            div {
+                id: "{dialog_id}",
+                class: "relative bg-white dark:bg-gray-800 rounded-lg shadow-xl {max_width} w-full mx-4 focus:outline-none",
+                role: "dialog",
+                "aria-modal": "true",
+                "aria-labelledby": "{title_id}",
+                tabindex: -1,
+                onclick: move |evt| evt.stop_propagation(),
+
+                button {
+                    class: "absolute top-4 right-4 text-gray-400 hover:text-gray-600",
+                    r#type: "button",
+                    "aria-label": "Close",
+                    "data-modal-close": "true",
+                    onclick: move |_| on_close.call(()),
+                    svg {
+                        class: "h-6 w-6",
+                        fill: "none",
+                        view_box: "0 0 24 24",
+                        stroke: "currentColor",
+                        path {
+                            stroke_linecap: "round",
+                            stroke_linejoin: "round",
+                            stroke_width: "2",
+                            d: "M6 18L18 6M6 6l12 12"
+                        }
+                    }
+                }
+
+                div {
+                    class: "p-6",
+                    h2 {
+                        id: "{title_id}",
+                        class: "text-xl font-bold text-gray-900 dark:text-gray-100 mb-4 pr-8",
+                        "{title}"
+                    }
+                    {children}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::offline::OfflineState;
+use crate::transport::SharedClient;
+use dioxus::document::eval;
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+
+/// Пауза между попытками отправить отложенные изменения, пока сервер недоступен
+const QUEUE_RETRY_MS: u32 = 30_000;
+
+/// Отправляет отложенные изменения и запоминает те, что отклонил сервер.
+async fn replay_queue(
+    client: SharedClient,
+    offline: OfflineState,
+    mut rejected: Signal<Vec<String>>,
+) {
+    match offline.replay(&client).await {
+        Ok(summary) => {
+            for (operation, err) in summary.rejected {
+                rejected
+                    .write()
+                    .push(format!("{}: {}", operation.describe(), err));
+            }
+        }
+        Err(err) => eprintln!("Failed to replay offline changes: {:?}", err),
+    }
+}
+
+/// Баннер, который показывается, пока браузер находится офлайн или есть
+/// изменения, ожидающие отправки.
+///
+/// Состояние сети берётся из `navigator.onLine` и событий `online`/`offline`.
+/// При восстановлении связи (и периодически, пока очередь не пуста) отложенные
+/// изменения отправляются на сервер.
+#[component]
+pub fn OfflineBanner() -> Element {
+    let client = use_context::<SharedClient>();
+    let offline = use_context::<OfflineState>();
+    let pending = offline.pending();
+    let mut is_online = use_signal(|| true);
+    let mut rejected = use_signal(Vec::<String>::new);
+
+    let client_for_network = client.clone();
+    let offline_for_network = offline.clone();
+    use_future(move || {
+        let client = client_for_network.clone();
+        let offline = offline_for_network.clone();
+        async move {
+            let mut network = eval(
+                r#"
+                dioxus.send(navigator.onLine);
+                window.addEventListener("online", () => dioxus.send(true));
+                window.addEventListener("offline", () => dioxus.send(false));
+                await new Promise(() => {});
+                "#,
+            );
+
+            while let Ok(online) = network.recv::<bool>().await {
+                is_online.set(online);
+                if online && !pending.read().is_empty() {
+                    replay_queue(client.clone(), offline.clone(), rejected).await;
+                }
+            }
+        }
+    });
+
+    // Сервер мог быть недоступен при работающей сети: событие `online` тогда не придёт
+    use_future(move || {
+        let client = client.clone();
+        let offline = offline.clone();
+        async move {
+            loop {
+                TimeoutFuture::new(QUEUE_RETRY_MS).await;
+                if is_online() && !pending.read().is_empty() {
+                    replay_queue(client.clone(), offline.clone(), rejected).await;
+                }
+            }
+        }
+    });
+
+    let pending_operations = pending.read().clone();
+    if is_online() && pending_operations.is_empty() && rejected.read().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "bg-yellow-100 border-b border-yellow-300 text-yellow-800 text-sm text-center px-4 py-2",
+            if !is_online() {
+                p {
+                    "You are offline. Showing cached posts; your changes will be sent when the connection is restored."
+                }
+            }
+            if !pending_operations.is_empty() {
+                p {
+                    class: "font-medium",
+                    "{pending_operations.len()} change(s) waiting to sync"
+                }
+                ul {
+                    for operation in pending_operations.iter() {
+                        li { "{operation.describe()}" }
+                    }
+                }
+            }
+            if !rejected.read().is_empty() {
+                div {
+                    class: "text-red-700 mt-1",
+                    p { "Some offline changes were rejected by the server:" }
+                    ul {
+                        for message in rejected.read().iter() {
+                            li { "{message}" }
+                        }
+                    }
+                    button {
+                        class: "underline",
+                        onclick: move |_| rejected.write().clear(),
+                        "Dismiss"
+                    }
+                }
+            }
+        }
+    }
+}
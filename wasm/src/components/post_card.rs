@@ -1,37 +1,79 @@
+use crate::route::{Link, Route};
+use crate::search;
 use dioxus::prelude::*;
 
-type PostViewData = (String, String, String, String, String);
-type PostEditData = (String, String, String);
+/// ID, заголовок, содержимое, описание, теги и ID категории поста
+type PostEditData = (String, String, String, String, Vec<String>, Option<String>);
+/// ID поста и требуемое состояние: `true` - опубликовать, `false` - вернуть в черновики
+type PublishData = (String, bool);
+/// ID поста и требуемое состояние: `true` - поставить лайк, `false` - снять
+type LikeData = (String, bool);
 
 #[component]
 pub fn PostCard(
     id: String,
     title: String,
     content: String,
+    /// Описание поста, которое показывается в карточке вместо содержимого
+    summary: String,
     created_at: String,
     updated_at: String,
+    #[props(default = Vec::new())] tags: Vec<String>,
+    #[props(default)] category_id: Option<String>,
+    /// Поисковый запрос, вхождения которого выделяются в заголовке
+    #[props(default)]
+    highlight: Option<String>,
+    #[props(default = false)] is_draft: bool,
+    /// Пост ещё не подтверждён сервером: карточка приглушена и не реагирует на клики
+    #[props(default = false)]
+    is_pending: bool,
+    #[props(default = 0)] like_count: u64,
+    #[props(default = false)] liked_by_me: bool,
     #[props(default = false)] is_authenticated: bool,
-    on_view: Option<EventHandler<PostViewData>>,
+    /// Пост текущего пользователя: показываются кнопки правки, публикации и удаления
+    #[props(default = false)]
+    is_owner: bool,
     on_edit: Option<EventHandler<PostEditData>>,
     on_delete: Option<EventHandler<String>>,
+    on_tag: Option<EventHandler<String>>,
+    on_publish: Option<EventHandler<PublishData>>,
+    on_like: Option<EventHandler<LikeData>>,
 ) -> Element {
     rsx! {
         div {
-            class: "post-card bg-white rounded-lg shadow-md p-6 mb-4 hover:shadow-lg transition-shadow",
+            class: if is_pending {
+                "post-card bg-white dark:bg-gray-800 rounded-lg shadow-md p-6 mb-4 opacity-60 pointer-events-none"
+            } else {
+                "post-card bg-white dark:bg-gray-800 rounded-lg shadow-md p-6 mb-4 hover:shadow-lg transition-shadow"
+            },
             div {
                 class: "post-header mb-3",
                 div {
                     class: "flex justify-between items-start",
                     h2 {
-                        class: "text-2xl font-bold text-gray-800 flex-1 cursor-pointer hover:text-blue-600 transition-colors",
-                        onclick: move |_| {
-                            if let Some(on_view_handler) = on_view {
-                                on_view_handler.call((id.clone(), title.clone(), content.clone(), created_at.clone(), updated_at.clone()));
+                        class: "text-2xl font-bold text-gray-800 dark:text-gray-100 flex-1",
+                        Link {
+                            to: Route::Post { id: id.clone() },
+                            class: "hover:text-blue-600 transition-colors",
+                            for (part, found) in search::highlight(&title, highlight.as_deref().unwrap_or_default()) {
+                                if found {
+                                    mark {
+                                        class: "bg-yellow-200 rounded-sm",
+                                        "{part}"
+                                    }
+                                } else {
+                                    "{part}"
+                                }
                             }
-                        },
-                        "{title}"
+                        }
                     }
-                    if is_authenticated {
+                    if is_draft {
+                        span {
+                            class: "ml-3 mt-1 px-2 py-0.5 text-xs font-semibold uppercase text-yellow-800 bg-yellow-100 rounded",
+                            "Draft"
+                        }
+                    }
+                    if is_owner {
                         div {
                             class: "flex space-x-2 ml-4",
                             if let Some(on_edit_handler) = on_edit {
@@ -41,11 +83,24 @@ pub fn PostCard(
                                         let id = id.clone();
                                         let title = title.clone();
                                         let content = content.clone();
-                                        move |_| on_edit_handler.call((id.clone(), title.clone(), content.clone()))
+                                        let summary = summary.clone();
+                                        let tags = tags.clone();
+                                        let category_id = category_id.clone();
+                                        move |_| on_edit_handler.call((id.clone(), title.clone(), content.clone(), summary.clone(), tags.clone(), category_id.clone()))
                                     },
                                     "Edit"
                                 }
                             }
+                            if let Some(on_publish_handler) = on_publish {
+                                button {
+                                    class: "px-3 py-1 text-sm font-medium text-green-700 bg-green-50 rounded hover:bg-green-100 focus:outline-none focus:ring-2 focus:ring-green-500",
+                                    onclick: {
+                                        let id = id.clone();
+                                        move |_| on_publish_handler.call((id.clone(), is_draft))
+                                    },
+                                    if is_draft { "Publish" } else { "Unpublish" }
+                                }
+                            }
                             if let Some(on_delete_handler) = on_delete {
                                 button {
                                     class: "px-3 py-1 text-sm font-medium text-red-600 bg-red-50 rounded hover:bg-red-100 focus:outline-none focus:ring-2 focus:ring-red-500",
@@ -71,8 +126,46 @@ pub fn PostCard(
                 }
             }
             div {
-                class: "post-content text-gray-700 line-clamp-2",
-                p { "{content}" }
+                class: "post-content text-gray-700 dark:text-gray-300 line-clamp-2",
+                p { "{summary}" }
+            }
+            if !tags.is_empty() {
+                div {
+                    class: "flex flex-wrap gap-2 mt-3",
+                    for tag in tags.iter().cloned() {
+                        button {
+                            key: "{tag}",
+                            class: "px-2 py-0.5 text-xs font-medium text-gray-600 dark:text-gray-400 bg-gray-100 dark:bg-gray-700 rounded-full hover:bg-blue-100 hover:text-blue-700",
+                            onclick: {
+                                let tag = tag.clone();
+                                move |_| {
+                                    if let Some(on_tag_handler) = on_tag {
+                                        on_tag_handler.call(tag.clone());
+                                    }
+                                }
+                            },
+                            "#{tag}"
+                        }
+                    }
+                }
+            }
+            div {
+                class: "mt-3",
+                button {
+                    class: if liked_by_me {
+                        "text-sm font-medium text-red-600 hover:text-red-700 disabled:cursor-default"
+                    } else {
+                        "text-sm font-medium text-gray-500 hover:text-red-600 disabled:cursor-default disabled:hover:text-gray-500"
+                    },
+                    title: if is_authenticated { "" } else { "Sign in to like posts" },
+                    disabled: !is_authenticated,
+                    onclick: move |_| {
+                        if let Some(on_like_handler) = on_like {
+                            on_like_handler.call((id.clone(), !liked_by_me));
+                        }
+                    },
+                    if liked_by_me { "♥ {like_count}" } else { "♡ {like_count}" }
+                }
             }
         }
     }
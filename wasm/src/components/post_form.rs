@@ -1,9 +1,20 @@
-use client::{blog_client::BlogClient, http_client::HttpClient};
+use crate::markdown;
+use crate::transport::SharedClient;
+use crate::validation::{
+    validate_post_content, validate_post_summary, validate_post_title, validate_tags, FieldErrors,
+    MAX_TAGS_PER_POST, POST_SUMMARY_MAX_LEN, POST_TITLE_MAX_LEN,
+};
+use client::offline_queue::PendingOperation;
+use client::types::Category;
 use dioxus::prelude::*;
+use uuid::Uuid;
+
+use super::field::input_class;
 
 #[derive(Props, Clone, PartialEq)]
 pub struct PostFormProps {
-    pub on_success: EventHandler<()>,
+    /// Получает операцию создания или обновления поста; отправляет её владелец формы
+    pub on_submit: EventHandler<PendingOperation>,
     pub on_cancel: EventHandler<()>,
     #[props(default = None)]
     pub post_id: Option<String>,
@@ -11,58 +22,185 @@ pub struct PostFormProps {
     pub initial_title: String,
     #[props(default = String::new())]
     pub initial_content: String,
+    /// Описание поста, которое показывается в списках
+    #[props(default = String::new())]
+    pub initial_summary: String,
+    #[props(default = Vec::new())]
+    pub initial_tags: Vec<String>,
+    /// ID категории поста; `None` - без категории
+    #[props(default = None)]
+    pub initial_category_id: Option<String>,
+    #[props(default = false)]
+    pub initial_draft: bool,
+    /// Ошибки полей, которыми сервер отклонил прошлую отправку формы
+    #[props(default)]
+    pub server_errors: FieldErrors,
+}
+
+/// Что показывает поле содержимого поста.
+#[derive(Clone, Copy, PartialEq)]
+enum EditorMode {
+    /// Только редактор
+    Write,
+    /// Редактор и предпросмотр рядом
+    Split,
+    /// Только предпросмотр
+    Preview,
+}
+
+impl EditorMode {
+    const ALL: [EditorMode; 3] = [EditorMode::Write, EditorMode::Split, EditorMode::Preview];
+
+    fn label(self) -> &'static str {
+        match self {
+            EditorMode::Write => "Write",
+            EditorMode::Split => "Split",
+            EditorMode::Preview => "Preview",
+        }
+    }
+}
+
+/// Разбирает строку тегов, введённых через запятую.
+///
+/// Окончательная нормализация (регистр, дубликаты, лимиты) выполняется на сервере.
+fn parse_tags(input: &str) -> Vec<String> {
+    input
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Пункты списка категорий в порядке дерева: подкатегории идут после родителя
+/// с отступом по глубине вложенности.
+pub(super) fn category_options(categories: &[Category]) -> Vec<(String, String)> {
+    fn add(
+        categories: &[Category],
+        parent: Option<Uuid>,
+        depth: usize,
+        options: &mut Vec<(String, String)>,
+    ) {
+        for category in categories.iter().filter(|c| c.parent_id == parent) {
+            let label = format!("{}{}", "\u{2014} ".repeat(depth), category.name);
+            options.push((category.id.to_string(), label));
+            add(categories, Some(category.id), depth + 1, options);
+        }
+    }
+
+    let mut options = Vec::new();
+    add(categories, None, 0, &mut options);
+    options
 }
 
 #[component]
 pub fn PostForm(props: PostFormProps) -> Element {
-    let client = use_context::<HttpClient>();
-
     let mut title = use_signal(|| props.initial_title.clone());
     let mut content = use_signal(|| props.initial_content.clone());
-    let mut error_message = use_signal(|| None::<String>);
-    let mut is_loading = use_signal(|| false);
+    let initial_summary = props.initial_summary.clone();
+    let mut summary = use_signal(|| initial_summary.clone());
+    let mut tags = use_signal(|| props.initial_tags.join(", "));
+    let initial_category = props.initial_category_id.clone().unwrap_or_default();
+    let mut category = use_signal(|| initial_category.clone());
+    let mut save_as_draft = use_signal(|| props.initial_draft);
+    let mut editor_mode = use_signal(|| EditorMode::Split);
+    // Предпросмотр пересчитывается только при изменении содержимого
+    let preview_html = use_memo(move || markdown::render(&content.read()));
+    // Пустые поля подсвечиваются только после первой попытки отправки
+    let mut submitted = use_signal(|| false);
+    let mut server_errors = use_signal(|| props.server_errors.clone());
+
+    let client = use_context::<SharedClient>();
+    // Без загруженных категорий список предлагает только «No category»
+    let categories = use_resource(move || {
+        let client = client.clone();
+        async move { client.list_categories().await }
+    });
+    let options = use_memo(move || match categories.read().as_ref() {
+        Some(Ok(categories)) => category_options(categories),
+        _ => Vec::new(),
+    });
+
+    let title_error = use_memo(move || {
+        let value = title();
+        server_errors.read().get("title").cloned().or_else(|| {
+            (submitted() || !value.is_empty())
+                .then(|| validate_post_title(&value))
+                .flatten()
+        })
+    });
+
+    let content_error = use_memo(move || {
+        let value = content();
+        server_errors.read().get("content").cloned().or_else(|| {
+            (submitted() || !value.is_empty())
+                .then(|| validate_post_content(&value))
+                .flatten()
+        })
+    });
+
+    let summary_error = use_memo(move || {
+        let value = summary();
+        server_errors
+            .read()
+            .get("summary")
+            .cloned()
+            .or_else(|| validate_post_summary(&value))
+    });
+
+    let tags_error = use_memo(move || {
+        let value = parse_tags(&tags.read());
+        server_errors
+            .read()
+            .get("tags")
+            .cloned()
+            .or_else(|| validate_tags(&value))
+    });
+
+    let title_len = use_memo(move || title.read().chars().count());
+    let summary_len = use_memo(move || summary.read().trim().chars().count());
 
     let is_edit_mode = props.post_id.is_some();
 
     let on_submit = move |evt: Event<FormData>| {
         evt.prevent_default();
 
-        let client = client.clone();
-        let post_id = props.post_id.clone();
-        let on_success = props.on_success;
-
-        spawn(async move {
-            is_loading.set(true);
-            error_message.set(None);
-
-            let title_val = title.read().clone();
-            let content_val = content.read().clone();
-
-            let result = if let Some(id) = post_id {
-                // Update existing post
-                client
-                    .update_post(&id, &title_val, &content_val)
-                    .await
-                    .map(|_| ())
-            } else {
-                // Create new post
-                client
-                    .create_post(&title_val, &content_val)
-                    .await
-                    .map(|_| ())
-            };
-
-            match result {
-                Ok(_) => {
-                    is_loading.set(false);
-                    on_success.call(());
-                }
-                Err(err) => {
-                    is_loading.set(false);
-                    error_message.set(Some(format!("Failed to save post: {:?}", err)));
-                }
-            }
-        });
+        submitted.set(true);
+        if title_error().is_some()
+            || content_error().is_some()
+            || summary_error().is_some()
+            || tags_error().is_some()
+        {
+            return;
+        }
+
+        let title_val = title.read().clone();
+        let content_val = content.read().clone();
+        let tags_val = parse_tags(&tags.read());
+        let category_val = category.read().clone();
+        let summary_val = summary.read().trim().to_string();
+
+        let operation = match props.post_id.clone() {
+            // Категория отправляется, только если её поменяли: пустая строка убирает её
+            Some(post_id) => PendingOperation::UpdatePost {
+                post_id,
+                title: title_val,
+                content: content_val,
+                tags: tags_val,
+                category_id: (category_val != initial_category).then_some(category_val),
+                // Как и категория: пустая строка возвращает описание из начала содержимого
+                summary: (summary_val != initial_summary.trim()).then_some(summary_val),
+            },
+            None => PendingOperation::CreatePost {
+                title: title_val,
+                content: content_val,
+                tags: tags_val,
+                category_id: Some(category_val).filter(|id| !id.is_empty()),
+                summary: Some(summary_val).filter(|summary| !summary.is_empty()),
+                draft: save_as_draft(),
+            },
+        };
+        props.on_submit.call(operation);
     };
 
     rsx! {
@@ -70,40 +208,187 @@ pub fn PostForm(props: PostFormProps) -> Element {
             class: "space-y-4",
             onsubmit: on_submit,
 
-            if let Some(err) = error_message.read().as_ref() {
-                div {
-                    class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded",
-                    p { "{err}" }
-                }
-            }
-
             div {
                 label {
-                    class: "block text-sm font-medium text-gray-700 mb-1",
+                    class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
                     "Title"
                 }
                 input {
-                    class: "appearance-none block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm placeholder-gray-400 text-gray-900 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    class: input_class(&title_error()),
                     r#type: "text",
                     placeholder: "Enter post title",
-                    required: true,
                     value: "{title}",
-                    oninput: move |evt| title.set(evt.value().clone()),
+                    oninput: move |evt| {
+                        server_errors.write().remove("title");
+                        title.set(evt.value().clone());
+                    },
+                }
+                div {
+                    class: "mt-1 flex justify-between text-sm",
+                    if let Some(err) = title_error() {
+                        p { class: "text-red-600", "{err}" }
+                    } else {
+                        span {}
+                    }
+                    span {
+                        class: if title_len() > POST_TITLE_MAX_LEN { "text-xs text-red-600" } else { "text-xs text-gray-500" },
+                        "{title_len}/{POST_TITLE_MAX_LEN}"
+                    }
+                }
+            }
+
+            div {
+                div {
+                    class: "flex justify-between items-center mb-1",
+                    label {
+                        class: "block text-sm font-medium text-gray-700 dark:text-gray-300",
+                        "Content"
+                        span {
+                            class: "ml-2 text-xs font-normal text-gray-500",
+                            "Markdown supported"
+                        }
+                    }
+                    div {
+                        class: "inline-flex rounded-md shadow-sm",
+                        for mode in EditorMode::ALL {
+                            button {
+                                key: "{mode.label()}",
+                                class: if editor_mode() == mode {
+                                    "px-3 py-1 text-xs font-medium text-white bg-blue-600 border border-blue-600 first:rounded-l-md last:rounded-r-md"
+                                } else {
+                                    "px-3 py-1 text-xs font-medium text-gray-700 dark:text-gray-300 bg-white dark:bg-gray-800 border border-gray-300 dark:border-gray-600 hover:bg-gray-50 dark:hover:bg-gray-700 first:rounded-l-md last:rounded-r-md"
+                                },
+                                r#type: "button",
+                                onclick: move |_| editor_mode.set(mode),
+                                "{mode.label()}"
+                            }
+                        }
+                    }
+                }
+                div {
+                    class: if editor_mode() == EditorMode::Split { "grid grid-cols-1 md:grid-cols-2 gap-4" } else { "" },
+                    // В режиме предпросмотра поле скрывается, а не удаляется: введённый текст сохраняется
+                    textarea {
+                        class: if editor_mode() == EditorMode::Preview { "hidden".to_string() } else { format!("{} font-mono", input_class(&content_error())) },
+                        placeholder: "Write your post content here...",
+                        rows: "12",
+                        value: "{content}",
+                        oninput: move |evt| {
+                            server_errors.write().remove("content");
+                            content.set(evt.value().clone());
+                        },
+                    }
+                    if editor_mode() != EditorMode::Write {
+                        if content.read().trim().is_empty() {
+                            div {
+                                class: "px-3 py-2 border border-gray-200 dark:border-gray-700 rounded-md bg-gray-50 dark:bg-gray-900 text-sm text-gray-400",
+                                "Nothing to preview"
+                            }
+                        } else {
+                            div {
+                                class: "markdown-preview px-3 py-2 border border-gray-200 dark:border-gray-700 rounded-md bg-gray-50 dark:bg-gray-900 text-sm text-gray-900 dark:text-gray-100 overflow-y-auto max-h-80",
+                                dangerous_inner_html: "{preview_html}",
+                            }
+                        }
+                    }
+                }
+                if let Some(err) = content_error() {
+                    p { class: "mt-1 text-sm text-red-600", "{err}" }
                 }
             }
 
             div {
                 label {
-                    class: "block text-sm font-medium text-gray-700 mb-1",
-                    "Content"
+                    class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                    "Summary"
+                    span {
+                        class: "ml-2 text-xs font-normal text-gray-500",
+                        "Optional"
+                    }
                 }
                 textarea {
-                    class: "appearance-none block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm placeholder-gray-400 text-gray-900 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
-                    placeholder: "Write your post content here...",
-                    required: true,
-                    rows: "8",
-                    value: "{content}",
-                    oninput: move |evt| content.set(evt.value().clone()),
+                    class: input_class(&summary_error()),
+                    placeholder: "Shown in post lists; the beginning of the content is used if empty",
+                    rows: "2",
+                    value: "{summary}",
+                    oninput: move |evt| {
+                        server_errors.write().remove("summary");
+                        summary.set(evt.value().clone());
+                    },
+                }
+                div {
+                    class: "mt-1 flex justify-between text-sm",
+                    if let Some(err) = summary_error() {
+                        p { class: "text-red-600", "{err}" }
+                    } else {
+                        span {}
+                    }
+                    span {
+                        class: if summary_len() > POST_SUMMARY_MAX_LEN { "text-xs text-red-600" } else { "text-xs text-gray-500" },
+                        "{summary_len}/{POST_SUMMARY_MAX_LEN}"
+                    }
+                }
+            }
+
+            div {
+                label {
+                    class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                    "Tags"
+                }
+                input {
+                    class: input_class(&tags_error()),
+                    r#type: "text",
+                    placeholder: "rust, web, tutorial",
+                    value: "{tags}",
+                    oninput: move |evt| {
+                        server_errors.write().remove("tags");
+                        tags.set(evt.value().clone());
+                    },
+                }
+                if let Some(err) = tags_error() {
+                    p { class: "mt-1 text-sm text-red-600", "{err}" }
+                } else {
+                    p {
+                        class: "mt-1 text-xs text-gray-500",
+                        "Comma-separated, up to {MAX_TAGS_PER_POST} tags"
+                    }
+                }
+            }
+
+            div {
+                label {
+                    class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                    "Category"
+                }
+                select {
+                    class: "block w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-gray-900 dark:text-gray-100 bg-white dark:bg-gray-700 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    onchange: move |evt| category.set(evt.value()),
+                    option {
+                        value: "",
+                        selected: category.read().is_empty(),
+                        "No category"
+                    }
+                    for (id, label) in options() {
+                        option {
+                            key: "{id}",
+                            value: "{id}",
+                            selected: *category.read() == id,
+                            "{label}"
+                        }
+                    }
+                }
+            }
+
+            if !is_edit_mode {
+                label {
+                    class: "flex items-center space-x-2 text-sm text-gray-700 dark:text-gray-300",
+                    input {
+                        class: "h-4 w-4 text-blue-600 border-gray-300 dark:border-gray-600 rounded focus:ring-blue-500",
+                        r#type: "checkbox",
+                        checked: save_as_draft(),
+                        onchange: move |evt| save_as_draft.set(evt.checked()),
+                    }
+                    span { "Save as draft (only you can see it)" }
                 }
             }
 
@@ -112,23 +397,10 @@ pub fn PostForm(props: PostFormProps) -> Element {
                 button {
                     class: "flex-1 justify-center py-2 px-4 border border-transparent rounded-md shadow-sm text-sm font-medium text-white bg-blue-600 hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed",
                     r#type: "submit",
-                    disabled: is_loading(),
-                    if is_loading() {
-                        if is_edit_mode {
-                            "Updating..."
-                        } else {
-                            "Creating..."
-                        }
-                    } else {
-                        if is_edit_mode {
-                            "Update Post"
-                        } else {
-                            "Create Post"
-                        }
-                    }
+                    if is_edit_mode { "Update Post" } else { "Create Post" }
                 }
                 button {
-                    class: "flex-1 justify-center py-2 px-4 border border-gray-300 rounded-md shadow-sm text-sm font-medium text-gray-700 bg-white hover:bg-gray-50 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500",
+                    class: "flex-1 justify-center py-2 px-4 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-sm font-medium text-gray-700 dark:text-gray-300 bg-white dark:bg-gray-800 hover:bg-gray-50 dark:hover:bg-gray-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500",
                     r#type: "button",
                     onclick: move |_| props.on_cancel.call(()),
                     "Cancel"
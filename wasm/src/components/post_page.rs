@@ -0,0 +1,83 @@
+use crate::route::{use_navigator, Link, Route};
+use crate::transport::SharedClient;
+use client::error::ClientError;
+use dioxus::prelude::*;
+
+use super::{FollowButton, PostView};
+
+/// Страница одного поста (`/posts/:id`); пост загружается по ID из адреса.
+///
+/// Вошедшему пользователю (`current_user_id`) под чужим постом показывается кнопка
+/// подписки на автора.
+#[component]
+pub fn PostPage(id: String, current_user_id: Option<String>) -> Element {
+    let client = use_context::<SharedClient>();
+    let mut navigator = use_navigator();
+
+    let post_resource = use_resource(use_reactive!(|(id,)| {
+        let client = client.clone();
+        async move { client.get_post(&id).await }
+    }));
+
+    rsx! {
+        div {
+            class: "flex justify-center p-6",
+            match post_resource.read().as_ref() {
+                None => rsx! {
+                    div {
+                        class: "flex justify-center items-center py-12",
+                        div {
+                            class: "animate-spin rounded-full h-12 w-12 border-b-2 border-blue-500"
+                        }
+                    }
+                },
+                Some(Err(ClientError::NotFound)) => rsx! {
+                    div {
+                        class: "text-center py-12",
+                        p {
+                            class: "text-gray-600 dark:text-gray-400 text-lg mb-4",
+                            "Post not found."
+                        }
+                        Link {
+                            to: Route::Home,
+                            class: "text-blue-600 hover:text-blue-800",
+                            "← Back to posts"
+                        }
+                    }
+                },
+                Some(Err(err)) => rsx! {
+                    div {
+                        class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded",
+                        p { "Failed to load post: {err:?}" }
+                    }
+                },
+                Some(Ok(post)) => {
+                    let author_id = post.author_id.to_string();
+                    let can_follow = current_user_id
+                        .as_deref()
+                        .is_some_and(|user_id| user_id != author_id);
+                    rsx! {
+                        div {
+                            class: "flex flex-col items-center w-full space-y-4",
+                            // Ключ пересоздаёт просмотр при переходе к другому посту
+                            PostView {
+                                key: "{post.id}",
+                                initial_title: post.title.clone(),
+                                initial_content: post.content.clone(),
+                                created_at: post.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                                updated_at: post.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+                                on_close: move |_| navigator.push(Route::Home),
+                            }
+                            if can_follow {
+                                FollowButton {
+                                    author_id,
+                                    author_username: post.author_username.clone(),
+                                }
+                            }
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
@@ -17,14 +17,14 @@ pub fn PostView(props: PostViewProps) -> Element {
 
     rsx! {
         div {
-            class: "bg-white rounded-lg shadow-xl max-w-3xl w-full max-h-[90vh] overflow-hidden",
+            class: "bg-white dark:bg-gray-800 rounded-lg shadow-xl max-w-3xl w-full max-h-[90vh] overflow-hidden",
             onclick: move |e| e.stop_propagation(),
 
             // Header
             div {
                 class: "flex items-center justify-between p-6 border-b",
                 h2 {
-                    class: "text-2xl font-bold text-gray-900",
+                    class: "text-2xl font-bold text-gray-900 dark:text-gray-100",
                     "{title}"
                 }
                 button {
@@ -75,7 +75,7 @@ pub fn PostView(props: PostViewProps) -> Element {
                 div {
                     class: "prose max-w-none",
                     p {
-                        class: "text-gray-700 whitespace-pre-wrap",
+                        class: "text-gray-700 dark:text-gray-300 whitespace-pre-wrap",
                         "{content}"
                     }
                 }
@@ -83,7 +83,7 @@ pub fn PostView(props: PostViewProps) -> Element {
 
             // Footer
             div {
-                class: "flex justify-end gap-3 p-6 border-t bg-gray-50",
+                class: "flex justify-end gap-3 p-6 border-t bg-gray-50 dark:bg-gray-900",
                 button {
                     class: "px-4 py-2 bg-gray-600 text-white rounded-lg hover:bg-gray-700 transition-colors",
                     onclick: move |_| {
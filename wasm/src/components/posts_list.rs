@@ -1,196 +1,414 @@
-use client::{blog_client::BlogClient, http_client::HttpClient};
-use dioxus::document::eval;
+use crate::offline::OfflineState;
+use crate::optimistic::{self, Rollback};
+use crate::search;
+use crate::transport::{self, SharedClient, TransportKind};
+use crate::url_state::{self, ListQuery};
+use crate::validation::{server_field_errors, FieldErrors};
+use client::offline_queue::{PendingOperation, Submitted};
+use client::types::{ClientResult, Post, PostPage, PostStatus};
 use dioxus::prelude::*;
+use futures_util::StreamExt;
+use gloo_timers::future::TimeoutFuture;
+use uuid::Uuid;
 
-use super::{PostCard, PostForm, PostView};
+use super::{Modal, PostCard, PostForm};
 
 #[derive(Props, Clone, PartialEq)]
 pub struct PostsListProps {
     #[props(default = false)]
     pub is_authenticated: bool,
+    /// Показывать только посты этого автора (страница `/me`)
+    #[props(default)]
+    pub author_id: Option<String>,
+    /// ID вошедшего пользователя: кнопки правки и удаления видны только на его постах
+    #[props(default)]
+    pub current_user_id: Option<String>,
+    /// Вызывается, когда сервер принял созданный, изменённый или удалённый пост
+    #[props(default)]
+    pub on_posts_changed: Option<EventHandler<()>>,
 }
 
-#[derive(Clone, Debug)]
+const PAGE_SIZE: u32 = 10;
+/// Пауза в наборе, после которой применяется поисковый запрос
+const SEARCH_DEBOUNCE_MS: u32 = 300;
+
+/// Как список постов переходит к следующим постам.
+#[derive(Clone, Copy, PartialEq)]
+enum ListMode {
+    /// Кнопки «назад»/«вперёд», номер страницы хранится в адресе
+    Pages,
+    /// Следующая страница дописывается к списку при прокрутке до конца
+    InfiniteScroll,
+}
+
+impl ListMode {
+    /// Определяет режим по переменной `POSTS_LIST_MODE`, заданной при сборке.
+    ///
+    /// Допустимые значения: `pages` (по умолчанию) и `infinite`.
+    fn from_env() -> Self {
+        match option_env!("POSTS_LIST_MODE") {
+            Some("infinite") => ListMode::InfiniteScroll,
+            _ => ListMode::Pages,
+        }
+    }
+}
+
+/// Посты, накопленные при бесконечной прокрутке.
+#[derive(Clone, Default)]
+struct Feed {
+    posts: Vec<Post>,
+    /// Последняя загруженная страница
+    last_page: Option<u32>,
+    /// Фильтр по тегу, с которым загружены посты
+    tag: Option<String>,
+    /// Значение `refresh_trigger`, при котором загружены посты
+    refresh: i32,
+}
+
+/// Пост в открытой форме создания или редактирования.
+#[derive(Clone, Debug, Default)]
 struct EditingPost {
-    id: String,
+    /// ID редактируемого поста; `None` - новый пост
+    id: Option<String>,
     title: String,
     content: String,
+    summary: String,
+    tags: Vec<String>,
+    category_id: Option<String>,
+    draft: bool,
+    /// Ошибки полей, которыми сервер отклонил прошлую отправку формы
+    server_errors: FieldErrors,
 }
 
-#[derive(Clone, Debug)]
-struct ViewingPost {
-    title: String,
-    content: String,
-    created_at: String,
-    updated_at: String,
+impl EditingPost {
+    /// Форма с отклонённым сервером изменением, чтобы пользователь мог его исправить
+    fn rejected(operation: PendingOperation, server_errors: FieldErrors) -> Option<Self> {
+        match operation {
+            PendingOperation::CreatePost {
+                title,
+                content,
+                tags,
+                category_id,
+                summary,
+                draft,
+            } => Some(EditingPost {
+                id: None,
+                title,
+                content,
+                summary: summary.unwrap_or_default(),
+                tags,
+                category_id,
+                draft,
+                server_errors,
+            }),
+            PendingOperation::UpdatePost {
+                post_id,
+                title,
+                content,
+                tags,
+                category_id,
+                summary,
+            } => Some(EditingPost {
+                id: Some(post_id),
+                title,
+                content,
+                summary: summary.unwrap_or_default(),
+                tags,
+                category_id: category_id.filter(|id| !id.is_empty()),
+                draft: false,
+                server_errors,
+            }),
+            PendingOperation::DeletePost { .. } => None,
+        }
+    }
 }
 
 #[component]
 pub fn PostsList(props: PostsListProps) -> Element {
     // Получаем клиента из контекста
-    let client = use_context::<HttpClient>();
-    let client_for_delete = client.clone();
+    let client = use_context::<SharedClient>();
+    let client_for_mutate = client.clone();
+    let offline = use_context::<OfflineState>();
+    let client_for_publish = client.clone();
+    let client_for_like = client.clone();
 
-    const PAGE_SIZE: u32 = 10;
+    /// Пауза перед повторной подпиской на живую ленту после обрыва соединения
+    const POST_EVENTS_RETRY_MS: u32 = 5_000;
 
     let mut refresh_trigger = use_signal(|| 0);
     let mut current_page = use_signal(|| 0u32);
-    let mut show_create_modal = use_signal(|| false);
+    let mut current_tag = use_signal(|| None::<String>);
+    // Текст в поле поиска и применённый после паузы запрос
+    let mut search_input = use_signal(String::new);
+    let mut current_search = use_signal(|| None::<String>);
     let mut editing_post = use_signal(|| None::<EditingPost>);
-    let mut viewing_post = use_signal(|| None::<ViewingPost>);
     let mut deleting_post_id = use_signal(|| None::<String>);
+    let list_mode = ListMode::from_env();
+    let mut feed = use_signal(Feed::default);
+    // Созданные посты, которые ещё не получили ID от сервера
+    let mut pending_posts = use_signal(Vec::<Uuid>::new);
+    let mut mutation_error = use_signal(|| None::<String>);
+    // Виден ли конец списка (при бесконечной прокрутке)
+    let mut list_end_visible = use_signal(|| false);
+
+    // Номер страницы, тег и поиск хранятся в адресе: восстанавливаем их при загрузке
+    // и при навигации назад/вперёд
+    use_future(move || async move {
+        url_state::watch_list_query(move |query| {
+            current_page.set(query.page);
+            current_tag.set(query.tag);
+            search_input.set(query.q.clone().unwrap_or_default());
+            current_search.set(query.q);
+        })
+        .await;
+    });
 
-    // Используем use_resource для загрузки постов
-    let posts_resource = use_resource(move || {
-        let client = client.clone();
-        let _ = refresh_trigger();
-        let page = current_page();
+    // Поиск применяется после паузы в наборе: ресурс перезапускается на каждый ввод
+    use_resource(move || {
+        let input = search_input();
         async move {
-            // Получаем список постов
-            client
-                .list_posts(PAGE_SIZE, page)
-                .await
-                .map_err(|e| format!("Failed to fetch posts: {:?}", e))
+            TimeoutFuture::new(SEARCH_DEBOUNCE_MS).await;
+            let search = Some(input.trim().to_string()).filter(|q| !q.is_empty());
+            if search != *current_search.peek() {
+                current_search.set(search.clone());
+                url_state::replace_list_query(&ListQuery {
+                    page: *current_page.peek(),
+                    tag: current_tag.peek().clone(),
+                    q: search,
+                });
+            }
         }
     });
 
-    let open_create_modal = move |_| {
-        show_create_modal.set(true);
-    };
+    // Живая лента: любое изменение опубликованных постов перезагружает текущую страницу
+    use_future(move || async move {
+        loop {
+            match transport::subscribe_post_events(
+                TransportKind::from_env(),
+                crate::get_backend_url(),
+            )
+            .await
+            {
+                Ok(events) => {
+                    let mut events = std::pin::pin!(events);
+                    while let Some(Ok(_event)) = events.next().await {
+                        refresh_trigger.set(refresh_trigger() + 1);
+                    }
+                }
+                Err(err) => eprintln!("Failed to subscribe to post events: {:?}", err),
+            }
+            TimeoutFuture::new(POST_EVENTS_RETRY_MS).await;
+        }
+    });
 
-    let close_create_modal = move |_| {
-        show_create_modal.set(false);
+    let mut go_to_page = move |page: u32| {
+        current_page.set(page);
+        url_state::push_list_query(&ListQuery {
+            page,
+            tag: current_tag(),
+            q: current_search(),
+        });
     };
 
-    let close_create_modal_click = move |_evt: Event<MouseData>| {
-        show_create_modal.set(false);
+    let mut filter_by_tag = move |tag: Option<String>| {
+        current_tag.set(tag.clone());
+        current_page.set(0);
+        url_state::push_list_query(&ListQuery {
+            page: 0,
+            tag,
+            q: current_search(),
+        });
     };
 
-    let on_create_success = move |_| {
-        show_create_modal.set(false);
-        current_page.set(0);
-        refresh_trigger.set(refresh_trigger() + 1);
+    let author_id = props.author_id.clone();
+
+    // Используем use_resource для загрузки постов
+    let mut posts_resource = use_resource(move || {
+        let client = client.clone();
+        let author_id = author_id.clone();
+        let refresh = refresh_trigger();
+        let page = current_page();
+        let tag = current_tag();
+        async move {
+            let result = match list_mode {
+                ListMode::Pages => {
+                    fetch_page(&client, author_id.as_deref(), tag.as_deref(), page).await
+                }
+                ListMode::InfiniteScroll => {
+                    load_feed(&client, feed, author_id.as_deref(), tag, page, refresh).await
+                }
+            };
+            result.map_err(|e| format!("Failed to fetch posts: {:?}", e))
+        }
+    });
+
+    // При бесконечной прокрутке следующая страница загружается, как только
+    // конец списка виден и предыдущая загрузка завершилась
+    use_effect(move || {
+        if list_mode != ListMode::InfiniteScroll
+            || !list_end_visible()
+            || !posts_resource.finished()
+        {
+            return;
+        }
+        let has_next = matches!(&*posts_resource.read(), Some(Ok(page)) if page.has_next);
+        if has_next {
+            let next_page = *current_page.peek() + 1;
+            current_page.set(next_page);
+        }
+    });
+
+    // Изменение сразу показывается в загруженном списке и отправляется на сервер в фоне;
+    // если сервер его отклонил, изменение откатывается и показывается ошибка
+    let mutate = use_callback(move |operation: PendingOperation| {
+        let insert_created = *current_page.peek() == 0
+            && match (&operation, current_tag.peek().as_ref()) {
+                (PendingOperation::CreatePost { tags, .. }, Some(tag)) => tags.contains(tag),
+                _ => true,
+            };
+        let rollback = match posts_resource.write().as_mut() {
+            Some(Ok(page)) => optimistic::apply(&mut page.posts, &operation, insert_created),
+            _ => Rollback::Nothing,
+        };
+        if let Rollback::Remove(id) = rollback {
+            pending_posts.write().push(id);
+        }
+        // При бесконечной прокрутке следующие страницы дописываются к накопленным постам
+        let mut sync_feed = move || {
+            if let Some(Ok(page)) = posts_resource.peek().as_ref() {
+                feed.write().posts = page.posts.clone();
+            }
+        };
+        sync_feed();
+
+        let client = client_for_mutate.clone();
+        let offline = offline.clone();
+        spawn(async move {
+            let is_create = matches!(operation, PendingOperation::CreatePost { .. });
+            // Без сети изменение откладывается и отправляется при восстановлении связи
+            match offline.submit(&client, operation.clone()).await {
+                // Настоящие ID и метки времени созданного поста приходят с сервера
+                Ok(Submitted::Sent) => {
+                    if is_create {
+                        refresh_trigger.set(refresh_trigger() + 1);
+                    }
+                    if let Some(on_posts_changed) = props.on_posts_changed {
+                        on_posts_changed.call(());
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    if let Some(Ok(page)) = posts_resource.write().as_mut() {
+                        rollback.undo(&mut page.posts);
+                    }
+                    sync_feed();
+                    // Ошибки полей (ответ 422) показываются в снова открытой форме
+                    match server_field_errors(&err)
+                        .and_then(|fields| EditingPost::rejected(operation, fields))
+                    {
+                        // Уже открытую форму не подменяем
+                        Some(form) if editing_post.peek().is_none() => editing_post.set(Some(form)),
+                        _ => mutation_error.set(Some(format!("Failed to save changes: {:?}", err))),
+                    }
+                }
+            }
+        });
+    });
+
+    let open_create_modal = move |_| {
+        editing_post.set(Some(EditingPost::default()));
     };
 
-    let on_view = move |(_id, title, content, created_at, updated_at): (
-        String,
+    let on_edit = move |(id, title, content, summary, tags, category_id): (
         String,
         String,
         String,
         String,
+        Vec<String>,
+        Option<String>,
     )| {
-        viewing_post.set(Some(ViewingPost {
+        editing_post.set(Some(EditingPost {
+            id: Some(id),
             title,
             content,
-            created_at,
-            updated_at,
+            summary,
+            tags,
+            category_id,
+            ..EditingPost::default()
         }));
     };
 
-    let close_view_modal = move |_| {
-        viewing_post.set(None);
-    };
+    // Обработчик захватывает клиента, поэтому оборачивается в копируемый Callback
+    let on_publish = use_callback(move |(id, publish): (String, bool)| {
+        let client = client_for_publish.clone();
+        spawn(async move {
+            let result = if publish {
+                client.publish_post(&id).await
+            } else {
+                client.unpublish_post(&id).await
+            };
+            match result {
+                Ok(_) => refresh_trigger.set(refresh_trigger() + 1),
+                Err(err) => {
+                    // TODO: Show error message
+                    eprintln!("Failed to change post status: {:?}", err);
+                }
+            }
+        });
+    });
 
-    let on_edit = move |(id, title, content): (String, String, String)| {
-        editing_post.set(Some(EditingPost { id, title, content }));
-    };
+    let on_like = use_callback(move |(id, like): (String, bool)| {
+        let client = client_for_like.clone();
+        spawn(async move {
+            let result = if like {
+                client.like_post(&id).await
+            } else {
+                client.unlike_post(&id).await
+            };
+            match result {
+                Ok(_) => refresh_trigger.set(refresh_trigger() + 1),
+                Err(err) => {
+                    // TODO: Show error message
+                    eprintln!("Failed to update like: {:?}", err);
+                }
+            }
+        });
+    });
 
-    let close_edit_modal = move |_| {
-        editing_post.set(None);
-    };
+    // В списке постов автора фильтр по тегу не применяется, теги не кликабельны
+    let on_tag = props
+        .author_id
+        .is_none()
+        .then(|| EventHandler::new(move |tag: String| filter_by_tag(Some(tag))));
 
-    let close_edit_modal_click = move |_evt: Event<MouseData>| {
+    let close_form_modal = move |()| {
         editing_post.set(None);
     };
 
-    let on_edit_success = move |_| {
+    let on_form_submit = move |operation: PendingOperation| {
+        let is_create = matches!(operation, PendingOperation::CreatePost { .. });
         editing_post.set(None);
-        refresh_trigger.set(refresh_trigger() + 1);
+        mutate.call(operation);
+        // Новый пост появляется на первой странице
+        if is_create && current_page() != 0 {
+            go_to_page(0);
+        }
     };
 
     let on_delete = move |id: String| {
         deleting_post_id.set(Some(id));
     };
 
-    let cancel_delete = move |_| {
+    let mut cancel_delete = move |()| {
         deleting_post_id.set(None);
     };
 
     let confirm_delete = move |_| {
-        if let Some(id) = deleting_post_id.read().clone() {
-            let client = client_for_delete.clone();
-            spawn(async move {
-                match client.delete_post(&id).await {
-                    Ok(_) => {
-                        deleting_post_id.set(None);
-                        refresh_trigger.set(refresh_trigger() + 1);
-                    }
-                    Err(err) => {
-                        // TODO: Show error message
-                        eprintln!("Failed to delete post: {:?}", err);
-                        deleting_post_id.set(None);
-                    }
-                }
-            });
+        if let Some(post_id) = deleting_post_id.take() {
+            mutate.call(PendingOperation::DeletePost { post_id });
         }
     };
 
-    // Фокусируем модальное окно создания при открытии для работы ESC
-    use_effect(move || {
-        if show_create_modal() {
-            eval(
-                r#"
-                setTimeout(() => {
-                    const modals = document.querySelectorAll('.modal-backdrop');
-                    if (modals.length > 0) modals[modals.length - 1].focus();
-                }, 0);
-                "#,
-            );
-        }
-    });
-
-    // Фокусируем модальное окно редактирования при открытии для работы ESC
-    use_effect(move || {
-        if editing_post.read().is_some() {
-            eval(
-                r#"
-                setTimeout(() => {
-                    const modals = document.querySelectorAll('.modal-backdrop');
-                    if (modals.length > 0) modals[modals.length - 1].focus();
-                }, 0);
-                "#,
-            );
-        }
-    });
-
-    // Фокусируем модальное окно удаления при открытии для работы ESC
-    use_effect(move || {
-        if deleting_post_id.read().is_some() {
-            eval(
-                r#"
-                setTimeout(() => {
-                    const modals = document.querySelectorAll('.modal-backdrop');
-                    if (modals.length > 0) modals[modals.length - 1].focus();
-                }, 0);
-                "#,
-            );
-        }
-    });
-
-    // Фокусируем модальное окно просмотра при открытии для работы ESC
-    use_effect(move || {
-        if viewing_post.read().is_some() {
-            eval(
-                r#"
-                setTimeout(() => {
-                    const modals = document.querySelectorAll('.modal-backdrop');
-                    if (modals.length > 0) modals[modals.length - 1].focus();
-                }, 0);
-                "#,
-            );
-        }
-    });
-
     rsx! {
         div {
             class: "posts-list-container max-w-4xl mx-auto p-6",
@@ -198,7 +416,7 @@ pub fn PostsList(props: PostsListProps) -> Element {
                 class: "flex justify-between items-center mb-8",
                 h1 {
                     class: "text-4xl font-bold",
-                    "Blog Posts"
+                    if props.author_id.is_some() { "My Posts" } else { "Blog Posts" }
                 }
                 if props.is_authenticated {
                     button {
@@ -209,6 +427,55 @@ pub fn PostsList(props: PostsListProps) -> Element {
                 }
             }
 
+            div {
+                class: "relative mb-6",
+                input {
+                    class: "appearance-none block w-full px-3 py-2 pr-8 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm placeholder-gray-400 text-gray-900 dark:text-gray-100 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    r#type: "search",
+                    placeholder: "Search posts by title",
+                    value: "{search_input}",
+                    oninput: move |evt| search_input.set(evt.value().clone()),
+                }
+                if !search_input.read().is_empty() {
+                    button {
+                        class: "absolute inset-y-0 right-0 px-3 text-gray-400 hover:text-gray-600",
+                        title: "Clear search",
+                        onclick: move |_| search_input.set(String::new()),
+                        "×"
+                    }
+                }
+            }
+
+            if let Some(err) = mutation_error() {
+                div {
+                    class: "flex justify-between items-start bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded mb-6",
+                    p { "{err}" }
+                    button {
+                        class: "ml-4 text-red-500 hover:text-red-800",
+                        title: "Dismiss",
+                        onclick: move |_| mutation_error.set(None),
+                        "×"
+                    }
+                }
+            }
+
+            if let Some(tag) = current_tag() {
+                div {
+                    class: "flex items-center mb-6 space-x-2 text-sm text-gray-700 dark:text-gray-300",
+                    span { "Showing posts tagged" }
+                    span {
+                        class: "inline-flex items-center px-2 py-0.5 font-medium text-blue-700 bg-blue-100 rounded-full",
+                        "#{tag}"
+                        button {
+                            class: "ml-1 text-blue-500 hover:text-blue-800",
+                            title: "Clear tag filter",
+                            onclick: move |_| filter_by_tag(None),
+                            "×"
+                        }
+                    }
+                }
+            }
+
             match posts_resource.read().as_ref() {
                 None => rsx! {
                     div {
@@ -224,22 +491,45 @@ pub fn PostsList(props: PostsListProps) -> Element {
                         p { "Error: {err}" }
                     }
                 },
-                Some(Ok(posts)) => {
+                Some(Ok(page)) => {
+                    let search = current_search();
+                    let posts: Vec<_> = page
+                        .posts
+                        .iter()
+                        .filter(|post| {
+                            search
+                                .as_deref()
+                                .is_none_or(|query| search::matches(&post.title, query))
+                        })
+                        .collect();
                     let has_prev = current_page() > 0;
-                    let has_next = posts.len() == PAGE_SIZE as usize;
+                    let has_next = page.has_next;
 
-                    if posts.is_empty() && current_page() == 0 {
+                    if page.posts.is_empty() && current_page() == 0 {
                         rsx! {
                             div {
                                 class: "text-center py-12",
                                 p {
-                                    class: "text-gray-600 text-lg",
-                                    "No posts found. Create your first post!"
+                                    class: "text-gray-600 dark:text-gray-400 text-lg",
+                                    if current_tag().is_some() {
+                                        "No posts with this tag yet."
+                                    } else {
+                                        "No posts found. Create your first post!"
+                                    }
                                 }
                             }
                         }
                     } else {
                         rsx! {
+                            if let Some(query) = search.as_ref().filter(|_| posts.is_empty()) {
+                                div {
+                                    class: "text-center py-12",
+                                    p {
+                                        class: "text-gray-600 dark:text-gray-400 text-lg",
+                                        "No posts on this page match \"{query}\"."
+                                    }
+                                }
+                            }
                             div {
                                 class: "posts-grid",
                                 for post in posts {
@@ -247,209 +537,184 @@ pub fn PostsList(props: PostsListProps) -> Element {
                                         id: post.id.to_string(),
                                         title: post.title.clone(),
                                         content: post.content.clone(),
+                                        summary: post.summary.clone(),
                                         created_at: post.created_at.format("%Y-%m-%d %H:%M").to_string(),
                                         updated_at: post.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+                                        tags: post.tags.clone(),
+                                        category_id: post.category_id.map(|id| id.to_string()),
+                                        highlight: search.clone(),
+                                        is_pending: pending_posts.read().contains(&post.id),
+                                        is_draft: post.status == PostStatus::Draft,
+                                        like_count: post.like_count,
+                                        liked_by_me: post.liked_by_me,
                                         is_authenticated: props.is_authenticated,
-                                        on_view: on_view,
+                                        is_owner: props.current_user_id.as_deref() == Some(post.author_id.to_string().as_str()),
                                         on_edit: on_edit,
                                         on_delete: on_delete,
+                                        on_tag: on_tag,
+                                        on_publish: on_publish,
+                                        on_like: on_like,
                                     }
                                 }
                             }
 
-                            // Pagination controls
-                            div {
-                                class: "flex justify-center items-center mt-8 space-x-4",
-                                button {
-                                    class: "px-4 py-2 border border-gray-300 rounded-md shadow-sm text-sm font-medium text-gray-700 bg-white hover:bg-gray-50 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed",
-                                    disabled: !has_prev,
-                                    onclick: move |_| {
-                                        if current_page() > 0 {
-                                            current_page.set(current_page() - 1);
-                                        }
+                            if list_mode == ListMode::InfiniteScroll {
+                                div {
+                                    class: "flex justify-center items-center py-6 text-sm text-gray-500",
+                                    onvisible: move |evt: Event<VisibleData>| {
+                                        list_end_visible.set(evt.is_intersecting().unwrap_or(false));
                                     },
-                                    "← Previous"
-                                }
-                                span {
-                                    class: "text-sm",
-                                    "Page {current_page() + 1}"
-                                }
-                                button {
-                                    class: "px-4 py-2 border border-gray-300 rounded-md shadow-sm text-sm font-medium text-gray-700 bg-white hover:bg-gray-50 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed",
-                                    disabled: !has_next,
-                                    onclick: move |_| {
-                                        current_page.set(current_page() + 1);
-                                    },
-                                    "Next →"
+                                    if has_next {
+                                        div {
+                                            class: "animate-spin rounded-full h-8 w-8 border-b-2 border-blue-500"
+                                        }
+                                    } else {
+                                        "No more posts"
+                                    }
                                 }
-                            }
-                        }
-                    }
-                }
-            }
-
-            // Create Post Modal
-            if show_create_modal() {
-                div {
-                    class: "fixed inset-0 modal-backdrop overflow-y-auto h-full w-full z-50 flex items-center justify-center",
-                    onclick: close_create_modal_click,
-                    onkeyup: move |evt: Event<KeyboardData>| {
-                        if evt.key() == Key::Escape {
-                            show_create_modal.set(false);
-                        }
-                    },
-                    tabindex: 0,
-                    div {
-                        class: "relative bg-white rounded-lg shadow-xl max-w-2xl w-full mx-4",
-                        onclick: move |evt| evt.stop_propagation(),
-
-                        button {
-                            class: "absolute top-4 right-4 text-gray-400 hover:text-gray-600",
-                            onclick: close_create_modal_click,
-                            svg {
-                                class: "h-6 w-6",
-                                fill: "none",
-                                view_box: "0 0 24 24",
-                                stroke: "currentColor",
-                                path {
-                                    stroke_linecap: "round",
-                                    stroke_linejoin: "round",
-                                    stroke_width: "2",
-                                    d: "M6 18L18 6M6 6l12 12"
+                            } else {
+                                // Pagination controls
+                                div {
+                                    class: "flex justify-center items-center mt-8 space-x-4",
+                                    button {
+                                        class: "px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-sm font-medium text-gray-700 dark:text-gray-300 bg-white dark:bg-gray-800 hover:bg-gray-50 dark:hover:bg-gray-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed",
+                                        disabled: !has_prev,
+                                        onclick: move |_| {
+                                            if current_page() > 0 {
+                                                go_to_page(current_page() - 1);
+                                            }
+                                        },
+                                        "← Previous"
+                                    }
+                                    span {
+                                        class: "text-sm",
+                                        "Page {current_page() + 1}"
+                                    }
+                                    button {
+                                        class: "px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-sm font-medium text-gray-700 dark:text-gray-300 bg-white dark:bg-gray-800 hover:bg-gray-50 dark:hover:bg-gray-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed",
+                                        disabled: !has_next,
+                                        onclick: move |_| {
+                                            go_to_page(current_page() + 1);
+                                        },
+                                        "Next →"
+                                    }
                                 }
                             }
                         }
-
-                        div {
-                            class: "p-6",
-                            h2 {
-                                class: "text-2xl font-bold text-gray-900 mb-4",
-                                "Create New Post"
-                            }
-                            PostForm {
-                                on_success: on_create_success,
-                                on_cancel: close_create_modal,
-                            }
-                        }
                     }
                 }
             }
 
-            // Edit Post Modal
+            // Create/Edit Post Modal
             if let Some(post) = editing_post.read().as_ref() {
-                div {
-                    class: "fixed inset-0 modal-backdrop overflow-y-auto h-full w-full z-50 flex items-center justify-center",
-                    onclick: close_edit_modal_click,
-                    onkeyup: move |evt: Event<KeyboardData>| {
-                        if evt.key() == Key::Escape {
-                            editing_post.set(None);
-                        }
-                    },
-                    tabindex: 0,
-                    div {
-                        class: "relative bg-white rounded-lg shadow-xl max-w-2xl w-full mx-4",
-                        onclick: move |evt| evt.stop_propagation(),
-
-                        button {
-                            class: "absolute top-4 right-4 text-gray-400 hover:text-gray-600",
-                            onclick: close_edit_modal_click,
-                            svg {
-                                class: "h-6 w-6",
-                                fill: "none",
-                                view_box: "0 0 24 24",
-                                stroke: "currentColor",
-                                path {
-                                    stroke_linecap: "round",
-                                    stroke_linejoin: "round",
-                                    stroke_width: "2",
-                                    d: "M6 18L18 6M6 6l12 12"
-                                }
-                            }
-                        }
-
-                        div {
-                            class: "p-6",
-                            h2 {
-                                class: "text-2xl font-bold text-gray-900 mb-4",
-                                "Edit Post"
-                            }
-                            PostForm {
-                                post_id: post.id.clone(),
-                                initial_title: post.title.clone(),
-                                initial_content: post.content.clone(),
-                                on_success: on_edit_success,
-                                on_cancel: close_edit_modal,
-                            }
-                        }
+                Modal {
+                    title: if post.id.is_some() { "Edit Post" } else { "Create New Post" },
+                    max_width: "max-w-5xl",
+                    on_close: close_form_modal,
+                    PostForm {
+                        post_id: post.id.clone(),
+                        initial_title: post.title.clone(),
+                        initial_content: post.content.clone(),
+                        initial_summary: post.summary.clone(),
+                        initial_tags: post.tags.clone(),
+                        initial_category_id: post.category_id.clone(),
+                        initial_draft: post.draft,
+                        server_errors: post.server_errors.clone(),
+                        on_submit: on_form_submit,
+                        on_cancel: close_form_modal,
                     }
                 }
             }
 
             // Delete Confirmation Modal
             if deleting_post_id.read().is_some() {
-                div {
-                    class: "fixed inset-0 modal-backdrop overflow-y-auto h-full w-full z-50 flex items-center justify-center",
-                    onclick: cancel_delete,
-                    onkeyup: move |evt: Event<KeyboardData>| {
-                        if evt.key() == Key::Escape {
-                            deleting_post_id.set(None);
-                        }
-                    },
-                    tabindex: 0,
+                Modal {
+                    title: "Delete Post",
+                    on_close: cancel_delete,
+                    p {
+                        class: "text-gray-600 dark:text-gray-400 mb-6",
+                        "Are you sure you want to delete this post? This action cannot be undone."
+                    }
                     div {
-                        class: "relative bg-white rounded-lg shadow-xl max-w-md w-full mx-4",
-                        onclick: move |evt| evt.stop_propagation(),
-
-                        div {
-                            class: "p-6",
-                            h2 {
-                                class: "text-xl font-bold text-gray-900 mb-4",
-                                "Delete Post"
-                            }
-                            p {
-                                class: "text-gray-600 mb-6",
-                                "Are you sure you want to delete this post? This action cannot be undone."
-                            }
-                            div {
-                                class: "flex space-x-3",
-                                button {
-                                    class: "flex-1 justify-center py-2 px-4 border border-transparent rounded-md shadow-sm text-sm font-medium text-white bg-red-600 hover:bg-red-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-red-500",
-                                    onclick: confirm_delete,
-                                    "Delete"
-                                }
-                                button {
-                                    class: "flex-1 justify-center py-2 px-4 border border-gray-300 rounded-md shadow-sm text-sm font-medium text-gray-700 bg-white hover:bg-gray-50 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500",
-                                    onclick: cancel_delete,
-                                    "Cancel"
-                                }
-                            }
+                        class: "flex space-x-3",
+                        button {
+                            class: "flex-1 justify-center py-2 px-4 border border-transparent rounded-md shadow-sm text-sm font-medium text-white bg-red-600 hover:bg-red-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-red-500",
+                            onclick: confirm_delete,
+                            "Delete"
                         }
-                    }
-                }
-            }
-
-            // View Post Modal
-            if let Some(post) = viewing_post() {
-                div {
-                    class: "fixed inset-0 modal-backdrop overflow-y-auto h-full w-full z-50 flex items-center justify-center",
-                    onclick: move |_| {
-                        viewing_post.set(None);
-                    },
-                    onkeyup: move |evt: Event<KeyboardData>| {
-                        if evt.key() == Key::Escape {
-                            viewing_post.set(None);
+                        button {
+                            class: "flex-1 justify-center py-2 px-4 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-sm font-medium text-gray-700 dark:text-gray-300 bg-white dark:bg-gray-800 hover:bg-gray-50 dark:hover:bg-gray-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500",
+                            onclick: move |_| cancel_delete(()),
+                            "Cancel"
                         }
-                    },
-                    tabindex: 0,
-                    PostView {
-                        initial_title: post.title,
-                        initial_content: post.content,
-                        created_at: post.created_at,
-                        updated_at: post.updated_at,
-                        on_close: close_view_modal,
                     }
                 }
             }
         }
     }
 }
+
+/// Загружает страницу списка: посты автора, посты с тегом или все посты
+async fn fetch_page(
+    client: &SharedClient,
+    author_id: Option<&str>,
+    tag: Option<&str>,
+    page: u32,
+) -> ClientResult<PostPage> {
+    match (author_id, tag) {
+        (Some(author_id), _) => {
+            client
+                .list_posts_by_author(author_id, PAGE_SIZE, page)
+                .await
+        }
+        (None, Some(tag)) => client.list_posts_by_tag(tag, PAGE_SIZE, page).await,
+        (None, None) => client.list_posts(PAGE_SIZE, page, None).await,
+    }
+}
+
+/// Загружает страницы бесконечной прокрутки до `page` включительно.
+///
+/// Если `page` следует сразу за уже накопленными страницами, загружается только она.
+/// После обновления списка (живая лента, лайк, правка) или смены тега все страницы
+/// перечитываются заново. Возвращает последнюю страницу со всеми накопленными постами.
+async fn load_feed(
+    client: &SharedClient,
+    mut feed: Signal<Feed>,
+    author_id: Option<&str>,
+    tag: Option<String>,
+    page: u32,
+    refresh: i32,
+) -> ClientResult<PostPage> {
+    let (first_page, mut posts) = {
+        let feed = feed.peek();
+        let appends = feed.refresh == refresh
+            && feed.tag == tag
+            && feed.last_page.map(|last| last + 1) == Some(page);
+        if appends {
+            (page, feed.posts.clone())
+        } else {
+            (0, Vec::new())
+        }
+    };
+
+    let mut last = None;
+    for page in first_page..=page {
+        let mut result = fetch_page(client, author_id, tag.as_deref(), page).await?;
+        // Новые посты сдвигают страницы, поэтому уже показанные посты пропускаются
+        result
+            .posts
+            .retain(|post| !posts.iter().any(|shown: &Post| shown.id == post.id));
+        posts.append(&mut result.posts);
+        last = Some(result);
+    }
+
+    feed.set(Feed {
+        posts: posts.clone(),
+        last_page: Some(page),
+        tag,
+        refresh,
+    });
+    let mut last = last.expect("range first_page..=page is never empty");
+    last.posts = posts;
+    Ok(last)
+}
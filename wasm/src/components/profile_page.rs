@@ -0,0 +1,327 @@
+use crate::route::{Link, Route};
+use crate::transport::SharedClient;
+use crate::validation::{self, AVATAR_URL_MAX_LEN, BIO_MAX_LEN, DISPLAY_NAME_MAX_LEN};
+use client::types::{DigestFrequency, UserProfile};
+use dioxus::prelude::*;
+
+/// Пустое после обрезки пробелов поле очищается в профиле
+fn non_empty(value: &str) -> Option<&str> {
+    Some(value.trim()).filter(|value| !value.is_empty())
+}
+
+/// Страница профиля текущего пользователя (`/profile`): просмотр и редактирование.
+#[component]
+pub fn ProfilePage(user_id: String) -> Element {
+    let client = use_context::<SharedClient>();
+    let client_for_save = client.clone();
+
+    let mut profile_resource = use_resource(use_reactive!(|(user_id,)| {
+        let client = client.clone();
+        async move { client.get_user_profile(&user_id).await }
+    }));
+
+    let mut is_editing = use_signal(|| false);
+    let mut display_name = use_signal(String::new);
+    let mut bio = use_signal(String::new);
+    let mut avatar_url = use_signal(String::new);
+    let mut error_message = use_signal(|| None::<String>);
+    let mut is_saving = use_signal(|| false);
+
+    let mut start_editing = move |profile: &UserProfile| {
+        display_name.set(profile.display_name.clone().unwrap_or_default());
+        bio.set(profile.bio.clone().unwrap_or_default());
+        avatar_url.set(profile.avatar_url.clone().unwrap_or_default());
+        error_message.set(None);
+        is_editing.set(true);
+    };
+
+    let on_save = move |evt: Event<FormData>| {
+        evt.prevent_default();
+        if let Some(error) = validation::validate_avatar_url(&avatar_url.read()) {
+            error_message.set(Some(error));
+            return;
+        }
+
+        let client = client_for_save.clone();
+        spawn(async move {
+            is_saving.set(true);
+            error_message.set(None);
+
+            let display_name_val = display_name.read().clone();
+            let bio_val = bio.read().clone();
+            let avatar_url_val = avatar_url.read().clone();
+            let result = client
+                .update_my_profile(
+                    non_empty(&display_name_val),
+                    non_empty(&bio_val),
+                    non_empty(&avatar_url_val),
+                )
+                .await;
+
+            is_saving.set(false);
+            match result {
+                Ok(_) => {
+                    is_editing.set(false);
+                    profile_resource.restart();
+                }
+                Err(err) => {
+                    error_message.set(Some(format!("Failed to save profile: {:?}", err)));
+                }
+            }
+        });
+    };
+
+    let profile = match profile_resource.read().as_ref() {
+        None => {
+            return rsx! {
+                div {
+                    class: "flex justify-center items-center py-12",
+                    div {
+                        class: "animate-spin rounded-full h-12 w-12 border-b-2 border-blue-500"
+                    }
+                }
+            };
+        }
+        Some(Err(err)) => {
+            return rsx! {
+                div {
+                    class: "max-w-2xl mx-auto p-6",
+                    div {
+                        class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded",
+                        p { "Failed to load profile: {err:?}" }
+                    }
+                }
+            };
+        }
+        Some(Ok(profile)) => profile.clone(),
+    };
+    let name = profile
+        .display_name
+        .clone()
+        .unwrap_or_else(|| profile.username.clone());
+    let initial = name
+        .chars()
+        .next()
+        .unwrap_or('?')
+        .to_uppercase()
+        .to_string();
+
+    rsx! {
+        div {
+            class: "max-w-2xl mx-auto p-6",
+            div {
+                class: "bg-white dark:bg-gray-800 rounded-lg shadow-md p-6",
+                div {
+                    class: "flex items-center space-x-4 mb-6",
+                    if let Some(url) = profile.avatar_url.as_ref() {
+                        img {
+                            class: "h-16 w-16 rounded-full object-cover",
+                            src: "{url}",
+                            alt: "Avatar",
+                        }
+                    } else {
+                        div {
+                            class: "h-16 w-16 rounded-full bg-blue-100 text-blue-700 flex items-center justify-center text-2xl font-bold",
+                            "{initial}"
+                        }
+                    }
+                    div {
+                        h1 {
+                            class: "text-2xl font-bold text-gray-900 dark:text-gray-100",
+                            "{name}"
+                        }
+                        p {
+                            class: "text-sm text-gray-500",
+                            "@{profile.username} · Member since {profile.created_at.format(\"%Y-%m-%d\")}"
+                        }
+                    }
+                }
+
+                if is_editing() {
+                    form {
+                        class: "space-y-4",
+                        onsubmit: on_save,
+
+                        if let Some(err) = error_message.read().as_ref() {
+                            div {
+                                class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded",
+                                p { "{err}" }
+                            }
+                        }
+
+                        div {
+                            label {
+                                class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                                "Display name"
+                            }
+                            input {
+                                class: "appearance-none block w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm placeholder-gray-400 text-gray-900 dark:text-gray-100 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                                r#type: "text",
+                                placeholder: "{profile.username}",
+                                maxlength: DISPLAY_NAME_MAX_LEN as i64,
+                                value: "{display_name}",
+                                oninput: move |evt| display_name.set(evt.value().clone()),
+                            }
+                        }
+
+                        div {
+                            label {
+                                class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                                "Bio"
+                            }
+                            textarea {
+                                class: "appearance-none block w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm placeholder-gray-400 text-gray-900 dark:text-gray-100 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                                placeholder: "Tell readers about yourself",
+                                rows: "4",
+                                maxlength: BIO_MAX_LEN as i64,
+                                value: "{bio}",
+                                oninput: move |evt| bio.set(evt.value().clone()),
+                            }
+                        }
+
+                        div {
+                            label {
+                                class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
+                                "Avatar URL"
+                            }
+                            input {
+                                class: "appearance-none block w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm placeholder-gray-400 text-gray-900 dark:text-gray-100 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                                r#type: "url",
+                                placeholder: "https://example.com/avatar.png",
+                                maxlength: AVATAR_URL_MAX_LEN as i64,
+                                value: "{avatar_url}",
+                                oninput: move |evt| avatar_url.set(evt.value().clone()),
+                            }
+                            p {
+                                class: "mt-1 text-xs text-gray-500",
+                                "Leave a field empty to clear it"
+                            }
+                        }
+
+                        div {
+                            class: "flex space-x-3",
+                            button {
+                                class: "flex-1 justify-center py-2 px-4 border border-transparent rounded-md shadow-sm text-sm font-medium text-white bg-blue-600 hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed",
+                                r#type: "submit",
+                                disabled: is_saving(),
+                                if is_saving() { "Saving..." } else { "Save Profile" }
+                            }
+                            button {
+                                class: "flex-1 justify-center py-2 px-4 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-sm font-medium text-gray-700 dark:text-gray-300 bg-white dark:bg-gray-800 hover:bg-gray-50 dark:hover:bg-gray-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500",
+                                r#type: "button",
+                                onclick: move |_| is_editing.set(false),
+                                "Cancel"
+                            }
+                        }
+                    }
+                } else {
+                    p {
+                        class: "text-gray-700 dark:text-gray-300 whitespace-pre-wrap mb-6",
+                        if let Some(bio) = profile.bio.as_ref() {
+                            "{bio}"
+                        } else {
+                            span { class: "text-gray-400", "No bio yet." }
+                        }
+                    }
+                    div {
+                        class: "flex items-center space-x-4",
+                        button {
+                            class: "px-4 py-2 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500",
+                            onclick: move |_| start_editing(&profile),
+                            "Edit Profile"
+                        }
+                        Link {
+                            to: Route::Me,
+                            class: "text-sm text-blue-600 hover:text-blue-800",
+                            "My posts →"
+                        }
+                    }
+                }
+            }
+            DigestSettingsPanel {}
+        }
+    }
+}
+
+/// Значение пункта списка частоты дайджеста
+fn frequency_value(frequency: Option<DigestFrequency>) -> &'static str {
+    match frequency {
+        Some(DigestFrequency::Daily) => "daily",
+        Some(DigestFrequency::Weekly) => "weekly",
+        None => "off",
+    }
+}
+
+/// Подписка на email-дайджест новых постов: частота сохраняется сразу при выборе.
+#[component]
+fn DigestSettingsPanel() -> Element {
+    let client = use_context::<SharedClient>();
+    let client_for_save = client.clone();
+
+    let mut settings_resource = use_resource(move || {
+        let client = client.clone();
+        async move { client.get_digest_settings().await }
+    });
+    let mut error_message = use_signal(|| None::<String>);
+    let mut is_saving = use_signal(|| false);
+
+    let on_change = move |evt: Event<FormData>| {
+        let frequency = match evt.value().as_str() {
+            "daily" => Some(DigestFrequency::Daily),
+            "weekly" => Some(DigestFrequency::Weekly),
+            _ => None,
+        };
+        let client = client_for_save.clone();
+        spawn(async move {
+            is_saving.set(true);
+            error_message.set(None);
+            let result = client.update_digest_settings(frequency).await;
+            is_saving.set(false);
+            match result {
+                Ok(_) => settings_resource.restart(),
+                Err(err) => {
+                    error_message.set(Some(format!("Failed to save digest settings: {:?}", err)));
+                }
+            }
+        });
+    };
+
+    let current = match settings_resource.read().as_ref() {
+        Some(Ok(settings)) => settings.frequency,
+        // Пока настройки не загружены, список не показывается
+        _ => return rsx! {},
+    };
+    let selected = frequency_value(current);
+
+    rsx! {
+        div {
+            class: "bg-white dark:bg-gray-800 rounded-lg shadow-md p-6 mt-6",
+            h2 {
+                class: "text-lg font-semibold text-gray-900 dark:text-gray-100 mb-2",
+                "Email digest"
+            }
+            p {
+                class: "text-sm text-gray-500 mb-4",
+                "Get an email with posts published since the previous digest."
+            }
+            if let Some(err) = error_message.read().as_ref() {
+                div {
+                    class: "bg-red-100 border border-red-400 text-red-700 px-4 py-3 rounded mb-4",
+                    p { "{err}" }
+                }
+            }
+            select {
+                class: "block w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-md shadow-sm text-gray-900 dark:text-gray-100 bg-white dark:bg-gray-700 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm disabled:opacity-50",
+                disabled: is_saving(),
+                onchange: on_change,
+                for (value, label) in [("off", "Off"), ("daily", "Daily"), ("weekly", "Weekly")] {
+                    option {
+                        value: "{value}",
+                        selected: value == selected,
+                        "{label}"
+                    }
+                }
+            }
+        }
+    }
+}
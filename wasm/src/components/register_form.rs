@@ -1,36 +1,154 @@
-use client::{blog_client::BlogClient, http_client::HttpClient};
+use crate::transport::SharedClient;
+use crate::validation::{
+    password_strength, server_field_errors, validate_email, validate_password, validate_username,
+};
+use client::error::ClientError;
 use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+
+use super::field::input_class;
+
+/// Задержка перед проверкой доступности имени пользователя (мс)
+const USERNAME_CHECK_DEBOUNCE_MS: u32 = 400;
+
+/// Ошибки сервера, привязанные к полям формы.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ServerFieldErrors {
+    username: Option<String>,
+    email: Option<String>,
+    password: Option<String>,
+}
+
+/// Раскладывает ошибку регистрации по полям формы.
+///
+/// Возвращает ошибки полей и общее сообщение для тех ошибок, которые нельзя
+/// отнести к конкретному полю.
+fn map_register_error(err: &ClientError) -> (ServerFieldErrors, Option<String>) {
+    let mut fields = ServerFieldErrors::default();
+    match err {
+        ClientError::InvalidRequest(message) if message.contains("already exists") => {
+            fields.username = Some("This username or email is already taken".to_string());
+            (fields, None)
+        }
+        ClientError::InvalidRequest(message) if message.contains("Invalid password") => {
+            fields.password = Some("Password does not meet the requirements".to_string());
+            (fields, None)
+        }
+        ClientError::Validation { .. } => {
+            let mut errors = server_field_errors(err).unwrap_or_default();
+            fields.username = errors.remove("username");
+            fields.email = errors.remove("email");
+            fields.password = errors.remove("password");
+            (fields, None)
+        }
+        _ => (fields, Some(format!("Registration failed: {err}"))),
+    }
+}
 
 #[component]
 pub fn RegisterForm(on_success: EventHandler<()>, on_switch_to_login: EventHandler<()>) -> Element {
-    let client = use_context::<HttpClient>();
+    let client = use_context::<SharedClient>();
+    let client_for_check = client.clone();
 
     let mut username = use_signal(String::new);
     let mut email = use_signal(String::new);
     let mut password = use_signal(String::new);
     let mut confirm_password = use_signal(String::new);
+    let mut server_errors = use_signal(ServerFieldErrors::default);
     let mut error_message = use_signal(|| None::<String>);
     let mut is_loading = use_signal(|| false);
 
+    // Проверка доступности имени с задержкой: ресурс перезапускается на каждый ввод,
+    // поэтому запрос уходит только после паузы в наборе
+    let username_available = use_resource(move || {
+        let client = client_for_check.clone();
+        let name = username();
+        async move {
+            if name.is_empty() || validate_username(&name).is_some() {
+                return None;
+            }
+            TimeoutFuture::new(USERNAME_CHECK_DEBOUNCE_MS).await;
+            client.is_username_available(&name).await.ok()
+        }
+    });
+
+    let username_error = use_memo(move || {
+        let name = username();
+        if let Some(err) = server_errors.read().username.clone() {
+            return Some(err);
+        }
+        if name.is_empty() {
+            return None;
+        }
+        if let Some(err) = validate_username(&name) {
+            return Some(err);
+        }
+        match username_available.read().as_ref() {
+            Some(Some(false)) => Some("This username is already taken".to_string()),
+            _ => None,
+        }
+    });
+
+    let email_error = use_memo(move || {
+        let value = email();
+        if let Some(err) = server_errors.read().email.clone() {
+            return Some(err);
+        }
+        if value.is_empty() {
+            None
+        } else {
+            validate_email(&value)
+        }
+    });
+
+    let password_error = use_memo(move || {
+        let value = password();
+        if let Some(err) = server_errors.read().password.clone() {
+            return Some(err);
+        }
+        if value.is_empty() {
+            None
+        } else {
+            validate_password(&value)
+        }
+    });
+
+    let confirm_error = use_memo(move || {
+        let value = confirm_password();
+        if value.is_empty() || value == password() {
+            None
+        } else {
+            Some("Passwords do not match".to_string())
+        }
+    });
+
+    let strength = use_memo(move || password_strength(&password()));
+
+    let has_errors = use_memo(move || {
+        username_error().is_some()
+            || email_error().is_some()
+            || password_error().is_some()
+            || confirm_error().is_some()
+    });
+
     let on_submit = move |evt: Event<FormData>| {
         evt.prevent_default();
 
+        let username_val = username.read().clone();
+        let email_val = email.read().clone();
+        let password_val = password.read().clone();
+        let confirm_password_val = confirm_password.read().clone();
+
+        // Пустые поля отсекает атрибут `required`, остальное проверяется при вводе
+        if has_errors() || password_val != confirm_password_val {
+            return;
+        }
+
         let client = client.clone();
         spawn(async move {
             is_loading.set(true);
             error_message.set(None);
-
-            let username_val = username.read().clone();
-            let email_val = email.read().clone();
-            let password_val = password.read().clone();
-            let confirm_password_val = confirm_password.read().clone();
-
-            // Validate passwords match
-            if password_val != confirm_password_val {
-                is_loading.set(false);
-                error_message.set(Some("Passwords do not match".to_string()));
-                return;
-            }
+            server_errors.set(ServerFieldErrors::default());
 
             match client
                 .register(&username_val, &email_val, &password_val)
@@ -45,15 +163,16 @@ pub fn RegisterForm(on_success: EventHandler<()>, on_switch_to_login: EventHandl
                         }
                         Err(err) => {
                             error_message.set(Some(format!(
-                                "Registration successful but login failed: {:?}",
-                                err
+                                "Registration successful but login failed: {err}"
                             )));
                         }
                     }
                 }
                 Err(err) => {
                     is_loading.set(false);
-                    error_message.set(Some(format!("Registration failed: {:?}", err)));
+                    let (fields, general) = map_register_error(&err);
+                    server_errors.set(fields);
+                    error_message.set(general);
                 }
             }
         });
@@ -73,69 +192,114 @@ pub fn RegisterForm(on_success: EventHandler<()>, on_switch_to_login: EventHandl
 
             div {
                 label {
-                    class: "block text-sm font-medium text-gray-700 mb-1",
+                    class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
                     "Username"
                 }
                 input {
-                    class: "appearance-none block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm placeholder-gray-400 text-gray-900 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    class: input_class(&username_error()),
                     r#type: "text",
                     placeholder: "Choose a username",
                     required: true,
                     value: "{username}",
-                    oninput: move |evt| username.set(evt.value().clone()),
+                    oninput: move |evt| {
+                        server_errors.write().username = None;
+                        username.set(evt.value().clone());
+                    },
+                }
+                if let Some(err) = username_error() {
+                    p { class: "mt-1 text-sm text-red-600", "{err}" }
+                } else if let Some(Some(true)) = username_available.read().as_ref() {
+                    p { class: "mt-1 text-sm text-green-600", "Username is available" }
                 }
             }
 
             div {
                 label {
-                    class: "block text-sm font-medium text-gray-700 mb-1",
+                    class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
                     "Email"
                 }
                 input {
-                    class: "appearance-none block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm placeholder-gray-400 text-gray-900 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    class: input_class(&email_error()),
                     r#type: "email",
                     placeholder: "your@email.com",
                     required: true,
                     value: "{email}",
-                    oninput: move |evt| email.set(evt.value().clone()),
+                    oninput: move |evt| {
+                        server_errors.write().email = None;
+                        email.set(evt.value().clone());
+                    },
+                }
+                if let Some(err) = email_error() {
+                    p { class: "mt-1 text-sm text-red-600", "{err}" }
                 }
             }
 
             div {
                 label {
-                    class: "block text-sm font-medium text-gray-700 mb-1",
+                    class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
                     "Password"
                 }
                 input {
-                    class: "appearance-none block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm placeholder-gray-400 text-gray-900 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    class: input_class(&password_error()),
                     r#type: "password",
                     placeholder: "Create a password",
                     required: true,
                     value: "{password}",
-                    oninput: move |evt| password.set(evt.value().clone()),
+                    oninput: move |evt| {
+                        server_errors.write().password = None;
+                        password.set(evt.value().clone());
+                    },
+                }
+                if !password().is_empty() {
+                    div {
+                        class: "mt-2",
+                        div {
+                            class: "flex space-x-1",
+                            for segment in 0..4 {
+                                div {
+                                    key: "{segment}",
+                                    class: if segment < strength().segments() {
+                                        format!("h-1 flex-1 rounded {}", strength().color_class())
+                                    } else {
+                                        "h-1 flex-1 rounded bg-gray-200".to_string()
+                                    },
+                                }
+                            }
+                        }
+                        p {
+                            class: "mt-1 text-xs text-gray-500",
+                            "Strength: {strength().label()}"
+                        }
+                    }
+                }
+                if let Some(err) = password_error() {
+                    p { class: "mt-1 text-sm text-red-600", "{err}" }
                 }
             }
 
             div {
                 label {
-                    class: "block text-sm font-medium text-gray-700 mb-1",
+                    class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-1",
                     "Confirm Password"
                 }
                 input {
-                    class: "appearance-none block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm placeholder-gray-400 text-gray-900 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm",
+                    class: input_class(&confirm_error()),
                     r#type: "password",
                     placeholder: "Confirm your password",
                     required: true,
                     value: "{confirm_password}",
                     oninput: move |evt| confirm_password.set(evt.value().clone()),
                 }
+                if let Some(err) = confirm_error() {
+                    p { class: "mt-1 text-sm text-red-600", "{err}" }
+                }
             }
 
             div {
                 button {
                     class: "w-full flex justify-center py-2 px-4 border border-transparent rounded-md shadow-sm text-sm font-medium text-white bg-blue-600 hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed",
                     r#type: "submit",
-                    disabled: is_loading(),
+                    disabled: is_loading() || has_errors(),
                     if is_loading() {
                         "Creating account..."
                     } else {
@@ -145,7 +309,7 @@ pub fn RegisterForm(on_success: EventHandler<()>, on_switch_to_login: EventHandl
             }
 
             div {
-                class: "text-center text-sm text-gray-600",
+                class: "text-center text-sm text-gray-600 dark:text-gray-400",
                 "Already have an account? "
                 button {
                     class: "font-medium text-blue-600 hover:text-blue-500",
@@ -0,0 +1,18 @@
+use crate::theme::{self, Theme};
+use dioxus::prelude::*;
+
+/// Кнопка переключения светлой и тёмной темы
+#[component]
+pub fn ThemeToggle() -> Element {
+    let theme = use_context::<Signal<Theme>>();
+    let is_dark = theme() == Theme::Dark;
+
+    rsx! {
+        button {
+            class: "p-2 rounded-md text-gray-500 hover:text-gray-900 hover:bg-gray-100 dark:text-gray-400 dark:hover:text-gray-100 dark:hover:bg-gray-700 focus:outline-none focus:ring-2 focus:ring-blue-500",
+            title: if is_dark { "Switch to light theme" } else { "Switch to dark theme" },
+            onclick: move |_| theme::toggle(theme),
+            if is_dark { "☀" } else { "☾" }
+        }
+    }
+}
@@ -1,11 +1,19 @@
 use dioxus::prelude::*;
 
-use client::{blog_client::BlogClient, http_client::HttpClient, TokenUpdateEvent};
 use components::AuthenticatedApp;
-use tokio::sync::mpsc;
+use transport::{SharedClient, TransportKind};
 
 mod components;
-mod storage;
+mod markdown;
+mod offline;
+mod optimistic;
+mod route;
+mod search;
+mod session;
+mod theme;
+mod transport;
+mod url_state;
+mod validation;
 
 const FAVICON: Asset = asset!("/assets/favicon.ico");
 const MAIN_CSS: Asset = asset!("/assets/styling/main.css");
@@ -22,29 +30,41 @@ fn main() {
     dioxus::launch(App);
 }
 
+/// Регистрирует service worker (`public/sw.js`) для офлайн-режима
+fn register_service_worker() {
+    document::eval(
+        r#"
+        if ("serviceWorker" in navigator) {
+            navigator.serviceWorker
+                .register("/sw.js")
+                .catch((err) => console.warn("Service worker registration failed:", err));
+        }
+        "#,
+    );
+}
+
 #[component]
 fn App() -> Element {
-    let client_resource = use_resource(|| async move {
-        let backend_url = get_backend_url();
-        let (token_sender, mut token_receiver) = mpsc::unbounded_channel::<TokenUpdateEvent>();
-        let client = HttpClient::new_with_token_notifier(backend_url, token_sender).await?;
+    use_hook(register_service_worker);
+    // Навигатор нужен всем страницам, поэтому создаётся в корне
+    route::use_navigator_provider();
+    theme::use_theme_provider();
+    // Изменения, сделанные без сети, переживают перезагрузку страницы
+    use_context_provider(offline::OfflineState::load);
 
-        let client_for_storage = client.clone();
-        spawn(async move {
-            while let Some(_event) = token_receiver.recv().await {
-                if let Ok(Some(auth_data)) = client_for_storage.get_auth_data().await {
-                    if let Err(e) = storage::save_auth_data(&auth_data) {
-                        eprintln!("Failed to save auth data to localStorage: {:?}", e);
-                    }
-                }
-            }
-        });
+    let session_expired = session::use_session_provider();
 
-        Ok::<_, client::error::ClientError>(client)
+    let client_resource = use_resource(move || {
+        let session_expired = session_expired.clone();
+        async move {
+            let backend_url = get_backend_url();
+            transport::create_client(TransportKind::from_env(), backend_url, session_expired).await
+        }
     });
 
     rsx! {
         document::Link { rel: "icon", href: FAVICON }
+        document::Link { rel: "manifest", href: "/manifest.webmanifest" }
         document::Link { rel: "stylesheet", href: MAIN_CSS }
         document::Link { rel: "stylesheet", href: TAILWIND_CSS }
 
@@ -68,6 +88,7 @@ fn App() -> Element {
             },
             Some(Ok(client)) => rsx! {
                 { use_context_provider(|| client.clone()); }
+                { use_context_provider(|| client.clone() as SharedClient); }
                 AuthenticatedApp {}
             }
         }
@@ -0,0 +1,17 @@
+use pulldown_cmark::{html, Options, Parser};
+
+/// Рендерит Markdown в HTML для предпросмотра поста.
+///
+/// Настройки совпадают с серверным рендерингом (`content_html`), поэтому предпросмотр
+/// выглядит так же, как опубликованный пост. HTML проходит через санитайзер, прежде
+/// чем попасть в `dangerous_inner_html`.
+pub fn render(content: &str) -> String {
+    let options =
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    let parser = Parser::new_ext(content, options);
+
+    let mut unsafe_html = String::with_capacity(content.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, parser);
+
+    ammonia::clean(&unsafe_html)
+}
@@ -0,0 +1,55 @@
+use std::rc::Rc;
+
+use client::offline_queue::{OfflineQueue, PendingOperation, ReplaySummary, Submitted};
+use client::types::ClientResult;
+use dioxus::prelude::*;
+
+use crate::transport::SharedClient;
+
+/// Очередь изменений, сделанных без сети, и её содержимое для отображения.
+///
+/// Разделяется между компонентами через контекст: формы отправляют изменения
+/// через [`OfflineState::submit`], а баннер показывает очередь и отправляет её
+/// при восстановлении связи.
+#[derive(Clone)]
+pub struct OfflineState {
+    queue: Rc<OfflineQueue>,
+    pending: Signal<Vec<PendingOperation>>,
+}
+
+impl OfflineState {
+    /// Загружает очередь, сохранённую в localStorage.
+    pub fn load() -> Self {
+        let queue = Rc::new(OfflineQueue::default());
+        let pending = Signal::new(queue.pending_operations());
+        Self { queue, pending }
+    }
+
+    /// Операции, ожидающие отправки
+    pub fn pending(&self) -> Signal<Vec<PendingOperation>> {
+        self.pending
+    }
+
+    /// Выполняет операцию или откладывает её до восстановления связи.
+    pub async fn submit(
+        &self,
+        client: &SharedClient,
+        operation: PendingOperation,
+    ) -> ClientResult<Submitted> {
+        let result = self.queue.submit(client.as_ref(), operation).await;
+        self.refresh();
+        result
+    }
+
+    /// Отправляет отложенные операции.
+    pub async fn replay(&self, client: &SharedClient) -> ClientResult<ReplaySummary> {
+        let result = self.queue.replay(client.as_ref()).await;
+        self.refresh();
+        result
+    }
+
+    fn refresh(&self) {
+        let mut pending = self.pending;
+        pending.set(self.queue.pending_operations());
+    }
+}
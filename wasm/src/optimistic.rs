@@ -0,0 +1,146 @@
+//! Оптимистичные изменения загруженного списка постов.
+//!
+//! Изменение применяется к списку до ответа сервера; если сервер его отклонил,
+//! [`Rollback`] возвращает список к прежнему виду, не затрагивая другие изменения,
+//! сделанные за это время.
+
+use chrono::Utc;
+use client::offline_queue::PendingOperation;
+use client::types::{Post, PostStatus};
+use uuid::Uuid;
+
+/// Как отменить оптимистичное изменение.
+#[derive(Debug, Clone)]
+pub enum Rollback {
+    /// Убрать добавленный пост
+    Remove(Uuid),
+    /// Вернуть удалённый пост на прежнее место
+    Insert(usize, Post),
+    /// Вернуть прежнюю версию изменённого поста
+    Replace(Post),
+    /// Список не менялся
+    Nothing,
+}
+
+impl Rollback {
+    /// Отменяет изменение в списке
+    pub fn undo(self, posts: &mut Vec<Post>) {
+        match self {
+            Rollback::Remove(id) => posts.retain(|post| post.id != id),
+            Rollback::Insert(index, post) => {
+                if !posts.iter().any(|current| current.id == post.id) {
+                    posts.insert(index.min(posts.len()), post);
+                }
+            }
+            Rollback::Replace(post) => {
+                if let Some(current) = posts.iter_mut().find(|current| current.id == post.id) {
+                    *current = post;
+                }
+            }
+            Rollback::Nothing => {}
+        }
+    }
+}
+
+/// Применяет операцию к списку и возвращает способ её отменить.
+///
+/// Созданный пост добавляется в начало списка с временным ID, только если
+/// `insert_created` (пост попадёт на текущую страницу); настоящие ID и метки
+/// времени приходят при следующей загрузке списка.
+pub fn apply(
+    posts: &mut Vec<Post>,
+    operation: &PendingOperation,
+    insert_created: bool,
+) -> Rollback {
+    match operation {
+        PendingOperation::CreatePost {
+            title,
+            content,
+            tags,
+            category_id,
+            summary,
+            draft,
+        } => {
+            if !insert_created {
+                return Rollback::Nothing;
+            }
+            let now = Utc::now();
+            let id = Uuid::new_v4();
+            posts.insert(
+                0,
+                Post {
+                    id,
+                    title: title.clone(),
+                    content: content.clone(),
+                    content_html: None,
+                    // Описание из начала содержимого составит сервер; до ответа карточка
+                    // показывает содержимое, обрезанное стилями
+                    summary: summary.clone().unwrap_or_else(|| content.clone()),
+                    author_id: Uuid::nil(),
+                    author_username: String::new(),
+                    author_avatar_url: None,
+                    tags: tags.clone(),
+                    category_id: category_id
+                        .as_deref()
+                        .and_then(|id| Uuid::parse_str(id).ok()),
+                    blog_id: None,
+                    attachments: Vec::new(),
+                    status: if *draft {
+                        PostStatus::Draft
+                    } else {
+                        PostStatus::Published
+                    },
+                    like_count: 0,
+                    liked_by_me: false,
+                    created_at: now,
+                    updated_at: now,
+                },
+            );
+            Rollback::Remove(id)
+        }
+        PendingOperation::UpdatePost {
+            post_id,
+            title,
+            content,
+            tags,
+            category_id,
+            summary,
+        } => {
+            let Some(post) = posts
+                .iter_mut()
+                .find(|post| post.id.to_string() == *post_id)
+            else {
+                return Rollback::Nothing;
+            };
+            let previous = post.clone();
+            post.title = title.clone();
+            post.content = content.clone();
+            // Отрендеренный сервером HTML относится к прежнему содержимому
+            post.content_html = None;
+            post.tags = tags.clone();
+            // Пустая строка убирает категорию
+            if let Some(category_id) = category_id {
+                post.category_id = Uuid::parse_str(category_id).ok();
+            }
+            // Пустая строка возвращает описание из начала содержимого
+            if let Some(summary) = summary {
+                post.summary = if summary.is_empty() {
+                    content.clone()
+                } else {
+                    summary.clone()
+                };
+            }
+            post.updated_at = Utc::now();
+            Rollback::Replace(previous)
+        }
+        PendingOperation::DeletePost { post_id } => {
+            match posts
+                .iter()
+                .position(|post| post.id.to_string() == *post_id)
+            {
+                Some(index) => Rollback::Insert(index, posts.remove(index)),
+                None => Rollback::Nothing,
+            }
+        }
+    }
+}
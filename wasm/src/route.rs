@@ -0,0 +1,149 @@
+//! Адреса страниц приложения и переходы между ними без перезагрузки страницы.
+//!
+//! Текущая страница хранится в [`Navigator`]: переход записывает адрес в историю
+//! браузера, а кнопки «назад»/«вперёд» (`popstate`) возвращают страницу из истории.
+//! Поэтому на любую страницу можно дать ссылку, а обновление её не сбрасывает.
+
+use dioxus::document::eval;
+use dioxus::prelude::*;
+
+/// Страница приложения.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Route {
+    /// Список постов: `/`
+    Home,
+    /// Пост: `/posts/:id`
+    Post { id: String },
+    /// Вход: `/login`
+    Login,
+    /// Регистрация: `/register`
+    Register,
+    /// Посты текущего пользователя: `/me`
+    Me,
+    /// Профиль текущего пользователя: `/profile`
+    Profile,
+    /// Лента постов авторов из подписок: `/following`
+    Following,
+    /// Раздел администратора: `/admin`
+    Admin,
+    /// Неизвестный адрес
+    NotFound { path: String },
+}
+
+impl Route {
+    /// Разбирает путь адреса (`location.pathname`)
+    pub fn parse(path: &str) -> Self {
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        match segments.as_slice() {
+            [] => Route::Home,
+            ["posts", id] => Route::Post { id: id.to_string() },
+            ["login"] => Route::Login,
+            ["register"] => Route::Register,
+            ["me"] => Route::Me,
+            ["profile"] => Route::Profile,
+            ["following"] => Route::Following,
+            ["admin"] => Route::Admin,
+            _ => Route::NotFound {
+                path: path.to_string(),
+            },
+        }
+    }
+
+    /// Путь страницы
+    pub fn path(&self) -> String {
+        match self {
+            Route::Home => "/".to_string(),
+            Route::Post { id } => format!("/posts/{}", id),
+            Route::Login => "/login".to_string(),
+            Route::Register => "/register".to_string(),
+            Route::Me => "/me".to_string(),
+            Route::Profile => "/profile".to_string(),
+            Route::Following => "/following".to_string(),
+            Route::Admin => "/admin".to_string(),
+            Route::NotFound { path } => path.clone(),
+        }
+    }
+}
+
+/// Текущая страница и переходы; доступен компонентам через [`use_navigator`].
+#[derive(Clone, Copy)]
+pub struct Navigator {
+    route: Signal<Option<Route>>,
+}
+
+impl Navigator {
+    /// Текущая страница; `None`, пока адрес ещё не прочитан
+    pub fn route(&self) -> Option<Route> {
+        self.route.read().clone()
+    }
+
+    /// Переходит на страницу, добавляя её адрес в историю браузера
+    pub fn push(&mut self, route: Route) {
+        let js = eval(
+            r#"
+            const path = await dioxus.recv();
+            if (path !== location.pathname) {
+                history.pushState(null, "", path);
+                window.scrollTo(0, 0);
+            }
+            "#,
+        );
+        let _ = js.send(route.path());
+        self.route.set(Some(route));
+    }
+}
+
+/// Создаёт [`Navigator`] для всего дерева компонентов.
+///
+/// Вызывается один раз в корневом компоненте: читает адрес при загрузке и
+/// следит за навигацией назад/вперёд, пока компонент существует.
+pub fn use_navigator_provider() -> Navigator {
+    let mut route = use_signal(|| None::<Route>);
+    use_future(move || async move {
+        let mut watcher = eval(
+            r#"
+            dioxus.send(location.pathname);
+            window.addEventListener("popstate", () => dioxus.send(location.pathname));
+            await new Promise(() => {});
+            "#,
+        );
+        while let Ok(path) = watcher.recv::<String>().await {
+            route.set(Some(Route::parse(&path)));
+        }
+    });
+    use_context_provider(|| Navigator { route })
+}
+
+/// [`Navigator`] из контекста
+pub fn use_navigator() -> Navigator {
+    use_context::<Navigator>()
+}
+
+/// Ссылка на страницу приложения.
+///
+/// Обычный клик переходит без перезагрузки; клик с Ctrl, Cmd или Shift
+/// открывает адрес в новой вкладке или окне, как у обычной ссылки.
+#[component]
+pub fn Link(to: Route, #[props(default)] class: String, children: Element) -> Element {
+    let mut navigator = use_navigator();
+    let href = to.path();
+
+    rsx! {
+        a {
+            class,
+            href,
+            onclick: move |evt: Event<MouseData>| {
+                let modifiers = evt.modifiers();
+                if modifiers.contains(Modifiers::CONTROL)
+                    || modifiers.contains(Modifiers::META)
+                    || modifiers.contains(Modifiers::SHIFT)
+                {
+                    return;
+                }
+                evt.prevent_default();
+                navigator.push(to.clone());
+            },
+            {children}
+        }
+    }
+}
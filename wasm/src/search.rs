@@ -0,0 +1,57 @@
+//! Поиск постов по заголовку на клиенте.
+//!
+//! Сервер пока не умеет искать посты, поэтому запрос применяется к уже
+//! загруженной странице списка.
+
+/// Символ без учёта регистра; символы, которые в нижнем регистре
+/// превращаются в несколько символов, сравниваются как есть
+fn fold(c: char) -> char {
+    let mut lower = c.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(lower), None) => lower,
+        _ => c,
+    }
+}
+
+/// Делит текст на части, отмечая `true` вхождения `query` без учёта регистра.
+pub fn highlight(text: &str, query: &str) -> Vec<(String, bool)> {
+    let query: Vec<char> = query.chars().map(fold).collect();
+    if query.is_empty() {
+        return vec![(text.to_string(), false)];
+    }
+
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut segments = Vec::new();
+    // Начало ещё не добавленной части без совпадения (в байтах)
+    let mut plain_start = 0;
+    let mut i = 0;
+    while i + query.len() <= chars.len() {
+        let found = chars[i..i + query.len()]
+            .iter()
+            .zip(&query)
+            .all(|((_, c), q)| fold(*c) == *q);
+        if !found {
+            i += 1;
+            continue;
+        }
+        let from = chars[i].0;
+        let to = chars
+            .get(i + query.len())
+            .map_or(text.len(), |(index, _)| *index);
+        if plain_start < from {
+            segments.push((text[plain_start..from].to_string(), false));
+        }
+        segments.push((text[from..to].to_string(), true));
+        plain_start = to;
+        i += query.len();
+    }
+    if plain_start < text.len() {
+        segments.push((text[plain_start..].to_string(), false));
+    }
+    segments
+}
+
+/// Содержит ли текст `query` без учёта регистра
+pub fn matches(text: &str, query: &str) -> bool {
+    highlight(text, query).iter().any(|(_, found)| *found)
+}
@@ -0,0 +1,52 @@
+//! Истечение сессии входа.
+//!
+//! Когда сервер отклоняет refresh токен, клиент удаляет сохранённые токены и
+//! присылает уведомление. [`Session`] превращает его в сигнал, по которому
+//! приложение выходит из аккаунта и предлагает войти заново.
+
+use client::mpsc;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+/// Состояние сессии; доступно компонентам через [`use_session`].
+#[derive(Clone, Copy)]
+pub struct Session {
+    expired: Signal<bool>,
+}
+
+impl Session {
+    /// Сессия истекла, а пользователь ещё не вошёл заново
+    pub fn is_expired(&self) -> bool {
+        (self.expired)()
+    }
+
+    /// Снимает отметку об истечении: пользователь вошёл заново или остался гостем
+    pub fn dismiss(&mut self) {
+        self.expired.set(false);
+    }
+
+    /// Отмечает сессию истёкшей при каждом уведомлении клиента
+    async fn watch(mut self, mut events: mpsc::UnboundedReceiver<()>) {
+        while events.next().await.is_some() {
+            self.expired.set(true);
+        }
+    }
+}
+
+/// Создаёт [`Session`] для всего дерева компонентов.
+///
+/// Возвращает отправителя уведомлений, которого нужно передать клиенту при создании.
+pub fn use_session_provider() -> mpsc::UnboundedSender<()> {
+    let expired = use_signal(|| false);
+    let session = use_context_provider(|| Session { expired });
+    use_hook(|| {
+        let (sender, receiver) = mpsc::unbounded();
+        spawn(session.watch(receiver));
+        sender
+    })
+}
+
+/// [`Session`] из контекста
+pub fn use_session() -> Session {
+    use_context::<Session>()
+}
@@ -0,0 +1,73 @@
+//! Светлая и тёмная тема интерфейса.
+//!
+//! Выбор пользователя хранится в localStorage; пока его нет, тема следует
+//! системной настройке `prefers-color-scheme`.
+
+use dioxus::document::eval;
+use dioxus::prelude::*;
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+/// Ключ localStorage с выбранной темой
+const THEME_KEY: &str = "theme";
+
+/// Тема интерфейса.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+impl Theme {
+    /// Противоположная тема
+    pub fn toggled(self) -> Self {
+        match self {
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::Light,
+        }
+    }
+}
+
+/// Включает `dark:` классы Tailwind классом `dark` на `<html>`
+fn apply(theme: Theme) {
+    let js = eval(
+        r#"
+        const dark = await dioxus.recv();
+        document.documentElement.classList.toggle("dark", dark);
+        document.documentElement.style.colorScheme = dark ? "dark" : "light";
+        "#,
+    );
+    let _ = js.send(theme == Theme::Dark);
+}
+
+/// Создаёт сигнал темы для всего приложения и применяет тему к странице.
+///
+/// Вызывается один раз в корневом компоненте; потомки получают сигнал через
+/// `use_context::<Signal<Theme>>()`.
+pub fn use_theme_provider() -> Signal<Theme> {
+    let stored = LocalStorage::get::<Theme>(THEME_KEY).ok();
+    let mut theme = use_context_provider(|| Signal::new(stored.unwrap_or(Theme::Light)));
+
+    // Без сохранённого выбора тема берётся из системной настройки
+    use_future(move || async move {
+        if stored.is_some() {
+            return;
+        }
+        let mut query =
+            eval(r#"dioxus.send(window.matchMedia("(prefers-color-scheme: dark)").matches);"#);
+        if let Ok(true) = query.recv::<bool>().await {
+            theme.set(Theme::Dark);
+        }
+    });
+
+    use_effect(move || apply(theme()));
+    theme
+}
+
+/// Переключает тему и сохраняет выбор в localStorage
+pub fn toggle(mut theme: Signal<Theme>) {
+    let next = theme.peek().toggled();
+    theme.set(next);
+    let _ = LocalStorage::set(THEME_KEY, next);
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use client::{
+    admin::AdminClient,
+    blog_client::{BlogClient, BlogClientBuilder, Transport},
+    error::ClientError,
+    http_client::HttpClient,
+    mpsc,
+    token_store::LocalStorageTokenStore,
+    types::{ClientResult, PostEvent},
+};
+use futures_util::Stream;
+
+/// Клиент блога, разделяемый между компонентами через контекст.
+///
+/// Компоненты работают только с трейтом [`BlogClient`], поэтому конкретный
+/// транспорт выбирается один раз при старте приложения. Тип тот же, что у
+/// [`BlogClient::into_dyn`], так что общий код с нативной сборкой принимает его как есть.
+pub type SharedClient = Arc<dyn BlogClient>;
+
+/// Тот же клиент с административными вызовами для раздела администратора.
+///
+/// Токены у него общие с [`SharedClient`]; вызовы выполняются, только если
+/// вошедший пользователь - администратор.
+pub type SharedAdminClient = Arc<dyn AdminClient>;
+
+/// Транспорт, которым приложение ходит в backend.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransportKind {
+    /// REST API через HTTP
+    Http,
+    /// gRPC через прокси gRPC-Web
+    GrpcWeb,
+}
+
+impl TransportKind {
+    /// Определяет транспорт по переменной `BACKEND_TRANSPORT`, заданной при сборке.
+    ///
+    /// Допустимые значения: `http` (по умолчанию) и `grpc-web`.
+    pub fn from_env() -> Self {
+        match option_env!("BACKEND_TRANSPORT") {
+            Some("grpc-web") | Some("grpc") => TransportKind::GrpcWeb,
+            _ => TransportKind::Http,
+        }
+    }
+}
+
+/// Создаёт клиента для выбранного транспорта.
+///
+/// Токены хранятся в localStorage, поэтому вход переживает перезагрузку страницы.
+/// Когда сервер отклоняет refresh токен, клиент отправляет уведомление в `session_expired`.
+pub async fn create_client(
+    kind: TransportKind,
+    backend_url: String,
+    session_expired: mpsc::UnboundedSender<()>,
+) -> Result<SharedAdminClient, ClientError> {
+    let transport = match kind {
+        TransportKind::Http => Transport::Http(backend_url),
+        TransportKind::GrpcWeb => Transport::Grpc(backend_url),
+    };
+    let client = BlogClientBuilder::new(transport)
+        .token_store(LocalStorageTokenStore::default())
+        .session_expired_notifier(session_expired)
+        .build_admin()
+        .await?;
+    Ok(Arc::from(client))
+}
+
+/// Подписывается на живую ленту опубликованных постов.
+///
+/// Лента передаётся через Server-Sent Events и доступна только для HTTP-транспорта.
+pub async fn subscribe_post_events(
+    kind: TransportKind,
+    backend_url: String,
+) -> Result<impl Stream<Item = ClientResult<PostEvent>>, ClientError> {
+    match kind {
+        TransportKind::Http => {
+            HttpClient::new(backend_url)
+                .await?
+                .subscribe_post_events()
+                .await
+        }
+        TransportKind::GrpcWeb => Err(ClientError::TransportError(
+            "live post events are not supported over gRPC-Web".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,85 @@
+use dioxus::document::eval;
+use serde::{Deserialize, Serialize};
+
+/// Состояние списка постов, отражаемое в query string адреса.
+///
+/// Значения по умолчанию в адрес не попадают, поэтому первая страница
+/// соответствует чистому `/`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ListQuery {
+    /// Номер страницы (начиная с 0)
+    pub page: u32,
+    /// Фильтр по тегу
+    pub tag: Option<String>,
+    /// Поиск по заголовкам
+    pub q: Option<String>,
+}
+
+/// JS-функция, разбирающая `location.search` в [`ListQuery`].
+const READ_QUERY_JS: &str = r#"
+    const readQuery = () => {
+        const params = new URLSearchParams(location.search);
+        const page = parseInt(params.get("page") ?? "0", 10);
+        const tag = params.get("tag");
+        const q = params.get("q");
+        return {
+            page: Number.isNaN(page) || page < 0 ? 0 : page,
+            tag: tag ? tag : null,
+            q: q ? q : null,
+        };
+    };
+"#;
+
+/// Записывает состояние списка в адрес и добавляет запись в историю браузера.
+pub fn push_list_query(query: &ListQuery) {
+    write_list_query(query, "pushState");
+}
+
+/// Записывает состояние списка в адрес, заменяя текущую запись истории.
+///
+/// Для значений, которые меняются при вводе (поиск): история не засоряется
+/// промежуточными состояниями.
+pub fn replace_list_query(query: &ListQuery) {
+    write_list_query(query, "replaceState");
+}
+
+/// `method` - метод `history`: `pushState` или `replaceState`
+fn write_list_query(query: &ListQuery, method: &str) {
+    let js = eval(&format!(
+        r#"
+        const query = await dioxus.recv();
+        const params = new URLSearchParams();
+        for (const [key, value] of Object.entries(query)) {{
+            if (value !== null && value !== "" && value !== 0) {{
+                params.set(key, value);
+            }}
+        }}
+        const search = params.toString();
+        const url = search ? `${{location.pathname}}?${{search}}` : location.pathname;
+        if (url !== location.pathname + location.search) {{
+            history.{method}(null, "", url);
+        }}
+        "#
+    ));
+    let _ = js.send(query);
+}
+
+/// Подписывается на состояние списка в адресе.
+///
+/// `on_change` вызывается сразу с текущим состоянием и затем при каждой
+/// навигации назад/вперёд (`popstate`). Future завершается только вместе
+/// с компонентом.
+pub async fn watch_list_query(mut on_change: impl FnMut(ListQuery)) {
+    let mut watcher = eval(&format!(
+        r#"
+        {READ_QUERY_JS}
+        dioxus.send(readQuery());
+        window.addEventListener("popstate", () => dioxus.send(readQuery()));
+        await new Promise(() => {{}});
+        "#
+    ));
+
+    while let Ok(query) = watcher.recv::<ListQuery>().await {
+        on_change(query);
+    }
+}
@@ -0,0 +1,258 @@
+//! Клиентская валидация полей форм.
+//!
+//! Проверки дублируют правила сервера, чтобы пользователь видел ошибки
+//! ещё до отправки формы. Ошибки полей, которые сервер всё же вернул (ответ 422),
+//! раскладываются по полям формы через [`server_field_errors`].
+
+use std::collections::HashMap;
+
+use client::error::ClientError;
+
+/// Минимальная длина имени пользователя
+pub const USERNAME_MIN_LEN: usize = 3;
+/// Максимальная длина имени пользователя
+pub const USERNAME_MAX_LEN: usize = 32;
+/// Минимальная длина пароля
+pub const PASSWORD_MIN_LEN: usize = 8;
+/// Максимальная длина отображаемого имени в профиле
+pub const DISPLAY_NAME_MAX_LEN: usize = 64;
+/// Максимальная длина описания профиля
+pub const BIO_MAX_LEN: usize = 1000;
+/// Максимальная длина ссылки на аватар
+pub const AVATAR_URL_MAX_LEN: usize = 512;
+/// Максимальная длина заголовка поста
+pub const POST_TITLE_MAX_LEN: usize = 500;
+/// Максимальная длина содержимого поста
+pub const POST_CONTENT_MAX_LEN: usize = 100_000;
+/// Максимальная длина описания поста
+pub const POST_SUMMARY_MAX_LEN: usize = 500;
+/// Максимальное количество тегов у поста
+pub const MAX_TAGS_PER_POST: usize = 10;
+/// Максимальная длина тега
+pub const TAG_MAX_LEN: usize = 32;
+/// Длина кода двухфакторной аутентификации
+pub const TOTP_CODE_LEN: usize = 6;
+
+/// Ошибки полей формы: имя поля запроса → сообщение для показа под полем.
+pub type FieldErrors = HashMap<String, String>;
+
+/// Ошибки полей из ответа сервера 422; для остальных ошибок - `None`.
+///
+/// Для каждого поля берётся первое сообщение сервера.
+pub fn server_field_errors(err: &ClientError) -> Option<FieldErrors> {
+    match err {
+        ClientError::Validation { details, .. } => Some(
+            details
+                .iter()
+                .filter_map(|(field, messages)| {
+                    messages
+                        .first()
+                        .map(|message| (field.clone(), message.clone()))
+                })
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+/// Проверяет имя пользователя: длина и допустимые символы (`a-z`, `0-9`, `_`, `-`, `.`).
+pub fn validate_username(username: &str) -> Option<String> {
+    let len = username.chars().count();
+    if len < USERNAME_MIN_LEN {
+        return Some(format!(
+            "Username must be at least {USERNAME_MIN_LEN} characters"
+        ));
+    }
+    if len > USERNAME_MAX_LEN {
+        return Some(format!(
+            "Username must be at most {USERNAME_MAX_LEN} characters"
+        ));
+    }
+    if !username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        return Some("Only latin letters, digits, '_', '-' and '.' are allowed".to_string());
+    }
+    None
+}
+
+/// Проверяет формат email: `local@domain.tld` без пробелов.
+pub fn validate_email(email: &str) -> Option<String> {
+    let valid = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+
+    if valid {
+        None
+    } else {
+        Some("Enter a valid email address".to_string())
+    }
+}
+
+/// Проверяет пароль на соответствие политике (минимальная длина).
+pub fn validate_password(password: &str) -> Option<String> {
+    if password.chars().count() < PASSWORD_MIN_LEN {
+        return Some(format!(
+            "Password must be at least {PASSWORD_MIN_LEN} characters"
+        ));
+    }
+    None
+}
+
+/// Проверяет, что обязательное поле заполнено не одними пробелами.
+pub fn validate_required(value: &str, field: &str) -> Option<String> {
+    if value.trim().is_empty() {
+        Some(format!("{field} must not be empty"))
+    } else {
+        None
+    }
+}
+
+/// Проверяет код двухфакторной аутентификации: ровно [`TOTP_CODE_LEN`] цифр.
+pub fn validate_totp_code(code: &str) -> Option<String> {
+    let code = code.trim();
+    if code.len() == TOTP_CODE_LEN && code.chars().all(|c| c.is_ascii_digit()) {
+        None
+    } else {
+        Some(format!("Code must be {TOTP_CODE_LEN} digits"))
+    }
+}
+
+/// Проверяет заголовок поста: непустой и не длиннее [`POST_TITLE_MAX_LEN`].
+pub fn validate_post_title(title: &str) -> Option<String> {
+    if let Some(err) = validate_required(title, "Title") {
+        return Some(err);
+    }
+    let len = title.chars().count();
+    if len > POST_TITLE_MAX_LEN {
+        return Some(format!(
+            "Title must be at most {POST_TITLE_MAX_LEN} characters ({len} now)"
+        ));
+    }
+    None
+}
+
+/// Проверяет содержимое поста: непустое и не длиннее [`POST_CONTENT_MAX_LEN`].
+pub fn validate_post_content(content: &str) -> Option<String> {
+    if let Some(err) = validate_required(content, "Content") {
+        return Some(err);
+    }
+    let len = content.chars().count();
+    if len > POST_CONTENT_MAX_LEN {
+        return Some(format!(
+            "Content must be at most {POST_CONTENT_MAX_LEN} characters ({len} now)"
+        ));
+    }
+    None
+}
+
+/// Проверяет описание поста: необязательное и не длиннее [`POST_SUMMARY_MAX_LEN`].
+pub fn validate_post_summary(summary: &str) -> Option<String> {
+    let len = summary.trim().chars().count();
+    if len > POST_SUMMARY_MAX_LEN {
+        return Some(format!(
+            "Summary must be at most {POST_SUMMARY_MAX_LEN} characters ({len} now)"
+        ));
+    }
+    None
+}
+
+/// Проверяет теги поста: не больше [`MAX_TAGS_PER_POST`], каждый не длиннее [`TAG_MAX_LEN`].
+pub fn validate_tags(tags: &[String]) -> Option<String> {
+    if tags.len() > MAX_TAGS_PER_POST {
+        return Some(format!("At most {MAX_TAGS_PER_POST} tags are allowed"));
+    }
+    if let Some(tag) = tags.iter().find(|tag| tag.chars().count() > TAG_MAX_LEN) {
+        return Some(format!(
+            "Tag \"{tag}\" is longer than {TAG_MAX_LEN} characters"
+        ));
+    }
+    None
+}
+
+/// Проверяет ссылку на аватар: пустая или `http(s)://` не длиннее [`AVATAR_URL_MAX_LEN`].
+pub fn validate_avatar_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    if url.is_empty() {
+        return None;
+    }
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Some("Avatar URL must start with http:// or https://".to_string());
+    }
+    if url.chars().count() > AVATAR_URL_MAX_LEN {
+        return Some(format!(
+            "Avatar URL must be at most {AVATAR_URL_MAX_LEN} characters"
+        ));
+    }
+    None
+}
+
+/// Оценка надёжности пароля для индикатора в форме регистрации.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PasswordStrength {
+    Weak,
+    Fair,
+    Good,
+    Strong,
+}
+
+impl PasswordStrength {
+    /// Подпись для индикатора
+    pub fn label(self) -> &'static str {
+        match self {
+            PasswordStrength::Weak => "Weak",
+            PasswordStrength::Fair => "Fair",
+            PasswordStrength::Good => "Good",
+            PasswordStrength::Strong => "Strong",
+        }
+    }
+
+    /// Количество закрашенных сегментов индикатора (из 4)
+    pub fn segments(self) -> usize {
+        self as usize + 1
+    }
+
+    /// Tailwind-класс цвета индикатора
+    pub fn color_class(self) -> &'static str {
+        match self {
+            PasswordStrength::Weak => "bg-red-500",
+            PasswordStrength::Fair => "bg-yellow-500",
+            PasswordStrength::Good => "bg-blue-500",
+            PasswordStrength::Strong => "bg-green-500",
+        }
+    }
+}
+
+/// Оценивает надёжность пароля по длине и разнообразию символов.
+pub fn password_strength(password: &str) -> PasswordStrength {
+    let len = password.chars().count();
+    if len < PASSWORD_MIN_LEN {
+        return PasswordStrength::Weak;
+    }
+
+    let classes = [
+        password.chars().any(|c| c.is_lowercase()),
+        password.chars().any(|c| c.is_uppercase()),
+        password.chars().any(|c| c.is_ascii_digit()),
+        password.chars().any(|c| !c.is_alphanumeric()),
+    ]
+    .into_iter()
+    .filter(|present| *present)
+    .count();
+
+    let score = classes + usize::from(len >= 12) + usize::from(len >= 16);
+    match score {
+        0..=2 => PasswordStrength::Fair,
+        3..=4 => PasswordStrength::Good,
+        _ => PasswordStrength::Strong,
+    }
+}